@@ -35,6 +35,8 @@ impl<W: Write> Print<W> {
             Payload::Insert(n) => affected(*n, "inserted")?,
             Payload::Delete(n) => affected(*n, "deleted")?,
             Payload::Update(n) => affected(*n, "updated")?,
+            Payload::Merge(n) => affected(*n, "merged")?,
+            Payload::RefreshMaterializedView(n) => affected(*n, "refreshed")?,
             Payload::ShowVariable(PayloadVariable::Version(v)) => {
                 writeln!(self.output, "v{}\n", v)?
             }
@@ -171,6 +173,7 @@ mod tests {
         test!("7 rows inserted", &Payload::Insert(7));
         test!("300 rows deleted", &Payload::Delete(300));
         test!("123 rows updated", &Payload::Update(123));
+        test!("2 rows refreshed", &Payload::RefreshMaterializedView(2));
         test!(
             "v11.6.1989",
             &Payload::ShowVariable(PayloadVariable::Version("11.6.1989".to_owned()))
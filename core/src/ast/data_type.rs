@@ -13,12 +13,18 @@ pub enum DataType {
     Float,
     Text,
     Bytea,
+    /// `BIT(length)`, a bit string of exactly `length` bits, `BIT` alone is `BIT(1)`
+    Bit(usize),
     Date,
     Timestamp,
+    #[strum(serialize = "TIMESTAMPTZ")]
+    TimestampTz,
     Time,
     Interval,
     Uuid,
     Map,
     List,
     Decimal,
+    /// `ENUM('a', 'b', ...)`, text which is one of the listed values
+    Enum(Vec<String>),
 }
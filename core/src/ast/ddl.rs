@@ -11,6 +11,7 @@ pub enum AlterTableOperation {
     DropColumn {
         column_name: String,
         if_exists: bool,
+        cascade: bool,
     },
     /// `RENAME [ COLUMN ] <old_column_name> TO <new_column_name>`
     RenameColumn {
@@ -19,6 +20,11 @@ pub enum AlterTableOperation {
     },
     /// `RENAME TO <table_name>`
     RenameTable { table_name: ObjectName },
+    /// `ALTER [ COLUMN ] <column_name> [ SET DATA ] TYPE <data_type>`
+    AlterColumnType {
+        column_name: String,
+        data_type: DataType,
+    },
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -28,6 +34,13 @@ pub struct ColumnDef {
     pub options: Vec<ColumnOptionDef>,
 }
 
+/// Parameter of `CREATE FUNCTION`
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct FunctionArg {
+    pub name: String,
+    pub data_type: DataType,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct ColumnOptionDef {
     pub name: Option<String>,
@@ -44,4 +57,18 @@ pub enum ColumnOption {
     Default(Expr),
     /// `{ PRIMARY KEY | UNIQUE }`
     Unique { is_primary: bool },
+    /// `COLLATE <collation>`
+    Collate(Collation),
+    /// `AUTOINCREMENT`, `AUTO_INCREMENT`
+    AutoIncrement,
+    /// `GENERATED ALWAYS AS (<expr>) [STORED]`
+    Generated(Expr),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Collation {
+    /// `BINARY`, text is compared as it is
+    Binary,
+    /// `NOCASE`, text is compared and ordered ignoring case
+    NoCase,
 }
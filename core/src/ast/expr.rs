@@ -1,9 +1,10 @@
 use {
     super::{
         Aggregate, AstLiteral, BinaryOperator, DataType, DateTimeField, Function, Query, ToSql,
-        UnaryOperator,
+        UnaryOperator, Window, WindowFunction,
     },
     serde::{Deserialize, Serialize},
+    sqlparser::keywords::ALL_KEYWORDS,
 };
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -33,6 +34,18 @@ pub enum Expr {
         op: BinaryOperator,
         right: Box<Expr>,
     },
+    /// `left op ANY (right)`, which holds if `op` holds for any element of a list or subquery
+    AnyOp {
+        left: Box<Expr>,
+        op: BinaryOperator,
+        right: Box<Expr>,
+    },
+    /// `left op ALL (right)`, which holds if `op` holds for every element of a list or subquery
+    AllOp {
+        left: Box<Expr>,
+        op: BinaryOperator,
+        right: Box<Expr>,
+    },
     UnaryOp {
         op: UnaryOperator,
         expr: Box<Expr>,
@@ -46,13 +59,18 @@ pub enum Expr {
         expr: Box<Expr>,
     },
     Nested(Box<Expr>),
+    /// Row value `(a, b, ...)`, which can only be compared with another row value
+    Tuple(Vec<Expr>),
     Literal(AstLiteral),
+    /// Parameter `$n` of a statement, numbered from 1, see `Glue::check_parameters`
+    Placeholder(usize),
     TypedString {
         data_type: DataType,
         value: String,
     },
     Function(Box<Function>),
     Aggregate(Box<Aggregate>),
+    Window(Box<Window>),
     Exists(Box<Query>),
     Subquery(Box<Query>),
     Case {
@@ -65,11 +83,21 @@ pub enum Expr {
 impl ToSql for Expr {
     fn to_sql(&self) -> String {
         match self {
-            Expr::Identifier(s) => s.to_string(),
+            Expr::Identifier(s) => quote_ident(s),
             Expr::BinaryOp { left, op, right } => {
                 format!("{} {} {}", left.to_sql(), op.to_sql(), right.to_sql())
             }
-            Expr::CompoundIdentifier(idents) => idents.join("."),
+            Expr::AnyOp { left, op, right } => {
+                format!("{} {} ANY({})", left.to_sql(), op.to_sql(), right.to_sql())
+            }
+            Expr::AllOp { left, op, right } => {
+                format!("{} {} ALL({})", left.to_sql(), op.to_sql(), right.to_sql())
+            }
+            Expr::CompoundIdentifier(idents) => idents
+                .iter()
+                .map(|ident| quote_ident(ident))
+                .collect::<Vec<_>>()
+                .join("."),
             Expr::IsNull(s) => format!("{} IS NULL", s.to_sql()),
             Expr::IsNotNull(s) => format!("{} IS NOT NULL", s.to_sql()),
             Expr::InList {
@@ -105,7 +133,13 @@ impl ToSql for Expr {
                 }
             }
             Expr::UnaryOp { op, expr } => match op {
-                UnaryOperator::Factorial => format!("{}{}", expr.to_sql(), op.to_sql()),
+                UnaryOperator::Factorial
+                | UnaryOperator::IsTrue
+                | UnaryOperator::IsNotTrue
+                | UnaryOperator::IsFalse
+                | UnaryOperator::IsNotFalse
+                | UnaryOperator::IsUnknown
+                | UnaryOperator::IsNotUnknown => format!("{}{}", expr.to_sql(), op.to_sql()),
                 _ => format!("{}{}", op.to_sql(), expr.to_sql()),
             },
             Expr::Cast { expr, data_type } => {
@@ -115,7 +149,17 @@ impl ToSql for Expr {
                 format!(r#"EXTRACT({field} FROM "{}")"#, expr.to_sql())
             }
             Expr::Nested(expr) => format!("({})", expr.to_sql()),
+            Expr::Tuple(exprs) => {
+                let exprs = exprs
+                    .iter()
+                    .map(ToSql::to_sql)
+                    .collect::<Vec<_>>()
+                    .join(", ");
+
+                format!("({exprs})")
+            }
             Expr::Literal(s) => s.to_sql(),
+            Expr::Placeholder(index) => format!("${index}"),
             Expr::TypedString { data_type, value } => format!("{data_type}(\"{value}\")"),
             Expr::Case {
                 operand,
@@ -141,7 +185,14 @@ impl ToSql for Expr {
                 [operand, when_then, else_result, "END".to_owned()].join("\n")
             }
             Expr::Aggregate(a) => a.to_sql(),
-            Expr::Function(func) => format!("{func}(..)"),
+            Expr::Function(func) => match func.as_ref() {
+                Function::Custom { name, .. } => format!("{name}(..)"),
+                func => format!("{func}(..)"),
+            },
+            Expr::Window(window) => match &window.function {
+                WindowFunction::Aggregate(aggregate) => format!("{} OVER (..)", aggregate.to_sql()),
+                function => format!("{function}(..) OVER (..)"),
+            },
             Expr::InSubquery { expr, negated, .. } => match negated {
                 true => format!("{} NOT IN (..query..)", expr.to_sql()),
                 false => format!("{} IN (..query..)", expr.to_sql()),
@@ -152,12 +203,31 @@ impl ToSql for Expr {
     }
 }
 
+/// Quotes `ident` in backticks unless it can be written unquoted, which a keyword can not.
+fn quote_ident(ident: &str) -> String {
+    let mut chars = ident.chars();
+    let plain = chars
+        .next()
+        .map(|c| c.is_alphabetic() || c == '_')
+        .unwrap_or(false)
+        && chars.all(|c| c.is_alphanumeric() || c == '_')
+        && ALL_KEYWORDS
+            .binary_search(&ident.to_uppercase().as_str())
+            .is_err();
+
+    if plain {
+        ident.to_owned()
+    } else {
+        format!("`{ident}`")
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use {
         crate::ast::{
             Aggregate, AstLiteral, BinaryOperator, CountArgExpr, DataType, DateTimeField, Expr,
-            Function, ToSql, UnaryOperator,
+            Function, OrderByExpr, ToSql, UnaryOperator, Window, WindowFunction, WindowSpec,
         },
         bigdecimal::BigDecimal,
         regex::Regex,
@@ -170,6 +240,15 @@ mod tests {
         let trim = |s: &str| re.replace_all(s.trim(), "\n").into_owned();
 
         assert_eq!("id", Expr::Identifier("id".to_string()).to_sql());
+        assert_eq!(
+            "`Full Name`",
+            Expr::Identifier("Full Name".to_string()).to_sql()
+        );
+        assert_eq!("`order`", Expr::Identifier("order".to_string()).to_sql());
+        assert_eq!(
+            "Item.`1st`",
+            Expr::CompoundIdentifier(vec!["Item".to_string(), "1st".to_string()]).to_sql()
+        );
 
         assert_eq!(
             "id + num",
@@ -221,6 +300,36 @@ mod tests {
             .to_sql()
         );
 
+        assert_eq!(
+            "items @> wanted",
+            Expr::BinaryOp {
+                left: Box::new(Expr::Identifier("items".to_owned())),
+                op: BinaryOperator::Contains,
+                right: Box::new(Expr::Identifier("wanted".to_owned()))
+            }
+            .to_sql()
+        );
+
+        assert_eq!(
+            "id = ANY(items)",
+            Expr::AnyOp {
+                left: Box::new(Expr::Identifier("id".to_owned())),
+                op: BinaryOperator::Eq,
+                right: Box::new(Expr::Identifier("items".to_owned()))
+            }
+            .to_sql()
+        );
+
+        assert_eq!(
+            "id > ALL(items)",
+            Expr::AllOp {
+                left: Box::new(Expr::Identifier("id".to_owned())),
+                op: BinaryOperator::Gt,
+                right: Box::new(Expr::Identifier("items".to_owned()))
+            }
+            .to_sql()
+        );
+
         assert_eq!(
             "+id",
             Expr::UnaryOp {
@@ -257,6 +366,15 @@ mod tests {
             .to_sql(),
         );
 
+        assert_eq!(
+            "done IS NOT UNKNOWN",
+            Expr::UnaryOp {
+                op: UnaryOperator::IsNotUnknown,
+                expr: Box::new(Expr::Identifier("done".to_owned())),
+            }
+            .to_sql(),
+        );
+
         assert_eq!(
             "id.name.first",
             Expr::CompoundIdentifier(vec![
@@ -284,6 +402,8 @@ mod tests {
             .to_sql()
         );
 
+        assert_eq!("$2", Expr::Placeholder(2).to_sql());
+
         assert_eq!(
             r#"INT("1")"#,
             Expr::TypedString {
@@ -438,5 +558,99 @@ mod tests {
             ))))
             .to_sql()
         );
+        assert_eq!(
+            "BOOL_AND(valid)",
+            &Expr::Aggregate(Box::new(Aggregate::BoolAnd(Expr::Identifier(
+                "valid".to_string()
+            ))))
+            .to_sql()
+        );
+        assert_eq!(
+            "BOOL_OR(valid)",
+            &Expr::Aggregate(Box::new(Aggregate::BoolOr(Expr::Identifier(
+                "valid".to_string()
+            ))))
+            .to_sql()
+        );
+        assert_eq!(
+            "ARRAY_AGG(name)",
+            &Expr::Aggregate(Box::new(Aggregate::ArrayAgg {
+                expr: Expr::Identifier("name".to_string()),
+                order_by: None,
+            }))
+            .to_sql()
+        );
+        assert_eq!(
+            "ARRAY_AGG(name ORDER BY id DESC)",
+            &Expr::Aggregate(Box::new(Aggregate::ArrayAgg {
+                expr: Expr::Identifier("name".to_string()),
+                order_by: Some(OrderByExpr {
+                    expr: Expr::Identifier("id".to_string()),
+                    asc: Some(false),
+                    nulls_first: None,
+                    collation: None,
+                }),
+            }))
+            .to_sql()
+        );
+        assert_eq!(
+            "JSON_AGG(detail)",
+            &Expr::Aggregate(Box::new(Aggregate::JsonAgg(Expr::Identifier(
+                "detail".to_string()
+            ))))
+            .to_sql()
+        );
+        assert_eq!(
+            "JSON_OBJECT_AGG(name, score)",
+            &Expr::Aggregate(Box::new(Aggregate::JsonObjectAgg {
+                key: Expr::Identifier("name".to_string()),
+                value: Expr::Identifier("score".to_string()),
+            }))
+            .to_sql()
+        );
+        assert_eq!(
+            "COUNT(*) FILTER (WHERE ok)",
+            &Expr::Aggregate(Box::new(Aggregate::Filter {
+                aggregate: Box::new(Aggregate::Count(CountArgExpr::Wildcard)),
+                filter: Expr::Identifier("ok".to_string()),
+            }))
+            .to_sql()
+        );
+        assert_eq!(
+            "GROUPING(city, name)",
+            &Expr::Aggregate(Box::new(Aggregate::Grouping(vec![
+                Expr::Identifier("city".to_string()),
+                Expr::Identifier("name".to_string()),
+            ])))
+            .to_sql()
+        );
+
+        assert_eq!(
+            "SUM(total) OVER (..)",
+            &Expr::Window(Box::new(Window {
+                function: WindowFunction::Aggregate(Aggregate::Sum(Expr::Identifier(
+                    "total".to_string()
+                ))),
+                over: WindowSpec {
+                    partition_by: vec![],
+                    order_by: vec![],
+                    window_frame: None,
+                },
+            }))
+            .to_sql()
+        );
+
+        assert_eq!(
+            "LAST_VALUE(..) OVER (..)",
+            &Expr::Window(Box::new(Window {
+                function: WindowFunction::LastValue(Expr::Identifier("name".to_string())),
+                over: WindowSpec {
+                    partition_by: vec![],
+                    order_by: vec![],
+                    window_frame: None,
+                },
+            }))
+            .to_sql()
+        );
     }
 }
@@ -1,5 +1,5 @@
 use {
-    super::{ast_literal::TrimWhereField, Expr},
+    super::{ast_literal::TrimWhereField, Expr, OrderByExpr},
     crate::ast::ToSql,
     serde::{Deserialize, Serialize},
     strum_macros::Display,
@@ -34,12 +34,22 @@ pub enum Function {
     },
     Ceil(Expr),
     Concat(Vec<Expr>),
+    ConcatWs {
+        separator: Expr,
+        exprs: Vec<Expr>,
+    },
+    Format {
+        format: Expr,
+        args: Vec<Expr>,
+    },
     IfNull {
         expr: Expr,
         then: Expr,
     },
     Round(Expr),
     Floor(Expr),
+    /// `TRIM([{LEADING | TRAILING | BOTH} filter_chars FROM] expr)`, which trims both ends when
+    /// no direction is given. Without `filter_chars` it trims whitespace, in any direction.
     Trim {
         expr: Expr,
         filter_chars: Option<Expr>,
@@ -94,7 +104,32 @@ pub enum Function {
         expr: Expr,
         num: Expr,
     },
+    Glob {
+        expr: Expr,
+        pattern: Expr,
+    },
+    /// `LEVENSHTEIN(left, right[, max_distance])`
+    Levenshtein {
+        left: Expr,
+        right: Expr,
+        max_distance: Option<Expr>,
+    },
+    /// `SIMILARITY(left, right)`
+    Similarity {
+        left: Expr,
+        right: Expr,
+    },
+    /// `SOUNDEX(expr)`
+    Soundex(Expr),
     Sign(Expr),
+    Length(Expr),
+    Ascii(Expr),
+    Chr(Expr),
+    Ord(Expr),
+    Hex(Expr),
+    Unhex(Expr),
+    ToBase64(Expr),
+    FromBase64(Expr),
     Substr {
         expr: Expr,
         start: Expr,
@@ -105,6 +140,17 @@ pub enum Function {
         selector: Expr,
     },
     GenerateUuid(),
+    /// `RANDOM()` or `RANDOM(min, max)`
+    Random(Option<(Expr, Expr)>),
+    /// `NEXTVAL(sequence)`
+    Nextval(Expr),
+    /// `CURRVAL(sequence)`
+    Currval(Expr),
+    /// Function registered by `Session::register_function`
+    Custom {
+        name: String,
+        args: Vec<Expr>,
+    },
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -116,6 +162,30 @@ pub enum Aggregate {
     Avg(Expr),
     Variance(Expr),
     Stdev(Expr),
+    BoolAnd(Expr),
+    BoolOr(Expr),
+    ArrayAgg {
+        expr: Expr,
+        order_by: Option<OrderByExpr>,
+    },
+    JsonAgg(Expr),
+    JsonObjectAgg {
+        key: Expr,
+        value: Expr,
+    },
+    /// `aggregate FILTER (WHERE filter)`, which only accumulates the rows passing `filter`
+    Filter {
+        aggregate: Box<Aggregate>,
+        filter: Expr,
+    },
+    /// `GROUPING(a, b, ...)`, a bit mask of the grouping columns left out of the grouping set
+    /// of each group, where the last column is the lowest bit
+    Grouping(Vec<Expr>),
+    /// Aggregate registered by `Session::register_aggregate`
+    Custom {
+        name: String,
+        expr: Expr,
+    },
 }
 
 impl ToSql for Aggregate {
@@ -128,6 +198,58 @@ impl ToSql for Aggregate {
             Aggregate::Avg(e) => format!("AVG({})", e.to_sql()),
             Aggregate::Variance(e) => format!("VARIANCE({})", e.to_sql()),
             Aggregate::Stdev(e) => format!("STDEV({})", e.to_sql()),
+            Aggregate::BoolAnd(e) => format!("BOOL_AND({})", e.to_sql()),
+            Aggregate::BoolOr(e) => format!("BOOL_OR({})", e.to_sql()),
+            Aggregate::ArrayAgg {
+                expr,
+                order_by: None,
+            } => format!("ARRAY_AGG({})", expr.to_sql()),
+            Aggregate::ArrayAgg {
+                expr,
+                order_by:
+                    Some(OrderByExpr {
+                        expr: key,
+                        asc,
+                        nulls_first,
+                        ..
+                    }),
+            } => {
+                let order = match asc {
+                    Some(true) => " ASC",
+                    Some(false) => " DESC",
+                    None => "",
+                };
+                let nulls = match nulls_first {
+                    Some(true) => " NULLS FIRST",
+                    Some(false) => " NULLS LAST",
+                    None => "",
+                };
+
+                format!(
+                    "ARRAY_AGG({} ORDER BY {}{}{})",
+                    expr.to_sql(),
+                    key.to_sql(),
+                    order,
+                    nulls
+                )
+            }
+            Aggregate::JsonAgg(e) => format!("JSON_AGG({})", e.to_sql()),
+            Aggregate::Custom { name, expr } => format!("{}({})", name, expr.to_sql()),
+            Aggregate::JsonObjectAgg { key, value } => {
+                format!("JSON_OBJECT_AGG({}, {})", key.to_sql(), value.to_sql())
+            }
+            Aggregate::Filter { aggregate, filter } => {
+                format!("{} FILTER (WHERE {})", aggregate.to_sql(), filter.to_sql())
+            }
+            Aggregate::Grouping(exprs) => {
+                let exprs = exprs
+                    .iter()
+                    .map(ToSql::to_sql)
+                    .collect::<Vec<_>>()
+                    .join(", ");
+
+                format!("GROUPING({exprs})")
+            }
         }
     }
 }
@@ -5,6 +5,7 @@ mod expr;
 mod function;
 mod operator;
 mod query;
+mod window;
 
 pub use ast_literal::{AstLiteral, DateTimeField, TrimWhereField};
 pub use data_type::DataType;
@@ -13,6 +14,7 @@ pub use expr::Expr;
 pub use function::{Aggregate, CountArgExpr, Function};
 pub use operator::*;
 pub use query::*;
+pub use window::*;
 
 use serde::{Deserialize, Serialize};
 
@@ -30,6 +32,8 @@ pub enum Statement {
     },
     /// SELECT
     Query(Box<Query>),
+    /// EXPLAIN ANALYZE, which runs the query and reports the rows and time of each stage
+    ExplainAnalyze(Box<Query>),
     /// INSERT
     Insert {
         /// TABLE
@@ -38,6 +42,8 @@ pub enum Statement {
         columns: Vec<String>,
         /// A SQL query that specifies what to insert
         source: Box<Query>,
+        /// RETURNING, the inserted rows are not returned if empty
+        returning: Vec<SelectItem>,
     },
     /// UPDATE
     Update {
@@ -47,6 +53,8 @@ pub enum Statement {
         assignments: Vec<Assignment>,
         /// WHERE
         selection: Option<Expr>,
+        /// RETURNING, the updated rows are not returned if empty
+        returning: Vec<SelectItem>,
     },
     /// DELETE
     Delete {
@@ -54,7 +62,11 @@ pub enum Statement {
         table_name: ObjectName,
         /// WHERE
         selection: Option<Expr>,
+        /// RETURNING, the deleted rows are not returned if empty
+        returning: Vec<SelectItem>,
     },
+    /// MERGE
+    Merge(Box<Merge>),
     /// CREATE TABLE
     CreateTable {
         if_not_exists: bool,
@@ -64,6 +76,27 @@ pub enum Statement {
         columns: Vec<ColumnDef>,
         source: Option<Box<Query>>,
     },
+    /// CREATE VIEW
+    CreateView {
+        /// View name
+        name: ObjectName,
+        /// Column names, the labels of the query are used if empty
+        columns: Vec<String>,
+        query: Box<Query>,
+    },
+    /// CREATE MATERIALIZED VIEW
+    CreateMaterializedView {
+        /// View name
+        name: ObjectName,
+        /// Column names, the labels of the query are used if empty
+        columns: Vec<String>,
+        query: Box<Query>,
+    },
+    /// REFRESH MATERIALIZED VIEW
+    RefreshMaterializedView {
+        /// View name
+        name: ObjectName,
+    },
     /// ALTER TABLE
     #[cfg(feature = "alter-table")]
     AlterTable {
@@ -71,6 +104,37 @@ pub enum Statement {
         name: ObjectName,
         operation: AlterTableOperation,
     },
+    /// TRUNCATE TABLE
+    Truncate {
+        /// TABLE
+        table_name: ObjectName,
+    },
+    /// CREATE SEQUENCE
+    CreateSequence {
+        /// Sequence name
+        name: ObjectName,
+    },
+    /// DROP SEQUENCE
+    DropSequence {
+        /// An optional `IF EXISTS` clause
+        if_exists: bool,
+        name: ObjectName,
+    },
+    /// CREATE FUNCTION
+    CreateFunction {
+        /// Function name
+        name: ObjectName,
+        args: Vec<FunctionArg>,
+        return_type: DataType,
+        /// Expression over the parameters, evaluated for each call
+        body: Expr,
+    },
+    /// DROP FUNCTION
+    DropFunction {
+        /// An optional `IF EXISTS` clause
+        if_exists: bool,
+        name: ObjectName,
+    },
     /// DROP TABLE
     DropTable {
         /// An optional `IF EXISTS` clause. (Non-standard.)
@@ -100,6 +164,21 @@ pub enum Statement {
     /// ROLLBACK
     #[cfg(feature = "transaction")]
     Rollback,
+    /// SAVEPOINT
+    #[cfg(feature = "transaction")]
+    Savepoint {
+        name: String,
+    },
+    /// ROLLBACK TO SAVEPOINT
+    #[cfg(feature = "transaction")]
+    RollbackToSavepoint {
+        name: String,
+    },
+    /// RELEASE SAVEPOINT
+    #[cfg(feature = "transaction")]
+    ReleaseSavepoint {
+        name: String,
+    },
     /// SHOW VARIABLE
     #[cfg(feature = "metadata")]
     ShowVariable(Variable),
@@ -107,12 +186,89 @@ pub enum Statement {
     ShowIndexes(ObjectName),
 }
 
+impl Statement {
+    /// Leading keywords of the statement, e.g. `CREATE TABLE` or `INSERT`.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Statement::ShowColumns { .. } => "SHOW COLUMNS",
+            Statement::Query(_) => "SELECT",
+            Statement::ExplainAnalyze(_) => "EXPLAIN ANALYZE",
+            Statement::Insert { .. } => "INSERT",
+            Statement::Update { .. } => "UPDATE",
+            Statement::Delete { .. } => "DELETE",
+            Statement::Merge(_) => "MERGE",
+            Statement::CreateTable { .. } => "CREATE TABLE",
+            Statement::CreateView { .. } => "CREATE VIEW",
+            Statement::CreateMaterializedView { .. } => "CREATE MATERIALIZED VIEW",
+            Statement::RefreshMaterializedView { .. } => "REFRESH MATERIALIZED VIEW",
+            #[cfg(feature = "alter-table")]
+            Statement::AlterTable { .. } => "ALTER TABLE",
+            Statement::Truncate { .. } => "TRUNCATE TABLE",
+            Statement::CreateSequence { .. } => "CREATE SEQUENCE",
+            Statement::DropSequence { .. } => "DROP SEQUENCE",
+            Statement::CreateFunction { .. } => "CREATE FUNCTION",
+            Statement::DropFunction { .. } => "DROP FUNCTION",
+            Statement::DropTable { .. } => "DROP TABLE",
+            #[cfg(feature = "index")]
+            Statement::CreateIndex { .. } => "CREATE INDEX",
+            #[cfg(feature = "index")]
+            Statement::DropIndex { .. } => "DROP INDEX",
+            #[cfg(feature = "transaction")]
+            Statement::StartTransaction => "BEGIN",
+            #[cfg(feature = "transaction")]
+            Statement::Commit => "COMMIT",
+            #[cfg(feature = "transaction")]
+            Statement::Rollback => "ROLLBACK",
+            #[cfg(feature = "transaction")]
+            Statement::Savepoint { .. } => "SAVEPOINT",
+            #[cfg(feature = "transaction")]
+            Statement::RollbackToSavepoint { .. } => "ROLLBACK TO SAVEPOINT",
+            #[cfg(feature = "transaction")]
+            Statement::ReleaseSavepoint { .. } => "RELEASE SAVEPOINT",
+            #[cfg(feature = "metadata")]
+            Statement::ShowVariable(_) => "SHOW",
+            #[cfg(feature = "index")]
+            Statement::ShowIndexes(_) => "SHOW INDEXES",
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct Assignment {
     pub id: String,
     pub value: Expr,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Merge {
+    /// INTO
+    pub table_name: ObjectName,
+    pub alias: Option<String>,
+    /// USING
+    pub source: TableFactor,
+    /// ON
+    pub on: Expr,
+    /// WHEN clauses, the first one whose kind and condition fit a row applies to it
+    pub clauses: Vec<MergeClause>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum MergeClause {
+    /// `WHEN MATCHED [AND predicate] THEN UPDATE SET ...`
+    MatchedUpdate {
+        predicate: Option<Expr>,
+        assignments: Vec<Assignment>,
+    },
+    /// `WHEN MATCHED [AND predicate] THEN DELETE`
+    MatchedDelete { predicate: Option<Expr> },
+    /// `WHEN NOT MATCHED [AND predicate] THEN INSERT [(columns)] VALUES (values)`
+    NotMatched {
+        predicate: Option<Expr>,
+        columns: Vec<String>,
+        values: Vec<Expr>,
+    },
+}
+
 #[cfg(feature = "metadata")]
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum Variable {
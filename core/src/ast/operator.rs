@@ -9,6 +9,14 @@ pub enum UnaryOperator {
     Minus,
     Not,
     Factorial,
+    BitwiseNot,
+    /// Postfix truth tests, which give TRUE or FALSE even for a NULL operand
+    IsTrue,
+    IsNotTrue,
+    IsFalse,
+    IsNotFalse,
+    IsUnknown,
+    IsNotUnknown,
 }
 
 impl ToSql for UnaryOperator {
@@ -18,6 +26,13 @@ impl ToSql for UnaryOperator {
             UnaryOperator::Minus => "-".to_string(),
             UnaryOperator::Not => "NOT ".to_string(),
             UnaryOperator::Factorial => "!".to_string(),
+            UnaryOperator::BitwiseNot => "~".to_string(),
+            UnaryOperator::IsTrue => " IS TRUE".to_string(),
+            UnaryOperator::IsNotTrue => " IS NOT TRUE".to_string(),
+            UnaryOperator::IsFalse => " IS FALSE".to_string(),
+            UnaryOperator::IsNotFalse => " IS NOT FALSE".to_string(),
+            UnaryOperator::IsUnknown => " IS UNKNOWN".to_string(),
+            UnaryOperator::IsNotUnknown => " IS NOT UNKNOWN".to_string(),
         }
     }
 }
@@ -43,6 +58,16 @@ pub enum BinaryOperator {
     ILike,
     NotLike,
     NotILike,
+    BitwiseAnd,
+    BitwiseOr,
+    BitwiseXor,
+    BitwiseShiftLeft,
+    BitwiseShiftRight,
+    /// NULL-safe `<>`, two NULLs are not distinct from each other
+    IsDistinctFrom,
+    IsNotDistinctFrom,
+    /// `@>`, whether the left list holds every element of the right list
+    Contains,
 }
 
 impl ToSql for BinaryOperator {
@@ -67,6 +92,14 @@ impl ToSql for BinaryOperator {
             BinaryOperator::ILike => "ILIKE".to_string(),
             BinaryOperator::NotLike => "NOT LIKE".to_string(),
             BinaryOperator::NotILike => "NOT ILIKE".to_string(),
+            BinaryOperator::BitwiseAnd => "&".to_string(),
+            BinaryOperator::BitwiseOr => "|".to_string(),
+            BinaryOperator::BitwiseXor => "^".to_string(),
+            BinaryOperator::BitwiseShiftLeft => "<<".to_string(),
+            BinaryOperator::BitwiseShiftRight => ">>".to_string(),
+            BinaryOperator::IsDistinctFrom => "IS DISTINCT FROM".to_string(),
+            BinaryOperator::IsNotDistinctFrom => "IS NOT DISTINCT FROM".to_string(),
+            BinaryOperator::Contains => "@>".to_string(),
         }
     }
 }
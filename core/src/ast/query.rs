@@ -1,15 +1,33 @@
 use {
-    super::{Expr, IndexOperator, ObjectName},
+    super::{Collation, Expr, IndexOperator, ObjectName},
     serde::{Deserialize, Serialize},
+    std::ops::Bound,
 };
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct Query {
+    /// Common table expressions of `WITH`, each visible to the following ones and to the body
+    pub with: Vec<Cte>,
     pub body: SetExpr,
     pub limit: Option<Expr>,
     pub offset: Option<Expr>,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Cte {
+    pub alias: TableAlias,
+    pub query: Query,
+    /// `WITH RECURSIVE` term, run on the rows of the previous round until it returns none
+    pub recursive: Option<RecursiveTerm>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct RecursiveTerm {
+    /// `UNION ALL` keeps duplicate rows, `UNION` drops them
+    pub all: bool,
+    pub query: Query,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum SetExpr {
     Select(Box<Select>),
@@ -23,8 +41,39 @@ pub struct Select {
     /// WHERE
     pub selection: Option<Expr>,
     pub group_by: Vec<Expr>,
+    /// Grouping sets of `GROUP BY`, which always group by `group_by` as well
+    pub grouping_sets: Option<GroupingSets>,
     pub having: Option<Expr>,
     pub order_by: Vec<OrderByExpr>,
+    /// DISTINCT ON, always the leading expressions of `order_by`
+    pub distinct_on: Vec<Expr>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum GroupingSets {
+    /// `CUBE (a, b, ...)`, which groups by every subset of the columns
+    Cube(Vec<Expr>),
+    /// `GROUPING SETS ((a, b), (a), ())`, which groups by each of the listed sets
+    Sets(Vec<Vec<Expr>>),
+}
+
+impl GroupingSets {
+    /// Columns which appear in any of the grouping sets, in order of their first appearance.
+    pub fn columns(&self) -> Vec<&Expr> {
+        match self {
+            GroupingSets::Cube(exprs) => exprs.iter().collect(),
+            GroupingSets::Sets(sets) => {
+                let mut columns = Vec::new();
+                for expr in sets.iter().flatten() {
+                    if !columns.contains(&expr) {
+                        columns.push(expr);
+                    }
+                }
+
+                columns
+            }
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -47,7 +96,23 @@ pub struct TableWithJoins {
 pub struct IndexItem {
     pub name: String,
     pub asc: Option<bool>,
-    pub cmp_expr: Option<(IndexOperator, Expr)>,
+    pub cmp_expr: Option<IndexCmp>,
+}
+
+/// Values an index scan is bounded by
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum IndexCmp {
+    /// `indexed_expr op expr`
+    Op(IndexOperator, Expr),
+    /// `indexed_expr IN (...)`, or equalities joined by `OR`, each looked up on its own
+    In(Vec<Expr>),
+    /// `indexed_expr BETWEEN ...`, or a lower and an upper bound joined by `AND`
+    Range {
+        start: Bound<Expr>,
+        end: Bound<Expr>,
+    },
+    /// Equalities on the leading columns of a composite index
+    Prefix(Vec<Expr>),
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -57,11 +122,30 @@ pub enum TableFactor {
         alias: Option<TableAlias>,
         /// Query planner result
         index: Option<IndexItem>,
+        sample: Option<TableSample>,
     },
     Derived {
+        /// `LATERAL` subqueries are re-executed for each row of the preceding relations
+        lateral: bool,
         subquery: Query,
         alias: TableAlias,
     },
+    /// `generate_series(start, end [, step])`
+    Series {
+        alias: TableAlias,
+        start: Expr,
+        end: Expr,
+        step: Option<Expr>,
+    },
+}
+
+/// `TABLESAMPLE BERNOULLI (percentage) [REPEATABLE (seed)]`, which keeps each row of the table
+/// with the probability of `percentage` out of 100
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct TableSample {
+    pub percentage: Expr,
+    /// Seed which makes the same rows be kept on every scan of the same table
+    pub seed: Option<Expr>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -103,6 +187,10 @@ pub enum JoinConstraint {
 pub struct OrderByExpr {
     pub expr: Expr,
     pub asc: Option<bool>,
+    /// `NULLS FIRST` or `NULLS LAST`, NULL is otherwise ordered as the greatest value
+    pub nulls_first: Option<bool>,
+    /// `COLLATE`, applied to text keys only when comparing them
+    pub collation: Option<Collation>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -0,0 +1,69 @@
+use {
+    super::{Aggregate, Expr, OrderByExpr},
+    serde::{Deserialize, Serialize},
+    strum_macros::Display,
+};
+
+/// `<window function> OVER (<window spec>)`
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Window {
+    pub function: WindowFunction,
+    pub over: WindowSpec,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, Display)]
+#[strum(serialize_all = "SCREAMING_SNAKE_CASE")]
+pub enum WindowFunction {
+    FirstValue(Expr),
+    LastValue(Expr),
+    NthValue {
+        expr: Expr,
+        n: Expr,
+    },
+    /// `LAG(expr [, offset [, default]])`
+    Lag {
+        expr: Expr,
+        offset: Option<Expr>,
+        default: Option<Expr>,
+    },
+    /// `LEAD(expr [, offset [, default]])`
+    Lead {
+        expr: Expr,
+        offset: Option<Expr>,
+        default: Option<Expr>,
+    },
+    /// Aggregate function computed over the window frame, e.g. `SUM(expr) OVER (..)`
+    Aggregate(Aggregate),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct WindowSpec {
+    pub partition_by: Vec<Expr>,
+    pub order_by: Vec<OrderByExpr>,
+    pub window_frame: Option<WindowFrame>,
+}
+
+/// `ROWS | RANGE BETWEEN <start_bound> AND <end_bound>`
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct WindowFrame {
+    pub units: WindowFrameUnits,
+    pub start_bound: WindowFrameBound,
+    /// `None` if the frame is specified without `BETWEEN`, which means `CURRENT ROW`
+    pub end_bound: Option<WindowFrameBound>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum WindowFrameUnits {
+    Rows,
+    Range,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum WindowFrameBound {
+    /// `CURRENT ROW`
+    CurrentRow,
+    /// `<N> PRECEDING` or `UNBOUNDED PRECEDING`
+    Preceding(Option<u64>),
+    /// `<N> FOLLOWING` or `UNBOUNDED FOLLOWING`
+    Following(Option<u64>),
+}
@@ -33,6 +33,7 @@ impl DeleteNode {
         Ok(Statement::Delete {
             table_name,
             selection,
+            returning: Vec::new(),
         })
     }
 }
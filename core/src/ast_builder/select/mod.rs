@@ -50,11 +50,14 @@ impl NodeData {
             from,
             selection,
             group_by,
+            grouping_sets: None,
             having,
             order_by: vec![],
+            distinct_on: vec![],
         };
 
         let query = Query {
+            with: Vec::new(),
             body: SetExpr::Select(Box::new(select)),
             offset,
             limit,
@@ -60,6 +60,7 @@ impl Prebuild for SelectNode {
             name: ObjectName(vec![self.table_name]),
             alias: None,
             index: None,
+            sample: None,
         };
 
         let from = TableWithJoins {
@@ -44,7 +44,10 @@ impl TryFrom<SelectItemNode> for SelectItem {
             }
             SelectItemNode::Expr(expr_node) => {
                 let expr = Expr::try_from(expr_node)?;
-                let label = expr.to_sql();
+                let label = match &expr {
+                    Expr::Identifier(ident) => ident.to_owned(),
+                    _ => expr.to_sql(),
+                };
 
                 Ok(SelectItem::Expr { expr, label })
             }
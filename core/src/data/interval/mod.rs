@@ -5,7 +5,10 @@ mod string;
 use {
     super::Value,
     crate::{ast::DateTimeField, result::Result},
-    chrono::{Datelike, Duration, NaiveDate, NaiveDateTime, NaiveTime, Timelike},
+    chrono::{
+        DateTime, Datelike, Duration, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime, TimeZone,
+        Timelike,
+    },
     core::str::FromStr,
     rust_decimal::{prelude::ToPrimitive, Decimal},
     serde::{Deserialize, Serialize},
@@ -20,6 +23,16 @@ pub enum Interval {
     Microsecond(i64),
 }
 
+fn with_offset(local: NaiveDateTime, offset: &FixedOffset) -> Result<DateTime<FixedOffset>> {
+    offset.from_local_datetime(&local).single().ok_or_else(|| {
+        IntervalError::DateOverflow {
+            year: local.year(),
+            month: local.month() as i32,
+        }
+        .into()
+    })
+}
+
 impl PartialOrd<Interval> for Interval {
     fn partial_cmp(&self, other: &Interval) -> Option<Ordering> {
         match (self, other) {
@@ -43,6 +56,20 @@ impl Interval {
         }
     }
 
+    /// Multiplies by an integer, `None` if the product does not fit in the interval.
+    pub fn checked_mul(&self, rhs: i128) -> Option<Self> {
+        match self {
+            Interval::Month(v) => i128::from(*v)
+                .checked_mul(rhs)
+                .and_then(|v| i32::try_from(v).ok())
+                .map(Interval::Month),
+            Interval::Microsecond(v) => i128::from(*v)
+                .checked_mul(rhs)
+                .and_then(|v| i64::try_from(v).ok())
+                .map(Interval::Microsecond),
+        }
+    }
+
     pub fn add(&self, other: &Interval) -> Result<Self> {
         use Interval::*;
 
@@ -105,6 +132,26 @@ impl Interval {
         }
     }
 
+    /// Adds to the local time of `timestamp`, so a month or a day later keeps its wall clock
+    /// time and offset.
+    pub fn add_timestamptz(
+        &self,
+        timestamp: &DateTime<FixedOffset>,
+    ) -> Result<DateTime<FixedOffset>> {
+        self.add_timestamp(&timestamp.naive_local())
+            .and_then(|local| with_offset(local, timestamp.offset()))
+    }
+
+    pub fn subtract_from_timestamptz(
+        &self,
+        timestamp: &DateTime<FixedOffset>,
+    ) -> Result<DateTime<FixedOffset>> {
+        self.subtract_from_timestamp(&timestamp.naive_local())
+            .and_then(|local| with_offset(local, timestamp.offset()))
+    }
+
+    /// Turns `time` forward like a clock, so it wraps around at midnight and whole days drop out,
+    /// e.g. `23:30:00 + 1 hour` is `00:30:00`. A year or month interval errors.
     pub fn add_time(&self, time: &NaiveTime) -> Result<NaiveTime> {
         match self {
             Interval::Month(_) => Err(IntervalError::AddYearOrMonthToTime {
@@ -116,6 +163,7 @@ impl Interval {
         }
     }
 
+    /// Turns `time` back like a clock, so `00:15:00 - 30 minutes` is `23:45:00`.
     pub fn subtract_from_time(&self, time: &NaiveTime) -> Result<NaiveTime> {
         match self {
             Interval::Month(_) => Err(IntervalError::SubtractYearOrMonthToTime {
@@ -314,6 +362,12 @@ mod tests {
         assert_eq!(Month(1).unary_minus(), Month(-1));
         assert_eq!(Microsecond(1).unary_minus(), Microsecond(-1));
 
+        assert_eq!(Month(2).checked_mul(3), Some(Month(6)));
+        assert_eq!(Microsecond(2).checked_mul(-3), Some(Microsecond(-6)));
+        assert_eq!(Month(i32::MAX).checked_mul(2), None);
+        assert_eq!(Microsecond(i64::MAX).checked_mul(2), None);
+        assert_eq!(Microsecond(1).checked_mul(i128::MAX), None);
+
         // date
         assert_eq!(
             Month(2).add_date(&date(2021, 11, 11)),
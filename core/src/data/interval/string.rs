@@ -6,12 +6,17 @@ use {
         result::{Error, Result},
         translate::translate_expr,
     },
+    std::fmt,
 };
 
 impl TryFrom<&str> for Interval {
     type Error = Error;
 
     fn try_from(s: &str) -> Result<Self> {
+        if let Some(interval) = Interval::parse_text(s) {
+            return Ok(interval);
+        }
+
         let parsed = parse_interval(s)?;
 
         match translate_expr(&parsed)? {
@@ -145,6 +150,141 @@ impl From<Interval> for String {
     }
 }
 
+/// Canonical text of an interval, which `CAST(.. AS TEXT)` gives and `CAST(.. AS INTERVAL)`
+/// reads back, e.g. `1 year 2 months`, `1 day 02:00:00` or `-00:00:01.5`.
+///
+/// A negative interval has a single leading sign which applies to all of its parts.
+impl fmt::Display for Interval {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fn unit(n: i64, name: &str) -> String {
+            format!("{} {}{}", n, name, if n == 1 { "" } else { "s" })
+        }
+
+        match *self {
+            Interval::Month(v) => {
+                let v = i64::from(v);
+                let (sign, v) = if v < 0 { ("-", -v) } else { ("", v) };
+
+                let year = v / 12;
+                let month = v % 12;
+
+                match (year, month) {
+                    (0, _) => write!(f, "{}{}", sign, unit(month, "month")),
+                    (_, 0) => write!(f, "{}{}", sign, unit(year, "year")),
+                    _ => write!(f, "{}{} {}", sign, unit(year, "year"), unit(month, "month")),
+                }
+            }
+            Interval::Microsecond(v) => {
+                let (sign, v) = if v < 0 { ("-", -v) } else { ("", v) };
+
+                let day = v / DAY;
+                let hour = (v % DAY) / HOUR;
+                let minute = (v % HOUR) / MINUTE;
+                let second = (v % MINUTE) / SECOND;
+                let microsecond = v % SECOND;
+
+                write!(f, "{}", sign)?;
+
+                if day != 0 {
+                    write!(f, "{} ", unit(day, "day"))?;
+                }
+
+                write!(f, "{:02}:{:02}:{:02}", hour, minute, second)?;
+
+                match microsecond {
+                    0 => Ok(()),
+                    _ => write!(
+                        f,
+                        ".{}",
+                        format!("{:06}", microsecond).trim_end_matches('0')
+                    ),
+                }
+            }
+        }
+    }
+}
+
+impl Interval {
+    /// Parses the canonical text `Display` gives, `None` if `text` is not in that form.
+    ///
+    /// Parts may be repeated and years or months can not be mixed with days or a time.
+    fn parse_text(text: &str) -> Option<Self> {
+        let text = text.trim();
+        let (negative, text) = match text.strip_prefix('-') {
+            Some(text) => (true, text),
+            None => (false, text.strip_prefix('+').unwrap_or(text)),
+        };
+
+        let mut months: Option<i64> = None;
+        let mut microseconds: Option<i64> = None;
+        let mut tokens = text.split_whitespace().peekable();
+
+        while let Some(token) = tokens.next() {
+            if token.contains(':') {
+                if tokens.peek().is_some() {
+                    return None;
+                }
+
+                let time = parse_time(token)?;
+                microseconds = Some(microseconds.unwrap_or(0).checked_add(time)?);
+                continue;
+            }
+
+            let n = token.parse::<i64>().ok()?;
+            let (total, n) = match tokens.next()?.to_lowercase().as_str() {
+                "year" | "years" => (&mut months, n.checked_mul(12)?),
+                "month" | "months" => (&mut months, n),
+                "day" | "days" => (&mut microseconds, n.checked_mul(DAY)?),
+                _ => return None,
+            };
+
+            *total = Some(total.unwrap_or(0).checked_add(n)?);
+        }
+
+        let sign = if negative { -1 } else { 1 };
+
+        match (months, microseconds) {
+            (Some(months), None) => i32::try_from(sign * months).ok().map(Interval::Month),
+            (None, Some(microseconds)) => Some(Interval::Microsecond(sign * microseconds)),
+            _ => None,
+        }
+    }
+}
+
+/// Microseconds of `HH:MM:SS[.ffffff]`, the hours are not limited to a day.
+fn parse_time(text: &str) -> Option<i64> {
+    fn digits(text: &str) -> Option<i64> {
+        if text.is_empty() || !text.bytes().all(|b| b.is_ascii_digit()) {
+            return None;
+        }
+
+        text.parse().ok()
+    }
+
+    let mut parts = text.split(':');
+    let (hour, minute, second) = (parts.next()?, parts.next()?, parts.next()?);
+    if parts.next().is_some() {
+        return None;
+    }
+
+    let (second, fraction) = match second.split_once('.') {
+        Some((second, fraction)) if fraction.len() <= 6 => (
+            second,
+            digits(fraction)? * 10_i64.pow(6 - fraction.len() as u32),
+        ),
+        Some(_) => return None,
+        None => (second, 0),
+    };
+
+    let (hour, minute, second) = (digits(hour)?, digits(minute)?, digits(second)?);
+    if minute >= 60 || second >= 60 {
+        return None;
+    }
+
+    hour.checked_mul(HOUR)?
+        .checked_add(minute * MINUTE + second * SECOND + fraction)
+}
+
 #[cfg(test)]
 mod tests {
     use super::Interval;
@@ -265,4 +405,39 @@ mod tests {
             "31 06:01:40.000003" DAY TO SECOND
         );
     }
+
+    #[test]
+    fn text() {
+        macro_rules! test {
+            ($interval: expr, $text: literal) => {
+                assert_eq!($interval.to_string(), $text);
+                assert_eq!(Interval::try_from($text), Ok($interval));
+            };
+        }
+
+        test!(Interval::Month(0), "0 months");
+        test!(Interval::Month(1), "1 month");
+        test!(Interval::Month(12), "1 year");
+        test!(Interval::Month(14), "1 year 2 months");
+        test!(Interval::Month(-27), "-2 years 3 months");
+        test!(Interval::Microsecond(0), "00:00:00");
+        test!(Interval::hours(26), "1 day 02:00:00");
+        test!(Interval::days(-3), "-3 days 00:00:00");
+        test!(Interval::minutes(-90), "-01:30:00");
+        test!(Interval::Microsecond(-1_500_000), "-00:00:01.5");
+        test!(Interval::Microsecond(1_000_003), "00:00:01.000003");
+
+        assert_eq!(Interval::try_from("2 Days"), Ok(Interval::days(2)));
+        assert_eq!(Interval::try_from("+ 1 day 1 day"), Ok(Interval::days(2)));
+        assert_eq!(Interval::try_from("36:00:00"), Ok(Interval::hours(36)));
+        assert_eq!(
+            Interval::try_from("00:00:00.25"),
+            Ok(Interval::Microsecond(250_000))
+        );
+        assert_eq!(Interval::parse_text("1 year 2 days"), None);
+        assert_eq!(Interval::parse_text("00:61:00"), None);
+        assert_eq!(Interval::parse_text("00:00:00.1234567"), None);
+        assert_eq!(Interval::parse_text("1 fortnight"), None);
+        assert_eq!(Interval::parse_text("00:00:00 1 day"), None);
+    }
 }
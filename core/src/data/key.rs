@@ -3,7 +3,7 @@ use {
         data::{Interval, Value},
         result::{Error, Result},
     },
-    chrono::{Datelike, NaiveDate, NaiveDateTime, NaiveTime, Timelike},
+    chrono::{DateTime, Datelike, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime, Timelike},
     rust_decimal::Decimal,
     serde::{Deserialize, Serialize},
     std::{cmp::Ordering, fmt::Debug},
@@ -20,6 +20,9 @@ pub enum KeyError {
 
     #[error("LIST data type cannot be used as Key")]
     ListTypeKeyNotSupported,
+
+    #[error("BIT data type cannot be used as Key")]
+    BitTypeKeyNotSupported,
 }
 
 #[derive(PartialEq, Eq, Hash, Clone, Debug, Serialize, Deserialize)]
@@ -34,6 +37,7 @@ pub enum Key {
     Bytea(Vec<u8>),
     Date(NaiveDate),
     Timestamp(NaiveDateTime),
+    TimestampTz(DateTime<FixedOffset>),
     Time(NaiveTime),
     Interval(Interval),
     Uuid(u128),
@@ -53,6 +57,7 @@ impl PartialOrd for Key {
             (Key::Bytea(l), Key::Bytea(r)) => Some(l.cmp(r)),
             (Key::Date(l), Key::Date(r)) => Some(l.cmp(r)),
             (Key::Timestamp(l), Key::Timestamp(r)) => Some(l.cmp(r)),
+            (Key::TimestampTz(l), Key::TimestampTz(r)) => Some(l.cmp(r)),
             (Key::Time(l), Key::Time(r)) => Some(l.cmp(r)),
             (Key::Interval(l), Key::Interval(r)) => l.partial_cmp(r),
             (Key::Uuid(l), Key::Uuid(r)) => Some(l.cmp(r)),
@@ -79,6 +84,7 @@ impl TryFrom<Value> for Key {
             Bytea(v) => Ok(Key::Bytea(v)),
             Date(v) => Ok(Key::Date(v)),
             Timestamp(v) => Ok(Key::Timestamp(v)),
+            TimestampTz(v) => Ok(Key::TimestampTz(v)),
             Time(v) => Ok(Key::Time(v)),
             Interval(v) => Ok(Key::Interval(v)),
             Uuid(v) => Ok(Key::Uuid(v)),
@@ -87,6 +93,7 @@ impl TryFrom<Value> for Key {
             F64(_) => Err(KeyError::FloatTypeKeyNotSupported.into()),
             Map(_) => Err(KeyError::MapTypeKeyNotSupported.into()),
             List(_) => Err(KeyError::ListTypeKeyNotSupported.into()),
+            Bit(_) => Err(KeyError::BitTypeKeyNotSupported.into()),
         }
     }
 }
@@ -193,6 +200,7 @@ impl Key {
                     .copied()
                     .collect::<Vec<_>>()
             }
+            Key::TimestampTz(datetime) => Key::Timestamp(datetime.naive_utc()).to_cmp_be_bytes(),
             Key::Interval(interval) => {
                 let (month, microsec) = match interval {
                     Interval::Month(month) => (*month, 0),
@@ -15,7 +15,7 @@ pub use {
     key::{Key, KeyError},
     literal::{Literal, LiteralError},
     row::{Row, RowError},
-    schema::{Schema, SchemaIndex, SchemaIndexOrd},
+    schema::{Schema, SchemaFunction, SchemaIndex, SchemaIndexOrd, SchemaView},
     string_ext::{StringExt, StringExtError},
     table::{get_alias, get_name, TableError},
     value::{NumericBinaryOperator, Value, ValueError},
@@ -23,3 +23,6 @@ pub use {
 
 #[cfg(feature = "index")]
 pub use table::get_index;
+
+#[cfg(feature = "metadata")]
+pub use table::InformationSchema;
@@ -26,6 +26,9 @@ pub enum RowError {
 
     #[error("VALUES lists must all be the same length")]
     NumberOfValuesDifferent,
+
+    #[error("cannot write the generated column: {0}")]
+    WriteToGeneratedColumn(String),
 }
 
 #[derive(iter_enum::Iterator)]
@@ -78,7 +81,16 @@ impl Row {
                     .find(|(name, _)| name == &def_name)
                     .map(|(_, value)| value);
 
-                let nullable = column_def.is_nullable();
+                let nullable = column_def.is_nullable() || column_def.is_auto_increment();
+
+                match (value, column_def.get_generated()) {
+                    (Some(_), Some(_)) => {
+                        return Err(RowError::WriteToGeneratedColumn(def_name.to_owned()).into());
+                    }
+                    // computed once the other values are known
+                    (None, Some(_)) => return Ok(Value::Null),
+                    _ => {}
+                }
 
                 match (value, column_def.get_default(), nullable) {
                     (Some(&expr), _, _) | (None, Some(expr), _) => {
@@ -91,7 +103,94 @@ impl Row {
                 }
             })
             .collect::<Result<_>>()
-            .map(Self)
+            .map(Self)?
+            .generate(column_defs)
+    }
+
+    /// Places the values given for `columns`, or for the leading columns if `columns` is empty,
+    /// into the columns of `column_defs`, and fills the others with their defaults.
+    pub fn arrange(self, column_defs: &[ColumnDef], columns: &[String]) -> Result<Self> {
+        let columns = if columns.is_empty() {
+            Columns::All(column_defs.iter().map(|ColumnDef { name, .. }| name))
+        } else {
+            Columns::Specified(columns.iter())
+        };
+
+        let column_name_value_list = columns.zip(self.0).collect::<Vec<(_, _)>>();
+
+        column_defs
+            .iter()
+            .map(|column_def| {
+                let ColumnDef {
+                    name: def_name,
+                    data_type,
+                    ..
+                } = column_def;
+
+                let value = column_name_value_list
+                    .iter()
+                    .find(|(name, _)| name == &def_name)
+                    .map(|(_, value)| value);
+
+                let nullable = column_def.is_nullable() || column_def.is_auto_increment();
+
+                match (value, column_def.get_generated()) {
+                    (Some(_), Some(_)) => {
+                        return Err(RowError::WriteToGeneratedColumn(def_name.to_owned()).into());
+                    }
+                    (None, Some(_)) => return Ok(Value::Null),
+                    _ => {}
+                }
+
+                match (value, column_def.get_default(), nullable) {
+                    (Some(value), _, _) => Ok(value.clone()),
+                    (None, Some(expr), _) => {
+                        evaluate_stateless(None, expr)?.try_into_value(data_type, nullable)
+                    }
+                    (None, None, true) => Ok(Value::Null),
+                    (None, None, false) => {
+                        Err(RowError::LackOfRequiredColumn(def_name.to_owned()).into())
+                    }
+                }
+            })
+            .collect::<Result<_>>()
+            .map(Self)?
+            .generate(column_defs)
+    }
+
+    /// Computes the values of the generated columns from the other values of the row.
+    pub fn generate(self, column_defs: &[ColumnDef]) -> Result<Self> {
+        if column_defs
+            .iter()
+            .all(|column_def| column_def.get_generated().is_none())
+        {
+            return Ok(self);
+        }
+
+        let columns = column_defs
+            .iter()
+            .map(|ColumnDef { name, .. }| name.to_owned())
+            .collect::<Vec<_>>();
+
+        let values = column_defs
+            .iter()
+            .zip(self.0.iter())
+            .map(|(column_def, value)| {
+                let expr = match column_def.get_generated() {
+                    Some(expr) => expr,
+                    None => return Ok(value.clone()),
+                };
+
+                let value = evaluate_stateless(Some((columns.as_slice(), &self)), expr)?
+                    .cast(&column_def.data_type)
+                    .and_then(Value::try_from)?;
+                value.validate_null(column_def.is_nullable())?;
+
+                Ok(value)
+            })
+            .collect::<Result<_>>()?;
+
+        Ok(Self(values))
     }
 
     pub fn validate(&self, column_defs: &[ColumnDef]) -> Result<()> {
@@ -106,7 +205,7 @@ impl Row {
 
         for (value, column_def) in items {
             let ColumnDef { data_type, .. } = column_def;
-            let nullable = column_def.is_nullable();
+            let nullable = column_def.is_nullable() || column_def.is_auto_increment();
 
             value.validate_type(data_type)?;
             value.validate_null(nullable)?;
@@ -1,5 +1,7 @@
 use {
-    crate::ast::{ColumnDef, ColumnOption, ColumnOptionDef, Expr},
+    crate::ast::{
+        Collation, ColumnDef, ColumnOption, ColumnOptionDef, DataType, Expr, FunctionArg, Query,
+    },
     serde::{Deserialize, Serialize},
     std::fmt::Debug,
     strum_macros::Display,
@@ -20,17 +22,60 @@ pub struct SchemaIndex {
     pub order: SchemaIndexOrd,
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
 pub struct Schema {
     pub table_name: String,
     pub column_defs: Vec<ColumnDef>,
     pub indexes: Vec<SchemaIndex>,
+    /// Query of a materialized view, which `REFRESH MATERIALIZED VIEW` runs again
+    pub materialized_view: Option<Query>,
+    /// Definition of a view, which holds no rows of its own
+    pub view: Option<SchemaView>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct SchemaView {
+    pub columns: Vec<String>,
+    pub query: Query,
+}
+
+/// Function created by `CREATE FUNCTION`, which the storage keeps by its uppercase `name`.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct SchemaFunction {
+    pub name: String,
+    pub args: Vec<FunctionArg>,
+    pub return_type: DataType,
+    pub body: Expr,
+}
+
+impl Schema {
+    /// Names of the columns, which a view takes from its definition.
+    pub fn column_names(&self) -> Vec<&str> {
+        match &self.view {
+            Some(SchemaView { columns, .. }) => columns.iter().map(String::as_str).collect(),
+            None => self
+                .column_defs
+                .iter()
+                .map(|ColumnDef { name, .. }| name.as_str())
+                .collect(),
+        }
+    }
 }
 
 pub trait ColumnDefExt {
     fn is_nullable(&self) -> bool;
 
     fn get_default(&self) -> Option<&Expr>;
+
+    fn get_collation(&self) -> Collation;
+
+    /// Whether the column takes the next value of its sequence when an inserted row leaves it
+    /// NULL, which an insert accepts even though the column is not nullable.
+    fn is_auto_increment(&self) -> bool;
+
+    /// Expression a generated column takes its value from, computed from the other columns of
+    /// its row whenever the row is inserted or updated.
+    fn get_generated(&self) -> Option<&Expr>;
 }
 
 impl ColumnDefExt for ColumnDef {
@@ -48,4 +93,29 @@ impl ColumnDefExt for ColumnDef {
                 _ => None,
             })
     }
+
+    fn get_collation(&self) -> Collation {
+        self.options
+            .iter()
+            .find_map(|ColumnOptionDef { option, .. }| match option {
+                ColumnOption::Collate(collation) => Some(*collation),
+                _ => None,
+            })
+            .unwrap_or(Collation::Binary)
+    }
+
+    fn is_auto_increment(&self) -> bool {
+        self.options
+            .iter()
+            .any(|ColumnOptionDef { option, .. }| option == &ColumnOption::AutoIncrement)
+    }
+
+    fn get_generated(&self) -> Option<&Expr> {
+        self.options
+            .iter()
+            .find_map(|ColumnOptionDef { option, .. }| match option {
+                ColumnOption::Generated(expr) => Some(expr),
+                _ => None,
+            })
+    }
 }
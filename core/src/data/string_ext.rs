@@ -4,10 +4,27 @@ use {crate::result::Result, regex::Regex, serde::Serialize, thiserror::Error};
 pub enum StringExtError {
     #[error("unreachable literal unary operation")]
     UnreachablePatternParsing,
+
+    #[error("invalid glob pattern: {0}")]
+    InvalidGlobPattern(String),
 }
 
 pub trait StringExt {
     fn like(&self, pattern: &str, case_sensitive: bool) -> Result<bool>;
+
+    fn glob(&self, pattern: &str) -> Result<bool>;
+
+    /// Number of characters to insert, delete or substitute to turn `self` into `other`.
+    ///
+    /// With `max_distance`, stops as soon as the distance is known to exceed it and returns
+    /// `max_distance + 1`.
+    fn levenshtein(&self, other: &str, max_distance: Option<usize>) -> usize;
+
+    /// American Soundex code of `self`, its first letter followed by three digits.
+    ///
+    /// Only ASCII letters are coded, every other character is skipped, so `"Müller"` has the
+    /// code of `"Mller"` and a string without any ASCII letter has the empty code.
+    fn soundex(&self) -> String;
 }
 
 impl StringExt for String {
@@ -31,4 +48,227 @@ impl StringExt for String {
         .map_err(|_| StringExtError::UnreachablePatternParsing)?
         .is_match(match_string.as_str()))
     }
+
+    fn glob(&self, pattern: &str) -> Result<bool> {
+        let chars = pattern.chars().collect::<Vec<_>>();
+        let mut regex = String::from("(?s)^");
+        let mut i = 0;
+
+        while i < chars.len() {
+            match chars[i] {
+                '*' => regex.push_str(".*"),
+                '?' => regex.push('.'),
+                '[' => match glob_class(&chars[i + 1..]) {
+                    Some((class, len)) => {
+                        regex.push_str(&class);
+                        i += len;
+                    }
+                    None => regex.push_str(r"\["),
+                },
+                c => regex.push_str(&regex::escape(&c.to_string())),
+            }
+
+            i += 1;
+        }
+
+        regex.push('$');
+
+        Ok(Regex::new(&regex)
+            .map_err(|_| StringExtError::InvalidGlobPattern(pattern.to_owned()))?
+            .is_match(self))
+    }
+
+    fn levenshtein(&self, other: &str, max_distance: Option<usize>) -> usize {
+        let left = self.chars().collect::<Vec<_>>();
+        let right = other.chars().collect::<Vec<_>>();
+
+        levenshtein(&left, &right, max_distance)
+    }
+
+    fn soundex(&self) -> String {
+        let mut letters = self
+            .chars()
+            .filter(char::is_ascii_alphabetic)
+            .map(|c| c.to_ascii_uppercase());
+
+        let first = match letters.next() {
+            Some(first) => first,
+            None => return String::new(),
+        };
+
+        let mut code = String::from(first);
+        let mut last = soundex_digit(first);
+
+        for c in letters {
+            let digit = soundex_digit(c);
+
+            if let Some(digit) = digit.filter(|digit| Some(*digit) != last) {
+                code.push(digit);
+
+                if code.len() == 4 {
+                    return code;
+                }
+            }
+
+            // `H` and `W` do not separate letters of the same digit, vowels do
+            if !matches!(c, 'H' | 'W') {
+                last = digit;
+            }
+        }
+
+        format!("{code:0<4}")
+    }
+}
+
+fn levenshtein(left: &[char], right: &[char], max_distance: Option<usize>) -> usize {
+    let max = max_distance.unwrap_or(usize::MAX);
+    if left.len().abs_diff(right.len()) > max {
+        return max + 1;
+    }
+
+    let mut previous = (0..=right.len()).collect::<Vec<_>>();
+    let mut current = vec![0; right.len() + 1];
+
+    for (i, l) in left.iter().enumerate() {
+        current[0] = i + 1;
+
+        for (j, r) in right.iter().enumerate() {
+            let substitution = previous[j] + usize::from(l != r);
+
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+
+        // the distances of the following rows are never smaller than the smallest of this row
+        if current.iter().all(|distance| *distance > max) {
+            return max + 1;
+        }
+
+        std::mem::swap(&mut previous, &mut current);
+    }
+
+    previous[right.len()].min(max.saturating_add(1))
+}
+
+fn soundex_digit(c: char) -> Option<char> {
+    match c {
+        'B' | 'F' | 'P' | 'V' => Some('1'),
+        'C' | 'G' | 'J' | 'K' | 'Q' | 'S' | 'X' | 'Z' => Some('2'),
+        'D' | 'T' => Some('3'),
+        'L' => Some('4'),
+        'M' | 'N' => Some('5'),
+        'R' => Some('6'),
+        _ => None,
+    }
+}
+
+/// Converts the glob character class following `[` into a regex character class.
+/// Returns `None` if the class is not closed, so that `[` is matched literally.
+fn glob_class(chars: &[char]) -> Option<(String, usize)> {
+    let (negated, start) = match chars.first() {
+        Some('!' | '^') => (true, 1),
+        _ => (false, 0),
+    };
+
+    // `]` right after the opening bracket is a member of the class
+    let end = chars.iter().skip(start + 1).position(|c| *c == ']')? + start + 1;
+    let members = &chars[start..end];
+    let escape = |c: char| regex::escape(&c.to_string());
+
+    let mut class = String::from(if negated { "[^" } else { "[" });
+    let mut i = 0;
+
+    while i < members.len() {
+        if i + 2 < members.len() && members[i + 1] == '-' {
+            let (from, to) = (escape(members[i]), escape(members[i + 2]));
+
+            class.push_str(&format!("{from}-{to}"));
+            i += 3;
+        } else {
+            class.push_str(&escape(members[i]));
+            i += 1;
+        }
+    }
+
+    class.push(']');
+
+    Some((class, end + 1))
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::{StringExt, StringExtError},
+        crate::result::Error,
+    };
+
+    #[test]
+    fn glob() {
+        let test = |text: &str, pattern: &str| text.to_owned().glob(pattern).unwrap();
+
+        assert!(test("hello", "hello"));
+        assert!(!test("hello", "Hello"));
+        assert!(test("hello", "h*"));
+        assert!(test("hello", "*llo"));
+        assert!(test("", "*"));
+        assert!(test("hello", "h?llo"));
+        assert!(!test("hllo", "h?llo"));
+        assert!(test("hello", "h[aeiou]llo"));
+        assert!(!test("hyllo", "h[aeiou]llo"));
+        assert!(test("hyllo", "h[!aeiou]llo"));
+        assert!(test("file7.txt", "file[0-9].txt"));
+        assert!(!test("fileA.txt", "file[0-9].txt"));
+        assert!(test("a-b", "a[-]b"));
+        assert!(test("a]b", "a[]]b"));
+        assert!(test("a[b", "a[b"));
+        assert!(test("a.b", "a.b"));
+        assert!(!test("axb", "a.b"));
+        assert!(test("line\nbreak", "line*"));
+
+        assert_eq!(
+            "a".to_owned().glob("[z-a]"),
+            Err(Error::StringExt(StringExtError::InvalidGlobPattern(
+                "[z-a]".to_owned()
+            )))
+        );
+    }
+
+    #[test]
+    fn levenshtein() {
+        let test = |left: &str, right: &str, max_distance: Option<usize>| {
+            left.to_owned().levenshtein(right, max_distance)
+        };
+
+        assert_eq!(test("", "", None), 0);
+        assert_eq!(test("abc", "", None), 3);
+        assert_eq!(test("", "abc", None), 3);
+        assert_eq!(test("kitten", "sitting", None), 3);
+        assert_eq!(test("flaw", "lawn", None), 2);
+        assert_eq!(test("gumbo", "gambol", None), 2);
+        assert_eq!(test("café", "cafe", None), 1);
+        assert_eq!(test("한글", "한국어", None), 2);
+
+        assert_eq!(test("kitten", "sitting", Some(3)), 3);
+        assert_eq!(test("kitten", "sitting", Some(2)), 3);
+        assert_eq!(test("kitten", "sitting", Some(0)), 1);
+        assert_eq!(test("a", "abcdefgh", Some(2)), 3);
+        assert_eq!(test("same", "same", Some(0)), 0);
+    }
+
+    #[test]
+    fn soundex() {
+        let test = |text: &str| text.to_owned().soundex();
+
+        assert_eq!(test("Robert"), "R163");
+        assert_eq!(test("Rupert"), "R163");
+        assert_eq!(test("Rubin"), "R150");
+        assert_eq!(test("Ashcraft"), "A261");
+        assert_eq!(test("Tymczak"), "T522");
+        assert_eq!(test("Pfister"), "P236");
+        assert_eq!(test("Honeyman"), "H555");
+        assert_eq!(test("lee"), "L000");
+        assert_eq!(test("O'Hara"), "O600");
+        assert_eq!(test("Müller"), "M460");
+        assert_eq!(test(""), "");
+        assert_eq!(test("123"), "");
+    }
 }
@@ -31,6 +31,10 @@ pub fn get_alias(table_factor: &TableFactor) -> Result<&String> {
         | TableFactor::Derived {
             alias: TableAlias { name, .. },
             ..
+        }
+        | TableFactor::Series {
+            alias: TableAlias { name, .. },
+            ..
         } => Ok(name),
     }
 }
@@ -41,6 +45,45 @@ use crate::ast::IndexItem;
 pub fn get_index(table_factor: &TableFactor) -> Option<&IndexItem> {
     match table_factor {
         TableFactor::Table { index, .. } => index.as_ref(),
-        TableFactor::Derived { .. } => None,
+        TableFactor::Derived { .. } | TableFactor::Series { .. } => None,
+    }
+}
+
+/// Read-only relations under `information_schema`, resolved from the schema catalog.
+#[cfg(feature = "metadata")]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum InformationSchema {
+    Tables,
+    Columns,
+}
+
+#[cfg(feature = "metadata")]
+impl InformationSchema {
+    pub fn from_name(table_name: &ObjectName) -> Option<Self> {
+        match table_name.0.as_slice() {
+            [schema, table] if schema.to_lowercase() == "information_schema" => {
+                match table.to_lowercase().as_str() {
+                    "tables" => Some(Self::Tables),
+                    "columns" => Some(Self::Columns),
+                    _ => None,
+                }
+            }
+            _ => None,
+        }
+    }
+
+    pub fn columns(&self) -> Vec<String> {
+        let columns: &[&str] = match self {
+            Self::Tables => &["table_name"],
+            Self::Columns => &[
+                "table_name",
+                "column_name",
+                "ordinal_position",
+                "data_type",
+                "is_nullable",
+            ],
+        };
+
+        columns.iter().map(|column| column.to_string()).collect()
     }
 }
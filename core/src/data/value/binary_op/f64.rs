@@ -20,7 +20,7 @@ impl PartialEq<Value> for f64 {
             I32(rhs) => (lhs - (rhs as f64)).abs() < f64::EPSILON,
             I64(rhs) => (lhs - (rhs as f64)).abs() < f64::EPSILON,
             I128(rhs) => (lhs - (rhs as f64)).abs() < f64::EPSILON,
-            F64(rhs) => (lhs - rhs).abs() < f64::EPSILON,
+            F64(rhs) => lhs == rhs || (lhs - rhs).abs() < f64::EPSILON,
             Decimal(rhs) => Decimal::from_f64_retain(lhs)
                 .map(|x| rhs == x)
                 .unwrap_or(false),
@@ -199,6 +199,10 @@ mod tests {
         assert_eq!(base, Decimal(Decimal::from(1)));
 
         assert_ne!(base, Bool(true));
+
+        assert_eq!(f64::INFINITY, F64(f64::INFINITY));
+        assert_ne!(f64::INFINITY, F64(f64::NEG_INFINITY));
+        assert_ne!(f64::NAN, F64(f64::NAN));
     }
 
     #[test]
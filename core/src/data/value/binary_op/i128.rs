@@ -104,7 +104,17 @@ impl TryBinaryOperator for i128 {
                 })
                 .map(I128),
             F64(rhs) => Ok(F64(lhs as f64 + rhs)),
-            Decimal(rhs) => Ok(Decimal(Decimal::from(lhs) + rhs)),
+            Decimal(rhs) => Decimal::from(lhs)
+                .checked_add(rhs)
+                .ok_or_else(|| {
+                    ValueError::BinaryOperationOverflow {
+                        lhs: I128(lhs),
+                        rhs: Decimal(rhs),
+                        operator: NumericBinaryOperator::Add,
+                    }
+                    .into()
+                })
+                .map(Decimal),
             Null => Ok(Null),
             _ => Err(ValueError::NonNumericMathOperation {
                 lhs: I128(lhs),
@@ -175,7 +185,17 @@ impl TryBinaryOperator for i128 {
                 })
                 .map(I128),
             F64(rhs) => Ok(F64(lhs as f64 - rhs)),
-            Decimal(rhs) => Ok(Decimal(Decimal::from(lhs) - rhs)),
+            Decimal(rhs) => Decimal::from(lhs)
+                .checked_sub(rhs)
+                .ok_or_else(|| {
+                    ValueError::BinaryOperationOverflow {
+                        lhs: I128(lhs),
+                        rhs: Decimal(rhs),
+                        operator: NumericBinaryOperator::Subtract,
+                    }
+                    .into()
+                })
+                .map(Decimal),
 
             Null => Ok(Null),
             _ => Err(ValueError::NonNumericMathOperation {
@@ -245,8 +265,28 @@ impl TryBinaryOperator for i128 {
                 })
                 .map(I128),
             F64(rhs) => Ok(F64(lhs as f64 * rhs)),
-            Decimal(rhs) => Ok(Decimal(Decimal::from(lhs) * rhs)),
-            Interval(rhs) => Ok(Interval(lhs * rhs)),
+            Decimal(rhs) => Decimal::from(lhs)
+                .checked_mul(rhs)
+                .ok_or_else(|| {
+                    ValueError::BinaryOperationOverflow {
+                        lhs: I128(lhs),
+                        rhs: Decimal(rhs),
+                        operator: NumericBinaryOperator::Multiply,
+                    }
+                    .into()
+                })
+                .map(Decimal),
+            Interval(rhs) => rhs
+                .checked_mul(lhs)
+                .ok_or_else(|| {
+                    ValueError::BinaryOperationOverflow {
+                        lhs: I128(lhs),
+                        rhs: Interval(rhs),
+                        operator: NumericBinaryOperator::Multiply,
+                    }
+                    .into()
+                })
+                .map(Interval),
             Null => Ok(Null),
             _ => Err(ValueError::NonNumericMathOperation {
                 lhs: I128(lhs),
@@ -317,7 +357,17 @@ impl TryBinaryOperator for i128 {
                 })
                 .map(I128),
             F64(rhs) => Ok(F64(lhs as f64 / rhs)),
-            Decimal(rhs) => Ok(Decimal(Decimal::from(lhs) / rhs)),
+            Decimal(rhs) => Decimal::from(lhs)
+                .checked_div(rhs)
+                .ok_or_else(|| {
+                    ValueError::BinaryOperationOverflow {
+                        lhs: I128(lhs),
+                        rhs: Decimal(rhs),
+                        operator: NumericBinaryOperator::Divide,
+                    }
+                    .into()
+                })
+                .map(Decimal),
             Null => Ok(Null),
             _ => Err(ValueError::NonNumericMathOperation {
                 lhs: I128(lhs),
@@ -387,7 +437,17 @@ impl TryBinaryOperator for i128 {
                 })
                 .map(I128),
             F64(rhs) => Ok(F64(lhs as f64 % rhs)),
-            Decimal(rhs) => Ok(Decimal(Decimal::from(lhs) % rhs)),
+            Decimal(rhs) => Decimal::from(lhs)
+                .checked_rem(rhs)
+                .ok_or_else(|| {
+                    ValueError::BinaryOperationOverflow {
+                        lhs: I128(lhs),
+                        operator: NumericBinaryOperator::Modulo,
+                        rhs: Decimal(rhs),
+                    }
+                    .into()
+                })
+                .map(Decimal),
             Null => Ok(Null),
             _ => Err(ValueError::NonNumericMathOperation {
                 lhs: I128(lhs),
@@ -403,7 +463,7 @@ impl TryBinaryOperator for i128 {
 mod tests {
     use {
         super::{TryBinaryOperator, Value::*},
-        crate::data::{NumericBinaryOperator, ValueError},
+        crate::data::{Interval, NumericBinaryOperator, ValueError},
         rust_decimal::prelude::Decimal,
         std::cmp::Ordering,
     };
@@ -665,6 +725,35 @@ mod tests {
             }
             .into())
         );
+
+        assert_eq!(
+            i128::MAX.try_multiply(&Interval(Interval::Microsecond(i64::MAX))),
+            Err(ValueError::BinaryOperationOverflow {
+                lhs: I128(i128::MAX),
+                rhs: Interval(Interval::Microsecond(i64::MAX)),
+                operator: (NumericBinaryOperator::Multiply)
+            }
+            .into())
+        );
+
+        assert_eq!(
+            i128::MAX.try_divide(&Decimal(Decimal::ZERO)),
+            Err(ValueError::BinaryOperationOverflow {
+                lhs: I128(i128::MAX),
+                rhs: Decimal(Decimal::ZERO),
+                operator: (NumericBinaryOperator::Divide)
+            }
+            .into())
+        );
+        assert_eq!(
+            i128::MAX.try_modulo(&Decimal(Decimal::ZERO)),
+            Err(ValueError::BinaryOperationOverflow {
+                lhs: I128(i128::MAX),
+                rhs: Decimal(Decimal::ZERO),
+                operator: (NumericBinaryOperator::Modulo)
+            }
+            .into())
+        );
     }
 
     #[test]
@@ -105,7 +105,17 @@ impl TryBinaryOperator for i16 {
                 })
                 .map(I128),
             F64(rhs) => Ok(F64(lhs as f64 + rhs)),
-            Decimal(rhs) => Ok(Decimal(Decimal::from(lhs) + rhs)),
+            Decimal(rhs) => Decimal::from(lhs)
+                .checked_add(rhs)
+                .ok_or_else(|| {
+                    ValueError::BinaryOperationOverflow {
+                        lhs: I16(lhs),
+                        rhs: Decimal(rhs),
+                        operator: NumericBinaryOperator::Add,
+                    }
+                    .into()
+                })
+                .map(Decimal),
             Null => Ok(Null),
             _ => Err(ValueError::NonNumericMathOperation {
                 lhs: I16(lhs),
@@ -268,7 +278,17 @@ impl TryBinaryOperator for i16 {
                     .into()
                 })
                 .map(Decimal),
-            Interval(rhs) => Ok(Interval(lhs * rhs)),
+            Interval(rhs) => rhs
+                .checked_mul(lhs as i128)
+                .ok_or_else(|| {
+                    ValueError::BinaryOperationOverflow {
+                        lhs: I16(lhs),
+                        rhs: Interval(rhs),
+                        operator: NumericBinaryOperator::Multiply,
+                    }
+                    .into()
+                })
+                .map(Interval),
             Null => Ok(Null),
             _ => Err(ValueError::NonNumericMathOperation {
                 lhs: I16(lhs),
@@ -446,7 +466,7 @@ impl TryBinaryOperator for i16 {
 mod tests {
     use {
         super::{TryBinaryOperator, Value::*},
-        crate::data::{NumericBinaryOperator, ValueError},
+        crate::data::{Interval, NumericBinaryOperator, ValueError},
         rust_decimal::prelude::Decimal,
         std::cmp::Ordering,
     };
@@ -745,6 +765,16 @@ mod tests {
             }
             .into())
         );
+
+        assert_eq!(
+            i16::MAX.try_multiply(&Interval(Interval::Microsecond(i64::MAX))),
+            Err(ValueError::BinaryOperationOverflow {
+                lhs: I16(i16::MAX),
+                rhs: Interval(Interval::Microsecond(i64::MAX)),
+                operator: (NumericBinaryOperator::Multiply)
+            }
+            .into())
+        );
     }
 
     #[test]
@@ -278,7 +278,17 @@ impl TryBinaryOperator for i64 {
                     .into()
                 })
                 .map(Decimal),
-            Interval(rhs) => Ok(Interval(lhs * rhs)),
+            Interval(rhs) => rhs
+                .checked_mul(lhs as i128)
+                .ok_or_else(|| {
+                    ValueError::BinaryOperationOverflow {
+                        lhs: I64(lhs),
+                        rhs: Interval(rhs),
+                        operator: NumericBinaryOperator::Multiply,
+                    }
+                    .into()
+                })
+                .map(Interval),
             Null => Ok(Null),
             _ => Err(ValueError::NonNumericMathOperation {
                 lhs: I64(lhs),
@@ -456,7 +466,7 @@ impl TryBinaryOperator for i64 {
 mod tests {
     use {
         super::{TryBinaryOperator, Value::*},
-        crate::data::{NumericBinaryOperator, ValueError},
+        crate::data::{Interval, NumericBinaryOperator, ValueError},
         rust_decimal::prelude::Decimal,
         std::cmp::Ordering,
     };
@@ -728,6 +738,16 @@ mod tests {
             }
             .into())
         );
+
+        assert_eq!(
+            i64::MAX.try_multiply(&Interval(Interval::Microsecond(i64::MAX))),
+            Err(ValueError::BinaryOperationOverflow {
+                lhs: I64(i64::MAX),
+                rhs: Interval(Interval::Microsecond(i64::MAX)),
+                operator: (NumericBinaryOperator::Multiply)
+            }
+            .into())
+        );
     }
 
     #[test]
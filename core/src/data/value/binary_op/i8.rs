@@ -103,7 +103,17 @@ impl TryBinaryOperator for i8 {
                 })
                 .map(I128),
             F64(rhs) => Ok(F64(lhs as f64 + rhs)),
-            Decimal(rhs) => Ok(Decimal(Decimal::from(lhs) + rhs)),
+            Decimal(rhs) => Decimal::from(lhs)
+                .checked_add(rhs)
+                .ok_or_else(|| {
+                    ValueError::BinaryOperationOverflow {
+                        lhs: I8(lhs),
+                        rhs: Decimal(rhs),
+                        operator: NumericBinaryOperator::Add,
+                    }
+                    .into()
+                })
+                .map(Decimal),
             Null => Ok(Null),
             _ => Err(ValueError::NonNumericMathOperation {
                 lhs: I8(lhs),
@@ -266,7 +276,17 @@ impl TryBinaryOperator for i8 {
                     .into()
                 })
                 .map(Decimal),
-            Interval(rhs) => Ok(Interval(lhs * rhs)),
+            Interval(rhs) => rhs
+                .checked_mul(lhs as i128)
+                .ok_or_else(|| {
+                    ValueError::BinaryOperationOverflow {
+                        lhs: I8(lhs),
+                        rhs: Interval(rhs),
+                        operator: NumericBinaryOperator::Multiply,
+                    }
+                    .into()
+                })
+                .map(Interval),
             Null => Ok(Null),
             _ => Err(ValueError::NonNumericMathOperation {
                 lhs: I8(lhs),
@@ -444,7 +464,7 @@ impl TryBinaryOperator for i8 {
 mod tests {
     use {
         super::{TryBinaryOperator, Value::*},
-        crate::data::{NumericBinaryOperator, ValueError},
+        crate::data::{Interval, NumericBinaryOperator, ValueError},
         rust_decimal::prelude::Decimal,
         std::cmp::Ordering,
     };
@@ -703,6 +723,16 @@ mod tests {
             }
             .into())
         );
+
+        assert_eq!(
+            i8::MAX.try_multiply(&Interval(Interval::Microsecond(i64::MAX))),
+            Err(ValueError::BinaryOperationOverflow {
+                lhs: I8(i8::MAX),
+                rhs: Interval(Interval::Microsecond(i64::MAX)),
+                operator: (NumericBinaryOperator::Multiply)
+            }
+            .into())
+        );
     }
 
     #[test]
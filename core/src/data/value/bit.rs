@@ -0,0 +1,64 @@
+use {
+    super::ValueError,
+    crate::result::{Error, Result},
+};
+
+pub fn parse_bit(v: &str) -> Result<Vec<bool>> {
+    v.chars()
+        .map(|c| match c {
+            '0' => Ok(false),
+            '1' => Ok(true),
+            _ => Err(Error::Value(ValueError::FailedToParseBitString(
+                v.to_owned(),
+            ))),
+        })
+        .collect()
+}
+
+/// Parses `v` as a value of `BIT(length)`.
+pub fn parse_bit_of_length(v: &str, length: usize) -> Result<Vec<bool>> {
+    let bits = parse_bit(v)?;
+    validate_bit_length(&bits, length)?;
+
+    Ok(bits)
+}
+
+pub fn validate_bit_length(bits: &[bool], length: usize) -> Result<()> {
+    if bits.len() != length {
+        return Err(ValueError::IncompatibleBitLength {
+            length,
+            found: bits.len(),
+        }
+        .into());
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::data::value::ValueError;
+
+    #[test]
+    fn parse_bit() {
+        assert_eq!(super::parse_bit("0101"), Ok(vec![false, true, false, true]));
+        assert_eq!(super::parse_bit(""), Ok(vec![]));
+        assert_eq!(
+            super::parse_bit("0120"),
+            Err(ValueError::FailedToParseBitString("0120".to_owned()).into())
+        );
+    }
+
+    #[test]
+    fn parse_bit_of_length() {
+        assert_eq!(super::parse_bit_of_length("01", 2), Ok(vec![false, true]));
+        assert_eq!(
+            super::parse_bit_of_length("011", 2),
+            Err(ValueError::IncompatibleBitLength {
+                length: 2,
+                found: 3
+            }
+            .into())
+        );
+    }
+}
@@ -1,13 +1,13 @@
 use {
     super::{
-        date::{parse_date, parse_time, parse_timestamp},
+        date::{parse_date, parse_time, parse_timestamp, parse_timestamptz, utc_timestamptz},
         Value, ValueError,
     },
     crate::{
         data::Interval,
         result::{Error, Result},
     },
-    chrono::{NaiveDate, NaiveDateTime, NaiveTime},
+    chrono::{DateTime, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime},
     rust_decimal::prelude::{Decimal, FromPrimitive, FromStr, ToPrimitive},
     uuid::Uuid,
 };
@@ -17,6 +17,7 @@ impl From<&Value> for String {
         match v {
             Value::Str(value) => value.to_string(),
             Value::Bytea(value) => hex::encode(value),
+            Value::Bit(value) => value.iter().map(|v| if *v { '1' } else { '0' }).collect(),
             Value::Bool(value) => (if *value { "TRUE" } else { "FALSE" }).to_string(),
             Value::I8(value) => value.to_string(),
             Value::I16(value) => value.to_string(),
@@ -26,8 +27,9 @@ impl From<&Value> for String {
             Value::F64(value) => value.to_string(),
             Value::Date(value) => value.to_string(),
             Value::Timestamp(value) => value.to_string(),
+            Value::TimestampTz(value) => value.to_string(),
             Value::Time(value) => value.to_string(),
-            Value::Interval(value) => String::from(value),
+            Value::Interval(value) => value.to_string(),
             Value::Uuid(value) => Uuid::from_u128(*value).to_string(),
             Value::Map(_) => "[MAP]".to_owned(),
             Value::List(_) => "[LIST]".to_owned(),
@@ -102,12 +104,14 @@ impl TryFrom<&Value> for bool {
             }
             Value::Date(_)
             | Value::Timestamp(_)
+            | Value::TimestampTz(_)
             | Value::Time(_)
             | Value::Interval(_)
             | Value::Uuid(_)
             | Value::Map(_)
             | Value::List(_)
             | Value::Bytea(_)
+            | Value::Bit(_)
             | Value::Null => return Err(ValueError::ImpossibleCast.into()),
         })
     }
@@ -137,12 +141,14 @@ impl TryFrom<&Value> for i8 {
             Value::Decimal(value) => value.to_i8().ok_or(ValueError::ImpossibleCast)?,
             Value::Date(_)
             | Value::Timestamp(_)
+            | Value::TimestampTz(_)
             | Value::Time(_)
             | Value::Interval(_)
             | Value::Uuid(_)
             | Value::Map(_)
             | Value::List(_)
             | Value::Bytea(_)
+            | Value::Bit(_)
             | Value::Null => return Err(ValueError::ImpossibleCast.into()),
         })
     }
@@ -172,12 +178,14 @@ impl TryFrom<&Value> for i16 {
             Value::Decimal(value) => value.to_i16().ok_or(ValueError::ImpossibleCast)?,
             Value::Date(_)
             | Value::Timestamp(_)
+            | Value::TimestampTz(_)
             | Value::Time(_)
             | Value::Interval(_)
             | Value::Uuid(_)
             | Value::Map(_)
             | Value::List(_)
             | Value::Bytea(_)
+            | Value::Bit(_)
             | Value::Null => return Err(ValueError::ImpossibleCast.into()),
         })
     }
@@ -207,12 +215,14 @@ impl TryFrom<&Value> for i32 {
             Value::Decimal(value) => value.to_i32().ok_or(ValueError::ImpossibleCast)?,
             Value::Date(_)
             | Value::Timestamp(_)
+            | Value::TimestampTz(_)
             | Value::Time(_)
             | Value::Interval(_)
             | Value::Uuid(_)
             | Value::Map(_)
             | Value::List(_)
             | Value::Bytea(_)
+            | Value::Bit(_)
             | Value::Null => return Err(ValueError::ImpossibleCast.into()),
         })
     }
@@ -242,12 +252,14 @@ impl TryFrom<&Value> for i64 {
             Value::Decimal(value) => value.to_i64().ok_or(ValueError::ImpossibleCast)?,
             Value::Date(_)
             | Value::Timestamp(_)
+            | Value::TimestampTz(_)
             | Value::Time(_)
             | Value::Interval(_)
             | Value::Uuid(_)
             | Value::Map(_)
             | Value::List(_)
             | Value::Bytea(_)
+            | Value::Bit(_)
             | Value::Null => return Err(ValueError::ImpossibleCast.into()),
         })
     }
@@ -277,12 +289,14 @@ impl TryFrom<&Value> for i128 {
             Value::Decimal(value) => value.to_i128().ok_or(ValueError::ImpossibleCast)?,
             Value::Date(_)
             | Value::Timestamp(_)
+            | Value::TimestampTz(_)
             | Value::Time(_)
             | Value::Interval(_)
             | Value::Uuid(_)
             | Value::Map(_)
             | Value::List(_)
             | Value::Bytea(_)
+            | Value::Bit(_)
             | Value::Null => return Err(ValueError::ImpossibleCast.into()),
         })
     }
@@ -312,12 +326,14 @@ impl TryFrom<&Value> for f64 {
             Value::Decimal(value) => value.to_f64().ok_or(ValueError::ImpossibleCast)?,
             Value::Date(_)
             | Value::Timestamp(_)
+            | Value::TimestampTz(_)
             | Value::Time(_)
             | Value::Interval(_)
             | Value::Uuid(_)
             | Value::Map(_)
             | Value::List(_)
             | Value::Bytea(_)
+            | Value::Bit(_)
             | Value::Null => return Err(ValueError::ImpossibleCast.into()),
         })
     }
@@ -347,12 +363,14 @@ impl TryFrom<&Value> for Decimal {
             Value::Decimal(value) => *value,
             Value::Date(_)
             | Value::Timestamp(_)
+            | Value::TimestampTz(_)
             | Value::Time(_)
             | Value::Interval(_)
             | Value::Uuid(_)
             | Value::Map(_)
             | Value::List(_)
             | Value::Bytea(_)
+            | Value::Bit(_)
             | Value::Null => return Err(ValueError::ImpossibleCast.into()),
         })
     }
@@ -380,6 +398,7 @@ impl TryFrom<&Value> for NaiveDate {
         Ok(match v {
             Value::Date(value) => *value,
             Value::Timestamp(value) => value.date(),
+            Value::TimestampTz(value) => value.naive_utc().date(),
             Value::Str(value) => parse_date(value).ok_or(ValueError::ImpossibleCast)?,
             _ => return Err(ValueError::ImpossibleCast.into()),
         })
@@ -406,6 +425,21 @@ impl TryFrom<&Value> for NaiveDateTime {
             Value::Date(value) => value.and_hms(0, 0, 0),
             Value::Str(value) => parse_timestamp(value).ok_or(ValueError::ImpossibleCast)?,
             Value::Timestamp(value) => *value,
+            Value::TimestampTz(value) => value.naive_utc(),
+            _ => return Err(ValueError::ImpossibleCast.into()),
+        })
+    }
+}
+
+impl TryFrom<&Value> for DateTime<FixedOffset> {
+    type Error = Error;
+
+    fn try_from(v: &Value) -> Result<DateTime<FixedOffset>> {
+        Ok(match v {
+            Value::Date(value) => utc_timestamptz(&value.and_hms(0, 0, 0)),
+            Value::Str(value) => parse_timestamptz(value).ok_or(ValueError::ImpossibleCast)?,
+            Value::Timestamp(value) => utc_timestamptz(value),
+            Value::TimestampTz(value) => *value,
             _ => return Err(ValueError::ImpossibleCast.into()),
         })
     }
@@ -470,7 +504,7 @@ mod tests {
             "2021-11-20 10:00:00"
         );
         test!(Value::Time(time(10, 0, 0, 0)), "10:00:00");
-        test!(Value::Interval(I::Month(1)), String::from(I::Month(1)));
+        test!(Value::Interval(I::Month(1)), "1 month");
         test!(
             Value::Uuid(195965723427462096757863453463987888808),
             "936da01f-9abd-4d9d-80c7-02af85c822a8"
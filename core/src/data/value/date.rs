@@ -1,6 +1,21 @@
-use chrono::{offset::Utc, DateTime, NaiveDate, NaiveDateTime, NaiveTime};
+use {
+    crate::executor::{current_session, Session},
+    chrono::{offset::Utc, DateTime, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime, TimeZone},
+};
+
+/// Parses with the first of the `formats` configured in the session that matches, or returns
+/// `None` to fall back to the built-in formats when none are configured or no session runs.
+fn parse_configured<T>(formats: &[String], parse: impl Fn(&str) -> Option<T>) -> Option<Option<T>> {
+    (!formats.is_empty()).then(|| formats.iter().find_map(|form| parse(form)))
+}
 
 pub fn parse_date(v: &str) -> Option<NaiveDate> {
+    let formats = current_session(Session::date_formats).unwrap_or_default();
+
+    if let Some(date) = parse_configured(&formats, |form| NaiveDate::parse_from_str(v, form).ok()) {
+        return date;
+    }
+
     if let Ok(v) = v.parse::<NaiveDate>() {
         return Some(v);
     }
@@ -50,6 +65,16 @@ pub fn parse_time(v: &str) -> Option<NaiveTime> {
 }
 
 pub fn parse_timestamp(v: &str) -> Option<NaiveDateTime> {
+    let formats = current_session(Session::timestamp_formats).unwrap_or_default();
+
+    if let Some(timestamp) = parse_configured(&formats, |form| {
+        NaiveDateTime::parse_from_str(v, form)
+            .or_else(|_| NaiveDate::parse_from_str(v, form).map(|date| date.and_hms(0, 0, 0)))
+            .ok()
+    }) {
+        return timestamp;
+    }
+
     if let Ok(v) = v.parse::<DateTime<Utc>>() {
         return Some(v.naive_utc());
     } else if let Ok(v) = v.parse::<NaiveDateTime>() {
@@ -68,3 +93,16 @@ pub fn parse_timestamp(v: &str) -> Option<NaiveDateTime> {
 
     None
 }
+
+/// Parses a TIMESTAMPTZ with its offset, e.g. `2024-01-02 03:04:05+09:00`. A string without an
+/// offset is taken as UTC.
+pub fn parse_timestamptz(v: &str) -> Option<DateTime<FixedOffset>> {
+    v.parse::<DateTime<FixedOffset>>()
+        .ok()
+        .or_else(|| parse_timestamp(v).map(|v| utc_timestamptz(&v)))
+}
+
+/// TIMESTAMPTZ of the UTC `timestamp`, with a zero offset.
+pub fn utc_timestamptz(timestamp: &NaiveDateTime) -> DateTime<FixedOffset> {
+    Utc.from_utc_datetime(timestamp).into()
+}
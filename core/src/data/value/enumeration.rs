@@ -0,0 +1,36 @@
+use {
+    super::ValueError,
+    crate::result::{Error, Result},
+};
+
+/// Checks that `v` is one of the `allowed` values of an `ENUM` type.
+pub fn validate_enum(allowed: &[String], v: &str) -> Result<()> {
+    if allowed.iter().any(|allowed| allowed == v) {
+        return Ok(());
+    }
+
+    Err(Error::Value(ValueError::InvalidEnumValue {
+        value: v.to_owned(),
+        allowed: allowed.to_vec(),
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::data::value::ValueError;
+
+    #[test]
+    fn validate_enum() {
+        let allowed = vec!["active".to_owned(), "inactive".to_owned()];
+
+        assert_eq!(super::validate_enum(&allowed, "active"), Ok(()));
+        assert_eq!(
+            super::validate_enum(&allowed, "Active"),
+            Err(ValueError::InvalidEnumValue {
+                value: "Active".to_owned(),
+                allowed,
+            }
+            .into())
+        );
+    }
+}
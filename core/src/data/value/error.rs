@@ -161,6 +161,31 @@ pub enum ValueError {
 
     #[error("non numeric value in sqrt {0:?}")]
     SqrtOnNonNumeric(Value),
+
+    #[error("failed to parse bit string: {0}")]
+    FailedToParseBitString(String),
+
+    #[error("bit string of length {found} does not fit BIT({length})")]
+    IncompatibleBitLength { length: usize, found: usize },
+
+    #[error("invalid enum value: {value:?}, allowed values: {allowed:?}")]
+    InvalidEnumValue { value: String, allowed: Vec<String> },
+
+    #[error("non-bitwise values {lhs:?} {operator} {rhs:?}")]
+    NonBitwiseOperation {
+        lhs: Value,
+        rhs: Value,
+        operator: NumericBinaryOperator,
+    },
+
+    #[error("bitwise operation on bit strings of different lengths: {lhs} and {rhs}")]
+    BitLengthMismatch { lhs: usize, rhs: usize },
+
+    #[error("unary bitwise not operation for unsupported value: {0:?}")]
+    BitwiseNotOnUnsupportedValue(Value),
+
+    #[error("shift amount {amount} is out of range for {bits}-bit integer")]
+    BitwiseShiftOutOfRange { amount: i128, bits: u32 },
 }
 
 #[derive(Debug, PartialEq, Eq, Serialize, Display)]
@@ -175,4 +200,14 @@ pub enum NumericBinaryOperator {
     Divide,
     #[strum(to_string = "%")]
     Modulo,
+    #[strum(to_string = "&")]
+    BitwiseAnd,
+    #[strum(to_string = "|")]
+    BitwiseOr,
+    #[strum(to_string = "^")]
+    BitwiseXor,
+    #[strum(to_string = "<<")]
+    BitwiseShiftLeft,
+    #[strum(to_string = ">>")]
+    BitwiseShiftRight,
 }
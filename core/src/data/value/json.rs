@@ -49,8 +49,10 @@ impl TryFrom<Value> for JsonValue {
                 .map_err(|_| ValueError::UnreachableJsonNumberParseFailure(v.to_string()).into()),
             Value::Str(v) => Ok(v.into()),
             Value::Bytea(v) => Ok(hex::encode(v).into()),
+            Value::Bit(_) => Ok(String::from(&value).into()),
             Value::Date(v) => Ok(v.to_string().into()),
             Value::Timestamp(v) => Ok(DateTime::<Utc>::from_utc(v, Utc).to_string().into()),
+            Value::TimestampTz(v) => Ok(v.to_rfc3339().into()),
             Value::Time(v) => Ok(v.to_string().into()),
             Value::Interval(v) => Ok(String::from(&v).into()),
             Value::Uuid(v) => Ok(Uuid::from_u128(v).to_hyphenated().to_string().into()),
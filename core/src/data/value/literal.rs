@@ -1,6 +1,8 @@
 use {
     super::{
-        date::{parse_date, parse_time, parse_timestamp},
+        bit::{parse_bit, parse_bit_of_length},
+        date::{parse_date, parse_time, parse_timestamp, parse_timestamptz},
+        enumeration::validate_enum,
         error::ValueError,
         Value,
     },
@@ -9,7 +11,6 @@ use {
         data::{value::uuid::parse_uuid, BigDecimalExt, Interval, Literal},
         result::{Error, Result},
     },
-    chrono::NaiveDate,
     rust_decimal::Decimal,
     std::cmp::Ordering,
 };
@@ -26,14 +27,19 @@ impl PartialEq<Literal<'_>> for Value {
             (Value::F64(l), Literal::Number(r)) => r.to_f64().map(|r| *l == r).unwrap_or(false),
             (Value::Str(l), Literal::Text(r)) => l == r.as_ref(),
             (Value::Bytea(l), Literal::Bytea(r)) => l == r,
-            (Value::Date(l), Literal::Text(r)) => match r.parse::<NaiveDate>() {
-                Ok(r) => l == &r,
-                Err(_) => false,
+            (Value::Bit(l), Literal::Text(r)) => parse_bit(r).map(|r| l == &r).unwrap_or(false),
+            (Value::Date(l), Literal::Text(r)) => match parse_date(r) {
+                Some(r) => l == &r,
+                None => false,
             },
             (Value::Timestamp(l), Literal::Text(r)) => match parse_timestamp(r) {
                 Some(r) => l == &r,
                 None => false,
             },
+            (Value::TimestampTz(l), Literal::Text(r)) => match parse_timestamptz(r) {
+                Some(r) => l == &r,
+                None => false,
+            },
             (Value::Time(l), Literal::Text(r)) => match parse_time(r) {
                 Some(r) => l == &r,
                 None => false,
@@ -67,14 +73,20 @@ impl PartialOrd<Literal<'_>> for Value {
                 r.to_f64().map(|r| l.partial_cmp(&r)).unwrap_or(None)
             }
             (Value::Str(l), Literal::Text(r)) => Some(l.cmp(r.as_ref())),
-            (Value::Date(l), Literal::Text(r)) => match r.parse::<NaiveDate>() {
-                Ok(r) => l.partial_cmp(&r),
-                Err(_) => None,
+            (Value::Bytea(l), Literal::Bytea(r)) => Some(l.cmp(r)),
+            (Value::Bit(l), Literal::Text(r)) => parse_bit(r).map(|r| l.cmp(&r)).ok(),
+            (Value::Date(l), Literal::Text(r)) => match parse_date(r) {
+                Some(r) => l.partial_cmp(&r),
+                None => None,
             },
             (Value::Timestamp(l), Literal::Text(r)) => match parse_timestamp(r) {
                 Some(r) => l.partial_cmp(&r),
                 None => None,
             },
+            (Value::TimestampTz(l), Literal::Text(r)) => match parse_timestamptz(r) {
+                Some(r) => l.partial_cmp(&r),
+                None => None,
+            },
             (Value::Time(l), Literal::Text(r)) => match parse_time(r) {
                 Some(r) => l.partial_cmp(&r),
                 None => None,
@@ -147,23 +159,37 @@ impl Value {
                 .map(Value::F64)
                 .ok_or_else(|| ValueError::UnreachableNumberParsing.into()),
             (DataType::Text, Literal::Text(v)) => Ok(Value::Str(v.to_string())),
+            (DataType::Enum(allowed), Literal::Text(v)) => {
+                validate_enum(allowed, v)?;
+
+                Ok(Value::Str(v.to_string()))
+            }
             (DataType::Bytea, Literal::Bytea(v)) => Ok(Value::Bytea(v.to_vec())),
-            (DataType::Bytea, Literal::Text(v)) => hex::decode(v.as_ref())
-                .map(Value::Bytea)
-                .map_err(|_| ValueError::FailedToParseHexString(v.to_string()).into()),
-            (DataType::Date, Literal::Text(v)) => v
-                .parse::<NaiveDate>()
+            (DataType::Bytea, Literal::Text(v)) => {
+                let v = v.as_ref();
+
+                hex::decode(v.strip_prefix("\\x").unwrap_or(v))
+                    .map(Value::Bytea)
+                    .map_err(|_| ValueError::FailedToParseHexString(v.to_owned()).into())
+            }
+            (DataType::Date, Literal::Text(v)) => parse_date(v)
                 .map(Value::Date)
-                .map_err(|_| ValueError::FailedToParseDate(v.to_string()).into()),
+                .ok_or_else(|| ValueError::FailedToParseDate(v.to_string()).into()),
             (DataType::Timestamp, Literal::Text(v)) => parse_timestamp(v)
                 .map(Value::Timestamp)
                 .ok_or_else(|| ValueError::FailedToParseTimestamp(v.to_string()).into()),
+            (DataType::TimestampTz, Literal::Text(v)) => parse_timestamptz(v)
+                .map(Value::TimestampTz)
+                .ok_or_else(|| ValueError::FailedToParseTimestamp(v.to_string()).into()),
             (DataType::Time, Literal::Text(v)) => parse_time(v)
                 .map(Value::Time)
                 .ok_or_else(|| ValueError::FailedToParseTime(v.to_string()).into()),
             (DataType::Interval, Literal::Interval(v)) => Ok(Value::Interval(*v)),
             (DataType::Uuid, Literal::Text(v)) => parse_uuid(v).map(Value::Uuid),
             (DataType::Uuid, Literal::Bytea(v)) => parse_uuid(&hex::encode(v)).map(Value::Uuid),
+            (DataType::Bit(length), Literal::Text(v)) => {
+                parse_bit_of_length(v, *length).map(Value::Bit)
+            }
             (DataType::Map, Literal::Text(v)) => Value::parse_json_map(v),
             (DataType::List, Literal::Text(v)) => Value::parse_json_list(v),
             (DataType::Decimal, Literal::Number(v)) => v
@@ -306,6 +332,14 @@ impl Value {
                 Interval::try_from(v.as_str()).map(Value::Interval)
             }
             (DataType::Uuid, Literal::Text(v)) => parse_uuid(v).map(Value::Uuid),
+            (DataType::Bit(length), Literal::Text(v)) => {
+                parse_bit_of_length(v, *length).map(Value::Bit)
+            }
+            (DataType::Enum(allowed), Literal::Text(v)) => {
+                validate_enum(allowed, v)?;
+
+                Ok(Value::Str(v.to_string()))
+            }
             (DataType::Boolean, Literal::Null)
             | (DataType::Int8, Literal::Null)
             | (DataType::Int16, Literal::Null)
@@ -314,7 +348,8 @@ impl Value {
             | (DataType::Int128, Literal::Null)
             | (DataType::Float, Literal::Null)
             | (DataType::Decimal, Literal::Null)
-            | (DataType::Text, Literal::Null) => Ok(Value::Null),
+            | (DataType::Text, Literal::Null)
+            | (DataType::Enum(_), Literal::Null) => Ok(Value::Null),
             (DataType::Date, Literal::Text(v)) => parse_date(v)
                 .map(Value::Date)
                 .ok_or_else(|| ValueError::LiteralCastToDateFailed(v.to_string()).into()),
@@ -324,6 +359,9 @@ impl Value {
             (DataType::Timestamp, Literal::Text(v)) => parse_timestamp(v)
                 .map(Value::Timestamp)
                 .ok_or_else(|| ValueError::LiteralCastToTimestampFailed(v.to_string()).into()),
+            (DataType::TimestampTz, Literal::Text(v)) => parse_timestamptz(v)
+                .map(Value::TimestampTz)
+                .ok_or_else(|| ValueError::LiteralCastToTimestampFailed(v.to_string()).into()),
             _ => Err(ValueError::UnimplementedLiteralCast {
                 data_type: data_type.clone(),
                 literal: format!("{:?}", literal),
@@ -504,6 +542,11 @@ mod tests {
             Value::Bytea(bytea("1234"))
         );
         test!(DataType::Bytea, text!("1234"), Value::Bytea(bytea("1234")));
+        test!(
+            DataType::Bytea,
+            text!("\\x0aFF"),
+            Value::Bytea(bytea("0aff"))
+        );
         assert_eq!(
             Value::try_from_literal(&DataType::Bytea, &text!("123")),
             Err(ValueError::FailedToParseHexString("123".to_owned()).into())
@@ -2,16 +2,20 @@ use {
     super::{Interval, Key, StringExt},
     crate::{ast::DataType, ast::DateTimeField, result::Result},
     binary_op::TryBinaryOperator,
-    chrono::{Datelike, NaiveDate, NaiveDateTime, NaiveTime, Timelike},
+    bit::{parse_bit_of_length, validate_bit_length},
+    chrono::{DateTime, Datelike, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime, Timelike},
     core::ops::Sub,
+    enumeration::validate_enum,
     rust_decimal::Decimal,
     serde::{Deserialize, Serialize},
     std::{cmp::Ordering, collections::HashMap, fmt::Debug},
 };
 
 mod binary_op;
+mod bit;
 mod convert;
 mod date;
+mod enumeration;
 mod error;
 mod json;
 mod literal;
@@ -21,6 +25,8 @@ mod uuid;
 pub use error::NumericBinaryOperator;
 pub use error::ValueError;
 
+/// Serialized as an externally tagged enum, e.g. `{"I64":1}`, `{"Decimal":"3.14"}` and `"Null"`,
+/// so every value keeps its exact type when it is deserialized back.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum Value {
     Bool(bool),
@@ -33,8 +39,11 @@ pub enum Value {
     Decimal(Decimal),
     Str(String),
     Bytea(Vec<u8>),
+    Bit(Vec<bool>),
     Date(NaiveDate),
     Timestamp(NaiveDateTime),
+    /// Instant with the offset it was given in, compared and grouped by the instant.
+    TimestampTz(DateTime<FixedOffset>),
     Time(NaiveTime),
     Interval(Interval),
     Uuid(u128),
@@ -56,10 +65,12 @@ impl PartialEq<Value> for Value {
             (Value::Bool(l), Value::Bool(r)) => l == r,
             (Value::Str(l), Value::Str(r)) => l == r,
             (Value::Bytea(l), Value::Bytea(r)) => l == r,
+            (Value::Bit(l), Value::Bit(r)) => l == r,
             (Value::Date(l), Value::Date(r)) => l == r,
             (Value::Date(l), Value::Timestamp(r)) => &l.and_hms(0, 0, 0) == r,
             (Value::Timestamp(l), Value::Date(r)) => l == &r.and_hms(0, 0, 0),
             (Value::Timestamp(l), Value::Timestamp(r)) => l == r,
+            (Value::TimestampTz(l), Value::TimestampTz(r)) => l == r,
             (Value::Time(l), Value::Time(r)) => l == r,
             (Value::Interval(l), Value::Interval(r)) => l == r,
             (Value::Uuid(l), Value::Uuid(r)) => l == r,
@@ -83,10 +94,12 @@ impl PartialOrd<Value> for Value {
             (Value::Bool(l), Value::Bool(r)) => Some(l.cmp(r)),
             (Value::Str(l), Value::Str(r)) => Some(l.cmp(r)),
             (Value::Bytea(l), Value::Bytea(r)) => Some(l.cmp(r)),
+            (Value::Bit(l), Value::Bit(r)) => Some(l.cmp(r)),
             (Value::Date(l), Value::Date(r)) => Some(l.cmp(r)),
             (Value::Date(l), Value::Timestamp(r)) => Some(l.and_hms(0, 0, 0).cmp(r)),
             (Value::Timestamp(l), Value::Date(r)) => Some(l.cmp(&r.and_hms(0, 0, 0))),
             (Value::Timestamp(l), Value::Timestamp(r)) => Some(l.cmp(r)),
+            (Value::TimestampTz(l), Value::TimestampTz(r)) => Some(l.cmp(r)),
             (Value::Time(l), Value::Time(r)) => Some(l.cmp(r)),
             (Value::Interval(l), Value::Interval(r)) => l.partial_cmp(r),
             (Value::Uuid(l), Value::Uuid(r)) => Some(l.cmp(r)),
@@ -109,6 +122,10 @@ impl Value {
         }
     }
 
+    pub fn is_nan(&self) -> bool {
+        matches!(self, Value::F64(v) if v.is_nan())
+    }
+
     pub fn get_type(&self) -> Option<DataType> {
         match self {
             Value::I8(_) => Some(DataType::Int8),
@@ -121,8 +138,10 @@ impl Value {
             Value::Bool(_) => Some(DataType::Boolean),
             Value::Str(_) => Some(DataType::Text),
             Value::Bytea(_) => Some(DataType::Bytea),
+            Value::Bit(v) => Some(DataType::Bit(v.len())),
             Value::Date(_) => Some(DataType::Date),
             Value::Timestamp(_) => Some(DataType::Timestamp),
+            Value::TimestampTz(_) => Some(DataType::TimestampTz),
             Value::Time(_) => Some(DataType::Time),
             Value::Interval(_) => Some(DataType::Interval),
             Value::Uuid(_) => Some(DataType::Uuid),
@@ -142,10 +161,19 @@ impl Value {
             Value::F64(_) => matches!(data_type, DataType::Float),
             Value::Decimal(_) => matches!(data_type, DataType::Decimal),
             Value::Bool(_) => matches!(data_type, DataType::Boolean),
-            Value::Str(_) => matches!(data_type, DataType::Text),
+            Value::Str(v) => match data_type {
+                DataType::Text => true,
+                DataType::Enum(allowed) => return validate_enum(allowed, v),
+                _ => false,
+            },
             Value::Bytea(_) => matches!(data_type, DataType::Bytea),
+            Value::Bit(v) => match data_type {
+                DataType::Bit(length) => return validate_bit_length(v, *length),
+                _ => false,
+            },
             Value::Date(_) => matches!(data_type, DataType::Date),
             Value::Timestamp(_) => matches!(data_type, DataType::Timestamp),
+            Value::TimestampTz(_) => matches!(data_type, DataType::TimestampTz),
             Value::Time(_) => matches!(data_type, DataType::Time),
             Value::Interval(_) => matches!(data_type, DataType::Interval),
             Value::Uuid(_) => matches!(data_type, DataType::Uuid),
@@ -187,9 +215,13 @@ impl Value {
             | (DataType::Bytea, Value::Bytea(_))
             | (DataType::Date, Value::Date(_))
             | (DataType::Timestamp, Value::Timestamp(_))
+            | (DataType::TimestampTz, Value::TimestampTz(_))
             | (DataType::Time, Value::Time(_))
             | (DataType::Interval, Value::Interval(_))
             | (DataType::Uuid, Value::Uuid(_)) => Ok(self.clone()),
+            (DataType::Bit(length), Value::Bit(v)) => {
+                validate_bit_length(v, *length).map(|()| self.clone())
+            }
             (_, Value::Null) => Ok(Value::Null),
 
             (DataType::Boolean, value) => value.try_into().map(Value::Bool),
@@ -201,11 +233,21 @@ impl Value {
             (DataType::Float, value) => value.try_into().map(Value::F64),
             (DataType::Decimal, value) => value.try_into().map(Value::Decimal),
             (DataType::Text, value) => Ok(Value::Str(value.into())),
+            (DataType::Enum(allowed), value) => {
+                let v = String::from(value);
+                validate_enum(allowed, &v)?;
+
+                Ok(Value::Str(v))
+            }
             (DataType::Date, value) => value.try_into().map(Value::Date),
             (DataType::Time, value) => value.try_into().map(Value::Time),
             (DataType::Timestamp, value) => value.try_into().map(Value::Timestamp),
+            (DataType::TimestampTz, value) => value.try_into().map(Value::TimestampTz),
             (DataType::Interval, value) => value.try_into().map(Value::Interval),
             (DataType::Uuid, value) => value.try_into().map(Value::Uuid),
+            (DataType::Bit(length), Value::Str(value)) => {
+                parse_bit_of_length(value, *length).map(Value::Bit)
+            }
 
             _ => Err(ValueError::UnimplementedCast.into()),
         }
@@ -232,6 +274,7 @@ impl Value {
             (Date(a), Time(b)) => Ok(Timestamp(NaiveDateTime::new(*a, *b))),
             (Date(a), Interval(b)) => b.add_date(a).map(Timestamp),
             (Timestamp(a), Interval(b)) => b.add_timestamp(a).map(Timestamp),
+            (TimestampTz(a), Interval(b)) => b.add_timestamptz(a).map(TimestampTz),
             (Time(a), Interval(b)) => b.add_time(a).map(Time),
             (Interval(a), Interval(b)) => a.add(b).map(Interval),
             (Null, I8(_))
@@ -243,9 +286,11 @@ impl Value {
             | (Null, Decimal(_))
             | (Null, Date(_))
             | (Null, Timestamp(_))
+            | (Null, TimestampTz(_))
             | (Null, Interval(_))
             | (Date(_), Null)
             | (Timestamp(_), Null)
+            | (TimestampTz(_), Null)
             | (Time(_), Null)
             | (Interval(_), Null)
             | (Null, Null) => Ok(Null),
@@ -273,6 +318,7 @@ impl Value {
             (Date(a), Date(b)) => Ok(Interval(I::days((*a - *b).num_days() as i32))),
             (Date(a), Interval(b)) => b.subtract_from_date(a).map(Timestamp),
             (Timestamp(a), Interval(b)) => b.subtract_from_timestamp(a).map(Timestamp),
+            (TimestampTz(a), Interval(b)) => b.subtract_from_timestamptz(a).map(TimestampTz),
             (Timestamp(a), Timestamp(b)) => a
                 .sub(*b)
                 .num_microseconds()
@@ -280,6 +326,13 @@ impl Value {
                     ValueError::UnreachableIntegerOverflow(format!("{:?} - {:?}", a, b)).into()
                 })
                 .map(|v| Interval(I::microseconds(v))),
+            (TimestampTz(a), TimestampTz(b)) => a
+                .sub(*b)
+                .num_microseconds()
+                .ok_or_else(|| {
+                    ValueError::UnreachableIntegerOverflow(format!("{:?} - {:?}", a, b)).into()
+                })
+                .map(|v| Interval(I::microseconds(v))),
             (Time(a), Time(b)) => a
                 .sub(*b)
                 .num_microseconds()
@@ -298,10 +351,12 @@ impl Value {
             | (Null, Decimal(_))
             | (Null, Date(_))
             | (Null, Timestamp(_))
+            | (Null, TimestampTz(_))
             | (Null, Time(_))
             | (Null, Interval(_))
             | (Date(_), Null)
             | (Timestamp(_), Null)
+            | (TimestampTz(_), Null)
             | (Time(_), Null)
             | (Interval(_), Null)
             | (Null, Null) => Ok(Null),
@@ -325,11 +380,17 @@ impl Value {
             (I128(a), _) => a.try_multiply(other),
             (F64(a), _) => a.try_multiply(other),
             (Decimal(a), _) => a.try_multiply(other),
-            (Interval(a), I8(b)) => Ok(Interval(*a * *b)),
-            (Interval(a), I16(b)) => Ok(Interval(*a * *b)),
-            (Interval(a), I32(b)) => Ok(Interval(*a * *b)),
-            (Interval(a), I64(b)) => Ok(Interval(*a * *b)),
-            (Interval(a), I128(b)) => Ok(Interval(*a * *b)),
+            (Interval(a), I8(_) | I16(_) | I32(_) | I64(_) | I128(_)) => a
+                .checked_mul(i128::try_from(other)?)
+                .ok_or_else(|| {
+                    ValueError::BinaryOperationOverflow {
+                        lhs: self.clone(),
+                        rhs: other.clone(),
+                        operator: NumericBinaryOperator::Multiply,
+                    }
+                    .into()
+                })
+                .map(Interval),
             (Interval(a), F64(b)) => Ok(Interval(*a * *b)),
             (Null, I8(_))
             | (Null, I16(_))
@@ -421,6 +482,129 @@ impl Value {
         }
     }
 
+    pub fn is_integer(&self) -> bool {
+        use Value::*;
+
+        matches!(self, I8(_) | I16(_) | I32(_) | I64(_) | I128(_))
+    }
+
+    pub fn bitwise_and(&self, other: &Value) -> Result<Value> {
+        let operator = NumericBinaryOperator::BitwiseAnd;
+
+        self.bitwise_op(other, operator, |a, b| a & b, |a, b| a & b)
+    }
+
+    pub fn bitwise_or(&self, other: &Value) -> Result<Value> {
+        let operator = NumericBinaryOperator::BitwiseOr;
+
+        self.bitwise_op(other, operator, |a, b| a | b, |a, b| a | b)
+    }
+
+    pub fn bitwise_xor(&self, other: &Value) -> Result<Value> {
+        let operator = NumericBinaryOperator::BitwiseXor;
+
+        self.bitwise_op(other, operator, |a, b| a ^ b, |a, b| a ^ b)
+    }
+
+    fn bitwise_op(
+        &self,
+        other: &Value,
+        operator: NumericBinaryOperator,
+        bit_op: fn(bool, bool) -> bool,
+        int_op: fn(i128, i128) -> i128,
+    ) -> Result<Value> {
+        use Value::*;
+
+        match (self, other) {
+            (Bit(a), Bit(b)) if a.len() != b.len() => Err(ValueError::BitLengthMismatch {
+                lhs: a.len(),
+                rhs: b.len(),
+            }
+            .into()),
+            (Bit(a), Bit(b)) => Ok(Bit(a.iter().zip(b).map(|(a, b)| bit_op(*a, *b)).collect())),
+            (a, b) if a.is_integer() && b.is_integer() => {
+                let result = int_op(i128::try_from(a)?, i128::try_from(b)?);
+
+                // both operands are promoted to the wider of their types,
+                // and the result of `&`, `|` and `^` always fits in that type
+                Ok(match (a, b) {
+                    (I128(_), _) | (_, I128(_)) => I128(result),
+                    (I64(_), _) | (_, I64(_)) => I64(result as i64),
+                    (I32(_), _) | (_, I32(_)) => I32(result as i32),
+                    (I16(_), _) | (_, I16(_)) => I16(result as i16),
+                    _ => I8(result as i8),
+                })
+            }
+            (Null, Bit(_)) | (Bit(_), Null) | (Null, Null) => Ok(Null),
+            (Null, _) if other.is_integer() => Ok(Null),
+            (_, Null) if self.is_integer() => Ok(Null),
+            _ => Err(ValueError::NonBitwiseOperation {
+                lhs: self.clone(),
+                operator,
+                rhs: other.clone(),
+            }
+            .into()),
+        }
+    }
+
+    pub fn bitwise_shift_left(&self, other: &Value) -> Result<Value> {
+        self.bitwise_shift(other, NumericBinaryOperator::BitwiseShiftLeft)
+    }
+
+    pub fn bitwise_shift_right(&self, other: &Value) -> Result<Value> {
+        self.bitwise_shift(other, NumericBinaryOperator::BitwiseShiftRight)
+    }
+
+    /// Shifts beyond the bit width of the left operand are rejected,
+    /// and `>>` on a negative value keeps its sign.
+    fn bitwise_shift(&self, other: &Value, operator: NumericBinaryOperator) -> Result<Value> {
+        use Value::*;
+
+        let valid = |v: &Value| v.is_integer() || v.is_null();
+
+        if !valid(self) || !valid(other) {
+            return Err(ValueError::NonBitwiseOperation {
+                lhs: self.clone(),
+                operator,
+                rhs: other.clone(),
+            }
+            .into());
+        }
+
+        if self.is_null() || other.is_null() {
+            return Ok(Null);
+        }
+
+        let amount = i128::try_from(other)?;
+        let left = operator == NumericBinaryOperator::BitwiseShiftLeft;
+
+        macro_rules! shift {
+            ($variant: ident, $a: expr, $ty: ty) => {{
+                let bits = <$ty>::BITS;
+                let amount = u32::try_from(amount)
+                    .ok()
+                    .filter(|amount| *amount < bits)
+                    .ok_or(ValueError::BitwiseShiftOutOfRange { amount, bits })?;
+
+                Ok($variant(if left { $a << amount } else { $a >> amount }))
+            }};
+        }
+
+        match self {
+            I8(a) => shift!(I8, a, i8),
+            I16(a) => shift!(I16, a, i16),
+            I32(a) => shift!(I32, a, i32),
+            I64(a) => shift!(I64, a, i64),
+            I128(a) => shift!(I128, a, i128),
+            _ => Err(ValueError::NonBitwiseOperation {
+                lhs: self.clone(),
+                operator,
+                rhs: other.clone(),
+            }
+            .into()),
+        }
+    }
+
     pub fn is_null(&self) -> bool {
         matches!(self, Value::Null)
     }
@@ -454,6 +638,21 @@ impl Value {
         }
     }
 
+    pub fn unary_bitwise_not(&self) -> Result<Value> {
+        use Value::*;
+
+        match self {
+            Bit(a) => Ok(Bit(a.iter().map(|v| !v).collect())),
+            I8(a) => Ok(I8(!a)),
+            I16(a) => Ok(I16(!a)),
+            I32(a) => Ok(I32(!a)),
+            I64(a) => Ok(I64(!a)),
+            I128(a) => Ok(I128(!a)),
+            Null => Ok(Null),
+            _ => Err(ValueError::BitwiseNotOnUnsupportedValue(self.clone()).into()),
+        }
+    }
+
     pub fn unary_factorial(&self) -> Result<Value> {
         use Value::*;
 
@@ -505,6 +704,9 @@ impl Value {
             (Value::Timestamp(v), DateTimeField::Hour) => v.hour().into(),
             (Value::Timestamp(v), DateTimeField::Minute) => v.minute().into(),
             (Value::Timestamp(v), DateTimeField::Second) => v.second().into(),
+            (Value::TimestampTz(v), _) => {
+                return Value::Timestamp(v.naive_local()).extract(date_type);
+            }
             (Value::Interval(v), _) => {
                 return v.extract(date_type);
             }
@@ -541,7 +743,7 @@ impl Value {
 #[cfg(test)]
 mod tests {
     use {
-        super::{Interval, Value::*},
+        super::{Interval, NumericBinaryOperator, Value::*},
         crate::data::{value::uuid::parse_uuid, ValueError},
         rust_decimal::Decimal,
     };
@@ -579,6 +781,14 @@ mod tests {
         assert_eq!(date, timestamp);
         assert_eq!(timestamp, date);
 
+        let timestamptz = |v: &str| TimestampTz(v.parse().unwrap());
+
+        assert_eq!(
+            timestamptz("2020-05-01T09:00:00+09:00"),
+            timestamptz("2020-05-01T00:00:00Z")
+        );
+        assert_ne!(timestamptz("2020-05-01T00:00:00+09:00"), timestamp);
+
         assert_eq!(
             Uuid(parse_uuid("936DA01F9ABD4d9d80C702AF85C822A8").unwrap()),
             Uuid(parse_uuid("936DA01F9ABD4d9d80C702AF85C822A8").unwrap())
@@ -674,6 +884,15 @@ mod tests {
         }
     }
 
+    #[test]
+    fn is_nan() {
+        assert!(F64(f64::NAN).is_nan());
+        assert!(!F64(f64::INFINITY).is_nan());
+        assert!(!F64(0.0).is_nan());
+        assert!(!I64(0).is_nan());
+        assert!(!Null.is_nan());
+    }
+
     #[test]
     fn arithmetic() {
         use chrono::{NaiveDate, NaiveTime};
@@ -914,6 +1133,24 @@ mod tests {
         test!(multiply mon!(3),  I64(2)   => mon!(6));
         test!(multiply mon!(3),  I128(2)    => mon!(6));
         test!(multiply mon!(3),  F64(2.0) => mon!(6));
+        assert_eq!(
+            I64(i64::MAX).multiply(&mon!(2)),
+            Err(ValueError::BinaryOperationOverflow {
+                lhs: I64(i64::MAX),
+                rhs: mon!(2),
+                operator: NumericBinaryOperator::Multiply,
+            }
+            .into())
+        );
+        assert_eq!(
+            mon!(2).multiply(&I64(i64::MAX)),
+            Err(ValueError::BinaryOperationOverflow {
+                lhs: mon!(2),
+                rhs: I64(i64::MAX),
+                operator: NumericBinaryOperator::Multiply,
+            }
+            .into())
+        );
 
         test!(divide I8(0),     I8(5)   => I8(0));
         test!(divide I8(0),     I16(5)   => I8(0));
@@ -1302,6 +1539,9 @@ mod tests {
         assert!(map.validate_type(&D::Int).is_err());
         assert!(list.validate_type(&D::List).is_ok());
         assert!(list.validate_type(&D::Int).is_err());
+        assert!(Bit(vec![true, false]).validate_type(&D::Bit(2)).is_ok());
+        assert!(Bit(vec![true]).validate_type(&D::Bit(2)).is_err());
+        assert!(Bit(vec![true]).validate_type(&D::Text).is_err());
         assert!(Null.validate_type(&D::Time).is_ok());
         assert!(Null.validate_type(&D::Boolean).is_ok());
 
@@ -1353,6 +1593,77 @@ mod tests {
         );
     }
 
+    #[test]
+    fn bitwise() {
+        let bit = |v: &str| Bit(v.chars().map(|c| c == '1').collect());
+
+        assert_eq!(bit("1100").bitwise_and(&bit("1010")), Ok(bit("1000")));
+        assert_eq!(bit("1100").bitwise_or(&bit("1010")), Ok(bit("1110")));
+        assert_eq!(bit("1100").bitwise_xor(&bit("1010")), Ok(bit("0110")));
+        assert_eq!(bit("1100").unary_bitwise_not(), Ok(bit("0011")));
+        assert!(bit("1100").bitwise_and(&Null).unwrap().is_null());
+        assert!(Null.unary_bitwise_not().unwrap().is_null());
+        assert_eq!(
+            bit("1100").bitwise_or(&bit("10")),
+            Err(ValueError::BitLengthMismatch { lhs: 4, rhs: 2 }.into())
+        );
+        assert_eq!(
+            bit("1").bitwise_xor(&Str("1".to_owned())),
+            Err(ValueError::NonBitwiseOperation {
+                lhs: bit("1"),
+                operator: NumericBinaryOperator::BitwiseXor,
+                rhs: Str("1".to_owned()),
+            }
+            .into())
+        );
+        assert_eq!(
+            Str("1".to_owned()).unary_bitwise_not(),
+            Err(ValueError::BitwiseNotOnUnsupportedValue(Str("1".to_owned())).into())
+        );
+
+        assert_eq!(I8(12).bitwise_and(&I8(10)), Ok(I8(8)));
+        assert_eq!(I16(12).bitwise_or(&I64(10)), Ok(I64(14)));
+        assert_eq!(I8(12).bitwise_and(&I64(1000)), Ok(I64(8)));
+        assert_eq!(I64(-1).bitwise_and(&I8(-2)), Ok(I64(-2)));
+        assert_eq!(I32(12).bitwise_xor(&I32(10)), Ok(I32(6)));
+        assert_eq!(I64(-1).bitwise_and(&I64(3)), Ok(I64(3)));
+        assert_eq!(I128(12).bitwise_and(&I8(10)), Ok(I128(8)));
+        assert_eq!(I64(5).unary_bitwise_not(), Ok(I64(-6)));
+        assert_eq!(I8(0).unary_bitwise_not(), Ok(I8(-1)));
+        assert!(I64(1).bitwise_or(&Null).unwrap().is_null());
+
+        assert_eq!(I64(1).bitwise_shift_left(&I64(3)), Ok(I64(8)));
+        assert_eq!(I8(1).bitwise_shift_left(&I64(7)), Ok(I8(i8::MIN)));
+        assert_eq!(I64(-16).bitwise_shift_right(&I64(2)), Ok(I64(-4)));
+        assert_eq!(I128(1).bitwise_shift_left(&I64(127)), Ok(I128(i128::MIN)));
+        assert!(Null.bitwise_shift_right(&I64(1)).unwrap().is_null());
+        assert_eq!(
+            I32(1).bitwise_shift_left(&I64(32)),
+            Err(ValueError::BitwiseShiftOutOfRange {
+                amount: 32,
+                bits: 32
+            }
+            .into())
+        );
+        assert_eq!(
+            I64(1).bitwise_shift_right(&I64(-1)),
+            Err(ValueError::BitwiseShiftOutOfRange {
+                amount: -1,
+                bits: 64
+            }
+            .into())
+        );
+        assert_eq!(
+            F64(1.0).bitwise_shift_left(&I64(1)),
+            Err(ValueError::NonBitwiseOperation {
+                lhs: F64(1.0),
+                operator: NumericBinaryOperator::BitwiseShiftLeft,
+                rhs: I64(1),
+            }
+            .into())
+        );
+    }
+
     #[test]
     fn sqrt() {
         assert_eq!(I8(9).sqrt(), Ok(F64(3.0)));
@@ -1404,4 +1715,95 @@ mod tests {
         assert_eq!(list.get_type(), Some(D::List));
         assert_eq!(Null.get_type(), None);
     }
+
+    #[test]
+    fn serde() {
+        use {
+            super::Value,
+            crate::data::{Interval as I, Row},
+            chrono::{FixedOffset, NaiveDate, NaiveTime, TimeZone},
+        };
+
+        let timestamptz = FixedOffset::east(9 * 3600)
+            .ymd(2021, 5, 1)
+            .and_hms(12, 34, 50);
+
+        let values = vec![
+            (Bool(true), r#"{"Bool":true}"#),
+            (I8(-1), r#"{"I8":-1}"#),
+            (I16(2), r#"{"I16":2}"#),
+            (I32(3), r#"{"I32":3}"#),
+            (I64(4), r#"{"I64":4}"#),
+            (
+                I128(i128::MAX),
+                r#"{"I128":170141183460469231731687303715884105727}"#,
+            ),
+            (F64(1.5), r#"{"F64":1.5}"#),
+            (
+                Decimal(rust_decimal::Decimal::new(31415, 4)),
+                r#"{"Decimal":"3.1415"}"#,
+            ),
+            (Str("Glue".to_owned()), r#"{"Str":"Glue"}"#),
+            (Bytea(hex::decode("9001").unwrap()), r#"{"Bytea":[144,1]}"#),
+            (Bit(vec![true, false]), r#"{"Bit":[true,false]}"#),
+            (
+                Date(NaiveDate::from_ymd(2021, 5, 1)),
+                r#"{"Date":"2021-05-01"}"#,
+            ),
+            (
+                Timestamp(NaiveDate::from_ymd(2021, 5, 1).and_hms(12, 34, 50)),
+                r#"{"Timestamp":"2021-05-01T12:34:50"}"#,
+            ),
+            (
+                TimestampTz(timestamptz),
+                r#"{"TimestampTz":"2021-05-01T12:34:50+09:00"}"#,
+            ),
+            (
+                Time(NaiveTime::from_hms(12, 30, 11)),
+                r#"{"Time":"12:30:11"}"#,
+            ),
+            (Interval(I::Month(14)), r#"{"Interval":{"Month":14}}"#),
+            (
+                Interval(I::hours(5)),
+                r#"{"Interval":{"Microsecond":18000000000}}"#,
+            ),
+            (
+                Uuid(u128::MAX),
+                r#"{"Uuid":340282366920938463463374607431768211455}"#,
+            ),
+            (
+                Value::parse_json_map(r#"{ "a": 10 }"#).unwrap(),
+                r#"{"Map":{"a":{"I64":10}}}"#,
+            ),
+            (
+                Value::parse_json_list(r#"[ true, "b" ]"#).unwrap(),
+                r#"{"List":[{"Bool":true},{"Str":"b"}]}"#,
+            ),
+        ];
+
+        for (value, json) in values {
+            assert_eq!(serde_json::to_string(&value).unwrap(), json);
+
+            let deserialized: Value = serde_json::from_str(json).unwrap();
+            assert_eq!(deserialized, value);
+            assert_eq!(deserialized.get_type(), value.get_type());
+        }
+
+        let json = r#"{"TimestampTz":"2021-05-01T12:34:50+09:00"}"#;
+        assert!(matches!(
+            serde_json::from_str::<Value>(json).unwrap(),
+            TimestampTz(v) if v.offset() == timestamptz.offset()
+        ));
+
+        assert_eq!(serde_json::to_string(&Null).unwrap(), r#""Null""#);
+        assert!(matches!(
+            serde_json::from_str::<Value>(r#""Null""#).unwrap(),
+            Null
+        ));
+
+        let row = Row(vec![I64(1), Str("Glue".to_owned())]);
+        let json = serde_json::to_string(&row).unwrap();
+        assert_eq!(json, r#"[{"I64":1},{"Str":"Glue"}]"#);
+        assert_eq!(serde_json::from_str::<Row>(&json).unwrap(), row);
+    }
 }
@@ -1,4 +1,9 @@
-use {crate::ast::Expr, serde::Serialize, std::fmt::Debug, thiserror::Error};
+use {
+    crate::{ast::Expr, data::Value},
+    serde::Serialize,
+    std::fmt::Debug,
+    thiserror::Error,
+};
 
 #[derive(Error, Serialize, Debug, PartialEq)]
 pub enum AggregateError {
@@ -11,6 +16,24 @@ pub enum AggregateError {
     #[error("value not found: {0}")]
     ValueNotFound(String),
 
+    #[error("boolean aggregate requires boolean value: {0:?}")]
+    BooleanValueRequired(Value),
+
+    #[error("JSON_OBJECT_AGG key must not be NULL")]
+    NullJsonObjectKey,
+
+    #[error("only columns are allowed in grouping sets: {0}")]
+    OnlyColumnAllowedInGroupingSet(String),
+
+    #[error("CUBE supports at most {limit} columns, found {found}")]
+    TooManyCubeColumns { limit: usize, found: usize },
+
+    #[error("GROUPING argument must be a grouping column: {0}")]
+    GroupingArgumentNotGrouped(String),
+
+    #[error("unreachable GROUPING accumulation")]
+    UnreachableGroupingAccumulation,
+
     #[error("unreachable rc unwrap failure")]
     UnreachableRcUnwrapFailure,
 }
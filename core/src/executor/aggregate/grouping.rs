@@ -0,0 +1,143 @@
+use {
+    super::error::AggregateError,
+    crate::{
+        ast::{Expr, GroupingSets, ToSql},
+        data::Key,
+        executor::context::BlendContext,
+        result::Result,
+    },
+    std::{iter::once, rc::Rc},
+};
+
+/// Most columns a `CUBE` may take, as it groups every row once for each of its `2^n` subsets.
+const CUBE_COLUMN_LIMIT: usize = 12;
+
+/// One grouping set, which groups by the columns it holds and shows the others as NULL.
+pub struct GroupingSet<'a> {
+    /// Whether each of the grouping set columns is held by this set
+    present: Vec<bool>,
+    /// `(table alias, column)` of the columns shown as NULL, the alias of a bare column is empty
+    masks: Vec<(&'a str, Rc<[String]>)>,
+}
+
+impl<'a> GroupingSet<'a> {
+    /// Group of a row whose `keys` are the values of `GROUP BY` followed by the values of the
+    /// grouping set columns, and its context with the columns absent from this set as NULL.
+    ///
+    /// The position of the set leads the group, so a NULL column of one set is not mixed up
+    /// with a column another set leaves out.
+    pub fn group(
+        &self,
+        position: usize,
+        keys: &[Key],
+        context: &Rc<BlendContext<'a>>,
+    ) -> (Vec<Key>, Rc<BlendContext<'a>>) {
+        let (group_by, columns) = keys.split_at(keys.len() - self.present.len());
+        let columns = columns
+            .iter()
+            .zip(self.present.iter())
+            .map(|(key, present)| if *present { key.clone() } else { Key::None });
+        let group = once(Key::I64(position as i64))
+            .chain(group_by.iter().cloned())
+            .chain(columns)
+            .collect();
+
+        let context = self
+            .masks
+            .iter()
+            .fold(Rc::clone(context), |next, (alias, columns)| {
+                Rc::new(BlendContext::new(
+                    *alias,
+                    Rc::clone(columns),
+                    None,
+                    Some(next),
+                ))
+            });
+
+        (group, context)
+    }
+
+    /// Value of `GROUPING(exprs)` for the groups of this set, given every grouping set column.
+    pub fn grouping(&self, columns: &[&Expr], group_by: &[Expr], exprs: &[Expr]) -> Result<i64> {
+        exprs.iter().try_fold(0, |bits, expr| {
+            let absent = match columns.iter().position(|column| *column == expr) {
+                Some(i) => !self.present[i],
+                None if group_by.contains(expr) => false,
+                None => {
+                    return Err(AggregateError::GroupingArgumentNotGrouped(expr.to_sql()).into());
+                }
+            };
+
+            Ok((bits << 1) | i64::from(absent))
+        })
+    }
+}
+
+/// Columns of the grouping sets and the sets themselves, a single set which holds every column
+/// if there are no grouping sets.
+pub fn expand(grouping_sets: Option<&GroupingSets>) -> Result<(Vec<&Expr>, Vec<GroupingSet<'_>>)> {
+    let grouping_sets = match grouping_sets {
+        Some(grouping_sets) => grouping_sets,
+        None => {
+            let set = GroupingSet {
+                present: Vec::new(),
+                masks: Vec::new(),
+            };
+
+            return Ok((Vec::new(), vec![set]));
+        }
+    };
+
+    let columns = grouping_sets.columns();
+    let masks = columns
+        .iter()
+        .map(|&expr| match expr {
+            Expr::Identifier(ident) => Ok(("", Rc::from(vec![ident.to_owned()]))),
+            Expr::CompoundIdentifier(idents) if idents.len() == 2 => {
+                Ok((idents[0].as_str(), Rc::from(vec![idents[1].to_owned()])))
+            }
+            _ => Err(AggregateError::OnlyColumnAllowedInGroupingSet(expr.to_sql()).into()),
+        })
+        .collect::<Result<Vec<(&str, Rc<[String]>)>>>()?;
+
+    let sets = match grouping_sets {
+        GroupingSets::Cube(_) => cube(columns.len())?,
+        GroupingSets::Sets(sets) => sets
+            .iter()
+            .map(|set| columns.iter().map(|&column| set.contains(column)).collect())
+            .collect(),
+    };
+    let sets = sets
+        .into_iter()
+        .map(|present| {
+            let masks = masks
+                .iter()
+                .zip(present.iter())
+                .filter(|(_, present)| !**present)
+                .map(|((alias, columns), _)| (*alias, Rc::clone(columns)))
+                .collect();
+
+            GroupingSet { present, masks }
+        })
+        .collect();
+
+    Ok((columns, sets))
+}
+
+/// Every subset of `n` columns, from the one which holds all of them down to the empty one.
+fn cube(n: usize) -> Result<Vec<Vec<bool>>> {
+    if n > CUBE_COLUMN_LIMIT {
+        return Err(AggregateError::TooManyCubeColumns {
+            limit: CUBE_COLUMN_LIMIT,
+            found: n,
+        }
+        .into());
+    }
+
+    let sets = (0..1_usize << n)
+        .rev()
+        .map(|bits| (0..n).map(|i| bits & (1 << (n - 1 - i)) != 0).collect())
+        .collect();
+
+    Ok(sets)
+}
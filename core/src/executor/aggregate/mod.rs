@@ -1,4 +1,5 @@
 mod error;
+mod grouping;
 mod state;
 
 use {
@@ -7,10 +8,11 @@ use {
         context::{AggregateContext, BlendContext, FilterContext},
         evaluate::{evaluate, Evaluated},
         filter::check_expr,
+        nulls::group_key,
     },
     crate::{
-        ast::{Expr, SelectItem},
-        data::Key,
+        ast::{Aggregate, Expr, GroupingSets, SelectItem},
+        data::{Key, Value},
         result::{Error, Result},
         store::GStore,
     },
@@ -18,12 +20,13 @@ use {
     std::{convert::identity, pin::Pin, rc::Rc},
 };
 
-pub use error::AggregateError;
+pub use {error::AggregateError, state::AggrValue};
 
 pub struct Aggregator<'a> {
     storage: &'a dyn GStore,
     fields: &'a [SelectItem],
     group_by: &'a [Expr],
+    grouping_sets: Option<&'a GroupingSets>,
     having: Option<&'a Expr>,
     filter_context: Option<Rc<FilterContext<'a>>>,
 }
@@ -36,6 +39,7 @@ impl<'a> Aggregator<'a> {
         storage: &'a dyn GStore,
         fields: &'a [SelectItem],
         group_by: &'a [Expr],
+        grouping_sets: Option<&'a GroupingSets>,
         having: Option<&'a Expr>,
         filter_context: Option<Rc<FilterContext<'a>>>,
     ) -> Self {
@@ -43,6 +47,7 @@ impl<'a> Aggregator<'a> {
             storage,
             fields,
             group_by,
+            grouping_sets,
             having,
             filter_context,
         }
@@ -55,17 +60,31 @@ impl<'a> Aggregator<'a> {
         if !self.check_aggregate() {
             let rows = rows.map_ok(|blend_context| AggregateContext {
                 aggregated: None,
+                windowed: None,
                 next: blend_context,
             });
             return Ok(Box::pin(rows));
         }
 
+        let aggregates = self
+            .fields
+            .iter()
+            .flat_map(|field| match field {
+                SelectItem::Expr { expr, .. } => collect_aggregates(expr),
+                _ => Vec::new(),
+            })
+            .collect::<Vec<_>>();
+        let aggregates = &aggregates;
+        let (columns, sets) = grouping::expand(self.grouping_sets)?;
+        let (columns, sets) = (&columns, &sets);
+
         let state = rows
             .into_stream()
             .enumerate()
             .map(|(i, row)| row.map(|row| (i, row)))
             .try_fold(State::new(), |state, (index, blend_context)| async move {
-                let evaluated: Vec<Evaluated<'_>> = stream::iter(self.group_by.iter())
+                let group_by = self.group_by.iter().chain(columns.iter().copied());
+                let evaluated: Vec<Evaluated<'_>> = stream::iter(group_by)
                     .then(|expr| {
                         let filter_context = FilterContext::concat(
                             self.filter_context.as_ref().map(Rc::clone),
@@ -73,25 +92,57 @@ impl<'a> Aggregator<'a> {
                         );
                         let filter_context = Some(filter_context).map(Rc::new);
 
-                        async move { evaluate(self.storage, filter_context, None, expr).await }
+                        async move {
+                            evaluate(self.storage, filter_context, None, None, expr).await
+                        }
                     })
                     .try_collect::<Vec<_>>()
                     .await?;
-                let group = evaluated
-                    .iter()
-                    .map(Key::try_from)
+                let keys = evaluated
+                    .into_iter()
+                    .map(|evaluated| Value::try_from(evaluated).and_then(group_key))
                     .collect::<Result<Vec<Key>>>()?;
 
-                let state = state.apply(index, group, Rc::clone(&blend_context));
-                let state = self
-                    .fields
-                    .iter()
-                    .try_fold(state, |state, field| match field {
-                        SelectItem::Expr { expr, .. } => aggregate(state, &blend_context, expr),
-                        _ => Ok(state),
-                    })?;
+                let passes: Vec<bool> = stream::iter(aggregates.iter().copied())
+                    .then(|aggr| {
+                        let blend_context = Rc::clone(&blend_context);
+
+                        async move {
+                            match aggr {
+                                Aggregate::Filter { filter, .. } => {
+                                    let filter_context = FilterContext::concat(
+                                        self.filter_context.as_ref().map(Rc::clone),
+                                        Some(blend_context),
+                                    );
+                                    let filter_context = Some(filter_context).map(Rc::new);
+
+                                    check_expr(self.storage, filter_context, None, filter).await
+                                }
+                                _ => Ok(true),
+                            }
+                        }
+                    })
+                    .try_collect()
+                    .await?;
 
-                Ok(state)
+                // each row is accumulated once for every grouping set
+                sets.iter()
+                    .enumerate()
+                    .try_fold(state, |state, (position, set)| {
+                        let (group, context) = set.group(position, &keys, &blend_context);
+                        let state = state.apply(index, group, context);
+
+                        aggregates.iter().copied().zip(passes.iter()).try_fold(
+                            state,
+                            |state, (aggr, pass)| match aggr {
+                                Aggregate::Grouping(exprs) => set
+                                    .grouping(columns, self.group_by, exprs)
+                                    .map(|bits| state.grouping(aggr, bits)),
+                                _ if *pass => state.accumulate(&blend_context, aggr),
+                                _ => Ok(state.skip(aggr)),
+                            },
+                        )
+                    })
             })
             .await?;
 
@@ -135,14 +186,18 @@ impl<'a> Aggregator<'a> {
                     .map(Rc::try_unwrap)
                     .transpose()
                     .map_err(|_| AggregateError::UnreachableRcUnwrapFailure.into())
-                    .map(|aggregated| AggregateContext { aggregated, next })
+                    .map(|aggregated| AggregateContext {
+                        aggregated,
+                        windowed: None,
+                        next,
+                    })
             });
 
         Ok(Box::pin(rows))
     }
 
-    fn check_aggregate(&self) -> bool {
-        if !self.group_by.is_empty() {
+    pub fn check_aggregate(&self) -> bool {
+        if !self.group_by.is_empty() || self.grouping_sets.is_some() {
             return true;
         }
 
@@ -156,26 +211,26 @@ impl<'a> Aggregator<'a> {
     }
 }
 
-fn aggregate<'a>(
-    state: State<'a>,
-    context: &BlendContext<'_>,
-    expr: &'a Expr,
-) -> Result<State<'a>> {
-    let aggr = |state, expr| aggregate(state, context, expr);
-
+/// Aggregates in `expr`, in the order they are accumulated.
+fn collect_aggregates(expr: &Expr) -> Vec<&Aggregate> {
     match expr {
         Expr::Between {
             expr, low, high, ..
         } => [expr, low, high]
-            .iter()
-            .try_fold(state, |state, expr| aggr(state, expr)),
-        Expr::BinaryOp { left, right, .. } => [left, right]
-            .iter()
-            .try_fold(state, |state, expr| aggr(state, expr)),
-        Expr::UnaryOp { expr, .. } => aggr(state, expr),
-        Expr::Nested(expr) => aggr(state, expr),
-        Expr::Aggregate(aggr) => state.accumulate(context, aggr.as_ref()),
-        _ => Ok(state),
+            .into_iter()
+            .flat_map(|expr| collect_aggregates(expr))
+            .collect(),
+        Expr::BinaryOp { left, right, .. }
+        | Expr::AnyOp { left, right, .. }
+        | Expr::AllOp { left, right, .. } => [left, right]
+            .into_iter()
+            .flat_map(|expr| collect_aggregates(expr))
+            .collect(),
+        Expr::UnaryOp { expr, .. } => collect_aggregates(expr),
+        Expr::Nested(expr) => collect_aggregates(expr),
+        Expr::Tuple(exprs) => exprs.iter().flat_map(collect_aggregates).collect(),
+        Expr::Aggregate(aggr) => vec![aggr.as_ref()],
+        _ => Vec::new(),
     }
 }
 
@@ -184,9 +239,12 @@ fn check(expr: &Expr) -> bool {
         Expr::Between {
             expr, low, high, ..
         } => check(expr) || check(low) || check(high),
-        Expr::BinaryOp { left, right, .. } => check(left) || check(right),
+        Expr::BinaryOp { left, right, .. }
+        | Expr::AnyOp { left, right, .. }
+        | Expr::AllOp { left, right, .. } => check(left) || check(right),
         Expr::UnaryOp { expr, .. } => check(expr),
         Expr::Nested(expr) => check(expr),
+        Expr::Tuple(exprs) => exprs.iter().any(check),
         Expr::Aggregate(_) => true,
         _ => false,
     }
@@ -1,20 +1,27 @@
 use {
     super::error::AggregateError,
     crate::{
-        ast::{Aggregate, CountArgExpr, Expr},
+        ast::{Aggregate, CountArgExpr, Expr, OrderByExpr},
         data::{Key, Value},
-        executor::context::BlendContext,
+        executor::{
+            context::BlendContext,
+            nulls::{self, Order},
+            udf::{accumulate_aggregate, finalize_aggregate},
+        },
         result::Result,
     },
     im_rc::{HashMap, HashSet},
     itertools::Itertools,
+    serde_json::Value as JsonValue,
     std::{cmp::Ordering, rc::Rc},
     utils::{IndexMap, Vector},
 };
 type Group = Rc<Vec<Key>>;
 type ValuesMap<'a> = HashMap<&'a Aggregate, Value>;
 type Context<'a> = Rc<BlendContext<'a>>;
-enum AggrValue {
+
+#[derive(Clone)]
+pub enum AggrValue {
     Count {
         wildcard: bool,
         count: i64,
@@ -36,20 +43,43 @@ enum AggrValue {
         sum: Value,
         count: i64,
     },
+    BoolAnd(Value),
+    BoolOr(Value),
+    /// Collected `(value, order key)` pairs, the order key is NULL when no `ORDER BY` is given.
+    /// NULL values are collected like any other value, as in PostgreSQL.
+    ArrayAgg {
+        order: Order,
+        items: im_rc::Vector<(Value, Value)>,
+    },
+    JsonAgg(im_rc::Vector<Value>),
+    /// A later value overwrites the earlier one on key collision.
+    JsonObjectAgg(HashMap<String, Value>),
+    /// Decided by the grouping set of the group rather than by its rows.
+    Grouping(i64),
+    /// State of a registered aggregate, `None` until a non-NULL value is accumulated.
+    Custom {
+        name: String,
+        state: Option<Value>,
+    },
 }
 
 impl<'a> AggrValue {
-    fn new(aggr: &Aggregate, value: &Value) -> Result<Self> {
+    pub fn new(aggr: &Aggregate, value: &Value) -> Result<Self> {
+        Self::new_keyed(aggr, value, &Value::Null)
+    }
+
+    pub fn new_keyed(aggr: &Aggregate, value: &Value, key: &Value) -> Result<Self> {
         let value = value.clone();
 
         Ok(match aggr {
+            Aggregate::Filter { aggregate, .. } => Self::new_keyed(aggregate, &value, key)?,
             Aggregate::Count(CountArgExpr::Wildcard) => AggrValue::Count {
                 wildcard: true,
                 count: 1,
             },
             Aggregate::Count(CountArgExpr::Expr(_)) => AggrValue::Count {
                 wildcard: false,
-                count: 1,
+                count: if value.is_null() { 0 } else { 1 },
             },
             Aggregate::Sum(_) => AggrValue::Sum(value),
             Aggregate::Min(_) => AggrValue::Min(value),
@@ -68,10 +98,31 @@ impl<'a> AggrValue {
                 sum: value,
                 count: 1,
             },
+            Aggregate::BoolAnd(_) => AggrValue::BoolAnd(check_bool(value)?),
+            Aggregate::BoolOr(_) => AggrValue::BoolOr(check_bool(value)?),
+            Aggregate::ArrayAgg { order_by, .. } => AggrValue::ArrayAgg {
+                order: order_by.as_ref().map(Order::from).unwrap_or_default(),
+                items: im_rc::vector![(value, key.clone())],
+            },
+            Aggregate::JsonAgg(_) => AggrValue::JsonAgg(im_rc::vector![to_json(value)?]),
+            Aggregate::JsonObjectAgg { .. } => {
+                AggrValue::JsonObjectAgg(HashMap::unit(json_key(key)?, to_json(value)?))
+            }
+            Aggregate::Grouping(_) => {
+                return Err(AggregateError::UnreachableGroupingAccumulation.into());
+            }
+            Aggregate::Custom { name, .. } => AggrValue::Custom {
+                name: name.clone(),
+                state: accumulate_aggregate(name, None, &value)?,
+            },
         })
     }
 
-    fn accumulate(&self, new_value: &Value) -> Result<Option<Self>> {
+    pub fn accumulate(&self, new_value: &Value) -> Result<Option<Self>> {
+        self.accumulate_keyed(new_value, &Value::Null)
+    }
+
+    pub fn accumulate_keyed(&self, new_value: &Value, key: &Value) -> Result<Option<Self>> {
         match self {
             Self::Count { wildcard, count } => {
                 let wildcard = *wildcard;
@@ -86,13 +137,21 @@ impl<'a> AggrValue {
                 }
             }
             Self::Sum(value) => Ok(Some(Self::Sum(value.add(new_value)?))),
-            Self::Min(value) => match &value.partial_cmp(new_value) {
-                Some(Ordering::Greater) => Ok(Some(Self::Min(new_value.clone()))),
-                _ => Ok(None),
+            Self::Min(value) => match (value, new_value) {
+                (_, Value::Null) => Ok(None),
+                (Value::Null, _) => Ok(Some(Self::Min(new_value.clone()))),
+                _ => match &value.partial_cmp(new_value) {
+                    Some(Ordering::Greater) => Ok(Some(Self::Min(new_value.clone()))),
+                    _ => Ok(None),
+                },
             },
-            Self::Max(value) => match &value.partial_cmp(new_value) {
-                Some(Ordering::Less) => Ok(Some(Self::Max(new_value.clone()))),
-                _ => Ok(None),
+            Self::Max(value) => match (value, new_value) {
+                (_, Value::Null) => Ok(None),
+                (Value::Null, _) => Ok(Some(Self::Max(new_value.clone()))),
+                _ => match &value.partial_cmp(new_value) {
+                    Some(Ordering::Less) => Ok(Some(Self::Max(new_value.clone()))),
+                    _ => Ok(None),
+                },
             },
             Self::Avg { sum, count } => Ok(Some(Self::Avg {
                 sum: sum.add(new_value)?,
@@ -116,10 +175,43 @@ impl<'a> AggrValue {
                 sum: sum.add(new_value)?,
                 count: count + 1,
             })),
+            Self::BoolAnd(value) => match (value, check_bool(new_value.clone())?) {
+                (_, Value::Null) | (Value::Bool(false), _) => Ok(None),
+                (_, new_value) => Ok(Some(Self::BoolAnd(new_value))),
+            },
+            Self::BoolOr(value) => match (value, check_bool(new_value.clone())?) {
+                (_, Value::Null) | (Value::Bool(true), _) => Ok(None),
+                (_, new_value) => Ok(Some(Self::BoolOr(new_value))),
+            },
+            Self::ArrayAgg { order, items } => {
+                let mut items = items.clone();
+                items.push_back((new_value.clone(), key.clone()));
+
+                Ok(Some(Self::ArrayAgg {
+                    order: *order,
+                    items,
+                }))
+            }
+            Self::JsonAgg(items) => {
+                let mut items = items.clone();
+                items.push_back(to_json(new_value.clone())?);
+
+                Ok(Some(Self::JsonAgg(items)))
+            }
+            Self::JsonObjectAgg(entries) => {
+                let entries = entries.update(json_key(key)?, to_json(new_value.clone())?);
+
+                Ok(Some(Self::JsonObjectAgg(entries)))
+            }
+            Self::Grouping(_) => Ok(None),
+            Self::Custom { name, state } => Ok(Some(Self::Custom {
+                name: name.clone(),
+                state: accumulate_aggregate(name, state.clone(), new_value)?,
+            })),
         }
     }
 
-    fn export(self) -> Result<Value> {
+    pub fn export(self) -> Result<Value> {
         let variance = |sum_square: Value, sum: Value, count: i64| {
             let sum_expr1 = sum_square.multiply(&Value::I64(count))?;
             let sum_expr2 = sum.multiply(&sum)?;
@@ -130,7 +222,11 @@ impl<'a> AggrValue {
 
         match self {
             Self::Count { count, .. } => Ok(Value::I64(count)),
-            Self::Sum(value) | Self::Min(value) | Self::Max(value) => Ok(value),
+            Self::Sum(value)
+            | Self::Min(value)
+            | Self::Max(value)
+            | Self::BoolAnd(value)
+            | Self::BoolOr(value) => Ok(value),
             Self::Avg { sum, count } => sum.divide(&Value::F64(count as f64)),
             Self::Variance {
                 sum_square,
@@ -142,14 +238,59 @@ impl<'a> AggrValue {
                 sum,
                 count,
             } => variance(sum_square, sum, count)?.sqrt(),
+            Self::ArrayAgg { order, items } => {
+                let mut items = items.into_iter().collect::<Vec<_>>();
+                // stable sort, rows with equal order keys keep their input order
+                items.sort_by(|(_, key_a), (_, key_b)| nulls::compare_value(key_a, key_b, order));
+
+                let values = items.into_iter().map(|(value, _)| value).collect();
+
+                Ok(Value::List(values))
+            }
+            Self::JsonAgg(items) => Ok(Value::List(items.into_iter().collect())),
+            Self::JsonObjectAgg(entries) => Ok(Value::Map(entries.into_iter().collect())),
+            Self::Grouping(bits) => Ok(Value::I64(bits)),
+            Self::Custom { name, state } => finalize_aggregate(&name, state),
         }
     }
 }
 
+/// Value of an aggregate which accumulated no rows, which only happens when `FILTER` drops
+/// every row of a group.
+fn empty(aggr: &Aggregate) -> Result<Value> {
+    match aggr {
+        Aggregate::Count(_) => Ok(Value::I64(0)),
+        Aggregate::Filter { aggregate, .. } => empty(aggregate),
+        Aggregate::Custom { name, .. } => finalize_aggregate(name, None),
+        _ => Ok(Value::Null),
+    }
+}
+
+/// Boolean aggregates accept only BOOLEAN values, and skip NULL.
+fn check_bool(value: Value) -> Result<Value> {
+    match value {
+        Value::Bool(_) | Value::Null => Ok(value),
+        _ => Err(AggregateError::BooleanValueRequired(value).into()),
+    }
+}
+
+/// JSON aggregates store values the way they are serialized to JSON.
+fn to_json(value: Value) -> Result<Value> {
+    JsonValue::try_from(value).and_then(Value::try_from)
+}
+
+fn json_key(key: &Value) -> Result<String> {
+    match key {
+        Value::Null => Err(AggregateError::NullJsonObjectKey.into()),
+        _ => Ok(String::from(key)),
+    }
+}
+
 pub struct State<'a> {
     index: usize,
     group: Group,
-    values: IndexMap<(Group, &'a Aggregate), (usize, AggrValue)>,
+    /// `None` for an aggregate whose `FILTER` has not passed any row of the group yet
+    values: IndexMap<(Group, &'a Aggregate), (usize, Option<AggrValue>)>,
     groups: HashSet<Group>,
     contexts: Vector<Rc<BlendContext<'a>>>,
 }
@@ -185,7 +326,7 @@ impl<'a> State<'a> {
         }
     }
 
-    fn update(self, aggr: &'a Aggregate, value: AggrValue) -> Self {
+    fn update(self, aggr: &'a Aggregate, value: Option<AggrValue>) -> Self {
         let key = (Rc::clone(&self.group), aggr);
         let (values, _) = self.values.insert(key, (self.index, value));
         Self {
@@ -197,7 +338,7 @@ impl<'a> State<'a> {
         }
     }
 
-    fn get(&self, aggr: &'a Aggregate) -> Option<&(usize, AggrValue)> {
+    fn get(&self, aggr: &'a Aggregate) -> Option<&(usize, Option<AggrValue>)> {
         let group = Rc::clone(&self.group);
 
         self.values.get(&(group, aggr))
@@ -226,8 +367,9 @@ impl<'a> State<'a> {
             .enumerate()
             .map(|(i, entries)| {
                 let aggregated = entries
-                    .map(|((_, aggr), (_, aggr_value))| {
-                        aggr_value.export().map(|value| (aggr, value))
+                    .map(|((_, aggr), (_, aggr_value))| match aggr_value {
+                        Some(aggr_value) => aggr_value.export().map(|value| (aggr, value)),
+                        None => empty(aggr).map(|value| (aggr, value)),
                     })
                     .collect::<Result<HashMap<&'a Aggregate, Value>>>()?;
                 let next = contexts.get(i).map(Rc::clone);
@@ -256,26 +398,49 @@ impl<'a> State<'a> {
             }
             _ => Err(AggregateError::OnlyIdentifierAllowed),
         };
-        let value = match aggr {
-            Aggregate::Count(CountArgExpr::Wildcard) => &Value::Null,
-            Aggregate::Count(CountArgExpr::Expr(expr))
-            | Aggregate::Sum(expr)
-            | Aggregate::Min(expr)
-            | Aggregate::Max(expr)
-            | Aggregate::Avg(expr)
-            | Aggregate::Variance(expr)
-            | Aggregate::Stdev(expr) => get_value(expr)?,
+        let target = match aggr {
+            Aggregate::Filter { aggregate, .. } => aggregate.as_ref(),
+            _ => aggr,
+        };
+        let value = match target.as_expr() {
+            Some(expr) => get_value(expr)?,
+            None => &Value::Null,
+        };
+        let key = match target {
+            Aggregate::ArrayAgg {
+                order_by: Some(OrderByExpr { expr, .. }),
+                ..
+            }
+            | Aggregate::JsonObjectAgg { key: expr, .. } => get_value(expr)?,
+            _ => &Value::Null,
         };
 
         let aggr_value = match self.get(aggr) {
             Some((index, _)) if self.index <= *index => None,
-            Some((_, aggr_value)) => aggr_value.accumulate(value)?,
-            None => Some(AggrValue::new(aggr, value)?),
+            Some((_, Some(aggr_value))) => aggr_value.accumulate_keyed(value, key)?,
+            Some((_, None)) | None => Some(AggrValue::new_keyed(aggr, value, key)?),
         };
 
         match aggr_value {
-            Some(aggr_value) => Ok(self.update(aggr, aggr_value)),
+            Some(aggr_value) => Ok(self.update(aggr, Some(aggr_value))),
             None => Ok(self),
         }
     }
+
+    /// Sets the value of `GROUPING()`, which is the same for every row of the group.
+    pub fn grouping(self, aggr: &'a Aggregate, bits: i64) -> Self {
+        match self.get(aggr) {
+            Some(_) => self,
+            None => self.update(aggr, Some(AggrValue::Grouping(bits))),
+        }
+    }
+
+    /// Keeps a place for an aggregate whose `FILTER` drops the current row, so every group
+    /// holds the same aggregates in the same order.
+    pub fn skip(self, aggr: &'a Aggregate) -> Self {
+        match self.get(aggr) {
+            Some(_) => self,
+            None => self.update(aggr, None),
+        }
+    }
 }
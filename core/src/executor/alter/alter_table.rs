@@ -1,10 +1,11 @@
 #![cfg(feature = "alter-table")]
 
 use {
-    super::validate,
+    super::{validate, AlterError},
     crate::{
-        ast::{AlterTableOperation, ObjectName},
-        data::get_name,
+        ast::{AlterTableOperation, ColumnDef, ObjectName},
+        data::{get_name, schema::ColumnDefExt, Schema},
+        executor::sequence::{auto_increment_sequence, delete_sequences, rename_sequences},
         result::{MutResult, TrySelf},
         store::{GStore, GStoreMut},
     },
@@ -12,10 +13,9 @@ use {
 
 #[cfg(feature = "index")]
 use {
-    super::AlterError,
     crate::{
-        ast::Expr,
-        data::{Schema, SchemaIndex},
+        ast::{Expr, OrderByExpr},
+        data::SchemaIndex,
     },
     futures::stream::{self, TryStreamExt},
 };
@@ -33,17 +33,190 @@ pub async fn alter_table<T: GStore + GStoreMut>(
         } => {
             let (storage, new_table_name) = get_name(new_table_name).try_self(storage)?;
 
-            storage.rename_schema(table_name, new_table_name).await
+            match storage.fetch_schema(new_table_name).await {
+                Ok(None) => {}
+                Ok(Some(_)) => {
+                    return Err((
+                        storage,
+                        AlterError::TableAlreadyExists(new_table_name.to_owned()).into(),
+                    ));
+                }
+                Err(e) => {
+                    return Err((storage, e));
+                }
+            };
+
+            let schema = match storage.fetch_schema(table_name).await {
+                Ok(schema) => schema,
+                Err(e) => {
+                    return Err((storage, e));
+                }
+            };
+
+            // index data is keyed by table name, so indexes are rebuilt under the new name
+            #[cfg(feature = "index")]
+            let indexes = schema
+                .as_ref()
+                .map(|Schema { indexes, .. }| indexes.clone())
+                .unwrap_or_default();
+
+            #[cfg(feature = "index")]
+            let storage = stream::iter(indexes.iter().map(Ok))
+                .try_fold(storage, |storage, SchemaIndex { name, .. }| async move {
+                    storage
+                        .drop_index(table_name, name)
+                        .await
+                        .map(|(storage, _)| storage)
+                })
+                .await?;
+
+            let (storage, _) = storage.rename_schema(table_name, new_table_name).await?;
+
+            let sequences = schema
+                .iter()
+                .flat_map(|Schema { column_defs, .. }| column_defs)
+                .filter(|column_def| column_def.is_auto_increment())
+                .map(|ColumnDef { name, .. }| {
+                    (
+                        auto_increment_sequence(table_name, name),
+                        auto_increment_sequence(new_table_name, name),
+                    )
+                })
+                .collect::<Vec<_>>();
+            let (storage, ()) = rename_sequences(storage, &sequences).await?;
+
+            #[cfg(feature = "index")]
+            let storage = stream::iter(indexes.iter().map(Ok))
+                .try_fold(storage, |storage, SchemaIndex { name, expr, .. }| {
+                    let column = OrderByExpr {
+                        expr: expr.clone(),
+                        asc: None,
+                        nulls_first: None,
+                        collation: None,
+                    };
+
+                    async move {
+                        storage
+                            .create_index(new_table_name, name, &column)
+                            .await
+                            .map(|(storage, _)| storage)
+                    }
+                })
+                .await?;
+
+            Ok((storage, ()))
         }
         AlterTableOperation::RenameColumn {
             old_column_name,
             new_column_name,
         } => {
-            storage
+            let schema = match storage.fetch_schema(table_name).await {
+                Ok(schema) => schema,
+                Err(e) => {
+                    return Err((storage, e));
+                }
+            };
+
+            let column_exists = schema
+                .iter()
+                .flat_map(|Schema { column_defs, .. }| column_defs)
+                .any(|ColumnDef { name, .. }| name == new_column_name);
+            if column_exists {
+                return Err((
+                    storage,
+                    AlterError::ColumnAlreadyExists(new_column_name.to_owned()).into(),
+                ));
+            }
+
+            let (storage, _) = storage
                 .rename_column(table_name, old_column_name, new_column_name)
-                .await
+                .await?;
+
+            let sequences = schema
+                .iter()
+                .flat_map(|Schema { column_defs, .. }| column_defs)
+                .filter(|column_def| {
+                    column_def.name == *old_column_name && column_def.is_auto_increment()
+                })
+                .map(|_| {
+                    (
+                        auto_increment_sequence(table_name, old_column_name),
+                        auto_increment_sequence(table_name, new_column_name),
+                    )
+                })
+                .collect::<Vec<_>>();
+            let (storage, ()) = rename_sequences(storage, &sequences).await?;
+
+            // indexes on the renamed column are rebuilt to refer to the new name
+            #[cfg(feature = "index")]
+            let (storage, _) = {
+                let indexes = schema
+                    .iter()
+                    .flat_map(|Schema { indexes, .. }| indexes)
+                    .filter(|SchemaIndex { expr, .. }| find_column(expr, old_column_name))
+                    .map(|SchemaIndex { name, expr, .. }| {
+                        (name, replace_column(expr, old_column_name, new_column_name))
+                    })
+                    .collect();
+
+                rebuild_indexes(storage, table_name, indexes).await?
+            };
+
+            Ok((storage, ()))
+        }
+        AlterTableOperation::AlterColumnType {
+            column_name,
+            data_type,
+        } => {
+            let schema = match storage.fetch_schema(table_name).await {
+                Ok(schema) => schema,
+                Err(e) => {
+                    return Err((storage, e));
+                }
+            };
+
+            let column_def = schema
+                .iter()
+                .flat_map(|Schema { column_defs, .. }| column_defs)
+                .find(|ColumnDef { name, .. }| name == column_name);
+            if let Some(column_def) = column_def {
+                let column_def = ColumnDef {
+                    data_type: data_type.clone(),
+                    ..column_def.clone()
+                };
+
+                if let Err(e) = validate(&column_def) {
+                    return Err((storage, e));
+                }
+            }
+
+            let (storage, _) = storage
+                .alter_column_type(table_name, column_name, data_type)
+                .await?;
+
+            // index data holds the old values, so the indexes on the column are rebuilt
+            #[cfg(feature = "index")]
+            let (storage, _) = {
+                let indexes = schema
+                    .iter()
+                    .flat_map(|Schema { indexes, .. }| indexes)
+                    .filter(|SchemaIndex { expr, .. }| find_column(expr, column_name))
+                    .map(|SchemaIndex { name, expr, .. }| (name, expr.clone()))
+                    .collect();
+
+                rebuild_indexes(storage, table_name, indexes).await?
+            };
+
+            Ok((storage, ()))
         }
         AlterTableOperation::AddColumn { column_def } => {
+            // the column would have to be computed for the rows the table already has
+            if column_def.get_generated().is_some() {
+                let error = AlterError::GeneratedColumnOnAddColumn(column_def.name.to_owned());
+
+                return Err((storage, error.into()));
+            }
+
             validate(column_def)
                 .try_self(storage)
                 .map(|(storage, _)| storage)?
@@ -53,6 +226,8 @@ pub async fn alter_table<T: GStore + GStoreMut>(
         AlterTableOperation::DropColumn {
             column_name,
             if_exists,
+            #[cfg_attr(not(feature = "index"), allow(unused_variables))]
+            cascade,
         } => {
             #[cfg(feature = "index")]
             let storage = {
@@ -72,9 +247,20 @@ pub async fn alter_table<T: GStore + GStoreMut>(
                 let indexes = indexes
                     .iter()
                     .filter(|SchemaIndex { expr, .. }| find_column(expr, column_name))
-                    .map(Ok);
+                    .collect::<Vec<_>>();
 
-                stream::iter(indexes)
+                // RESTRICT is the default, the indexes on the column are only dropped with it
+                // when CASCADE is given
+                if let Some(SchemaIndex { name, .. }) = indexes.first().filter(|_| !*cascade) {
+                    let error = AlterError::ColumnUsedByIndex {
+                        column: column_name.to_owned(),
+                        index: name.to_owned(),
+                    };
+
+                    return Err((storage, error.into()));
+                }
+
+                stream::iter(indexes.into_iter().map(Ok))
                     .try_fold(storage, |storage, SchemaIndex { name, .. }| async move {
                         storage
                             .drop_index(table_name, name)
@@ -84,9 +270,11 @@ pub async fn alter_table<T: GStore + GStoreMut>(
                     .await?
             };
 
-            storage
+            let (storage, ()) = storage
                 .drop_column(table_name, column_name, *if_exists)
-                .await
+                .await?;
+
+            delete_sequences(storage, &[auto_increment_sequence(table_name, column_name)]).await
         }
     }
 }
@@ -101,6 +289,64 @@ fn find_column(expr: &Expr, column_name: &str) -> bool {
         Expr::BinaryOp { left, right, .. } => find(left) || find(right),
         Expr::UnaryOp { expr, .. } => find(expr),
         Expr::Cast { expr, .. } => find(expr),
+        Expr::Tuple(exprs) => exprs.iter().any(find),
         _ => false,
     }
 }
+
+#[cfg(feature = "index")]
+async fn rebuild_indexes<T: GStore + GStoreMut>(
+    storage: T,
+    table_name: &str,
+    indexes: Vec<(&String, Expr)>,
+) -> MutResult<T, ()> {
+    stream::iter(indexes.into_iter().map(Ok))
+        .try_fold(storage, |storage, (name, expr)| async move {
+            let column = OrderByExpr {
+                expr,
+                asc: None,
+                nulls_first: None,
+                collation: None,
+            };
+            let (storage, _) = storage.drop_index(table_name, name).await?;
+
+            storage
+                .create_index(table_name, name, &column)
+                .await
+                .map(|(storage, _)| storage)
+        })
+        .await
+        .map(|storage| (storage, ()))
+}
+
+#[cfg(feature = "index")]
+fn replace_column(expr: &Expr, old_column_name: &str, new_column_name: &str) -> Expr {
+    let replace = |expr| Box::new(replace_column(expr, old_column_name, new_column_name));
+
+    match expr {
+        Expr::Identifier(ident) if ident == old_column_name => {
+            Expr::Identifier(new_column_name.to_owned())
+        }
+        Expr::Nested(expr) => Expr::Nested(replace(expr)),
+        Expr::BinaryOp { left, op, right } => Expr::BinaryOp {
+            left: replace(left),
+            op: op.clone(),
+            right: replace(right),
+        },
+        Expr::UnaryOp { op, expr } => Expr::UnaryOp {
+            op: op.clone(),
+            expr: replace(expr),
+        },
+        Expr::Cast { expr, data_type } => Expr::Cast {
+            expr: replace(expr),
+            data_type: data_type.clone(),
+        },
+        Expr::Tuple(exprs) => Expr::Tuple(
+            exprs
+                .iter()
+                .map(|expr| replace_column(expr, old_column_name, new_column_name))
+                .collect(),
+        ),
+        _ => expr.clone(),
+    }
+}
@@ -18,10 +18,40 @@ pub enum AlterError {
     #[error("CTAS source table does not exist: {0}")]
     CtasSourceTableNotFound(String),
 
+    // CREATE MATERIALIZED VIEW, REFRESH MATERIALIZED VIEW
+    #[error("materialized view does not exist: {0}")]
+    MaterializedViewNotFound(String),
+
+    #[error("view has {expected} columns but its query returns {found} columns")]
+    ViewColumnCountMismatch { expected: usize, found: usize },
+
+    // ALTER TABLE
+    #[error("column already exists: {0}")]
+    ColumnAlreadyExists(String),
+
+    #[error("column '{column}' is used by index '{index}', drop it with CASCADE")]
+    ColumnUsedByIndex { column: String, index: String },
+
+    // fold identifier names
+    #[error("'{0}' and '{1}' would have the same name once folded to lowercase")]
+    FoldedNameConflict(String, String),
+
     // validate column def
     #[error("column '{0}' of data type '{1:?}' is unsupported for unique constraint")]
     UnsupportedDataTypeForUniqueColumn(String, DataType),
 
+    #[error("column '{0}' of data type '{1:?}' is unsupported for collation")]
+    UnsupportedDataTypeForCollation(String, DataType),
+
+    #[error("column '{0}' of data type '{1:?}' is unsupported for auto increment")]
+    UnsupportedDataTypeForAutoIncrement(String, DataType),
+
+    #[error("generated column '{0}' can only read other columns of its row: {1:#?}")]
+    UnsupportedGeneratedColumnExpr(String, Expr),
+
+    #[error("generated column can not be added to an existing table: {0}")]
+    GeneratedColumnOnAddColumn(String),
+
     // validate index expr
     #[error("unsupported index expr: {0:#?}")]
     UnsupportedIndexExpr(Expr),
@@ -0,0 +1,528 @@
+#![cfg(feature = "metadata")]
+
+use {
+    super::AlterError,
+    crate::{
+        ast::{
+            Aggregate, ColumnDef, ColumnOption, ColumnOptionDef, CountArgExpr, Cte, Expr,
+            FunctionArg, GroupingSets, IndexCmp, IndexItem, Join, JoinConstraint, JoinExecutor,
+            JoinOperator, ObjectName, OrderByExpr, Query, RecursiveTerm, Select, SelectItem,
+            SetExpr, TableAlias, TableFactor, TableSample, TableWithJoins, Values, Window,
+            WindowFunction, WindowSpec,
+        },
+        data::{schema::ColumnDefExt, Row, Schema, SchemaFunction, SchemaIndex, SchemaView},
+        executor::{
+            execute::autocommit,
+            sequence::{auto_increment_sequence, rename_sequences},
+        },
+        result::{MutResult, Result, TrySelf},
+        store::{GStore, GStoreMut},
+    },
+    futures::stream::{self, TryStreamExt},
+    std::{collections::HashMap, ops::Bound},
+};
+
+/// Folds the names of the tables, views, columns and indexes the storage keeps, and the
+/// identifiers of their stored expressions and queries and of the functions, to lowercase, so a
+/// database made without `ParseOptions::fold_identifiers` can be used with it.
+///
+/// Only the names which could be written unquoted are folded, `Item` becomes `item` while
+/// `Full Name` is kept. A table is rewritten with its rows, which are read into memory one table
+/// at a time. Sequences made by `CREATE SEQUENCE` are not listed by the storage and keep their
+/// names. Nothing is changed if two names would be the same once folded.
+pub async fn fold_identifier_names<T: GStore + GStoreMut>(storage: T) -> MutResult<T, ()> {
+    autocommit(storage, |storage| async move {
+        let schemas = fetch_schemas(&storage).await;
+        let (storage, schemas) = schemas.try_self(storage)?;
+        let functions = storage.fetch_functions().await;
+        let (storage, functions) = functions.try_self(storage)?;
+        let (storage, ()) = check_conflicts(&schemas, &functions).try_self(storage)?;
+
+        let storage = stream::iter(schemas.into_iter().map(Ok))
+            .try_fold(storage, |storage, schema| async move {
+                fold_table(storage, schema)
+                    .await
+                    .map(|(storage, ())| storage)
+            })
+            .await?;
+
+        stream::iter(functions.into_iter().map(Ok))
+            .try_fold((storage, ()), |(storage, ()), function| async move {
+                let folded = fold_function(&function);
+                if folded == function {
+                    return Ok((storage, ()));
+                }
+
+                let (storage, ()) = storage.delete_function(&function.name).await?;
+
+                storage.insert_function(&folded).await
+            })
+            .await
+    })
+    .await
+}
+
+async fn fetch_schemas(storage: &impl GStore) -> Result<Vec<Schema>> {
+    let mut schemas = Vec::new();
+
+    for table_name in storage.schema_names().await? {
+        if let Some(schema) = storage.fetch_schema(&table_name).await? {
+            schemas.push(schema);
+        }
+    }
+
+    Ok(schemas)
+}
+
+fn check_conflicts(schemas: &[Schema], functions: &[SchemaFunction]) -> Result<()> {
+    check_names(schemas.iter().map(|schema| schema.table_name.as_str()))?;
+
+    for schema in schemas {
+        check_names(schema.column_names())?;
+        check_names(schema.indexes.iter().map(|index| index.name.as_str()))?;
+    }
+
+    for SchemaFunction { args, .. } in functions {
+        check_names(args.iter().map(|arg| arg.name.as_str()))?;
+    }
+
+    Ok(())
+}
+
+/// Fails if two of `names` would be the same once folded.
+fn check_names<'a>(names: impl IntoIterator<Item = &'a str>) -> Result<()> {
+    let mut folded = HashMap::new();
+
+    for name in names {
+        if let Some(other) = folded.insert(fold_name(name), name) {
+            let error = AlterError::FoldedNameConflict(other.to_owned(), name.to_owned());
+
+            return Err(error.into());
+        }
+    }
+
+    Ok(())
+}
+
+/// Rewrites the table of `schema` with its rows under the folded names, unless none of them
+/// changes.
+async fn fold_table<T: GStore + GStoreMut>(storage: T, schema: Schema) -> MutResult<T, ()> {
+    let folded = fold_schema(&schema);
+    if folded == schema {
+        return Ok((storage, ()));
+    }
+
+    let rows = match schema.view {
+        Some(_) => Ok(Vec::new()),
+        None => storage
+            .scan_data(&schema.table_name)
+            .await
+            .and_then(|rows| {
+                rows.map(|item| item.map(|(_, row)| row))
+                    .collect::<Result<Vec<Row>>>()
+            }),
+    };
+    let (storage, rows) = rows.try_self(storage)?;
+    let table_name = folded.table_name.as_str();
+
+    // the indexes are built again once the rows are back
+    let (storage, ()) = storage.delete_schema(&schema.table_name).await?;
+    let (storage, ()) = storage
+        .insert_schema(&Schema {
+            indexes: Vec::new(),
+            ..folded.clone()
+        })
+        .await?;
+    let (storage, ()) = storage.insert_data(table_name, rows).await?;
+
+    #[cfg(feature = "index")]
+    let storage = stream::iter(folded.indexes.iter().map(Ok))
+        .try_fold(storage, |storage, SchemaIndex { name, expr, .. }| {
+            let column = OrderByExpr {
+                expr: expr.clone(),
+                asc: None,
+                nulls_first: None,
+                collation: None,
+            };
+
+            async move {
+                storage
+                    .create_index(table_name, name, &column)
+                    .await
+                    .map(|(storage, _)| storage)
+            }
+        })
+        .await?;
+
+    let sequences = schema
+        .column_defs
+        .iter()
+        .zip(&folded.column_defs)
+        .filter(|(column_def, _)| column_def.is_auto_increment())
+        .map(|(column_def, folded_def)| {
+            (
+                auto_increment_sequence(&schema.table_name, &column_def.name),
+                auto_increment_sequence(table_name, &folded_def.name),
+            )
+        })
+        .filter(|(from, to)| from != to)
+        .collect::<Vec<_>>();
+
+    rename_sequences(storage, &sequences).await
+}
+
+/// `name` in lowercase if it could be written unquoted, otherwise as it is.
+fn fold_name(name: &str) -> String {
+    let mut chars = name.chars();
+    let unquoted = matches!(chars.next(), Some(c) if c.is_alphabetic() || c == '_')
+        && chars.all(|c| c.is_alphanumeric() || c == '_');
+
+    match unquoted {
+        true => name.to_lowercase(),
+        false => name.to_owned(),
+    }
+}
+
+fn fold(name: &mut String) {
+    *name = fold_name(name);
+}
+
+fn fold_schema(schema: &Schema) -> Schema {
+    let mut schema = schema.clone();
+    let Schema {
+        table_name,
+        column_defs,
+        indexes,
+        materialized_view,
+        view,
+    } = &mut schema;
+
+    fold(table_name);
+
+    for ColumnDef { name, options, .. } in column_defs {
+        fold(name);
+
+        for ColumnOptionDef { name, option } in options {
+            name.iter_mut().for_each(fold);
+
+            if let ColumnOption::Default(expr) | ColumnOption::Generated(expr) = option {
+                fold_expr(expr);
+            }
+        }
+    }
+
+    for SchemaIndex { name, expr, .. } in indexes {
+        fold(name);
+        fold_expr(expr);
+    }
+
+    materialized_view.iter_mut().for_each(fold_query);
+
+    if let Some(SchemaView { columns, query }) = view {
+        columns.iter_mut().for_each(fold);
+        fold_query(query);
+    }
+
+    schema
+}
+
+fn fold_function(function: &SchemaFunction) -> SchemaFunction {
+    let mut function = function.clone();
+
+    for FunctionArg { name, .. } in function.args.iter_mut() {
+        fold(name);
+    }
+    fold_expr(&mut function.body);
+
+    function
+}
+
+fn fold_query(query: &mut Query) {
+    let Query {
+        with,
+        body,
+        limit,
+        offset,
+    } = query;
+
+    for Cte {
+        alias,
+        query,
+        recursive,
+    } in with
+    {
+        fold_alias(alias);
+        fold_query(query);
+
+        if let Some(RecursiveTerm { query, .. }) = recursive {
+            fold_query(query);
+        }
+    }
+
+    match body {
+        SetExpr::Select(select) => fold_select(select),
+        SetExpr::Values(Values(values_list)) => {
+            values_list.iter_mut().flatten().for_each(fold_expr);
+        }
+    }
+
+    limit
+        .iter_mut()
+        .chain(offset.iter_mut())
+        .for_each(fold_expr);
+}
+
+fn fold_select(select: &mut Select) {
+    let Select {
+        projection,
+        from: TableWithJoins { relation, joins },
+        selection,
+        group_by,
+        grouping_sets,
+        having,
+        order_by,
+        distinct_on,
+    } = select;
+
+    for select_item in projection {
+        match select_item {
+            SelectItem::Expr { expr, label } => {
+                fold_expr(expr);
+                fold(label);
+            }
+            SelectItem::QualifiedWildcard(ObjectName(names)) => names.iter_mut().for_each(fold),
+            SelectItem::Wildcard => {}
+        }
+    }
+
+    fold_table_factor(relation);
+
+    for Join {
+        relation,
+        join_operator,
+        join_executor,
+    } in joins
+    {
+        fold_table_factor(relation);
+
+        if let JoinOperator::Inner(JoinConstraint::On(expr))
+        | JoinOperator::LeftOuter(JoinConstraint::On(expr)) = join_operator
+        {
+            fold_expr(expr);
+        }
+
+        if let JoinExecutor::Hash {
+            key_expr,
+            value_expr,
+            where_clause,
+        } = join_executor
+        {
+            fold_expr(key_expr);
+            fold_expr(value_expr);
+            where_clause.iter_mut().for_each(fold_expr);
+        }
+    }
+
+    match grouping_sets {
+        Some(GroupingSets::Cube(exprs)) => exprs.iter_mut().for_each(fold_expr),
+        Some(GroupingSets::Sets(sets)) => sets.iter_mut().flatten().for_each(fold_expr),
+        None => {}
+    }
+
+    selection
+        .iter_mut()
+        .chain(group_by.iter_mut())
+        .chain(having.iter_mut())
+        .chain(distinct_on.iter_mut())
+        .chain(order_by.iter_mut().map(|OrderByExpr { expr, .. }| expr))
+        .for_each(fold_expr);
+}
+
+fn fold_table_factor(table_factor: &mut TableFactor) {
+    match table_factor {
+        TableFactor::Table {
+            name: ObjectName(names),
+            alias,
+            index,
+            sample,
+        } => {
+            names.iter_mut().for_each(fold);
+            alias.iter_mut().for_each(fold_alias);
+
+            if let Some(IndexItem { name, cmp_expr, .. }) = index {
+                fold(name);
+
+                match cmp_expr {
+                    Some(IndexCmp::Op(_, expr)) => fold_expr(expr),
+                    Some(IndexCmp::In(exprs) | IndexCmp::Prefix(exprs)) => {
+                        exprs.iter_mut().for_each(fold_expr);
+                    }
+                    Some(IndexCmp::Range { start, end }) => {
+                        for bound in [start, end] {
+                            if let Bound::Included(expr) | Bound::Excluded(expr) = bound {
+                                fold_expr(expr);
+                            }
+                        }
+                    }
+                    None => {}
+                }
+            }
+
+            if let Some(TableSample { percentage, seed }) = sample {
+                fold_expr(percentage);
+                seed.iter_mut().for_each(fold_expr);
+            }
+        }
+        TableFactor::Derived {
+            subquery, alias, ..
+        } => {
+            fold_query(subquery);
+            fold_alias(alias);
+        }
+        TableFactor::Series {
+            alias,
+            start,
+            end,
+            step,
+        } => {
+            fold_alias(alias);
+            fold_expr(start);
+            fold_expr(end);
+            step.iter_mut().for_each(fold_expr);
+        }
+    }
+}
+
+fn fold_alias(TableAlias { name, columns }: &mut TableAlias) {
+    fold(name);
+    columns.iter_mut().for_each(fold);
+}
+
+fn fold_expr(expr: &mut Expr) {
+    match expr {
+        Expr::Identifier(name) => fold(name),
+        Expr::CompoundIdentifier(names) => names.iter_mut().for_each(fold),
+        Expr::IsNull(expr)
+        | Expr::IsNotNull(expr)
+        | Expr::Nested(expr)
+        | Expr::UnaryOp { expr, .. }
+        | Expr::Cast { expr, .. }
+        | Expr::Extract { expr, .. } => fold_expr(expr),
+        Expr::InList { expr, list, .. } => {
+            fold_expr(expr);
+            list.iter_mut().for_each(fold_expr);
+        }
+        Expr::InSubquery { expr, subquery, .. } => {
+            fold_expr(expr);
+            fold_query(subquery);
+        }
+        Expr::Between {
+            expr, low, high, ..
+        } => {
+            fold_expr(expr);
+            fold_expr(low);
+            fold_expr(high);
+        }
+        Expr::BinaryOp { left, right, .. }
+        | Expr::AnyOp { left, right, .. }
+        | Expr::AllOp { left, right, .. } => {
+            fold_expr(left);
+            fold_expr(right);
+        }
+        Expr::Tuple(exprs) => exprs.iter_mut().for_each(fold_expr),
+        Expr::Literal(_) | Expr::Placeholder(_) | Expr::TypedString { .. } => {}
+        Expr::Function(function) => function.as_exprs_mut().for_each(fold_expr),
+        Expr::Aggregate(aggregate) => fold_aggregate(aggregate),
+        Expr::Window(window) => fold_window(window),
+        Expr::Exists(query) | Expr::Subquery(query) => fold_query(query),
+        Expr::Case {
+            operand,
+            when_then,
+            else_result,
+        } => {
+            for (when, then) in when_then {
+                fold_expr(when);
+                fold_expr(then);
+            }
+
+            operand
+                .iter_mut()
+                .chain(else_result.iter_mut())
+                .for_each(|expr| fold_expr(expr));
+        }
+    }
+}
+
+fn fold_aggregate(aggregate: &mut Aggregate) {
+    match aggregate {
+        Aggregate::Count(CountArgExpr::Wildcard) => {}
+        Aggregate::Count(CountArgExpr::Expr(expr))
+        | Aggregate::Sum(expr)
+        | Aggregate::Max(expr)
+        | Aggregate::Min(expr)
+        | Aggregate::Avg(expr)
+        | Aggregate::Variance(expr)
+        | Aggregate::Stdev(expr)
+        | Aggregate::BoolAnd(expr)
+        | Aggregate::BoolOr(expr)
+        | Aggregate::JsonAgg(expr)
+        | Aggregate::Custom { expr, .. } => fold_expr(expr),
+        Aggregate::ArrayAgg { expr, order_by } => {
+            fold_expr(expr);
+
+            if let Some(OrderByExpr { expr, .. }) = order_by {
+                fold_expr(expr);
+            }
+        }
+        Aggregate::JsonObjectAgg { key, value } => {
+            fold_expr(key);
+            fold_expr(value);
+        }
+        Aggregate::Filter { aggregate, filter } => {
+            fold_aggregate(aggregate);
+            fold_expr(filter);
+        }
+        Aggregate::Grouping(exprs) => exprs.iter_mut().for_each(fold_expr),
+    }
+}
+
+fn fold_window(window: &mut Window) {
+    let Window {
+        function,
+        over: WindowSpec {
+            partition_by,
+            order_by,
+            ..
+        },
+    } = window;
+
+    match function {
+        WindowFunction::FirstValue(expr) | WindowFunction::LastValue(expr) => fold_expr(expr),
+        WindowFunction::NthValue { expr, n } => {
+            fold_expr(expr);
+            fold_expr(n);
+        }
+        WindowFunction::Lag {
+            expr,
+            offset,
+            default,
+        }
+        | WindowFunction::Lead {
+            expr,
+            offset,
+            default,
+        } => {
+            fold_expr(expr);
+            offset
+                .iter_mut()
+                .chain(default.iter_mut())
+                .for_each(fold_expr);
+        }
+        WindowFunction::Aggregate(aggregate) => fold_aggregate(aggregate),
+    }
+
+    partition_by
+        .iter_mut()
+        .chain(order_by.iter_mut().map(|OrderByExpr { expr, .. }| expr))
+        .for_each(fold_expr);
+}
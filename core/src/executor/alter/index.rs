@@ -29,11 +29,19 @@ pub async fn create_index<T: GStore + GStoreMut>(
             .map(|ColumnDef { name, .. }| name)
             .collect::<Vec<_>>();
 
-        let (valid, has_ident) = validate_index_expr(&columns, expr);
-        if !valid {
-            return Err(AlterError::UnsupportedIndexExpr(expr.clone()).into());
-        } else if !has_ident {
-            return Err(AlterError::IdentifierNotFound(expr.clone()).into());
+        // each column of a composite index is validated on its own
+        let exprs: Vec<&Expr> = match expr {
+            Expr::Tuple(exprs) => exprs.iter().collect(),
+            expr => vec![expr],
+        };
+
+        for expr in exprs {
+            let (valid, has_ident) = validate_index_expr(&columns, expr);
+            if !valid {
+                return Err(AlterError::UnsupportedIndexExpr(expr.clone()).into());
+            } else if !has_ident {
+                return Err(AlterError::IdentifierNotFound(expr.clone()).into());
+            }
         }
 
         Ok((table_name, index_name))
@@ -1,14 +1,21 @@
 mod alter_table;
 mod error;
+mod fold;
 mod index;
 mod table;
 mod validate;
+mod view;
 
-use validate::validate;
+use validate::{validate, validate_generated};
 
 #[cfg(feature = "alter-table")]
 pub use alter_table::alter_table;
 pub use error::AlterError;
+#[cfg(feature = "metadata")]
+pub use fold::fold_identifier_names;
 #[cfg(feature = "index")]
 pub use index::{create_index, drop_index};
-pub use table::{create_table, drop_table};
+pub use {
+    table::{create_table, drop_table, truncate_table},
+    view::{create_materialized_view, create_view, refresh_materialized_view},
+};
@@ -1,15 +1,32 @@
 use {
-    super::{validate, AlterError},
+    super::{validate, validate_generated, AlterError},
     crate::{
-        ast::{ColumnDef, ObjectName, Query, SetExpr, TableFactor},
-        data::{get_name, Schema, TableError},
-        executor::select::select,
-        result::{Error, MutResult, TrySelf},
+        ast::{
+            ColumnDef, ColumnOption, ColumnOptionDef, DataType, Join, ObjectName, Query, SetExpr,
+            TableFactor,
+        },
+        data::{get_name, Row, Schema},
+        executor::{
+            cancel::Cancellation,
+            execute::check_writable,
+            metrics::Metrics,
+            select::select_with_labels,
+            sequence::{auto_increment_sequences, delete_sequences},
+        },
+        result::{Error, MutResult, Result, TrySelf},
         store::{GStore, GStoreMut},
     },
     futures::stream::{self, TryStreamExt},
+    std::{collections::HashMap, iter::once},
 };
 
+#[cfg(feature = "metadata")]
+use crate::data::InformationSchema;
+
+/// Type of the columns `CREATE TABLE AS SELECT` finds no value for, unless a source table has a
+/// column of the same name.
+const DEFAULT_INFERRED_TYPE: DataType = DataType::Text;
+
 pub async fn create_table<T: GStore + GStoreMut>(
     storage: T,
     name: &ObjectName,
@@ -17,73 +34,154 @@ pub async fn create_table<T: GStore + GStoreMut>(
     if_not_exists: bool,
     source: &Option<Box<Query>>,
 ) -> MutResult<T, ()> {
-    let (storage, target_table_name) = get_name(name).try_self(storage)?;
-    let schema = (|| async {
-        let target_columns_defs = match source.as_ref().map(AsRef::as_ref) {
-            Some(Query {
-                body: SetExpr::Select(select_query),
-                ..
-            }) => {
-                if let TableFactor::Table {
-                    name: source_name, ..
-                } = &select_query.from.relation
-                {
-                    let table_name = get_name(source_name)?;
-                    let schema = storage.fetch_schema(table_name).await?;
-                    let Schema {
-                        column_defs: source_column_defs,
-                        ..
-                    } = schema.ok_or_else(|| -> Error {
-                        AlterError::CtasSourceTableNotFound(table_name.to_owned()).into()
-                    })?;
-                    source_column_defs
-                } else {
-                    return Err(Error::Table(TableError::Unreachable));
-                }
+    let table: Result<Option<(Schema, Option<Vec<Row>>)>> = (|| async {
+        let table_name = get_name(name)?;
+
+        match (storage.fetch_schema(table_name).await?, if_not_exists) {
+            (None, _) => {}
+            (Some(_), true) => return Ok(None),
+            (Some(_), false) => {
+                return Err(AlterError::TableAlreadyExists(table_name.to_owned()).into());
             }
-            _ => column_defs.to_vec(),
-        };
+        }
 
-        let schema = Schema {
-            table_name: target_table_name.to_string(),
-            column_defs: target_columns_defs,
-            indexes: vec![],
+        let (column_defs, rows) = match source.as_deref() {
+            Some(query) => {
+                let source_types = fetch_source_types(&storage, query).await?;
+                let (labels, rows) = select_with_labels(
+                    &storage,
+                    query,
+                    None,
+                    true,
+                    Cancellation::default(),
+                    Metrics::default(),
+                )
+                .await?;
+                let rows = rows.try_collect::<Vec<_>>().await?;
+
+                (infer_column_defs(labels, &rows, &source_types), Some(rows))
+            }
+            None => (column_defs.to_vec(), None),
         };
 
-        for column_def in &schema.column_defs {
+        for column_def in &column_defs {
             validate(column_def)?;
         }
 
-        match (
-            storage.fetch_schema(&schema.table_name).await?,
-            if_not_exists,
-        ) {
-            (None, _) => Ok(Some(schema)),
-            (Some(_), true) => Ok(None),
-            (Some(_), false) => {
-                Err(AlterError::TableAlreadyExists(schema.table_name.to_owned()).into())
-            }
-        }
+        validate_generated(table_name, &column_defs)?;
+
+        let schema = Schema {
+            table_name: table_name.to_owned(),
+            column_defs,
+            indexes: vec![],
+            materialized_view: None,
+            view: None,
+        };
+
+        Ok(Some((schema, rows)))
     })()
     .await;
 
-    let storage = match schema.try_self(storage)? {
-        (storage, Some(schema)) => storage.insert_schema(&schema).await?.0,
-        (storage, None) => storage,
+    let (storage, (schema, rows)) = match table.try_self(storage)? {
+        (storage, None) => return Ok((storage, ())),
+        (storage, Some(table)) => (storage, table),
     };
+    let (storage, ()) = storage.insert_schema(&schema).await?;
 
-    match source {
-        Some(q) => {
-            let (storage, rows) = async { select(&storage, q, None).await?.try_collect().await }
-                .await
-                .try_self(storage)?;
-
-            storage.insert_data(target_table_name, rows).await
-        }
+    match rows {
+        Some(rows) => storage.insert_data(&schema.table_name, rows).await,
         None => Ok((storage, ())),
     }
 }
 
+/// Nullable column definitions of the rows a query returns, typed by the first non-NULL value of
+/// each column. A column of only NULLs takes the type of the source column of the same name in
+/// `source_types`, or `DEFAULT_INFERRED_TYPE` if there is none.
+pub(super) fn infer_column_defs(
+    labels: Vec<String>,
+    rows: &[Row],
+    source_types: &HashMap<String, DataType>,
+) -> Vec<ColumnDef> {
+    labels
+        .into_iter()
+        .enumerate()
+        .map(|(i, name)| {
+            let data_type = rows
+                .iter()
+                .find_map(|Row(values)| values.get(i)?.get_type())
+                .or_else(|| source_types.get(&name).cloned())
+                .unwrap_or(DEFAULT_INFERRED_TYPE);
+
+            ColumnDef {
+                name,
+                data_type,
+                options: vec![ColumnOptionDef {
+                    name: None,
+                    option: ColumnOption::Null,
+                }],
+            }
+        })
+        .collect()
+}
+
+/// Types of the columns of the tables a query selects from, by column name.
+pub(super) async fn fetch_source_types(
+    storage: &dyn GStore,
+    query: &Query,
+) -> Result<HashMap<String, DataType>> {
+    let select = match &query.body {
+        SetExpr::Select(select) => select,
+        SetExpr::Values(_) => return Ok(HashMap::new()),
+    };
+
+    let relations = once(&select.from.relation)
+        .chain(
+            select
+                .from
+                .joins
+                .iter()
+                .map(|Join { relation, .. }| relation),
+        )
+        .filter_map(|relation| match relation {
+            TableFactor::Table { name, .. } => Some(name),
+            TableFactor::Derived { .. } | TableFactor::Series { .. } => None,
+        });
+
+    let column_defs = stream::iter(relations.map(Ok::<_, Error>))
+        .and_then(|name| async move {
+            #[cfg(feature = "metadata")]
+            if InformationSchema::from_name(name).is_some() {
+                return Ok(Vec::new());
+            }
+
+            let table_name = get_name(name)?;
+            let schema = storage
+                .fetch_schema(table_name)
+                .await?
+                .ok_or_else(|| -> Error {
+                    AlterError::CtasSourceTableNotFound(table_name.to_owned()).into()
+                })?;
+
+            Ok(schema.column_defs)
+        })
+        .try_collect::<Vec<_>>()
+        .await?;
+
+    // the first table with a column of the name wins, so later ones are inserted first
+    let source_types = column_defs
+        .into_iter()
+        .flatten()
+        .rev()
+        .map(
+            |ColumnDef {
+                 name, data_type, ..
+             }| (name, data_type),
+        )
+        .collect();
+
+    Ok(source_types)
+}
+
 pub async fn drop_table<T: GStore + GStoreMut>(
     storage: T,
     table_names: &[ObjectName],
@@ -96,21 +194,57 @@ pub async fn drop_table<T: GStore + GStoreMut>(
                 let schema = storage.fetch_schema(table_name).await?;
 
                 if !if_exists {
-                    schema.ok_or_else(|| AlterError::TableNotFound(table_name.to_owned()))?;
+                    schema
+                        .as_ref()
+                        .ok_or_else(|| AlterError::TableNotFound(table_name.to_owned()))?;
                 }
 
-                Ok(table_name)
+                let sequences = schema
+                    .map(|Schema { column_defs, .. }| {
+                        auto_increment_sequences(table_name, &column_defs)
+                    })
+                    .unwrap_or_default();
+
+                Ok((table_name, sequences))
             })()
             .await;
 
-            let schema = match schema {
+            let (schema, sequences) = match schema {
                 Ok(s) => s,
                 Err(e) => {
                     return Err((storage, e));
                 }
             };
 
-            storage.delete_schema(schema).await
+            let (storage, ()) = storage.delete_schema(schema).await?;
+
+            delete_sequences(storage, &sequences).await
         })
         .await
 }
+
+/// Deletes every row of the table and restarts its AUTOINCREMENT columns from 1.
+pub async fn truncate_table<T: GStore + GStoreMut>(
+    storage: T,
+    table_name: &ObjectName,
+) -> MutResult<T, ()> {
+    let (storage, (table_name, sequences)) = async {
+        let table_name = get_name(table_name)?;
+        let schema = storage
+            .fetch_schema(table_name)
+            .await?
+            .ok_or_else(|| AlterError::TableNotFound(table_name.to_owned()))?;
+        check_writable(&schema)?;
+
+        Ok((
+            table_name,
+            auto_increment_sequences(table_name, &schema.column_defs),
+        ))
+    }
+    .await
+    .try_self(storage)?;
+
+    let (storage, ()) = storage.truncate_data(table_name).await?;
+
+    delete_sequences(storage, &sequences).await
+}
@@ -1,10 +1,13 @@
 use {
     super::AlterError,
     crate::{
-        ast::{ColumnDef, ColumnOption, ColumnOptionDef, DataType},
+        ast::{Collation, ColumnDef, ColumnOption, ColumnOptionDef, DataType},
+        data::schema::ColumnDefExt,
         executor::evaluate_stateless,
+        plan::{check_evaluable, Context},
         result::Result,
     },
+    std::rc::Rc,
 };
 
 pub fn validate(column_def: &ColumnDef) -> Result<()> {
@@ -28,6 +31,24 @@ pub fn validate(column_def: &ColumnDef) -> Result<()> {
         .into());
     }
 
+    // collation + data type
+    if data_type != &DataType::Text && column_def.get_collation() != Collation::Binary {
+        return Err(AlterError::UnsupportedDataTypeForCollation(
+            name.to_string(),
+            data_type.clone(),
+        )
+        .into());
+    }
+
+    // auto increment + data type
+    if data_type != &DataType::Int && column_def.is_auto_increment() {
+        return Err(AlterError::UnsupportedDataTypeForAutoIncrement(
+            name.to_string(),
+            data_type.clone(),
+        )
+        .into());
+    }
+
     let default = options
         .iter()
         .find_map(|ColumnOptionDef { option, .. }| match option {
@@ -41,3 +62,31 @@ pub fn validate(column_def: &ColumnDef) -> Result<()> {
 
     Ok(())
 }
+
+/// Checks that the expressions of generated columns read nothing but the columns of the same row
+/// which are not generated themselves.
+pub fn validate_generated(table_name: &str, column_defs: &[ColumnDef]) -> Result<()> {
+    let columns = column_defs
+        .iter()
+        .filter(|column_def| column_def.get_generated().is_none())
+        .map(|ColumnDef { name, .. }| name.as_str())
+        .collect();
+    let context = Rc::new(Context::new(table_name.to_owned(), columns, None, None));
+
+    for column_def in column_defs {
+        let expr = match column_def.get_generated() {
+            Some(expr) => expr,
+            None => continue,
+        };
+
+        if !check_evaluable(Some(Rc::clone(&context)), expr) {
+            return Err(AlterError::UnsupportedGeneratedColumnExpr(
+                column_def.name.to_owned(),
+                expr.clone(),
+            )
+            .into());
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,207 @@
+use {
+    super::{
+        table::{fetch_source_types, infer_column_defs},
+        AlterError,
+    },
+    crate::{
+        ast::{ColumnDef, ObjectName, Query},
+        data::{get_name, Key, Row, Schema, SchemaView},
+        executor::{cancel::Cancellation, metrics::Metrics, select::select_with_labels},
+        result::{MutResult, Result, TrySelf},
+        store::{GStore, GStoreMut},
+    },
+    futures::stream::TryStreamExt,
+};
+
+pub async fn create_view<T: GStore + GStoreMut>(
+    storage: T,
+    name: &ObjectName,
+    columns: &[String],
+    query: &Query,
+    cancellation: &Cancellation,
+    metrics: &Metrics,
+) -> MutResult<T, ()> {
+    let (storage, schema) = define(&storage, name, columns, query, cancellation, metrics)
+        .await
+        .try_self(storage)?;
+
+    storage.insert_schema(&schema).await
+}
+
+pub async fn create_materialized_view<T: GStore + GStoreMut>(
+    storage: T,
+    name: &ObjectName,
+    columns: &[String],
+    query: &Query,
+    cancellation: &Cancellation,
+    metrics: &Metrics,
+) -> MutResult<T, ()> {
+    let materialized = materialize(&storage, name, columns, query, cancellation, metrics).await;
+    let (storage, (schema, rows)) = materialized.try_self(storage)?;
+
+    let (storage, ()) = storage.insert_schema(&schema).await?;
+
+    storage.insert_data(&schema.table_name, rows).await
+}
+
+/// Replaces the rows of a materialized view with the current result of its query, and returns
+/// the number of rows it holds now.
+pub async fn refresh_materialized_view<T: GStore + GStoreMut>(
+    storage: T,
+    name: &ObjectName,
+    cancellation: &Cancellation,
+    metrics: &Metrics,
+) -> MutResult<T, usize> {
+    let refreshed = refresh(&storage, name, cancellation, metrics).await;
+    let (storage, (table_name, keys, rows)) = refreshed.try_self(storage)?;
+    let num_rows = rows.len();
+
+    let (storage, ()) = storage.delete_data(table_name, keys).await?;
+
+    storage
+        .insert_data(table_name, rows)
+        .await
+        .map(|(storage, ())| (storage, num_rows))
+}
+
+/// Schema of a new view, whose query is only checked here and runs each time the view is read.
+async fn define(
+    storage: &dyn GStore,
+    name: &ObjectName,
+    columns: &[String],
+    query: &Query,
+    cancellation: &Cancellation,
+    metrics: &Metrics,
+) -> Result<Schema> {
+    let table_name = get_name(name)?;
+    check_new(storage, table_name).await?;
+
+    let (labels, _) = select_with_labels(
+        storage,
+        query,
+        None,
+        true,
+        cancellation.clone(),
+        metrics.clone(),
+    )
+    .await?;
+
+    Ok(Schema {
+        table_name: table_name.to_owned(),
+        column_defs: Vec::new(),
+        indexes: Vec::new(),
+        materialized_view: None,
+        view: Some(SchemaView {
+            columns: name_columns(columns, labels)?,
+            query: query.clone(),
+        }),
+    })
+}
+
+/// Schema of a new materialized view, whose columns are typed as `CREATE TABLE AS SELECT` does,
+/// and its rows.
+async fn materialize(
+    storage: &dyn GStore,
+    name: &ObjectName,
+    columns: &[String],
+    query: &Query,
+    cancellation: &Cancellation,
+    metrics: &Metrics,
+) -> Result<(Schema, Vec<Row>)> {
+    let table_name = get_name(name)?;
+    check_new(storage, table_name).await?;
+
+    let (labels, rows) = run(storage, query, cancellation, metrics).await?;
+    let names = name_columns(columns, labels.clone())?;
+    let source_types = fetch_source_types(storage, query).await?;
+    let column_defs = infer_column_defs(labels, &rows, &source_types)
+        .into_iter()
+        .zip(names)
+        .map(|(column_def, name)| ColumnDef { name, ..column_def })
+        .collect();
+
+    let schema = Schema {
+        table_name: table_name.to_owned(),
+        column_defs,
+        indexes: Vec::new(),
+        materialized_view: Some(query.clone()),
+        view: None,
+    };
+
+    Ok((schema, rows))
+}
+
+/// Keys of the rows a materialized view holds, and the rows its query returns now.
+async fn refresh<'a>(
+    storage: &dyn GStore,
+    name: &'a ObjectName,
+    cancellation: &Cancellation,
+    metrics: &Metrics,
+) -> Result<(&'a str, Vec<Key>, Vec<Row>)> {
+    let table_name = get_name(name)?;
+    let (column_defs, query) = match storage.fetch_schema(table_name).await? {
+        Some(Schema {
+            column_defs,
+            materialized_view: Some(query),
+            ..
+        }) => (column_defs, query),
+        _ => return Err(AlterError::MaterializedViewNotFound(table_name.to_owned()).into()),
+    };
+
+    let (labels, rows) = run(storage, &query, cancellation, metrics).await?;
+    if labels.len() != column_defs.len() {
+        return Err(AlterError::ViewColumnCountMismatch {
+            expected: column_defs.len(),
+            found: labels.len(),
+        }
+        .into());
+    }
+
+    let keys = storage
+        .scan_data(table_name)
+        .await?
+        .map(|item| item.map(|(key, _)| key))
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok((table_name, keys, rows))
+}
+
+async fn check_new(storage: &dyn GStore, table_name: &str) -> Result<()> {
+    match storage.fetch_schema(table_name).await? {
+        Some(_) => Err(AlterError::TableAlreadyExists(table_name.to_owned()).into()),
+        None => Ok(()),
+    }
+}
+
+/// Column names of a view, which are the labels of its query unless `columns` names them.
+fn name_columns(columns: &[String], labels: Vec<String>) -> Result<Vec<String>> {
+    match columns {
+        [] => Ok(labels),
+        _ if columns.len() == labels.len() => Ok(columns.to_vec()),
+        _ => Err(AlterError::ViewColumnCountMismatch {
+            expected: columns.len(),
+            found: labels.len(),
+        }
+        .into()),
+    }
+}
+
+async fn run(
+    storage: &dyn GStore,
+    query: &Query,
+    cancellation: &Cancellation,
+    metrics: &Metrics,
+) -> Result<(Vec<String>, Vec<Row>)> {
+    let (labels, rows) = select_with_labels(
+        storage,
+        query,
+        None,
+        true,
+        cancellation.clone(),
+        metrics.clone(),
+    )
+    .await?;
+    let rows = rows.try_collect().await?;
+
+    Ok((labels, rows))
+}
@@ -0,0 +1,37 @@
+use {
+    super::ExecuteError,
+    crate::result::Result,
+    std::sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
+
+/// Cooperative cancellation flag, checked between rows while a statement runs.
+///
+/// Clones share the same flag, so a clone can be handed to another thread to abort execution.
+#[derive(Clone, Debug, Default)]
+pub struct Cancellation(Arc<AtomicBool>);
+
+impl Cancellation {
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    /// Clears the flag so that following statements can run again.
+    pub fn reset(&self) {
+        self.0.store(false, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+
+    pub fn check(&self) -> Result<()> {
+        if self.is_cancelled() {
+            return Err(ExecuteError::Cancelled.into());
+        }
+
+        Ok(())
+    }
+}
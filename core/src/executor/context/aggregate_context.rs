@@ -1,6 +1,9 @@
 use {
     super::BlendContext,
-    crate::{ast::Aggregate, data::Value},
+    crate::{
+        ast::{Aggregate, Window},
+        data::Value,
+    },
     im_rc::HashMap,
     std::{fmt::Debug, rc::Rc},
 };
@@ -8,5 +11,6 @@ use {
 #[derive(Debug)]
 pub struct AggregateContext<'a> {
     pub aggregated: Option<HashMap<&'a Aggregate, Value>>,
+    pub windowed: Option<HashMap<&'a Window, Value>>,
     pub next: Rc<BlendContext<'a>>,
 }
@@ -0,0 +1,80 @@
+use {
+    super::{fetch_column_defs, insert_rows, into_row, CopyError},
+    crate::{
+        ast::ColumnDef,
+        data::{Row, Value},
+        executor::{execute::autocommit, Payload},
+        result::{MutResult, Result, TrySelf},
+        store::{GStore, GStoreMut},
+    },
+};
+
+/// Turns one array per column into rows, casting each value into the type of its column.
+fn transpose(
+    column_defs: &[ColumnDef],
+    columns: &[&str],
+    column_arrays: Vec<Vec<Value>>,
+) -> Result<Vec<Row>> {
+    if columns.len() != column_arrays.len() {
+        return Err(CopyError::ArrayCountMismatch {
+            expected: columns.len(),
+            found: column_arrays.len(),
+        }
+        .into());
+    }
+
+    let num_rows = column_arrays.first().map(Vec::len).unwrap_or(0);
+    let mut rows = vec![vec![None; column_defs.len()]; num_rows];
+
+    for (&name, array) in columns.iter().zip(column_arrays) {
+        let index = column_defs
+            .iter()
+            .position(|column_def| column_def.name == name)
+            .ok_or_else(|| CopyError::ColumnNotFound(name.to_owned()))?;
+
+        if array.len() != num_rows {
+            return Err(CopyError::ArrayLengthMismatch {
+                column: name.to_owned(),
+                expected: num_rows,
+                found: array.len(),
+            }
+            .into());
+        }
+
+        let data_type = &column_defs[index].data_type;
+        for (values, value) in rows.iter_mut().zip(array) {
+            let value = if value.get_type().as_ref() == Some(data_type) {
+                value
+            } else {
+                value.cast(data_type)?
+            };
+
+            values[index] = Some(value);
+        }
+    }
+
+    rows.into_iter()
+        .map(|values| into_row(column_defs, values))
+        .collect()
+}
+
+/// Inserts rows given as one array per column in `columns`, all of the same length.
+///
+/// Columns which are not listed take their default, or NULL.
+/// The rows are written with a single storage call, every row is inserted or none of them.
+pub async fn bulk_insert<T: GStore + GStoreMut>(
+    storage: T,
+    table_name: &str,
+    columns: &[&str],
+    column_arrays: Vec<Vec<Value>>,
+) -> MutResult<T, Payload> {
+    autocommit(storage, |storage| async move {
+        let column_defs = fetch_column_defs(&storage, table_name).await;
+        let (storage, column_defs) = column_defs.try_self(storage)?;
+        let rows = transpose(&column_defs, columns, column_arrays);
+        let (storage, rows) = rows.try_self(storage)?;
+
+        insert_rows(storage, table_name, column_defs, rows).await
+    })
+    .await
+}
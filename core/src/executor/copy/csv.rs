@@ -0,0 +1,245 @@
+use {
+    super::{coerce, fetch_column_defs, insert_rows, into_row, select_labeled, CopyError},
+    crate::{
+        ast::{ColumnDef, Statement},
+        data::{Row, Value},
+        executor::{cancel::Cancellation, execute::autocommit, Payload},
+        result::{MutResult, Result, TrySelf},
+        store::{GStore, GStoreMut},
+    },
+    futures::stream::TryStreamExt,
+    serde_json::Value as JsonValue,
+    std::{io::Write, mem::take},
+};
+
+#[derive(Clone, Debug)]
+pub struct CsvOptions {
+    /// The first record holds column names, which may list the table columns in any order.
+    /// Without a header, every record has all the columns in the table order.
+    pub header: bool,
+    pub delimiter: char,
+    /// Unquoted fields equal to this token are NULL, `""` is always an empty string.
+    /// On export, NULL is written as this token and text equal to it is quoted.
+    pub null: String,
+}
+
+impl Default for CsvOptions {
+    fn default() -> Self {
+        Self {
+            header: false,
+            delimiter: ',',
+            null: String::new(),
+        }
+    }
+}
+
+struct Field {
+    value: String,
+    quoted: bool,
+}
+
+/// Splits `csv` into records, each of them paired with the line number it starts at.
+fn parse_records(csv: &str, delimiter: char) -> Result<Vec<(usize, Vec<Field>)>> {
+    let mut records = Vec::new();
+    let mut fields = Vec::new();
+    let mut value = String::new();
+    let mut quoted = false;
+    let mut in_quotes = false;
+    let mut line = 1;
+    let mut record_line = 1;
+    let mut chars = csv.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if in_quotes {
+            match c {
+                '"' if chars.peek() == Some(&'"') => {
+                    chars.next();
+                    value.push('"');
+                }
+                '"' => in_quotes = false,
+                _ => {
+                    if c == '\n' {
+                        line += 1;
+                    }
+
+                    value.push(c);
+                }
+            }
+
+            continue;
+        }
+
+        match c {
+            '"' if value.is_empty() && !quoted => {
+                in_quotes = true;
+                quoted = true;
+            }
+            '\r' if chars.peek() == Some(&'\n') => {}
+            '\n' => {
+                fields.push(Field {
+                    value: take(&mut value),
+                    quoted: take(&mut quoted),
+                });
+                records.push((record_line, take(&mut fields)));
+
+                line += 1;
+                record_line = line;
+            }
+            _ if c == delimiter => {
+                fields.push(Field {
+                    value: take(&mut value),
+                    quoted: take(&mut quoted),
+                });
+            }
+            _ => value.push(c),
+        }
+    }
+
+    if in_quotes {
+        return Err(CopyError::UnterminatedQuote(record_line).into());
+    }
+
+    if !value.is_empty() || quoted || !fields.is_empty() {
+        fields.push(Field { value, quoted });
+        records.push((record_line, fields));
+    }
+
+    Ok(records)
+}
+
+fn read_rows(column_defs: &[ColumnDef], csv: &str, options: &CsvOptions) -> Result<Vec<Row>> {
+    let mut records = parse_records(csv, options.delimiter)?.into_iter();
+
+    let indexes = if options.header {
+        records
+            .next()
+            .map(|(_, fields)| fields)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|Field { value: name, .. }| {
+                column_defs
+                    .iter()
+                    .position(|column_def| column_def.name == name)
+                    .ok_or_else(|| CopyError::ColumnNotFound(name).into())
+            })
+            .collect::<Result<Vec<_>>>()?
+    } else {
+        (0..column_defs.len()).collect()
+    };
+
+    records
+        .map(|(line, fields)| {
+            if fields.len() != indexes.len() {
+                return Err(CopyError::FieldCountMismatch {
+                    line,
+                    expected: indexes.len(),
+                    found: fields.len(),
+                }
+                .into());
+            }
+
+            let read_row = || {
+                let mut values = vec![None; column_defs.len()];
+                for (&index, Field { value, quoted }) in indexes.iter().zip(fields) {
+                    values[index] = if !quoted && value == options.null {
+                        Some(Value::Null)
+                    } else {
+                        coerce(value, &column_defs[index].data_type).map(Some)?
+                    };
+                }
+
+                into_row(column_defs, values)
+            };
+
+            read_row().map_err(|error| {
+                CopyError::InvalidRecord {
+                    line,
+                    message: error.to_string(),
+                }
+                .into()
+            })
+        })
+        .collect()
+}
+
+/// Inserts the records of `csv` into the table, converting each field into its column type.
+///
+/// Every record is inserted or none of them, a failing record is reported with its line number.
+pub async fn import_csv<T: GStore + GStoreMut>(
+    storage: T,
+    table_name: &str,
+    csv: &str,
+    options: &CsvOptions,
+) -> MutResult<T, Payload> {
+    autocommit(storage, |storage| async move {
+        let column_defs = fetch_column_defs(&storage, table_name).await;
+        let (storage, column_defs) = column_defs.try_self(storage)?;
+        let (storage, rows) = read_rows(&column_defs, csv, options).try_self(storage)?;
+
+        insert_rows(storage, table_name, column_defs, rows).await
+    })
+    .await
+}
+
+fn write_record(
+    writer: &mut impl Write,
+    fields: impl Iterator<Item = Result<Option<String>>>,
+    options: &CsvOptions,
+) -> Result<()> {
+    let delimiter = options.delimiter.to_string();
+    let fields = fields
+        .map(|field| {
+            let field = match field? {
+                Some(field) => field,
+                None => return Ok(options.null.clone()),
+            };
+
+            let needs_quotes = field == options.null
+                || field.contains(options.delimiter)
+                || field.contains(|c: char| matches!(c, '"' | '\r' | '\n'));
+
+            if needs_quotes {
+                Ok(format!("\"{}\"", field.replace('"', "\"\"")))
+            } else {
+                Ok(field)
+            }
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    writeln!(writer, "{}", fields.join(&delimiter))
+        .map_err(|error| CopyError::WriteFailed(error.to_string()).into())
+}
+
+fn render(value: Value) -> Result<Option<String>> {
+    match value {
+        Value::Null => Ok(None),
+        Value::Map(_) | Value::List(_) => JsonValue::try_from(value)
+            .map(|json| json.to_string())
+            .map(Some),
+        _ => Ok(Some(value.into())),
+    }
+}
+
+/// Writes the result of a `SELECT` statement as CSV, row by row, and returns the number of rows.
+///
+/// The labels are written as a header when `options.header` is set.
+pub async fn export_csv(
+    storage: &dyn GStore,
+    statement: &Statement,
+    mut writer: impl Write,
+    options: &CsvOptions,
+    cancellation: &Cancellation,
+) -> Result<usize> {
+    let (labels, rows) = select_labeled(storage, statement, cancellation).await?;
+
+    if options.header {
+        write_record(&mut writer, labels.into_iter().map(Some).map(Ok), options)?;
+    }
+
+    rows.try_fold(0, |num_rows, Row(values)| {
+        let written = write_record(&mut writer, values.into_iter().map(render), options);
+
+        async move { written.map(|()| num_rows + 1) }
+    })
+    .await
+}
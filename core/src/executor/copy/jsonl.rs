@@ -0,0 +1,114 @@
+use {
+    super::{coerce, fetch_column_defs, insert_rows, into_row, select_labeled, CopyError},
+    crate::{
+        ast::{ColumnDef, DataType, Statement},
+        data::{Row, Value, ValueError},
+        executor::{cancel::Cancellation, execute::autocommit, Payload},
+        result::{MutResult, Result, TrySelf},
+        store::{GStore, GStoreMut},
+    },
+    futures::stream::TryStreamExt,
+    serde_json::Value as JsonValue,
+    std::io::Write,
+};
+
+/// Converts a JSON field into the data type of its column.
+/// Strings and numbers are converted the same way `CAST` converts text.
+fn coerce_json(json: JsonValue, data_type: &DataType) -> Result<Value> {
+    match json {
+        JsonValue::Null => Ok(Value::Null),
+        JsonValue::Bool(v) => Value::Bool(v).cast(data_type),
+        JsonValue::Number(v) => coerce(v.to_string(), data_type),
+        JsonValue::String(v) => coerce(v, data_type),
+        JsonValue::Array(_) | JsonValue::Object(_) => Value::try_from(json),
+    }
+}
+
+fn read_row(column_defs: &[ColumnDef], line: &str) -> Result<Row> {
+    let object = match serde_json::from_str(line).map_err(|_| ValueError::InvalidJsonString)? {
+        JsonValue::Object(object) => object,
+        _ => return Err(ValueError::JsonObjectTypeRequired.into()),
+    };
+
+    let mut values = vec![None; column_defs.len()];
+    for (name, json) in object {
+        let index = column_defs
+            .iter()
+            .position(|column_def| column_def.name == name)
+            .ok_or(CopyError::ColumnNotFound(name))?;
+
+        values[index] = Some(coerce_json(json, &column_defs[index].data_type)?);
+    }
+
+    into_row(column_defs, values)
+}
+
+fn read_rows(column_defs: &[ColumnDef], jsonl: &str) -> Result<Vec<Row>> {
+    jsonl
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(index, line)| {
+            read_row(column_defs, line).map_err(|error| {
+                CopyError::InvalidRecord {
+                    line: index + 1,
+                    message: error.to_string(),
+                }
+                .into()
+            })
+        })
+        .collect()
+}
+
+/// Inserts every line of `jsonl`, each of them a JSON object keyed by column names.
+///
+/// Columns missing from an object take their default, or NULL.
+/// A key which is not a column of the table fails the import instead of being dropped.
+/// Every line is inserted or none of them, a failing line is reported with its line number.
+pub async fn import_jsonl<T: GStore + GStoreMut>(
+    storage: T,
+    table_name: &str,
+    jsonl: &str,
+) -> MutResult<T, Payload> {
+    autocommit(storage, |storage| async move {
+        let column_defs = fetch_column_defs(&storage, table_name).await;
+        let (storage, column_defs) = column_defs.try_self(storage)?;
+        let (storage, rows) = read_rows(&column_defs, jsonl).try_self(storage)?;
+
+        insert_rows(storage, table_name, column_defs, rows).await
+    })
+    .await
+}
+
+fn write_object(writer: &mut impl Write, labels: &[String], values: Vec<Value>) -> Result<()> {
+    let fields = labels
+        .iter()
+        .zip(values)
+        .map(|(label, value)| {
+            let value = JsonValue::try_from(value)?;
+
+            Ok(format!("{}:{}", JsonValue::from(label.as_str()), value))
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    writeln!(writer, "{{{}}}", fields.join(","))
+        .map_err(|error| CopyError::WriteFailed(error.to_string()).into())
+}
+
+/// Writes the result of a `SELECT` statement as JSON lines, one object keyed by the labels
+/// for each row, and returns the number of rows.
+pub async fn export_jsonl(
+    storage: &dyn GStore,
+    statement: &Statement,
+    mut writer: impl Write,
+    cancellation: &Cancellation,
+) -> Result<usize> {
+    let (labels, rows) = select_labeled(storage, statement, cancellation).await?;
+
+    rows.try_fold(0, |num_rows, Row(values)| {
+        let written = write_object(&mut writer, &labels, values);
+
+        async move { written.map(|()| num_rows + 1) }
+    })
+    .await
+}
@@ -0,0 +1,156 @@
+mod bulk;
+mod csv;
+mod jsonl;
+
+pub use {
+    self::csv::{export_csv, import_csv, CsvOptions},
+    bulk::bulk_insert,
+    jsonl::{export_jsonl, import_jsonl},
+};
+
+use {
+    super::{
+        cancel::Cancellation,
+        evaluate_stateless,
+        execute::Payload,
+        metrics::Metrics,
+        select::select_with_labels,
+        sequence::fill_auto_increment,
+        validate::{validate_unique, ColumnValidation},
+    },
+    crate::{
+        ast::{ColumnDef, DataType, Statement},
+        data::{schema::ColumnDefExt, Literal, Row, RowError, Schema, Value},
+        executor::ExecuteError,
+        result::{Error, MutResult, Result, TrySelf},
+        store::{GStore, GStoreMut},
+    },
+    futures::stream::TryStream,
+    serde::Serialize,
+    std::{borrow::Cow, fmt::Debug, rc::Rc},
+    thiserror::Error as ThisError,
+};
+
+#[derive(ThisError, Serialize, Debug, PartialEq)]
+pub enum CopyError {
+    #[error("column does not exist in the table: {0}")]
+    ColumnNotFound(String),
+
+    #[error("line {line}: expected {expected} fields but found {found}")]
+    FieldCountMismatch {
+        line: usize,
+        expected: usize,
+        found: usize,
+    },
+
+    #[error("line {0}: quoted field is not terminated")]
+    UnterminatedQuote(usize),
+
+    #[error("line {line}: {message}")]
+    InvalidRecord { line: usize, message: String },
+
+    #[error("expected {expected} column arrays but found {found}")]
+    ArrayCountMismatch { expected: usize, found: usize },
+
+    #[error("column array {column} has {found} values but expected {expected}")]
+    ArrayLengthMismatch {
+        column: String,
+        expected: usize,
+        found: usize,
+    },
+
+    #[error("only a SELECT statement can be exported")]
+    QueryRequired,
+
+    #[error("failed to write: {0}")]
+    WriteFailed(String),
+}
+
+async fn fetch_column_defs(storage: &impl GStore, table_name: &str) -> Result<Vec<ColumnDef>> {
+    storage
+        .fetch_schema(table_name)
+        .await?
+        .map(|Schema { column_defs, .. }| column_defs)
+        .ok_or_else(|| ExecuteError::TableNotFound(table_name.to_owned()).into())
+}
+
+/// Converts a text field into the data type of its column, the same way `CAST` does.
+fn coerce(field: String, data_type: &DataType) -> Result<Value> {
+    let literal = Literal::Text(Cow::Owned(field));
+
+    match data_type {
+        DataType::Bytea | DataType::Map | DataType::List => {
+            Value::try_from_literal(data_type, &literal)
+        }
+        _ => Value::try_cast_from_literal(data_type, &literal),
+    }
+}
+
+/// Builds a row from `values`, which are given in the order of `column_defs`.
+/// Columns without a value take their default, or NULL. Generated columns are computed again.
+fn into_row(column_defs: &[ColumnDef], values: Vec<Option<Value>>) -> Result<Row> {
+    let row = column_defs
+        .iter()
+        .zip(values)
+        .map(|(column_def, value)| {
+            let nullable = column_def.is_nullable() || column_def.is_auto_increment();
+
+            match (value, column_def.get_default(), nullable) {
+                (Some(value), _, _) => Ok(value),
+                (None, Some(expr), _) => {
+                    evaluate_stateless(None, expr)?.try_into_value(&column_def.data_type, nullable)
+                }
+                (None, None, true) => Ok(Value::Null),
+                (None, None, false) => {
+                    Err(RowError::LackOfRequiredColumn(column_def.name.to_owned()).into())
+                }
+            }
+        })
+        .collect::<Result<Vec<_>>>()
+        .map(Row)?
+        .generate(column_defs)?;
+
+    row.validate(column_defs)?;
+
+    Ok(row)
+}
+
+async fn insert_rows<T: GStore + GStoreMut>(
+    storage: T,
+    table_name: &str,
+    column_defs: Vec<ColumnDef>,
+    rows: Vec<Row>,
+) -> MutResult<T, Payload> {
+    let (storage, rows) = fill_auto_increment(storage, table_name, &column_defs, rows).await?;
+    let column_validation = ColumnValidation::All(Rc::from(column_defs));
+    let validated = validate_unique(&storage, table_name, column_validation, rows.iter()).await;
+    let (storage, ()) = validated.try_self(storage)?;
+
+    let num_rows = rows.len();
+
+    storage
+        .insert_data(table_name, rows)
+        .await
+        .map(|(storage, _)| (storage, Payload::Insert(num_rows)))
+}
+
+async fn select_labeled<'a>(
+    storage: &'a dyn GStore,
+    statement: &'a Statement,
+    cancellation: &Cancellation,
+) -> Result<(Vec<String>, impl TryStream<Ok = Row, Error = Error> + 'a)> {
+    let query = match statement {
+        Statement::Query(query) => query,
+        _ => return Err(CopyError::QueryRequired.into()),
+    };
+
+    select_with_labels(
+        storage,
+        query,
+        None,
+        true,
+        cancellation.clone(),
+        Metrics::default(),
+    )
+    .await
+}
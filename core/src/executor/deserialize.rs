@@ -0,0 +1,200 @@
+use {
+    crate::data::Value,
+    serde::{
+        de::{
+            self,
+            value::{MapDeserializer, SeqDeserializer},
+            DeserializeOwned, DeserializeSeed, IntoDeserializer, MapAccess, Visitor,
+        },
+        forward_to_deserialize_any, Deserializer, Serialize,
+    },
+    std::fmt::{Debug, Display},
+    thiserror::Error as ThisError,
+};
+
+#[derive(ThisError, Serialize, Debug, PartialEq)]
+pub enum DeserializeError {
+    #[error("only a SELECT statement gives rows to deserialize")]
+    QueryRequired,
+
+    #[error("column {0} has no field to be deserialized into")]
+    UnknownColumn(String),
+
+    #[error("NULL can only be deserialized into an Option")]
+    UnexpectedNull,
+
+    #[error("failed to deserialize column {column}: {message}")]
+    InvalidColumn { column: String, message: String },
+
+    #[error("{0}")]
+    Message(String),
+}
+
+impl de::Error for DeserializeError {
+    fn custom<T: Display>(message: T) -> Self {
+        DeserializeError::Message(message.to_string())
+    }
+}
+
+/// Deserializes each row of a `SELECT` result into `D`, whose fields take the values of the
+/// columns of the same labels.
+///
+/// A column without a field of its label fails, as does a NULL for a field which is not an
+/// `Option`. A missing column is left to `D`, which fills an `Option` field with `None`.
+pub fn deserialize_rows<D: DeserializeOwned>(
+    labels: &[String],
+    rows: Vec<Vec<Value>>,
+) -> crate::result::Result<Vec<D>> {
+    rows.into_iter()
+        .map(|values| D::deserialize(RowDeserializer { labels, values }).map_err(Into::into))
+        .collect()
+}
+
+struct RowDeserializer<'a> {
+    labels: &'a [String],
+    values: Vec<Value>,
+}
+
+impl<'de, 'a> Deserializer<'de> for RowDeserializer<'a> {
+    type Error = DeserializeError;
+
+    fn deserialize_any<V: Visitor<'de>>(
+        self,
+        visitor: V,
+    ) -> std::result::Result<V::Value, Self::Error> {
+        visitor.visit_map(RowAccess {
+            columns: self.labels.iter().zip(self.values),
+            value: None,
+        })
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> std::result::Result<V::Value, Self::Error> {
+        let unknown = self
+            .labels
+            .iter()
+            .find(|label| !fields.contains(&label.as_str()));
+
+        match unknown {
+            Some(label) => Err(DeserializeError::UnknownColumn(label.to_owned())),
+            None => self.deserialize_any(visitor),
+        }
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string bytes byte_buf option
+        unit unit_struct newtype_struct seq tuple tuple_struct map enum identifier ignored_any
+    }
+}
+
+struct RowAccess<'a, I> {
+    columns: I,
+    value: Option<(&'a String, Value)>,
+}
+
+impl<'de, 'a, I> MapAccess<'de> for RowAccess<'a, I>
+where
+    I: Iterator<Item = (&'a String, Value)>,
+{
+    type Error = DeserializeError;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> std::result::Result<Option<K::Value>, Self::Error> {
+        let (label, value) = match self.columns.next() {
+            Some(column) => column,
+            None => return Ok(None),
+        };
+        self.value = Some((label, value));
+
+        seed.deserialize(label.as_str().into_deserializer())
+            .map(Some)
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(
+        &mut self,
+        seed: V,
+    ) -> std::result::Result<V::Value, Self::Error> {
+        let (label, value) = self
+            .value
+            .take()
+            .ok_or_else(|| DeserializeError::Message("value read before its column".to_owned()))?;
+
+        seed.deserialize(ValueDeserializer(value)).map_err(|error| {
+            DeserializeError::InvalidColumn {
+                column: label.to_owned(),
+                message: error.to_string(),
+            }
+        })
+    }
+}
+
+struct ValueDeserializer(Value);
+
+impl<'de> IntoDeserializer<'de, DeserializeError> for ValueDeserializer {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self {
+        self
+    }
+}
+
+impl<'de> Deserializer<'de> for ValueDeserializer {
+    type Error = DeserializeError;
+
+    fn deserialize_any<V: Visitor<'de>>(
+        self,
+        visitor: V,
+    ) -> std::result::Result<V::Value, Self::Error> {
+        match self.0 {
+            Value::Bool(v) => visitor.visit_bool(v),
+            Value::I8(v) => visitor.visit_i8(v),
+            Value::I16(v) => visitor.visit_i16(v),
+            Value::I32(v) => visitor.visit_i32(v),
+            Value::I64(v) => visitor.visit_i64(v),
+            Value::I128(v) => visitor.visit_i128(v),
+            Value::F64(v) => visitor.visit_f64(v),
+            Value::Str(v) => visitor.visit_string(v),
+            Value::Bytea(v) => visitor.visit_byte_buf(v),
+            Value::List(values) => {
+                let mut values = SeqDeserializer::<_, DeserializeError>::new(
+                    values.into_iter().map(ValueDeserializer),
+                );
+                let value = visitor.visit_seq(&mut values)?;
+                values.end()?;
+
+                Ok(value)
+            }
+            Value::Map(values) => {
+                let values = values.into_iter().map(|(k, v)| (k, ValueDeserializer(v)));
+                let mut values = MapDeserializer::<_, DeserializeError>::new(values);
+                let value = visitor.visit_map(&mut values)?;
+                values.end()?;
+
+                Ok(value)
+            }
+            Value::Null => Err(DeserializeError::UnexpectedNull),
+            value => visitor.visit_string(String::from(&value)),
+        }
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(
+        self,
+        visitor: V,
+    ) -> std::result::Result<V::Value, Self::Error> {
+        match self.0 {
+            Value::Null => visitor.visit_none(),
+            _ => visitor.visit_some(self),
+        }
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string bytes byte_buf unit
+        unit_struct newtype_struct seq tuple tuple_struct map struct enum identifier ignored_any
+    }
+}
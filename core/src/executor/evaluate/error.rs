@@ -1,5 +1,8 @@
 use {
-    crate::ast::{Aggregate, Expr},
+    crate::{
+        ast::{Aggregate, Expr, Window},
+        data::Value,
+    },
     serde::Serialize,
     std::fmt::Debug,
     thiserror::Error,
@@ -22,6 +25,9 @@ pub enum EvaluateError {
     #[error("function requires float or integer value: {0}")]
     FunctionRequiresFloatOrIntegerValue(String),
 
+    #[error("function requires string or bytea value: {0}")]
+    FunctionRequiresStringOrByteaValue(String),
+
     #[error("function requires usize value: {0}")]
     FunctionRequiresUSizeValue(String),
 
@@ -34,6 +40,9 @@ pub enum EvaluateError {
     #[error("function requires map value: {0}")]
     FunctionRequiresMapValue(String),
 
+    #[error("list value is required: {0}")]
+    ListTypeRequired(String),
+
     #[error("value not found: {0}")]
     ValueNotFound(String),
 
@@ -52,12 +61,52 @@ pub enum EvaluateError {
     #[error("unreachable empty aggregate value: {0:?}")]
     UnreachableEmptyAggregateValue(Aggregate),
 
+    #[error("unreachable empty window value: {0:?}")]
+    UnreachableEmptyWindowValue(Window),
+
     #[error("the divisor should not be zero")]
     DivisorShouldNotBeZero,
 
     #[error("negative substring length not allowed")]
     NegativeSubstrLenNotAllowed,
 
+    #[error("non-ASCII character not allowed: {0}")]
+    NonAsciiCharacterNotAllowed(String),
+
+    #[error("invalid unicode code point: {0}")]
+    InvalidUnicodeCodePoint(i64),
+
+    #[error("RANDOM range minimum {min} is greater than maximum {max}")]
+    InvalidRandomRange { min: i64, max: i64 },
+
+    #[error("invalid hex string: {0}")]
+    InvalidHexString(String),
+
+    #[error("invalid base64 string: {0}")]
+    InvalidBase64String(String),
+
+    #[error("unsupported format specifier: {0}")]
+    UnsupportedFormatSpecifier(String),
+
+    #[error("format specifier {specifier} does not accept value: {value:?}")]
+    FormatSpecifierTypeMismatch { specifier: String, value: Value },
+
+    #[error("format argument count mismatch: expected {expected}, found {found}")]
+    FormatArgumentCountMismatch { expected: usize, found: usize },
+
     #[error("subquery returns more than one row")]
     MoreThanOneRowReturned,
+
+    #[error("row values of different sizes cannot be compared: {left} and {right}")]
+    TupleArityMismatch { left: usize, right: usize },
+
+    #[error("unsupported operation on row values: {0}")]
+    UnsupportedTupleOperation(String),
+
+    #[error("strict typing requires an explicit CAST: {left} {operator} {right}")]
+    StrictTypeMismatch {
+        left: String,
+        operator: String,
+        right: String,
+    },
 }
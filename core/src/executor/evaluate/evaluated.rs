@@ -122,6 +122,26 @@ where
     }
 }
 
+fn value_binary_op<'b, T>(
+    l: &Evaluated<'_>,
+    r: &Evaluated<'_>,
+    value_op: T,
+) -> Result<Evaluated<'b>>
+where
+    T: FnOnce(&Value, &Value) -> Result<Value>,
+{
+    let l = match l {
+        Evaluated::Literal(l) => Cow::Owned(Value::try_from(l)?),
+        Evaluated::Value(l) => Cow::Borrowed(l.as_ref()),
+    };
+    let r = match r {
+        Evaluated::Literal(r) => Cow::Owned(Value::try_from(r)?),
+        Evaluated::Value(r) => Cow::Borrowed(r.as_ref()),
+    };
+
+    value_op(&l, &r).map(Evaluated::from)
+}
+
 impl<'a> Evaluated<'a> {
     pub fn add<'b>(&'a self, other: &Evaluated<'b>) -> Result<Evaluated<'b>> {
         binary_op(self, other, |l, r| l.add(r), |l, r| l.add(r))
@@ -143,6 +163,26 @@ impl<'a> Evaluated<'a> {
         binary_op(self, other, |l, r| l.modulo(r), |l, r| l.modulo(r))
     }
 
+    pub fn bitwise_and<'b>(&'a self, other: &Evaluated<'b>) -> Result<Evaluated<'b>> {
+        value_binary_op(self, other, |l, r| l.bitwise_and(r))
+    }
+
+    pub fn bitwise_or<'b>(&'a self, other: &Evaluated<'b>) -> Result<Evaluated<'b>> {
+        value_binary_op(self, other, |l, r| l.bitwise_or(r))
+    }
+
+    pub fn bitwise_xor<'b>(&'a self, other: &Evaluated<'b>) -> Result<Evaluated<'b>> {
+        value_binary_op(self, other, |l, r| l.bitwise_xor(r))
+    }
+
+    pub fn bitwise_shift_left<'b>(&'a self, other: &Evaluated<'b>) -> Result<Evaluated<'b>> {
+        value_binary_op(self, other, |l, r| l.bitwise_shift_left(r))
+    }
+
+    pub fn bitwise_shift_right<'b>(&'a self, other: &Evaluated<'b>) -> Result<Evaluated<'b>> {
+        value_binary_op(self, other, |l, r| l.bitwise_shift_right(r))
+    }
+
     pub fn unary_plus(&self) -> Result<Evaluated<'a>> {
         match self {
             Evaluated::Literal(v) => v.unary_plus().map(Evaluated::Literal),
@@ -165,6 +205,14 @@ impl<'a> Evaluated<'a> {
         .map(Evaluated::from)
     }
 
+    pub fn unary_bitwise_not(&self) -> Result<Evaluated<'a>> {
+        match self {
+            Evaluated::Literal(v) => Value::try_from(v).and_then(|v| v.unary_bitwise_not()),
+            Evaluated::Value(v) => v.unary_bitwise_not(),
+        }
+        .map(Evaluated::from)
+    }
+
     pub fn cast(self, data_type: &DataType) -> Result<Evaluated<'a>> {
         let cast_literal = |literal: &Literal| Value::try_cast_from_literal(data_type, literal);
         let cast_value = |value: &Value| value.cast(data_type);
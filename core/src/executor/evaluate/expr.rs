@@ -1,13 +1,39 @@
 use {
-    super::Evaluated,
+    super::{EvaluateError, Evaluated},
     crate::{
-        ast::{AstLiteral, BinaryOperator, DataType, UnaryOperator},
+        ast::{AstLiteral, BinaryOperator, DataType, ToSql, UnaryOperator},
         data::{Literal, Value},
         result::Result,
     },
-    std::borrow::Cow,
+    std::{borrow::Cow, cmp::Ordering},
 };
 
+/// Result of dividing a FLOAT by zero, integer and decimal division by zero always errors.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FloatDivisionByZero {
+    /// Fails with `DivisorShouldNotBeZero`, the default.
+    Error,
+    /// Follows IEEE 754, so `1.0 / 0` is `Infinity`, `-1.0 / 0` is `-Infinity` and `0.0 / 0`
+    /// is `NaN`.
+    Infinity,
+}
+
+impl Default for FloatDivisionByZero {
+    fn default() -> Self {
+        Self::Error
+    }
+}
+
+/// Settings of the running session which change how operators evaluate, see `Session`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct OperatorSettings {
+    /// Comparisons and arithmetic require operands of the same type
+    pub strict_typing: bool,
+
+    /// How a FLOAT divided by zero evaluates
+    pub float_division_by_zero: FloatDivisionByZero,
+}
+
 pub fn literal(ast_literal: &AstLiteral) -> Result<Evaluated<'_>> {
     Literal::try_from(ast_literal).map(Evaluated::Literal)
 }
@@ -22,6 +48,7 @@ pub fn binary_op<'a>(
     op: &BinaryOperator,
     l: Evaluated<'a>,
     r: Evaluated<'a>,
+    settings: OperatorSettings,
 ) -> Result<Evaluated<'a>> {
     macro_rules! cmp {
         ($expr: expr) => {
@@ -39,11 +66,18 @@ pub fn binary_op<'a>(
         }};
     }
 
+    if settings.strict_typing {
+        check_strict_types(op, &l, &r)?;
+    }
+
     match op {
         BinaryOperator::Plus => l.add(&r),
         BinaryOperator::Minus => l.subtract(&r),
         BinaryOperator::Multiply => l.multiply(&r),
-        BinaryOperator::Divide => l.divide(&r),
+        BinaryOperator::Divide => match float_division_by_zero(&l, &r, settings) {
+            Some(quotient) => Ok(Evaluated::from(Value::F64(quotient))),
+            None => l.divide(&r),
+        },
         BinaryOperator::Modulo => l.modulo(&r),
         BinaryOperator::StringConcat => l.concat(r),
         BinaryOperator::Eq => cmp!(l == r),
@@ -63,7 +97,233 @@ pub fn binary_op<'a>(
         BinaryOperator::NotILike => {
             cmp!(l.like(r, false)? == Evaluated::Literal(Literal::Boolean(false)))
         }
+        BinaryOperator::BitwiseAnd => l.bitwise_and(&r),
+        BinaryOperator::BitwiseOr => l.bitwise_or(&r),
+        BinaryOperator::BitwiseXor => l.bitwise_xor(&r),
+        BinaryOperator::BitwiseShiftLeft => l.bitwise_shift_left(&r),
+        BinaryOperator::BitwiseShiftRight => l.bitwise_shift_right(&r),
+        BinaryOperator::IsDistinctFrom => cmp!(is_distinct(&l, &r)),
+        BinaryOperator::IsNotDistinctFrom => cmp!(!is_distinct(&l, &r)),
+        BinaryOperator::Contains => contains(l, r),
+    }
+}
+
+/// Type of an operand under strict typing, `None` for NULL.
+enum OperandType {
+    Typed(DataType),
+    /// An untyped number literal, which fits any numeric type
+    Number,
+}
+
+fn operand_type(evaluated: &Evaluated<'_>) -> Option<OperandType> {
+    let data_type = match evaluated {
+        Evaluated::Literal(Literal::Null) => return None,
+        Evaluated::Literal(Literal::Number(_)) => return Some(OperandType::Number),
+        Evaluated::Literal(Literal::Boolean(_)) => DataType::Boolean,
+        Evaluated::Literal(Literal::Text(_)) => DataType::Text,
+        Evaluated::Literal(Literal::Bytea(_)) => DataType::Bytea,
+        Evaluated::Literal(Literal::Interval(_)) => DataType::Interval,
+        Evaluated::Value(value) => value.get_type()?,
+    };
+
+    Some(OperandType::Typed(data_type))
+}
+
+/// Comparisons take operands of the same type only, and so does arithmetic unless it involves
+/// a date, time or interval, which have their own rules, e.g. `DATE + INTERVAL`.
+fn check_strict_types(op: &BinaryOperator, l: &Evaluated<'_>, r: &Evaluated<'_>) -> Result<()> {
+    use {BinaryOperator::*, DataType::*};
+
+    let arithmetic = matches!(op, Plus | Minus | Multiply | Divide | Modulo);
+    if !arithmetic && !matches!(op, Eq | NotEq | Lt | LtEq | Gt | GtEq) {
+        return Ok(());
+    }
+
+    let is_numeric = |data_type: &DataType| {
+        matches!(
+            data_type,
+            Int8 | Int16 | Int32 | Int | Int128 | Float | Decimal
+        )
+    };
+    let is_temporal = |data_type: &DataType| {
+        matches!(data_type, Date | Timestamp | TimestampTz | Time | Interval)
+    };
+
+    let (l, r) = match (operand_type(l), operand_type(r)) {
+        (Some(l), Some(r)) => (l, r),
+        _ => return Ok(()),
+    };
+    let matched = match (&l, &r) {
+        (OperandType::Number, OperandType::Number) => true,
+        (OperandType::Number, OperandType::Typed(data_type))
+        | (OperandType::Typed(data_type), OperandType::Number) => {
+            is_numeric(data_type) || (arithmetic && is_temporal(data_type))
+        }
+        (OperandType::Typed(l), OperandType::Typed(r)) => {
+            l == r || (arithmetic && (is_temporal(l) || is_temporal(r)))
+        }
+    };
+
+    if matched {
+        return Ok(());
+    }
+
+    let name = |operand_type: OperandType| match operand_type {
+        OperandType::Typed(data_type) => data_type.to_string(),
+        OperandType::Number => "NUMBER".to_owned(),
+    };
+
+    Err(EvaluateError::StrictTypeMismatch {
+        left: name(l),
+        operator: op.to_sql(),
+        right: name(r),
     }
+    .into())
+}
+
+/// Whether the left list holds every element of the right list, a NULL element is never held.
+fn contains<'a>(l: Evaluated<'a>, r: Evaluated<'a>) -> Result<Evaluated<'a>> {
+    let v = match (Value::try_from(l)?, Value::try_from(r)?) {
+        (Value::List(l), Value::List(r)) => r.iter().all(|r| l.contains(r)),
+        (Value::Null, _) | (_, Value::Null) => false,
+        (Value::List(_), value) | (value, _) => {
+            return Err(EvaluateError::ListTypeRequired(format!("{:?}", value)).into());
+        }
+    };
+
+    Ok(Evaluated::from(Value::Bool(v)))
+}
+
+/// Applies `op` between `left` and each element of the `right` list, which must hold for every
+/// element if `all` is set, or for any of them otherwise.
+///
+/// A comparison involving NULL does not hold, as with scalar comparisons, so an empty list
+/// gives TRUE for ALL and FALSE for ANY, while a NULL list gives FALSE for both.
+pub fn quantified_op<'a>(
+    op: &BinaryOperator,
+    all: bool,
+    left: Evaluated<'a>,
+    right: Evaluated<'a>,
+    settings: OperatorSettings,
+) -> Result<Evaluated<'a>> {
+    let elements = match Value::try_from(right)? {
+        Value::List(elements) => elements,
+        Value::Null => return Ok(Evaluated::from(Value::Bool(false))),
+        value => return Err(EvaluateError::ListTypeRequired(format!("{:?}", value)).into()),
+    };
+
+    for element in elements {
+        if holds(op, &left, Evaluated::from(element), settings)? != all {
+            return Ok(Evaluated::from(Value::Bool(!all)));
+        }
+    }
+
+    Ok(Evaluated::from(Value::Bool(all)))
+}
+
+/// Whether `left op right` holds, which it never does if either side is NULL.
+///
+/// `ANY` and `ALL` stop at the first element for which this decides the result.
+pub fn holds<'a>(
+    op: &BinaryOperator,
+    left: &Evaluated<'a>,
+    right: Evaluated<'a>,
+    settings: OperatorSettings,
+) -> Result<bool> {
+    if left.is_null() || right.is_null() {
+        return Ok(false);
+    }
+
+    binary_op(op, left.clone(), right, settings)?.try_into()
+}
+
+/// Compares like `<>` but treats NULL as a value, so the result is never unknown.
+fn is_distinct(l: &Evaluated<'_>, r: &Evaluated<'_>) -> bool {
+    match (l.is_null(), r.is_null()) {
+        (true, true) => false,
+        (false, false) => l != r,
+        _ => true,
+    }
+}
+
+/// Compares row values pair by pair, following SQL row value comparison.
+///
+/// `=` holds when every pair is equal and `<>` holds when any pair is not equal,
+/// ordering operators are decided by the first pair which is not equal.
+/// A result which depends on a NULL is unknown and, as with scalar comparisons, gives FALSE.
+pub fn tuple_op<'a>(
+    op: &BinaryOperator,
+    l: Vec<Evaluated<'a>>,
+    r: Vec<Evaluated<'a>>,
+) -> Result<Evaluated<'a>> {
+    if l.len() != r.len() {
+        return Err(EvaluateError::TupleArityMismatch {
+            left: l.len(),
+            right: r.len(),
+        }
+        .into());
+    }
+
+    let pairs = || {
+        l.iter()
+            .zip(r.iter())
+            .map(|(l, r)| (!l.is_null() && !r.is_null()).then(|| (l, r)))
+    };
+
+    let ordering = || {
+        pairs()
+            .map(|pair| pair.and_then(|(l, r)| l.partial_cmp(r)))
+            .find(|ordering| ordering != &Some(Ordering::Equal))
+            .unwrap_or(Some(Ordering::Equal))
+    };
+
+    let v = match op {
+        BinaryOperator::Eq => pairs().all(|pair| matches!(pair, Some((l, r)) if l == r)),
+        BinaryOperator::NotEq => pairs().any(|pair| matches!(pair, Some((l, r)) if l != r)),
+        BinaryOperator::Lt => ordering() == Some(Ordering::Less),
+        BinaryOperator::LtEq => matches!(ordering(), Some(Ordering::Less | Ordering::Equal)),
+        BinaryOperator::Gt => ordering() == Some(Ordering::Greater),
+        BinaryOperator::GtEq => matches!(ordering(), Some(Ordering::Greater | Ordering::Equal)),
+        _ => return Err(EvaluateError::UnsupportedTupleOperation(op.to_sql()).into()),
+    };
+
+    Ok(Evaluated::from(Value::Bool(v)))
+}
+
+/// IEEE 754 quotient of a division by zero with a FLOAT operand, when the session evaluates
+/// it instead of erroring.
+fn float_division_by_zero(
+    l: &Evaluated<'_>,
+    r: &Evaluated<'_>,
+    settings: OperatorSettings,
+) -> Option<f64> {
+    if settings.float_division_by_zero == FloatDivisionByZero::Error {
+        return None;
+    }
+
+    let l = Value::try_from(l.clone()).ok()?;
+    let r = Value::try_from(r.clone()).ok()?;
+    let is_numeric = |v: &Value| {
+        matches!(
+            v,
+            Value::I8(_)
+                | Value::I16(_)
+                | Value::I32(_)
+                | Value::I64(_)
+                | Value::I128(_)
+                | Value::F64(_)
+                | Value::Decimal(_)
+        )
+    };
+
+    if !(matches!(l, Value::F64(_)) || matches!(r, Value::F64(_)))
+        || !is_numeric(&l)
+        || !r.is_zero()
+    {
+        return None;
+    }
+
+    Some(f64::try_from(&l).ok()? / f64::try_from(&r).ok()?)
 }
 
 pub fn unary_op<'a>(op: &UnaryOperator, v: Evaluated<'a>) -> Result<Evaluated<'a>> {
@@ -72,9 +332,30 @@ pub fn unary_op<'a>(op: &UnaryOperator, v: Evaluated<'a>) -> Result<Evaluated<'a
         UnaryOperator::Minus => v.unary_minus(),
         UnaryOperator::Not => v.try_into().map(|v: bool| Evaluated::from(Value::Bool(!v))),
         UnaryOperator::Factorial => v.unary_factorial(),
+        UnaryOperator::BitwiseNot => v.unary_bitwise_not(),
+        UnaryOperator::IsTrue => truth_test(v, |v| v == Some(true)),
+        UnaryOperator::IsNotTrue => truth_test(v, |v| v != Some(true)),
+        UnaryOperator::IsFalse => truth_test(v, |v| v == Some(false)),
+        UnaryOperator::IsNotFalse => truth_test(v, |v| v != Some(false)),
+        UnaryOperator::IsUnknown => truth_test(v, |v| v.is_none()),
+        UnaryOperator::IsNotUnknown => truth_test(v, |v| v.is_some()),
     }
 }
 
+/// Applies `test` to the truth value of a boolean, where NULL is unknown.
+fn truth_test<'a>(
+    v: Evaluated<'a>,
+    test: impl FnOnce(Option<bool>) -> bool,
+) -> Result<Evaluated<'a>> {
+    let v: Option<bool> = if v.is_null() {
+        None
+    } else {
+        Some(v.try_into()?)
+    };
+
+    Ok(Evaluated::from(Value::Bool(test(v))))
+}
+
 pub fn between<'a>(
     target: Evaluated<'a>,
     negated: bool,
@@ -1,7 +1,14 @@
 use {
-    super::{EvaluateError, Evaluated},
-    crate::{ast::TrimWhereField, data::Value, result::Result},
-    std::cmp::{max, min},
+    super::{random::Rng, EvaluateError, Evaluated},
+    crate::{
+        ast::TrimWhereField,
+        data::{StringExt, Value},
+        executor::{
+            sequence::{current_value, next_value},
+            session,
+        },
+        result::Result,
+    },
     uuid::Uuid,
 };
 
@@ -19,6 +26,21 @@ macro_rules! eval_to_str {
     };
 }
 
+macro_rules! eval_to_bytes {
+    ($name: expr, $evaluated: expr) => {
+        match $evaluated.try_into()? {
+            Value::Str(value) => value.into_bytes(),
+            Value::Bytea(value) => value,
+            Value::Null => {
+                return Ok(Value::Null);
+            }
+            _ => {
+                return Err(EvaluateError::FunctionRequiresStringOrByteaValue($name).into());
+            }
+        }
+    };
+}
+
 macro_rules! eval_to_int {
     ($name: expr, $evaluated: expr) => {
         match $evaluated.try_into()? {
@@ -50,6 +72,10 @@ macro_rules! eval_to_float {
 
 // --- text ---
 
+/// Concatenates the arguments, skipping NULL values.
+///
+/// Unlike the `||` operator, which yields NULL when either operand is NULL,
+/// `CONCAT` only ignores NULL arguments.
 pub fn concat(exprs: Vec<Evaluated<'_>>) -> Result<Value> {
     exprs
         .into_iter()
@@ -60,6 +86,170 @@ pub fn concat(exprs: Vec<Evaluated<'_>>) -> Result<Value> {
         })
 }
 
+/// Joins the non-NULL arguments with the separator.
+///
+/// A NULL separator yields NULL, while NULL arguments are skipped.
+pub fn concat_ws(
+    name: String,
+    separator: Evaluated<'_>,
+    exprs: Vec<Evaluated<'_>>,
+) -> Result<Value> {
+    let separator = eval_to_str!(name, separator);
+
+    exprs
+        .into_iter()
+        .map(Value::try_from)
+        .filter(|value| !matches!(value, Ok(Value::Null)))
+        .map(|value| value.map(String::from))
+        .collect::<Result<Vec<_>>>()
+        .map(|values| Value::Str(values.join(&separator)))
+}
+
+enum FormatToken {
+    Text(String),
+    Str,
+    Int,
+    Float(Option<usize>),
+}
+
+fn parse_format(format: &str) -> Result<Vec<FormatToken>> {
+    let mut tokens = Vec::new();
+    let mut text = String::new();
+    let mut chars = format.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c != '%' {
+            text.push(c);
+            continue;
+        }
+
+        let mut specifier = String::from('%');
+        let precision = match chars.peek() {
+            Some('.') => {
+                specifier.push('.');
+                chars.next();
+
+                let mut digits = String::new();
+                while let Some(digit) = chars.next_if(char::is_ascii_digit) {
+                    digits.push(digit);
+                }
+                specifier.push_str(&digits);
+
+                Some(digits.parse::<usize>().ok())
+            }
+            _ => None,
+        };
+
+        let token = match (chars.next(), precision) {
+            (Some('%'), None) => {
+                text.push('%');
+                continue;
+            }
+            (Some('s'), None) => FormatToken::Str,
+            (Some('d'), None) => FormatToken::Int,
+            (Some('f'), None) => FormatToken::Float(None),
+            (Some('f'), Some(Some(precision))) => FormatToken::Float(Some(precision)),
+            (c, _) => {
+                specifier.extend(c);
+
+                return Err(EvaluateError::UnsupportedFormatSpecifier(specifier).into());
+            }
+        };
+
+        if !text.is_empty() {
+            tokens.push(FormatToken::Text(std::mem::take(&mut text)));
+        }
+
+        tokens.push(token);
+    }
+
+    if !text.is_empty() {
+        tokens.push(FormatToken::Text(text));
+    }
+
+    Ok(tokens)
+}
+
+/// Formats the arguments printf-style.
+///
+/// Supports `%s`, `%d`, `%f`, `%.Nf` and `%%`. `%f` uses six fractional digits
+/// by default. NULL arguments are rendered as `NULL` regardless of the specifier.
+pub fn format(name: String, format: Evaluated<'_>, args: Vec<Evaluated<'_>>) -> Result<Value> {
+    let tokens = parse_format(&eval_to_str!(name, format))?;
+
+    let expected = tokens
+        .iter()
+        .filter(|token| !matches!(token, FormatToken::Text(_)))
+        .count();
+    if expected != args.len() {
+        return Err(EvaluateError::FormatArgumentCountMismatch {
+            expected,
+            found: args.len(),
+        }
+        .into());
+    }
+
+    let mut args = args.into_iter();
+    let mut formatted = String::new();
+
+    for token in tokens {
+        let (specifier, value) = match token {
+            FormatToken::Text(text) => {
+                formatted.push_str(&text);
+                continue;
+            }
+            FormatToken::Str => ("%s".to_owned(), args.next()),
+            FormatToken::Int => ("%d".to_owned(), args.next()),
+            FormatToken::Float(None) => ("%f".to_owned(), args.next()),
+            FormatToken::Float(Some(precision)) => (format!("%.{precision}f"), args.next()),
+        };
+        let value: Value = match value {
+            Some(value) => value.try_into()?,
+            None => continue,
+        };
+
+        let rendered = match (token, value) {
+            (_, Value::Null) => "NULL".to_owned(),
+            (FormatToken::Str, value) => String::from(value),
+            (
+                FormatToken::Int,
+                value @ (Value::I8(_)
+                | Value::I16(_)
+                | Value::I32(_)
+                | Value::I64(_)
+                | Value::I128(_)),
+            ) => String::from(value),
+            (FormatToken::Float(precision), value) => {
+                let precision = precision.unwrap_or(6);
+
+                match value {
+                    Value::F64(v) => format!("{v:.precision$}"),
+                    Value::I8(v) => format!("{:.precision$}", f64::from(v)),
+                    Value::I16(v) => format!("{:.precision$}", f64::from(v)),
+                    Value::I32(v) => format!("{:.precision$}", f64::from(v)),
+                    Value::I64(v) => format!("{:.precision$}", v as f64),
+                    Value::I128(v) => format!("{:.precision$}", v as f64),
+                    Value::Decimal(v) => format!("{:.precision$}", v.round_dp(precision as u32)),
+                    value => {
+                        return Err(EvaluateError::FormatSpecifierTypeMismatch {
+                            specifier,
+                            value,
+                        }
+                        .into());
+                    }
+                }
+            }
+            (_, value) => {
+                return Err(EvaluateError::FormatSpecifierTypeMismatch { specifier, value }.into());
+            }
+        };
+
+        formatted.push_str(&rendered);
+    }
+
+    Ok(Value::Str(formatted))
+}
+
 pub fn lower(name: String, expr: Evaluated<'_>) -> Result<Value> {
     Ok(Value::Str(eval_to_str!(name, expr).to_lowercase()))
 }
@@ -150,14 +340,22 @@ pub fn trim(
     let expr_str = expr_str.as_str();
     let filter_chars = match filter_chars {
         Some(expr) => eval_to_str!(name, expr).chars().collect::<Vec<_>>(),
-        None => vec![' '],
+        None => {
+            // without a character set, every direction trims whitespace as `TRIM(expr)` does
+            let value = match trim_where_field {
+                Some(TrimWhereField::Leading) => expr_str.trim_start(),
+                Some(TrimWhereField::Trailing) => expr_str.trim_end(),
+                Some(TrimWhereField::Both) | None => expr_str.trim(),
+            };
+
+            return Ok(Value::Str(value.to_owned()));
+        }
     };
 
     let value = match trim_where_field {
-        Some(TrimWhereField::Both) => expr_str.trim_matches(&filter_chars[..]),
+        Some(TrimWhereField::Both) | None => expr_str.trim_matches(&filter_chars[..]),
         Some(TrimWhereField::Leading) => expr_str.trim_start_matches(&filter_chars[..]),
         Some(TrimWhereField::Trailing) => expr_str.trim_end_matches(&filter_chars[..]),
-        None => expr_str.trim(),
     };
 
     Ok(Value::Str(value.to_owned()))
@@ -191,6 +389,81 @@ pub fn reverse(name: String, expr: Evaluated<'_>) -> Result<Value> {
     Ok(Value::Str(value))
 }
 
+pub fn soundex(name: String, expr: Evaluated<'_>) -> Result<Value> {
+    let value = eval_to_str!(name, expr).soundex();
+
+    Ok(Value::Str(value))
+}
+
+pub fn length(name: String, expr: Evaluated<'_>) -> Result<Value> {
+    let length = match expr.try_into()? {
+        Value::Str(value) => value.chars().count(),
+        Value::Bytea(value) => value.len(),
+        Value::Null => return Ok(Value::Null),
+        _ => return Err(EvaluateError::FunctionRequiresStringOrByteaValue(name).into()),
+    };
+
+    Ok(Value::I64(length as i64))
+}
+
+pub fn ascii(name: String, expr: Evaluated<'_>) -> Result<Value> {
+    let value = match eval_to_str!(name, expr).chars().next() {
+        Some(c) if c.is_ascii() => c as i64,
+        Some(c) => return Err(EvaluateError::NonAsciiCharacterNotAllowed(c.to_string()).into()),
+        None => 0,
+    };
+
+    Ok(Value::I64(value))
+}
+
+pub fn ord(name: String, expr: Evaluated<'_>) -> Result<Value> {
+    let value = eval_to_str!(name, expr)
+        .chars()
+        .next()
+        .map(|c| c as i64)
+        .unwrap_or(0);
+
+    Ok(Value::I64(value))
+}
+
+pub fn chr(name: String, expr: Evaluated<'_>) -> Result<Value> {
+    let code = eval_to_int!(name, expr);
+
+    u32::try_from(code)
+        .ok()
+        .and_then(char::from_u32)
+        .map(|c| Value::Str(c.to_string()))
+        .ok_or_else(|| EvaluateError::InvalidUnicodeCodePoint(code).into())
+}
+
+pub fn hex(name: String, expr: Evaluated<'_>) -> Result<Value> {
+    let bytes = eval_to_bytes!(name, expr);
+
+    Ok(Value::Str(hex::encode(bytes)))
+}
+
+pub fn unhex(name: String, expr: Evaluated<'_>) -> Result<Value> {
+    let string = eval_to_str!(name, expr);
+
+    hex::decode(&string)
+        .map(Value::Bytea)
+        .map_err(|_| EvaluateError::InvalidHexString(string).into())
+}
+
+pub fn to_base64(name: String, expr: Evaluated<'_>) -> Result<Value> {
+    let bytes = eval_to_bytes!(name, expr);
+
+    Ok(Value::Str(base64::encode(bytes)))
+}
+
+pub fn from_base64(name: String, expr: Evaluated<'_>) -> Result<Value> {
+    let string = eval_to_str!(name, expr);
+
+    base64::decode(&string)
+        .map(Value::Bytea)
+        .map_err(|_| EvaluateError::InvalidBase64String(string).into())
+}
+
 pub fn repeat(name: String, expr: Evaluated<'_>, num: Evaluated<'_>) -> Result<Value> {
     let expr = eval_to_str!(name, expr);
     let num = eval_to_int!(name, num) as usize;
@@ -199,6 +472,50 @@ pub fn repeat(name: String, expr: Evaluated<'_>, num: Evaluated<'_>) -> Result<V
     Ok(Value::Str(value))
 }
 
+pub fn glob(name: String, expr: Evaluated<'_>, pattern: Evaluated<'_>) -> Result<Value> {
+    let expr = eval_to_str!(name, expr);
+    let pattern = eval_to_str!(name, pattern);
+
+    expr.glob(&pattern).map(Value::Bool)
+}
+
+/// Edit distance in characters, or `max_distance + 1` once it is known to exceed `max_distance`.
+pub fn levenshtein(
+    name: String,
+    left: Evaluated<'_>,
+    right: Evaluated<'_>,
+    max_distance: Option<Evaluated<'_>>,
+) -> Result<Value> {
+    let left = eval_to_str!(name, left);
+    let right = eval_to_str!(name, right);
+    let max_distance = match max_distance {
+        Some(max_distance) => Some(
+            usize::try_from(eval_to_int!(name, max_distance))
+                .map_err(|_| EvaluateError::FunctionRequiresUSizeValue(name.clone()))?,
+        ),
+        None => None,
+    };
+
+    let distance = left.levenshtein(&right, max_distance);
+
+    Ok(Value::I64(distance as i64))
+}
+
+/// `1 - distance / length of the longer string`, `1` for two empty strings.
+pub fn similarity(name: String, left: Evaluated<'_>, right: Evaluated<'_>) -> Result<Value> {
+    let left = eval_to_str!(name, left);
+    let right = eval_to_str!(name, right);
+    let len = left.chars().count().max(right.chars().count());
+
+    if len == 0 {
+        return Ok(Value::F64(1.0));
+    }
+
+    let distance = left.levenshtein(&right, None);
+
+    Ok(Value::F64(1.0 - distance as f64 / len as f64))
+}
+
 pub fn substr(
     name: String,
     expr: Evaluated<'_>,
@@ -206,20 +523,25 @@ pub fn substr(
     count: Option<Evaluated<'_>>,
 ) -> Result<Value> {
     let string = eval_to_str!(name, expr);
-    let start = eval_to_int!(name, start) - 1;
+    let len = string.chars().count() as i64;
+    let start = eval_to_int!(name, start).saturating_sub(1);
     let count = match count {
         Some(v) => eval_to_int!(name, v),
-        None => string.len() as i64,
+        None => len,
     };
 
-    let end = if count < 0 {
+    if count < 0 {
         return Err(EvaluateError::NegativeSubstrLenNotAllowed.into());
-    } else {
-        min(max(start + count, 0) as usize, string.len())
-    };
+    }
+
+    let end = start.saturating_add(count).clamp(0, len);
+    let start = start.clamp(0, len);
+    let string = string
+        .chars()
+        .skip(start as usize)
+        .take((end - start) as usize)
+        .collect();
 
-    let start = min(max(start, 0) as usize, string.len());
-    let string = String::from(&string[start..end]);
     Ok(Value::Str(string))
 }
 
@@ -407,6 +729,39 @@ pub fn unwrap(name: String, expr: Evaluated<'_>, selector: Evaluated<'_>) -> Res
     value.selector(&selector)
 }
 
+/// Evaluated once per call, so every row gets its own value.
+pub fn random(name: String, range: Option<(Evaluated<'_>, Evaluated<'_>)>) -> Result<Value> {
+    let (min, max) = match range {
+        Some((min, max)) => (eval_to_int!(name, min), eval_to_int!(name, max)),
+        None => {
+            let value = session::current(|session| session.with_rng(Rng::next_f64))?;
+
+            return Ok(Value::F64(value));
+        }
+    };
+
+    if min > max {
+        return Err(EvaluateError::InvalidRandomRange { min, max }.into());
+    }
+
+    let value = session::current(|session| session.with_rng(|rng| rng.next_i64_in(min, max)))?;
+
+    Ok(Value::I64(value))
+}
+
 pub fn generate_uuid() -> Value {
     Value::Uuid(Uuid::new_v4().as_u128())
 }
+
+/// Takes the next value of the sequence, which must already be loaded for the running statement.
+pub fn nextval(name: String, sequence: Evaluated<'_>) -> Result<Value> {
+    let sequence = eval_to_str!(name, sequence);
+
+    next_value(&sequence).map(Value::I64)
+}
+
+pub fn currval(name: String, sequence: Evaluated<'_>) -> Result<Value> {
+    let sequence = eval_to_str!(name, sequence);
+
+    current_value(&sequence).map(Value::I64)
+}
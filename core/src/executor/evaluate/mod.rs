@@ -2,13 +2,21 @@ mod error;
 mod evaluated;
 mod expr;
 mod function;
+mod random;
 mod stateless;
 
 use {
-    super::{context::FilterContext, select::select},
+    super::{
+        context::FilterContext,
+        select::select,
+        sequence::load_sequence,
+        session::{self, Session},
+        udf::call_function,
+    },
     crate::{
-        ast::{Aggregate, Expr, Function},
+        ast::{Aggregate, Expr, Function, ToSql, Window},
         data::Value,
+        plan::ParameterError,
         result::Result,
         store::GStore,
     },
@@ -22,24 +30,33 @@ use {
     std::{borrow::Cow, rc::Rc},
 };
 
-pub use {error::EvaluateError, evaluated::Evaluated, stateless::evaluate_stateless};
+pub use {
+    error::EvaluateError,
+    evaluated::Evaluated,
+    expr::{FloatDivisionByZero, OperatorSettings},
+    random::Rng,
+    stateless::evaluate_stateless,
+};
 
 #[async_recursion(?Send)]
 pub async fn evaluate<'a>(
     storage: &'a dyn GStore,
     context: Option<Rc<FilterContext<'a>>>,
     aggregated: Option<Rc<HashMap<&'a Aggregate, Value>>>,
+    windowed: Option<Rc<HashMap<&'a Window, Value>>>,
     expr: &'a Expr,
 ) -> Result<Evaluated<'a>> {
     let eval = |expr| {
         let context = context.as_ref().map(Rc::clone);
         let aggregated = aggregated.as_ref().map(Rc::clone);
+        let windowed = windowed.as_ref().map(Rc::clone);
 
-        evaluate(storage, context, aggregated, expr)
+        evaluate(storage, context, aggregated, windowed, expr)
     };
 
     match expr {
         Expr::Literal(ast_literal) => expr::literal(ast_literal),
+        Expr::Placeholder(index) => Err(ParameterError::NotBound(*index).into()),
         Expr::TypedString { data_type, value } => {
             expr::typed_string(data_type, Cow::Borrowed(value))
         }
@@ -85,12 +102,45 @@ pub async fn evaluate<'a>(
                 .next()
                 .unwrap_or_else(|| Err(EvaluateError::NestedSelectRowNotFound.into()))
         }
-        Expr::BinaryOp { op, left, right } => {
-            let left = eval(left).await?;
-            let right = eval(right).await?;
+        Expr::BinaryOp { op, left, right } => match (left.as_ref(), right.as_ref()) {
+            (Expr::Tuple(left), Expr::Tuple(right)) => {
+                let left = stream::iter(left).then(eval).try_collect().await?;
+                let right = stream::iter(right).then(eval).try_collect().await?;
 
-            expr::binary_op(op, left, right)
+                expr::tuple_op(op, left, right)
+            }
+            _ => {
+                let left = eval(left).await?;
+                let right = eval(right).await?;
+                let settings = session::current(Session::operator_settings).unwrap_or_default();
+
+                expr::binary_op(op, left, right, settings)
+            }
+        },
+        Expr::AnyOp { left, op, right } | Expr::AllOp { left, op, right } => {
+            let all = matches!(expr, Expr::AllOp { .. });
+            let left = eval(left).await?;
+            let settings = session::current(Session::operator_settings).unwrap_or_default();
+
+            match right.as_ref() {
+                Expr::Subquery(query) => select(storage, query, context)
+                    .await?
+                    .and_then(|row| ready(row.take_first_value().map(Evaluated::from)))
+                    .and_then(|right| ready(expr::holds(op, &left, right, settings)))
+                    .try_filter(|holds| ready(*holds != all))
+                    .try_next()
+                    .await
+                    .map(|v| v.is_some() ^ all)
+                    .map(Value::Bool)
+                    .map(Evaluated::from),
+                _ => {
+                    let right = eval(right).await?;
+
+                    expr::quantified_op(op, all, left, right, settings)
+                }
+            }
         }
+        Expr::Tuple(_) => Err(EvaluateError::UnsupportedTupleOperation(expr.to_sql()).into()),
         Expr::UnaryOp { op, expr } => {
             let v = eval(expr).await?;
 
@@ -103,11 +153,19 @@ pub async fn evaluate<'a>(
             Some(value) => Ok(Evaluated::from(value.clone())),
             None => Err(EvaluateError::UnreachableEmptyAggregateValue(*aggr.clone()).into()),
         },
+        Expr::Window(window) => match windowed
+            .as_ref()
+            .and_then(|windowed| windowed.get(window.as_ref()))
+        {
+            Some(value) => Ok(Evaluated::from(value.clone())),
+            None => Err(EvaluateError::UnreachableEmptyWindowValue(*window.clone()).into()),
+        },
         Expr::Function(func) => {
             let context = context.as_ref().map(Rc::clone);
             let aggregated = aggregated.as_ref().map(Rc::clone);
+            let windowed = windowed.as_ref().map(Rc::clone);
 
-            evaluate_function(storage, context, aggregated, func).await
+            evaluate_function(storage, context, aggregated, windowed, func).await
         }
         Expr::Cast { expr, data_type } => eval(expr).await?.cast(data_type),
         Expr::Extract { field, expr } => eval(expr).await?.extract(field),
@@ -134,16 +192,30 @@ pub async fn evaluate<'a>(
             negated,
         } => {
             let target = eval(expr).await?;
+            let mut rows = Box::pin(
+                select(storage, subquery, context)
+                    .await?
+                    .and_then(|row| ready(row.take_first_value().map(Evaluated::from))),
+            );
+
+            // without a match, a NULL on either side makes it NULL unless the subquery is empty
+            let mut empty = true;
+            let mut has_null = target.is_null();
+            while let Some(evaluated) = rows.try_next().await? {
+                empty = false;
+
+                if evaluated.is_null() {
+                    has_null = true;
+                } else if !target.is_null() && evaluated == target {
+                    return Ok(Evaluated::from(Value::Bool(!negated)));
+                }
+            }
 
-            select(storage, subquery, context)
-                .await?
-                .and_then(|row| ready(row.take_first_value().map(Evaluated::from)))
-                .try_filter(|evaluated| ready(evaluated == &target))
-                .try_next()
-                .await
-                .map(|v| v.is_some() ^ negated)
-                .map(Value::Bool)
-                .map(Evaluated::from)
+            if empty || !has_null {
+                Ok(Evaluated::from(Value::Bool(*negated)))
+            } else {
+                Ok(Evaluated::from(Value::Null))
+            }
         }
         Expr::Between {
             expr,
@@ -204,6 +276,7 @@ async fn evaluate_function<'a>(
     storage: &'a dyn GStore,
     context: Option<Rc<FilterContext<'a>>>,
     aggregated: Option<Rc<HashMap<&'a Aggregate, Value>>>,
+    windowed: Option<Rc<HashMap<&'a Window, Value>>>,
     func: &'a Function,
 ) -> Result<Evaluated<'a>> {
     use function as f;
@@ -211,8 +284,9 @@ async fn evaluate_function<'a>(
     let eval = |expr| {
         let context = context.as_ref().map(Rc::clone);
         let aggregated = aggregated.as_ref().map(Rc::clone);
+        let windowed = windowed.as_ref().map(Rc::clone);
 
-        evaluate(storage, context, aggregated, expr)
+        evaluate(storage, context, aggregated, windowed, expr)
     };
 
     let name = || func.to_string();
@@ -223,6 +297,18 @@ async fn evaluate_function<'a>(
             let exprs = stream::iter(exprs).then(eval).try_collect().await?;
             f::concat(exprs)
         }
+        Function::ConcatWs { separator, exprs } => {
+            let separator = eval(separator).await?;
+            let exprs = stream::iter(exprs).then(eval).try_collect().await?;
+
+            f::concat_ws(name(), separator, exprs)
+        }
+        Function::Format { format, args } => {
+            let format = eval(format).await?;
+            let args = stream::iter(args).then(eval).try_collect().await?;
+
+            f::format(name(), format, args)
+        }
         Function::IfNull { expr, then } => f::ifnull(eval(expr).await?, eval(then).await?),
         Function::Lower(expr) => f::lower(name(), eval(expr).await?),
         Function::Upper(expr) => f::upper(name(), eval(expr).await?),
@@ -278,12 +364,43 @@ async fn evaluate_function<'a>(
 
             f::reverse(name(), expr)
         }
+        Function::Soundex(expr) => {
+            let expr = eval(expr).await?;
+
+            f::soundex(name(), expr)
+        }
         Function::Repeat { expr, num } => {
             let expr = eval(expr).await?;
             let num = eval(num).await?;
 
             f::repeat(name(), expr, num)
         }
+        Function::Glob { expr, pattern } => {
+            let expr = eval(expr).await?;
+            let pattern = eval(pattern).await?;
+
+            f::glob(name(), expr, pattern)
+        }
+        Function::Levenshtein {
+            left,
+            right,
+            max_distance,
+        } => {
+            let left = eval(left).await?;
+            let right = eval(right).await?;
+            let max_distance = match max_distance {
+                Some(v) => Some(eval(v).await?),
+                None => None,
+            };
+
+            f::levenshtein(name(), left, right, max_distance)
+        }
+        Function::Similarity { left, right } => {
+            let left = eval(left).await?;
+            let right = eval(right).await?;
+
+            f::similarity(name(), left, right)
+        }
         Function::Substr { expr, start, count } => {
             let expr = eval(expr).await?;
             let start = eval(start).await?;
@@ -298,6 +415,14 @@ async fn evaluate_function<'a>(
         // --- float ---
         Function::Abs(expr) => f::abs(name(), eval(expr).await?),
         Function::Sign(expr) => f::sign(name(), eval(expr).await?),
+        Function::Length(expr) => f::length(name(), eval(expr).await?),
+        Function::Ascii(expr) => f::ascii(name(), eval(expr).await?),
+        Function::Chr(expr) => f::chr(name(), eval(expr).await?),
+        Function::Ord(expr) => f::ord(name(), eval(expr).await?),
+        Function::Hex(expr) => f::hex(name(), eval(expr).await?),
+        Function::Unhex(expr) => f::unhex(name(), eval(expr).await?),
+        Function::ToBase64(expr) => f::to_base64(name(), eval(expr).await?),
+        Function::FromBase64(expr) => f::from_base64(name(), eval(expr).await?),
         Function::Sqrt(expr) => f::sqrt(eval(expr).await?),
         Function::Power { expr, power } => {
             let expr = eval(expr).await?;
@@ -362,6 +487,32 @@ async fn evaluate_function<'a>(
             f::unwrap(name(), expr, selector)
         }
         Function::GenerateUuid() => Ok(f::generate_uuid()),
+        Function::Random(range) => {
+            let range = match range {
+                Some((min, max)) => Some((eval(min).await?, eval(max).await?)),
+                None => None,
+            };
+
+            f::random(name(), range)
+        }
+        Function::Nextval(expr) => {
+            let sequence: Value = eval(expr).await?.try_into()?;
+            if let Value::Str(sequence) = &sequence {
+                load_sequence(storage, sequence).await?;
+            }
+
+            f::nextval(name(), Evaluated::from(sequence))
+        }
+        Function::Currval(expr) => f::currval(name(), eval(expr).await?),
+        Function::Custom { name, args } => {
+            let args = stream::iter(args)
+                .then(eval)
+                .map(|evaluated| evaluated.and_then(Value::try_from))
+                .try_collect()
+                .await?;
+
+            call_function(name, args)
+        }
         Function::Now() => Ok(Value::Timestamp(Utc::now().naive_utc())),
     }
     .map(Evaluated::from)
@@ -3,6 +3,11 @@ use {
     crate::{
         ast::{Expr, Function},
         data::{Row, Value},
+        executor::{
+            session::{self, Session},
+            udf::call_function,
+        },
+        plan::ParameterError,
         result::Result,
     },
     chrono::prelude::Utc,
@@ -19,6 +24,7 @@ pub fn evaluate_stateless<'a>(
 
     match expr {
         Expr::Literal(ast_literal) => expr::literal(ast_literal),
+        Expr::Placeholder(index) => Err(ParameterError::NotBound(*index).into()),
         Expr::TypedString { data_type, value } => {
             expr::typed_string(data_type, Cow::Borrowed(value))
         }
@@ -42,11 +48,28 @@ pub fn evaluate_stateless<'a>(
             .map(Evaluated::from)
         }
         Expr::Nested(expr) => eval(expr),
-        Expr::BinaryOp { op, left, right } => {
+        Expr::BinaryOp { op, left, right } => match (left.as_ref(), right.as_ref()) {
+            (Expr::Tuple(left), Expr::Tuple(right)) => {
+                let left = left.iter().map(eval).collect::<Result<_>>()?;
+                let right = right.iter().map(eval).collect::<Result<_>>()?;
+
+                expr::tuple_op(op, left, right)
+            }
+            _ => {
+                let left = eval(left)?;
+                let right = eval(right)?;
+                let settings = session::current(Session::operator_settings).unwrap_or_default();
+
+                expr::binary_op(op, left, right, settings)
+            }
+        },
+        Expr::AnyOp { left, op, right } | Expr::AllOp { left, op, right } => {
+            let all = matches!(expr, Expr::AllOp { .. });
             let left = eval(left)?;
             let right = eval(right)?;
+            let settings = session::current(Session::operator_settings).unwrap_or_default();
 
-            expr::binary_op(op, left, right)
+            expr::quantified_op(op, all, left, right, settings)
         }
         Expr::UnaryOp { op, expr } => {
             let v = eval(expr)?;
@@ -128,6 +151,18 @@ fn evaluate_function<'a>(
 
             f::concat(exprs)
         }
+        Function::ConcatWs { separator, exprs } => {
+            let separator = eval(separator)?;
+            let exprs = exprs.iter().map(eval).collect::<Result<_>>()?;
+
+            f::concat_ws(name(), separator, exprs)
+        }
+        Function::Format { format, args } => {
+            let format = eval(format)?;
+            let args = args.iter().map(eval).collect::<Result<_>>()?;
+
+            f::format(name(), format, args)
+        }
         Function::Lower(expr) => f::lower(name(), eval(expr)?),
         Function::Upper(expr) => f::upper(name(), eval(expr)?),
         Function::Left { expr, size } | Function::Right { expr, size } => {
@@ -170,12 +205,40 @@ fn evaluate_function<'a>(
 
             f::reverse(name(), expr)
         }
+        Function::Soundex(expr) => {
+            let expr = eval(expr)?;
+
+            f::soundex(name(), expr)
+        }
         Function::Repeat { expr, num } => {
             let expr = eval(expr)?;
             let num = eval(num)?;
 
             f::repeat(name(), expr, num)
         }
+        Function::Glob { expr, pattern } => {
+            let expr = eval(expr)?;
+            let pattern = eval(pattern)?;
+
+            f::glob(name(), expr, pattern)
+        }
+        Function::Levenshtein {
+            left,
+            right,
+            max_distance,
+        } => {
+            let left = eval(left)?;
+            let right = eval(right)?;
+            let max_distance = eval_opt(max_distance.as_ref())?;
+
+            f::levenshtein(name(), left, right, max_distance)
+        }
+        Function::Similarity { left, right } => {
+            let left = eval(left)?;
+            let right = eval(right)?;
+
+            f::similarity(name(), left, right)
+        }
         Function::Substr { expr, start, count } => {
             let expr = eval(expr)?;
             let start = eval(start)?;
@@ -195,6 +258,14 @@ fn evaluate_function<'a>(
         Function::Abs(expr) => f::abs(name(), eval(expr)?),
         Function::IfNull { expr, then } => f::ifnull(eval(expr)?, eval(then)?),
         Function::Sign(expr) => f::sign(name(), eval(expr)?),
+        Function::Length(expr) => f::length(name(), eval(expr)?),
+        Function::Ascii(expr) => f::ascii(name(), eval(expr)?),
+        Function::Chr(expr) => f::chr(name(), eval(expr)?),
+        Function::Ord(expr) => f::ord(name(), eval(expr)?),
+        Function::Hex(expr) => f::hex(name(), eval(expr)?),
+        Function::Unhex(expr) => f::unhex(name(), eval(expr)?),
+        Function::ToBase64(expr) => f::to_base64(name(), eval(expr)?),
+        Function::FromBase64(expr) => f::from_base64(name(), eval(expr)?),
         Function::Ceil(expr) => f::ceil(name(), eval(expr)?),
         Function::Round(expr) => f::round(name(), eval(expr)?),
         Function::Floor(expr) => f::floor(name(), eval(expr)?),
@@ -252,6 +323,24 @@ fn evaluate_function<'a>(
             f::unwrap(name(), expr, selector)
         }
         Function::GenerateUuid() => Ok(f::generate_uuid()),
+        Function::Random(range) => {
+            let range = match range {
+                Some((min, max)) => Some((eval(min)?, eval(max)?)),
+                None => None,
+            };
+
+            f::random(name(), range)
+        }
+        Function::Nextval(expr) => f::nextval(name(), eval(expr)?),
+        Function::Currval(expr) => f::currval(name(), eval(expr)?),
+        Function::Custom { name, args } => {
+            let args = args
+                .iter()
+                .map(|arg| eval(arg).and_then(Value::try_from))
+                .collect::<Result<Vec<_>>>()?;
+
+            call_function(name, args)
+        }
         Function::Now() => Ok(Value::Timestamp(Utc::now().naive_utc())),
     }
     .map(Evaluated::from)
@@ -1,22 +1,37 @@
 use {
     super::{
-        alter::{create_table, drop_table},
-        fetch::{fetch, fetch_columns},
-        select::{select, select_with_labels},
+        alter::{
+            create_materialized_view, create_table, create_view, drop_table,
+            refresh_materialized_view, truncate_table,
+        },
+        cancel::Cancellation,
+        explain::explain_analyze,
+        fetch::{fetch, FetchError},
+        merge::merge,
+        metrics::Metrics,
+        returning::returning,
+        select::select_with_labels,
+        sequence::{
+            create_sequence, discard_sequences, drop_sequence, fill_auto_increment,
+            flush_sequences, load_nextval_sequences,
+        },
+        session::Session,
+        udf::{create_function, drop_function, load_functions},
         update::Update,
         validate::{validate_unique, ColumnValidation},
     },
     crate::{
-        ast::{DataType, SetExpr, Statement, Values},
-        data::{Row, Schema, Value},
+        ast::{ColumnDef, DataType, SetExpr, Statement, Values},
+        data::{get_name, schema::ColumnDefExt, Row, Schema, Value},
         executor::limit::Limit,
-        result::MutResult,
+        result::{MutResult, Result, TrySelf},
         store::{GStore, GStoreMut},
     },
     futures::stream::{self, TryStreamExt},
     serde::{Deserialize, Serialize},
-    std::{fmt::Debug, rc::Rc},
+    std::{fmt::Debug, future::Future, rc::Rc},
     thiserror::Error as ThisError,
+    utils::IndexMap,
 };
 
 #[cfg(feature = "alter-table")]
@@ -28,14 +43,37 @@ use {
     crate::data::SchemaIndex,
 };
 
-use crate::data::get_name;
 #[cfg(feature = "metadata")]
-use crate::{ast::Variable, result::TrySelf};
+use crate::ast::Variable;
 
 #[derive(ThisError, Serialize, Debug, PartialEq)]
 pub enum ExecuteError {
     #[error("table not found: {0}")]
     TableNotFound(String),
+
+    #[error("DELETE and UPDATE require a WHERE clause, use WHERE TRUE to affect every row")]
+    WhereClauseRequired,
+
+    #[error("statement execution was cancelled")]
+    Cancelled,
+
+    #[error(
+        "INSERT has {} target columns {:?} but its SELECT gives {} columns {:?}",
+        .target_columns.len(),
+        .target_columns,
+        .source_columns.len(),
+        .source_columns
+    )]
+    InsertColumnCountMismatch {
+        target_columns: Vec<String>,
+        source_columns: Vec<String>,
+    },
+
+    #[error("materialized view can only be changed by REFRESH MATERIALIZED VIEW: {0}")]
+    MaterializedViewNotWritable(String),
+
+    #[error("view has no rows of its own to change, change the tables it reads: {0}")]
+    ViewNotWritable(String),
 }
 
 #[derive(Debug, Serialize, Deserialize, PartialEq)]
@@ -49,6 +87,11 @@ pub enum Payload {
     },
     Delete(usize),
     Update(usize),
+    Merge(usize),
+    RefreshMaterializedView(usize),
+    Truncate,
+    DropSequence,
+    DropFunction,
     DropTable,
 
     #[cfg(feature = "alter-table")]
@@ -64,6 +107,12 @@ pub enum Payload {
     Commit,
     #[cfg(feature = "transaction")]
     Rollback,
+    #[cfg(feature = "transaction")]
+    Savepoint,
+    #[cfg(feature = "transaction")]
+    RollbackToSavepoint,
+    #[cfg(feature = "transaction")]
+    ReleaseSavepoint,
 
     #[cfg(feature = "metadata")]
     ShowVariable(PayloadVariable),
@@ -72,6 +121,48 @@ pub enum Payload {
     ShowIndexes(Vec<SchemaIndex>),
 }
 
+impl Payload {
+    /// Zips the labels of a `SELECT` result with the values of each row, in column order.
+    ///
+    /// Returns `None` if the payload is not a `SELECT` result.
+    pub fn select(&self) -> Option<impl Iterator<Item = IndexMap<&str, &Value>>> {
+        match self {
+            Payload::Select { labels, rows } => Some(
+                rows.iter()
+                    .map(move |row| labels.iter().map(String::as_str).zip(row.iter()).collect()),
+            ),
+            _ => None,
+        }
+    }
+
+    /// Number of rows an `INSERT`, `UPDATE`, `DELETE` or `MERGE` affected.
+    ///
+    /// Returns `None` if the payload is not a result of those statements.
+    pub fn affected(&self) -> Option<usize> {
+        match self {
+            Payload::Insert(num)
+            | Payload::Update(num)
+            | Payload::Delete(num)
+            | Payload::Merge(num) => Some(*num),
+            _ => None,
+        }
+    }
+}
+
+/// Rejects changing the rows of `schema` unless it is a table: a view has no rows of its own,
+/// and the rows of a materialized view only change when it is refreshed.
+pub(crate) fn check_writable(schema: &Schema) -> Result<()> {
+    let table_name = schema.table_name.to_owned();
+
+    if schema.view.is_some() {
+        return Err(ExecuteError::ViewNotWritable(table_name).into());
+    } else if schema.materialized_view.is_some() {
+        return Err(ExecuteError::MaterializedViewNotWritable(table_name).into());
+    }
+
+    Ok(())
+}
+
 #[cfg(feature = "metadata")]
 #[derive(Debug, Serialize, Deserialize, PartialEq)]
 pub enum PayloadVariable {
@@ -83,16 +174,54 @@ pub enum PayloadVariable {
 pub async fn execute_atomic<T: GStore + GStoreMut>(
     storage: T,
     statement: &Statement,
+) -> MutResult<T, Payload> {
+    execute_atomic_cancellable(
+        storage,
+        statement,
+        &Session::default(),
+        &Cancellation::default(),
+        &Metrics::default(),
+    )
+    .await
+}
+
+#[cfg(feature = "transaction")]
+pub async fn execute_atomic_cancellable<T: GStore + GStoreMut>(
+    storage: T,
+    statement: &Statement,
+    session: &Session,
+    cancellation: &Cancellation,
+    metrics: &Metrics,
 ) -> MutResult<T, Payload> {
     if matches!(
         statement,
-        Statement::StartTransaction | Statement::Rollback | Statement::Commit
+        Statement::StartTransaction
+            | Statement::Rollback
+            | Statement::Commit
+            | Statement::Savepoint { .. }
+            | Statement::RollbackToSavepoint { .. }
+            | Statement::ReleaseSavepoint { .. }
     ) {
-        return execute(storage, statement).await;
+        return execute_cancellable(storage, statement, session, cancellation, metrics).await;
     }
 
+    autocommit(storage, |storage| {
+        execute_cancellable(storage, statement, session, cancellation, metrics)
+    })
+    .await
+}
+
+/// Runs `f` in a transaction of its own, which is committed on success and rolled back on
+/// failure. Inside an explicit transaction, `f` just runs as a part of it.
+#[cfg(feature = "transaction")]
+pub(crate) async fn autocommit<T, F, Fut, U>(storage: T, f: F) -> MutResult<T, U>
+where
+    T: GStore + GStoreMut,
+    F: FnOnce(T) -> Fut,
+    Fut: Future<Output = MutResult<T, U>>,
+{
     let (storage, autocommit) = storage.begin(true).await?;
-    let result = execute(storage, statement).await;
+    let result = f(storage).await;
 
     match (result, autocommit) {
         (Ok((storage, payload)), true) => {
@@ -109,9 +238,72 @@ pub async fn execute_atomic<T: GStore + GStoreMut>(
     }
 }
 
+#[cfg(not(feature = "transaction"))]
+pub(crate) async fn autocommit<T, F, Fut, U>(storage: T, f: F) -> MutResult<T, U>
+where
+    T: GStore + GStoreMut,
+    F: FnOnce(T) -> Fut,
+    Fut: Future<Output = MutResult<T, U>>,
+{
+    f(storage).await
+}
+
+#[cfg(not(feature = "transaction"))]
 pub async fn execute<T: GStore + GStoreMut>(
     storage: T,
     statement: &Statement,
+) -> MutResult<T, Payload> {
+    execute_cancellable(
+        storage,
+        statement,
+        &Session::default(),
+        &Cancellation::default(),
+        &Metrics::default(),
+    )
+    .await
+}
+
+/// Same as `execute`, but runs in `session`, stops between rows with `ExecuteError::Cancelled`
+/// once `cancellation` is cancelled, and records the statement statistics into `metrics`.
+pub async fn execute_cancellable<T: GStore + GStoreMut>(
+    storage: T,
+    statement: &Statement,
+    session: &Session,
+    cancellation: &Cancellation,
+    metrics: &Metrics,
+) -> MutResult<T, Payload> {
+    session
+        .scope(async {
+            metrics.reset();
+            discard_sequences(session);
+
+            if let Err(error) = load_functions(&storage, session).await {
+                return Err((storage, error));
+            }
+
+            let executed = metrics
+                .measure(execute_statement(storage, statement, cancellation, metrics))
+                .await;
+
+            match executed {
+                Ok((storage, payload)) => flush_sequences(storage, session)
+                    .await
+                    .map(|(storage, ())| (storage, payload)),
+                Err((storage, error)) => {
+                    discard_sequences(session);
+
+                    Err((storage, error))
+                }
+            }
+        })
+        .await
+}
+
+async fn execute_statement<T: GStore + GStoreMut>(
+    storage: T,
+    statement: &Statement,
+    cancellation: &Cancellation,
+    metrics: &Metrics,
 ) -> MutResult<T, Payload> {
     macro_rules! try_block {
         ($storage: expr, $block: block) => {{
@@ -136,6 +328,47 @@ pub async fn execute<T: GStore + GStoreMut>(
         } => create_table(storage, name, columns, *if_not_exists, source)
             .await
             .map(|(storage, _)| (storage, Payload::Create)),
+        Statement::CreateView {
+            name,
+            columns,
+            query,
+        } => create_view(storage, name, columns, query, cancellation, metrics)
+            .await
+            .map(|(storage, _)| (storage, Payload::Create)),
+        Statement::CreateMaterializedView {
+            name,
+            columns,
+            query,
+        } => create_materialized_view(storage, name, columns, query, cancellation, metrics)
+            .await
+            .map(|(storage, _)| (storage, Payload::Create)),
+        Statement::RefreshMaterializedView { name } => {
+            refresh_materialized_view(storage, name, cancellation, metrics)
+                .await
+                .map(|(storage, num_rows)| (storage, Payload::RefreshMaterializedView(num_rows)))
+        }
+        Statement::Truncate { table_name } => truncate_table(storage, table_name)
+            .await
+            .map(|(storage, _)| (storage, Payload::Truncate)),
+        //-- Sequences
+        Statement::CreateSequence { name } => create_sequence(storage, name)
+            .await
+            .map(|(storage, _)| (storage, Payload::Create)),
+        Statement::DropSequence { name, if_exists } => drop_sequence(storage, name, *if_exists)
+            .await
+            .map(|(storage, _)| (storage, Payload::DropSequence)),
+        //-- Functions
+        Statement::CreateFunction {
+            name,
+            args,
+            return_type,
+            body,
+        } => create_function(storage, name, args, return_type, body)
+            .await
+            .map(|(storage, _)| (storage, Payload::Create)),
+        Statement::DropFunction { name, if_exists } => drop_function(storage, name, *if_exists)
+            .await
+            .map(|(storage, _)| (storage, Payload::DropFunction)),
         Statement::DropTable {
             names, if_exists, ..
         } => drop_table(storage, names, *if_exists)
@@ -173,24 +406,47 @@ pub async fn execute<T: GStore + GStoreMut>(
             .rollback()
             .await
             .map(|(storage, _)| (storage, Payload::Rollback)),
+        #[cfg(feature = "transaction")]
+        Statement::Savepoint { name } => storage
+            .savepoint(name)
+            .await
+            .map(|(storage, _)| (storage, Payload::Savepoint)),
+        #[cfg(feature = "transaction")]
+        Statement::RollbackToSavepoint { name } => storage
+            .rollback_to_savepoint(name)
+            .await
+            .map(|(storage, _)| (storage, Payload::RollbackToSavepoint)),
+        #[cfg(feature = "transaction")]
+        Statement::ReleaseSavepoint { name } => storage
+            .release_savepoint(name)
+            .await
+            .map(|(storage, _)| (storage, Payload::ReleaseSavepoint)),
         //-- Rows
         Statement::Insert {
             table_name,
             columns,
             source,
-            ..
+            returning: projection,
         } => {
-            let (rows, table_name) = try_block!(storage, {
+            let (rows, table_name, column_defs) = try_block!(storage, {
                 let table_name = get_name(table_name)?;
-                let Schema { column_defs, .. } = storage
+                let schema = storage
                     .fetch_schema(table_name)
                     .await?
                     .ok_or_else(|| ExecuteError::TableNotFound(table_name.to_owned()))?;
-                let column_defs = Rc::from(column_defs);
-                let column_validation = ColumnValidation::All(Rc::clone(&column_defs));
+                check_writable(&schema)?;
+                let Schema { column_defs, .. } = schema;
+                let column_defs: Rc<[ColumnDef]> = Rc::from(column_defs);
+
+                // values and defaults are evaluated without the storage, so the sequences their
+                // NEXTVAL calls advance are loaded beforehand
+                let defaults = column_defs.iter().filter_map(ColumnDefExt::get_default);
+                load_nextval_sequences(&storage, defaults).await?;
 
                 let rows = match &source.body {
                     SetExpr::Values(Values(values_list)) => {
+                        load_nextval_sequences(&storage, values_list.iter().flatten()).await?;
+
                         let limit = Limit::new(source.limit.as_ref(), source.offset.as_ref())?;
                         let rows = values_list
                             .iter()
@@ -200,61 +456,118 @@ pub async fn execute<T: GStore + GStoreMut>(
                         rows.try_collect::<Vec<_>>().await?
                     }
                     SetExpr::Select(_) => {
-                        select(&storage, source, None)
-                            .await?
-                            .and_then(|row| {
-                                let column_defs = Rc::clone(&column_defs);
-
-                                async move {
-                                    row.validate(&column_defs)?;
-                                    Ok(row)
-                                }
-                            })
-                            .try_collect::<Vec<_>>()
-                            .await?
+                        let (labels, rows) = select_with_labels(
+                            &storage,
+                            source,
+                            None,
+                            true,
+                            cancellation.clone(),
+                            metrics.clone(),
+                        )
+                        .await?;
+
+                        // the leading columns take the values when no columns are given, as
+                        // with VALUES
+                        let mismatched = if columns.is_empty() {
+                            labels.len() > column_defs.len()
+                        } else {
+                            labels.len() != columns.len()
+                        };
+                        if mismatched {
+                            let target_columns = if columns.is_empty() {
+                                column_defs
+                                    .iter()
+                                    .map(|ColumnDef { name, .. }| name.to_owned())
+                                    .collect()
+                            } else {
+                                columns.to_owned()
+                            };
+
+                            return Err(ExecuteError::InsertColumnCountMismatch {
+                                target_columns,
+                                source_columns: labels,
+                            }
+                            .into());
+                        }
+
+                        rows.and_then(|row| {
+                            let column_defs = Rc::clone(&column_defs);
+
+                            async move {
+                                let row = row.arrange(&column_defs, columns)?;
+                                row.validate(&column_defs)?;
+                                Ok(row)
+                            }
+                        })
+                        .try_collect::<Vec<_>>()
+                        .await?
                     }
                 };
 
-                validate_unique(&storage, table_name, column_validation, rows.iter()).await?;
-
-                Ok((rows, table_name))
+                Ok((rows, table_name, column_defs))
             });
 
-            let num_rows = rows.len();
+            let (storage, rows) =
+                fill_auto_increment(storage, table_name, &column_defs, rows).await?;
+            let column_validation = ColumnValidation::All(Rc::clone(&column_defs));
+            let validated =
+                validate_unique(&storage, table_name, column_validation, rows.iter()).await;
+            let (storage, ()) = validated.try_self(storage)?;
+
+            let payload = match projection.is_empty() {
+                true => Payload::Insert(rows.len()),
+                false => try_block!(storage, {
+                    let column_names = column_defs
+                        .iter()
+                        .map(|ColumnDef { name, .. }| name.to_owned())
+                        .collect();
+
+                    returning(&storage, table_name, column_names, projection, rows.iter()).await
+                }),
+            };
 
             storage
                 .insert_data(table_name, rows)
                 .await
-                .map(|(storage, _)| (storage, Payload::Insert(num_rows)))
+                .map(|(storage, _)| (storage, payload))
         }
         Statement::Update {
             table_name,
             selection,
             assignments,
+            returning: projection,
         } => {
-            let (table_name, rows) = try_block!(storage, {
+            let (table_name, rows, payload) = try_block!(storage, {
                 let table_name = get_name(table_name)?;
-                let Schema { column_defs, .. } = storage
+                let schema = storage
                     .fetch_schema(table_name)
                     .await?
                     .ok_or_else(|| ExecuteError::TableNotFound(table_name.to_owned()))?;
+                check_writable(&schema)?;
+                let Schema { column_defs, .. } = schema;
                 let update = Update::new(&storage, table_name, assignments, &column_defs)?;
 
-                let all_columns = Rc::from(update.all_columns());
+                let all_columns: Rc<[String]> = Rc::from(update.all_columns());
                 let columns_to_update = update.columns_to_update();
-                let rows = fetch(&storage, table_name, all_columns, selection.as_ref())
-                    .await?
-                    .and_then(|item| {
-                        let update = &update;
-                        let (_, key, row) = item;
-
-                        async move {
-                            let row = update.apply(row).await?;
-                            Ok((key, row))
-                        }
-                    })
-                    .try_collect::<Vec<_>>()
-                    .await?;
+                let rows = fetch(
+                    &storage,
+                    table_name,
+                    Rc::clone(&all_columns),
+                    selection.as_ref(),
+                    cancellation.clone(),
+                )
+                .await?
+                .and_then(|item| {
+                    let update = &update;
+                    let (_, key, row) = item;
+
+                    async move {
+                        let row = update.apply(row).await?;
+                        Ok((key, row))
+                    }
+                })
+                .try_collect::<Vec<_>>()
+                .await?;
 
                 let column_validation =
                     ColumnValidation::SpecifiedColumns(Rc::from(column_defs), columns_to_update);
@@ -266,45 +579,87 @@ pub async fn execute<T: GStore + GStoreMut>(
                 )
                 .await?;
 
-                Ok((table_name, rows))
-            });
+                let payload = match projection.is_empty() {
+                    true => Payload::Update(rows.len()),
+                    false => {
+                        let rows = rows.iter().map(|(_, row)| row);
 
-            let num_rows = rows.len();
+                        returning(&storage, table_name, all_columns, projection, rows).await?
+                    }
+                };
+
+                Ok((table_name, rows, payload))
+            });
 
             storage
                 .update_data(table_name, rows)
                 .await
-                .map(|(storage, _)| (storage, Payload::Update(num_rows)))
+                .map(|(storage, _)| (storage, payload))
         }
         Statement::Delete {
             table_name,
             selection,
+            returning: projection,
         } => {
-            let (table_name, keys) = try_block!(storage, {
+            let (table_name, keys, payload) = try_block!(storage, {
                 let table_name = get_name(table_name)?;
-                let columns = Rc::from(fetch_columns(&storage, table_name).await?);
-
-                let keys = fetch(&storage, table_name, columns, selection.as_ref())
+                let schema = storage
+                    .fetch_schema(table_name)
                     .await?
-                    .map_ok(|(_, key, _)| key)
-                    .try_collect::<Vec<_>>()
-                    .await?;
+                    .ok_or_else(|| FetchError::TableNotFound(table_name.to_owned()))?;
+                check_writable(&schema)?;
+                let columns: Rc<[String]> = schema
+                    .column_names()
+                    .into_iter()
+                    .map(ToOwned::to_owned)
+                    .collect();
+
+                let rows = fetch(
+                    &storage,
+                    table_name,
+                    Rc::clone(&columns),
+                    selection.as_ref(),
+                    cancellation.clone(),
+                )
+                .await?
+                .map_ok(|(_, key, row)| (key, row))
+                .try_collect::<Vec<_>>()
+                .await?;
 
-                Ok((table_name, keys))
-            });
+                let payload = match projection.is_empty() {
+                    true => Payload::Delete(rows.len()),
+                    false => {
+                        let deleted = rows.iter().map(|(_, row)| row);
+
+                        returning(&storage, table_name, columns, projection, deleted).await?
+                    }
+                };
+                let keys = rows.into_iter().map(|(key, _)| key).collect::<Vec<_>>();
 
-            let num_keys = keys.len();
+                Ok((table_name, keys, payload))
+            });
 
             storage
                 .delete_data(table_name, keys)
                 .await
-                .map(|(storage, _)| (storage, Payload::Delete(num_keys)))
+                .map(|(storage, _)| (storage, payload))
         }
+        Statement::Merge(statement) => merge(storage, statement, cancellation, metrics)
+            .await
+            .map(|(storage, num_rows)| (storage, Payload::Merge(num_rows))),
 
         //- Selection
         Statement::Query(query) => {
             let (labels, rows) = try_block!(storage, {
-                let (labels, rows) = select_with_labels(&storage, query, None, true).await?;
+                let (labels, rows) = select_with_labels(
+                    &storage,
+                    query,
+                    None,
+                    true,
+                    cancellation.clone(),
+                    metrics.clone(),
+                )
+                .await?;
                 let rows = rows
                     .map_ok(|Row(values)| values)
                     .try_collect::<Vec<_>>()
@@ -313,6 +668,13 @@ pub async fn execute<T: GStore + GStoreMut>(
             });
             Ok((storage, Payload::Select { labels, rows }))
         }
+        Statement::ExplainAnalyze(query) => {
+            let payload = try_block!(storage, {
+                explain_analyze(&storage, query, cancellation.clone()).await
+            });
+
+            Ok((storage, payload))
+        }
         Statement::ShowColumns { table_name } => {
             let keys = try_block!(storage, {
                 let table_name = get_name(table_name)?;
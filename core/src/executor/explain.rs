@@ -0,0 +1,96 @@
+use {
+    super::{
+        aggregate::Aggregator,
+        cancel::Cancellation,
+        execute::Payload,
+        metrics::{Metrics, Stage},
+        select::select_with_labels,
+    },
+    crate::{
+        ast::{Query, Select, SetExpr, TableWithJoins},
+        data::{get_alias, Interval, Value},
+        result::Result,
+        store::GStore,
+    },
+    futures::stream::TryStreamExt,
+};
+
+/// Runs the query and returns a row for each stage of its top level `SELECT`, from the output
+/// down to the scan, with the rows the stage produced and the time spent producing them.
+///
+/// The time of a stage includes the time of the stages it reads from, as rows are pulled
+/// through all of them at once.
+pub async fn explain_analyze(
+    storage: &dyn GStore,
+    query: &Query,
+    cancellation: Cancellation,
+) -> Result<Payload> {
+    let metrics = Metrics::profiled();
+    let (_, rows) =
+        select_with_labels(storage, query, None, false, cancellation, metrics.clone()).await?;
+    rows.try_for_each(|_| async { Ok(()) }).await?;
+
+    let rows = stages(storage, query)?
+        .into_iter()
+        .map(|(stage, operator)| {
+            let (rows, elapsed) = metrics.stage(stage);
+            let elapsed = elapsed.num_microseconds().unwrap_or(i64::MAX);
+
+            vec![
+                Value::Str(operator),
+                Value::I64(rows as i64),
+                Value::Interval(Interval::Microsecond(elapsed)),
+            ]
+        })
+        .collect();
+
+    Ok(Payload::Select {
+        labels: vec!["operator".to_owned(), "rows".to_owned(), "time".to_owned()],
+        rows,
+    })
+}
+
+/// Stages the query runs through, named as they are shown, in the reverse order of the flow.
+fn stages(storage: &dyn GStore, query: &Query) -> Result<Vec<(Stage, String)>> {
+    let Select {
+        projection,
+        from: TableWithJoins { relation, joins },
+        selection,
+        group_by,
+        grouping_sets,
+        having,
+        order_by,
+        ..
+    } = match &query.body {
+        SetExpr::Select(select) => select.as_ref(),
+        SetExpr::Values(_) => return Ok(vec![(Stage::Output, "Values".to_owned())]),
+    };
+
+    let aggregate = Aggregator::new(
+        storage,
+        projection,
+        group_by,
+        grouping_sets.as_ref(),
+        having.as_ref(),
+        None,
+    );
+
+    let stages = [
+        (Stage::Output, true, "Output".to_owned()),
+        (Stage::Sort, !order_by.is_empty(), "Sort".to_owned()),
+        (
+            Stage::Aggregate,
+            aggregate.check_aggregate(),
+            "Aggregate".to_owned(),
+        ),
+        (Stage::Filter, selection.is_some(), "Filter".to_owned()),
+        (Stage::Join, !joins.is_empty(), "Join".to_owned()),
+        (Stage::Scan, true, format!("Scan {}", get_alias(relation)?)),
+    ];
+
+    Ok(stages
+        .into_iter()
+        .filter(|(_, used, _)| *used)
+        .map(|(stage, _, operator)| (stage, operator))
+        .collect())
+}
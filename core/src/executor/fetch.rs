@@ -1,9 +1,18 @@
 use {
-    super::{context::FilterContext, filter::check_expr},
+    super::{
+        cancel::Cancellation,
+        context::FilterContext,
+        evaluate::{evaluate, Rng},
+        filter::check_expr,
+        session,
+    },
     crate::{
-        ast::{ColumnDef, Expr, Join, Query, Select, SetExpr, TableFactor, TableWithJoins},
-        data::{get_alias, get_name, Key, Row, TableError},
-        executor::select::{get_labels, select},
+        ast::{
+            Expr, Join, Query, Select, SetExpr, TableAlias, TableFactor, TableSample,
+            TableWithJoins, Values,
+        },
+        data::{get_alias, get_name, Key, Row, Value},
+        executor::select::{fetch_cte_labels, get_labels, select},
         result::{Error, Result},
         store::GStore,
     },
@@ -11,21 +20,42 @@ use {
     futures::stream::{self, StreamExt, TryStream, TryStreamExt},
     itertools::Itertools,
     serde::Serialize,
-    std::{fmt::Debug, rc::Rc},
+    std::{cmp::Ordering, fmt::Debug, iter::from_fn, rc::Rc},
     thiserror::Error as ThisError,
 };
 
 #[cfg(feature = "index")]
 use {
-    super::evaluate::evaluate,
-    crate::{ast::IndexItem, data::get_index},
+    crate::{
+        ast::{IndexCmp, IndexItem, IndexOperator},
+        data::get_index,
+    },
     iter_enum::Iterator,
+    std::{collections::HashSet, ops::Bound},
 };
 
+#[cfg(feature = "metadata")]
+use crate::data::{schema::ColumnDefExt, InformationSchema};
+
 #[derive(ThisError, Serialize, Debug, PartialEq)]
 pub enum FetchError {
     #[error("table not found: {0}")]
     TableNotFound(String),
+
+    #[error("series step must not be zero: {0:?}")]
+    SeriesStepIsZero(Value),
+
+    #[error("series boundaries are not comparable: {start:?} and {end:?}")]
+    SeriesBoundaryTypeMismatch { start: Value, end: Value },
+
+    #[error("TABLESAMPLE percentage must be a number between 0 and 100: {0:?}")]
+    InvalidSamplePercentage(Value),
+
+    #[error("TABLESAMPLE seed must be an integer: {0:?}")]
+    InvalidSampleSeed(Value),
+
+    #[error("derived table has {expected} columns but {found} column aliases")]
+    ColumnAliasCountMismatch { expected: usize, found: usize },
 }
 
 pub async fn fetch<'a>(
@@ -33,6 +63,7 @@ pub async fn fetch<'a>(
     table_name: &'a str,
     columns: Rc<[String]>,
     where_clause: Option<&'a Expr>,
+    cancellation: Cancellation,
 ) -> Result<impl TryStream<Ok = (Rc<[String]>, Key, Row), Error = Error> + 'a> {
     let rows = storage
         .scan_data(table_name)
@@ -40,8 +71,11 @@ pub async fn fetch<'a>(
         .map(stream::iter)?
         .try_filter_map(move |(key, row)| {
             let columns = Rc::clone(&columns);
+            let checked = cancellation.check();
 
             async move {
+                checked?;
+
                 let expr = match where_clause {
                     None => {
                         return Ok(Some((columns, key, row)));
@@ -61,9 +95,11 @@ pub async fn fetch<'a>(
 }
 
 #[derive(futures_enum::Stream)]
-pub enum Rows<I1, I2> {
+pub enum Rows<I1, I2, I3> {
     Derived(I1),
     Table(I2),
+    Series(I3),
+    Virtual(stream::Iter<std::vec::IntoIter<Result<Row>>>),
 }
 
 pub async fn fetch_relation_rows<'a>(
@@ -78,36 +114,107 @@ pub async fn fetch_relation_rows<'a>(
 
             Ok(Rows::Derived(rows))
         }
-        TableFactor::Table { name, .. } => {
+        TableFactor::Table { name, sample, .. } => {
+            let mut sampler = Sampler::new(storage, sample.as_ref()).await?;
+
+            #[cfg(feature = "metadata")]
+            if let Some(table) = InformationSchema::from_name(name) {
+                let rows = fetch_information_schema(storage, table).await?;
+                let rows = rows
+                    .into_iter()
+                    .filter_map(|row| sampler.keep().map(|keep| keep.then(|| row)).transpose())
+                    .collect::<Vec<_>>();
+
+                return Ok(Rows::Virtual(stream::iter(rows)));
+            }
+
             let table_name = get_name(name)?;
             #[cfg(feature = "index")]
             let rows = {
                 #[derive(Iterator)]
-                enum Rows<I1, I2> {
+                enum Rows<I1, I2, I3> {
                     FullScan(I1),
                     Indexed(I2),
+                    Union(I3),
                 }
 
                 match get_index(table_factor) {
                     Some(IndexItem {
                         name: index_name,
                         asc,
-                        cmp_expr,
+                        cmp_expr: Some(IndexCmp::In(exprs)),
+                    }) => {
+                        let mut probes = Vec::with_capacity(exprs.len());
+                        for expr in exprs {
+                            let evaluated = evaluate(storage, None, None, None, expr).await?;
+                            let cmp_value = Some((&IndexOperator::Eq, evaluated.try_into()?));
+                            let rows = storage
+                                .scan_indexed_data(table_name, index_name, *asc, cmp_value)
+                                .await?;
+
+                            probes.push(rows);
+                        }
+
+                        // a row found by more than one value is returned once
+                        let mut found = HashSet::new();
+                        let rows = probes.into_iter().flatten().filter(move |row| match row {
+                            Ok((key, _)) => found.insert(key.clone()),
+                            Err(_) => true,
+                        });
+
+                        Rows::Union(rows)
+                    }
+                    Some(IndexItem {
+                        name: index_name,
+                        asc,
+                        cmp_expr: Some(IndexCmp::Range { start, end }),
+                    }) => {
+                        let start = evaluate_bound(storage, start).await?;
+                        let end = evaluate_bound(storage, end).await?;
+
+                        storage
+                            .scan_indexed_range(table_name, index_name, *asc, start, end)
+                            .await
+                            .map(Rows::Indexed)?
+                    }
+                    Some(IndexItem {
+                        name: index_name,
+                        asc,
+                        cmp_expr: Some(IndexCmp::Prefix(exprs)),
                     }) => {
-                        let cmp_value = match cmp_expr {
-                            Some((op, expr)) => {
-                                let evaluated = evaluate(storage, None, None, expr).await?;
+                        let values = stream::iter(exprs)
+                            .then(|expr| async move {
+                                Value::try_from(evaluate(storage, None, None, None, expr).await?)
+                            })
+                            .try_collect::<Vec<_>>()
+                            .await?;
 
-                                Some((op, evaluated.try_into()?))
-                            }
-                            None => None,
-                        };
+                        storage
+                            .scan_indexed_prefix(table_name, index_name, *asc, values)
+                            .await
+                            .map(Rows::Indexed)?
+                    }
+                    Some(IndexItem {
+                        name: index_name,
+                        asc,
+                        cmp_expr: Some(IndexCmp::Op(op, expr)),
+                    }) => {
+                        let evaluated = evaluate(storage, None, None, None, expr).await?;
+                        let cmp_value = Some((op, evaluated.try_into()?));
 
                         storage
                             .scan_indexed_data(table_name, index_name, *asc, cmp_value)
                             .await
                             .map(Rows::Indexed)?
                     }
+                    Some(IndexItem {
+                        name: index_name,
+                        asc,
+                        cmp_expr: None,
+                    }) => storage
+                        .scan_indexed_data(table_name, index_name, *asc, None)
+                        .await
+                        .map(Rows::Indexed)?,
                     None => storage.scan_data(table_name).await.map(Rows::FullScan)?,
                 }
             };
@@ -115,11 +222,200 @@ pub async fn fetch_relation_rows<'a>(
             #[cfg(not(feature = "index"))]
             let rows = storage.scan_data(table_name).await?;
 
-            let rows = rows.map_ok(|(_, row)| row);
+            let rows = rows.map_ok(|(_, row)| row).filter_map(move |row| {
+                row.and_then(|row| sampler.keep().map(|keep| keep.then(|| row)))
+                    .transpose()
+            });
             let rows = stream::iter(rows);
 
             Ok(Rows::Table(rows))
         }
+        TableFactor::Series {
+            start, end, step, ..
+        } => {
+            let eval = |expr| async move {
+                let context = filter_context.as_ref().map(Rc::clone);
+
+                Value::try_from(evaluate(storage, context, None, None, expr).await?)
+            };
+
+            let start = eval(start).await?;
+            let end = eval(end).await?;
+            let step = match step {
+                Some(step) => eval(step).await?,
+                None => Value::I64(1),
+            };
+
+            let rows = fetch_series(start, end, step)?;
+
+            Ok(Rows::Series(stream::iter(rows)))
+        }
+    }
+}
+
+/// Decides which rows of a table scanned with `TABLESAMPLE BERNOULLI` are kept, each one on its
+/// own.
+enum Sampler {
+    All,
+    Bernoulli {
+        percentage: f64,
+        /// Generator of `REPEATABLE (seed)`, the one of the session without a seed
+        rng: Option<Rng>,
+    },
+}
+
+impl Sampler {
+    async fn new(storage: &dyn GStore, sample: Option<&TableSample>) -> Result<Self> {
+        let TableSample { percentage, seed } = match sample {
+            Some(sample) => sample,
+            None => return Ok(Self::All),
+        };
+
+        let value = Value::try_from(evaluate(storage, None, None, None, percentage).await?)?;
+        let percentage = match f64::try_from(&value) {
+            Ok(percentage) if (0.0..=100.0).contains(&percentage) => percentage,
+            _ => return Err(FetchError::InvalidSamplePercentage(value).into()),
+        };
+
+        let rng = match seed {
+            Some(seed) => {
+                let value = Value::try_from(evaluate(storage, None, None, None, seed).await?)?;
+
+                match i64::try_from(&value) {
+                    Ok(seed) => Some(Rng::new(seed as u64)),
+                    Err(_) => return Err(FetchError::InvalidSampleSeed(value).into()),
+                }
+            }
+            None => None,
+        };
+
+        Ok(Self::Bernoulli { percentage, rng })
+    }
+
+    fn keep(&mut self) -> Result<bool> {
+        match self {
+            Self::All => Ok(true),
+            Self::Bernoulli { percentage, rng } => {
+                let draw = match rng {
+                    Some(rng) => rng.next_f64(),
+                    None => session::current(|session| session.with_rng(Rng::next_f64))?,
+                };
+
+                Ok(draw * 100.0 < *percentage)
+            }
+        }
+    }
+}
+
+#[cfg(feature = "index")]
+async fn evaluate_bound<'a>(
+    storage: &'a dyn GStore,
+    bound: &'a Bound<Expr>,
+) -> Result<Bound<Value>> {
+    let eval =
+        |expr| async move { Value::try_from(evaluate(storage, None, None, None, expr).await?) };
+
+    match bound {
+        Bound::Included(expr) => eval(expr).await.map(Bound::Included),
+        Bound::Excluded(expr) => eval(expr).await.map(Bound::Excluded),
+        Bound::Unbounded => Ok(Bound::Unbounded),
+    }
+}
+
+/// Generates the values from `start` to `end` inclusive, advancing by `step`.
+///
+/// A step heading away from `end` yields no rows, and so do NULL arguments.
+fn fetch_series(
+    start: Value,
+    end: Value,
+    step: Value,
+) -> Result<impl Iterator<Item = Result<Row>>> {
+    let ascending = match (&start, &end, &step) {
+        (Value::Null, _, _) | (_, Value::Null, _) | (_, _, Value::Null) => None,
+        _ => match start.add(&step)?.partial_cmp(&start) {
+            Some(Ordering::Greater) => Some(true),
+            Some(Ordering::Less) => Some(false),
+            _ => return Err(FetchError::SeriesStepIsZero(step).into()),
+        },
+    };
+
+    if ascending.is_some() && start.partial_cmp(&end).is_none() {
+        return Err(FetchError::SeriesBoundaryTypeMismatch { start, end }.into());
+    }
+
+    let mut current = ascending.map(|_| start);
+    let rows = from_fn(move || {
+        let value = current.take()?;
+        let in_range = matches!(
+            (ascending, value.partial_cmp(&end)),
+            (Some(true), Some(Ordering::Less | Ordering::Equal))
+                | (Some(false), Some(Ordering::Greater | Ordering::Equal))
+        );
+
+        if !in_range {
+            return None;
+        }
+
+        // Overflow past the end of the integer range simply ends the series.
+        current = value.add(&step).ok();
+
+        Some(Ok(Row(vec![value])))
+    });
+
+    Ok(rows)
+}
+
+#[cfg(feature = "metadata")]
+async fn fetch_information_schema(
+    storage: &dyn GStore,
+    table: InformationSchema,
+) -> Result<Vec<Row>> {
+    let table_names = storage.schema_names().await?;
+
+    match table {
+        InformationSchema::Tables => Ok(table_names
+            .into_iter()
+            .map(|table_name| Row(vec![Value::Str(table_name)]))
+            .collect()),
+        InformationSchema::Columns => {
+            let rows = stream::iter(table_names)
+                .then(|table_name| async move {
+                    let column_defs = storage
+                        .fetch_schema(&table_name)
+                        .await?
+                        .map(|schema| schema.column_defs)
+                        .unwrap_or_default();
+
+                    let rows = column_defs
+                        .into_iter()
+                        .enumerate()
+                        .map(|(i, column_def)| {
+                            let is_nullable = if column_def.is_nullable() {
+                                "YES"
+                            } else {
+                                "NO"
+                            };
+
+                            Row(vec![
+                                Value::Str(table_name.clone()),
+                                Value::Str(column_def.name.clone()),
+                                Value::I64(i as i64 + 1),
+                                Value::Str(column_def.data_type.to_string()),
+                                Value::Str(is_nullable.to_owned()),
+                            ])
+                        })
+                        .collect::<Vec<_>>();
+
+                    Ok::<_, Error>(rows)
+                })
+                .try_collect::<Vec<_>>()
+                .await?
+                .into_iter()
+                .flatten()
+                .collect();
+
+            Ok(rows)
+        }
     }
 }
 
@@ -128,9 +424,9 @@ pub async fn fetch_columns(storage: &dyn GStore, table_name: &str) -> Result<Vec
         .fetch_schema(table_name)
         .await?
         .ok_or_else(|| FetchError::TableNotFound(table_name.to_string()))?
-        .column_defs
+        .column_names()
         .into_iter()
-        .map(|ColumnDef { name, .. }| name)
+        .map(ToOwned::to_owned)
         .collect::<Vec<String>>())
 }
 
@@ -141,17 +437,50 @@ pub async fn fetch_relation_columns(
 ) -> Result<Vec<String>> {
     match table_factor {
         TableFactor::Table { name, .. } => {
+            #[cfg(feature = "metadata")]
+            if let Some(table) = InformationSchema::from_name(name) {
+                return Ok(table.columns());
+            }
+
             let table_name = get_name(name)?;
 
             fetch_columns(storage, table_name).await
         }
         TableFactor::Derived {
-            subquery:
-                Query {
-                    body: SetExpr::Select(statement),
-                    ..
-                },
-            alias: _,
+            subquery,
+            alias: TableAlias { columns, .. },
+            ..
+        } => {
+            let labels = fetch_query_labels(storage, subquery).await?;
+
+            match columns.len() {
+                0 => Ok(labels),
+                found if found == labels.len() => Ok(columns.to_owned()),
+                found => Err(FetchError::ColumnAliasCountMismatch {
+                    expected: labels.len(),
+                    found,
+                }
+                .into()),
+            }
+        }
+        TableFactor::Series {
+            alias: TableAlias { name, columns },
+            ..
+        } => match columns.first() {
+            Some(column) => Ok(vec![column.to_owned()]),
+            None => Ok(vec![name.to_owned()]),
+        },
+    }
+}
+
+/// Labels of the rows of the derived table `query`, without running it.
+#[async_recursion(?Send)]
+pub async fn fetch_query_labels(storage: &dyn GStore, query: &Query) -> Result<Vec<String>> {
+    match query {
+        Query { with, .. } if !with.is_empty() => fetch_cte_labels(storage, query).await,
+        Query {
+            body: SetExpr::Select(statement),
+            ..
         } => {
             let Select {
                 from: TableWithJoins {
@@ -163,18 +492,25 @@ pub async fn fetch_relation_columns(
 
             let columns = fetch_relation_columns(storage, relation).await?;
             let join_columns = fetch_join_columns(joins, storage).await?;
-            let labels = get_labels(
+
+            get_labels(
                 projection,
                 get_alias(relation)?,
                 &columns,
                 Some(&join_columns),
-            )?;
+            )
+        }
+        Query {
+            body: SetExpr::Values(Values(values_list)),
+            ..
+        } => {
+            let first_len = values_list.first().map(Vec::len).unwrap_or(0);
 
-            Ok(labels)
+            Ok((1..=first_len).map(|i| format!("column{}", i)).collect())
         }
-        &TableFactor::Derived { .. } => Err(Error::Table(TableError::Unreachable)),
     }
 }
+
 pub async fn fetch_join_columns<'a>(
     joins: &'a [Join],
     storage: &dyn GStore,
@@ -2,10 +2,12 @@ use {
     super::{
         context::{BlendContext, FilterContext},
         evaluate::evaluate,
+        semi_join::SemiJoin,
     },
     crate::{
-        ast::{Aggregate, Expr},
+        ast::{Aggregate, BinaryOperator, Expr},
         data::Value,
+        plan::Context,
         result::Result,
         store::GStore,
     },
@@ -15,11 +17,17 @@ use {
 
 pub struct Filter<'a> {
     storage: &'a dyn GStore,
-    where_clause: Option<&'a Expr>,
+    conditions: Vec<Condition<'a>>,
     context: Option<Rc<FilterContext<'a>>>,
     aggregated: Option<Rc<HashMap<&'a Aggregate, Value>>>,
 }
 
+/// Part of a where clause, all of which have to hold for a row to pass.
+enum Condition<'a> {
+    Expr(&'a Expr),
+    SemiJoin(SemiJoin<'a>),
+}
+
 impl<'a> Filter<'a> {
     pub fn new(
         storage: &'a dyn GStore,
@@ -29,24 +37,91 @@ impl<'a> Filter<'a> {
     ) -> Self {
         Self {
             storage,
-            where_clause,
+            conditions: where_clause.map(Condition::Expr).into_iter().collect(),
             context,
             aggregated,
         }
     }
 
+    /// Checks the `IN` and `EXISTS` subqueries among the `AND`ed conditions of the where clause
+    /// as semi joins where it can, which run each subquery only once.
+    ///
+    /// `outer_context` has the columns of the rows the filter checks.
+    pub async fn with_semi_joins(
+        self,
+        outer_context: Option<Rc<Context<'_>>>,
+    ) -> Result<Filter<'a>> {
+        let where_clause = match self.conditions.as_slice() {
+            [Condition::Expr(expr)] => *expr,
+            _ => return Ok(self),
+        };
+
+        let mut conditions = Vec::new();
+        for expr in conjuncts(where_clause) {
+            let outer_context = outer_context.as_ref().map(Rc::clone);
+            let condition = match SemiJoin::new(self.storage, outer_context, expr).await? {
+                Some(semi_join) => Condition::SemiJoin(semi_join),
+                None => Condition::Expr(expr),
+            };
+
+            conditions.push(condition);
+        }
+
+        if !conditions
+            .iter()
+            .any(|condition| matches!(condition, Condition::SemiJoin(_)))
+        {
+            return Ok(self);
+        }
+
+        Ok(Self { conditions, ..self })
+    }
+
     pub async fn check(&self, blend_context: Rc<BlendContext<'a>>) -> Result<bool> {
-        match self.where_clause {
-            Some(expr) => {
-                let context = self.context.as_ref().map(Rc::clone);
-                let context = FilterContext::concat(context, Some(blend_context));
-                let context = Some(context).map(Rc::new);
-                let aggregated = self.aggregated.as_ref().map(Rc::clone);
-
-                check_expr(self.storage, context, aggregated, expr).await
+        if self.conditions.is_empty() {
+            return Ok(true);
+        }
+
+        let context = self.context.as_ref().map(Rc::clone);
+        let context = FilterContext::concat(context, Some(blend_context));
+        let context = Some(context).map(Rc::new);
+
+        for condition in self.conditions.iter() {
+            let context = context.as_ref().map(Rc::clone);
+            let aggregated = self.aggregated.as_ref().map(Rc::clone);
+
+            let pass = match condition {
+                Condition::Expr(expr) => {
+                    check_expr(self.storage, context, aggregated, *expr).await?
+                }
+                Condition::SemiJoin(semi_join) => {
+                    semi_join.check(self.storage, context, aggregated).await?
+                }
+            };
+
+            if !pass {
+                return Ok(false);
             }
-            None => Ok(true),
         }
+
+        Ok(true)
+    }
+}
+
+/// Conditions which all hold when `expr` does, split at each `AND`.
+pub fn conjuncts(expr: &Expr) -> Vec<&Expr> {
+    match expr {
+        Expr::BinaryOp {
+            left,
+            op: BinaryOperator::And,
+            right,
+        } => {
+            let mut exprs = conjuncts(left);
+            exprs.extend(conjuncts(right));
+
+            exprs
+        }
+        _ => vec![expr],
     }
 }
 
@@ -56,7 +131,7 @@ pub async fn check_expr<'a>(
     aggregated: Option<Rc<HashMap<&'a Aggregate, Value>>>,
     expr: &'a Expr,
 ) -> Result<bool> {
-    evaluate(storage, context, aggregated, expr)
+    evaluate(storage, context, aggregated, None, expr)
         .await
         .map(|evaluated| evaluated.try_into())?
 }
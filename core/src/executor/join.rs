@@ -2,14 +2,15 @@ use {
     super::fetch::fetch_relation_rows,
     crate::{
         ast::{
-            Expr, Join as AstJoin, JoinConstraint, JoinExecutor as AstJoinExecutor,
+            DataType, Expr, Join as AstJoin, JoinConstraint, JoinExecutor as AstJoinExecutor,
             JoinOperator as AstJoinOperator, TableFactor,
         },
-        data::{get_alias, Key, Row},
+        data::{get_alias, Key, Row, Value},
         executor::{
             context::{BlendContext, FilterContext},
-            evaluate::evaluate,
+            evaluate::{evaluate, Evaluated},
             filter::check_expr,
+            metrics::Metrics,
         },
         result::{Error, Result},
         store::GStore,
@@ -18,16 +19,25 @@ use {
         future,
         stream::{self, empty, once, Stream, StreamExt, TryStream, TryStreamExt},
     },
-    itertools::Itertools,
+    rust_decimal::prelude::ToPrimitive,
+    serde::Serialize,
     std::{borrow::Cow, collections::HashMap, pin::Pin, rc::Rc},
+    thiserror::Error as ThisError,
     utils::OrStream,
 };
 
+#[derive(ThisError, Serialize, Debug, PartialEq)]
+pub enum JoinError {
+    #[error("join keys of types {left} and {right} can not be compared")]
+    IncompatibleKeyTypes { left: String, right: String },
+}
+
 pub struct Join<'a> {
     storage: &'a dyn GStore,
     join_clauses: &'a [AstJoin],
     join_columns: Vec<Rc<[String]>>,
     filter_context: Option<Rc<FilterContext<'a>>>,
+    metrics: Metrics,
 }
 
 type JoinItem<'a> = Rc<BlendContext<'a>>;
@@ -40,12 +50,14 @@ impl<'a> Join<'a> {
         join_clauses: &'a [AstJoin],
         join_columns: Vec<Rc<[String]>>,
         filter_context: Option<Rc<FilterContext<'a>>>,
+        metrics: Metrics,
     ) -> Self {
         Self {
             storage,
             join_clauses,
             join_columns,
             filter_context,
+            metrics,
         }
     }
 
@@ -62,6 +74,7 @@ impl<'a> Join<'a> {
             .map(Ok)
             .try_fold(init_rows, |rows, (join_clause, join_columns)| {
                 let filter_context = self.filter_context.as_ref().map(Rc::clone);
+                let metrics = self.metrics.clone();
 
                 async move {
                     join(
@@ -70,6 +83,7 @@ impl<'a> Join<'a> {
                         join_clause,
                         join_columns,
                         rows,
+                        metrics,
                     )
                     .await
                 }
@@ -84,6 +98,7 @@ async fn join<'a>(
     ast_join: &'a AstJoin,
     columns: Rc<[String]>,
     left_rows: impl TryStream<Ok = JoinItem<'a>, Error = Error, Item = Result<JoinItem<'a>>> + 'a,
+    metrics: Metrics,
 ) -> Result<Joined<'a>> {
     let AstJoin {
         relation,
@@ -116,6 +131,7 @@ async fn join<'a>(
     let rows = left_rows.and_then(move |blend_context| {
         let filter_context = filter_context.as_ref().map(Rc::clone);
         let columns = Rc::clone(&columns);
+        let metrics = metrics.clone();
         let init_context = Rc::new(BlendContext::new(
             table_alias,
             Rc::clone(&columns),
@@ -142,6 +158,8 @@ async fn join<'a>(
                     let rows = rows
                         .and_then(|row| future::ok(Cow::Owned(row)))
                         .try_filter_map(move |row| {
+                            metrics.add_join_probe();
+
                             check_where_clause(
                                 storage,
                                 table_alias,
@@ -156,23 +174,33 @@ async fn join<'a>(
                 }
                 JoinExecutor::Hash {
                     rows_map,
+                    key_types,
                     value_expr,
                 } => {
-                    let rows = evaluate(
+                    let evaluated = evaluate(
                         storage,
                         filter_context.as_ref().map(Rc::clone),
                         None,
+                        None,
                         value_expr,
                     )
-                    .await
-                    .map(Key::try_from)?
-                    .map(|hash_key| rows_map.get(&hash_key))?;
+                    .await?;
+
+                    // a literal has no type of its own to check
+                    let typed = matches!(evaluated, Evaluated::Value(_));
+                    let value = Value::try_from(evaluated)?;
+                    if typed {
+                        check_key_type(&value, key_types)?;
+                    }
+                    let rows = rows_map.get(&hash_key(&value)?);
 
                     match rows {
                         None => Rows::Empty(empty()),
                         Some(rows) => {
                             let rows = stream::iter(rows.iter().map(Cow::Borrowed).map(Ok));
                             let rows = rows.try_filter_map(move |row| {
+                                metrics.add_join_probe();
+
                                 check_where_clause(
                                     storage,
                                     table_alias,
@@ -217,6 +245,8 @@ enum JoinExecutor<'a> {
     NestedLoop,
     Hash {
         rows_map: HashMap<Key, Vec<Row>>,
+        /// Types of the keys of the joined rows
+        key_types: Vec<DataType>,
         value_expr: &'a Expr,
     },
 }
@@ -238,8 +268,8 @@ impl<'a> JoinExecutor<'a> {
             } => (key_expr, value_expr, where_clause),
         };
 
-        let rows_map = fetch_relation_rows(storage, relation, &filter_context).await?;
-        let rows_map = rows_map
+        let rows = fetch_relation_rows(storage, relation, &filter_context).await?;
+        let rows = rows
             .try_filter_map(|row| {
                 let columns = Rc::clone(&columns);
                 let filter_context = filter_context.as_ref().map(Rc::clone);
@@ -252,38 +282,103 @@ impl<'a> JoinExecutor<'a> {
                         filter_context,
                     ));
 
-                    let hash_key: Key = evaluate(
+                    let value: Value = evaluate(
                         storage,
                         Some(&filter_context).map(Rc::clone),
                         None,
+                        None,
                         key_expr,
                     )
                     .await?
                     .try_into()?;
 
-                    if matches!(hash_key, Key::None) {
-                        return Ok(None);
-                    }
+                    let data_type = match value.get_type() {
+                        Some(data_type) => data_type,
+                        None => return Ok(None),
+                    };
+                    let hash_key = hash_key(&value)?;
 
                     match where_clause {
                         Some(expr) => check_expr(storage, Some(filter_context), None, expr)
                             .await
-                            .map(|pass| pass.then(|| (hash_key, row))),
-                        None => Ok(Some((hash_key, row))),
+                            .map(|pass| pass.then(|| (hash_key, data_type, row))),
+                        None => Ok(Some((hash_key, data_type, row))),
                     }
                 }
             })
             .try_collect::<Vec<_>>()
-            .await?
-            .into_iter()
-            .into_group_map();
+            .await?;
+
+        let mut rows_map: HashMap<Key, Vec<Row>> = HashMap::new();
+        let mut key_types = Vec::new();
+        for (hash_key, data_type, row) in rows {
+            if !key_types.contains(&data_type) {
+                key_types.push(data_type);
+            }
+
+            rows_map.entry(hash_key).or_default().push(row);
+        }
+
         Ok(Self::Hash {
             rows_map,
+            key_types,
             value_expr,
         })
     }
 }
 
+/// Key of a joined value, the same for every two values `=` holds for, so an INTEGER key matches
+/// an INT8 key of the same number and a DATE key the TIMESTAMP at its midnight.
+pub fn hash_key(value: &Value) -> Result<Key> {
+    match value {
+        Value::I8(v) => Ok(Key::I128(i128::from(*v))),
+        Value::I16(v) => Ok(Key::I128(i128::from(*v))),
+        Value::I32(v) => Ok(Key::I128(i128::from(*v))),
+        Value::I64(v) => Ok(Key::I128(i128::from(*v))),
+        Value::I128(v) => Ok(Key::I128(*v)),
+        Value::Decimal(v) if v.fract().is_zero() => match v.to_i128() {
+            Some(v) => Ok(Key::I128(v)),
+            None => Key::try_from(value),
+        },
+        Value::Date(v) => Ok(Key::Timestamp(v.and_hms(0, 0, 0))),
+        value => Key::try_from(value),
+    }
+}
+
+/// Errors unless `value` can be compared with the keys of the joined rows, which would
+/// otherwise silently match none of them. NULL and joins without keys are not checked.
+fn check_key_type(value: &Value, key_types: &[DataType]) -> Result<()> {
+    use DataType::*;
+
+    let is_numeric = |data_type: &DataType| {
+        matches!(
+            data_type,
+            Int8 | Int16 | Int32 | Int | Int128 | Float | Decimal
+        )
+    };
+    let comparable = |l: &DataType, r: &DataType| {
+        l == r
+            || (is_numeric(l) && is_numeric(r))
+            || matches!((l, r), (Date, Timestamp) | (Timestamp, Date))
+    };
+
+    let data_type = match value.get_type() {
+        Some(data_type) => data_type,
+        None => return Ok(()),
+    };
+
+    match key_types.first() {
+        Some(key_type) if !key_types.iter().any(|r| comparable(&data_type, r)) => {
+            Err(JoinError::IncompatibleKeyTypes {
+                left: data_type.to_string(),
+                right: key_type.to_string(),
+            }
+            .into())
+        }
+        _ => Ok(()),
+    }
+}
+
 async fn check_where_clause<'a, 'b>(
     storage: &'a dyn GStore,
     table_alias: &'a str,
@@ -0,0 +1,316 @@
+use {
+    super::{
+        cancel::Cancellation,
+        context::FilterContext,
+        evaluate::evaluate,
+        execute::{check_writable, ExecuteError},
+        fetch::fetch,
+        filter::check_expr,
+        metrics::Metrics,
+        select::select_with_labels,
+        sequence::fill_auto_increment,
+        update::Update,
+        validate::{validate_unique, ColumnValidation},
+    },
+    crate::{
+        ast::{
+            Assignment, ColumnDef, Expr, Merge, MergeClause, Query, Select, SelectItem, SetExpr,
+            TableFactor, TableWithJoins,
+        },
+        data::{get_alias, get_name, schema::ColumnDefExt, Key, Row, RowError, Schema, Value},
+        result::{MutResult, Result, TrySelf},
+        store::{GStore, GStoreMut},
+    },
+    futures::stream::{self, StreamExt, TryStreamExt},
+    serde::Serialize,
+    std::{fmt::Debug, rc::Rc},
+    thiserror::Error as ThisError,
+};
+
+#[derive(ThisError, Serialize, Debug, PartialEq)]
+pub enum MergeError {
+    #[error("a row of {0} matches more than one source row of MERGE")]
+    TargetRowMatchedMoreThanOnce(String),
+}
+
+/// Rows `MERGE` changes in its target table, collected before any of them is written.
+#[derive(Default)]
+struct Changes {
+    inserted: Vec<Row>,
+    updated: Vec<(Key, Row)>,
+    deleted: Vec<Key>,
+}
+
+/// Runs `MERGE` and returns the number of rows it inserted, updated and deleted.
+///
+/// Each source row is joined with the target rows by the `ON` condition, and the first
+/// `WHEN MATCHED` clause which holds applies to each matched target row, or the first
+/// `WHEN NOT MATCHED` clause to the source row which matches none.
+pub async fn merge<T: GStore + GStoreMut>(
+    storage: T,
+    merge: &Merge,
+    cancellation: &Cancellation,
+    metrics: &Metrics,
+) -> MutResult<T, usize> {
+    let (storage, table_name) = get_name(&merge.table_name).try_self(storage)?;
+    let changes = collect_changes(&storage, table_name, merge, cancellation, metrics).await;
+    let (storage, (column_defs, changes)) = changes.try_self(storage)?;
+    let Changes {
+        inserted,
+        updated,
+        deleted,
+    } = changes;
+    let num_rows = inserted.len() + updated.len() + deleted.len();
+
+    let (storage, inserted) =
+        fill_auto_increment(storage, table_name, &column_defs, inserted).await?;
+
+    let column_validation = ColumnValidation::All(Rc::clone(&column_defs));
+    let validated = validate_unique(&storage, table_name, column_validation, inserted.iter()).await;
+    let (storage, ()) = validated.try_self(storage)?;
+
+    let column_validation = ColumnValidation::SpecifiedColumns(
+        Rc::clone(&column_defs),
+        updated_columns(&merge.clauses),
+    );
+    let rows = updated.iter().map(|(_, row)| row);
+    let validated = validate_unique(&storage, table_name, column_validation, rows).await;
+    let (storage, ()) = validated.try_self(storage)?;
+
+    let (storage, ()) = storage.delete_data(table_name, deleted).await?;
+    let (storage, ()) = storage.update_data(table_name, updated).await?;
+
+    storage
+        .insert_data(table_name, inserted)
+        .await
+        .map(|(storage, ())| (storage, num_rows))
+}
+
+async fn collect_changes(
+    storage: &dyn GStore,
+    table_name: &str,
+    merge: &Merge,
+    cancellation: &Cancellation,
+    metrics: &Metrics,
+) -> Result<(Rc<[ColumnDef]>, Changes)> {
+    let Merge {
+        alias,
+        source,
+        on,
+        clauses,
+        ..
+    } = merge;
+
+    let schema = storage
+        .fetch_schema(table_name)
+        .await?
+        .ok_or_else(|| ExecuteError::TableNotFound(table_name.to_owned()))?;
+    check_writable(&schema)?;
+    let Schema { column_defs, .. } = schema;
+    let column_defs: Rc<[ColumnDef]> = Rc::from(column_defs);
+    let columns: Rc<[String]> = column_defs
+        .iter()
+        .map(|ColumnDef { name, .. }| name.to_owned())
+        .collect();
+    let table_alias = alias.as_deref().unwrap_or(table_name);
+
+    let targets = fetch(
+        storage,
+        table_name,
+        Rc::clone(&columns),
+        None,
+        cancellation.clone(),
+    )
+    .await?
+    .map_ok(|(_, key, row)| (key, row))
+    .try_collect::<Vec<_>>()
+    .await?;
+
+    let query = source_query(source);
+    let (labels, sources) = select_with_labels(
+        storage,
+        &query,
+        None,
+        true,
+        cancellation.clone(),
+        metrics.clone(),
+    )
+    .await?;
+    let sources = sources.try_collect::<Vec<_>>().await?;
+    let labels: Rc<[String]> = Rc::from(labels);
+    let source_alias = get_alias(source)?;
+
+    let mut changes = Changes::default();
+    let mut matched = vec![false; targets.len()];
+    for source_row in sources.iter() {
+        cancellation.check()?;
+
+        let source_context =
+            FilterContext::new(source_alias, Rc::clone(&labels), Some(source_row), None);
+        let source_context = Rc::new(source_context);
+        let mut found = false;
+
+        for (index, (key, target_row)) in targets.iter().enumerate() {
+            let context = FilterContext::new(
+                table_alias,
+                Rc::clone(&columns),
+                Some(target_row),
+                Some(Rc::clone(&source_context)),
+            );
+            let context = Rc::new(context);
+
+            if !check_expr(storage, Some(Rc::clone(&context)), None, on).await? {
+                continue;
+            } else if matched[index] {
+                return Err(MergeError::TargetRowMatchedMoreThanOnce(table_name.to_owned()).into());
+            }
+
+            matched[index] = true;
+            found = true;
+
+            for clause in clauses.iter() {
+                match clause {
+                    MergeClause::MatchedUpdate {
+                        predicate,
+                        assignments,
+                    } if check_predicate(storage, &context, predicate).await? => {
+                        let update = Update::new(storage, table_alias, assignments, &column_defs)?;
+                        let row = update
+                            .apply_with_context(
+                                target_row.clone(),
+                                Some(Rc::clone(&source_context)),
+                            )
+                            .await?;
+
+                        changes.updated.push((key.clone(), row));
+                        break;
+                    }
+                    MergeClause::MatchedDelete { predicate }
+                        if check_predicate(storage, &context, predicate).await? =>
+                    {
+                        changes.deleted.push(key.clone());
+                        break;
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        if found {
+            continue;
+        }
+
+        for clause in clauses.iter() {
+            if let MergeClause::NotMatched {
+                predicate,
+                columns,
+                values,
+            } = clause
+            {
+                if !check_predicate(storage, &source_context, predicate).await? {
+                    continue;
+                }
+
+                let row =
+                    insert_row(storage, &column_defs, columns, values, &source_context).await?;
+                row.validate(&column_defs)?;
+
+                changes.inserted.push(row);
+                break;
+            }
+        }
+    }
+
+    Ok((column_defs, changes))
+}
+
+/// `SELECT * FROM source`, which gives the source rows of `MERGE`.
+fn source_query(source: &TableFactor) -> Query {
+    let select = Select {
+        projection: vec![SelectItem::Wildcard],
+        from: TableWithJoins {
+            relation: source.clone(),
+            joins: Vec::new(),
+        },
+        selection: None,
+        group_by: Vec::new(),
+        grouping_sets: None,
+        having: None,
+        order_by: Vec::new(),
+        distinct_on: Vec::new(),
+    };
+
+    Query {
+        with: Vec::new(),
+        body: SetExpr::Select(Box::new(select)),
+        limit: None,
+        offset: None,
+    }
+}
+
+async fn check_predicate<'a>(
+    storage: &'a dyn GStore,
+    context: &Rc<FilterContext<'a>>,
+    predicate: &'a Option<Expr>,
+) -> Result<bool> {
+    match predicate {
+        Some(predicate) => check_expr(storage, Some(Rc::clone(context)), None, predicate).await,
+        None => Ok(true),
+    }
+}
+
+/// Row of `WHEN NOT MATCHED THEN INSERT`, whose values are evaluated against the source row.
+async fn insert_row<'a>(
+    storage: &'a dyn GStore,
+    column_defs: &[ColumnDef],
+    columns: &[String],
+    values: &'a [Expr],
+    context: &Rc<FilterContext<'a>>,
+) -> Result<Row> {
+    if !columns.is_empty() && values.len() != columns.len() {
+        return Err(RowError::ColumnAndValuesNotMatched.into());
+    } else if values.len() > column_defs.len() {
+        return Err(RowError::TooManyValues.into());
+    }
+
+    let names = match columns.is_empty() {
+        true => column_defs
+            .iter()
+            .map(|ColumnDef { name, .. }| name)
+            .collect::<Vec<_>>(),
+        false => columns.iter().collect(),
+    };
+
+    let values = stream::iter(names.into_iter().zip(values.iter()))
+        .then(move |(name, expr)| async move {
+            let evaluated = evaluate(storage, Some(Rc::clone(context)), None, None, expr).await?;
+
+            match column_defs
+                .iter()
+                .find(|column_def| &column_def.name == name)
+            {
+                Some(column_def) => {
+                    let nullable = column_def.is_nullable() || column_def.is_auto_increment();
+
+                    evaluated.try_into_value(&column_def.data_type, nullable)
+                }
+                None => Value::try_from(evaluated),
+            }
+        })
+        .try_collect::<Vec<_>>()
+        .await?;
+
+    Row(values).arrange(column_defs, columns)
+}
+
+/// Columns the `WHEN MATCHED THEN UPDATE` clauses assign.
+fn updated_columns(clauses: &[MergeClause]) -> Vec<String> {
+    clauses
+        .iter()
+        .flat_map(|clause| match clause {
+            MergeClause::MatchedUpdate { assignments, .. } => assignments.as_slice(),
+            _ => &[],
+        })
+        .map(|Assignment { id, .. }| id.to_owned())
+        .collect()
+}
@@ -0,0 +1,215 @@
+use {
+    crate::result::Result,
+    chrono::{Duration, Utc},
+    futures::{
+        future::Either,
+        stream::{self, Stream, StreamExt},
+    },
+    std::{
+        future::Future,
+        sync::{
+            atomic::{AtomicI64, AtomicU64, Ordering},
+            Arc,
+        },
+        task::Poll,
+    },
+};
+
+/// Stage of the top level `SELECT`, in the order rows flow through them.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Stage {
+    Scan,
+    Join,
+    Filter,
+    Aggregate,
+    Sort,
+    Output,
+}
+
+#[derive(Debug, Default)]
+struct StageCounters {
+    rows: AtomicU64,
+    elapsed_micros: AtomicI64,
+}
+
+#[derive(Debug, Default)]
+struct Counters {
+    rows_scanned: AtomicU64,
+    join_probes: AtomicU64,
+    rows_filtered: AtomicU64,
+    rows_aggregated: AtomicU64,
+    rows_sorted: AtomicU64,
+    rows_output: AtomicU64,
+    elapsed_micros: AtomicI64,
+    /// Whether `stages` are counted, which times every row of every stage.
+    profiling: bool,
+    stages: [StageCounters; 6],
+}
+
+/// Execution statistics of the last statement, counted while its rows are streamed.
+///
+/// Clones share the same counters, so a clone can be kept to read them after execution.
+#[derive(Clone, Debug, Default)]
+pub struct Metrics(Arc<Counters>);
+
+impl Metrics {
+    /// Metrics which also count the rows and the time of each stage, see `Metrics::stage`.
+    pub fn profiled() -> Self {
+        Self(Arc::new(Counters {
+            profiling: true,
+            ..Counters::default()
+        }))
+    }
+
+    /// Rows read from the base relation of the top level `SELECT`.
+    pub fn rows_scanned(&self) -> u64 {
+        self.0.rows_scanned.load(Ordering::Relaxed)
+    }
+
+    /// Candidate rows compared against the join constraints.
+    pub fn join_probes(&self) -> u64 {
+        self.0.join_probes.load(Ordering::Relaxed)
+    }
+
+    /// Rows which passed the `WHERE` clause.
+    pub fn rows_filtered(&self) -> u64 {
+        self.0.rows_filtered.load(Ordering::Relaxed)
+    }
+
+    /// Rows or groups produced by the aggregation stage.
+    pub fn rows_aggregated(&self) -> u64 {
+        self.0.rows_aggregated.load(Ordering::Relaxed)
+    }
+
+    /// Rows buffered by the sort stage to order them, zero when the rows are read in the
+    /// order of an index instead.
+    pub fn rows_sorted(&self) -> u64 {
+        self.0.rows_sorted.load(Ordering::Relaxed)
+    }
+
+    /// Rows returned to the caller, after `LIMIT` and `OFFSET`.
+    pub fn rows_output(&self) -> u64 {
+        self.0.rows_output.load(Ordering::Relaxed)
+    }
+
+    /// Wall time spent executing the statement.
+    pub fn elapsed(&self) -> Duration {
+        Duration::microseconds(self.0.elapsed_micros.load(Ordering::Relaxed))
+    }
+
+    /// Rows a stage produced and the wall time spent producing them, which includes the time of
+    /// the stages it reads from. Both are zero unless the metrics are `Metrics::profiled`.
+    pub fn stage(&self, stage: Stage) -> (u64, Duration) {
+        let StageCounters {
+            rows,
+            elapsed_micros,
+        } = &self.0.stages[stage as usize];
+
+        (
+            rows.load(Ordering::Relaxed),
+            Duration::microseconds(elapsed_micros.load(Ordering::Relaxed)),
+        )
+    }
+
+    pub(crate) fn reset(&self) {
+        let Counters {
+            rows_scanned,
+            join_probes,
+            rows_filtered,
+            rows_aggregated,
+            rows_sorted,
+            rows_output,
+            elapsed_micros,
+            profiling: _,
+            stages,
+        } = self.0.as_ref();
+
+        for counter in [
+            rows_scanned,
+            join_probes,
+            rows_filtered,
+            rows_aggregated,
+            rows_sorted,
+            rows_output,
+        ] {
+            counter.store(0, Ordering::Relaxed);
+        }
+        elapsed_micros.store(0, Ordering::Relaxed);
+
+        for StageCounters {
+            rows,
+            elapsed_micros,
+        } in stages
+        {
+            rows.store(0, Ordering::Relaxed);
+            elapsed_micros.store(0, Ordering::Relaxed);
+        }
+    }
+
+    /// Runs `f` and records its wall time.
+    pub(crate) async fn measure<F: Future>(&self, f: F) -> F::Output {
+        let started = Utc::now();
+        let output = f.await;
+        let elapsed = (Utc::now() - started)
+            .num_microseconds()
+            .unwrap_or(i64::MAX);
+        self.0.elapsed_micros.store(elapsed, Ordering::Relaxed);
+
+        output
+    }
+
+    pub(crate) fn add_scanned(&self) {
+        self.0.rows_scanned.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn add_join_probe(&self) {
+        self.0.join_probes.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn add_filtered(&self) {
+        self.0.rows_filtered.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn add_aggregated(&self) {
+        self.0.rows_aggregated.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn add_sorted(&self) {
+        self.0.rows_sorted.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn add_output(&self) {
+        self.0.rows_output.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Counts the rows of a stage and the time spent pulling them when profiling, or returns
+    /// them as they are.
+    pub(crate) fn profile<'a, T: 'a>(
+        &self,
+        stage: Stage,
+        rows: impl Stream<Item = Result<T>> + 'a,
+    ) -> impl Stream<Item = Result<T>> + 'a {
+        if !self.0.profiling {
+            return Either::Left(rows);
+        }
+
+        let metrics = self.clone();
+        let mut rows = Box::pin(rows);
+
+        Either::Right(stream::poll_fn(move |cx| {
+            let started = Utc::now();
+            let polled = rows.poll_next_unpin(cx);
+            let elapsed = (Utc::now() - started).num_microseconds().unwrap_or(0);
+
+            let counters = &metrics.0.stages[stage as usize];
+            counters
+                .elapsed_micros
+                .fetch_add(elapsed, Ordering::Relaxed);
+            if let Poll::Ready(Some(Ok(_))) = polled {
+                counters.rows.fetch_add(1, Ordering::Relaxed);
+            }
+
+            polled
+        }))
+    }
+}
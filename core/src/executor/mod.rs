@@ -1,30 +1,59 @@
 mod aggregate;
 mod alter;
+mod cancel;
 mod context;
+mod copy;
+mod deserialize;
 mod evaluate;
 mod execute;
+mod explain;
 mod fetch;
 mod filter;
 mod join;
 mod limit;
+mod merge;
+mod metrics;
+mod nulls;
+mod returning;
 mod select;
+mod semi_join;
+mod sequence;
+mod session;
 mod sort;
+#[cfg(feature = "tracing")]
+mod trace;
+mod udf;
 mod update;
 mod validate;
+mod window;
 
 pub use aggregate::AggregateError;
 pub use alter::AlterError;
-pub use evaluate::{evaluate_stateless, EvaluateError};
+pub use cancel::Cancellation;
+pub use copy::{
+    bulk_insert, export_csv, export_jsonl, import_csv, import_jsonl, CopyError, CsvOptions,
+};
+pub use deserialize::{deserialize_rows, DeserializeError};
+pub use evaluate::{evaluate_stateless, EvaluateError, FloatDivisionByZero};
 pub use execute::{ExecuteError, Payload};
 pub use fetch::FetchError;
-pub use select::SelectError;
+pub use join::JoinError;
+pub use merge::MergeError;
+pub use metrics::{Metrics, Stage};
+pub use select::{select_stream, SelectError};
+pub use sequence::SequenceError;
+pub(crate) use session::current as current_session;
+pub use session::{Session, SessionError};
+pub(crate) use udf::{get_function, is_aggregate};
+pub use udf::{EmptyGroup, UdfError, UserAggregate};
 pub use update::UpdateError;
 pub use validate::ValidateError;
+pub use window::WindowError;
 
 #[cfg(not(feature = "transaction"))]
-pub use execute::execute;
+pub use execute::{execute, execute_cancellable};
 #[cfg(feature = "transaction")]
-pub use execute::execute_atomic as execute;
+pub use execute::{execute_atomic as execute, execute_atomic_cancellable as execute_cancellable};
 
 #[cfg(feature = "metadata")]
-pub use execute::PayloadVariable;
+pub use {alter::fold_identifier_names, execute::PayloadVariable};
@@ -0,0 +1,95 @@
+//! NULL semantics of the stages which order, deduplicate and group rows: `ORDER BY`,
+//! `DISTINCT ON`, `GROUP BY` and the `OVER` clause of window functions.
+//!
+//! Unlike `=`, which never holds for NULL, all of them treat two NULLs as the same key.
+
+use {
+    crate::{
+        ast::OrderByExpr,
+        data::{Key, Value},
+        result::Result,
+    },
+    std::cmp::Ordering,
+};
+
+/// Direction of an ordering key and the side its NULLs are placed on.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Order {
+    pub asc: bool,
+    pub nulls_first: bool,
+}
+
+impl Order {
+    /// Without `NULLS FIRST` or `NULLS LAST`, NULL is ordered as the greatest value, so it comes
+    /// last in ascending order and first in descending order.
+    pub fn new(asc: Option<bool>, nulls_first: Option<bool>) -> Self {
+        let asc = asc.unwrap_or(true);
+
+        Self {
+            asc,
+            nulls_first: nulls_first.unwrap_or(!asc),
+        }
+    }
+}
+
+impl Default for Order {
+    fn default() -> Self {
+        Self::new(None, None)
+    }
+}
+
+impl From<&OrderByExpr> for Order {
+    fn from(order_by_expr: &OrderByExpr) -> Self {
+        Self::new(order_by_expr.asc, order_by_expr.nulls_first)
+    }
+}
+
+/// Compares two keys in `order`, values without an order between them are equal.
+///
+/// `NaN` is greater than every other float and equal to itself, so the order stays total.
+pub fn compare_value(value_a: &Value, value_b: &Value, order: Order) -> Ordering {
+    match (value_a.is_null(), value_b.is_null()) {
+        (true, true) => Ordering::Equal,
+        (true, false) if order.nulls_first => Ordering::Less,
+        (true, false) => Ordering::Greater,
+        (false, true) => compare_value(value_b, value_a, order).reverse(),
+        (false, false) => {
+            let ord = match (value_a.is_nan(), value_b.is_nan()) {
+                (true, true) => Ordering::Equal,
+                (true, false) => Ordering::Greater,
+                (false, true) => Ordering::Less,
+                (false, false) => value_a.partial_cmp(value_b).unwrap_or(Ordering::Equal),
+            };
+
+            if order.asc {
+                ord
+            } else {
+                ord.reverse()
+            }
+        }
+    }
+}
+
+/// Compares rows by their keys, each in the order it is paired with in `values_b`.
+pub fn compare(values_a: &[(Value, Order)], values_b: &[(Value, Order)]) -> Ordering {
+    values_a
+        .iter()
+        .zip(values_b.iter())
+        .map(|((value_a, _), (value_b, order))| compare_value(value_a, value_b, *order))
+        .find(|ord| *ord != Ordering::Equal)
+        .unwrap_or(Ordering::Equal)
+}
+
+/// Whether two keys are the same, which two NULLs and two `NaN`s are.
+pub fn is_same(value_a: &Value, value_b: &Value) -> bool {
+    match (value_a.is_null(), value_b.is_null()) {
+        (true, true) => true,
+        (false, false) => value_a == value_b || (value_a.is_nan() && value_b.is_nan()),
+        _ => false,
+    }
+}
+
+/// Key rows are grouped by, every NULL maps to the same `Key::None`.
+pub fn group_key(value: Value) -> Result<Key> {
+    Key::try_from(value)
+}
@@ -0,0 +1,36 @@
+use {
+    super::{
+        context::BlendContext,
+        execute::Payload,
+        select::{get_labels, Blend},
+    },
+    crate::{ast::SelectItem, data::Row, result::Result, store::GStore},
+    futures::stream::{self, StreamExt, TryStreamExt},
+    std::rc::Rc,
+};
+
+/// Projects `projection` of `RETURNING` over the rows a mutation affected, and returns them as
+/// the result of a `SELECT`.
+pub async fn returning<'a>(
+    storage: &'a dyn GStore,
+    table_name: &'a str,
+    columns: Rc<[String]>,
+    projection: &'a [SelectItem],
+    rows: impl Iterator<Item = &'a Row>,
+) -> Result<Payload> {
+    let labels = get_labels(projection, table_name, &columns, None)?;
+    let blend = Blend::new(storage, None, projection);
+
+    let rows = stream::iter(rows)
+        .then(|row| {
+            let context =
+                BlendContext::new(table_name, Rc::clone(&columns), Some(row.clone()), None);
+
+            blend.apply(None, None, Rc::new(context))
+        })
+        .map_ok(|Row(values)| values)
+        .try_collect::<Vec<_>>()
+        .await?;
+
+    Ok(Payload::Select { labels, rows })
+}
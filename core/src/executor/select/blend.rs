@@ -1,7 +1,7 @@
 use {
     super::SelectError,
     crate::{
-        ast::{Aggregate, SelectItem},
+        ast::{Aggregate, SelectItem, Window},
         data::{get_name, Row, Value},
         executor::{
             context::{BlendContext, FilterContext},
@@ -37,6 +37,7 @@ impl<'a> Blend<'a> {
     pub async fn apply(
         &self,
         aggregated: Option<Rc<HashMap<&'a Aggregate, Value>>>,
+        windowed: Option<Rc<HashMap<&'a Window, Value>>>,
         context: Rc<BlendContext<'a>>,
     ) -> Result<Row> {
         let filter_context = FilterContext::concat(
@@ -51,6 +52,7 @@ impl<'a> Blend<'a> {
                 let context = Rc::clone(&context);
                 let filter_context = filter_context.as_ref().map(Rc::clone);
                 let aggregated = aggregated.as_ref().map(Rc::clone);
+                let windowed = windowed.as_ref().map(Rc::clone);
 
                 async move {
                     match item {
@@ -67,7 +69,7 @@ impl<'a> Blend<'a> {
                             }
                         }
                         SelectItem::Expr { expr, .. } => {
-                            evaluate(self.storage, filter_context, aggregated, expr)
+                            evaluate(self.storage, filter_context, aggregated, windowed, expr)
                                 .await
                                 .map(|evaluated| evaluated.try_into())?
                                 .map(|v| vec![v])
@@ -0,0 +1,321 @@
+use {
+    super::{select_with_labels, SelectError},
+    crate::{
+        ast::{ColumnDef, Cte, DataType, Query, RecursiveTerm, TableAlias},
+        data::{Key, Row, Schema, SchemaFunction},
+        executor::{
+            cancel::Cancellation, context::FilterContext, fetch::fetch_query_labels,
+            metrics::Metrics,
+        },
+        result::Result,
+        store::{GStore, RowIter, Store},
+    },
+    async_trait::async_trait,
+    futures::stream::TryStreamExt,
+    std::{
+        collections::{HashMap, HashSet},
+        rc::Rc,
+    },
+};
+
+#[cfg(feature = "index")]
+use {
+    crate::{ast::IndexOperator, data::Value, store::Index},
+    std::ops::Bound,
+};
+
+#[cfg(feature = "metadata")]
+use crate::store::Metadata;
+
+/// Rounds a recursive term may run, which stops `UNION ALL` over a cyclic graph.
+const RECURSION_LIMIT: usize = 1000;
+
+/// Storage which serves the materialized rows of common table expressions as tables,
+/// and forwards everything else to the wrapped storage.
+struct CteStorage<'a> {
+    storage: &'a dyn GStore,
+    tables: HashMap<String, (Vec<String>, Vec<Row>)>,
+}
+
+#[async_trait(?Send)]
+impl Store for CteStorage<'_> {
+    async fn fetch_schema(&self, table_name: &str) -> Result<Option<Schema>> {
+        let (columns, rows) = match self.tables.get(table_name) {
+            Some(table) => table,
+            None => return self.storage.fetch_schema(table_name).await,
+        };
+
+        let column_defs = columns
+            .iter()
+            .enumerate()
+            .map(|(i, name)| ColumnDef {
+                name: name.to_owned(),
+                data_type: rows
+                    .iter()
+                    .find_map(|Row(values)| values.get(i)?.get_type())
+                    .unwrap_or(DataType::Text),
+                options: Vec::new(),
+            })
+            .collect();
+
+        Ok(Some(Schema {
+            table_name: table_name.to_owned(),
+            column_defs,
+            indexes: Vec::new(),
+            materialized_view: None,
+            view: None,
+        }))
+    }
+
+    async fn scan_data(&self, table_name: &str) -> Result<RowIter> {
+        let rows = match self.tables.get(table_name) {
+            Some((_, rows)) => rows.clone(),
+            None => return self.storage.scan_data(table_name).await,
+        };
+
+        let rows = rows
+            .into_iter()
+            .enumerate()
+            .map(|(i, row)| Ok((Key::I64(i as i64), row)));
+
+        Ok(Box::new(rows))
+    }
+
+    async fn fetch_sequence(&self, name: &str) -> Result<Option<i64>> {
+        self.storage.fetch_sequence(name).await
+    }
+
+    async fn fetch_functions(&self) -> Result<Vec<SchemaFunction>> {
+        self.storage.fetch_functions().await
+    }
+}
+
+#[cfg(feature = "index")]
+#[async_trait(?Send)]
+impl Index for CteStorage<'_> {
+    async fn scan_indexed_data(
+        &self,
+        table_name: &str,
+        index_name: &str,
+        asc: Option<bool>,
+        cmp_value: Option<(&IndexOperator, Value)>,
+    ) -> Result<RowIter> {
+        self.storage
+            .scan_indexed_data(table_name, index_name, asc, cmp_value)
+            .await
+    }
+
+    async fn scan_indexed_range(
+        &self,
+        table_name: &str,
+        index_name: &str,
+        asc: Option<bool>,
+        start: Bound<Value>,
+        end: Bound<Value>,
+    ) -> Result<RowIter> {
+        self.storage
+            .scan_indexed_range(table_name, index_name, asc, start, end)
+            .await
+    }
+
+    async fn scan_indexed_prefix(
+        &self,
+        table_name: &str,
+        index_name: &str,
+        asc: Option<bool>,
+        values: Vec<Value>,
+    ) -> Result<RowIter> {
+        self.storage
+            .scan_indexed_prefix(table_name, index_name, asc, values)
+            .await
+    }
+}
+
+#[cfg(feature = "metadata")]
+#[async_trait(?Send)]
+impl Metadata for CteStorage<'_> {
+    fn version(&self) -> String {
+        self.storage.version()
+    }
+
+    async fn schema_names(&self) -> Result<Vec<String>> {
+        self.storage.schema_names().await
+    }
+}
+
+impl GStore for CteStorage<'_> {}
+
+async fn run<'a>(
+    storage: &'a dyn GStore,
+    query: &'a Query,
+    filter_context: Option<Rc<FilterContext<'a>>>,
+    cancellation: &Cancellation,
+    metrics: Metrics,
+) -> Result<(Vec<String>, Vec<Row>)> {
+    let (labels, rows) = select_with_labels(
+        storage,
+        query,
+        filter_context,
+        true,
+        cancellation.clone(),
+        metrics,
+    )
+    .await?;
+
+    Ok((labels, rows.try_collect().await?))
+}
+
+/// Drops the rows which are already in `seen`, comparing NULLs as equal.
+fn distinct(seen: &mut HashSet<Vec<Key>>, rows: Vec<Row>) -> Result<Vec<Row>> {
+    let mut distinct = Vec::new();
+    for row in rows {
+        let key = row
+            .0
+            .iter()
+            .cloned()
+            .map(Key::try_from)
+            .collect::<Result<Vec<_>>>()?;
+
+        if seen.insert(key) {
+            distinct.push(row);
+        }
+    }
+
+    Ok(distinct)
+}
+
+async fn materialize<'a>(
+    ctes: &mut CteStorage<'a>,
+    cte: &Cte,
+    filter_context: Option<Rc<FilterContext<'a>>>,
+    cancellation: &Cancellation,
+) -> Result<()> {
+    let Cte {
+        alias: TableAlias { name, columns },
+        query,
+        recursive,
+    } = cte;
+
+    let check_columns = |labels: Vec<String>| match columns.as_slice() {
+        [] => Ok(labels),
+        columns if columns.len() == labels.len() => Ok(columns.to_vec()),
+        columns => Err(SelectError::CteColumnCountMismatch {
+            name: name.to_owned(),
+            expected: columns.len(),
+            found: labels.len(),
+        }),
+    };
+
+    let context = filter_context.as_ref().map(Rc::clone);
+    let (labels, mut rows) = run(&*ctes, query, context, cancellation, Metrics::default()).await?;
+    let columns = check_columns(labels)?;
+
+    let RecursiveTerm { all, query } = match recursive {
+        Some(recursive) => recursive,
+        None => {
+            ctes.tables.insert(name.to_owned(), (columns, rows));
+
+            return Ok(());
+        }
+    };
+
+    let mut seen = HashSet::new();
+    if !*all {
+        rows = distinct(&mut seen, rows)?;
+    }
+
+    let mut working = rows.clone();
+    let mut rounds = 0;
+    while !working.is_empty() {
+        if rounds == RECURSION_LIMIT {
+            return Err(
+                SelectError::RecursionLimitExceeded(name.to_owned(), RECURSION_LIMIT).into(),
+            );
+        }
+        rounds += 1;
+
+        ctes.tables
+            .insert(name.to_owned(), (columns.clone(), working));
+
+        let context = filter_context.as_ref().map(Rc::clone);
+        let (labels, found) = run(&*ctes, query, context, cancellation, Metrics::default()).await?;
+        if labels.len() != columns.len() {
+            return Err(SelectError::CteColumnCountMismatch {
+                name: name.to_owned(),
+                expected: columns.len(),
+                found: labels.len(),
+            }
+            .into());
+        }
+
+        working = if *all {
+            found
+        } else {
+            distinct(&mut seen, found)?
+        };
+        rows.extend(working.iter().cloned());
+    }
+
+    ctes.tables.insert(name.to_owned(), (columns, rows));
+
+    Ok(())
+}
+
+/// Materializes every common table expression of `query` in order, then runs its body
+/// with them visible as tables.
+pub async fn select_with_ctes<'a>(
+    storage: &'a dyn GStore,
+    query: &'a Query,
+    filter_context: Option<Rc<FilterContext<'a>>>,
+    cancellation: Cancellation,
+    metrics: Metrics,
+) -> Result<(Vec<String>, Vec<Row>)> {
+    let mut ctes = CteStorage {
+        storage,
+        tables: HashMap::new(),
+    };
+
+    for cte in &query.with {
+        let context = filter_context.as_ref().map(Rc::clone);
+        materialize(&mut ctes, cte, context, &cancellation).await?;
+    }
+
+    let body = Query {
+        with: Vec::new(),
+        ..query.clone()
+    };
+
+    run(&ctes, &body, filter_context, &cancellation, metrics).await
+}
+
+/// Labels of the body of `query`, read with its common table expressions visible as empty
+/// tables of their columns, so neither of them runs.
+pub async fn fetch_cte_labels(storage: &dyn GStore, query: &Query) -> Result<Vec<String>> {
+    let mut ctes = CteStorage {
+        storage,
+        tables: HashMap::new(),
+    };
+
+    for cte in &query.with {
+        let Cte {
+            alias: TableAlias { name, columns },
+            query,
+            ..
+        } = cte;
+
+        // a recursive term has the columns of the query it starts from
+        let columns = match columns.as_slice() {
+            [] => fetch_query_labels(&ctes, query).await?,
+            columns => columns.to_vec(),
+        };
+
+        ctes.tables.insert(name.to_owned(), (columns, Vec::new()));
+    }
+
+    let body = Query {
+        with: Vec::new(),
+        ..query.clone()
+    };
+
+    fetch_query_labels(&ctes, &body).await
+}
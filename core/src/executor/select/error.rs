@@ -8,6 +8,19 @@ pub enum SelectError {
     #[error("table alias for blend not found: {0}")]
     BlendTableAliasNotFound(String),
 
+    #[error("common table expression {name} has {found} columns but {expected} names")]
+    CteColumnCountMismatch {
+        name: String,
+        expected: usize,
+        found: usize,
+    },
+
+    #[error("recursive query {0} did not finish within {1} rounds")]
+    RecursionLimitExceeded(String, usize),
+
+    #[error("only a SELECT statement returns rows to stream")]
+    QueryRequired,
+
     #[error("unreachable!")]
     Unreachable,
 }
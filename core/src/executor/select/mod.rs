@@ -1,19 +1,25 @@
 mod blend;
+mod cte;
 mod error;
 
-pub use error::SelectError;
+pub use {blend::Blend, cte::fetch_cte_labels, error::SelectError};
 
 use {
-    self::blend::Blend,
+    self::cte::select_with_ctes,
     super::{
         aggregate::Aggregator,
+        cancel::Cancellation,
         context::{BlendContext, FilterContext},
         evaluate_stateless,
         fetch::{fetch_join_columns, fetch_relation_columns, fetch_relation_rows},
         filter::Filter,
         join::Join,
         limit::Limit,
-        sort::Sort,
+        metrics::{Metrics, Stage},
+        semi_join::outer_context,
+        session::{self, Session},
+        sort::{sort_rows, Sort},
+        window::Window,
     },
     crate::{
         ast::{Expr, Query, Select, SelectItem, SetExpr, TableWithJoins, Values},
@@ -23,14 +29,36 @@ use {
         store::GStore,
     },
     async_recursion::async_recursion,
-    futures::stream::{self, StreamExt, TryStream, TryStreamExt},
+    futures::{
+        future::{self, TryFutureExt},
+        stream::{self, Stream, StreamExt, TryStream, TryStreamExt},
+    },
     iter_enum::Iterator,
     std::{
         iter::{self, once},
+        pin::Pin,
         rc::Rc,
     },
 };
 
+/// Awaits the rows of an executor operator and runs the operator inside a `tracing` span,
+/// or just awaits them when the `tracing` feature is off.
+macro_rules! trace_operator {
+    ($name: literal, $rows: expr) => {{
+        #[cfg(feature = "tracing")]
+        let rows = {
+            let span = tracing::info_span!($name, rows = tracing::field::Empty);
+            let rows = tracing::Instrument::instrument($rows, span.clone()).await?;
+
+            super::trace::traced(span, rows)
+        };
+        #[cfg(not(feature = "tracing"))]
+        let rows = $rows.await?;
+
+        rows
+    }};
+}
+
 pub fn get_labels<'a>(
     projection: &[SelectItem],
     table_alias: &str,
@@ -108,6 +136,17 @@ pub fn get_labels<'a>(
         .collect::<Result<_>>()
 }
 
+/// Type given to a value by `expr` itself, which a NULL keeps, e.g. `CAST(NULL AS INTEGER)`.
+fn declared_type(expr: &Expr) -> Option<DataType> {
+    match expr {
+        Expr::Cast { data_type, .. } | Expr::TypedString { data_type, .. } => {
+            Some(data_type.to_owned())
+        }
+        Expr::Nested(expr) => declared_type(expr),
+        _ => None,
+    }
+}
+
 fn into_rows(exprs_list: &[Vec<Expr>]) -> (Vec<Result<Row>>, Vec<String>) {
     let first_len = exprs_list[0].len();
     let labels = (1..=first_len)
@@ -135,7 +174,7 @@ fn into_rows(exprs_list: &[Vec<Expr>]) -> (Vec<Result<Row>>, Vec<String>) {
                             Some(data_type) => evaluated.try_into_value(data_type, true)?,
                             None => {
                                 let value: Value = evaluated.try_into()?;
-                                *column_type = value.get_type();
+                                *column_type = value.get_type().or_else(|| declared_type(expr));
 
                                 value
                             }
@@ -154,30 +193,45 @@ fn into_rows(exprs_list: &[Vec<Expr>]) -> (Vec<Result<Row>>, Vec<String>) {
     (rows, labels)
 }
 
+type Rows<'a> = Pin<Box<dyn Stream<Item = Result<Row>> + 'a>>;
+
 #[async_recursion(?Send)]
 pub async fn select_with_labels<'a>(
     storage: &'a dyn GStore,
     query: &'a Query,
     filter_context: Option<Rc<FilterContext<'a>>>,
     with_labels: bool,
+    cancellation: Cancellation,
+    metrics: Metrics,
 ) -> Result<(
     Vec<String>,
     impl TryStream<Ok = Row, Error = Error, Item = Result<Row>> + 'a,
 )> {
+    if !query.with.is_empty() {
+        let (labels, rows) =
+            select_with_ctes(storage, query, filter_context, cancellation, metrics).await?;
+        let rows: Rows<'a> = Box::pin(stream::iter(rows.into_iter().map(Ok)));
+
+        return Ok((labels, rows));
+    }
+
     let Select {
         from: table_with_joins,
         selection: where_clause,
         projection,
         group_by,
+        grouping_sets,
         having,
         order_by,
+        distinct_on,
     } = match &query.body {
         SetExpr::Select(statement) => statement.as_ref(),
         SetExpr::Values(Values(values_list)) => {
             let limit = Limit::new(query.limit.as_ref(), query.offset.as_ref())?;
             let (rows, labels) = into_rows(values_list);
             let rows = stream::iter(rows);
-            let rows = limit.apply(rows);
+            let rows = metrics.profile(Stage::Output, limit.apply(rows));
+            let rows: Rows<'a> = Box::pin(rows.inspect_ok(move |_| metrics.add_output()));
 
             return Ok((labels, rows));
         }
@@ -188,15 +242,21 @@ pub async fn select_with_labels<'a>(
     let columns = Rc::from(columns);
     let rows = {
         let columns = Rc::clone(&columns);
-        fetch_relation_rows(storage, relation, &None)
-            .await?
-            .map(move |row| {
+        let cancellation = cancellation.clone();
+        let metrics = metrics.clone();
+        fetch_relation_rows(storage, relation, &None).map_ok(move |rows| {
+            rows.map(move |row| {
+                cancellation.check()?;
+                metrics.add_scanned();
+
                 let row = Some(row?);
                 let columns = Rc::clone(&columns);
                 let alias = get_alias(relation)?;
                 Ok(BlendContext::new(alias, columns, row, None))
             })
+        })
     };
+    let rows = metrics.profile(Stage::Scan, trace_operator!("scan", rows));
 
     let join_columns = fetch_join_columns(joins, storage).await?;
     let labels = if with_labels {
@@ -215,17 +275,20 @@ pub async fn select_with_labels<'a>(
         .map(|(_, columns)| columns)
         .map(Rc::from)
         .collect::<Vec<_>>();
+    let outer_context = outer_context(relation, &columns, joins, &join_columns)?;
     let join = Join::new(
         storage,
         joins,
-        join_columns,
+        join_columns.clone(),
         filter_context.as_ref().map(Rc::clone),
+        metrics.clone(),
     );
 
     let aggregate = Aggregator::new(
         storage,
         projection,
         group_by,
+        grouping_sets.as_ref(),
         having.as_ref(),
         filter_context.as_ref().map(Rc::clone),
     );
@@ -234,47 +297,115 @@ pub async fn select_with_labels<'a>(
         filter_context.as_ref().map(Rc::clone),
         projection,
     ));
-    let filter = Rc::new(Filter::new(
+    let filter = Filter::new(
         storage,
         where_clause.as_ref(),
         filter_context.as_ref().map(Rc::clone),
         None,
-    ));
+    )
+    .with_semi_joins(Some(outer_context))
+    .await
+    .map(Rc::new)?;
     let limit = Limit::new(query.limit.as_ref(), query.offset.as_ref())?;
-    let sort = Sort::new(storage, filter_context, order_by);
+    let window = Window::new(
+        storage,
+        projection,
+        order_by,
+        filter_context.as_ref().map(Rc::clone),
+    );
+    let sort = Sort::new(
+        storage,
+        filter_context,
+        order_by,
+        distinct_on.len(),
+        metrics.clone(),
+    );
 
-    let rows = join.apply(rows).await?;
-    let rows = rows.try_filter_map(move |blend_context| {
-        let filter = Rc::clone(&filter);
+    let rows = trace_operator!("join", join.apply(rows));
+    let rows = metrics.profile(Stage::Join, rows);
+    let rows = rows.try_filter_map({
+        let cancellation = cancellation.clone();
+        let metrics = metrics.clone();
 
-        async move {
-            filter
-                .check(Rc::clone(&blend_context))
-                .await
-                .map(|pass| pass.then(|| blend_context))
+        move |blend_context| {
+            let filter = Rc::clone(&filter);
+            let metrics = metrics.clone();
+            let checked = cancellation.check();
+
+            async move {
+                checked?;
+
+                let pass = filter.check(Rc::clone(&blend_context)).await?;
+                if pass {
+                    metrics.add_filtered();
+                }
+
+                Ok(pass.then(|| blend_context))
+            }
         }
     });
+    let rows = metrics.profile(
+        Stage::Filter,
+        trace_operator!("filter", future::ok::<_, Error>(rows)),
+    );
 
-    let rows = aggregate.apply(rows).await?;
-    let rows = sort
-        .apply(rows)
-        .await?
-        .and_then(move |(aggregated, context)| {
-            let blend = Rc::clone(&blend);
+    let rows = trace_operator!("aggregate", aggregate.apply(rows)).inspect_ok({
+        let metrics = metrics.clone();
 
-            async move { blend.apply(aggregated, context).await }
-        });
-    let rows = limit.apply(rows);
+        move |_| metrics.add_aggregated()
+    });
+    let rows = metrics.profile(Stage::Aggregate, rows);
+    let rows = window.apply(rows).await?;
+    let rows = trace_operator!("sort", sort.apply(rows));
+    let rows = metrics.profile(Stage::Sort, rows);
+    let rows = rows.and_then(move |(aggregated, windowed, context)| {
+        let blend = Rc::clone(&blend);
+        let checked = cancellation.check();
+
+        async move {
+            checked?;
+
+            blend.apply(aggregated, windowed, context).await
+        }
+    });
+    let deterministic_order = session::current(Session::deterministic_order).unwrap_or_default();
+    let rows: Rows<'a> = if order_by.is_empty() && deterministic_order {
+        let rows = sort_rows(rows.try_collect().await?);
+
+        Box::pin(stream::iter(rows.into_iter().map(Ok)))
+    } else {
+        Box::pin(rows)
+    };
+    let rows = metrics.profile(Stage::Output, limit.apply(rows));
+    let rows: Rows<'a> = Box::pin(rows.inspect_ok(move |_| metrics.add_output()));
 
     Ok((labels, rows))
 }
 
+/// Runs `query` and returns its labels with the stream of its rows, which are read from the
+/// storage as the stream is polled.
+pub async fn select_stream<'a>(
+    storage: &'a dyn GStore,
+    query: &'a Query,
+    cancellation: Cancellation,
+    metrics: Metrics,
+) -> Result<(Vec<String>, impl Stream<Item = Result<Row>> + 'a)> {
+    select_with_labels(storage, query, None, true, cancellation, metrics).await
+}
+
 pub async fn select<'a>(
     storage: &'a dyn GStore,
     query: &'a Query,
     filter_context: Option<Rc<FilterContext<'a>>>,
 ) -> Result<impl TryStream<Ok = Row, Error = Error, Item = Result<Row>> + 'a> {
-    select_with_labels(storage, query, filter_context, false)
-        .await
-        .map(|(_, rows)| rows)
+    select_with_labels(
+        storage,
+        query,
+        filter_context,
+        false,
+        Cancellation::default(),
+        Metrics::default(),
+    )
+    .await
+    .map(|(_, rows)| rows)
 }
@@ -0,0 +1,317 @@
+//! `IN` and `EXISTS` of subqueries checked by probing a hash set built from a single run of the
+//! subquery, instead of running the subquery again for every row the filter checks.
+//!
+//! * `expr [NOT] IN (subquery)` of a subquery which does not refer to the outer rows.
+//! * `EXISTS (subquery)` of a subquery which refers to them only through a single
+//!   `inner = outer` condition, which runs as `outer IN (SELECT inner FROM .. WHERE ..)` with
+//!   the rest of its conditions.
+//!
+//! A row passes on its first match, so several matches never repeat it.
+
+use {
+    super::{
+        context::FilterContext,
+        evaluate::{evaluate, Evaluated},
+        fetch::fetch_relation_columns,
+        filter::conjuncts,
+        join::hash_key,
+        select::select,
+    },
+    crate::{
+        ast::{
+            Aggregate, BinaryOperator, Expr, Join, Query, Select, SelectItem, SetExpr, TableFactor,
+            TableWithJoins,
+        },
+        data::{get_alias, Key, Value},
+        plan::{check_evaluable, check_query, Context},
+        result::Result,
+        store::GStore,
+    },
+    futures::{future::ready, stream::TryStreamExt},
+    im_rc::HashMap,
+    std::{collections::HashSet, rc::Rc},
+};
+
+pub struct SemiJoin<'a> {
+    probe: &'a Expr,
+    negated: bool,
+    keys: HashSet<Key>,
+    unkeyed: Vec<Value>,
+    values: Vec<Value>,
+    has_null: bool,
+}
+
+impl<'a> SemiJoin<'a> {
+    /// Runs the subquery of `expr`, `None` if `expr` can not be checked as a semi join.
+    ///
+    /// `outer_context` has the columns of the rows the filter checks.
+    pub async fn new(
+        storage: &'a dyn GStore,
+        outer_context: Option<Rc<Context<'_>>>,
+        expr: &'a Expr,
+    ) -> Result<Option<SemiJoin<'a>>> {
+        let (probe, negated, values) = match expr {
+            Expr::InSubquery {
+                expr,
+                subquery,
+                negated,
+            } => {
+                if !is_uncorrelated(storage, subquery).await? {
+                    return Ok(None);
+                }
+
+                (expr.as_ref(), *negated, run(storage, subquery).await?)
+            }
+            Expr::Exists(subquery) => match decorrelate(storage, outer_context, subquery).await? {
+                Some((probe, query)) => (probe, false, run(storage, &query).await?),
+                None => return Ok(None),
+            },
+            _ => return Ok(None),
+        };
+
+        let has_null = values.iter().any(Value::is_null);
+        let mut keys = HashSet::new();
+        let mut unkeyed = Vec::new();
+        for value in values.iter().filter(|value| !value.is_null()) {
+            match hash_key(value).ok() {
+                Some(key) => {
+                    keys.insert(key);
+                }
+                None => unkeyed.push(value.clone()),
+            }
+        }
+
+        Ok(Some(Self {
+            probe,
+            negated,
+            keys,
+            unkeyed,
+            values,
+            has_null,
+        }))
+    }
+
+    /// Whether the row of `context` passes, a NULL result does not.
+    ///
+    /// A NULL in the subquery keeps `NOT IN` from holding for any row unless the subquery is
+    /// empty.
+    pub async fn check(
+        &self,
+        storage: &'a dyn GStore,
+        context: Option<Rc<FilterContext<'a>>>,
+        aggregated: Option<Rc<HashMap<&'a Aggregate, Value>>>,
+    ) -> Result<bool> {
+        let target = evaluate(storage, context, aggregated, None, self.probe).await?;
+
+        if self.values.is_empty() {
+            return Ok(self.negated);
+        } else if target.is_null() || (self.negated && self.has_null) {
+            return Ok(false);
+        }
+
+        let found = match &target {
+            Evaluated::Value(value) => match hash_key(value).ok() {
+                Some(key) => {
+                    self.keys.contains(&key) || self.unkeyed.iter().any(|v| v == value.as_ref())
+                }
+                None => self.values.iter().any(|v| v == value.as_ref()),
+            },
+            Evaluated::Literal(_) => self
+                .values
+                .iter()
+                .any(|v| Evaluated::from(v.clone()) == target),
+        };
+
+        Ok(found ^ self.negated)
+    }
+}
+
+/// Columns of the rows a filter checks, the relation and its joins.
+pub fn outer_context<'c>(
+    relation: &TableFactor,
+    columns: &'c [String],
+    joins: &[Join],
+    join_columns: &'c [Rc<[String]>],
+) -> Result<Rc<Context<'c>>> {
+    let alias = get_alias(relation)?.to_owned();
+    let context = Context::new(
+        alias,
+        columns.iter().map(String::as_str).collect(),
+        None,
+        None,
+    );
+
+    joins
+        .iter()
+        .zip(join_columns)
+        .try_fold(Rc::new(context), |next, (join, columns)| {
+            let alias = get_alias(&join.relation)?.to_owned();
+            let columns = columns.iter().map(String::as_str).collect();
+
+            Ok(Rc::new(Context::new(alias, columns, Some(next), None)))
+        })
+}
+
+async fn run(storage: &dyn GStore, query: &Query) -> Result<Vec<Value>> {
+    select(storage, query, None)
+        .await?
+        .and_then(|row| ready(row.take_first_value()))
+        .try_collect()
+        .await
+}
+
+/// Columns of the single table `query` reads, `None` if it reads anything else.
+async fn table_context<'c>(
+    storage: &dyn GStore,
+    query: &Query,
+    columns: &'c mut Vec<String>,
+) -> Result<Option<Rc<Context<'c>>>> {
+    let select = match &query.body {
+        SetExpr::Select(select) if query.with.is_empty() => select,
+        _ => return Ok(None),
+    };
+
+    let TableWithJoins { relation, joins } = &select.from;
+    if !joins.is_empty() || !matches!(relation, TableFactor::Table { .. }) {
+        return Ok(None);
+    }
+
+    *columns = fetch_relation_columns(storage, relation).await?;
+    let columns: &'c [String] = columns;
+    let alias = get_alias(relation)?.to_owned();
+    let columns = columns.iter().map(String::as_str).collect();
+
+    Ok(Some(Rc::new(Context::new(alias, columns, None, None))))
+}
+
+/// Whether `query` reads nothing but its own single table, so it has the same rows for every
+/// outer row. Queries it can not tell this for are treated as correlated.
+async fn is_uncorrelated(storage: &dyn GStore, query: &Query) -> Result<bool> {
+    let mut columns = Vec::new();
+
+    Ok(match table_context(storage, query, &mut columns).await? {
+        Some(context) => check_query(Some(context), query),
+        None => false,
+    })
+}
+
+/// Splits the subquery of `EXISTS` into the outer expression to probe with and the query which
+/// gives the inner values it has to match, `None` unless the subquery is a plain filter on a
+/// single table with one `inner = outer` condition.
+async fn decorrelate<'a>(
+    storage: &dyn GStore,
+    outer_context: Option<Rc<Context<'_>>>,
+    query: &'a Query,
+) -> Result<Option<(&'a Expr, Query)>> {
+    let select = match &query.body {
+        SetExpr::Select(select) if query.limit.is_none() && query.offset.is_none() => select,
+        _ => return Ok(None),
+    };
+
+    let Select {
+        projection,
+        from,
+        selection,
+        group_by,
+        grouping_sets,
+        having,
+        distinct_on,
+        ..
+    } = select.as_ref();
+
+    // an aggregate would give a row even when no row matches
+    let plain_projection = projection.iter().all(|item| match item {
+        SelectItem::Expr { expr, .. } => matches!(
+            expr,
+            Expr::Literal(_) | Expr::Identifier(_) | Expr::CompoundIdentifier(_)
+        ),
+        SelectItem::QualifiedWildcard(_) | SelectItem::Wildcard => true,
+    });
+
+    let selection = match selection {
+        Some(selection)
+            if plain_projection
+                && group_by.is_empty()
+                && grouping_sets.is_none()
+                && having.is_none()
+                && distinct_on.is_empty() =>
+        {
+            selection
+        }
+        _ => return Ok(None),
+    };
+
+    let mut columns = Vec::new();
+    let inner_context = match table_context(storage, query, &mut columns).await? {
+        Some(context) => context,
+        None => return Ok(None),
+    };
+
+    let is_inner = |expr: &Expr| check_evaluable(Some(Rc::clone(&inner_context)), expr);
+    let is_outer = |expr: &Expr| {
+        matches!(expr, Expr::Identifier(_) | Expr::CompoundIdentifier(_))
+            && check_evaluable(outer_context.as_ref().map(Rc::clone), expr)
+            && !is_inner(expr)
+    };
+
+    let mut matched = None;
+    let mut rest = Vec::new();
+    for expr in conjuncts(selection) {
+        if is_inner(expr) {
+            rest.push(expr.clone());
+            continue;
+        }
+
+        match (expr, matched) {
+            (
+                Expr::BinaryOp {
+                    left,
+                    op: BinaryOperator::Eq,
+                    right,
+                },
+                None,
+            ) => {
+                if is_inner(left) && is_outer(right) {
+                    matched = Some((left.as_ref(), right.as_ref()));
+                } else if is_inner(right) && is_outer(left) {
+                    matched = Some((right.as_ref(), left.as_ref()));
+                } else {
+                    return Ok(None);
+                }
+            }
+            _ => return Ok(None),
+        }
+    }
+
+    let (inner, outer) = match matched {
+        Some(matched) => matched,
+        None => return Ok(None),
+    };
+
+    let selection = rest.into_iter().reduce(|left, right| Expr::BinaryOp {
+        left: Box::new(left),
+        op: BinaryOperator::And,
+        right: Box::new(right),
+    });
+    let select = Select {
+        projection: vec![SelectItem::Expr {
+            expr: inner.clone(),
+            label: "inner".to_owned(),
+        }],
+        from: from.clone(),
+        selection,
+        group_by: Vec::new(),
+        grouping_sets: None,
+        having: None,
+        order_by: Vec::new(),
+        distinct_on: Vec::new(),
+    };
+    let query = Query {
+        with: Vec::new(),
+        body: SetExpr::Select(Box::new(select)),
+        limit: None,
+        offset: None,
+    };
+
+    Ok(Some((outer, query)))
+}
@@ -0,0 +1,301 @@
+use {
+    super::{
+        evaluate_stateless,
+        session::{self, Session},
+    },
+    crate::{
+        ast::{ColumnDef, Expr, Function, ObjectName},
+        data::{get_name, schema::ColumnDefExt, Row, Value},
+        plan::PlanExpr,
+        result::{MutResult, Result, TrySelf},
+        store::{GStore, GStoreMut},
+    },
+    futures::stream::{self, TryStreamExt},
+    serde::Serialize,
+    std::{collections::HashMap, fmt::Debug, mem},
+    thiserror::Error as ThisError,
+};
+
+#[derive(ThisError, Serialize, Debug, PartialEq)]
+pub enum SequenceError {
+    #[error("sequence has no value left: {0}")]
+    Exhausted(String),
+
+    #[error("sequence not found: {0}")]
+    NotFound(String),
+
+    #[error("sequence already exists: {0}")]
+    AlreadyExists(String),
+
+    #[error("currval of sequence is not yet defined in this session: {0}")]
+    CurrentValueNotDefined(String),
+
+    #[error("nextval of sequence is not available in this statement: {0}")]
+    NextValueNotAvailable(String),
+}
+
+/// Sequence values a session keeps, see `Session`.
+#[derive(Default)]
+pub struct Sequences {
+    /// Values of the sequences `NEXTVAL` is used on in the running statement, which are written
+    /// to the storage once the statement succeeds.
+    pending: HashMap<String, i64>,
+
+    /// Last value `NEXTVAL` returned for each sequence in the session.
+    current: HashMap<String, i64>,
+}
+
+fn with_sequences<T>(f: impl FnOnce(&mut Sequences) -> T) -> Result<T> {
+    session::current(|session| session.with_sequences(f))
+}
+
+pub async fn create_sequence<T: GStore + GStoreMut>(
+    storage: T,
+    name: &ObjectName,
+) -> MutResult<T, ()> {
+    let (storage, name) = check_new(&storage, name).await.try_self(storage)?;
+
+    storage.update_sequence(name, 0).await
+}
+
+pub async fn drop_sequence<T: GStore + GStoreMut>(
+    storage: T,
+    name: &ObjectName,
+    if_exists: bool,
+) -> MutResult<T, ()> {
+    let (storage, name) = get_name(name).try_self(storage)?;
+    let value = storage.fetch_sequence(name).await;
+
+    match value.try_self(storage)? {
+        (storage, Some(_)) => storage.delete_sequence(name).await,
+        (storage, None) if if_exists => Ok((storage, ())),
+        (storage, None) => Err((storage, SequenceError::NotFound(name.to_owned()).into())),
+    }
+}
+
+async fn check_new<'a>(storage: &dyn GStore, name: &'a ObjectName) -> Result<&'a str> {
+    let name = get_name(name)?.as_str();
+
+    match storage.fetch_sequence(name).await? {
+        Some(_) => Err(SequenceError::AlreadyExists(name.to_owned()).into()),
+        None => Ok(name),
+    }
+}
+
+/// Reads the value of the sequence from the storage, unless the running statement has already
+/// read it, so `NEXTVAL` can take the values following it.
+pub async fn load_sequence(storage: &dyn GStore, name: &str) -> Result<()> {
+    if with_sequences(|sequences| sequences.pending.contains_key(name))? {
+        return Ok(());
+    }
+
+    let value = storage
+        .fetch_sequence(name)
+        .await?
+        .ok_or_else(|| SequenceError::NotFound(name.to_owned()))?;
+
+    with_sequences(|sequences| sequences.pending.insert(name.to_owned(), value))?;
+
+    Ok(())
+}
+
+/// Loads the sequences `NEXTVAL` is called on in `exprs`, which are evaluated without access to
+/// the storage, e.g. the VALUES of INSERT and the defaults of its columns.
+pub async fn load_nextval_sequences<'a>(
+    storage: &dyn GStore,
+    exprs: impl Iterator<Item = &'a Expr>,
+) -> Result<()> {
+    for name in exprs.flat_map(nextval_sequences) {
+        load_sequence(storage, &name).await?;
+    }
+
+    Ok(())
+}
+
+/// Advances the sequence loaded by `load_sequence` and returns its new value.
+pub fn next_value(name: &str) -> Result<i64> {
+    with_sequences(|Sequences { pending, current }| {
+        let value = pending
+            .get_mut(name)
+            .ok_or_else(|| SequenceError::NextValueNotAvailable(name.to_owned()))?;
+        *value = value
+            .checked_add(1)
+            .ok_or_else(|| SequenceError::Exhausted(name.to_owned()))?;
+        current.insert(name.to_owned(), *value);
+
+        Ok(*value)
+    })?
+}
+
+pub fn current_value(name: &str) -> Result<i64> {
+    with_sequences(|sequences| sequences.current.get(name).copied())?
+        .ok_or_else(|| SequenceError::CurrentValueNotDefined(name.to_owned()).into())
+}
+
+/// Writes the values `NEXTVAL` took in the statement `session` runs to the storage, as a part of
+/// the statement, so a rolled back transaction gives them out again.
+pub async fn flush_sequences<T: GStore + GStoreMut>(
+    storage: T,
+    session: &Session,
+) -> MutResult<T, ()> {
+    let pending = session.with_sequences(|sequences| mem::take(&mut sequences.pending));
+
+    stream::iter(pending.into_iter().map(Ok))
+        .try_fold((storage, ()), |(storage, ()), (name, value)| async move {
+            storage.update_sequence(&name, value).await
+        })
+        .await
+}
+
+/// Forgets the values `NEXTVAL` took in a statement of `session` which failed.
+pub fn discard_sequences(session: &Session) {
+    session.with_sequences(|sequences| sequences.pending.clear());
+}
+
+/// Names of the sequences `NEXTVAL` is called on in `expr`, skipping the names which can not be
+/// evaluated without a row, whose evaluation reports the error.
+fn nextval_sequences(expr: &Expr) -> Vec<String> {
+    let names = match expr {
+        Expr::Function(function) => match function.as_ref() {
+            Function::Nextval(name) => evaluate_stateless(None, name)
+                .and_then(Value::try_from)
+                .ok()
+                .and_then(|name| match name {
+                    Value::Str(name) => Some(name),
+                    _ => None,
+                })
+                .into_iter()
+                .collect(),
+            _ => Vec::new(),
+        },
+        _ => Vec::new(),
+    };
+
+    let exprs = match PlanExpr::from(expr) {
+        PlanExpr::Expr(expr) => vec![expr],
+        PlanExpr::TwoExprs(expr, expr2) => vec![expr, expr2],
+        PlanExpr::ThreeExprs(expr, expr2, expr3) => vec![expr, expr2, expr3],
+        PlanExpr::MultiExprs(exprs) => exprs,
+        PlanExpr::QueryAndExpr { expr, .. } => vec![expr],
+        PlanExpr::None
+        | PlanExpr::Identifier(_)
+        | PlanExpr::CompoundIdentifier(_)
+        | PlanExpr::Query(_) => Vec::new(),
+    };
+
+    names
+        .into_iter()
+        .chain(exprs.into_iter().flat_map(nextval_sequences))
+        .collect()
+}
+
+/// Name of the sequence an AUTOINCREMENT column takes its values from.
+pub fn auto_increment_sequence(table_name: &str, column_name: &str) -> String {
+    format!("{}.{}", table_name, column_name)
+}
+
+/// Names of the sequences of the AUTOINCREMENT columns of a table.
+pub fn auto_increment_sequences(table_name: &str, column_defs: &[ColumnDef]) -> Vec<String> {
+    column_defs
+        .iter()
+        .filter(|column_def| column_def.is_auto_increment())
+        .map(|ColumnDef { name, .. }| auto_increment_sequence(table_name, name))
+        .collect()
+}
+
+/// Gives the NULL values of the AUTOINCREMENT columns the values following their sequences, and
+/// moves each sequence to the largest value its column holds in `rows`, so an explicitly given
+/// value is never handed out again.
+pub async fn fill_auto_increment<T: GStore + GStoreMut>(
+    storage: T,
+    table_name: &str,
+    column_defs: &[ColumnDef],
+    rows: Vec<Row>,
+) -> MutResult<T, Vec<Row>> {
+    let columns = column_defs
+        .iter()
+        .enumerate()
+        .filter(|(_, column_def)| column_def.is_auto_increment())
+        .map(Ok);
+
+    stream::iter(columns)
+        .try_fold(
+            (storage, rows),
+            |(storage, rows), (index, ColumnDef { name, .. })| async move {
+                let name = auto_increment_sequence(table_name, name);
+                let last = storage.fetch_sequence(&name).await;
+                let (storage, last) = last.try_self(storage)?;
+                let last = last.unwrap_or(0);
+
+                let (storage, (rows, next)) = fill(&name, index, last, rows).try_self(storage)?;
+                if next == last {
+                    return Ok((storage, rows));
+                }
+
+                storage
+                    .update_sequence(&name, next)
+                    .await
+                    .map(|(storage, ())| (storage, rows))
+            },
+        )
+        .await
+}
+
+/// Moves the value of each sequence `from` to `to`, as a table or a column is renamed.
+pub async fn rename_sequences<T: GStore + GStoreMut>(
+    storage: T,
+    names: &[(String, String)],
+) -> MutResult<T, ()> {
+    stream::iter(names.iter().map(Ok))
+        .try_fold((storage, ()), |(storage, ()), (from, to)| async move {
+            let value = storage.fetch_sequence(from).await;
+
+            match value.try_self(storage)? {
+                (storage, Some(value)) => {
+                    let (storage, ()) = storage.update_sequence(to, value).await?;
+
+                    storage.delete_sequence(from).await
+                }
+                (storage, None) => Ok((storage, ())),
+            }
+        })
+        .await
+}
+
+pub async fn delete_sequences<T: GStore + GStoreMut>(
+    storage: T,
+    names: &[String],
+) -> MutResult<T, ()> {
+    stream::iter(names.iter().map(Ok))
+        .try_fold((storage, ()), |(storage, ()), name| async move {
+            storage.delete_sequence(name).await
+        })
+        .await
+}
+
+/// Gives the NULL values of the column at `index` the values following `last`, and returns the
+/// rows with the largest value the column holds.
+fn fill(name: &str, index: usize, last: i64, rows: Vec<Row>) -> Result<(Vec<Row>, i64)> {
+    let mut last = last;
+    let rows = rows
+        .into_iter()
+        .map(|Row(mut values)| {
+            match values.get(index) {
+                Some(Value::Null) => {
+                    last = last
+                        .checked_add(1)
+                        .ok_or_else(|| SequenceError::Exhausted(name.to_owned()))?;
+                    values[index] = Value::I64(last);
+                }
+                Some(Value::I64(value)) => {
+                    last = last.max(*value);
+                }
+                _ => {}
+            }
+
+            Ok(Row(values))
+        })
+        .collect::<Result<_>>()?;
+
+    Ok((rows, last))
+}
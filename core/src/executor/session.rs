@@ -0,0 +1,294 @@
+use {
+    super::{
+        evaluate::{FloatDivisionByZero, OperatorSettings, Rng},
+        sequence::Sequences,
+        udf::{self, EmptyGroup, Functions, UserAggregate},
+    },
+    crate::{ast::DataType, data::Value, result::Result, store::GStore},
+    futures::{
+        future::poll_fn,
+        stream::{self, Stream},
+        Future,
+    },
+    serde::Serialize,
+    std::{
+        cell::RefCell,
+        fmt::Debug,
+        rc::Rc,
+        sync::{Arc, Mutex, MutexGuard, PoisonError},
+    },
+    thiserror::Error as ThisError,
+};
+
+#[derive(ThisError, Serialize, Debug, PartialEq)]
+pub enum SessionError {
+    #[error("no session is running a statement, run it with Glue or execute_cancellable")]
+    NotEntered,
+}
+
+thread_local! {
+    /// Session of the statement being polled on this thread, see `Session::scope`.
+    static CURRENT: RefCell<Option<Rc<Session>>> = RefCell::new(None);
+}
+
+/// Settings and state shared by the statements of a session, e.g. the ones a `Glue` runs.
+///
+/// Clones share the state, so a clone handed to another `execute` keeps drawing from the same
+/// `RANDOM()` generator. Statements of different sessions never see each other's state.
+#[derive(Clone, Default)]
+pub struct Session {
+    state: Arc<Mutex<State>>,
+}
+
+#[derive(Default)]
+struct State {
+    /// Generator of `RANDOM()`, seeded from the OS entropy source on first use unless a seed
+    /// is given
+    rng: Option<Rng>,
+
+    /// Whether the queries without `ORDER BY` sort their rows
+    deterministic_order: bool,
+
+    /// Whether comparisons and arithmetic require operands of the same type
+    strict_typing: bool,
+
+    /// How a FLOAT divided by zero evaluates
+    float_division_by_zero: FloatDivisionByZero,
+
+    /// Formats DATE strings are parsed with, the built-in ones when empty
+    date_formats: Arc<Vec<String>>,
+
+    /// Formats TIMESTAMP strings are parsed with, the built-in ones when empty
+    timestamp_formats: Arc<Vec<String>>,
+
+    sequences: Sequences,
+
+    functions: Functions,
+}
+
+impl Session {
+    /// Sets the seed of `RANDOM()` for the following statements of the session.
+    ///
+    /// Once a seed is given, the same sequence of queries produces the same random values.
+    /// `None` makes the next call draw a fresh seed from the OS entropy source.
+    pub fn set_random_seed(&self, seed: Option<u64>) {
+        self.state().rng = seed.map(Rng::new);
+    }
+
+    /// Makes the following queries of the session without `ORDER BY` return their rows sorted
+    /// by their values, so the result no longer depends on the order the storage scans rows in.
+    ///
+    /// Meant for tests comparing whole results, every such query buffers its rows to sort them.
+    pub fn set_deterministic_order(&self, enabled: bool) {
+        self.state().deterministic_order = enabled;
+    }
+
+    pub(crate) fn deterministic_order(&self) -> bool {
+        self.state().deterministic_order
+    }
+
+    /// Makes comparisons and arithmetic of the following statements of the session require
+    /// operands of the same type, so `id = '1'` errors on an INTEGER `id` instead of comparing as
+    /// FALSE. Operands of different types need an explicit `CAST`.
+    pub fn set_strict_typing(&self, enabled: bool) {
+        self.state().strict_typing = enabled;
+    }
+
+    /// Chooses whether a FLOAT divided by zero errors or evaluates to `Infinity` or `NaN` in the
+    /// following statements of the session. Division of two number literals is exact and keeps
+    /// erroring.
+    pub fn set_float_division_by_zero(&self, mode: FloatDivisionByZero) {
+        self.state().float_division_by_zero = mode;
+    }
+
+    /// Settings of the session which change how operators evaluate.
+    pub(crate) fn operator_settings(&self) -> OperatorSettings {
+        let state = self.state();
+
+        OperatorSettings {
+            strict_typing: state.strict_typing,
+            float_division_by_zero: state.float_division_by_zero,
+        }
+    }
+
+    /// Makes the following statements of the session parse DATE strings with `formats`, `chrono`
+    /// format strings such as `%m/%d/%Y` tried in order. A string none of them matches fails to
+    /// parse, and an empty list restores the built-in formats.
+    pub fn set_date_formats(&self, formats: Vec<String>) {
+        self.state().date_formats = Arc::new(formats);
+    }
+
+    pub(crate) fn date_formats(&self) -> Arc<Vec<String>> {
+        Arc::clone(&self.state().date_formats)
+    }
+
+    /// Makes the following statements of the session parse TIMESTAMP strings with `formats`
+    /// tried in order, like `set_date_formats`. A format without a time of day parses the string
+    /// as midnight.
+    pub fn set_timestamp_formats(&self, formats: Vec<String>) {
+        self.state().timestamp_formats = Arc::new(formats);
+    }
+
+    pub(crate) fn timestamp_formats(&self) -> Arc<Vec<String>> {
+        Arc::clone(&self.state().timestamp_formats)
+    }
+
+    /// Registers `body` as the SQL function `name` for the session, replacing a built-in or
+    /// registered function of the same name.
+    ///
+    /// Each argument is cast into its type in `arg_types`, NULL passes as it is, and the function
+    /// must return a value of `return_type` or NULL. An `Err` it returns fails the statement with
+    /// its message.
+    pub fn register_function<F>(
+        &self,
+        name: &str,
+        arg_types: Vec<DataType>,
+        return_type: DataType,
+        body: F,
+    ) where
+        F: Fn(Vec<Value>) -> std::result::Result<Value, String> + Send + Sync + 'static,
+    {
+        self.state()
+            .functions
+            .register_function(name, arg_types, return_type, body);
+    }
+
+    /// Removes the function `name` registered by `register_function`, which brings back the
+    /// built-in of the same name if there is one.
+    pub fn unregister_function(&self, name: &str) {
+        self.state().functions.unregister_function(name);
+    }
+
+    /// Registers `aggregate` as the SQL aggregate function `name` taking a single argument, for
+    /// the session.
+    ///
+    /// Each value is cast into `arg_type` and NULL values are skipped, as the built-in aggregates
+    /// do. The aggregate must return a value of `return_type` or NULL, and `empty` decides what it
+    /// returns for a group without any value to accumulate.
+    pub fn register_aggregate<A>(
+        &self,
+        name: &str,
+        arg_type: DataType,
+        return_type: DataType,
+        empty: EmptyGroup,
+        aggregate: A,
+    ) where
+        A: UserAggregate + Send + Sync + 'static,
+    {
+        self.state()
+            .functions
+            .register_aggregate(name, arg_type, return_type, empty, aggregate);
+    }
+
+    /// Removes the aggregate `name` registered by `register_aggregate`.
+    pub fn unregister_aggregate(&self, name: &str) {
+        self.state().functions.unregister_aggregate(name);
+    }
+
+    /// Reads the functions `CREATE FUNCTION` stored in `storage` into the session, which
+    /// `translate` needs to find them. Every statement the session runs reads them again.
+    pub async fn load_functions<T: GStore>(&self, storage: &T) -> Result<()> {
+        udf::load_functions(storage, self).await
+    }
+
+    /// Runs `f` in the session, for code which reads it outside of a statement, e.g. `translate`
+    /// looking up the functions registered to the session.
+    pub fn enter<T>(&self, f: impl FnOnce() -> T) -> T {
+        enter(&Rc::new(self.clone()), f)
+    }
+
+    /// Runs `f` with the `RANDOM()` generator of the session.
+    pub(crate) fn with_rng<T>(&self, f: impl FnOnce(&mut Rng) -> T) -> T {
+        f(self.state().rng.get_or_insert_with(Rng::from_entropy))
+    }
+
+    /// Runs `f` with the sequence values `NEXTVAL` took in the session.
+    pub(crate) fn with_sequences<T>(&self, f: impl FnOnce(&mut Sequences) -> T) -> T {
+        f(&mut self.state().sequences)
+    }
+
+    /// Runs `f` with the functions registered to the session.
+    pub(crate) fn with_functions<T>(&self, f: impl FnOnce(&mut Functions) -> T) -> T {
+        f(&mut self.state().functions)
+    }
+
+    /// Runs `future` in the session, code it polls reads the session with `current`.
+    pub(crate) async fn scope<F: Future>(&self, future: F) -> F::Output {
+        let session = Rc::new(self.clone());
+        futures::pin_mut!(future);
+
+        poll_fn(|cx| enter(&session, || future.as_mut().poll(cx))).await
+    }
+
+    /// Same as `scope`, for each poll of a stream which outlives the call creating it.
+    pub(crate) fn scope_stream<'a, S>(&self, stream: S) -> impl Stream<Item = S::Item> + 'a
+    where
+        S: Stream + 'a,
+    {
+        let session = Rc::new(self.clone());
+        let mut stream = Box::pin(stream);
+
+        stream::poll_fn(move |cx| enter(&session, || stream.as_mut().poll_next(cx)))
+    }
+
+    fn state(&self) -> MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// Runs `f` with the session of the running statement.
+///
+/// Fails outside of any statement, e.g. for `evaluate_stateless` called by the user, so the
+/// state of a session is never kept in one which is dropped right after. Callers which only
+/// read a setting fall back to its default themselves.
+pub(crate) fn current<T>(f: impl FnOnce(&Session) -> T) -> Result<T> {
+    match CURRENT.with(|current| current.borrow().clone()) {
+        Some(session) => Ok(f(&session)),
+        None => Err(SessionError::NotEntered.into()),
+    }
+}
+
+fn enter<T>(session: &Rc<Session>, f: impl FnOnce() -> T) -> T {
+    struct Restore(Option<Rc<Session>>);
+
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            let previous = self.0.take();
+
+            CURRENT.with(|current| *current.borrow_mut() = previous);
+        }
+    }
+
+    let previous = CURRENT.with(|current| current.replace(Some(Rc::clone(session))));
+    let _restore = Restore(previous);
+
+    f()
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::{Session, SessionError},
+        crate::{
+            data::Value, executor::evaluate_stateless, parse_sql::parse_expr,
+            translate::translate_expr,
+        },
+    };
+
+    #[test]
+    fn current() {
+        let expr = translate_expr(&parse_expr("RANDOM()").unwrap()).unwrap();
+        let random = || evaluate_stateless(None, &expr).and_then(Value::try_from);
+
+        assert_eq!(random(), Err(SessionError::NotEntered.into()));
+
+        let seeded = || {
+            let session = Session::default();
+            session.set_random_seed(Some(1));
+
+            session.enter(random)
+        };
+        assert!(matches!(seeded(), Ok(Value::F64(_))));
+        assert_eq!(seeded(), seeded());
+    }
+}
@@ -2,10 +2,12 @@ use {
     super::{
         context::{AggregateContext, BlendContext, FilterContext},
         evaluate::evaluate,
+        metrics::Metrics,
+        nulls::{self, compare, is_same, Order},
     },
     crate::{
-        ast::{Aggregate, OrderByExpr},
-        data::Value,
+        ast::{Aggregate, Collation, OrderByExpr, Window},
+        data::{Row, Value},
         result::Result,
         store::GStore,
     },
@@ -19,10 +21,14 @@ pub struct Sort<'a> {
     storage: &'a dyn GStore,
     context: Option<Rc<FilterContext<'a>>>,
     order_by: &'a [OrderByExpr],
+    /// number of leading `order_by` keys used to keep only the first row of each DISTINCT ON key
+    distinct_on: usize,
+    metrics: Metrics,
 }
 
 type Item<'a> = Result<(
     Option<Rc<HashMap<&'a Aggregate, Value>>>,
+    Option<Rc<HashMap<&'a Window, Value>>>,
     Rc<BlendContext<'a>>,
 )>;
 
@@ -31,11 +37,15 @@ impl<'a> Sort<'a> {
         storage: &'a dyn GStore,
         context: Option<Rc<FilterContext<'a>>>,
         order_by: &'a [OrderByExpr],
+        distinct_on: usize,
+        metrics: Metrics,
     ) -> Self {
         Self {
             storage,
             context,
             order_by,
+            distinct_on,
+            metrics,
         }
     }
 
@@ -45,77 +55,111 @@ impl<'a> Sort<'a> {
     ) -> Result<Pin<Box<dyn Stream<Item = Item<'a>> + 'a>>> {
         if self.order_by.is_empty() {
             let rows = rows.map_ok(|aggregate_context| {
-                let AggregateContext { aggregated, next } = aggregate_context;
+                let AggregateContext {
+                    aggregated,
+                    windowed,
+                    next,
+                } = aggregate_context;
 
-                (aggregated.map(Rc::new), next)
+                (aggregated.map(Rc::new), windowed.map(Rc::new), next)
             });
 
             return Ok(Box::pin(rows));
         }
 
         let rows = rows
-            .and_then(move |AggregateContext { aggregated, next }| async move {
-                let blend_context = Rc::clone(&next);
-                let filter_context = Rc::new(FilterContext::concat(
-                    self.context.as_ref().map(Rc::clone),
-                    Some(Rc::clone(&next)),
-                ));
-                let aggregated = aggregated.map(Rc::new);
-
-                let values = stream::iter(self.order_by.iter())
-                    .then(|OrderByExpr { expr, asc }| {
-                        let context = Some(Rc::clone(&filter_context));
-                        let aggregated = aggregated.as_ref().map(Rc::clone);
-
-                        async move {
-                            evaluate(self.storage, context, aggregated, expr)
-                                .await?
-                                .try_into()
-                                .map(|value| (value, *asc))
-                        }
-                    })
-                    .try_collect::<Vec<_>>()
-                    .await?;
-
-                Ok((values, aggregated, blend_context))
-            })
+            .and_then(
+                move |AggregateContext {
+                          aggregated,
+                          windowed,
+                          next,
+                      }| async move {
+                    self.metrics.add_sorted();
+
+                    let blend_context = Rc::clone(&next);
+                    let filter_context = Rc::new(FilterContext::concat(
+                        self.context.as_ref().map(Rc::clone),
+                        Some(Rc::clone(&next)),
+                    ));
+                    let aggregated = aggregated.map(Rc::new);
+                    let windowed = windowed.map(Rc::new);
+
+                    let values = stream::iter(self.order_by.iter())
+                        .then(|order_by_expr| {
+                            let OrderByExpr {
+                                expr, collation, ..
+                            } = order_by_expr;
+                            let context = Some(Rc::clone(&filter_context));
+                            let aggregated = aggregated.as_ref().map(Rc::clone);
+                            let windowed = windowed.as_ref().map(Rc::clone);
+
+                            async move {
+                                evaluate(self.storage, context, aggregated, windowed, expr)
+                                    .await?
+                                    .try_into()
+                                    .map(|value| {
+                                        (collate(value, *collation), Order::from(order_by_expr))
+                                    })
+                            }
+                        })
+                        .try_collect::<Vec<_>>()
+                        .await?;
+
+                    Ok((values, aggregated, windowed, blend_context))
+                },
+            )
             .try_collect::<Vec<_>>()
             .await
             .map(Vector::from)?
-            .sort_by(|(values_a, _, _), (values_b, _, _)| {
-                let pairs = values_a
-                    .iter()
-                    .map(|(a, _)| a)
-                    .zip(values_b.iter())
-                    .map(|(a, (b, asc))| (a, b, asc.unwrap_or(true)));
-
-                for (value_a, value_b, asc) in pairs {
-                    let apply_asc = |ord: Ordering| if asc { ord } else { ord.reverse() };
-
-                    match (value_a, value_b) {
-                        (Value::Null, Value::Null) => {}
-                        (Value::Null, _) => {
-                            return apply_asc(Ordering::Greater);
-                        }
-                        (_, Value::Null) => {
-                            return apply_asc(Ordering::Less);
-                        }
-                        _ => {}
-                    };
-
-                    match value_a.partial_cmp(value_b) {
-                        Some(ord) if ord != Ordering::Equal => {
-                            return apply_asc(ord);
-                        }
-                        _ => {}
-                    }
+            .sort_by(|(values_a, ..), (values_b, ..)| compare(values_a, values_b))
+            .into_iter();
+
+        let distinct_on = self.distinct_on;
+        let mut prev_keys: Option<Vec<(Value, Order)>> = None;
+        let rows = rows
+            .filter(move |(values, ..)| {
+                if distinct_on == 0 {
+                    return true;
                 }
 
-                Ordering::Equal
+                let keys = &values[..distinct_on];
+                let is_first = match &prev_keys {
+                    Some(prev_keys) => !prev_keys
+                        .iter()
+                        .zip(keys.iter())
+                        .all(|((prev, _), (key, _))| is_same(prev, key)),
+                    None => true,
+                };
+                prev_keys = Some(keys.to_vec());
+
+                is_first
             })
-            .into_iter()
-            .map(|(_, aggregated, blend_context)| Ok((aggregated, blend_context)));
+            .map(|(_, aggregated, windowed, blend_context)| {
+                Ok((aggregated, windowed, blend_context))
+            });
 
         Ok(Box::pin(stream::iter(rows)))
     }
 }
+
+/// Converts a key into the form it is compared in, text under `NOCASE` is compared in lowercase.
+pub fn collate(value: Value, collation: Option<Collation>) -> Value {
+    match (value, collation) {
+        (Value::Str(v), Some(Collation::NoCase)) => Value::Str(v.to_lowercase()),
+        (value, _) => value,
+    }
+}
+
+/// Orders rows by their values from the first column on, as `ORDER BY` every column would.
+pub fn sort_rows(rows: Vec<Row>) -> Vec<Row> {
+    Vector::from(rows)
+        .sort_by(|Row(values_a), Row(values_b)| {
+            values_a
+                .iter()
+                .zip(values_b.iter())
+                .map(|(value_a, value_b)| nulls::compare_value(value_a, value_b, Order::default()))
+                .find(|ord| *ord != Ordering::Equal)
+                .unwrap_or(Ordering::Equal)
+        })
+        .into()
+}
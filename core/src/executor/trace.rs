@@ -0,0 +1,42 @@
+use {
+    crate::result::Result,
+    futures::stream::{self, Stream, StreamExt},
+    std::task::Poll,
+    tracing::Span,
+};
+
+/// Closes the span of an operator once its stream is dropped, with the number of rows it produced.
+struct Operator {
+    span: Span,
+    rows: u64,
+}
+
+impl Drop for Operator {
+    fn drop(&mut self) {
+        let rows = self.rows;
+
+        self.span.record("rows", &rows);
+        tracing::debug!(parent: &self.span, rows, "operator finished");
+    }
+}
+
+/// Enters `span` on every poll of `rows`, so the spans of the operators it pulls rows from
+/// are nested in it.
+pub fn traced<'a, T: 'a>(
+    span: Span,
+    rows: impl Stream<Item = Result<T>> + 'a,
+) -> impl Stream<Item = Result<T>> + 'a {
+    let mut operator = Operator { span, rows: 0 };
+    let mut rows = Box::pin(rows);
+
+    stream::poll_fn(move |cx| {
+        let _entered = operator.span.enter();
+        let row = rows.poll_next_unpin(cx);
+
+        if let Poll::Ready(Some(Ok(_))) = row {
+            operator.rows += 1;
+        }
+
+        row
+    })
+}
@@ -0,0 +1,386 @@
+use {
+    super::{
+        evaluate_stateless,
+        session::{self, Session},
+    },
+    crate::{
+        ast::{DataType, Expr, FunctionArg, ObjectName},
+        data::{get_name, Row, SchemaFunction, Value},
+        result::{MutResult, Result, TrySelf},
+        store::{GStore, GStoreMut},
+    },
+    serde::Serialize,
+    std::{
+        cell::Cell,
+        collections::HashMap,
+        fmt::Debug,
+        panic::{catch_unwind, AssertUnwindSafe},
+        sync::Arc,
+    },
+    thiserror::Error as ThisError,
+};
+
+#[derive(ThisError, Serialize, Debug, PartialEq)]
+pub enum UdfError {
+    #[error("function not registered: {0}")]
+    FunctionNotRegistered(String),
+
+    #[error("function already exists: {0}")]
+    FunctionAlreadyExists(String),
+
+    #[error("function {0} exceeded the limit of nested calls")]
+    RecursionLimitExceeded(String),
+
+    #[error("function {name} failed: {message}")]
+    FunctionFailed { name: String, message: String },
+
+    #[error("function {0} panicked")]
+    FunctionPanicked(String),
+
+    #[error("function {name} returned {value:?}, which is not of its return type {data_type}")]
+    ReturnTypeMismatch {
+        name: String,
+        data_type: DataType,
+        value: Value,
+    },
+}
+
+/// Nested calls of functions created by `CREATE FUNCTION` allowed in a single call.
+const MAX_DEPTH: usize = 32;
+
+type ScalarBody = dyn Fn(Vec<Value>) -> std::result::Result<Value, String> + Send + Sync;
+
+/// Callbacks of an aggregate function registered by `Session::register_aggregate`.
+///
+/// The non-NULL values of a group are folded by `accumulate` into a state starting from `init`,
+/// which `finalize` turns into the value of the aggregate. An `Err` returned from any of them
+/// fails the statement with its message.
+pub trait UserAggregate {
+    /// State of a group which has not accumulated any value.
+    fn init(&self) -> Value;
+
+    /// Folds `value` into `state`.
+    fn accumulate(&self, state: Value, value: Value) -> std::result::Result<Value, String>;
+
+    /// Combines two partial states of the same group into one.
+    fn merge(&self, state: Value, other: Value) -> std::result::Result<Value, String>;
+
+    /// Value of the aggregate for the final state of a group.
+    fn finalize(&self, state: Value) -> std::result::Result<Value, String>;
+}
+
+/// What an aggregate registered by `Session::register_aggregate` returns for a group with no non-NULL
+/// value to accumulate.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum EmptyGroup {
+    /// NULL, as the built-in aggregates other than `COUNT` do
+    Null,
+    /// The finalized value of the `init` state
+    Finalize,
+}
+
+#[derive(Clone)]
+enum Body {
+    Native(Arc<ScalarBody>),
+    /// Expression of a function created by `CREATE FUNCTION`, over its parameters.
+    Sql {
+        params: Arc<[String]>,
+        expr: Arc<Expr>,
+    },
+}
+
+/// Scalar function registered by `Session::register_function` or created by `CREATE FUNCTION`.
+#[derive(Clone)]
+pub(crate) struct ScalarFunction {
+    pub arg_types: Vec<DataType>,
+    pub return_type: DataType,
+    body: Body,
+}
+
+/// Aggregate function registered by the user, see `Session::register_aggregate`.
+#[derive(Clone)]
+struct AggregateFunction {
+    arg_type: DataType,
+    return_type: DataType,
+    empty: EmptyGroup,
+    body: Arc<dyn UserAggregate + Send + Sync>,
+}
+
+impl From<&SchemaFunction> for ScalarFunction {
+    fn from(function: &SchemaFunction) -> Self {
+        let SchemaFunction {
+            args,
+            return_type,
+            body,
+            ..
+        } = function;
+        let (params, arg_types): (Vec<_>, _) = args
+            .iter()
+            .map(|FunctionArg { name, data_type }| (name.clone(), data_type.clone()))
+            .unzip();
+
+        Self {
+            arg_types,
+            return_type: return_type.clone(),
+            body: Body::Sql {
+                params: params.into(),
+                expr: Arc::new(body.clone()),
+            },
+        }
+    }
+}
+
+/// Functions registered to a session, by their uppercase names, see `Session`.
+#[derive(Default)]
+pub struct Functions {
+    scalars: HashMap<String, ScalarFunction>,
+    aggregates: HashMap<String, AggregateFunction>,
+    /// Functions of `CREATE FUNCTION` the storage held when they were last loaded, see
+    /// `load_functions`
+    created: HashMap<String, ScalarFunction>,
+}
+
+impl Functions {
+    pub(crate) fn register_function<F>(
+        &mut self,
+        name: &str,
+        arg_types: Vec<DataType>,
+        return_type: DataType,
+        body: F,
+    ) where
+        F: Fn(Vec<Value>) -> std::result::Result<Value, String> + Send + Sync + 'static,
+    {
+        let function = ScalarFunction {
+            arg_types,
+            return_type,
+            body: Body::Native(Arc::new(body)),
+        };
+
+        self.scalars.insert(name.to_uppercase(), function);
+    }
+
+    pub(crate) fn unregister_function(&mut self, name: &str) {
+        self.scalars.remove(&name.to_uppercase());
+    }
+
+    pub(crate) fn register_aggregate<A>(
+        &mut self,
+        name: &str,
+        arg_type: DataType,
+        return_type: DataType,
+        empty: EmptyGroup,
+        aggregate: A,
+    ) where
+        A: UserAggregate + Send + Sync + 'static,
+    {
+        let aggregate = AggregateFunction {
+            arg_type,
+            return_type,
+            empty,
+            body: Arc::new(aggregate),
+        };
+
+        self.aggregates.insert(name.to_uppercase(), aggregate);
+    }
+
+    pub(crate) fn unregister_aggregate(&mut self, name: &str) {
+        self.aggregates.remove(&name.to_uppercase());
+    }
+}
+
+thread_local! {
+    /// Calls of functions created by `CREATE FUNCTION` running at the moment.
+    static DEPTH: Cell<usize> = Cell::new(0);
+}
+
+fn with_functions<T>(f: impl FnOnce(&mut Functions) -> T) -> Result<T> {
+    session::current(|session| session.with_functions(f))
+}
+
+/// Reads the functions of `CREATE FUNCTION` from the storage into `session`, so the statements
+/// which follow translate and call the ones created since, by any session.
+pub async fn load_functions(storage: &dyn GStore, session: &Session) -> Result<()> {
+    let created = storage
+        .fetch_functions()
+        .await?
+        .iter()
+        .map(|function| (function.name.clone(), ScalarFunction::from(function)))
+        .collect();
+
+    session.with_functions(|functions| functions.created = created);
+
+    Ok(())
+}
+
+/// Stores the function of `CREATE FUNCTION`, which evaluates `body` with its parameters bound
+/// to the arguments of each call.
+pub async fn create_function<T: GStore + GStoreMut>(
+    storage: T,
+    name: &ObjectName,
+    args: &[FunctionArg],
+    return_type: &DataType,
+    body: &Expr,
+) -> MutResult<T, ()> {
+    let (storage, name) = get_name(name).try_self(storage)?;
+    let name = name.to_uppercase();
+    let (storage, function) = get_function(&name).try_self(storage)?;
+    if function.is_some() {
+        return Err((storage, UdfError::FunctionAlreadyExists(name).into()));
+    }
+
+    let function = SchemaFunction {
+        name,
+        args: args.to_vec(),
+        return_type: return_type.clone(),
+        body: body.clone(),
+    };
+
+    storage.insert_function(&function).await
+}
+
+pub async fn drop_function<T: GStore + GStoreMut>(
+    storage: T,
+    name: &ObjectName,
+    if_exists: bool,
+) -> MutResult<T, ()> {
+    let (storage, name) = get_name(name).try_self(storage)?;
+    let name = name.to_uppercase();
+    let (storage, created) =
+        with_functions(|functions| functions.created.contains_key(&name)).try_self(storage)?;
+
+    match created {
+        true => storage.delete_function(&name).await,
+        false if if_exists => Ok((storage, ())),
+        false => Err((storage, UdfError::FunctionNotRegistered(name).into())),
+    }
+}
+
+/// Registered or created function of the uppercase `name`, a registered one taking the place
+/// of a created one.
+pub(crate) fn get_function(name: &str) -> Result<Option<ScalarFunction>> {
+    with_functions(|functions| {
+        functions
+            .scalars
+            .get(name)
+            .or_else(|| functions.created.get(name))
+            .cloned()
+    })
+}
+
+/// Calls the registered function of the uppercase `name` with `args`.
+pub(crate) fn call_function(name: &str, args: Vec<Value>) -> Result<Value> {
+    let ScalarFunction {
+        arg_types,
+        return_type,
+        body,
+    } = get_function(name)?.ok_or_else(|| UdfError::FunctionNotRegistered(name.to_owned()))?;
+
+    let args = args
+        .iter()
+        .zip(arg_types.iter())
+        .map(|(arg, data_type)| match arg {
+            Value::Null => Ok(Value::Null),
+            arg => arg.cast(data_type),
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let value = match body {
+        Body::Native(body) => call(name, || body(args))?,
+        Body::Sql { params, expr } => call_sql(name, &params, &expr, args)?,
+    };
+
+    check_return_type(name, &return_type, value)
+}
+
+/// Evaluates the expression of a function created by `CREATE FUNCTION`, with `params` bound to
+/// `args`.
+fn call_sql(name: &str, params: &[String], expr: &Expr, args: Vec<Value>) -> Result<Value> {
+    let depth = DEPTH.with(Cell::get);
+    if depth >= MAX_DEPTH {
+        return Err(UdfError::RecursionLimitExceeded(name.to_owned()).into());
+    }
+
+    DEPTH.with(|current| current.set(depth + 1));
+    let row = Row(args);
+    let value = evaluate_stateless(Some((params, &row)), expr).and_then(Value::try_from);
+    DEPTH.with(|current| current.set(depth));
+
+    value
+}
+
+/// Whether an aggregate of the uppercase `name` is registered.
+pub(crate) fn is_aggregate(name: &str) -> Result<bool> {
+    with_functions(|functions| functions.aggregates.contains_key(name))
+}
+
+fn get_aggregate(name: &str) -> Result<AggregateFunction> {
+    with_functions(|functions| functions.aggregates.get(name).cloned())?
+        .ok_or_else(|| UdfError::FunctionNotRegistered(name.to_owned()).into())
+}
+
+/// Accumulates `value` into the state of a group of the registered aggregate `name`, where
+/// `None` is the state of a group which has not accumulated any value yet.
+pub(crate) fn accumulate_aggregate(
+    name: &str,
+    state: Option<Value>,
+    value: &Value,
+) -> Result<Option<Value>> {
+    if value.is_null() {
+        return Ok(state);
+    }
+
+    let AggregateFunction { arg_type, body, .. } = get_aggregate(name)?;
+    let value = value.cast(&arg_type)?;
+
+    call(name, || {
+        body.accumulate(state.unwrap_or_else(|| body.init()), value)
+    })
+    .map(Some)
+}
+
+/// Value of the registered aggregate `name` for the final state of a group.
+pub(crate) fn finalize_aggregate(name: &str, state: Option<Value>) -> Result<Value> {
+    let AggregateFunction {
+        return_type,
+        empty,
+        body,
+        ..
+    } = get_aggregate(name)?;
+
+    let state = match (state, empty) {
+        (Some(state), _) => state,
+        (None, EmptyGroup::Finalize) => call(name, || Ok(body.init()))?,
+        (None, EmptyGroup::Null) => return Ok(Value::Null),
+    };
+    let value = call(name, || body.finalize(state))?;
+
+    check_return_type(name, &return_type, value)
+}
+
+/// Runs a callback of the function `name`, turning its `Err` and panic into errors.
+fn call<F>(name: &str, callback: F) -> Result<Value>
+where
+    F: FnOnce() -> std::result::Result<Value, String>,
+{
+    match catch_unwind(AssertUnwindSafe(callback)) {
+        Ok(Ok(value)) => Ok(value),
+        Ok(Err(message)) => Err(UdfError::FunctionFailed {
+            name: name.to_owned(),
+            message,
+        }
+        .into()),
+        Err(_) => Err(UdfError::FunctionPanicked(name.to_owned()).into()),
+    }
+}
+
+fn check_return_type(name: &str, return_type: &DataType, value: Value) -> Result<Value> {
+    match value.validate_type(return_type) {
+        Ok(()) => Ok(value),
+        Err(_) => Err(UdfError::ReturnTypeMismatch {
+            name: name.to_owned(),
+            data_type: return_type.clone(),
+            value,
+        }
+        .into()),
+    }
+}
@@ -5,7 +5,7 @@ use {
     },
     crate::{
         ast::{Assignment, ColumnDef},
-        data::{schema::ColumnDefExt, Row, Value},
+        data::{schema::ColumnDefExt, Row, RowError, Value},
         result::Result,
         store::GStore,
     },
@@ -41,8 +41,12 @@ impl<'a> Update<'a> {
         for assignment in fields.iter() {
             let Assignment { id, .. } = assignment;
 
-            if column_defs.iter().all(|col_def| &col_def.name != id) {
-                return Err(UpdateError::ColumnNotFound(id.to_owned()).into());
+            match column_defs.iter().find(|col_def| &col_def.name == id) {
+                None => return Err(UpdateError::ColumnNotFound(id.to_owned()).into()),
+                Some(col_def) if col_def.get_generated().is_some() => {
+                    return Err(RowError::WriteToGeneratedColumn(id.to_owned()).into());
+                }
+                Some(_) => {}
             }
         }
 
@@ -54,9 +58,15 @@ impl<'a> Update<'a> {
         })
     }
 
-    async fn find(&self, row: &Row, column_def: &ColumnDef) -> Result<Option<Value>> {
+    async fn find(
+        &self,
+        row: &Row,
+        column_def: &ColumnDef,
+        outer: Option<Rc<FilterContext<'_>>>,
+    ) -> Result<Option<Value>> {
         let all_columns = Rc::from(self.all_columns());
-        let context = FilterContext::new(self.table_name, Rc::clone(&all_columns), Some(row), None);
+        let context =
+            FilterContext::new(self.table_name, Rc::clone(&all_columns), Some(row), outer);
         let context = Some(Rc::new(context));
 
         match self
@@ -70,7 +80,7 @@ impl<'a> Update<'a> {
                 let ColumnDef { data_type, .. } = column_def;
                 let nullable = column_def.is_nullable();
 
-                let value = match evaluate(self.storage, context, None, value).await? {
+                let value = match evaluate(self.storage, context, None, None, value).await? {
                     Evaluated::Literal(v) => Value::try_from_literal(data_type, &v)?,
                     Evaluated::Value(v) => {
                         v.validate_type(data_type)?;
@@ -86,6 +96,16 @@ impl<'a> Update<'a> {
     }
 
     pub async fn apply(&self, row: Row) -> Result<Row> {
+        self.apply_with_context(row, None).await
+    }
+
+    /// Applies the assignments to `row`, which can also read the values of `outer`, as `MERGE`
+    /// does with its source row.
+    pub async fn apply_with_context(
+        &self,
+        row: Row,
+        outer: Option<Rc<FilterContext<'_>>>,
+    ) -> Result<Row> {
         let Row(values) = &row;
 
         let values = values.clone().into_iter().enumerate().map(|(i, value)| {
@@ -98,9 +118,10 @@ impl<'a> Update<'a> {
         stream::iter(values)
             .and_then(|(col_def, value)| {
                 let row = &row;
+                let outer = outer.as_ref().map(Rc::clone);
 
                 async move {
-                    self.find(row, col_def)
+                    self.find(row, col_def, outer)
                         .await
                         .transpose()
                         .unwrap_or(Ok(value))
@@ -108,7 +129,8 @@ impl<'a> Update<'a> {
             })
             .try_collect::<Vec<_>>()
             .await
-            .map(Row)
+            .map(Row)?
+            .generate(self.column_defs)
     }
 
     pub fn all_columns(&self) -> Vec<String> {
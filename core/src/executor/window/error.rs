@@ -0,0 +1,13 @@
+use {serde::Serialize, std::fmt::Debug, thiserror::Error};
+
+#[derive(Error, Serialize, Debug, PartialEq)]
+pub enum WindowError {
+    #[error("window function requires positive integer value: {0}")]
+    RequiresPositiveIntegerValue(String),
+
+    #[error("window function offset must not be negative: {0}")]
+    NegativeOffset(String),
+
+    #[error("RANGE frame with offset requires exactly one ORDER BY expression")]
+    RangeOffsetRequiresSingleOrderBy,
+}
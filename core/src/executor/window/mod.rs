@@ -0,0 +1,429 @@
+mod error;
+
+use {
+    super::{
+        aggregate::AggrValue,
+        context::{AggregateContext, FilterContext},
+        evaluate::evaluate,
+        nulls::{compare, group_key, Order},
+        sort::collate,
+    },
+    crate::{
+        ast::{
+            self, Aggregate, Expr, OrderByExpr, SelectItem, WindowFrame, WindowFrameBound,
+            WindowFrameUnits, WindowFunction,
+        },
+        data::{Key, Value},
+        result::Result,
+        store::GStore,
+    },
+    futures::stream::{self, Stream, StreamExt, TryStreamExt},
+    itertools::Itertools,
+    std::{cmp::Ordering, collections::HashMap, iter, pin::Pin, rc::Rc},
+};
+
+pub use error::WindowError;
+
+pub struct Window<'a> {
+    storage: &'a dyn GStore,
+    filter_context: Option<Rc<FilterContext<'a>>>,
+    windows: Vec<&'a ast::Window>,
+}
+
+type Applied<'a> = dyn Stream<Item = Result<AggregateContext<'a>>> + 'a;
+
+/// Row index and `ORDER BY` values of a row in a partition
+type PartitionRow = (usize, Vec<(Value, Order)>);
+
+impl<'a> Window<'a> {
+    pub fn new(
+        storage: &'a dyn GStore,
+        fields: &'a [SelectItem],
+        order_by: &'a [OrderByExpr],
+        filter_context: Option<Rc<FilterContext<'a>>>,
+    ) -> Self {
+        let windows = fields
+            .iter()
+            .filter_map(|field| match field {
+                SelectItem::Expr { expr, .. } => Some(expr),
+                _ => None,
+            })
+            .chain(order_by.iter().map(|OrderByExpr { expr, .. }| expr))
+            .flat_map(find)
+            .unique()
+            .collect();
+
+        Self {
+            storage,
+            filter_context,
+            windows,
+        }
+    }
+
+    pub async fn apply(
+        &self,
+        rows: impl Stream<Item = Result<AggregateContext<'a>>> + 'a,
+    ) -> Result<Pin<Box<Applied<'a>>>> {
+        if self.windows.is_empty() {
+            return Ok(Box::pin(rows));
+        }
+
+        let rows = rows.try_collect::<Vec<_>>().await?;
+        let values = stream::iter(self.windows.iter().copied())
+            .then(|window| self.compute(&rows, window))
+            .try_collect::<Vec<_>>()
+            .await?;
+
+        let windows = self.windows.clone();
+        let rows = rows
+            .into_iter()
+            .enumerate()
+            .map(move |(index, aggregate_context)| {
+                let AggregateContext {
+                    aggregated, next, ..
+                } = aggregate_context;
+                let windowed = windows
+                    .iter()
+                    .copied()
+                    .zip(values.iter())
+                    .map(|(window, values)| (window, values[index].clone()))
+                    .collect();
+
+                Ok(AggregateContext {
+                    aggregated,
+                    windowed: Some(windowed),
+                    next,
+                })
+            });
+
+        Ok(Box::pin(stream::iter(rows)))
+    }
+
+    async fn compute(
+        &self,
+        rows: &[AggregateContext<'a>],
+        window: &'a ast::Window,
+    ) -> Result<Vec<Value>> {
+        let ast::Window { function, over } = window;
+
+        let mut partitions: HashMap<Vec<Key>, Vec<PartitionRow>> = HashMap::new();
+        for (index, row) in rows.iter().enumerate() {
+            let keys = stream::iter(over.partition_by.iter())
+                .then(|expr| self.eval(row, expr))
+                .and_then(|value| async move { group_key(value) })
+                .try_collect::<Vec<_>>()
+                .await?;
+            let values = stream::iter(over.order_by.iter())
+                .then(|order_by_expr| async move {
+                    let OrderByExpr {
+                        expr, collation, ..
+                    } = order_by_expr;
+
+                    self.eval(row, expr)
+                        .await
+                        .map(|value| (collate(value, *collation), Order::from(order_by_expr)))
+                })
+                .try_collect::<Vec<_>>()
+                .await?;
+
+            partitions.entry(keys).or_default().push((index, values));
+        }
+
+        let expr = match function {
+            WindowFunction::FirstValue(expr)
+            | WindowFunction::LastValue(expr)
+            | WindowFunction::NthValue { expr, .. }
+            | WindowFunction::Lag { expr, .. }
+            | WindowFunction::Lead { expr, .. } => Some(expr),
+            WindowFunction::Aggregate(aggregate) => aggregate.as_expr(),
+        };
+        let evaluated = match expr {
+            Some(expr) => {
+                stream::iter(rows.iter())
+                    .then(|row| self.eval(row, expr))
+                    .try_collect::<Vec<_>>()
+                    .await?
+            }
+            None => vec![Value::Null; rows.len()],
+        };
+
+        let window_frame = over.window_frame.as_ref();
+        let mut values = vec![Value::Null; rows.len()];
+        for mut partition in partitions.into_values() {
+            partition.sort_by(|(_, values_a), (_, values_b)| compare(values_a, values_b));
+
+            let peers = get_peers(&partition);
+            let value_at = |target: usize| {
+                let (target_index, _) = &partition[target];
+
+                evaluated[*target_index].clone()
+            };
+            // accumulated aggregate of the frame [start, end) of the previous row
+            let mut accumulated: Option<(usize, usize, AggrValue)> = None;
+
+            for (position, (index, _)) in partition.iter().enumerate() {
+                let row = &rows[*index];
+                let value = match function {
+                    WindowFunction::Lag {
+                        offset, default, ..
+                    }
+                    | WindowFunction::Lead {
+                        offset, default, ..
+                    } => {
+                        let offset = match offset {
+                            Some(offset) => self.get_offset(row, offset, function).await?,
+                            None => Some(1),
+                        };
+                        let target = offset.map(|offset| match function {
+                            WindowFunction::Lag { .. } => position.checked_sub(offset),
+                            _ => position
+                                .checked_add(offset)
+                                .filter(|target| *target < partition.len()),
+                        });
+
+                        match (target, default) {
+                            (None, _) => Value::Null,
+                            (Some(Some(target)), _) => value_at(target),
+                            (Some(None), Some(default)) => self.eval(row, default).await?,
+                            (Some(None), None) => Value::Null,
+                        }
+                    }
+                    WindowFunction::FirstValue(_)
+                    | WindowFunction::LastValue(_)
+                    | WindowFunction::NthValue { .. } => {
+                        let frame = get_frame(window_frame, &partition, &peers, position)?;
+                        let target = match (function, frame) {
+                            (_, None) => None,
+                            (WindowFunction::NthValue { n, .. }, Some((start, end))) => self
+                                .get_offset(row, n, function)
+                                .await?
+                                .map(|n| start + n - 1)
+                                .filter(|target| *target <= end),
+                            (WindowFunction::LastValue(_), Some((_, end))) => Some(end),
+                            (_, Some((start, _))) => Some(start),
+                        };
+
+                        target.map(value_at).unwrap_or(Value::Null)
+                    }
+                    WindowFunction::Aggregate(aggregate) => {
+                        match get_frame(window_frame, &partition, &peers, position)? {
+                            None if matches!(aggregate, Aggregate::Count(_)) => Value::I64(0),
+                            None => Value::Null,
+                            Some((start, end)) => {
+                                // frames only move forward, so the previous accumulation can be
+                                // reused as long as both rows share the same frame start
+                                let (next, aggr_value) = match accumulated.take() {
+                                    Some((prev_start, next, aggr_value))
+                                        if prev_start == start && next <= end + 1 =>
+                                    {
+                                        (next, Some(aggr_value))
+                                    }
+                                    _ => (start, None),
+                                };
+                                let aggr_value = (next..=end).map(value_at).try_fold(
+                                    aggr_value,
+                                    |aggr_value, value| match aggr_value {
+                                        Some(aggr_value) => aggr_value
+                                            .accumulate(&value)
+                                            .map(|updated| updated.or(Some(aggr_value))),
+                                        None => AggrValue::new(aggregate, &value).map(Some),
+                                    },
+                                )?;
+
+                                match aggr_value {
+                                    Some(aggr_value) => {
+                                        let value = aggr_value.clone().export()?;
+                                        accumulated = Some((start, end + 1, aggr_value));
+
+                                        value
+                                    }
+                                    None => Value::Null,
+                                }
+                            }
+                        }
+                    }
+                };
+
+                values[*index] = value;
+            }
+        }
+
+        Ok(values)
+    }
+
+    /// Evaluates the offset argument of `NTH_VALUE`, `LAG` and `LEAD`, which must be
+    /// a positive integer of any integer type or NULL.
+    async fn get_offset(
+        &self,
+        row: &AggregateContext<'a>,
+        expr: &'a Expr,
+        function: &WindowFunction,
+    ) -> Result<Option<usize>> {
+        let n = match self.eval(row, expr).await? {
+            Value::I8(n) => i128::from(n),
+            Value::I16(n) => i128::from(n),
+            Value::I32(n) => i128::from(n),
+            Value::I64(n) => i128::from(n),
+            Value::I128(n) => n,
+            Value::Null => return Ok(None),
+            _ => {
+                return Err(WindowError::RequiresPositiveIntegerValue(function.to_string()).into())
+            }
+        };
+
+        if n < 0 {
+            return Err(WindowError::NegativeOffset(function.to_string()).into());
+        }
+
+        usize::try_from(n)
+            .ok()
+            .filter(|n| *n > 0)
+            .map(Some)
+            .ok_or_else(|| WindowError::RequiresPositiveIntegerValue(function.to_string()).into())
+    }
+
+    async fn eval(&self, row: &AggregateContext<'a>, expr: &'a Expr) -> Result<Value> {
+        let filter_context = FilterContext::concat(
+            self.filter_context.as_ref().map(Rc::clone),
+            Some(Rc::clone(&row.next)),
+        );
+        let filter_context = Some(filter_context).map(Rc::new);
+        let aggregated = row.aggregated.clone().map(Rc::new);
+
+        evaluate(self.storage, filter_context, aggregated, None, expr)
+            .await?
+            .try_into()
+    }
+}
+
+/// Returns the first and the last position of the peer group each row belongs to.
+/// Rows are peers if they have the same `ORDER BY` values in the sorted partition.
+fn get_peers(partition: &[PartitionRow]) -> Vec<(usize, usize)> {
+    let mut peers = Vec::with_capacity(partition.len());
+    let mut start = 0;
+
+    for i in 1..=partition.len() {
+        let (_, values_start) = &partition[start];
+
+        if i == partition.len() || compare(&partition[i].1, values_start) != Ordering::Equal {
+            peers.extend(iter::repeat((start, i - 1)).take(i - start));
+            start = i;
+        }
+    }
+
+    peers
+}
+
+/// Returns the inclusive range of positions in the frame of the row at `position`,
+/// or `None` if the frame is empty.
+fn get_frame(
+    window_frame: Option<&WindowFrame>,
+    partition: &[PartitionRow],
+    peers: &[(usize, usize)],
+    position: usize,
+) -> Result<Option<(usize, usize)>> {
+    let (first_peer, last_peer) = peers[position];
+    let WindowFrame {
+        units,
+        start_bound,
+        end_bound,
+    } = match window_frame {
+        // RANGE BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW
+        None => return Ok(Some((0, last_peer))),
+        Some(window_frame) => window_frame,
+    };
+
+    let len = partition.len() as i64;
+    let offset = |bound: &WindowFrameBound, is_start: bool| -> Result<i64> {
+        let peer = if is_start { first_peer } else { last_peer };
+
+        match (units, bound) {
+            (_, WindowFrameBound::Preceding(None)) => Ok(0),
+            (_, WindowFrameBound::Following(None)) => Ok(len - 1),
+            (WindowFrameUnits::Rows, WindowFrameBound::CurrentRow) => Ok(position as i64),
+            (WindowFrameUnits::Rows, WindowFrameBound::Preceding(Some(n))) => {
+                Ok(position as i64 - (*n).min(len as u64) as i64)
+            }
+            (WindowFrameUnits::Rows, WindowFrameBound::Following(Some(n))) => {
+                Ok(position as i64 + (*n).min(len as u64) as i64)
+            }
+            (WindowFrameUnits::Range, WindowFrameBound::CurrentRow) => Ok(peer as i64),
+            (
+                WindowFrameUnits::Range,
+                WindowFrameBound::Preceding(Some(n)) | WindowFrameBound::Following(Some(n)),
+            ) => {
+                let (_, values) = &partition[position];
+                let (value, order) = match values.as_slice() {
+                    [(value, order)] => (value, *order),
+                    _ => return Err(WindowError::RangeOffsetRequiresSingleOrderBy.into()),
+                };
+
+                if value.is_null() {
+                    return Ok(peer as i64);
+                }
+
+                let n = Value::I64(i64::try_from(*n).unwrap_or(i64::MAX));
+                let preceding = matches!(bound, WindowFrameBound::Preceding(_));
+                let target = if preceding == order.asc {
+                    value.subtract(&n)?
+                } else {
+                    value.add(&n)?
+                };
+                let position = partition.partition_point(|(_, values)| {
+                    let (value, _) = &values[0];
+
+                    precedes(value, &target, order, !is_start)
+                });
+
+                Ok(if is_start {
+                    position as i64
+                } else {
+                    position as i64 - 1
+                })
+            }
+        }
+    };
+
+    let start = offset(start_bound, true)?.max(0);
+    let end = offset(
+        end_bound.as_ref().unwrap_or(&WindowFrameBound::CurrentRow),
+        false,
+    )?
+    .min(len - 1);
+
+    Ok((start <= end).then(|| (start as usize, end as usize)))
+}
+
+/// Returns true if `value` is sorted before `target` in a `RANGE` frame,
+/// or if it is equal to `target` and `inclusive` is set.
+fn precedes(value: &Value, target: &Value, order: Order, inclusive: bool) -> bool {
+    if value.is_null() {
+        return order.nulls_first;
+    }
+
+    match value.partial_cmp(target) {
+        Some(Ordering::Less) => order.asc,
+        Some(Ordering::Greater) => !order.asc,
+        Some(Ordering::Equal) => inclusive,
+        None => false,
+    }
+}
+
+fn find(expr: &Expr) -> Vec<&ast::Window> {
+    match expr {
+        Expr::Between {
+            expr, low, high, ..
+        } => [expr, low, high]
+            .into_iter()
+            .flat_map(|expr| find(expr))
+            .collect(),
+        Expr::BinaryOp { left, right, .. }
+        | Expr::AnyOp { left, right, .. }
+        | Expr::AllOp { left, right, .. } => find(left).into_iter().chain(find(right)).collect(),
+        Expr::UnaryOp { expr, .. } | Expr::Nested(expr) | Expr::Cast { expr, .. } => find(expr),
+        Expr::Function(function) => function.as_exprs().flat_map(find).collect(),
+        Expr::Tuple(exprs) => exprs.iter().flat_map(find).collect(),
+        Expr::Window(window) => vec![window.as_ref()],
+        _ => vec![],
+    }
+}
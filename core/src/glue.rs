@@ -1,41 +1,157 @@
 use {
     crate::{
-        ast::Statement,
-        executor::{execute, Payload},
-        parse_sql::parse,
-        plan::plan,
-        result::Result,
+        ast::{DataType, Statement},
+        data::{Row, Value},
+        executor::{
+            bulk_insert, deserialize_rows, execute_cancellable, export_csv, export_jsonl,
+            import_csv, import_jsonl, select_stream, Cancellation, CopyError, CsvOptions,
+            DeserializeError, EmptyGroup, ExecuteError, Metrics, Payload, SelectError, Session,
+            UserAggregate,
+        },
+        parse_sql::{parse_extended_with, ParseOptions},
+        plan::{check_parameters, fetch_schema_map, parameter_types, plan, ParameterError},
+        result::{MutResult, Result},
         store::{GStore, GStoreMut},
-        translate::translate,
+        translate::translate_extended,
     },
     futures::{
         executor::block_on,
-        stream::{self, StreamExt},
+        stream::{self, Stream, StreamExt},
         TryStreamExt,
     },
+    serde::de::DeserializeOwned,
+    std::io::Write,
 };
 
+#[cfg(feature = "metadata")]
+use crate::executor::fold_identifier_names;
+
+/// What `Glue::execute_all` does when a statement of the script fails.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OnError {
+    /// Skips the rest of the script.
+    Stop,
+    /// Goes on with the next statement.
+    Continue,
+}
+
+/// Result of a statement of a script run by `Glue::execute_all`.
+#[derive(Debug, PartialEq)]
+pub struct StatementResult {
+    /// Kind of the statement, see `Statement::kind`, or `None` if it could not be translated.
+    pub kind: Option<&'static str>,
+    pub result: Result<Payload>,
+}
+
 pub struct Glue<T: GStore + GStoreMut> {
     pub storage: Option<T>,
+    /// Rejects `DELETE` and `UPDATE` without a `WHERE` clause, `WHERE TRUE` still passes.
+    pub require_where: bool,
+    /// Cancelling it aborts the running statement, and every following one until it is reset.
+    pub cancellation: Cancellation,
+    /// Statistics of the last executed statement.
+    pub metrics: Metrics,
+    /// Settings and state its statements share, e.g. the seed of `RANDOM()`.
+    pub session: Session,
+    /// How SQL is parsed, e.g. whether unquoted identifiers are folded to lowercase.
+    pub parse_options: ParseOptions,
 }
 
 impl<T: GStore + GStoreMut> Glue<T> {
     pub fn new(storage: T) -> Self {
         Self {
             storage: Some(storage),
+            require_where: false,
+            cancellation: Cancellation::default(),
+            metrics: Metrics::default(),
+            session: Session::default(),
+            parse_options: ParseOptions::default(),
         }
     }
 
+    /// Registers `body` as the SQL function `name` for the statements of this Glue only, see
+    /// `Session::register_function`.
+    pub fn register_function<F>(
+        &self,
+        name: &str,
+        arg_types: Vec<DataType>,
+        return_type: DataType,
+        body: F,
+    ) where
+        F: Fn(Vec<Value>) -> std::result::Result<Value, String> + Send + Sync + 'static,
+    {
+        self.session
+            .register_function(name, arg_types, return_type, body);
+    }
+
+    pub fn unregister_function(&self, name: &str) {
+        self.session.unregister_function(name);
+    }
+
+    /// Registers `aggregate` as the SQL aggregate function `name` for the statements of this
+    /// Glue only, see `Session::register_aggregate`.
+    pub fn register_aggregate<A>(
+        &self,
+        name: &str,
+        arg_type: DataType,
+        return_type: DataType,
+        empty: EmptyGroup,
+        aggregate: A,
+    ) where
+        A: UserAggregate + Send + Sync + 'static,
+    {
+        self.session
+            .register_aggregate(name, arg_type, return_type, empty, aggregate);
+    }
+
+    pub fn unregister_aggregate(&self, name: &str) {
+        self.session.unregister_aggregate(name);
+    }
+
     pub async fn plan<Sql: AsRef<str>>(&self, sql: Sql) -> Result<Vec<Statement>> {
-        let parsed = parse(sql)?;
+        let parsed = parse_extended_with(sql, &self.parse_options)?;
         let storage = self.storage.as_ref().unwrap();
+        self.session.load_functions(storage).await?;
+
         stream::iter(parsed)
-            .map(|p| translate(&p))
+            .map(|p| self.session.enter(|| translate_extended(&p)))
             .then(|statement| async move { plan(storage, statement?).await })
             .try_collect()
             .await
     }
 
+    /// Checks `parameters` against the parameters `$1`, `$2`, ... of the single statement of
+    /// `sql` before anything runs, and returns them cast into the numeric types they are compared
+    /// with or assigned to.
+    ///
+    /// The type each parameter expects is inferred from where it is used, a value of another type
+    /// fails with `ParameterError::TypeMismatch` naming the parameter.
+    pub fn check_parameters<Sql: AsRef<str>>(
+        &self,
+        sql: Sql,
+        parameters: Vec<Value>,
+    ) -> Result<Vec<Value>> {
+        block_on(self.check_parameters_async(sql, parameters))
+    }
+
+    pub async fn check_parameters_async<Sql: AsRef<str>>(
+        &self,
+        sql: Sql,
+        parameters: Vec<Value>,
+    ) -> Result<Vec<Value>> {
+        let parsed = match parse_extended_with(sql, &self.parse_options)? {
+            parsed if parsed.len() == 1 => parsed.into_iter().next().unwrap(),
+            parsed => return Err(ParameterError::SingleStatementRequired(parsed.len()).into()),
+        };
+        let storage = self.storage.as_ref().unwrap();
+        self.session.load_functions(storage).await?;
+
+        let statement = self.session.enter(|| translate_extended(&parsed))?;
+        let schema_map = fetch_schema_map(storage, &statement).await?;
+
+        check_parameters(&parameter_types(&schema_map, &statement), parameters)
+    }
+
     pub fn execute_stmt(&mut self, statement: &Statement) -> Result<Payload> {
         block_on(self.execute_stmt_async(statement))
     }
@@ -45,14 +161,271 @@ impl<T: GStore + GStoreMut> Glue<T> {
         statements.iter().map(|s| self.execute_stmt(s)).collect()
     }
 
+    /// Runs the statements of `sql` in order and returns the result of each one run. Each
+    /// statement is planned right before it runs, so it sees the tables the previous ones made.
+    ///
+    /// Fails as a whole only if `sql` does not parse.
+    pub fn execute_all<Sql: AsRef<str>>(
+        &mut self,
+        sql: Sql,
+        on_error: OnError,
+    ) -> Result<Vec<StatementResult>> {
+        block_on(self.execute_all_async(sql, on_error))
+    }
+
+    pub async fn execute_all_async<Sql: AsRef<str>>(
+        &mut self,
+        sql: Sql,
+        on_error: OnError,
+    ) -> Result<Vec<StatementResult>> {
+        let mut results = Vec::new();
+
+        for parsed in parse_extended_with(sql, &self.parse_options)? {
+            let storage = self.storage.as_ref().unwrap();
+            let statement = match self.session.load_functions(storage).await {
+                Ok(()) => self.session.enter(|| translate_extended(&parsed)),
+                Err(error) => Err(error),
+            };
+            let kind = statement.as_ref().ok().map(Statement::kind);
+            let result = match statement {
+                Ok(statement) => match plan(storage, statement).await {
+                    Ok(statement) => self.execute_stmt_async(&statement).await,
+                    Err(error) => Err(error),
+                },
+                Err(error) => Err(error),
+            };
+
+            let failed = result.is_err();
+            results.push(StatementResult { kind, result });
+
+            if failed && on_error == OnError::Stop {
+                break;
+            }
+        }
+
+        Ok(results)
+    }
+
     pub async fn execute_stmt_async(&mut self, statement: &Statement) -> Result<Payload> {
+        if self.require_where
+            && matches!(
+                statement,
+                Statement::Delete {
+                    selection: None,
+                    ..
+                } | Statement::Update {
+                    selection: None,
+                    ..
+                }
+            )
+        {
+            return Err(ExecuteError::WhereClauseRequired.into());
+        }
+
+        let storage = self.storage.take().unwrap();
+        let result = execute_cancellable(
+            storage,
+            statement,
+            &self.session,
+            &self.cancellation,
+            &self.metrics,
+        )
+        .await;
+
+        self.restore(result)
+    }
+
+    /// Runs the query `statement` and returns its labels with the stream of its rows.
+    ///
+    /// Rows are read from the storage as the stream is polled, so a large result is not held in
+    /// memory unless the query needs all of its rows before giving the first one, e.g. to sort
+    /// them. The row counts of `metrics` grow as the stream is consumed.
+    pub async fn select_stream<'a>(
+        &'a self,
+        statement: &'a Statement,
+    ) -> Result<(Vec<String>, impl Stream<Item = Result<Row>> + 'a)> {
+        let query = match statement {
+            Statement::Query(query) => query,
+            _ => return Err(SelectError::QueryRequired.into()),
+        };
+
+        self.metrics.reset();
+        let storage = self.storage.as_ref().unwrap();
+        let selected = select_stream(
+            storage,
+            query,
+            self.cancellation.clone(),
+            self.metrics.clone(),
+        );
+        let (labels, rows) = self.session.scope(selected).await?;
+
+        Ok((labels, self.session.scope_stream(rows)))
+    }
+
+    /// Runs the query `sql` and deserializes each of its rows into `D`, see `deserialize_rows`.
+    pub fn query_as<D: DeserializeOwned, Sql: AsRef<str>>(&mut self, sql: Sql) -> Result<Vec<D>> {
+        block_on(self.query_as_async(sql))
+    }
+
+    pub async fn query_as_async<D: DeserializeOwned, Sql: AsRef<str>>(
+        &mut self,
+        sql: Sql,
+    ) -> Result<Vec<D>> {
+        let mut statements = self.plan(sql).await?;
+        let statement = match (statements.pop(), statements.is_empty()) {
+            (Some(statement @ Statement::Query(_)), true) => statement,
+            _ => return Err(DeserializeError::QueryRequired.into()),
+        };
+
+        match self.execute_stmt_async(&statement).await? {
+            Payload::Select { labels, rows } => deserialize_rows(&labels, rows),
+            _ => Err(DeserializeError::QueryRequired.into()),
+        }
+    }
+
+    /// Inserts rows given as one array per column, `column_arrays[i]` holding the values of
+    /// `columns[i]`. Values are cast into the column types and unlisted columns take their
+    /// default or NULL.
+    pub fn bulk_insert(
+        &mut self,
+        table_name: &str,
+        columns: &[&str],
+        column_arrays: Vec<Vec<Value>>,
+    ) -> Result<Payload> {
+        block_on(self.bulk_insert_async(table_name, columns, column_arrays))
+    }
+
+    pub async fn bulk_insert_async(
+        &mut self,
+        table_name: &str,
+        columns: &[&str],
+        column_arrays: Vec<Vec<Value>>,
+    ) -> Result<Payload> {
+        let storage = self.storage.take().unwrap();
+        let result = self
+            .session
+            .scope(bulk_insert(storage, table_name, columns, column_arrays))
+            .await;
+
+        self.restore(result)
+    }
+
+    /// Inserts CSV records into `table_name`, see `CsvOptions` for the accepted format.
+    pub fn import_csv(
+        &mut self,
+        table_name: &str,
+        csv: &str,
+        options: &CsvOptions,
+    ) -> Result<Payload> {
+        block_on(self.import_csv_async(table_name, csv, options))
+    }
+
+    pub async fn import_csv_async(
+        &mut self,
+        table_name: &str,
+        csv: &str,
+        options: &CsvOptions,
+    ) -> Result<Payload> {
+        let storage = self.storage.take().unwrap();
+        let result = self
+            .session
+            .scope(import_csv(storage, table_name, csv, options))
+            .await;
+
+        self.restore(result)
+    }
+
+    /// Runs a single `SELECT` statement and writes its result into `writer` as CSV,
+    /// returns the number of written rows.
+    pub fn export_csv<Sql: AsRef<str>>(
+        &self,
+        sql: Sql,
+        writer: impl Write,
+        options: &CsvOptions,
+    ) -> Result<usize> {
+        block_on(self.export_csv_async(sql, writer, options))
+    }
+
+    pub async fn export_csv_async<Sql: AsRef<str>>(
+        &self,
+        sql: Sql,
+        writer: impl Write,
+        options: &CsvOptions,
+    ) -> Result<usize> {
+        let statement = self.plan_single(sql).await?;
+        let storage = self.storage.as_ref().unwrap();
+
+        let exported = export_csv(storage, &statement, writer, options, &self.cancellation);
+
+        self.session.scope(exported).await
+    }
+
+    /// Inserts JSON lines into `table_name`, each line being an object keyed by column names.
+    /// Missing columns take their default or NULL, and unknown keys are rejected.
+    pub fn import_jsonl(&mut self, table_name: &str, jsonl: &str) -> Result<Payload> {
+        block_on(self.import_jsonl_async(table_name, jsonl))
+    }
+
+    pub async fn import_jsonl_async(&mut self, table_name: &str, jsonl: &str) -> Result<Payload> {
+        let storage = self.storage.take().unwrap();
+        let result = self
+            .session
+            .scope(import_jsonl(storage, table_name, jsonl))
+            .await;
+
+        self.restore(result)
+    }
+
+    /// Runs a single `SELECT` statement and writes each row into `writer` as a JSON object
+    /// keyed by the labels, returns the number of written rows.
+    pub fn export_jsonl<Sql: AsRef<str>>(&self, sql: Sql, writer: impl Write) -> Result<usize> {
+        block_on(self.export_jsonl_async(sql, writer))
+    }
+
+    pub async fn export_jsonl_async<Sql: AsRef<str>>(
+        &self,
+        sql: Sql,
+        writer: impl Write,
+    ) -> Result<usize> {
+        let statement = self.plan_single(sql).await?;
+        let storage = self.storage.as_ref().unwrap();
+
+        let exported = export_jsonl(storage, &statement, writer, &self.cancellation);
+
+        self.session.scope(exported).await
+    }
+
+    async fn plan_single<Sql: AsRef<str>>(&self, sql: Sql) -> Result<Statement> {
+        let mut statements = self.plan(sql).await?;
+
+        match statements.len() {
+            1 => Ok(statements.remove(0)),
+            _ => Err(CopyError::QueryRequired.into()),
+        }
+    }
+
+    /// Folds the names the storage keeps to lowercase, so a database made before
+    /// `parse_options.fold_identifiers` was turned on can be used with it, see
+    /// `executor::fold_identifier_names`.
+    #[cfg(feature = "metadata")]
+    pub fn fold_identifier_names(&mut self) -> Result<()> {
+        block_on(self.fold_identifier_names_async())
+    }
+
+    #[cfg(feature = "metadata")]
+    pub async fn fold_identifier_names_async(&mut self) -> Result<()> {
         let storage = self.storage.take().unwrap();
+        let result = self.session.scope(fold_identifier_names(storage)).await;
+
+        self.restore(result)
+    }
 
-        match execute(storage, statement).await {
-            Ok((storage, payload)) => {
+    fn restore<V>(&mut self, result: MutResult<T, V>) -> Result<V> {
+        match result {
+            Ok((storage, v)) => {
                 self.storage = Some(storage);
 
-                Ok(payload)
+                Ok(v)
             }
             Err((storage, error)) => {
                 self.storage = Some(storage);
@@ -22,9 +22,9 @@ pub mod prelude {
         ast::DataType,
         data::{Key, Value},
         executor::{execute, Payload},
-        glue::Glue,
-        parse_sql::parse,
+        glue::{Glue, OnError, StatementResult},
+        parse_sql::{parse, parse_extended, parse_extended_with, ParseOptions},
         plan::plan,
-        translate::translate,
+        translate::{translate, translate_extended},
     };
 }
@@ -2,23 +2,1004 @@ use {
     crate::result::{Error, Result},
     sqlparser::{
         ast::{
-            Expr as SqlExpr, OrderByExpr, Query as SqlQuery, SelectItem as SqlSelectItem,
-            Statement as SqlStatement,
+            Assignment as SqlAssignment, ColumnOption as SqlColumnOption,
+            ColumnOptionDef as SqlColumnOptionDef, DataType as SqlDataType, Expr as SqlExpr,
+            Function as SqlFunction, FunctionArg as SqlFunctionArg,
+            FunctionArgExpr as SqlFunctionArgExpr, Ident as SqlIdent, ObjectName as SqlObjectName,
+            OrderByExpr, Query as SqlQuery, SelectItem as SqlSelectItem, Statement as SqlStatement,
+            TableFactor as SqlTableFactor,
         },
-        dialect::GenericDialect,
-        parser::Parser,
-        tokenizer::Tokenizer,
+        dialect::{Dialect, GenericDialect},
+        keywords::Keyword,
+        parser::{Parser, ParserError},
+        tokenizer::{Token, Tokenizer, Word},
     },
+    std::ops::Range,
 };
 
 const DIALECT: GenericDialect = GenericDialect {};
 
+/// Tokenizes as `GenericDialect` does, and also reads `` `quoted` `` identifiers.
+///
+/// A double quoted identifier is read as a string literal where an expression is expected, so
+/// backticks quote the identifiers of expressions. Either way, a quoted identifier keeps its case
+/// and can hold keywords, spaces and other special characters. Unquoted identifiers are not
+/// folded either, they are matched as they are written, unless `ParseOptions` asks for it.
+///
+/// A word may also start with `$`, so the parameters `$1`, `$2`, ... of a statement are
+/// read as identifiers which `translate` turns into placeholders.
+#[derive(Debug)]
+struct TokenizerDialect;
+
+impl Dialect for TokenizerDialect {
+    fn is_identifier_start(&self, ch: char) -> bool {
+        ch == '$' || DIALECT.is_identifier_start(ch)
+    }
+
+    fn is_identifier_part(&self, ch: char) -> bool {
+        DIALECT.is_identifier_part(ch)
+    }
+
+    fn is_delimited_identifier_start(&self, ch: char) -> bool {
+        ch == '"' || ch == '`'
+    }
+}
+
+/// Quote style of the words `parse_extended` makes up for the syntax it rewrites. The tokenizer
+/// never quotes a word in brackets, so no SQL can be read as one of them.
+const EXTENSION_QUOTE: char = '[';
+
+/// Name of the `CHECK` constraint `GENERATED ALWAYS AS (expr)` is passed on as.
+const GENERATED_COLUMN: &str = "GENERATED ALWAYS";
+
+/// Name of the function `TABLESAMPLE BERNOULLI (percentage)` is passed on as in a table hint.
+const TABLE_SAMPLE: &str = "TABLESAMPLE BERNOULLI";
+
+/// Names of the custom types `ENUM('a', ...)` and `BIT(n)` are passed on as.
+const ENUM: &str = "ENUM";
+const BIT: &str = "BIT";
+
+/// Data type `sqlparser` does not parse, which `parse_extended` passes on as a custom type.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ExtendedDataType {
+    /// `ENUM('a', 'b', ...)`
+    Enum(Vec<String>),
+    /// `BIT(n)`
+    Bit(usize),
+}
+
+impl ExtendedDataType {
+    /// Data type of the custom type `name`, none unless `parse_extended` made it up.
+    pub fn from_name(name: &SqlObjectName) -> Option<Self> {
+        let (data_type, args) = name.0.split_first()?;
+        let args = args
+            .iter()
+            .map(|arg| (arg.quote_style == Some(EXTENSION_QUOTE)).then(|| arg.value.as_str()))
+            .collect::<Option<Vec<_>>>()?;
+
+        if is_extension(data_type, ENUM) {
+            let values = args.into_iter().map(ToOwned::to_owned).collect();
+
+            Some(ExtendedDataType::Enum(values))
+        } else if is_extension(data_type, BIT) {
+            match args.as_slice() {
+                [length] => length.parse().ok().map(ExtendedDataType::Bit),
+                _ => None,
+            }
+        } else {
+            None
+        }
+    }
+}
+
+/// `TABLESAMPLE BERNOULLI (percentage) [REPEATABLE (seed)]` following a table, which
+/// `parse_extended` passes on as a table hint.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExtendedTableSample<'a> {
+    pub percentage: &'a SqlExpr,
+    pub seed: Option<&'a SqlExpr>,
+}
+
+impl<'a> ExtendedTableSample<'a> {
+    /// Table sample of the table hint `hint`, none unless `parse_extended` made it up.
+    pub fn from_hint(hint: &'a SqlExpr) -> Option<Self> {
+        let (name, args) = match hint {
+            SqlExpr::Function(SqlFunction { name, args, .. }) => (name, args),
+            _ => return None,
+        };
+
+        match name.0.as_slice() {
+            [name] if is_extension(name, TABLE_SAMPLE) => {}
+            _ => return None,
+        }
+
+        let args = args
+            .iter()
+            .map(|arg| match arg {
+                SqlFunctionArg::Unnamed(SqlFunctionArgExpr::Expr(expr)) => Some(expr),
+                _ => None,
+            })
+            .collect::<Option<Vec<_>>>()?;
+
+        match args.as_slice() {
+            [percentage] => Some(ExtendedTableSample {
+                percentage,
+                seed: None,
+            }),
+            [percentage, seed] => Some(ExtendedTableSample {
+                percentage,
+                seed: Some(seed),
+            }),
+            _ => None,
+        }
+    }
+}
+
+/// Expression of the column option `GENERATED ALWAYS AS (expr)`, which `parse_extended` passes
+/// on as a `CHECK` constraint, none for any other column option.
+pub fn generated_column_expr(option_def: &SqlColumnOptionDef) -> Option<&SqlExpr> {
+    match option_def {
+        SqlColumnOptionDef {
+            name: Some(name),
+            option: SqlColumnOption::Check(expr),
+        } if is_extension(name, GENERATED_COLUMN) => Some(expr),
+        _ => None,
+    }
+}
+
+fn is_extension(ident: &SqlIdent, value: &str) -> bool {
+    ident.quote_style == Some(EXTENSION_QUOTE) && ident.value == value
+}
+
+/// Word `parse_extended` makes up, see `EXTENSION_QUOTE`.
+fn extension_word(value: &str) -> Token {
+    Token::Word(Word {
+        value: value.to_owned(),
+        quote_style: Some(EXTENSION_QUOTE),
+        keyword: Keyword::NoKeyword,
+    })
+}
+
+/// Options of `parse_extended_with`, the defaults are the ones `parse_extended` parses with.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ParseOptions {
+    /// Folds unquoted identifiers to lowercase and reads `"double quoted"` ones as identifiers
+    /// keeping their case, as PostgreSQL does, so `Item` and `ITEM` both name the table `item`.
+    ///
+    /// Names kept by a database made without it can be folded once by
+    /// `Glue::fold_identifier_names`.
+    pub fold_identifiers: bool,
+}
+
+/// Statement parsed by `sqlparser`, or one GlueSQL parses on its own as `sqlparser` does not
+/// support it.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParsedStatement {
+    Sql(SqlStatement),
+    /// REFRESH MATERIALIZED VIEW
+    RefreshMaterializedView(SqlObjectName),
+    /// CREATE SEQUENCE
+    CreateSequence(SqlObjectName),
+    /// DROP SEQUENCE
+    DropSequence {
+        if_exists: bool,
+        name: SqlObjectName,
+    },
+    /// CREATE FUNCTION
+    CreateFunction {
+        name: SqlObjectName,
+        args: Vec<(SqlIdent, SqlDataType)>,
+        return_type: SqlDataType,
+        /// Expression of the function, given as a string literal
+        body: SqlExpr,
+    },
+    /// DROP FUNCTION
+    DropFunction {
+        if_exists: bool,
+        name: SqlObjectName,
+    },
+    /// SAVEPOINT
+    Savepoint(SqlIdent),
+    /// ROLLBACK TO SAVEPOINT
+    RollbackToSavepoint(SqlIdent),
+    /// RELEASE SAVEPOINT
+    ReleaseSavepoint(SqlIdent),
+    /// INSERT, UPDATE or DELETE followed by RETURNING
+    Returning {
+        statement: SqlStatement,
+        projection: Vec<SqlSelectItem>,
+    },
+    /// MERGE
+    Merge {
+        table: SqlTableFactor,
+        source: SqlTableFactor,
+        on: SqlExpr,
+        clauses: Vec<ParsedMergeClause>,
+    },
+}
+
+/// `WHEN` clause of `MERGE`
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParsedMergeClause {
+    MatchedUpdate {
+        predicate: Option<SqlExpr>,
+        assignments: Vec<SqlAssignment>,
+    },
+    MatchedDelete {
+        predicate: Option<SqlExpr>,
+    },
+    NotMatched {
+        predicate: Option<SqlExpr>,
+        columns: Vec<SqlIdent>,
+        values: Vec<SqlExpr>,
+    },
+}
+
 pub fn parse<Sql: AsRef<str>>(sql: Sql) -> Result<Vec<SqlStatement>> {
-    Parser::parse_sql(&DIALECT, sql.as_ref()).map_err(|e| Error::Parser(format!("{:#?}", e)))
+    let tokens = tokenize(sql.as_ref())?;
+
+    parse_statements(tokens, |parser| parser.parse_statement())
+}
+
+/// Parses `sql` as `parse` does, and also the statements and clauses `sqlparser` does not
+/// support, which GlueSQL parses on its own: `SAVEPOINT`, `MERGE`, `RETURNING`, sequences,
+/// functions, `REFRESH MATERIALIZED VIEW` and the `ENUM`, `BIT`, `GENERATED ALWAYS AS` and
+/// `TABLESAMPLE` syntax. `translate_extended` translates its statements.
+pub fn parse_extended<Sql: AsRef<str>>(sql: Sql) -> Result<Vec<ParsedStatement>> {
+    parse_extended_with(sql, &ParseOptions::default())
+}
+
+/// Parses `sql` as `parse_extended` does, with `options`.
+pub fn parse_extended_with<Sql: AsRef<str>>(
+    sql: Sql,
+    options: &ParseOptions,
+) -> Result<Vec<ParsedStatement>> {
+    let tokens = fold_tokens(tokenize(sql.as_ref())?, options);
+    let tokens = rewrite_generated_columns(rewrite_data_types(tokens));
+    let tokens = rewrite_table_samples(tokens);
+
+    parse_statements(tokens, |parser| parse_extended_statement(parser, options))
+}
+
+fn tokenize(sql: &str) -> Result<Vec<Token>> {
+    Tokenizer::new(&TokenizerDialect, sql)
+        .tokenize()
+        .map_err(|e| Error::Parser(format!("{:#?}", e)))
+}
+
+/// Lowercases the unquoted words of `tokens` and quotes the double quoted ones in backticks, so
+/// they are read as identifiers in expressions too, if `options` folds identifiers.
+fn fold_tokens(tokens: Vec<Token>, options: &ParseOptions) -> Vec<Token> {
+    if !options.fold_identifiers {
+        return tokens;
+    }
+
+    tokens
+        .into_iter()
+        .map(|token| match token {
+            Token::Word(Word {
+                value,
+                quote_style: None,
+                keyword,
+            }) => Token::Word(Word {
+                value: value.to_lowercase(),
+                quote_style: None,
+                keyword,
+            }),
+            Token::Word(Word {
+                value,
+                quote_style: Some('"'),
+                keyword,
+            }) => Token::Word(Word {
+                value,
+                quote_style: Some('`'),
+                keyword,
+            }),
+            token => token,
+        })
+        .collect()
+}
+
+/// Parses the statements of `tokens` separated by semicolons, each by `parse_statement`.
+fn parse_statements<T>(
+    tokens: Vec<Token>,
+    parse_statement: impl Fn(&mut Parser) -> std::result::Result<T, ParserError>,
+) -> Result<Vec<T>> {
+    let mut parser = Parser::new(tokens, &DIALECT);
+    let mut statements = Vec::new();
+    let mut expecting_delimiter = false;
+
+    loop {
+        while parser.consume_token(&Token::SemiColon) {
+            expecting_delimiter = false;
+        }
+
+        if parser.peek_token() == Token::EOF {
+            return Ok(statements);
+        }
+
+        let statement = if expecting_delimiter {
+            parser.expected("end of statement", parser.peek_token())
+        } else {
+            parse_statement(&mut parser)
+        }
+        .map_err(|e| Error::Parser(format!("{:#?}", e)))?;
+
+        statements.push(statement);
+        expecting_delimiter = true;
+    }
+}
+
+fn parse_extended_statement(
+    parser: &mut Parser,
+    options: &ParseOptions,
+) -> std::result::Result<ParsedStatement, ParserError> {
+    match parser.peek_token() {
+        Token::Word(word) if word.value.eq_ignore_ascii_case("REFRESH") => {
+            parser.next_token();
+            parser
+                .expect_keywords(&[Keyword::MATERIALIZED, Keyword::VIEW])
+                .and_then(|_| parser.parse_object_name())
+                .map(ParsedStatement::RefreshMaterializedView)
+        }
+        Token::Word(word) if word.value.eq_ignore_ascii_case("MERGE") => {
+            parser.next_token();
+            parse_merge(parser)
+        }
+        Token::Word(word) if word.value.eq_ignore_ascii_case("SAVEPOINT") => {
+            parser.next_token();
+            parser.parse_identifier().map(ParsedStatement::Savepoint)
+        }
+        Token::Word(word) if word.value.eq_ignore_ascii_case("RELEASE") => {
+            parser.next_token();
+            parse_word(parser, "SAVEPOINT");
+            parser
+                .parse_identifier()
+                .map(ParsedStatement::ReleaseSavepoint)
+        }
+        Token::Word(word) if word.keyword == Keyword::ROLLBACK => {
+            parser.next_token();
+            if parse_word(parser, "TO") {
+                parse_word(parser, "SAVEPOINT");
+                parser
+                    .parse_identifier()
+                    .map(ParsedStatement::RollbackToSavepoint)
+            } else {
+                parser.prev_token();
+                parser.parse_statement().map(ParsedStatement::Sql)
+            }
+        }
+        Token::Word(word) if word.keyword == Keyword::CREATE => {
+            parser.next_token();
+            if parse_word(parser, "SEQUENCE") {
+                parser
+                    .parse_object_name()
+                    .map(ParsedStatement::CreateSequence)
+            } else if parse_word(parser, "FUNCTION") {
+                parse_create_function(parser, options)
+            } else {
+                parser.prev_token();
+                parser.parse_statement().map(ParsedStatement::Sql)
+            }
+        }
+        Token::Word(word) if word.keyword == Keyword::DROP => {
+            parser.next_token();
+            if parse_word(parser, "SEQUENCE") {
+                let if_exists = parser.parse_keywords(&[Keyword::IF, Keyword::EXISTS]);
+                parser
+                    .parse_object_name()
+                    .map(|name| ParsedStatement::DropSequence { if_exists, name })
+            } else if parse_word(parser, "FUNCTION") {
+                let if_exists = parser.parse_keywords(&[Keyword::IF, Keyword::EXISTS]);
+                parser
+                    .parse_object_name()
+                    .map(|name| ParsedStatement::DropFunction { if_exists, name })
+            } else {
+                parser.prev_token();
+                parser.parse_statement().map(ParsedStatement::Sql)
+            }
+        }
+        _ => parser
+            .parse_statement()
+            .and_then(|statement| parse_returning(parser, statement)),
+    }
+}
+
+/// Rewrites the data types `sqlparser` does not parse into custom types `ExtendedDataType`
+/// reads: `ENUM('a', 'b', ...)` into `[ENUM].[a].[b]` and `BIT(n)` into `[BIT].[n]`.
+///
+/// Only the data types of column definitions and the targets of `CAST(expr AS type)` and
+/// `expr::type` are rewritten, so a function or column named `bit` or `enum` is left as it is.
+fn rewrite_data_types(tokens: Vec<Token>) -> Vec<Token> {
+    let data_types = data_type_positions(&tokens);
+    let mut rewritten = Vec::with_capacity(tokens.len());
+    let mut i = 0;
+
+    while i < tokens.len() {
+        let extended = match &tokens[i] {
+            _ if !data_types.contains(&i) => None,
+            token if is_word(token, ENUM) => {
+                enum_values(&tokens[i + 1..]).map(|(values, len)| (ENUM, values, len))
+            }
+            token if is_word(token, BIT) => {
+                bit_length(&tokens[i + 1..]).map(|(length, len)| (BIT, vec![length], len))
+            }
+            _ => None,
+        };
+
+        match extended {
+            Some((name, args, len)) => {
+                rewritten.push(extension_word(name));
+                for arg in args {
+                    rewritten.push(Token::Period);
+                    rewritten.push(extension_word(&arg));
+                }
+
+                i += 1 + len;
+            }
+            None => {
+                rewritten.push(tokens[i].clone());
+                i += 1;
+            }
+        }
+    }
+
+    rewritten
+}
+
+/// Values of the parenthesized list of string literals `tokens` start with, and the number of
+/// tokens the list takes.
+fn enum_values(tokens: &[Token]) -> Option<(Vec<String>, usize)> {
+    let mut tokens = tokens
+        .iter()
+        .enumerate()
+        .filter(|(_, token)| !matches!(token, Token::Whitespace(_)));
+
+    if !matches!(tokens.next(), Some((_, Token::LParen))) {
+        return None;
+    }
+
+    let mut values = Vec::new();
+    loop {
+        match tokens.next()? {
+            (_, Token::SingleQuotedString(value)) => values.push(value.to_owned()),
+            _ => return None,
+        }
+
+        match tokens.next()? {
+            (_, Token::Comma) => continue,
+            (i, Token::RParen) => return Some((values, i + 1)),
+            _ => return None,
+        }
+    }
+}
+
+/// Length in the parenthesized `(n)` `tokens` start with, and the number of tokens it takes.
+fn bit_length(tokens: &[Token]) -> Option<(String, usize)> {
+    let mut tokens = tokens
+        .iter()
+        .enumerate()
+        .filter(|(_, token)| !matches!(token, Token::Whitespace(_)));
+
+    match (tokens.next()?, tokens.next()?, tokens.next()?) {
+        ((_, Token::LParen), (_, Token::Number(length, false)), (i, Token::RParen))
+            if length.parse::<usize>().is_ok() =>
+        {
+            Some((length.to_owned(), i + 1))
+        }
+        _ => None,
+    }
+}
+
+/// Rewrites the column option `GENERATED ALWAYS AS (expr) [STORED]`, which `sqlparser` does not
+/// parse, into `CONSTRAINT [GENERATED ALWAYS] CHECK (expr)` which `generated_column_expr` reads.
+/// Only the options of column definitions are rewritten.
+fn rewrite_generated_columns(tokens: Vec<Token>) -> Vec<Token> {
+    let column_defs = column_definitions(&tokens);
+    let mut rewritten = Vec::with_capacity(tokens.len());
+    let mut i = 0;
+
+    while i < tokens.len() {
+        let in_column_def = column_defs.iter().any(|range| range.contains(&i));
+        let len = match words_len(&tokens[i..], &["GENERATED", "ALWAYS", "AS"]) {
+            Some(len) if in_column_def => len,
+            _ => {
+                rewritten.push(tokens[i].clone());
+                i += 1;
+
+                continue;
+            }
+        };
+
+        rewritten.push(Token::make_keyword("CONSTRAINT"));
+        rewritten.push(extension_word(GENERATED_COLUMN));
+        rewritten.push(Token::make_keyword("CHECK"));
+        i += len;
+
+        let len = parenthesized_len(&tokens[i..]);
+        rewritten.extend_from_slice(&tokens[i..i + len]);
+        i += len;
+
+        // generated columns are always stored
+        i += words_len(&tokens[i..], &["STORED"]).unwrap_or(0);
+    }
+
+    rewritten
+}
+
+/// Positions of the tokens a data type starts at: the type of each column definition, the target
+/// of each `CAST(expr AS type)` and the type following each `::`.
+fn data_type_positions(tokens: &[Token]) -> Vec<usize> {
+    let mut positions: Vec<usize> = column_definitions(tokens)
+        .into_iter()
+        .map(|range| range.start)
+        .collect();
+
+    for (i, token) in tokens.iter().enumerate() {
+        let position = match token {
+            Token::DoubleColon => Some(i + 1 + whitespace_len(&tokens[i + 1..])),
+            _ if is_word(token, "CAST") => {
+                cast_target(&tokens[i + 1..]).map(|position| i + 1 + position)
+            }
+            _ => None,
+        };
+
+        positions.extend(position);
+    }
+
+    positions
+}
+
+/// Position of the data type following the `AS` of the parenthesized `(expr AS type)` `tokens`
+/// start with.
+fn cast_target(tokens: &[Token]) -> Option<usize> {
+    let start = whitespace_len(tokens);
+    if tokens.get(start) != Some(&Token::LParen) {
+        return None;
+    }
+
+    let mut depth = 0;
+    for (i, token) in tokens.iter().enumerate().skip(start) {
+        match token {
+            Token::LParen => depth += 1,
+            Token::RParen if depth == 1 => return None,
+            Token::RParen => depth -= 1,
+            _ if depth == 1 && is_word(token, "AS") => {
+                return Some(i + 1 + whitespace_len(&tokens[i + 1..]))
+            }
+            _ => {}
+        }
+    }
+
+    None
+}
+
+/// Token ranges of the column definitions of `CREATE TABLE name (...)` and
+/// `ALTER TABLE name ADD [COLUMN] ...`, each from the data type of the column to the end of its
+/// options.
+fn column_definitions(tokens: &[Token]) -> Vec<Range<usize>> {
+    let mut column_defs = Vec::new();
+    let mut start = 0;
+
+    for statement in tokens.split(|token| token == &Token::SemiColon) {
+        let elements = if words_len(statement, &["CREATE", "TABLE"]).is_some() {
+            table_elements(statement)
+        } else if let Some(len) = words_len(statement, &["ALTER", "TABLE"]) {
+            added_column(statement, len).into_iter().collect()
+        } else {
+            Vec::new()
+        };
+
+        column_defs.extend(elements.into_iter().filter_map(|element| {
+            let data_type = column_data_type(&statement[element.clone()])?;
+
+            Some(start + element.start + data_type..start + element.end)
+        }));
+
+        start += statement.len() + 1;
+    }
+
+    column_defs
+}
+
+/// Token ranges of the comma separated elements in the parentheses following the name of the
+/// table `CREATE TABLE` creates, none for `CREATE TABLE name AS query`.
+fn table_elements(statement: &[Token]) -> Vec<Range<usize>> {
+    let open = match statement.iter().position(|token| token == &Token::LParen) {
+        Some(open) => open,
+        None => return Vec::new(),
+    };
+    if statement[..open].iter().any(|token| is_word(token, "AS")) {
+        return Vec::new();
+    }
+
+    let mut elements = Vec::new();
+    let mut element_start = open + 1;
+    let mut depth = 0;
+
+    for (i, token) in statement.iter().enumerate().skip(open) {
+        match token {
+            Token::LParen => depth += 1,
+            Token::Comma if depth == 1 => {
+                elements.push(element_start..i);
+                element_start = i + 1;
+            }
+            Token::RParen if depth == 1 => {
+                elements.push(element_start..i);
+                break;
+            }
+            Token::RParen => depth -= 1,
+            _ => {}
+        }
+    }
+
+    elements
+}
+
+/// Token range of the column `ALTER TABLE name ADD [COLUMN] ...` adds, following the `len`
+/// tokens of `ALTER TABLE`.
+fn added_column(statement: &[Token], len: usize) -> Option<Range<usize>> {
+    let add = len
+        + statement[len..]
+            .iter()
+            .position(|token| is_word(token, "ADD"))?
+        + 1;
+    let add = add + words_len(&statement[add..], &["COLUMN"]).unwrap_or(0);
+
+    Some(add..statement.len())
+}
+
+/// Position of the data type following the name of the column definition `element`, none if
+/// it is a table constraint.
+fn column_data_type(element: &[Token]) -> Option<usize> {
+    let name = whitespace_len(element);
+    let constraint = ["CONSTRAINT", "PRIMARY", "UNIQUE", "FOREIGN", "CHECK"];
+
+    match element.get(name)? {
+        token if constraint.iter().any(|word| is_word(token, word)) => None,
+        Token::Word(_) => Some(name + 1 + whitespace_len(&element[name + 1..])),
+        _ => None,
+    }
+}
+
+/// Number of whitespace tokens `tokens` start with.
+fn whitespace_len(tokens: &[Token]) -> usize {
+    tokens
+        .iter()
+        .take_while(|token| matches!(token, Token::Whitespace(_)))
+        .count()
+}
+
+/// Whether `token` is the unquoted word `expected`.
+fn is_word(token: &Token, expected: &str) -> bool {
+    matches!(
+        token,
+        Token::Word(Word {
+            value,
+            quote_style: None,
+            ..
+        }) if value.eq_ignore_ascii_case(expected)
+    )
+}
+
+/// Rewrites `TABLESAMPLE BERNOULLI (percentage) [REPEATABLE (seed)]` following a table, which
+/// `sqlparser` does not parse, into the table hint `WITH ([TABLESAMPLE BERNOULLI]((percentage),
+/// (seed)))` which `ExtendedTableSample` reads. Only the tables of `FROM` and `JOIN` are
+/// followed by a sample.
+fn rewrite_table_samples(tokens: Vec<Token>) -> Vec<Token> {
+    let table_ends = table_ends(&tokens);
+    let mut rewritten = Vec::with_capacity(tokens.len());
+    let mut i = 0;
+
+    while i < tokens.len() {
+        let len = match words_len(&tokens[i..], &["TABLESAMPLE", "BERNOULLI"]) {
+            Some(len) if table_ends.contains(&i) => len,
+            _ => {
+                rewritten.push(tokens[i].clone());
+                i += 1;
+
+                continue;
+            }
+        };
+
+        rewritten.push(Token::make_keyword("WITH"));
+        rewritten.push(Token::LParen);
+        rewritten.push(extension_word(TABLE_SAMPLE));
+        rewritten.push(Token::LParen);
+        i += len;
+
+        let len = parenthesized_len(&tokens[i..]);
+        rewritten.extend_from_slice(&tokens[i..i + len]);
+        i += len;
+
+        if let Some(len) = words_len(&tokens[i..], &["REPEATABLE"]) {
+            rewritten.push(Token::Comma);
+            i += len;
+
+            let len = parenthesized_len(&tokens[i..]);
+            rewritten.extend_from_slice(&tokens[i..i + len]);
+            i += len;
+        }
+
+        rewritten.push(Token::RParen);
+        rewritten.push(Token::RParen);
+    }
+
+    rewritten
+}
+
+/// Positions following the tables named in `FROM` and `JOIN`, and their aliases if any.
+fn table_ends(tokens: &[Token]) -> Vec<usize> {
+    let clauses = [
+        "WHERE", "GROUP", "HAVING", "ORDER", "LIMIT", "OFFSET", "UNION",
+    ];
+    let mut ends = Vec::new();
+    // depths of the parentheses of the FROM clauses not ended yet
+    let mut from_depths = Vec::new();
+    let mut depth = 0;
+
+    for (i, token) in tokens.iter().enumerate() {
+        let in_from = from_depths.last() == Some(&depth);
+        let table_follows = match token {
+            Token::LParen => {
+                depth += 1;
+                false
+            }
+            Token::RParen => {
+                from_depths.retain(|from_depth| *from_depth < depth);
+                depth -= 1;
+                false
+            }
+            Token::SemiColon => {
+                from_depths.clear();
+                false
+            }
+            Token::Comma => in_from,
+            _ if is_word(token, "FROM") => {
+                from_depths.push(depth);
+                true
+            }
+            _ if in_from && clauses.iter().any(|clause| is_word(token, clause)) => {
+                from_depths.pop();
+                false
+            }
+            _ => is_word(token, "JOIN"),
+        };
+
+        if table_follows {
+            ends.extend(table_len(&tokens[i + 1..]).map(|len| i + 1 + len));
+        }
+    }
+
+    ends
+}
+
+/// Number of tokens of the table name `tokens` start with, and its alias if any, whitespace
+/// before them included.
+fn table_len(tokens: &[Token]) -> Option<usize> {
+    let word_len = |tokens: &[Token]| {
+        let start = whitespace_len(tokens);
+
+        matches!(tokens.get(start), Some(Token::Word(_))).then(|| start + 1)
+    };
+
+    let mut len = word_len(tokens)?;
+    while tokens.get(len) == Some(&Token::Period) {
+        len += 1 + word_len(&tokens[len + 1..])?;
+    }
+
+    let next = len + whitespace_len(&tokens[len..]);
+    let end = match tokens.get(next) {
+        Some(token) if is_word(token, "AS") => {
+            word_len(&tokens[next + 1..]).map(|alias_len| next + 1 + alias_len)
+        }
+        Some(token) if is_word(token, "TABLESAMPLE") => None,
+        Some(Token::Word(Word {
+            keyword: Keyword::NoKeyword,
+            ..
+        }))
+        | Some(Token::Word(Word {
+            quote_style: Some(_),
+            ..
+        })) => Some(next + 1),
+        _ => None,
+    };
+
+    Some(end.unwrap_or(len))
+}
+
+/// Number of tokens `tokens` start the unquoted `words` with, whitespace between them included.
+fn words_len(tokens: &[Token], words: &[&str]) -> Option<usize> {
+    let mut tokens = tokens
+        .iter()
+        .enumerate()
+        .filter(|(_, token)| !matches!(token, Token::Whitespace(_)));
+    let mut len = 0;
+
+    for expected in words {
+        match tokens.next()? {
+            (
+                i,
+                Token::Word(Word {
+                    value,
+                    quote_style: None,
+                    ..
+                }),
+            ) if value.eq_ignore_ascii_case(expected) => len = i + 1,
+            _ => return None,
+        }
+    }
+
+    Some(len)
+}
+
+/// Number of tokens up to the parenthesis which closes the one `tokens` start with, none but the
+/// leading whitespace if they do not start with one, so the parser reports it.
+fn parenthesized_len(tokens: &[Token]) -> usize {
+    let mut depth = 0;
+
+    for (i, token) in tokens.iter().enumerate() {
+        match token {
+            Token::Whitespace(_) => {}
+            Token::LParen => depth += 1,
+            Token::RParen if depth == 1 => return i + 1,
+            Token::RParen if depth > 1 => depth -= 1,
+            _ if depth == 0 => return i,
+            _ => {}
+        }
+    }
+
+    tokens.len()
+}
+
+/// Consumes the next token if it is the word `expected`, for the words `sqlparser` does not
+/// handle as keywords.
+fn parse_word(parser: &mut Parser, expected: &str) -> bool {
+    match parser.peek_token() {
+        Token::Word(word) if word.value.eq_ignore_ascii_case(expected) => {
+            parser.next_token();
+            true
+        }
+        _ => false,
+    }
+}
+
+/// Parses the `RETURNING` clause which can follow `INSERT`, `UPDATE` and `DELETE`.
+fn parse_returning(
+    parser: &mut Parser,
+    statement: SqlStatement,
+) -> std::result::Result<ParsedStatement, ParserError> {
+    let mutation = matches!(
+        statement,
+        SqlStatement::Insert { .. } | SqlStatement::Update { .. } | SqlStatement::Delete { .. }
+    );
+    if !mutation || !parse_word(parser, "RETURNING") {
+        return Ok(ParsedStatement::Sql(statement));
+    }
+
+    let projection = parser.parse_comma_separated(Parser::parse_select_item)?;
+
+    Ok(ParsedStatement::Returning {
+        statement,
+        projection,
+    })
+}
+
+/// Parses `name (arg type, ...) RETURNS type AS 'body'` following `CREATE FUNCTION`, and the
+/// expression of `body` with the same `options`.
+fn parse_create_function(
+    parser: &mut Parser,
+    options: &ParseOptions,
+) -> std::result::Result<ParsedStatement, ParserError> {
+    let name = parser.parse_object_name()?;
+
+    parser.expect_token(&Token::LParen)?;
+    let args = if parser.consume_token(&Token::RParen) {
+        Vec::new()
+    } else {
+        let args = parser.parse_comma_separated(|parser| {
+            Ok((parser.parse_identifier()?, parser.parse_data_type()?))
+        })?;
+        parser.expect_token(&Token::RParen)?;
+
+        args
+    };
+
+    if !parse_word(parser, "RETURNS") {
+        return parser.expected("RETURNS", parser.peek_token());
+    }
+    let return_type = parser.parse_data_type()?;
+
+    parser.expect_keyword(Keyword::AS)?;
+    let body = parser.parse_literal_string()?;
+    let tokens = Tokenizer::new(&TokenizerDialect, &body)
+        .tokenize()
+        .map_err(|e| ParserError::TokenizerError(format!("{:#?}", e)))?;
+    let body = Parser::new(fold_tokens(tokens, options), &DIALECT).parse_expr()?;
+
+    Ok(ParsedStatement::CreateFunction {
+        name,
+        args,
+        return_type,
+        body,
+    })
+}
+
+/// Parses `INTO table USING source ON expr WHEN ...` following `MERGE`.
+fn parse_merge(parser: &mut Parser) -> std::result::Result<ParsedStatement, ParserError> {
+    parser.expect_keyword(Keyword::INTO)?;
+    let table = parser.parse_table_factor()?;
+    parser.expect_keyword(Keyword::USING)?;
+    let source = parser.parse_table_factor()?;
+    parser.expect_keyword(Keyword::ON)?;
+    let on = parser.parse_expr()?;
+
+    let mut clauses = Vec::new();
+    while parser.parse_keyword(Keyword::WHEN) {
+        let matched = !parser.parse_keyword(Keyword::NOT);
+        if !parse_word(parser, "MATCHED") {
+            return parser.expected("MATCHED", parser.peek_token());
+        }
+
+        let predicate = if parser.parse_keyword(Keyword::AND) {
+            Some(parser.parse_expr()?)
+        } else {
+            None
+        };
+        parser.expect_keyword(Keyword::THEN)?;
+
+        let clause = if !matched {
+            parser.expect_keyword(Keyword::INSERT)?;
+            let columns = if parser.consume_token(&Token::LParen) {
+                let columns = parser.parse_comma_separated(Parser::parse_identifier)?;
+                parser.expect_token(&Token::RParen)?;
+
+                columns
+            } else {
+                Vec::new()
+            };
+
+            parser.expect_keyword(Keyword::VALUES)?;
+            parser.expect_token(&Token::LParen)?;
+            let values = parser.parse_comma_separated(Parser::parse_expr)?;
+            parser.expect_token(&Token::RParen)?;
+
+            ParsedMergeClause::NotMatched {
+                predicate,
+                columns,
+                values,
+            }
+        } else if parser.parse_keyword(Keyword::UPDATE) {
+            parser.expect_keyword(Keyword::SET)?;
+            let assignments = parser.parse_comma_separated(Parser::parse_assignment)?;
+
+            ParsedMergeClause::MatchedUpdate {
+                predicate,
+                assignments,
+            }
+        } else {
+            parser.expect_keyword(Keyword::DELETE)?;
+
+            ParsedMergeClause::MatchedDelete { predicate }
+        };
+
+        clauses.push(clause);
+    }
+
+    if clauses.is_empty() {
+        return parser.expected("WHEN", parser.peek_token());
+    }
+
+    Ok(ParsedStatement::Merge {
+        table,
+        source,
+        on,
+        clauses,
+    })
 }
 
 pub fn parse_query<Sql: AsRef<str>>(sql_expr: Sql) -> Result<SqlQuery> {
-    let tokens = Tokenizer::new(&DIALECT, sql_expr.as_ref())
+    let tokens = Tokenizer::new(&TokenizerDialect, sql_expr.as_ref())
         .tokenize()
         .map_err(|e| Error::Parser(format!("{:#?}", e)))?;
 
@@ -28,7 +1009,7 @@ pub fn parse_query<Sql: AsRef<str>>(sql_expr: Sql) -> Result<SqlQuery> {
 }
 
 pub fn parse_expr<Sql: AsRef<str>>(sql_expr: Sql) -> Result<SqlExpr> {
-    let tokens = Tokenizer::new(&DIALECT, sql_expr.as_ref())
+    let tokens = Tokenizer::new(&TokenizerDialect, sql_expr.as_ref())
         .tokenize()
         .map_err(|e| Error::Parser(format!("{:#?}", e)))?;
 
@@ -38,7 +1019,7 @@ pub fn parse_expr<Sql: AsRef<str>>(sql_expr: Sql) -> Result<SqlExpr> {
 }
 
 pub fn parse_comma_separated_exprs<Sql: AsRef<str>>(sql_exprs: Sql) -> Result<Vec<SqlExpr>> {
-    let tokens = Tokenizer::new(&DIALECT, sql_exprs.as_ref())
+    let tokens = Tokenizer::new(&TokenizerDialect, sql_exprs.as_ref())
         .tokenize()
         .map_err(|e| Error::Parser(format!("{:#?}", e)))?;
 
@@ -48,7 +1029,7 @@ pub fn parse_comma_separated_exprs<Sql: AsRef<str>>(sql_exprs: Sql) -> Result<Ve
 }
 
 pub fn parse_select_item<Sql: AsRef<str>>(sql_select_item: Sql) -> Result<SqlSelectItem> {
-    let tokens = Tokenizer::new(&DIALECT, sql_select_item.as_ref())
+    let tokens = Tokenizer::new(&TokenizerDialect, sql_select_item.as_ref())
         .tokenize()
         .map_err(|e| Error::Parser(format!("{:#?}", e)))?;
 
@@ -58,7 +1039,7 @@ pub fn parse_select_item<Sql: AsRef<str>>(sql_select_item: Sql) -> Result<SqlSel
 }
 
 pub fn parse_select_items<Sql: AsRef<str>>(sql_select_items: Sql) -> Result<Vec<SqlSelectItem>> {
-    let tokens = Tokenizer::new(&DIALECT, sql_select_items.as_ref())
+    let tokens = Tokenizer::new(&TokenizerDialect, sql_select_items.as_ref())
         .tokenize()
         .map_err(|e| Error::Parser(format!("{:#?}", e)))?;
 
@@ -68,7 +1049,7 @@ pub fn parse_select_items<Sql: AsRef<str>>(sql_select_items: Sql) -> Result<Vec<
 }
 
 pub fn parse_interval<Sql: AsRef<str>>(sql_interval: Sql) -> Result<SqlExpr> {
-    let tokens = Tokenizer::new(&DIALECT, sql_interval.as_ref())
+    let tokens = Tokenizer::new(&TokenizerDialect, sql_interval.as_ref())
         .tokenize()
         .map_err(|e| Error::Parser(format!("{:#?}", e)))?;
 
@@ -78,7 +1059,7 @@ pub fn parse_interval<Sql: AsRef<str>>(sql_interval: Sql) -> Result<SqlExpr> {
 }
 
 pub fn parse_order_by_expr<Sql: AsRef<str>>(sql_order_by_expr: Sql) -> Result<OrderByExpr> {
-    let tokens = Tokenizer::new(&DIALECT, sql_order_by_expr.as_ref())
+    let tokens = Tokenizer::new(&TokenizerDialect, sql_order_by_expr.as_ref())
         .tokenize()
         .map_err(|e| Error::Parser(format!("{:#?}", e)))?;
 
@@ -0,0 +1,433 @@
+use {
+    crate::{
+        ast::{
+            Cte, Expr, Join, JoinConstraint, JoinOperator, OrderByExpr, Query, RecursiveTerm,
+            Select, SelectItem, SetExpr, Statement, TableAlias, TableFactor, TableWithJoins,
+            Values,
+        },
+        data::{get_name, Schema},
+    },
+    std::{collections::HashMap, iter::once},
+};
+
+/// Resolves projection aliases referenced in `GROUP BY`, `HAVING` and `ORDER BY` into the
+/// expressions they name, e.g. `SELECT LENGTH(name) AS len ... ORDER BY len`.
+///
+/// A column of the `FROM` clause with the same name takes precedence over an alias, so aliases
+/// are only resolved when every column of the `FROM` clause is known in advance.
+///
+/// The queries of `INSERT`, `CREATE TABLE ... AS` and views are planned the same way.
+pub fn plan(schema_map: &HashMap<String, Schema>, statement: Statement) -> Statement {
+    let planner = Planner { schema_map };
+    let plan_query = |query: Box<Query>| Box::new(planner.query(&HashMap::new(), *query));
+
+    match statement {
+        Statement::Query(query) => Statement::Query(plan_query(query)),
+        Statement::Insert {
+            table_name,
+            columns,
+            source,
+            returning,
+        } => Statement::Insert {
+            table_name,
+            columns,
+            source: plan_query(source),
+            returning,
+        },
+        Statement::CreateTable {
+            if_not_exists,
+            name,
+            columns,
+            source,
+        } => Statement::CreateTable {
+            if_not_exists,
+            name,
+            columns,
+            source: source.map(plan_query),
+        },
+        Statement::CreateView {
+            name,
+            columns,
+            query,
+        } => Statement::CreateView {
+            name,
+            columns,
+            query: plan_query(query),
+        },
+        Statement::CreateMaterializedView {
+            name,
+            columns,
+            query,
+        } => Statement::CreateMaterializedView {
+            name,
+            columns,
+            query: plan_query(query),
+        },
+        _ => statement,
+    }
+}
+
+/// Columns of the common table expressions visible to a query, `None` if they are not known.
+type Ctes = HashMap<String, Option<Vec<String>>>;
+
+type Aliases = HashMap<String, Expr>;
+
+/// Column names a query returns, `None` if they depend on a wildcard.
+fn labels(query: &Query) -> Option<Vec<String>> {
+    match &query.body {
+        SetExpr::Select(select) => select
+            .projection
+            .iter()
+            .map(|select_item| match select_item {
+                SelectItem::Expr { label, .. } => Some(label.to_owned()),
+                SelectItem::QualifiedWildcard(_) | SelectItem::Wildcard => None,
+            })
+            .collect(),
+        SetExpr::Values(Values(values_list)) => {
+            let first_len = values_list.first().map(Vec::len).unwrap_or(0);
+
+            Some((1..=first_len).map(|i| format!("column{}", i)).collect())
+        }
+    }
+}
+
+struct Planner<'a> {
+    schema_map: &'a HashMap<String, Schema>,
+}
+
+impl<'a> Planner<'a> {
+    fn query(&self, ctes: &Ctes, query: Query) -> Query {
+        let Query {
+            with,
+            body,
+            limit,
+            offset,
+        } = query;
+
+        let mut ctes = ctes.clone();
+        let with = with
+            .into_iter()
+            .map(|cte| {
+                let Cte {
+                    alias,
+                    query,
+                    recursive,
+                } = cte;
+
+                let query = self.query(&ctes, query);
+                let columns = match alias.columns.as_slice() {
+                    [] => labels(&query),
+                    columns => Some(columns.to_vec()),
+                };
+                ctes.insert(alias.name.to_owned(), columns);
+
+                let recursive = recursive.map(|RecursiveTerm { all, query }| RecursiveTerm {
+                    all,
+                    query: self.query(&ctes, query),
+                });
+
+                Cte {
+                    alias,
+                    query,
+                    recursive,
+                }
+            })
+            .collect();
+
+        let body = match body {
+            SetExpr::Select(select) => {
+                let select = self.select(&ctes, *select);
+
+                SetExpr::Select(Box::new(select))
+            }
+            SetExpr::Values(_) => body,
+        };
+
+        Query {
+            with,
+            body,
+            limit,
+            offset,
+        }
+    }
+
+    fn select(&self, ctes: &Ctes, select: Select) -> Select {
+        let Select {
+            projection,
+            from,
+            selection,
+            group_by,
+            grouping_sets,
+            having,
+            order_by,
+            distinct_on,
+        } = select;
+
+        let no_aliases = Aliases::new();
+        let columns = self.columns(ctes, &from);
+        let from = self.table_with_joins(ctes, from);
+        let projection = projection
+            .into_iter()
+            .map(|select_item| match select_item {
+                SelectItem::Expr { expr, label } => SelectItem::Expr {
+                    expr: self.expr(ctes, &no_aliases, expr),
+                    label,
+                },
+                SelectItem::QualifiedWildcard(_) | SelectItem::Wildcard => select_item,
+            })
+            .collect::<Vec<_>>();
+
+        let aliases = match columns {
+            Some(columns) => collect_aliases(&projection, &columns),
+            None => Aliases::new(),
+        };
+
+        let selection = selection.map(|expr| self.expr(ctes, &no_aliases, expr));
+        let group_by = group_by
+            .into_iter()
+            .map(|expr| self.expr(ctes, &aliases, expr))
+            .collect();
+        let having = having.map(|expr| self.expr(ctes, &aliases, expr));
+        let order_by = order_by
+            .into_iter()
+            .map(|order_by_expr| OrderByExpr {
+                expr: self.expr(ctes, &aliases, order_by_expr.expr),
+                ..order_by_expr
+            })
+            .collect();
+        let distinct_on = distinct_on
+            .into_iter()
+            .map(|expr| self.expr(ctes, &no_aliases, expr))
+            .collect();
+
+        Select {
+            projection,
+            from,
+            selection,
+            group_by,
+            grouping_sets,
+            having,
+            order_by,
+            distinct_on,
+        }
+    }
+
+    /// Names of every column the `FROM` clause provides, `None` if any of them is not known.
+    fn columns(&self, ctes: &Ctes, from: &TableWithJoins) -> Option<Vec<String>> {
+        let TableWithJoins { relation, joins } = from;
+
+        let columns = once(relation)
+            .chain(joins.iter().map(|Join { relation, .. }| relation))
+            .map(|table_factor| match table_factor {
+                TableFactor::Table { name, .. } => {
+                    let name = get_name(name).ok()?;
+
+                    // common table expressions shadow the tables of the same name
+                    match ctes.get(name) {
+                        Some(columns) => columns.clone(),
+                        None => self.schema_map.get(name).map(|schema| {
+                            schema
+                                .column_names()
+                                .into_iter()
+                                .map(ToOwned::to_owned)
+                                .collect()
+                        }),
+                    }
+                }
+                TableFactor::Derived {
+                    subquery,
+                    alias: TableAlias { columns, .. },
+                    ..
+                } => match (&subquery.body, columns.as_slice()) {
+                    (SetExpr::Values(_), [_, ..]) => Some(columns.to_vec()),
+                    _ => labels(subquery),
+                },
+                TableFactor::Series {
+                    alias: TableAlias { name, columns },
+                    ..
+                } => Some(vec![columns.first().unwrap_or(name).to_owned()]),
+            })
+            .collect::<Option<Vec<Vec<String>>>>()?;
+
+        Some(columns.concat())
+    }
+
+    fn table_with_joins(&self, ctes: &Ctes, table_with_joins: TableWithJoins) -> TableWithJoins {
+        let TableWithJoins { relation, joins } = table_with_joins;
+
+        let no_aliases = Aliases::new();
+        let relation = self.table_factor(ctes, relation);
+        let joins = joins
+            .into_iter()
+            .map(|join| {
+                let Join {
+                    relation,
+                    join_operator,
+                    join_executor,
+                } = join;
+
+                let relation = self.table_factor(ctes, relation);
+                let join_operator = match join_operator {
+                    JoinOperator::Inner(JoinConstraint::On(expr)) => {
+                        JoinOperator::Inner(JoinConstraint::On(self.expr(ctes, &no_aliases, expr)))
+                    }
+                    JoinOperator::LeftOuter(JoinConstraint::On(expr)) => JoinOperator::LeftOuter(
+                        JoinConstraint::On(self.expr(ctes, &no_aliases, expr)),
+                    ),
+                    JoinOperator::Inner(JoinConstraint::None)
+                    | JoinOperator::LeftOuter(JoinConstraint::None) => join_operator,
+                };
+
+                Join {
+                    relation,
+                    join_operator,
+                    join_executor,
+                }
+            })
+            .collect();
+
+        TableWithJoins { relation, joins }
+    }
+
+    fn table_factor(&self, ctes: &Ctes, table_factor: TableFactor) -> TableFactor {
+        match table_factor {
+            TableFactor::Derived {
+                lateral,
+                subquery,
+                alias,
+            } => TableFactor::Derived {
+                lateral,
+                subquery: self.query(ctes, subquery),
+                alias,
+            },
+            TableFactor::Table { .. } | TableFactor::Series { .. } => table_factor,
+        }
+    }
+
+    fn expr(&self, ctes: &Ctes, aliases: &Aliases, expr: Expr) -> Expr {
+        let expr_box = |expr: Box<Expr>| Box::new(self.expr(ctes, aliases, *expr));
+        let subquery = |query: Box<Query>| Box::new(self.query(ctes, *query));
+
+        match expr {
+            Expr::Identifier(ident) => match aliases.get(&ident) {
+                Some(expr) => expr.clone(),
+                None => Expr::Identifier(ident),
+            },
+            Expr::BinaryOp { left, op, right } => Expr::BinaryOp {
+                left: expr_box(left),
+                op,
+                right: expr_box(right),
+            },
+            Expr::AnyOp { left, op, right } => Expr::AnyOp {
+                left: expr_box(left),
+                op,
+                right: expr_box(right),
+            },
+            Expr::AllOp { left, op, right } => Expr::AllOp {
+                left: expr_box(left),
+                op,
+                right: expr_box(right),
+            },
+            Expr::UnaryOp { op, expr } => Expr::UnaryOp {
+                op,
+                expr: expr_box(expr),
+            },
+            Expr::Nested(expr) => Expr::Nested(expr_box(expr)),
+            Expr::Tuple(exprs) => Expr::Tuple(
+                exprs
+                    .into_iter()
+                    .map(|expr| self.expr(ctes, aliases, expr))
+                    .collect(),
+            ),
+            Expr::IsNull(expr) => Expr::IsNull(expr_box(expr)),
+            Expr::IsNotNull(expr) => Expr::IsNotNull(expr_box(expr)),
+            Expr::Cast { expr, data_type } => Expr::Cast {
+                expr: expr_box(expr),
+                data_type,
+            },
+            Expr::Extract { field, expr } => Expr::Extract {
+                field,
+                expr: expr_box(expr),
+            },
+            Expr::InList {
+                expr,
+                list,
+                negated,
+            } => Expr::InList {
+                expr: expr_box(expr),
+                list: list
+                    .into_iter()
+                    .map(|expr| self.expr(ctes, aliases, expr))
+                    .collect(),
+                negated,
+            },
+            Expr::Between {
+                expr,
+                negated,
+                low,
+                high,
+            } => Expr::Between {
+                expr: expr_box(expr),
+                negated,
+                low: expr_box(low),
+                high: expr_box(high),
+            },
+            Expr::Case {
+                operand,
+                when_then,
+                else_result,
+            } => Expr::Case {
+                operand: operand.map(expr_box),
+                when_then: when_then
+                    .into_iter()
+                    .map(|(when, then)| {
+                        (
+                            self.expr(ctes, aliases, when),
+                            self.expr(ctes, aliases, then),
+                        )
+                    })
+                    .collect(),
+                else_result: else_result.map(expr_box),
+            },
+            Expr::Subquery(query) => Expr::Subquery(subquery(query)),
+            Expr::Exists(query) => Expr::Exists(subquery(query)),
+            Expr::InSubquery {
+                expr,
+                subquery: query,
+                negated,
+            } => Expr::InSubquery {
+                expr: expr_box(expr),
+                subquery: subquery(query),
+                negated,
+            },
+            _ => expr,
+        }
+    }
+}
+
+/// Aliases of the projection which do not collide with a column, or with each other.
+fn collect_aliases(projection: &[SelectItem], columns: &[String]) -> Aliases {
+    let mut aliases = Aliases::new();
+    let mut ambiguous = Vec::new();
+
+    for select_item in projection {
+        let (expr, label) = match select_item {
+            SelectItem::Expr { expr, label } => (expr, label),
+            SelectItem::QualifiedWildcard(_) | SelectItem::Wildcard => continue,
+        };
+
+        if columns.contains(label) {
+            continue;
+        }
+
+        if aliases.insert(label.to_owned(), expr.clone()).is_some() {
+            ambiguous.push(label);
+        }
+    }
+
+    for label in ambiguous {
+        aliases.remove(label);
+    }
+
+    aliases
+}
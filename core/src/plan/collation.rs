@@ -0,0 +1,746 @@
+use {
+    crate::{
+        ast::{
+            Aggregate, Assignment, AstLiteral, BinaryOperator, Collation, ColumnDef, CountArgExpr,
+            Cte, DataType, Expr, Function, GroupingSets, Join, JoinConstraint, JoinOperator,
+            ObjectName, OrderByExpr, Query, RecursiveTerm, Select, SelectItem, SetExpr, Statement,
+            TableAlias, TableFactor, TableWithJoins, Window, WindowFunction, WindowSpec,
+        },
+        data::{get_name, schema::ColumnDefExt, Schema},
+    },
+    bigdecimal::BigDecimal,
+    std::{
+        collections::{HashMap, HashSet},
+        iter::once,
+        mem,
+        rc::Rc,
+    },
+};
+
+/// Rewrites comparisons, `GROUP BY`, `ORDER BY` and `DISTINCT ON` which involve a `NOCASE`
+/// column to work on `LOWER(...)` of their operands, so the executor needs no collation support.
+/// The queries of `INSERT`, `CREATE TABLE ... AS`, views and common table expressions are
+/// planned the same way.
+///
+/// `ORDER BY` of an `ENUM` column is rewritten to order by the position of each value in the
+/// declaration of the type.
+pub fn plan(schema_map: &HashMap<String, Schema>, statement: Statement) -> Statement {
+    let planner = Planner { schema_map };
+    let plan_query = |query: Box<Query>| Box::new(planner.query(&Ctes::new(), None, *query));
+
+    match statement {
+        Statement::Query(query) => Statement::Query(plan_query(query)),
+        Statement::Insert {
+            table_name,
+            columns,
+            source,
+            returning,
+        } => Statement::Insert {
+            table_name,
+            columns,
+            source: plan_query(source),
+            returning,
+        },
+        Statement::CreateTable {
+            if_not_exists,
+            name,
+            columns,
+            source,
+        } => Statement::CreateTable {
+            if_not_exists,
+            name,
+            columns,
+            source: source.map(plan_query),
+        },
+        Statement::CreateView {
+            name,
+            columns,
+            query,
+        } => Statement::CreateView {
+            name,
+            columns,
+            query: plan_query(query),
+        },
+        Statement::CreateMaterializedView {
+            name,
+            columns,
+            query,
+        } => Statement::CreateMaterializedView {
+            name,
+            columns,
+            query: plan_query(query),
+        },
+        Statement::Update {
+            table_name,
+            assignments,
+            selection,
+            returning,
+        } => {
+            let scope = planner.table_scope(&table_name);
+            let assignments = assignments
+                .into_iter()
+                .map(|Assignment { id, value }| Assignment {
+                    id,
+                    value: planner.expr(&Ctes::new(), &scope, value),
+                })
+                .collect();
+            let selection = selection.map(|expr| planner.expr(&Ctes::new(), &scope, expr));
+
+            Statement::Update {
+                table_name,
+                assignments,
+                selection,
+                returning,
+            }
+        }
+        Statement::Delete {
+            table_name,
+            selection,
+            returning,
+        } => {
+            let selection = selection.map(|expr| {
+                let scope = planner.table_scope(&table_name);
+
+                planner.expr(&Ctes::new(), &scope, expr)
+            });
+
+            Statement::Delete {
+                table_name,
+                selection,
+                returning,
+            }
+        }
+        _ => statement,
+    }
+}
+
+/// Names of the common table expressions in scope, which shadow the tables of `schema_map`.
+type Ctes = HashSet<String>;
+
+/// Columns visible to an expression, the tables of its own `FROM` first and then the outer ones.
+struct Scope<'a> {
+    tables: Vec<(String, &'a [ColumnDef])>,
+    outer: Option<Rc<Scope<'a>>>,
+}
+
+impl<'a> Scope<'a> {
+    fn find(&self, alias: Option<&str>, column: &str) -> Option<&'a ColumnDef> {
+        self.tables
+            .iter()
+            .filter(|(name, _)| alias.map(|alias| alias == name).unwrap_or(true))
+            .find_map(|&(_, column_defs)| {
+                column_defs
+                    .iter()
+                    .find(|column_def| column_def.name == column)
+            })
+            .or_else(|| self.outer.as_ref()?.find(alias, column))
+    }
+
+    fn column_def(&self, expr: &Expr) -> Option<&'a ColumnDef> {
+        match expr {
+            Expr::Identifier(column) => self.find(None, column),
+            Expr::CompoundIdentifier(idents) => match idents.as_slice() {
+                [alias, column] => self.find(Some(alias.as_str()), column),
+                _ => None,
+            },
+            Expr::Nested(expr) => self.column_def(expr),
+            _ => None,
+        }
+    }
+
+    fn is_nocase(&self, expr: &Expr) -> bool {
+        self.column_def(expr).map(ColumnDefExt::get_collation) == Some(Collation::NoCase)
+    }
+
+    fn enum_values(&self, expr: &Expr) -> Option<&'a [String]> {
+        match &self.column_def(expr)?.data_type {
+            DataType::Enum(values) => Some(values),
+            _ => None,
+        }
+    }
+}
+
+fn lower(expr: Expr) -> Expr {
+    Expr::Function(Box::new(Function::Lower(expr)))
+}
+
+/// `CASE expr WHEN 'a' THEN 0 WHEN 'b' THEN 1 ... END`, NULL stays NULL.
+fn enum_position(expr: Expr, values: &[String]) -> Expr {
+    let when_then = values
+        .iter()
+        .enumerate()
+        .map(|(position, value)| {
+            let when = Expr::Literal(AstLiteral::QuotedString(value.to_owned()));
+            let then = Expr::Literal(AstLiteral::Number(BigDecimal::from(position as u64)));
+
+            (when, then)
+        })
+        .collect();
+
+    Expr::Case {
+        operand: Some(Box::new(expr)),
+        when_then,
+        else_result: None,
+    }
+}
+
+struct Planner<'a> {
+    schema_map: &'a HashMap<String, Schema>,
+}
+
+impl<'a> Planner<'a> {
+    fn table(
+        &self,
+        ctes: &Ctes,
+        name: &ObjectName,
+        alias: Option<&TableAlias>,
+    ) -> Option<(String, &'a [ColumnDef])> {
+        let name = get_name(name).ok()?;
+
+        if ctes.contains(name) {
+            return None;
+        }
+
+        let Schema {
+            table_name,
+            column_defs,
+            ..
+        } = self.schema_map.get(name)?;
+
+        match alias {
+            None => Some((table_name.to_owned(), column_defs)),
+            Some(TableAlias { name, columns }) if columns.is_empty() => {
+                Some((name.to_owned(), column_defs))
+            }
+            // renamed columns are not looked up
+            Some(_) => None,
+        }
+    }
+
+    fn table_scope(&self, table_name: &ObjectName) -> Rc<Scope<'a>> {
+        Rc::new(Scope {
+            tables: self
+                .table(&Ctes::new(), table_name, None)
+                .into_iter()
+                .collect(),
+            outer: None,
+        })
+    }
+
+    fn query(&self, ctes: &Ctes, outer: Option<Rc<Scope<'a>>>, query: Query) -> Query {
+        let Query {
+            with,
+            body,
+            limit,
+            offset,
+        } = query;
+
+        // each common table expression sees the ones before it, a recursive term sees itself too
+        let mut ctes = ctes.clone();
+        let with = with
+            .into_iter()
+            .map(|cte| {
+                let Cte {
+                    alias,
+                    query,
+                    recursive,
+                } = cte;
+
+                let query = self.query(&ctes, outer.as_ref().map(Rc::clone), query);
+                ctes.insert(alias.name.to_owned());
+                let recursive = recursive.map(|RecursiveTerm { all, query }| RecursiveTerm {
+                    all,
+                    query: self.query(&ctes, outer.as_ref().map(Rc::clone), query),
+                });
+
+                Cte {
+                    alias,
+                    query,
+                    recursive,
+                }
+            })
+            .collect();
+
+        let body = match body {
+            SetExpr::Select(select) => {
+                let select = self.select(&ctes, outer, *select);
+
+                SetExpr::Select(Box::new(select))
+            }
+            SetExpr::Values(_) => body,
+        };
+
+        Query {
+            with,
+            body,
+            limit,
+            offset,
+        }
+    }
+
+    fn select(&self, ctes: &Ctes, outer: Option<Rc<Scope<'a>>>, select: Select) -> Select {
+        let Select {
+            projection,
+            from,
+            selection,
+            group_by,
+            grouping_sets,
+            having,
+            order_by,
+            distinct_on,
+        } = select;
+
+        let scope = Rc::new(Scope {
+            tables: self.tables(ctes, &from),
+            outer: outer.as_ref().map(Rc::clone),
+        });
+        let from = self.table_with_joins(ctes, outer, &scope, from);
+
+        let projection = projection
+            .into_iter()
+            .map(|select_item| match select_item {
+                SelectItem::Expr { expr, label } => SelectItem::Expr {
+                    expr: self.expr(ctes, &scope, expr),
+                    label,
+                },
+                SelectItem::QualifiedWildcard(_) | SelectItem::Wildcard => select_item,
+            })
+            .collect();
+        let keys = |exprs: Vec<Expr>| {
+            exprs
+                .into_iter()
+                .map(|expr| self.key(ctes, &scope, expr))
+                .collect::<Vec<_>>()
+        };
+        let selection = selection.map(|expr| self.expr(ctes, &scope, expr));
+        let group_by = keys(group_by);
+        let grouping_sets = grouping_sets.map(|grouping_sets| match grouping_sets {
+            GroupingSets::Cube(exprs) => GroupingSets::Cube(keys(exprs)),
+            GroupingSets::Sets(sets) => GroupingSets::Sets(sets.into_iter().map(keys).collect()),
+        });
+        let having = having.map(|expr| self.expr(ctes, &scope, expr));
+        let order_by = order_by
+            .into_iter()
+            .map(|order_by_expr| self.order_by_expr(ctes, &scope, order_by_expr))
+            .collect();
+        let distinct_on = keys(distinct_on);
+
+        Select {
+            projection,
+            from,
+            selection,
+            group_by,
+            grouping_sets,
+            having,
+            order_by,
+            distinct_on,
+        }
+    }
+
+    fn tables(&self, ctes: &Ctes, from: &TableWithJoins) -> Vec<(String, &'a [ColumnDef])> {
+        let TableWithJoins { relation, joins } = from;
+
+        once(relation)
+            .chain(joins.iter().map(|Join { relation, .. }| relation))
+            .filter_map(|table_factor| match table_factor {
+                TableFactor::Table { name, alias, .. } => self.table(ctes, name, alias.as_ref()),
+                TableFactor::Derived { .. } | TableFactor::Series { .. } => None,
+            })
+            .collect()
+    }
+
+    fn table_with_joins(
+        &self,
+        ctes: &Ctes,
+        outer: Option<Rc<Scope<'a>>>,
+        scope: &Rc<Scope<'a>>,
+        table_with_joins: TableWithJoins,
+    ) -> TableWithJoins {
+        let TableWithJoins { relation, joins } = table_with_joins;
+
+        let relation = self.table_factor(ctes, outer.as_ref().map(Rc::clone), scope, relation);
+        let joins = joins
+            .into_iter()
+            .map(|join| {
+                let Join {
+                    relation,
+                    join_operator,
+                    join_executor,
+                } = join;
+
+                let relation =
+                    self.table_factor(ctes, outer.as_ref().map(Rc::clone), scope, relation);
+                let join_operator = match join_operator {
+                    JoinOperator::Inner(JoinConstraint::On(expr)) => {
+                        JoinOperator::Inner(JoinConstraint::On(self.expr(ctes, scope, expr)))
+                    }
+                    JoinOperator::LeftOuter(JoinConstraint::On(expr)) => {
+                        JoinOperator::LeftOuter(JoinConstraint::On(self.expr(ctes, scope, expr)))
+                    }
+                    JoinOperator::Inner(JoinConstraint::None)
+                    | JoinOperator::LeftOuter(JoinConstraint::None) => join_operator,
+                };
+
+                Join {
+                    relation,
+                    join_operator,
+                    join_executor,
+                }
+            })
+            .collect();
+
+        TableWithJoins { relation, joins }
+    }
+
+    fn table_factor(
+        &self,
+        ctes: &Ctes,
+        outer: Option<Rc<Scope<'a>>>,
+        scope: &Rc<Scope<'a>>,
+        table_factor: TableFactor,
+    ) -> TableFactor {
+        match table_factor {
+            TableFactor::Derived {
+                lateral,
+                subquery,
+                alias,
+            } => {
+                // LATERAL subqueries see the columns of the preceding relations
+                let outer = if lateral {
+                    Some(Rc::clone(scope))
+                } else {
+                    outer
+                };
+
+                TableFactor::Derived {
+                    lateral,
+                    subquery: self.query(ctes, outer, subquery),
+                    alias,
+                }
+            }
+            TableFactor::Table { .. } | TableFactor::Series { .. } => table_factor,
+        }
+    }
+
+    /// Plans an expression which groups or orders rows, a `NOCASE` column becomes its lowercase.
+    fn key(&self, ctes: &Ctes, scope: &Rc<Scope<'a>>, expr: Expr) -> Expr {
+        if scope.is_nocase(&expr) {
+            lower(expr)
+        } else {
+            self.expr(ctes, scope, expr)
+        }
+    }
+
+    fn order_by_expr(
+        &self,
+        ctes: &Ctes,
+        scope: &Rc<Scope<'a>>,
+        order_by_expr: OrderByExpr,
+    ) -> OrderByExpr {
+        let OrderByExpr {
+            expr,
+            asc,
+            nulls_first,
+            collation,
+        } = order_by_expr;
+
+        // an explicit COLLATE overrides the collation of the column
+        let expr = match (collation, scope.enum_values(&expr)) {
+            (Some(_), _) => self.expr(ctes, scope, expr),
+            (None, Some(values)) => enum_position(expr, values),
+            (None, None) => self.key(ctes, scope, expr),
+        };
+
+        OrderByExpr {
+            expr,
+            asc,
+            nulls_first,
+            collation,
+        }
+    }
+
+    /// Plans both operands of a comparison, which compare in lowercase if either is `NOCASE`.
+    fn compare(&self, ctes: &Ctes, scope: &Rc<Scope<'a>>, left: Expr, right: Expr) -> (Expr, Expr) {
+        match (left, right) {
+            (Expr::Tuple(left), Expr::Tuple(right)) if left.len() == right.len() => {
+                let (left, right) = left
+                    .into_iter()
+                    .zip(right)
+                    .map(|(left, right)| self.compare(ctes, scope, left, right))
+                    .unzip();
+
+                (Expr::Tuple(left), Expr::Tuple(right))
+            }
+            (left, right) => {
+                let nocase = scope.is_nocase(&left) || scope.is_nocase(&right);
+                let left = self.expr(ctes, scope, left);
+                let right = self.expr(ctes, scope, right);
+
+                if nocase {
+                    (lower(left), lower(right))
+                } else {
+                    (left, right)
+                }
+            }
+        }
+    }
+
+    fn aggregate(&self, ctes: &Ctes, scope: &Rc<Scope<'a>>, aggregate: Aggregate) -> Aggregate {
+        let expr = |expr| self.expr(ctes, scope, expr);
+
+        match aggregate {
+            Aggregate::Count(CountArgExpr::Wildcard) => aggregate,
+            Aggregate::Count(CountArgExpr::Expr(expr2)) => {
+                Aggregate::Count(CountArgExpr::Expr(expr(expr2)))
+            }
+            Aggregate::Sum(expr2) => Aggregate::Sum(expr(expr2)),
+            Aggregate::Max(expr2) => Aggregate::Max(expr(expr2)),
+            Aggregate::Min(expr2) => Aggregate::Min(expr(expr2)),
+            Aggregate::Avg(expr2) => Aggregate::Avg(expr(expr2)),
+            Aggregate::Variance(expr2) => Aggregate::Variance(expr(expr2)),
+            Aggregate::Stdev(expr2) => Aggregate::Stdev(expr(expr2)),
+            Aggregate::BoolAnd(expr2) => Aggregate::BoolAnd(expr(expr2)),
+            Aggregate::BoolOr(expr2) => Aggregate::BoolOr(expr(expr2)),
+            Aggregate::ArrayAgg {
+                expr: expr2,
+                order_by,
+            } => Aggregate::ArrayAgg {
+                expr: expr(expr2),
+                order_by: order_by
+                    .map(|order_by_expr| self.order_by_expr(ctes, scope, order_by_expr)),
+            },
+            Aggregate::JsonAgg(expr2) => Aggregate::JsonAgg(expr(expr2)),
+            Aggregate::JsonObjectAgg { key, value } => Aggregate::JsonObjectAgg {
+                key: expr(key),
+                value: expr(value),
+            },
+            Aggregate::Filter { aggregate, filter } => Aggregate::Filter {
+                aggregate: Box::new(self.aggregate(ctes, scope, *aggregate)),
+                filter: expr(filter),
+            },
+            // arguments of GROUPING name the columns of GROUP BY, so they are planned as keys too
+            Aggregate::Grouping(exprs) => Aggregate::Grouping(
+                exprs
+                    .into_iter()
+                    .map(|expr| self.key(ctes, scope, expr))
+                    .collect(),
+            ),
+            Aggregate::Custom { name, expr: expr2 } => Aggregate::Custom {
+                name,
+                expr: expr(expr2),
+            },
+        }
+    }
+
+    fn window(&self, ctes: &Ctes, scope: &Rc<Scope<'a>>, window: Window) -> Window {
+        let Window {
+            function,
+            over:
+                WindowSpec {
+                    partition_by,
+                    order_by,
+                    window_frame,
+                },
+        } = window;
+        let expr = |expr| self.expr(ctes, scope, expr);
+
+        let function = match function {
+            WindowFunction::FirstValue(expr2) => WindowFunction::FirstValue(expr(expr2)),
+            WindowFunction::LastValue(expr2) => WindowFunction::LastValue(expr(expr2)),
+            WindowFunction::NthValue { expr: expr2, n } => WindowFunction::NthValue {
+                expr: expr(expr2),
+                n: expr(n),
+            },
+            WindowFunction::Lag {
+                expr: expr2,
+                offset,
+                default,
+            } => WindowFunction::Lag {
+                expr: expr(expr2),
+                offset: offset.map(expr),
+                default: default.map(expr),
+            },
+            WindowFunction::Lead {
+                expr: expr2,
+                offset,
+                default,
+            } => WindowFunction::Lead {
+                expr: expr(expr2),
+                offset: offset.map(expr),
+                default: default.map(expr),
+            },
+            WindowFunction::Aggregate(aggregate) => {
+                WindowFunction::Aggregate(self.aggregate(ctes, scope, aggregate))
+            }
+        };
+        let partition_by = partition_by
+            .into_iter()
+            .map(|expr| self.key(ctes, scope, expr))
+            .collect();
+        let order_by = order_by
+            .into_iter()
+            .map(|order_by_expr| self.order_by_expr(ctes, scope, order_by_expr))
+            .collect();
+
+        Window {
+            function,
+            over: WindowSpec {
+                partition_by,
+                order_by,
+                window_frame,
+            },
+        }
+    }
+
+    fn expr(&self, ctes: &Ctes, scope: &Rc<Scope<'a>>, expr: Expr) -> Expr {
+        let expr_box = |expr: Box<Expr>| Box::new(self.expr(ctes, scope, *expr));
+        let subquery =
+            |query: Box<Query>| Box::new(self.query(ctes, Some(Rc::clone(scope)), *query));
+
+        match expr {
+            Expr::BinaryOp { left, op, right } => match op {
+                BinaryOperator::Eq
+                | BinaryOperator::NotEq
+                | BinaryOperator::Lt
+                | BinaryOperator::LtEq
+                | BinaryOperator::Gt
+                | BinaryOperator::GtEq
+                | BinaryOperator::IsDistinctFrom
+                | BinaryOperator::IsNotDistinctFrom => {
+                    let (left, right) = self.compare(ctes, scope, *left, *right);
+
+                    Expr::BinaryOp {
+                        left: Box::new(left),
+                        op,
+                        right: Box::new(right),
+                    }
+                }
+                _ => Expr::BinaryOp {
+                    left: expr_box(left),
+                    op,
+                    right: expr_box(right),
+                },
+            },
+            Expr::AnyOp { left, op, right } => Expr::AnyOp {
+                left: expr_box(left),
+                op,
+                right: expr_box(right),
+            },
+            Expr::AllOp { left, op, right } => Expr::AllOp {
+                left: expr_box(left),
+                op,
+                right: expr_box(right),
+            },
+            Expr::InList {
+                expr,
+                list,
+                negated,
+            } => {
+                let nocase =
+                    scope.is_nocase(&expr) || list.iter().any(|expr| scope.is_nocase(expr));
+                let collate = |expr| {
+                    let expr = self.expr(ctes, scope, expr);
+
+                    if nocase {
+                        lower(expr)
+                    } else {
+                        expr
+                    }
+                };
+
+                Expr::InList {
+                    expr: Box::new(collate(*expr)),
+                    list: list.into_iter().map(collate).collect(),
+                    negated,
+                }
+            }
+            Expr::Between {
+                expr,
+                negated,
+                low,
+                high,
+            } => {
+                let nocase = [&expr, &low, &high]
+                    .into_iter()
+                    .any(|expr| scope.is_nocase(expr));
+                let collate = |expr: Box<Expr>| {
+                    let expr = self.expr(ctes, scope, *expr);
+
+                    Box::new(if nocase { lower(expr) } else { expr })
+                };
+
+                Expr::Between {
+                    expr: collate(expr),
+                    negated,
+                    low: collate(low),
+                    high: collate(high),
+                }
+            }
+            Expr::Nested(expr) => Expr::Nested(expr_box(expr)),
+            Expr::UnaryOp { op, expr } => Expr::UnaryOp {
+                op,
+                expr: expr_box(expr),
+            },
+            Expr::Cast { expr, data_type } => Expr::Cast {
+                expr: expr_box(expr),
+                data_type,
+            },
+            Expr::Extract { field, expr } => Expr::Extract {
+                field,
+                expr: expr_box(expr),
+            },
+            Expr::IsNull(expr) => Expr::IsNull(expr_box(expr)),
+            Expr::IsNotNull(expr) => Expr::IsNotNull(expr_box(expr)),
+            Expr::Tuple(exprs) => Expr::Tuple(
+                exprs
+                    .into_iter()
+                    .map(|expr| self.expr(ctes, scope, expr))
+                    .collect(),
+            ),
+            Expr::Case {
+                operand,
+                when_then,
+                else_result,
+            } => Expr::Case {
+                operand: operand.map(expr_box),
+                when_then: when_then
+                    .into_iter()
+                    .map(|(when, then)| {
+                        (self.expr(ctes, scope, when), self.expr(ctes, scope, then))
+                    })
+                    .collect(),
+                else_result: else_result.map(expr_box),
+            },
+            Expr::Function(mut function) => {
+                for expr in function.as_exprs_mut() {
+                    // Expr has no cheap default, an empty tuple holds the place while planning
+                    let arg = mem::replace(expr, Expr::Tuple(Vec::new()));
+
+                    *expr = self.expr(ctes, scope, arg);
+                }
+
+                Expr::Function(function)
+            }
+            Expr::Aggregate(aggregate) => {
+                Expr::Aggregate(Box::new(self.aggregate(ctes, scope, *aggregate)))
+            }
+            Expr::Window(window) => Expr::Window(Box::new(self.window(ctes, scope, *window))),
+            Expr::Subquery(query) => Expr::Subquery(subquery(query)),
+            Expr::Exists(query) => Expr::Exists(subquery(query)),
+            Expr::InSubquery {
+                expr,
+                subquery: query,
+                negated,
+            } => Expr::InSubquery {
+                expr: expr_box(expr),
+                subquery: subquery(query),
+                negated,
+            },
+            Expr::Identifier(_)
+            | Expr::CompoundIdentifier(_)
+            | Expr::Literal(_)
+            | Expr::Placeholder(_)
+            | Expr::TypedString { .. } => expr,
+        }
+    }
+}
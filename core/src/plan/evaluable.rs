@@ -2,8 +2,8 @@ use {
     super::{context::Context, expr::PlanExpr},
     crate::{
         ast::{
-            Expr, Join, JoinConstraint, JoinOperator, Query, Select, SelectItem, SetExpr,
-            TableAlias, TableFactor, TableWithJoins, Values,
+            Expr, GroupingSets, Join, JoinConstraint, JoinOperator, Query, Select, SelectItem,
+            SetExpr, TableAlias, TableFactor, TableWithJoins, Values,
         },
         data::get_name,
     },
@@ -45,13 +45,18 @@ pub fn check_expr(context: Option<Rc<Context<'_>>>, expr: &Expr) -> bool {
     }
 }
 
-fn check_query(context: Option<Rc<Context<'_>>>, query: &Query) -> bool {
+pub fn check_query(context: Option<Rc<Context<'_>>>, query: &Query) -> bool {
     let Query {
+        with,
         body,
         limit,
         offset,
     } = query;
 
+    if !with.is_empty() {
+        return false;
+    }
+
     let body = match body {
         SetExpr::Select(select) => check_select(context.as_ref().map(Rc::clone), select),
         SetExpr::Values(Values(rows)) => rows
@@ -78,8 +83,10 @@ fn check_select(context: Option<Rc<Context<'_>>>, select: &Select) -> bool {
         from,
         selection,
         group_by,
+        grouping_sets,
         having,
         order_by,
+        ..
     } = select;
 
     if !projection
@@ -129,6 +136,7 @@ fn check_select(context: Option<Rc<Context<'_>>>, select: &Select) -> bool {
     selection
         .iter()
         .chain(group_by.iter())
+        .chain(grouping_sets.iter().flat_map(GroupingSets::columns))
         .chain(having.iter())
         .chain(order_by.iter().map(|order_by| &order_by.expr))
         .map(|expr| check_expr(context.as_ref().map(Rc::clone), expr))
@@ -148,7 +156,9 @@ fn check_table_factor(context: Option<Rc<Context<'_>>>, table_factor: &TableFact
                 .map(|TableAlias { name, .. }| name.clone())
                 .unwrap_or_else(|| name.clone())
         }
-        TableFactor::Derived { alias, .. } => alias.to_owned().name,
+        TableFactor::Derived { alias, .. } | TableFactor::Series { alias, .. } => {
+            alias.to_owned().name
+        }
     };
 
     context
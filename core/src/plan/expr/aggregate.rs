@@ -3,14 +3,21 @@ use crate::ast::{Aggregate, CountArgExpr, Expr};
 impl Aggregate {
     pub fn as_expr(&self) -> Option<&Expr> {
         match self {
-            Aggregate::Count(CountArgExpr::Wildcard) => None,
+            Aggregate::Count(CountArgExpr::Wildcard) | Aggregate::Grouping(_) => None,
             Aggregate::Count(CountArgExpr::Expr(expr))
             | Aggregate::Sum(expr)
             | Aggregate::Max(expr)
             | Aggregate::Min(expr)
             | Aggregate::Avg(expr)
             | Aggregate::Variance(expr)
-            | Aggregate::Stdev(expr) => Some(expr),
+            | Aggregate::Stdev(expr)
+            | Aggregate::BoolAnd(expr)
+            | Aggregate::BoolOr(expr)
+            | Aggregate::ArrayAgg { expr, .. }
+            | Aggregate::JsonAgg(expr)
+            | Aggregate::JsonObjectAgg { value: expr, .. }
+            | Aggregate::Custom { expr, .. } => Some(expr),
+            Aggregate::Filter { aggregate, .. } => aggregate.as_expr(),
         }
     }
 }
@@ -60,5 +67,30 @@ mod tests {
         let actual = parse("VARIANCE(id)");
         let expected = Expr::Identifier("id".to_owned());
         assert_eq!(actual.as_expr(), Some(&expected));
+
+        let actual = parse("BOOL_AND(valid)");
+        let expected = Expr::Identifier("valid".to_owned());
+        assert_eq!(actual.as_expr(), Some(&expected));
+
+        let actual = parse("EVERY(valid)");
+        assert_eq!(actual, Aggregate::BoolAnd(expected));
+
+        let actual = parse("BOOL_OR(valid)");
+        let expected = Expr::Identifier("valid".to_owned());
+        assert_eq!(actual.as_expr(), Some(&expected));
+
+        let actual = parse("ARRAY_AGG(name ORDER BY id DESC)");
+        let expected = Expr::Identifier("name".to_owned());
+        assert_eq!(actual.as_expr(), Some(&expected));
+
+        let actual = parse("JSON_AGG(detail)");
+        let expected = Expr::Identifier("detail".to_owned());
+        assert_eq!(actual.as_expr(), Some(&expected));
+
+        let actual = parse("JSON_OBJECT_AGG(name, score)");
+        let expected = Expr::Identifier("score".to_owned());
+        assert_eq!(actual.as_expr(), Some(&expected));
+
+        assert_eq!(parse("GROUPING(city, name)").as_expr(), None);
     }
 }
@@ -1,123 +1,181 @@
 use {
     crate::ast::{Expr, Function},
-    std::iter::empty,
+    std::iter::{empty, once},
 };
 
-impl Function {
-    pub fn as_exprs(&self) -> impl ExactSizeIterator<Item = &Expr> {
-        #[derive(iter_enum::Iterator, iter_enum::ExactSizeIterator)]
-        enum Exprs<I0, I1, I2, I3, I4> {
-            Empty(I0),
-            Single(I1),
-            Double(I2),
-            Triple(I3),
-            VariableArgs(I4),
-        }
+#[derive(iter_enum::Iterator, iter_enum::ExactSizeIterator)]
+enum Exprs<I0, I1, I2, I3, I4, I5> {
+    Empty(I0),
+    Single(I1),
+    Double(I2),
+    Triple(I3),
+    VariableArgs(I4),
+    LeadingAndVariableArgs(I5),
+}
 
-        match self {
-            Self::Now() | Function::Pi() | Function::GenerateUuid() => Exprs::Empty(empty()),
-            Self::Lower(expr)
-            | Self::Upper(expr)
-            | Self::Sin(expr)
-            | Self::Cos(expr)
-            | Self::Tan(expr)
-            | Self::Asin(expr)
-            | Self::Acos(expr)
-            | Self::Atan(expr)
-            | Self::Radians(expr)
-            | Self::Degrees(expr)
-            | Self::Ceil(expr)
-            | Self::Round(expr)
-            | Self::Floor(expr)
-            | Self::Exp(expr)
-            | Self::Ln(expr)
-            | Self::Log2(expr)
-            | Self::Log10(expr)
-            | Self::Sqrt(expr)
-            | Self::Abs(expr)
-            | Self::Sign(expr)
-            | Self::Ltrim { expr, chars: None }
-            | Self::Rtrim { expr, chars: None }
-            | Self::Trim {
+/// Arguments of `$function`, borrowed the way `$function` is, so `as_exprs` and `as_exprs_mut`
+/// share the same match.
+macro_rules! exprs {
+    ($function: expr) => {
+        match $function {
+            Function::Now()
+            | Function::Pi()
+            | Function::GenerateUuid()
+            | Function::Random(None) => Exprs::Empty(empty()),
+            Function::Lower(expr)
+            | Function::Upper(expr)
+            | Function::Sin(expr)
+            | Function::Cos(expr)
+            | Function::Tan(expr)
+            | Function::Asin(expr)
+            | Function::Acos(expr)
+            | Function::Atan(expr)
+            | Function::Radians(expr)
+            | Function::Degrees(expr)
+            | Function::Ceil(expr)
+            | Function::Round(expr)
+            | Function::Floor(expr)
+            | Function::Exp(expr)
+            | Function::Ln(expr)
+            | Function::Log2(expr)
+            | Function::Log10(expr)
+            | Function::Sqrt(expr)
+            | Function::Abs(expr)
+            | Function::Sign(expr)
+            | Function::Length(expr)
+            | Function::Ascii(expr)
+            | Function::Chr(expr)
+            | Function::Ord(expr)
+            | Function::Hex(expr)
+            | Function::Unhex(expr)
+            | Function::ToBase64(expr)
+            | Function::FromBase64(expr)
+            | Function::Ltrim { expr, chars: None }
+            | Function::Rtrim { expr, chars: None }
+            | Function::Trim {
                 expr,
                 filter_chars: None,
                 ..
             }
-            | Self::Reverse(expr) => Exprs::Single([expr].into_iter()),
-            Self::Left { expr, size: expr2 }
-            | Self::Right { expr, size: expr2 }
-            | Self::Lpad {
+            | Function::Reverse(expr)
+            | Function::Soundex(expr)
+            | Function::Nextval(expr)
+            | Function::Currval(expr) => Exprs::Single([expr].into_iter()),
+            Function::Left { expr, size: expr2 }
+            | Function::Right { expr, size: expr2 }
+            | Function::Lpad {
                 expr,
                 size: expr2,
                 fill: None,
             }
-            | Self::Rpad {
+            | Function::Rpad {
                 expr,
                 size: expr2,
                 fill: None,
             }
-            | Self::Trim {
+            | Function::Trim {
                 expr,
                 filter_chars: Some(expr2),
                 ..
             }
-            | Self::Log {
+            | Function::Log {
                 antilog: expr,
                 base: expr2,
             }
-            | Self::Div {
+            | Function::Div {
                 dividend: expr,
                 divisor: expr2,
             }
-            | Self::Mod {
+            | Function::Mod {
                 dividend: expr,
                 divisor: expr2,
             }
-            | Self::Gcd {
+            | Function::Gcd {
                 left: expr,
                 right: expr2,
             }
-            | Self::Lcm {
+            | Function::Lcm {
                 left: expr,
                 right: expr2,
             }
-            | Self::Power { expr, power: expr2 }
-            | Self::Ltrim {
+            | Function::Power { expr, power: expr2 }
+            | Function::Ltrim {
                 expr,
                 chars: Some(expr2),
             }
-            | Self::Rtrim {
+            | Function::Rtrim {
                 expr,
                 chars: Some(expr2),
             }
-            | Self::Repeat { expr, num: expr2 }
-            | Self::Substr {
+            | Function::Repeat { expr, num: expr2 }
+            | Function::Glob {
+                expr,
+                pattern: expr2,
+            }
+            | Function::Levenshtein {
+                left: expr,
+                right: expr2,
+                max_distance: None,
+            }
+            | Function::Similarity {
+                left: expr,
+                right: expr2,
+            }
+            | Function::Substr {
                 expr,
                 start: expr2,
                 count: None,
             }
-            | Self::IfNull { expr, then: expr2 }
-            | Self::Unwrap {
+            | Function::IfNull { expr, then: expr2 }
+            | Function::Unwrap {
                 expr,
                 selector: expr2,
-            } => Exprs::Double([expr, expr2].into_iter()),
-            Self::Lpad {
+            }
+            | Function::Random(Some((expr, expr2))) => Exprs::Double([expr, expr2].into_iter()),
+            Function::Lpad {
                 expr,
                 size: expr2,
                 fill: Some(expr3),
             }
-            | Self::Rpad {
+            | Function::Rpad {
                 expr,
                 size: expr2,
                 fill: Some(expr3),
             }
-            | Self::Substr {
+            | Function::Substr {
                 expr,
                 start: expr2,
                 count: Some(expr3),
+            }
+            | Function::Levenshtein {
+                left: expr,
+                right: expr2,
+                max_distance: Some(expr3),
             } => Exprs::Triple([expr, expr2, expr3].into_iter()),
-            Self::Concat(exprs) => Exprs::VariableArgs(exprs.iter()),
+            Function::Concat(exprs) | Function::Custom { args: exprs, .. } => {
+                Exprs::VariableArgs(IntoIterator::into_iter(exprs))
+            }
+            Function::ConcatWs {
+                separator: expr,
+                exprs,
+            }
+            | Function::Format {
+                format: expr,
+                args: exprs,
+            } => Exprs::LeadingAndVariableArgs(
+                once(expr).chain(exprs).collect::<Vec<_>>().into_iter(),
+            ),
         }
+    };
+}
+
+impl Function {
+    pub fn as_exprs(&self) -> impl ExactSizeIterator<Item = &Expr> {
+        exprs!(self)
+    }
+
+    pub fn as_exprs_mut(&mut self) -> impl ExactSizeIterator<Item = &mut Expr> {
+        exprs!(self)
     }
 }
 
@@ -151,6 +209,7 @@ mod tests {
         test("NOW()", &[]);
         test("PI()", &[]);
         test("GENERATE_UUID()", &[]);
+        test("RANDOM()", &[]);
 
         // Single
         test("LOWER(id)", &["id"]);
@@ -175,6 +234,14 @@ mod tests {
         test(r#"RTRIM("world  ")"#, &[r#""world  ""#]);
         test(r#"TRIM("  rust  ")"#, &[r#""  rust  ""#]);
         test(r#"REVERSE("abcde")"#, &[r#""abcde""#]);
+        test("LENGTH(name)", &["name"]);
+        test("ASCII('a')", &["'a'"]);
+        test("CHR(97)", &["97"]);
+        test("ORD(name)", &["name"]);
+        test("HEX(name)", &["name"]);
+        test("UNHEX('616263')", &["'616263'"]);
+        test("TO_BASE64(name)", &["name"]);
+        test("FROM_BASE64('YWJj')", &["'YWJj'"]);
 
         test(r#"ABS(1)"#, &["1"]);
         test(r#"ABS(-1)"#, &["-1"]);
@@ -203,12 +270,14 @@ mod tests {
         test("DIV(6, 2)", &["6", "2"]);
         test("MOD(6, 2)", &["6", "2"]);
         test("GCD(6, 2)", &["6", "2"]);
+        test("RANDOM(1, 6)", &["1", "6"]);
         test("LCM(6, 2)", &["6", "2"]);
         test("POWER(base, 10)", &["base", "10"]);
         test(r#"LTRIM(name, "xyz")"#, &["name", r#""xyz""#]);
         test(r#"RTRIM(name, "xyz")"#, &["name", r#""xyz""#]);
         test("REPEAT(col || col2, 3)", &["col || col2", "3"]);
         test("REPEAT(column, 2)", &["column", "2"]);
+        test("GLOB(name, 'a*')", &["name", "'a*'"]);
         test(r#"UNWRAP(field, "foo.1")"#, &["field", r#""foo.1""#]);
 
         // Triple
@@ -236,5 +305,30 @@ mod tests {
             r#"CONCAT("gluesql", " ", "is", " ", "cool")"#,
             &[r#""gluesql""#, r#"" ""#, r#""is""#, r#"" ""#, r#""cool""#],
         );
+
+        test("CONCAT_WS(',', name)", &["','", "name"]);
+        test("CONCAT_WS('-', a, b, c)", &["'-'", "a", "b", "c"]);
+        test("FORMAT('100%%')", &["'100%%'"]);
+        test(
+            "FORMAT('%s is %d', name, age)",
+            &["'%s is %d'", "name", "age"],
+        );
+    }
+
+    #[test]
+    fn as_exprs_mut() {
+        let mut function = match expr("CONCAT_WS(',', a, b)") {
+            Expr::Function(function) => *function,
+            _ => unreachable!("only for function tests"),
+        };
+
+        for arg in function.as_exprs_mut() {
+            *arg = expr("c");
+        }
+
+        assert_eq!(
+            Expr::Function(Box::new(function)),
+            expr("CONCAT_WS(c, c, c)")
+        );
     }
 }
@@ -1,8 +1,9 @@
 mod aggregate;
 mod function;
+mod window;
 
 use {
-    crate::ast::{Expr, Query},
+    crate::ast::{Aggregate, Expr, OrderByExpr, Query},
     std::iter::once,
 };
 
@@ -22,7 +23,7 @@ pub enum PlanExpr<'a> {
 impl<'a> From<&'a Expr> for PlanExpr<'a> {
     fn from(expr: &'a Expr) -> Self {
         match expr {
-            Expr::Literal(_) | Expr::TypedString { .. } => PlanExpr::None,
+            Expr::Literal(_) | Expr::Placeholder(_) | Expr::TypedString { .. } => PlanExpr::None,
             Expr::Identifier(ident) => PlanExpr::Identifier(ident),
             Expr::CompoundIdentifier(idents) => PlanExpr::CompoundIdentifier(idents),
             Expr::Nested(expr)
@@ -31,11 +32,11 @@ impl<'a> From<&'a Expr> for PlanExpr<'a> {
             | Expr::Extract { expr, .. }
             | Expr::IsNull(expr)
             | Expr::IsNotNull(expr) => PlanExpr::Expr(expr),
-            Expr::Aggregate(aggregate) => match aggregate.as_expr() {
-                Some(expr) => PlanExpr::Expr(expr),
-                None => PlanExpr::None,
-            },
-            Expr::BinaryOp { left, right, .. } => PlanExpr::TwoExprs(left, right),
+            Expr::Aggregate(aggregate) => PlanExpr::from(aggregate.as_ref()),
+            Expr::BinaryOp { left, right, .. }
+            | Expr::AnyOp { left, right, .. }
+            | Expr::AllOp { left, right, .. } => PlanExpr::TwoExprs(left, right),
+            Expr::Tuple(exprs) => PlanExpr::MultiExprs(exprs.iter().collect()),
             Expr::Between {
                 expr, low, high, ..
             } => PlanExpr::ThreeExprs(expr, low, high),
@@ -62,6 +63,7 @@ impl<'a> From<&'a Expr> for PlanExpr<'a> {
                 PlanExpr::MultiExprs(exprs)
             }
             Expr::Function(function) => PlanExpr::MultiExprs(function.as_exprs().collect()),
+            Expr::Window(window) => PlanExpr::MultiExprs(window.as_exprs().collect()),
             Expr::Subquery(query) | Expr::Exists(query) => PlanExpr::Query(query),
             Expr::InSubquery {
                 expr,
@@ -72,6 +74,32 @@ impl<'a> From<&'a Expr> for PlanExpr<'a> {
     }
 }
 
+impl<'a> From<&'a Aggregate> for PlanExpr<'a> {
+    fn from(aggregate: &'a Aggregate) -> Self {
+        match aggregate {
+            Aggregate::ArrayAgg {
+                expr,
+                order_by: Some(OrderByExpr { expr: key, .. }),
+            } => PlanExpr::TwoExprs(expr, key),
+            Aggregate::JsonObjectAgg { key, value } => PlanExpr::TwoExprs(key, value),
+            Aggregate::Grouping(exprs) => PlanExpr::MultiExprs(exprs.iter().collect()),
+            Aggregate::Filter { aggregate, filter } => {
+                let exprs = match PlanExpr::from(aggregate.as_ref()) {
+                    PlanExpr::Expr(expr) => vec![expr],
+                    PlanExpr::TwoExprs(expr, expr2) => vec![expr, expr2],
+                    _ => Vec::new(),
+                };
+
+                PlanExpr::MultiExprs(exprs.into_iter().chain(once(filter)).collect())
+            }
+            aggregate => match aggregate.as_expr() {
+                Some(expr) => PlanExpr::Expr(expr),
+                None => PlanExpr::None,
+            },
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::PlanExpr;
@@ -163,6 +191,12 @@ mod tests {
         let expected = PlanExpr::TwoExprs(&left, &right);
         test!(actual, expected);
 
+        let actual = expr("ARRAY_AGG(name ORDER BY id)");
+        let value = expr("name");
+        let key = expr("id");
+        let expected = PlanExpr::TwoExprs(&value, &key);
+        test!(actual, expected);
+
         // PlanExpr::ThreeExprs
         let actual = expr("100 BETWEEN min_score AND max_score");
         let target = expr("100");
@@ -203,6 +237,14 @@ mod tests {
         let expected = PlanExpr::MultiExprs(expected.iter().collect());
         test!(actual, expected);
 
+        let actual = expr("SUM(price) FILTER (WHERE id > 1)");
+        let expected = ["price", "id > 1"]
+            .into_iter()
+            .map(expr)
+            .collect::<Vec<_>>();
+        let expected = PlanExpr::MultiExprs(expected.iter().collect());
+        test!(actual, expected);
+
         let actual = Expr::Subquery(Box::new(query("SELECT id FROM Foo")));
         let expected = query("SELECT id FROM Foo");
         let expected = PlanExpr::Query(&expected);
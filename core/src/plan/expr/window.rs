@@ -0,0 +1,133 @@
+use {
+    crate::ast::{Expr, OrderByExpr, Window, WindowFunction, WindowSpec},
+    std::iter::empty,
+};
+
+impl WindowFunction {
+    pub fn as_exprs(&self) -> impl ExactSizeIterator<Item = &Expr> {
+        #[derive(iter_enum::Iterator, iter_enum::ExactSizeIterator)]
+        enum Exprs<I0, I1, I2, I3> {
+            Empty(I0),
+            Single(I1),
+            Double(I2),
+            Triple(I3),
+        }
+
+        match self {
+            Self::FirstValue(expr)
+            | Self::LastValue(expr)
+            | Self::Lag {
+                expr,
+                offset: None,
+                default: None,
+            }
+            | Self::Lead {
+                expr,
+                offset: None,
+                default: None,
+            } => Exprs::Single([expr].into_iter()),
+            Self::NthValue { expr, n: expr2 }
+            | Self::Lag {
+                expr,
+                offset: Some(expr2),
+                default: None,
+            }
+            | Self::Lag {
+                expr,
+                offset: None,
+                default: Some(expr2),
+            }
+            | Self::Lead {
+                expr,
+                offset: Some(expr2),
+                default: None,
+            }
+            | Self::Lead {
+                expr,
+                offset: None,
+                default: Some(expr2),
+            } => Exprs::Double([expr, expr2].into_iter()),
+            Self::Lag {
+                expr,
+                offset: Some(expr2),
+                default: Some(expr3),
+            }
+            | Self::Lead {
+                expr,
+                offset: Some(expr2),
+                default: Some(expr3),
+            } => Exprs::Triple([expr, expr2, expr3].into_iter()),
+            Self::Aggregate(aggregate) => match aggregate.as_expr() {
+                Some(expr) => Exprs::Single([expr].into_iter()),
+                None => Exprs::Empty(empty()),
+            },
+        }
+    }
+}
+
+impl Window {
+    pub fn as_exprs(&self) -> impl Iterator<Item = &Expr> {
+        let Window {
+            function,
+            over:
+                WindowSpec {
+                    partition_by,
+                    order_by,
+                    ..
+                },
+        } = self;
+
+        function
+            .as_exprs()
+            .chain(partition_by.iter())
+            .chain(order_by.iter().map(|OrderByExpr { expr, .. }| expr))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{ast::Expr, parse_sql::parse_expr, translate::translate_expr};
+
+    fn expr(sql: &str) -> Expr {
+        let parsed = parse_expr(sql).expect(sql);
+
+        translate_expr(&parsed).expect(sql)
+    }
+
+    fn test(sql: &str, expected: &[&str]) {
+        let window = match expr(sql) {
+            Expr::Window(window) => *window,
+            _ => unreachable!("only for window tests"),
+        };
+        let actual = window.as_exprs().collect::<Vec<_>>();
+
+        assert_eq!(actual.len(), expected.len(), "{sql}");
+
+        for (expected, actual) in expected.iter().zip(actual) {
+            assert_eq!(actual, &expr(expected), "{sql}");
+        }
+    }
+
+    #[test]
+    fn as_exprs() {
+        test("FIRST_VALUE(id) OVER ()", &["id"]);
+        test(
+            "LAST_VALUE(name) OVER (PARTITION BY category ORDER BY id)",
+            &["name", "category", "id"],
+        );
+        test(
+            "NTH_VALUE(name, 2) OVER (PARTITION BY a, b ORDER BY c DESC)",
+            &["name", "2", "a", "b", "c"],
+        );
+        test("LAG(price) OVER (ORDER BY id)", &["price", "id"]);
+        test(
+            "LEAD(price, 2, 0) OVER (PARTITION BY category ORDER BY id)",
+            &["price", "2", "0", "category", "id"],
+        );
+        test(
+            "SUM(price) OVER (ORDER BY id ROWS BETWEEN 1 PRECEDING AND CURRENT ROW)",
+            &["price", "id"],
+        );
+        test("COUNT(*) OVER (PARTITION BY category)", &["category"]);
+    }
+}
@@ -1,13 +1,15 @@
 use {
     crate::{
         ast::{
-            AstLiteral, BinaryOperator, Expr, IndexItem, IndexOperator, OrderByExpr, Query, Select,
-            SetExpr, Statement, TableFactor, TableWithJoins,
+            AstLiteral, BinaryOperator, Collation, Expr, IndexCmp, IndexItem, IndexOperator,
+            OrderByExpr, Query, Select, SelectItem, SetExpr, Statement, TableFactor,
+            TableWithJoins,
         },
         data::{get_name, Schema, SchemaIndex, SchemaIndexOrd, TableError},
+        plan::PlanExpr,
         result::{Error, Result},
     },
-    std::collections::HashMap,
+    std::{collections::HashMap, ops::Bound},
     utils::Vector,
 };
 
@@ -34,7 +36,15 @@ impl Indexes {
         self.0
             .iter()
             .find(|SchemaIndex { expr, order, .. }| {
-                if expr != &target.expr {
+                // an index is ordered as its values are, not under a collation,
+                // and keeps NULL as the greatest value
+                let nulls_greatest = target.nulls_first.map_or(true, |nulls_first| {
+                    nulls_first == (target.asc == Some(false))
+                });
+                if expr != &target.expr
+                    || target.collation == Some(Collation::NoCase)
+                    || !nulls_greatest
+                {
                     return false;
                 }
 
@@ -52,15 +62,18 @@ impl Indexes {
 
 fn plan_query(schema_map: &HashMap<String, Schema>, query: Query) -> Result<Query> {
     let Query {
+        with,
         body,
         limit,
         offset,
     } = query;
 
     let select = match body {
-        SetExpr::Select(select) => select,
-        SetExpr::Values(_) => {
+        // names of common table expressions may shadow the tables in schema_map
+        SetExpr::Select(select) if with.is_empty() => select,
+        _ => {
             return Ok(Query {
+                with,
                 body,
                 limit,
                 offset,
@@ -71,8 +84,9 @@ fn plan_query(schema_map: &HashMap<String, Schema>, query: Query) -> Result<Quer
     let TableWithJoins { relation, .. } = &select.from;
     let table_name = match relation {
         TableFactor::Table { name, .. } => get_name(name)?,
-        TableFactor::Derived { .. } => {
+        TableFactor::Derived { .. } | TableFactor::Series { .. } => {
             return Ok(Query {
+                with,
                 body: SetExpr::Select(select),
                 limit,
                 offset,
@@ -84,6 +98,7 @@ fn plan_query(schema_map: &HashMap<String, Schema>, query: Query) -> Result<Quer
         Some(Schema { indexes, .. }) => Indexes(indexes.clone()),
         None => {
             return Ok(Query {
+                with,
                 body: SetExpr::Select(select),
                 limit,
                 offset,
@@ -91,13 +106,19 @@ fn plan_query(schema_map: &HashMap<String, Schema>, query: Query) -> Result<Quer
         }
     };
 
-    let index = select.order_by.last().and_then(|value_expr| {
-        indexes.find_ordered(value_expr).map(|name| IndexItem {
-            name,
-            asc: value_expr.asc,
-            cmp_expr: None,
-        })
-    });
+    // DISTINCT ON relies on the sort stage to see every ORDER BY key,
+    // and aggregation does not keep the order rows are read in
+    let index = select
+        .order_by
+        .last()
+        .filter(|_| select.distinct_on.is_empty() && !check_aggregate(&select))
+        .and_then(|value_expr| {
+            indexes.find_ordered(value_expr).map(|name| IndexItem {
+                name,
+                asc: value_expr.asc,
+                cmp_expr: None,
+            })
+        });
 
     match index {
         index if index.is_some() => {
@@ -106,20 +127,32 @@ fn plan_query(schema_map: &HashMap<String, Schema>, query: Query) -> Result<Quer
                 from,
                 selection,
                 group_by,
+                grouping_sets,
                 having,
                 order_by,
+                distinct_on,
             } = *select;
 
             let TableWithJoins { relation, joins } = from;
-            let (name, alias) = match relation {
-                TableFactor::Table { name, alias, .. } => (name, alias),
-                TableFactor::Derived { .. } => {
+            let (name, alias, sample) = match relation {
+                TableFactor::Table {
+                    name,
+                    alias,
+                    sample,
+                    ..
+                } => (name, alias, sample),
+                TableFactor::Derived { .. } | TableFactor::Series { .. } => {
                     return Err(Error::Table(TableError::Unreachable));
                 }
             };
 
             let from = TableWithJoins {
-                relation: TableFactor::Table { name, alias, index },
+                relation: TableFactor::Table {
+                    name,
+                    alias,
+                    index,
+                    sample,
+                },
                 joins,
             };
 
@@ -128,11 +161,14 @@ fn plan_query(schema_map: &HashMap<String, Schema>, query: Query) -> Result<Quer
                 from,
                 selection,
                 group_by,
+                grouping_sets,
                 having,
                 order_by: Vector::from(order_by).pop().0.into(),
+                distinct_on,
             };
 
             Ok(Query {
+                with,
                 body: SetExpr::Select(Box::new(select)),
                 limit,
                 offset,
@@ -142,6 +178,7 @@ fn plan_query(schema_map: &HashMap<String, Schema>, query: Query) -> Result<Quer
             let select = plan_select(schema_map, &indexes, *select)?;
             let body = SetExpr::Select(Box::new(select));
             let query = Query {
+                with,
                 body,
                 limit,
                 offset,
@@ -152,6 +189,43 @@ fn plan_query(schema_map: &HashMap<String, Schema>, query: Query) -> Result<Quer
     }
 }
 
+fn check_aggregate(select: &Select) -> bool {
+    let Select {
+        projection,
+        group_by,
+        grouping_sets,
+        having,
+        ..
+    } = select;
+
+    !group_by.is_empty()
+        || grouping_sets.is_some()
+        || having.iter().any(contains_aggregate)
+        || projection.iter().any(|item| match item {
+            SelectItem::Expr { expr, .. } => contains_aggregate(expr),
+            SelectItem::QualifiedWildcard(_) | SelectItem::Wildcard => false,
+        })
+}
+
+fn contains_aggregate(expr: &Expr) -> bool {
+    if matches!(expr, Expr::Aggregate(_)) {
+        return true;
+    }
+
+    match PlanExpr::from(expr) {
+        PlanExpr::None
+        | PlanExpr::Identifier(_)
+        | PlanExpr::CompoundIdentifier(_)
+        | PlanExpr::Query(_) => false,
+        PlanExpr::Expr(expr) | PlanExpr::QueryAndExpr { expr, .. } => contains_aggregate(expr),
+        PlanExpr::TwoExprs(expr, expr2) => contains_aggregate(expr) || contains_aggregate(expr2),
+        PlanExpr::ThreeExprs(expr, expr2, expr3) => {
+            contains_aggregate(expr) || contains_aggregate(expr2) || contains_aggregate(expr3)
+        }
+        PlanExpr::MultiExprs(exprs) => exprs.into_iter().any(contains_aggregate),
+    }
+}
+
 fn plan_select(
     schema_map: &HashMap<String, Schema>,
     indexes: &Indexes,
@@ -162,8 +236,10 @@ fn plan_select(
         from,
         selection,
         group_by,
+        grouping_sets,
         having,
         order_by,
+        distinct_on,
     } = select;
 
     let selection = match selection {
@@ -174,31 +250,51 @@ fn plan_select(
                 from,
                 selection,
                 group_by,
+                grouping_sets,
                 having,
                 order_by,
+                distinct_on,
             });
         }
     };
 
-    match plan_index(schema_map, indexes, selection)? {
+    // a composite index matching more than one column is the narrowest lookup,
+    // otherwise it is only used when no other index applies
+    let planned = match search_composite(indexes, &selection) {
+        Some((planned, num_columns)) if num_columns > 1 => planned,
+        composite => match plan_index(schema_map, indexes, selection)? {
+            Planned::Expr(selection) => composite
+                .map(|(planned, _)| planned)
+                .unwrap_or(Planned::Expr(selection)),
+            planned => planned,
+        },
+    };
+
+    match planned {
         Planned::Expr(selection) => Ok(Select {
             projection,
             from,
             selection: Some(selection),
             group_by,
+            grouping_sets,
             having,
             order_by,
+            distinct_on,
         }),
         Planned::IndexedExpr {
             index_name,
-            index_op,
-            index_value_expr,
+            index_cmp,
             selection,
         } => {
             let TableWithJoins { relation, joins } = from;
-            let (name, alias) = match relation {
-                TableFactor::Table { name, alias, .. } => (name, alias),
-                TableFactor::Derived { .. } => {
+            let (name, alias, sample) = match relation {
+                TableFactor::Table {
+                    name,
+                    alias,
+                    sample,
+                    ..
+                } => (name, alias, sample),
+                TableFactor::Derived { .. } | TableFactor::Series { .. } => {
                     return Err(Error::Table(TableError::Unreachable));
                 }
             };
@@ -206,10 +302,15 @@ fn plan_select(
             let index = Some(IndexItem {
                 name: index_name,
                 asc: None,
-                cmp_expr: Some((index_op, index_value_expr)),
+                cmp_expr: Some(index_cmp),
             });
             let from = TableWithJoins {
-                relation: TableFactor::Table { name, alias, index },
+                relation: TableFactor::Table {
+                    name,
+                    alias,
+                    index,
+                    sample,
+                },
                 joins,
             };
 
@@ -218,8 +319,10 @@ fn plan_select(
                 from,
                 selection,
                 group_by,
+                grouping_sets,
                 having,
                 order_by,
+                distinct_on,
             })
         }
     }
@@ -228,8 +331,7 @@ fn plan_select(
 enum Planned {
     IndexedExpr {
         index_name: String,
-        index_op: IndexOperator,
-        index_value_expr: Expr,
+        index_cmp: IndexCmp,
         selection: Option<Expr>,
     },
     Expr(Expr),
@@ -273,10 +375,21 @@ fn plan_index(
                 Planned::Expr(selection) => selection,
                 Planned::IndexedExpr {
                     index_name,
-                    index_value_expr,
-                    index_op,
+                    index_cmp,
                     selection,
                 } => {
+                    if selection.is_none() {
+                        if let Some(index_cmp) =
+                            search_range(indexes, &index_name, &index_cmp, &right)
+                        {
+                            return Ok(Planned::IndexedExpr {
+                                index_name,
+                                index_cmp,
+                                selection: None,
+                            });
+                        }
+                    }
+
                     let selection = match selection {
                         Some(expr) => Expr::BinaryOp {
                             left: Box::new(expr),
@@ -288,8 +401,7 @@ fn plan_index(
 
                     return Ok(Planned::IndexedExpr {
                         index_name,
-                        index_op,
-                        index_value_expr,
+                        index_cmp,
                         selection: Some(selection),
                     });
                 }
@@ -303,8 +415,7 @@ fn plan_index(
                 })),
                 Planned::IndexedExpr {
                     index_name,
-                    index_op,
-                    index_value_expr,
+                    index_cmp,
                     selection,
                 } => {
                     let selection = match selection {
@@ -318,8 +429,7 @@ fn plan_index(
 
                     Ok(Planned::IndexedExpr {
                         index_name,
-                        index_value_expr,
-                        index_op,
+                        index_cmp,
                         selection: Some(selection),
                     })
                 }
@@ -350,10 +460,321 @@ fn plan_index(
             op: BinaryOperator::Eq,
             right,
         } => Ok(search_index_op(indexes, IndexOperator::Eq, left, right)),
+        Expr::BinaryOp {
+            left,
+            op: BinaryOperator::Like,
+            right,
+        } => Ok(search_like(indexes, left, right)),
+        Expr::BinaryOp {
+            op: BinaryOperator::Or,
+            ..
+        }
+        | Expr::InList { negated: false, .. } => Ok(search_in(indexes, selection)),
+        Expr::Between {
+            expr,
+            negated: false,
+            low,
+            high,
+        } => match indexes.find(&expr) {
+            Some(index_name) if is_stateless(&low) && is_stateless(&high) => {
+                Ok(Planned::IndexedExpr {
+                    index_name,
+                    index_cmp: IndexCmp::Range {
+                        start: Bound::Included(*low),
+                        end: Bound::Included(*high),
+                    },
+                    selection: None,
+                })
+            }
+            _ => Ok(Planned::Expr(Expr::Between {
+                expr,
+                negated: false,
+                low,
+                high,
+            })),
+        },
         _ => Ok(Planned::Expr(selection)),
     }
 }
 
+/// `LIKE` with a literal prefix scans the index range of the values starting with it, and is
+/// kept as the filter of the rows found.
+fn search_like(indexes: &Indexes, left: Box<Expr>, right: Box<Expr>) -> Planned {
+    let index = match right.as_ref() {
+        Expr::Literal(AstLiteral::QuotedString(pattern)) => indexes
+            .find(&left)
+            .and_then(|index_name| like_range(pattern).map(|index_cmp| (index_name, index_cmp))),
+        _ => None,
+    };
+
+    let selection = Expr::BinaryOp {
+        left,
+        op: BinaryOperator::Like,
+        right,
+    };
+
+    match index {
+        Some((index_name, index_cmp)) => Planned::IndexedExpr {
+            index_name,
+            index_cmp,
+            selection: Some(selection),
+        },
+        None => Planned::Expr(selection),
+    }
+}
+
+/// Range from the prefix of `pattern` before its first wildcard, up to the prefix with its last
+/// character advanced, e.g. `[joh, joi)` for `joh%`.
+fn like_range(pattern: &str) -> Option<IndexCmp> {
+    let prefix = pattern
+        .chars()
+        .take_while(|c| !matches!(c, '%' | '_'))
+        .collect::<String>();
+
+    if prefix.is_empty() {
+        return None;
+    }
+
+    let literal = |value| Expr::Literal(AstLiteral::QuotedString(value));
+    let mut upper = prefix.clone();
+    let end = loop {
+        let next = upper
+            .pop()
+            .map(|c| (c as u32 + 1..=char::MAX as u32).find_map(char::from_u32));
+
+        match next {
+            Some(Some(c)) => {
+                upper.push(c);
+
+                break Bound::Excluded(literal(upper));
+            }
+            // the last character is already the greatest one, advance the one before it
+            Some(None) => continue,
+            None => break Bound::Unbounded,
+        }
+    };
+
+    Some(IndexCmp::Range {
+        start: Bound::Included(literal(prefix)),
+        end,
+    })
+}
+
+/// A lower and an upper bound of the same index, joined by `AND`, scan only the index range
+/// between them.
+fn search_range(
+    indexes: &Indexes,
+    index_name: &str,
+    index_cmp: &IndexCmp,
+    expr: &Expr,
+) -> Option<IndexCmp> {
+    let other = match expr {
+        Expr::BinaryOp { left, op, right } => {
+            let index_op = match op {
+                BinaryOperator::Gt => IndexOperator::Gt,
+                BinaryOperator::Lt => IndexOperator::Lt,
+                BinaryOperator::GtEq => IndexOperator::GtEq,
+                BinaryOperator::LtEq => IndexOperator::LtEq,
+                _ => return None,
+            };
+
+            match search_index_op(indexes, index_op, left.clone(), right.clone()) {
+                Planned::IndexedExpr {
+                    index_name: other_name,
+                    index_cmp,
+                    selection: None,
+                } if other_name == index_name => index_cmp,
+                _ => return None,
+            }
+        }
+        _ => return None,
+    };
+
+    match (range_bound(index_cmp)?, range_bound(&other)?) {
+        ((true, start), (false, end)) | ((false, end), (true, start)) => {
+            Some(IndexCmp::Range { start, end })
+        }
+        _ => None,
+    }
+}
+
+/// Bound of a comparison, and whether it is a lower bound.
+fn range_bound(index_cmp: &IndexCmp) -> Option<(bool, Bound<Expr>)> {
+    let (index_op, expr) = match index_cmp {
+        IndexCmp::Op(index_op, expr) if is_stateless(expr) => (index_op, expr.clone()),
+        _ => return None,
+    };
+
+    match index_op {
+        IndexOperator::Gt => Some((true, Bound::Excluded(expr))),
+        IndexOperator::GtEq => Some((true, Bound::Included(expr))),
+        IndexOperator::Lt => Some((false, Bound::Excluded(expr))),
+        IndexOperator::LtEq => Some((false, Bound::Included(expr))),
+        IndexOperator::Eq => None,
+    }
+}
+
+/// Finds the composite index with the most leading columns compared by equality in the `AND`
+/// chain of `selection`, and returns the number of the columns matched with the plan.
+fn search_composite(indexes: &Indexes, selection: &Expr) -> Option<(Planned, usize)> {
+    let terms = conjuncts(selection);
+
+    // on a tie, the index created first wins
+    indexes
+        .0
+        .iter()
+        .filter_map(|index| search_composite_prefix(index, &terms))
+        .rev()
+        .max_by_key(|(_, num_columns)| *num_columns)
+}
+
+fn search_composite_prefix(index: &SchemaIndex, terms: &[&Expr]) -> Option<(Planned, usize)> {
+    let columns = match &index.expr {
+        Expr::Tuple(columns) => columns,
+        _ => return None,
+    };
+
+    let mut matched = Vec::new();
+    let mut values = Vec::new();
+    for column in columns {
+        let found = terms
+            .iter()
+            .enumerate()
+            .find_map(|(i, term)| search_eq_value(term, column).map(|value| (i, value)));
+
+        match found {
+            Some((i, value)) => {
+                matched.push(i);
+                values.push(value.clone());
+            }
+            None => break,
+        }
+    }
+
+    if values.is_empty() {
+        return None;
+    }
+
+    let selection = terms
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| !matched.contains(i))
+        .map(|(_, term)| (*term).clone())
+        .reduce(|left, right| Expr::BinaryOp {
+            left: Box::new(left),
+            op: BinaryOperator::And,
+            right: Box::new(right),
+        });
+    let planned = Planned::IndexedExpr {
+        index_name: index.name.to_owned(),
+        index_cmp: IndexCmp::Prefix(values),
+        selection,
+    };
+
+    Some((planned, matched.len()))
+}
+
+fn conjuncts(expr: &Expr) -> Vec<&Expr> {
+    match expr {
+        Expr::Nested(expr) => conjuncts(expr),
+        Expr::BinaryOp {
+            left,
+            op: BinaryOperator::And,
+            right,
+        } => {
+            let mut terms = conjuncts(left);
+            terms.extend(conjuncts(right));
+
+            terms
+        }
+        _ => vec![expr],
+    }
+}
+
+/// Value `target` equals in `term`, as in `target = value` or `value = target`.
+fn search_eq_value<'a>(term: &'a Expr, target: &Expr) -> Option<&'a Expr> {
+    match term {
+        Expr::Nested(term) => search_eq_value(term, target),
+        Expr::BinaryOp {
+            left,
+            op: BinaryOperator::Eq,
+            right,
+        } => {
+            if left.as_ref() == target && is_stateless(right) {
+                Some(right.as_ref())
+            } else if right.as_ref() == target && is_stateless(left) {
+                Some(left.as_ref())
+            } else {
+                None
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Equalities of one indexed expression joined by `OR`, or its `IN` list, are looked up in the
+/// index value by value, and the union of the rows found replaces the selection.
+fn search_in(indexes: &Indexes, selection: Expr) -> Planned {
+    let mut values = Vec::new();
+
+    match find_in_values(indexes, &selection, &mut values) {
+        Some(index_name) => Planned::IndexedExpr {
+            index_name,
+            index_cmp: IndexCmp::In(values),
+            selection: None,
+        },
+        None => Planned::Expr(selection),
+    }
+}
+
+fn find_in_values(indexes: &Indexes, expr: &Expr, values: &mut Vec<Expr>) -> Option<String> {
+    match expr {
+        Expr::Nested(expr) => find_in_values(indexes, expr, values),
+        Expr::BinaryOp {
+            left,
+            op: BinaryOperator::Or,
+            right,
+        } => {
+            let left = find_in_values(indexes, left, values)?;
+            let right = find_in_values(indexes, right, values)?;
+
+            (left == right).then(|| left)
+        }
+        Expr::BinaryOp {
+            left,
+            op: BinaryOperator::Eq,
+            right,
+        } => {
+            let (index_name, value) = match indexes.find(left) {
+                Some(index_name) => (index_name, right),
+                None => (indexes.find(right)?, left),
+            };
+
+            if !is_stateless(value) {
+                return None;
+            }
+
+            values.push(value.as_ref().clone());
+            Some(index_name)
+        }
+        Expr::InList {
+            expr,
+            list,
+            negated: false,
+        } => {
+            let index_name = indexes.find(expr)?;
+
+            if !list.iter().all(is_stateless) {
+                return None;
+            }
+
+            values.extend(list.iter().cloned());
+            Some(index_name)
+        }
+        _ => None,
+    }
+}
+
 fn search_is_null(indexes: &Indexes, null: bool, expr: Box<Expr>) -> Planned {
     match indexes.find(expr.as_ref()) {
         Some(index_name) => {
@@ -365,8 +786,7 @@ fn search_is_null(indexes: &Indexes, null: bool, expr: Box<Expr>) -> Planned {
 
             Planned::IndexedExpr {
                 index_name,
-                index_op,
-                index_value_expr: Expr::Literal(AstLiteral::Null),
+                index_cmp: IndexCmp::Op(index_op, Expr::Literal(AstLiteral::Null)),
                 selection: None,
             }
         }
@@ -388,14 +808,17 @@ fn search_index_op(
     left: Box<Expr>,
     right: Box<Expr>,
 ) -> Planned {
+    if let (Expr::Tuple(_), Expr::Tuple(_)) = (left.as_ref(), right.as_ref()) {
+        return search_row_value_op(indexes, index_op, left, right);
+    }
+
     if let Some(index_name) = indexes
         .find(left.as_ref())
         .and_then(|index_name| is_stateless(right.as_ref()).then(|| index_name))
     {
         Planned::IndexedExpr {
             index_name,
-            index_op,
-            index_value_expr: *right,
+            index_cmp: IndexCmp::Op(index_op, *right),
             selection: None,
         }
     } else if let Some(index_name) = indexes
@@ -404,8 +827,7 @@ fn search_index_op(
     {
         Planned::IndexedExpr {
             index_name,
-            index_op: index_op.reverse(),
-            index_value_expr: *left,
+            index_cmp: IndexCmp::Op(index_op.reverse(), *left),
             selection: None,
         }
     } else if let Expr::Nested(left) = *left {
@@ -421,6 +843,53 @@ fn search_index_op(
     }
 }
 
+/// Row values compare lexicographically, so only their first elements can bound an index scan.
+/// The scan includes the first key and the comparison itself stays as the selection,
+/// which lets `WHERE (a, b) > (?, ?) ORDER BY a, b` page forward without an OFFSET.
+fn search_row_value_op(
+    indexes: &Indexes,
+    op: IndexOperator,
+    left: Box<Expr>,
+    right: Box<Expr>,
+) -> Planned {
+    let index_op = match &op {
+        IndexOperator::Gt | IndexOperator::GtEq => IndexOperator::GtEq,
+        IndexOperator::Lt | IndexOperator::LtEq => IndexOperator::LtEq,
+        IndexOperator::Eq => IndexOperator::Eq,
+    };
+
+    let first = match (left.as_ref(), right.as_ref()) {
+        (Expr::Tuple(l), Expr::Tuple(r)) if l.len() == r.len() => l.first().zip(r.first()),
+        _ => None,
+    };
+
+    let found = first.and_then(|(l, r)| {
+        if let Some(index_name) = indexes.find(l).filter(|_| is_stateless(r)) {
+            Some((index_name, index_op, r.clone()))
+        } else {
+            indexes
+                .find(r)
+                .filter(|_| is_stateless(l))
+                .map(|index_name| (index_name, index_op.reverse(), l.clone()))
+        }
+    });
+
+    let selection = Expr::BinaryOp {
+        left,
+        op: op.into(),
+        right,
+    };
+
+    match found {
+        Some((index_name, index_op, index_value_expr)) => Planned::IndexedExpr {
+            index_name,
+            index_cmp: IndexCmp::Op(index_op, index_value_expr),
+            selection: Some(selection),
+        },
+        None => Planned::Expr(selection),
+    }
+}
+
 fn is_stateless(expr: &Expr) -> bool {
     match expr {
         Expr::Literal(AstLiteral::Null) => false,
@@ -2,8 +2,8 @@ use {
     super::{context::Context, evaluable::check_expr as check_evaluable},
     crate::{
         ast::{
-            BinaryOperator, ColumnDef, Expr, Join, JoinConstraint, JoinExecutor, JoinOperator,
-            Query, Select, SetExpr, Statement, TableAlias, TableFactor, TableWithJoins,
+            BinaryOperator, Expr, Join, JoinConstraint, JoinExecutor, JoinOperator, Query, Select,
+            SetExpr, Statement, TableAlias, TableFactor, TableWithJoins,
         },
         data::{get_name, Schema},
     },
@@ -31,11 +31,22 @@ struct Planner<'a> {
 impl<'a> Planner<'a> {
     fn query(&self, outer_context: Option<Rc<Context<'a>>>, query: Query) -> Query {
         let Query {
+            with,
             body,
             limit,
             offset,
         } = query;
 
+        // names of common table expressions may shadow the tables in schema_map
+        if !with.is_empty() {
+            return Query {
+                with,
+                body,
+                limit,
+                offset,
+            };
+        }
+
         let body = match body {
             SetExpr::Select(select) => {
                 let select = self.select(outer_context, *select);
@@ -46,6 +57,7 @@ impl<'a> Planner<'a> {
         };
 
         Query {
+            with,
             body,
             limit,
             offset,
@@ -58,8 +70,10 @@ impl<'a> Planner<'a> {
             from,
             selection,
             group_by,
+            grouping_sets,
             having,
             order_by,
+            distinct_on,
         } = select;
 
         let (outer_context, from) = self.table_with_joins(outer_context, from);
@@ -70,8 +84,10 @@ impl<'a> Planner<'a> {
             from,
             selection,
             group_by,
+            grouping_sets,
             having,
             order_by,
+            distinct_on,
         }
     }
 
@@ -110,7 +126,10 @@ impl<'a> Planner<'a> {
             join_executor,
         } = join;
 
-        if matches!(join_executor, JoinExecutor::Hash { .. }) {
+        // LATERAL subqueries depend on each preceding row, so they always run as a nested loop
+        if matches!(join_executor, JoinExecutor::Hash { .. })
+            || matches!(relation, TableFactor::Derived { lateral: true, .. })
+        {
             let context = self.update_context(inner_context, &relation);
             let join = Join {
                 relation,
@@ -172,6 +191,7 @@ impl<'a> Planner<'a> {
             Expr::Identifier(_)
             | Expr::CompoundIdentifier(_)
             | Expr::Literal(_)
+            | Expr::Placeholder(_)
             | Expr::TypedString { .. } => expr,
             Expr::IsNull(expr) => Expr::IsNull(Box::new(self.subquery_expr(outer_context, *expr))),
             Expr::IsNotNull(expr) => {
@@ -234,6 +254,16 @@ impl<'a> Planner<'a> {
                 op,
                 right: Box::new(self.subquery_expr(outer_context, *right)),
             },
+            Expr::AnyOp { left, op, right } => Expr::AnyOp {
+                left: Box::new(self.subquery_expr(outer_context.as_ref().map(Rc::clone), *left)),
+                op,
+                right: Box::new(self.subquery_expr(outer_context, *right)),
+            },
+            Expr::AllOp { left, op, right } => Expr::AllOp {
+                left: Box::new(self.subquery_expr(outer_context.as_ref().map(Rc::clone), *left)),
+                op,
+                right: Box::new(self.subquery_expr(outer_context, *right)),
+            },
             Expr::UnaryOp { op, expr } => Expr::UnaryOp {
                 op,
                 expr: Box::new(self.subquery_expr(outer_context, *expr)),
@@ -247,6 +277,12 @@ impl<'a> Planner<'a> {
                 expr: Box::new(self.subquery_expr(outer_context, *expr)),
             },
             Expr::Nested(expr) => Expr::Nested(Box::new(self.subquery_expr(outer_context, *expr))),
+            Expr::Tuple(exprs) => Expr::Tuple(
+                exprs
+                    .into_iter()
+                    .map(|expr| self.subquery_expr(outer_context.as_ref().map(Rc::clone), expr))
+                    .collect(),
+            ),
             Expr::Case {
                 operand,
                 when_then,
@@ -273,7 +309,7 @@ impl<'a> Planner<'a> {
                     else_result,
                 }
             }
-            Expr::Function(_) | Expr::Aggregate(_) => expr,
+            Expr::Function(_) | Expr::Aggregate(_) | Expr::Window(_) => expr,
         }
     }
 
@@ -477,16 +513,12 @@ impl<'a> Planner<'a> {
 
                 (name, alias)
             }
-            TableFactor::Derived { .. } => return next,
+            TableFactor::Derived { .. } | TableFactor::Series { .. } => return next,
         };
-        let column_defs = match self.schema_map.get(&name) {
-            Some(Schema { column_defs, .. }) => column_defs,
+        let columns = match self.schema_map.get(&name) {
+            Some(schema) => schema.column_names(),
             None => return next,
         };
-        let columns = column_defs
-            .iter()
-            .map(|ColumnDef { name, .. }| name.as_str())
-            .collect::<Vec<_>>();
 
         let context = Context::new(alias.unwrap_or(name), columns, next, None);
         Some(Rc::new(context))
@@ -586,11 +618,13 @@ mod tests {
                 columns: Vec::new(),
             }),
             index: None,
+            sample: None,
         }
     }
 
     fn select(select: Select) -> Statement {
         Statement::Query(Box::new(Query {
+            with: Vec::new(),
             body: SetExpr::Select(Box::new(select)),
             limit: None,
             offset: None,
@@ -621,8 +655,10 @@ mod tests {
             },
             selection: None,
             group_by: Vec::new(),
+            grouping_sets: None,
             having: None,
             order_by: Vec::new(),
+            distinct_on: Vec::new(),
         });
         assert_eq!(actual, expected, "basic select:\n{sql}");
 
@@ -631,6 +667,7 @@ mod tests {
         let expected = Statement::Delete {
             table_name: ObjectName(vec!["User".to_owned()]),
             selection: Some(expr("id = 1")),
+            returning: Vec::new(),
         };
         assert_eq!(actual, expected, "plan not covered:\n{sql}");
 
@@ -652,8 +689,10 @@ mod tests {
             },
             selection: None,
             group_by: Vec::new(),
+            grouping_sets: None,
             having: None,
             order_by: Vec::new(),
+            distinct_on: Vec::new(),
         });
         assert_eq!(actual, expected, "basic nested loop join:\n{sql}");
 
@@ -675,8 +714,10 @@ mod tests {
             },
             selection: None,
             group_by: Vec::new(),
+            grouping_sets: None,
             having: None,
             order_by: Vec::new(),
+            distinct_on: Vec::new(),
         });
         assert_eq!(actual, expected, "basic nested loop join 2:\n{sql}");
 
@@ -706,8 +747,10 @@ mod tests {
             },
             selection: None,
             group_by: Vec::new(),
+            grouping_sets: None,
             having: None,
             order_by: Vec::new(),
+            distinct_on: Vec::new(),
         });
         assert_eq!(actual, expected, "self multiple joins:\n{sql}");
 
@@ -736,8 +779,10 @@ mod tests {
                 },
                 selection: None,
                 group_by: Vec::new(),
+                grouping_sets: None,
                 having: None,
                 order_by: Vec::new(),
+                distinct_on: Vec::new(),
             })
         };
         assert_eq!(actual, expected, "basic hash join query:\n{sql}");
@@ -772,8 +817,10 @@ mod tests {
                 },
                 selection: None,
                 group_by: Vec::new(),
+                grouping_sets: None,
                 having: None,
                 order_by: Vec::new(),
+                distinct_on: Vec::new(),
             })
         };
         assert_eq!(
@@ -799,8 +846,10 @@ mod tests {
                 },
                 selection: None,
                 group_by: Vec::new(),
+                grouping_sets: None,
                 having: None,
                 order_by: Vec::new(),
+                distinct_on: Vec::new(),
             })
         };
         assert_eq!(actual, expected, "subquery in join_constraint:\n{sql}");
@@ -852,8 +901,10 @@ mod tests {
                 },
                 selection: Some(expr("True")),
                 group_by: Vec::new(),
+                grouping_sets: None,
                 having: None,
                 order_by: Vec::new(),
+                distinct_on: Vec::new(),
             })
         };
         assert_eq!(actual, expected, "where_clause AND hash_join expr:\n{sql}");
@@ -886,8 +937,10 @@ mod tests {
                 },
                 selection: None,
                 group_by: Vec::new(),
+                grouping_sets: None,
                 having: None,
                 order_by: Vec::new(),
+                distinct_on: Vec::new(),
             })
         };
         assert_eq!(
@@ -924,8 +977,10 @@ mod tests {
                 },
                 selection: Some(expr("True")),
                 group_by: Vec::new(),
+                grouping_sets: None,
                 having: None,
                 order_by: Vec::new(),
+                distinct_on: Vec::new(),
             })
         };
         assert_eq!(actual, expected, "complex where_clause:\n{sql}");
@@ -958,8 +1013,10 @@ mod tests {
                 },
                 selection: Some(expr("True")),
                 group_by: Vec::new(),
+                grouping_sets: None,
                 having: None,
                 order_by: Vec::new(),
+                distinct_on: Vec::new(),
             })
         };
         assert_eq!(actual, expected, "hash_join expr AND where_clause:\n{sql}");
@@ -979,6 +1036,7 @@ mod tests {
         let actual = plan_join(&storage, sql);
         let expected = {
             let subquery = Query {
+                with: Vec::new(),
                 body: SetExpr::Select(Box::new(Select {
                     projection: vec![SelectItem::Expr {
                         expr: expr("u3.id"),
@@ -998,8 +1056,10 @@ mod tests {
                     },
                     selection: None,
                     group_by: Vec::new(),
+                    grouping_sets: None,
                     having: None,
                     order_by: Vec::new(),
+                    distinct_on: Vec::new(),
                 })),
                 limit: None,
                 offset: None,
@@ -1021,8 +1081,10 @@ mod tests {
                     right: Box::new(Expr::Subquery(Box::new(subquery))),
                 }),
                 group_by: Vec::new(),
+                grouping_sets: None,
                 having: None,
                 order_by: Vec::new(),
+                distinct_on: Vec::new(),
             })
         };
         assert_eq!(actual, expected, "hash join in subquery:\n{sql}");
@@ -1047,6 +1109,7 @@ mod tests {
             };
 
             let join_subquery = Query {
+                with: Vec::new(),
                 body: SetExpr::Select(Box::new(Select {
                     projection: vec![SelectItem::Wildcard],
                     from: TableWithJoins {
@@ -1059,14 +1122,17 @@ mod tests {
                     },
                     selection: None,
                     group_by: Vec::new(),
+                    grouping_sets: None,
                     having: None,
                     order_by: Vec::new(),
+                    distinct_on: Vec::new(),
                 })),
                 limit: None,
                 offset: None,
             };
 
             let subquery = Query {
+                with: Vec::new(),
                 body: SetExpr::Select(Box::new(Select {
                     projection: vec![SelectItem::Wildcard],
                     from: TableWithJoins {
@@ -1079,8 +1145,10 @@ mod tests {
                         right: Box::new(Expr::Subquery(Box::new(join_subquery))),
                     }),
                     group_by: Vec::new(),
+                    grouping_sets: None,
                     having: None,
                     order_by: Vec::new(),
+                    distinct_on: Vec::new(),
                 })),
                 limit: None,
                 offset: None,
@@ -1098,8 +1166,10 @@ mod tests {
                     right: Box::new(Expr::Subquery(Box::new(subquery))),
                 }),
                 group_by: Vec::new(),
+                grouping_sets: None,
                 having: None,
                 order_by: Vec::new(),
+                distinct_on: Vec::new(),
             })
         };
         assert_eq!(actual, expected, "hash join in nested subquery:\n{sql}");
@@ -1136,8 +1206,10 @@ mod tests {
                 },
                 selection: Some(expr("True")),
                 group_by: Vec::new(),
+                grouping_sets: None,
                 having: None,
                 order_by: Vec::new(),
+                distinct_on: Vec::new(),
             })
         };
         assert_eq!(
@@ -1177,8 +1249,10 @@ mod tests {
                 },
                 selection: Some(expr("True")),
                 group_by: Vec::new(),
+                grouping_sets: None,
                 having: None,
                 order_by: Vec::new(),
+                distinct_on: Vec::new(),
             })
         };
         assert_eq!(
@@ -1214,6 +1288,7 @@ mod tests {
             };
 
             Box::new(Query {
+                with: Vec::new(),
                 body: SetExpr::Select(Box::new(Select {
                     projection: vec![SelectItem::Expr {
                         expr: expr("u.id"),
@@ -1229,8 +1304,10 @@ mod tests {
                     },
                     selection: None,
                     group_by: Vec::new(),
+                    grouping_sets: None,
                     having: None,
                     order_by: Vec::new(),
+                    distinct_on: Vec::new(),
                 })),
                 limit: None,
                 offset: None,
@@ -1247,8 +1324,10 @@ mod tests {
                 },
                 selection: Some(selection),
                 group_by: Vec::new(),
+                grouping_sets: None,
                 having: None,
                 order_by: Vec::new(),
+                distinct_on: Vec::new(),
             })
         };
 
@@ -10,7 +10,7 @@ use crate::store::Transaction;
 use crate::store::{Index, IndexMut};
 use {
     crate::{
-        data::{Key, Row, Schema},
+        data::{Key, Row, Schema, SchemaFunction},
         executor::execute,
         parse_sql::parse,
         result::{Error, MutResult, Result},
@@ -65,6 +65,16 @@ impl Store for MockStorage {
             "[MockStorage] scan_data not supported".to_owned(),
         ))
     }
+
+    async fn fetch_sequence(&self, _name: &str) -> Result<Option<i64>> {
+        Err(Error::StorageMsg(
+            "[MockStorage] fetch_sequence not supported".to_owned(),
+        ))
+    }
+
+    async fn fetch_functions(&self) -> Result<Vec<SchemaFunction>> {
+        Ok(Vec::new())
+    }
 }
 
 #[async_trait(?Send)]
@@ -102,6 +112,36 @@ impl StoreMut for MockStorage {
 
         Err((self, Error::StorageMsg(msg)))
     }
+
+    async fn truncate_data(self, _table_name: &str) -> MutResult<Self, ()> {
+        let msg = "[MockStorage] truncate_data is not supported".to_owned();
+
+        Err((self, Error::StorageMsg(msg)))
+    }
+
+    async fn update_sequence(self, _name: &str, _value: i64) -> MutResult<Self, ()> {
+        let msg = "[MockStorage] update_sequence is not supported".to_owned();
+
+        Err((self, Error::StorageMsg(msg)))
+    }
+
+    async fn delete_sequence(self, _name: &str) -> MutResult<Self, ()> {
+        let msg = "[MockStorage] delete_sequence is not supported".to_owned();
+
+        Err((self, Error::StorageMsg(msg)))
+    }
+
+    async fn insert_function(self, _function: &SchemaFunction) -> MutResult<Self, ()> {
+        let msg = "[MockStorage] insert_function is not supported".to_owned();
+
+        Err((self, Error::StorageMsg(msg)))
+    }
+
+    async fn delete_function(self, _name: &str) -> MutResult<Self, ()> {
+        let msg = "[MockStorage] delete_function is not supported".to_owned();
+
+        Err((self, Error::StorageMsg(msg)))
+    }
 }
 
 #[cfg(feature = "alter-table")]
@@ -197,6 +237,8 @@ mod tests {
                         value: "true".to_owned(),
                     },
                     asc: None,
+                    nulls_first: None,
+                    collation: None,
                 },
             ));
             let storage = test(storage.drop_index("Foo", "idx_col"));
@@ -1,27 +1,54 @@
+mod alias;
+mod collation;
 mod context;
 mod error;
 mod evaluable;
 mod expr;
 mod index;
 mod join;
+mod parameter;
 mod schema;
 mod validate;
+mod view;
 
 #[cfg(test)]
 mod mock;
 
 use crate::{ast::Statement, result::Result, store::Store};
 
+pub(crate) use {
+    context::Context,
+    evaluable::{check_expr as check_evaluable, check_query},
+    expr::PlanExpr,
+    parameter::{check_parameters, parameter_types},
+};
+
 pub use {
-    self::validate::validate, error::*, index::plan as plan_index, join::plan as plan_join,
-    schema::fetch_schema_map,
+    self::validate::validate, alias::plan as plan_alias, collation::plan as plan_collation,
+    error::*, index::plan as plan_index, join::plan as plan_join, parameter::ParameterError,
+    schema::fetch_schema_map, view::plan as plan_view,
 };
 
 pub async fn plan(storage: &dyn Store, statement: Statement) -> Result<Statement> {
+    match statement {
+        Statement::ExplainAnalyze(query) => plan_statement(storage, Statement::Query(query))
+            .await
+            .map(|statement| match statement {
+                Statement::Query(query) => Statement::ExplainAnalyze(query),
+                statement => statement,
+            }),
+        statement => plan_statement(storage, statement).await,
+    }
+}
+
+async fn plan_statement(storage: &dyn Store, statement: Statement) -> Result<Statement> {
     let schema_map = fetch_schema_map(storage, &statement).await?;
+    let (schema_map, statement) = plan_view(storage, schema_map, statement).await?;
 
     let statement = validate(&schema_map, statement)?;
 
+    let statement = plan_alias(&schema_map, statement);
+    let statement = plan_collation(&schema_map, statement);
     let statement = plan_index(&schema_map, statement)?;
     let statement = plan_join(&schema_map, statement);
 
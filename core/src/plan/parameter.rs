@@ -0,0 +1,592 @@
+use {
+    super::expr::PlanExpr,
+    crate::{
+        ast::{
+            Assignment, BinaryOperator, ColumnDef, Cte, DataType, Expr, Join, JoinConstraint,
+            JoinOperator, ObjectName, OrderByExpr, Query, Select, SelectItem, SetExpr, Statement,
+            TableAlias, TableFactor, TableWithJoins, Values,
+        },
+        data::{get_name, Schema, Value},
+        result::Result,
+    },
+    serde::Serialize,
+    std::{collections::HashMap, fmt::Debug, iter::once, rc::Rc},
+    thiserror::Error as ThisError,
+};
+
+#[derive(ThisError, Serialize, Debug, PartialEq)]
+pub enum ParameterError {
+    #[error("statement takes {expected} parameter(s), found: {found}")]
+    CountMismatch { expected: usize, found: usize },
+
+    #[error("parameter ${index} expects {expected}, found: {found:?}")]
+    TypeMismatch {
+        index: usize,
+        expected: DataType,
+        found: Value,
+    },
+
+    #[error("parameter ${0} can not be evaluated, parameters are only type-checked")]
+    NotBound(usize),
+
+    #[error("parameters are checked against a single statement, found: {0}")]
+    SingleStatementRequired(usize),
+}
+
+/// Parameters `$index` found in a statement, with the type of the column each is used with.
+type Found<'a> = Vec<(usize, Option<&'a DataType>)>;
+
+/// Types the parameters `$1`, `$2`, ... of `statement` take, inferred from where they are used.
+///
+/// A parameter compared with a column, e.g. `id = $1`, `id IN ($1, $2)` or
+/// `id BETWEEN $1 AND $2`, takes the type of the column, and so does one inserted into or
+/// assigned to a column. A parameter used nowhere else is of an unknown type, `None`.
+pub fn parameter_types(
+    schema_map: &HashMap<String, Schema>,
+    statement: &Statement,
+) -> Vec<Option<DataType>> {
+    let inference = Inference { schema_map };
+
+    let found = match statement {
+        Statement::Query(query) | Statement::ExplainAnalyze(query) => inference.query(None, query),
+        Statement::Insert {
+            table_name,
+            columns,
+            source,
+            ..
+        } => match &source.body {
+            SetExpr::Values(Values(rows)) if source.with.is_empty() => {
+                let column_defs = inference
+                    .table(None, table_name, None)
+                    .map(|(_, column_defs)| column_defs)
+                    .unwrap_or_default();
+                let column_defs = match columns.as_slice() {
+                    [] => column_defs.iter().collect::<Vec<_>>(),
+                    columns => columns
+                        .iter()
+                        .filter_map(|column| column_defs.iter().find(|def| &def.name == column))
+                        .collect(),
+                };
+                let scope = Rc::new(Scope::default());
+
+                rows.iter()
+                    .flat_map(|row| row.iter().enumerate())
+                    .flat_map(|(i, expr)| {
+                        let data_type = column_defs.get(i).map(|def| &def.data_type);
+
+                        inference.assign(&scope, data_type, expr)
+                    })
+                    .collect()
+            }
+            _ => inference.query(None, source),
+        },
+        Statement::Update {
+            table_name,
+            assignments,
+            selection,
+            ..
+        } => {
+            let scope = inference.table_scope(table_name);
+            let assignments = assignments.iter().flat_map(|Assignment { id, value }| {
+                let data_type = scope.find(None, id).map(|def| &def.data_type);
+
+                inference.assign(&scope, data_type, value)
+            });
+            let selection = selection
+                .iter()
+                .flat_map(|expr| inference.expr(&scope, expr));
+
+            assignments.chain(selection).collect()
+        }
+        Statement::Delete {
+            table_name,
+            selection: Some(expr),
+            ..
+        } => inference.expr(&inference.table_scope(table_name), expr),
+        _ => Vec::new(),
+    };
+
+    let count = found.iter().map(|(index, _)| *index).max().unwrap_or(0);
+
+    // the first known type of each parameter wins
+    (1..=count)
+        .map(|index| {
+            found
+                .iter()
+                .filter(|(i, _)| *i == index)
+                .find_map(|(_, data_type)| data_type.cloned())
+        })
+        .collect()
+}
+
+/// Checks the values given for the parameters of a statement against the `types` inferred for
+/// them by `parameter_types`, so a mismatch fails before anything runs.
+///
+/// NULL and the parameters of an unknown type take any value. A number of another numeric type
+/// is cast into the expected one, e.g. an `I64` given where an `INT8` column is compared.
+pub fn check_parameters(types: &[Option<DataType>], values: Vec<Value>) -> Result<Vec<Value>> {
+    if types.len() != values.len() {
+        return Err(ParameterError::CountMismatch {
+            expected: types.len(),
+            found: values.len(),
+        }
+        .into());
+    }
+
+    types
+        .iter()
+        .zip(values)
+        .enumerate()
+        .map(|(i, (data_type, value))| -> Result<Value> {
+            let data_type = match data_type {
+                Some(data_type) if value.validate_type(data_type).is_err() => data_type,
+                _ => return Ok(value),
+            };
+
+            let cast = match value.get_type() {
+                Some(found) if is_number(&found) && is_number(data_type) => {
+                    value.cast(data_type).ok()
+                }
+                _ => None,
+            };
+
+            cast.ok_or_else(|| {
+                ParameterError::TypeMismatch {
+                    index: i + 1,
+                    expected: data_type.clone(),
+                    found: value,
+                }
+                .into()
+            })
+        })
+        .collect()
+}
+
+/// Columns visible to an expression, the tables of its own `FROM` first and then the outer ones.
+#[derive(Default)]
+struct Scope<'a> {
+    tables: Vec<(String, &'a [ColumnDef])>,
+    /// Names of the common table expressions of a `WITH`, which shadow the tables of the storage
+    ctes: Vec<String>,
+    outer: Option<Rc<Scope<'a>>>,
+}
+
+impl<'a> Scope<'a> {
+    fn find(&self, alias: Option<&str>, column: &str) -> Option<&'a ColumnDef> {
+        self.tables
+            .iter()
+            .filter(|(name, _)| alias.map(|alias| alias == name).unwrap_or(true))
+            .find_map(|&(_, column_defs)| {
+                column_defs
+                    .iter()
+                    .find(|column_def| column_def.name == column)
+            })
+            .or_else(|| self.outer.as_ref()?.find(alias, column))
+    }
+
+    fn is_cte(&self, name: &str) -> bool {
+        self.ctes.iter().any(|cte| cte == name)
+            || self.outer.as_ref().map(|outer| outer.is_cte(name)) == Some(true)
+    }
+
+    fn data_type(&self, expr: &Expr) -> Option<&'a DataType> {
+        let column_def = match expr {
+            Expr::Identifier(column) => self.find(None, column),
+            Expr::CompoundIdentifier(idents) => match idents.as_slice() {
+                [alias, column] => self.find(Some(alias.as_str()), column),
+                _ => None,
+            },
+            Expr::Nested(expr) => return self.data_type(expr),
+            _ => None,
+        };
+
+        column_def.map(|column_def| &column_def.data_type)
+    }
+}
+
+struct Inference<'a> {
+    schema_map: &'a HashMap<String, Schema>,
+}
+
+impl<'a> Inference<'a> {
+    fn table(
+        &self,
+        outer: Option<&Rc<Scope<'a>>>,
+        name: &ObjectName,
+        alias: Option<&TableAlias>,
+    ) -> Option<(String, &'a [ColumnDef])> {
+        let name = get_name(name).ok()?;
+
+        if outer.map(|outer| outer.is_cte(name)) == Some(true) {
+            return None;
+        }
+
+        let Schema {
+            table_name,
+            column_defs,
+            ..
+        } = self.schema_map.get(name)?;
+
+        match alias {
+            None => Some((table_name.to_owned(), column_defs)),
+            Some(TableAlias { name, columns }) if columns.is_empty() => {
+                Some((name.to_owned(), column_defs))
+            }
+            // renamed columns are not looked up
+            Some(_) => None,
+        }
+    }
+
+    fn table_scope(&self, table_name: &ObjectName) -> Rc<Scope<'a>> {
+        Rc::new(Scope {
+            tables: self.table(None, table_name, None).into_iter().collect(),
+            ..Scope::default()
+        })
+    }
+
+    /// `expr` is stored into a column of `data_type`.
+    fn assign(
+        &self,
+        scope: &Rc<Scope<'a>>,
+        data_type: Option<&'a DataType>,
+        expr: &Expr,
+    ) -> Found<'a> {
+        match expr {
+            Expr::Placeholder(index) => vec![(*index, data_type)],
+            expr => self.expr(scope, expr),
+        }
+    }
+
+    /// Parameters among `exprs` take the type of `operand` if it is a column.
+    fn compare<'b>(
+        &self,
+        scope: &Rc<Scope<'a>>,
+        operand: &Expr,
+        exprs: impl IntoIterator<Item = &'b Expr>,
+    ) -> Found<'a> {
+        let data_type = match scope.data_type(operand) {
+            Some(data_type) => data_type,
+            None => return Vec::new(),
+        };
+
+        exprs
+            .into_iter()
+            .filter_map(|expr| match unnest(expr) {
+                Expr::Placeholder(index) => Some((*index, Some(data_type))),
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn query(&self, outer: Option<Rc<Scope<'a>>>, query: &Query) -> Found<'a> {
+        let Query {
+            with,
+            body,
+            limit,
+            offset,
+        } = query;
+
+        let outer = match with.is_empty() {
+            true => outer,
+            false => Some(Rc::new(Scope {
+                tables: Vec::new(),
+                ctes: with.iter().map(|cte| cte.alias.name.to_owned()).collect(),
+                outer,
+            })),
+        };
+
+        let ctes = with
+            .iter()
+            .flat_map(
+                |Cte {
+                     query, recursive, ..
+                 }| {
+                    once(query).chain(recursive.iter().map(|recursive| &recursive.query))
+                },
+            )
+            .flat_map(|query| self.query(outer.as_ref().map(Rc::clone), query));
+
+        let body = match body {
+            SetExpr::Select(select) => self.select(outer.as_ref().map(Rc::clone), select),
+            SetExpr::Values(Values(rows)) => {
+                let scope = Rc::new(Scope {
+                    outer: outer.as_ref().map(Rc::clone),
+                    ..Scope::default()
+                });
+
+                rows.iter()
+                    .flatten()
+                    .flat_map(|expr| self.expr(&scope, expr))
+                    .collect()
+            }
+        };
+
+        let scope = Rc::new(Scope {
+            outer: outer.as_ref().map(Rc::clone),
+            ..Scope::default()
+        });
+        let limit = limit
+            .iter()
+            .chain(offset)
+            .flat_map(|expr| self.expr(&scope, expr));
+
+        ctes.chain(body).chain(limit).collect()
+    }
+
+    fn select(&self, outer: Option<Rc<Scope<'a>>>, select: &Select) -> Found<'a> {
+        let Select {
+            projection,
+            from,
+            selection,
+            group_by,
+            having,
+            order_by,
+            ..
+        } = select;
+
+        let scope = Rc::new(Scope {
+            tables: self.tables(outer.as_ref(), from),
+            ctes: Vec::new(),
+            outer: outer.as_ref().map(Rc::clone),
+        });
+        let from = self.table_with_joins(outer, &scope, from);
+
+        let projection = projection
+            .iter()
+            .filter_map(|select_item| match select_item {
+                SelectItem::Expr { expr, .. } => Some(expr),
+                SelectItem::QualifiedWildcard(_) | SelectItem::Wildcard => None,
+            });
+        let order_by = order_by.iter().map(|OrderByExpr { expr, .. }| expr);
+        let exprs = projection
+            .chain(selection)
+            .chain(group_by)
+            .chain(having)
+            .chain(order_by)
+            .flat_map(|expr| self.expr(&scope, expr));
+
+        from.into_iter().chain(exprs).collect()
+    }
+
+    fn tables(
+        &self,
+        outer: Option<&Rc<Scope<'a>>>,
+        from: &TableWithJoins,
+    ) -> Vec<(String, &'a [ColumnDef])> {
+        let TableWithJoins { relation, joins } = from;
+
+        once(relation)
+            .chain(joins.iter().map(|Join { relation, .. }| relation))
+            .filter_map(|table_factor| match table_factor {
+                TableFactor::Table { name, alias, .. } => self.table(outer, name, alias.as_ref()),
+                TableFactor::Derived { .. } | TableFactor::Series { .. } => None,
+            })
+            .collect()
+    }
+
+    fn table_with_joins(
+        &self,
+        outer: Option<Rc<Scope<'a>>>,
+        scope: &Rc<Scope<'a>>,
+        table_with_joins: &TableWithJoins,
+    ) -> Found<'a> {
+        let TableWithJoins { relation, joins } = table_with_joins;
+
+        let relation = self.table_factor(outer.as_ref().map(Rc::clone), scope, relation);
+        let joins = joins.iter().flat_map(
+            |Join {
+                 relation,
+                 join_operator,
+                 ..
+             }| {
+                let relation = self.table_factor(outer.as_ref().map(Rc::clone), scope, relation);
+                let constraint = match join_operator {
+                    JoinOperator::Inner(JoinConstraint::On(expr))
+                    | JoinOperator::LeftOuter(JoinConstraint::On(expr)) => self.expr(scope, expr),
+                    JoinOperator::Inner(JoinConstraint::None)
+                    | JoinOperator::LeftOuter(JoinConstraint::None) => Vec::new(),
+                };
+
+                relation.into_iter().chain(constraint)
+            },
+        );
+
+        relation.into_iter().chain(joins).collect()
+    }
+
+    fn table_factor(
+        &self,
+        outer: Option<Rc<Scope<'a>>>,
+        scope: &Rc<Scope<'a>>,
+        table_factor: &TableFactor,
+    ) -> Found<'a> {
+        match table_factor {
+            TableFactor::Derived {
+                lateral, subquery, ..
+            } => {
+                // LATERAL subqueries see the columns of the preceding relations
+                let outer = if *lateral {
+                    Some(Rc::clone(scope))
+                } else {
+                    outer
+                };
+
+                self.query(outer, subquery)
+            }
+            TableFactor::Table { .. } | TableFactor::Series { .. } => Vec::new(),
+        }
+    }
+
+    fn expr(&self, scope: &Rc<Scope<'a>>, expr: &Expr) -> Found<'a> {
+        let compared = match expr {
+            Expr::Placeholder(index) => vec![(*index, None)],
+            Expr::BinaryOp { left, op, right } if is_comparison(op) => self
+                .compare(scope, left, once(right.as_ref()))
+                .into_iter()
+                .chain(self.compare(scope, right, once(left.as_ref())))
+                .collect(),
+            Expr::InList { expr, list, .. } => self.compare(scope, expr, list),
+            Expr::Between {
+                expr, low, high, ..
+            } => self.compare(scope, expr, [low.as_ref(), high.as_ref()]),
+            _ => Vec::new(),
+        };
+
+        let nested = match PlanExpr::from(expr) {
+            PlanExpr::None | PlanExpr::Identifier(_) | PlanExpr::CompoundIdentifier(_) => {
+                Vec::new()
+            }
+            PlanExpr::Expr(expr) => self.expr(scope, expr),
+            PlanExpr::TwoExprs(expr, expr2) => [expr, expr2]
+                .into_iter()
+                .flat_map(|expr| self.expr(scope, expr))
+                .collect(),
+            PlanExpr::ThreeExprs(expr, expr2, expr3) => [expr, expr2, expr3]
+                .into_iter()
+                .flat_map(|expr| self.expr(scope, expr))
+                .collect(),
+            PlanExpr::MultiExprs(exprs) => exprs
+                .into_iter()
+                .flat_map(|expr| self.expr(scope, expr))
+                .collect(),
+            PlanExpr::Query(query) => self.query(Some(Rc::clone(scope)), query),
+            PlanExpr::QueryAndExpr { query, expr } => self
+                .query(Some(Rc::clone(scope)), query)
+                .into_iter()
+                .chain(self.expr(scope, expr))
+                .collect(),
+        };
+
+        compared.into_iter().chain(nested).collect()
+    }
+}
+
+fn is_comparison(op: &BinaryOperator) -> bool {
+    matches!(
+        op,
+        BinaryOperator::Eq
+            | BinaryOperator::NotEq
+            | BinaryOperator::Lt
+            | BinaryOperator::LtEq
+            | BinaryOperator::Gt
+            | BinaryOperator::GtEq
+            | BinaryOperator::IsDistinctFrom
+            | BinaryOperator::IsNotDistinctFrom
+    )
+}
+
+fn is_number(data_type: &DataType) -> bool {
+    matches!(
+        data_type,
+        DataType::Int8
+            | DataType::Int16
+            | DataType::Int32
+            | DataType::Int
+            | DataType::Int128
+            | DataType::Float
+            | DataType::Decimal
+    )
+}
+
+fn unnest(expr: &Expr) -> &Expr {
+    match expr {
+        Expr::Nested(expr) => unnest(expr),
+        expr => expr,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::parameter_types,
+        crate::{
+            ast::DataType,
+            parse_sql::parse,
+            plan::{
+                fetch_schema_map,
+                mock::{run, MockStorage},
+            },
+            translate::translate,
+        },
+        futures::executor::block_on,
+    };
+
+    fn infer(storage: &MockStorage, sql: &str) -> Vec<Option<DataType>> {
+        let parsed = parse(sql).expect(sql).into_iter().next().unwrap();
+        let statement = translate(&parsed).expect(sql);
+        let schema_map = block_on(fetch_schema_map(storage, &statement)).expect(sql);
+
+        parameter_types(&schema_map, &statement)
+    }
+
+    #[test]
+    fn infer_types() {
+        let storage = run("
+            CREATE TABLE Player (id INTEGER, name TEXT);
+            CREATE TABLE Team (id INT(8), rank FLOAT);
+        ");
+
+        let actual = infer(&storage, "SELECT * FROM Player WHERE id = $1 AND $2 = name");
+        let expected = vec![Some(DataType::Int), Some(DataType::Text)];
+        assert_eq!(actual, expected, "comparison");
+
+        let actual = infer(
+            &storage,
+            "SELECT * FROM Player p JOIN Team t ON p.id = $2 WHERE t.rank BETWEEN $1 AND $3",
+        );
+        let expected = vec![
+            Some(DataType::Float),
+            Some(DataType::Int),
+            Some(DataType::Float),
+        ];
+        assert_eq!(actual, expected, "join and between");
+
+        let actual = infer(
+            &storage,
+            "SELECT $1 FROM Player WHERE id IN (SELECT id FROM Team WHERE id IN ($2, $3))",
+        );
+        let expected = vec![None, Some(DataType::Int8), Some(DataType::Int8)];
+        assert_eq!(actual, expected, "subquery");
+
+        let actual = infer(
+            &storage,
+            "WITH Player AS (SELECT id FROM Team) SELECT * FROM Player WHERE id = $1",
+        );
+        assert_eq!(actual, vec![None], "common table expression");
+
+        let actual = infer(&storage, "INSERT INTO Player (name, id) VALUES ($1, $2)");
+        let expected = vec![Some(DataType::Text), Some(DataType::Int)];
+        assert_eq!(actual, expected, "insert");
+
+        let actual = infer(&storage, "UPDATE Team SET rank = $2 WHERE id = $1");
+        let expected = vec![Some(DataType::Int8), Some(DataType::Float)];
+        assert_eq!(actual, expected, "update");
+
+        let actual = infer(
+            &storage,
+            "DELETE FROM Player WHERE name = $1 OR id + $2 > 3",
+        );
+        let expected = vec![Some(DataType::Text), None];
+        assert_eq!(actual, expected, "delete");
+    }
+}
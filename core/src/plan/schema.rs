@@ -2,8 +2,8 @@ use {
     super::expr::PlanExpr,
     crate::{
         ast::{
-            Expr, Join, JoinConstraint, JoinOperator, Query, Select, SelectItem, SetExpr,
-            Statement, TableFactor, TableWithJoins,
+            Expr, GroupingSets, Join, JoinConstraint, JoinOperator, Query, Select, SelectItem,
+            SetExpr, Statement, TableFactor, TableWithJoins,
         },
         data::{get_name, Schema},
         result::Result,
@@ -14,20 +14,31 @@ use {
         future,
         stream::{self, StreamExt, TryStreamExt},
     },
-    std::collections::HashMap,
+    std::{collections::HashMap, iter::once},
 };
 
+#[cfg(feature = "metadata")]
+use crate::data::InformationSchema;
+
 pub async fn fetch_schema_map(
     storage: &dyn Store,
     statement: &Statement,
 ) -> Result<HashMap<String, Schema>> {
     match statement {
-        Statement::Query(query) => scan_query(storage, query).await.map(|schema_list| {
-            schema_list
-                .into_iter()
-                .map(|schema| (schema.table_name.clone(), schema))
-                .collect::<HashMap<_, _>>()
-        }),
+        Statement::Query(query)
+        | Statement::CreateTable {
+            source: Some(query),
+            ..
+        }
+        | Statement::CreateView { query, .. }
+        | Statement::CreateMaterializedView { query, .. } => {
+            scan_query(storage, query).await.map(|schema_list| {
+                schema_list
+                    .into_iter()
+                    .map(|schema| (schema.table_name.clone(), schema))
+                    .collect::<HashMap<_, _>>()
+            })
+        }
         Statement::Insert {
             table_name, source, ..
         } => {
@@ -46,6 +57,34 @@ pub async fn fetch_schema_map(
 
             Ok(schema_list)
         }
+        Statement::Update {
+            table_name,
+            selection,
+            ..
+        }
+        | Statement::Delete {
+            table_name,
+            selection,
+            ..
+        } => {
+            let table_name = get_name(table_name)?;
+            let table_schema = storage
+                .fetch_schema(table_name)
+                .await?
+                .map(|schema| vec![schema])
+                .unwrap_or_else(Vec::new);
+            let selection_schema_list = match selection {
+                Some(expr) => scan_expr(storage, expr).await?,
+                None => Vec::new(),
+            };
+            let schema_list = [table_schema, selection_schema_list]
+                .into_iter()
+                .flatten()
+                .map(|schema| (schema.table_name.clone(), schema))
+                .collect();
+
+            Ok(schema_list)
+        }
         Statement::DropTable { names, .. } => {
             stream::iter(names)
                 .map(Ok)
@@ -64,17 +103,35 @@ pub async fn fetch_schema_map(
     }
 }
 
-async fn scan_query(storage: &dyn Store, query: &Query) -> Result<Vec<Schema>> {
+#[async_recursion(?Send)]
+pub(super) async fn scan_query(storage: &dyn Store, query: &Query) -> Result<Vec<Schema>> {
     let Query {
+        with,
         body,
         limit,
         offset,
     } = query;
 
+    let queries = with.iter().flat_map(|cte| {
+        let recursive = cte.recursive.iter().map(|recursive| &recursive.query);
+
+        once(&cte.query).chain(recursive)
+    });
+    let with_schema_list = stream::iter(queries)
+        .then(|query| scan_query(storage, query))
+        .try_collect::<Vec<Vec<Schema>>>()
+        .await?
+        .into_iter()
+        .flatten();
+
     let schema_list = match body {
         SetExpr::Select(select) => scan_select(storage, select).await?,
         SetExpr::Values(_) => Vec::new(),
     };
+    let schema_list = schema_list
+        .into_iter()
+        .chain(with_schema_list)
+        .collect::<Vec<_>>();
 
     let schema_list = match (limit, offset) {
         (Some(limit), Some(offset)) => schema_list
@@ -98,8 +155,10 @@ async fn scan_select(storage: &dyn Store, select: &Select) -> Result<Vec<Schema>
         from,
         selection,
         group_by,
+        grouping_sets,
         having,
         order_by,
+        ..
     } = select;
 
     let projection = stream::iter(projection)
@@ -119,6 +178,7 @@ async fn scan_select(storage: &dyn Store, select: &Select) -> Result<Vec<Schema>
     let exprs = selection
         .iter()
         .chain(group_by.iter())
+        .chain(grouping_sets.iter().flat_map(GroupingSets::columns))
         .chain(having.iter())
         .chain(order_by.iter().map(|order_by| &order_by.expr));
 
@@ -176,6 +236,11 @@ async fn scan_join(storage: &dyn Store, join: &Join) -> Result<Vec<Schema>> {
 async fn scan_table_factor(storage: &dyn Store, table_factor: &TableFactor) -> Result<Vec<Schema>> {
     match table_factor {
         TableFactor::Table { name, .. } => {
+            #[cfg(feature = "metadata")]
+            if InformationSchema::from_name(name).is_some() {
+                return Ok(Vec::new());
+            }
+
             let table_name = get_name(name)?;
             let schema = storage.fetch_schema(table_name).await?;
             let schema_list = schema.map(|schema| vec![schema]).unwrap_or_else(Vec::new);
@@ -183,6 +248,20 @@ async fn scan_table_factor(storage: &dyn Store, table_factor: &TableFactor) -> R
             Ok(schema_list)
         }
         TableFactor::Derived { subquery, .. } => scan_query(storage, subquery).await,
+        TableFactor::Series {
+            start, end, step, ..
+        } => {
+            let exprs = [start, end].into_iter().chain(step);
+            let schema_list = stream::iter(exprs)
+                .then(|expr| scan_expr(storage, expr))
+                .try_collect::<Vec<Vec<_>>>()
+                .await?
+                .into_iter()
+                .flatten()
+                .collect();
+
+            Ok(schema_list)
+        }
     }
 }
 
@@ -266,6 +345,12 @@ mod tests {
         test("SELECT * FROM Foo", &["Foo"]);
         test("INSERT INTO Foo VALUES (1), (2), (3);", &["Foo"]);
         test("DROP TABLE Foo, Bar;", &["Bar", "Foo"]);
+        test(
+            "WITH Baz AS (SELECT * FROM Bar) SELECT * FROM Foo",
+            &["Bar", "Foo"],
+        );
+        test("CREATE TABLE Baz AS SELECT * FROM Foo", &["Foo"]);
+        test("CREATE VIEW Baz AS SELECT * FROM Bar", &["Bar"]);
 
         // Unimplemented
         test("DELETE FROM Foo;", &[]);
@@ -22,10 +22,9 @@ pub fn validate(schema_map: &HashMap<String, Schema>, statement: Statement) -> R
                             ..
                         } = select_item
                         {
-                            let tables_with_given_col =
-                                schema_map.iter().filter_map(|(_, schema)| {
-                                    schema.column_defs.iter().find(|col| &col.name == ident)
-                                });
+                            let tables_with_given_col = schema_map.iter().filter(|(_, schema)| {
+                                schema.column_names().contains(&ident.as_str())
+                            });
 
                             if tables_with_given_col.count() > 1 {
                                 return Err(
@@ -0,0 +1,360 @@
+use {
+    super::{schema::scan_query, PlanError},
+    crate::{
+        ast::{
+            Aggregate, Assignment, CountArgExpr, Cte, Expr, GroupingSets, Join, JoinConstraint,
+            JoinOperator, OrderByExpr, Query, RecursiveTerm, Select, SelectItem, SetExpr,
+            Statement, TableAlias, TableFactor, TableWithJoins, Values, Window, WindowFunction,
+            WindowSpec,
+        },
+        data::{get_name, Schema, SchemaView},
+        result::Result,
+        store::Store,
+    },
+    std::collections::{HashMap, HashSet},
+};
+
+/// Replaces each reference to a view with a derived table of its query, so a view goes through
+/// the same planners and the same streaming execution as a subquery written in its place.
+///
+/// Views are taken out of `schema_map`, so each is looked up once per statement, and the tables
+/// their queries read are added to it for the planners which run next. A view defined on other
+/// views holds their queries as they were when it was created, so its query needs no expansion.
+pub async fn plan(
+    storage: &dyn Store,
+    schema_map: HashMap<String, Schema>,
+    mut statement: Statement,
+) -> Result<(HashMap<String, Schema>, Statement)> {
+    let (views, mut schema_map): (HashMap<_, _>, HashMap<_, _>) = schema_map
+        .into_iter()
+        .partition(|(_, schema)| schema.view.is_some());
+
+    if views.is_empty() {
+        return Ok((schema_map, statement));
+    }
+
+    let views = views
+        .into_iter()
+        .filter_map(|(table_name, schema)| Some((table_name, schema.view?)))
+        .collect::<HashMap<_, _>>();
+
+    for SchemaView { query, .. } in views.values() {
+        for schema in scan_query(storage, query).await? {
+            schema_map.insert(schema.table_name.clone(), schema);
+        }
+    }
+
+    Planner { views: &views }.statement(&mut statement)?;
+
+    Ok((schema_map, statement))
+}
+
+/// Names of the common table expressions in scope, which shadow the views of the same name.
+type Ctes = HashSet<String>;
+
+struct Planner<'a> {
+    views: &'a HashMap<String, SchemaView>,
+}
+
+impl<'a> Planner<'a> {
+    fn statement(&self, statement: &mut Statement) -> Result<()> {
+        let ctes = Ctes::new();
+
+        match statement {
+            Statement::Query(query)
+            | Statement::Insert { source: query, .. }
+            | Statement::CreateTable {
+                source: Some(query),
+                ..
+            }
+            | Statement::CreateView { query, .. }
+            | Statement::CreateMaterializedView { query, .. } => self.query(&ctes, query),
+            Statement::Update {
+                assignments,
+                selection,
+                ..
+            } => assignments
+                .iter_mut()
+                .map(|Assignment { value, .. }| value)
+                .chain(selection.iter_mut())
+                .try_for_each(|expr| self.expr(&ctes, expr)),
+            Statement::Delete { selection, .. } => selection
+                .iter_mut()
+                .try_for_each(|expr| self.expr(&ctes, expr)),
+            _ => Ok(()),
+        }
+    }
+
+    fn query(&self, ctes: &Ctes, query: &mut Query) -> Result<()> {
+        let Query {
+            with,
+            body,
+            limit,
+            offset,
+        } = query;
+
+        let mut ctes = ctes.clone();
+        for Cte {
+            alias,
+            query,
+            recursive,
+        } in with
+        {
+            self.query(&ctes, query)?;
+            ctes.insert(alias.name.to_owned());
+
+            if let Some(RecursiveTerm { query, .. }) = recursive {
+                self.query(&ctes, query)?;
+            }
+        }
+
+        match body {
+            SetExpr::Select(select) => self.select(&ctes, select)?,
+            SetExpr::Values(Values(values_list)) => values_list
+                .iter_mut()
+                .flatten()
+                .try_for_each(|expr| self.expr(&ctes, expr))?,
+        }
+
+        limit
+            .iter_mut()
+            .chain(offset.iter_mut())
+            .try_for_each(|expr| self.expr(&ctes, expr))
+    }
+
+    fn select(&self, ctes: &Ctes, select: &mut Select) -> Result<()> {
+        let Select {
+            projection,
+            from: TableWithJoins { relation, joins },
+            selection,
+            group_by,
+            grouping_sets,
+            having,
+            order_by,
+            distinct_on,
+        } = select;
+
+        self.table_factor(ctes, relation)?;
+
+        for Join {
+            relation,
+            join_operator,
+            ..
+        } in joins
+        {
+            self.table_factor(ctes, relation)?;
+
+            if let JoinOperator::Inner(JoinConstraint::On(expr))
+            | JoinOperator::LeftOuter(JoinConstraint::On(expr)) = join_operator
+            {
+                self.expr(ctes, expr)?;
+            }
+        }
+
+        let grouping_sets = match grouping_sets {
+            Some(GroupingSets::Cube(exprs)) => exprs.iter_mut().collect(),
+            Some(GroupingSets::Sets(sets)) => sets.iter_mut().flatten().collect(),
+            None => Vec::new(),
+        };
+
+        projection
+            .iter_mut()
+            .filter_map(|select_item| match select_item {
+                SelectItem::Expr { expr, .. } => Some(expr),
+                SelectItem::QualifiedWildcard(_) | SelectItem::Wildcard => None,
+            })
+            .chain(selection.iter_mut())
+            .chain(group_by.iter_mut())
+            .chain(grouping_sets)
+            .chain(having.iter_mut())
+            .chain(distinct_on.iter_mut())
+            .chain(order_by.iter_mut().map(|OrderByExpr { expr, .. }| expr))
+            .try_for_each(|expr| self.expr(ctes, expr))
+    }
+
+    fn table_factor(&self, ctes: &Ctes, table_factor: &mut TableFactor) -> Result<()> {
+        match table_factor {
+            TableFactor::Table {
+                name,
+                alias,
+                sample,
+                ..
+            } => {
+                let table_name = get_name(name)?;
+                let view = match self.views.get(table_name) {
+                    Some(view) if !ctes.contains(table_name) => view,
+                    _ => return Ok(()),
+                };
+
+                if sample.is_some() {
+                    return Err(PlanError::TableSampleOnView(table_name.to_owned()).into());
+                }
+
+                // columns renamed by the alias take precedence over the columns of the view
+                let alias = match alias.take() {
+                    Some(TableAlias { name, columns }) if columns.is_empty() => TableAlias {
+                        name,
+                        columns: view.columns.clone(),
+                    },
+                    Some(alias) => alias,
+                    None => TableAlias {
+                        name: table_name.to_owned(),
+                        columns: view.columns.clone(),
+                    },
+                };
+
+                *table_factor = TableFactor::Derived {
+                    lateral: false,
+                    subquery: view.query.clone(),
+                    alias,
+                };
+
+                Ok(())
+            }
+            TableFactor::Derived { subquery, .. } => self.query(ctes, subquery),
+            TableFactor::Series {
+                start, end, step, ..
+            } => [start, end]
+                .into_iter()
+                .chain(step.iter_mut())
+                .try_for_each(|expr| self.expr(ctes, expr)),
+        }
+    }
+
+    fn expr(&self, ctes: &Ctes, expr: &mut Expr) -> Result<()> {
+        match expr {
+            Expr::IsNull(expr)
+            | Expr::IsNotNull(expr)
+            | Expr::Nested(expr)
+            | Expr::UnaryOp { expr, .. }
+            | Expr::Cast { expr, .. }
+            | Expr::Extract { expr, .. } => self.expr(ctes, expr),
+            Expr::InList { expr, list, .. } => {
+                self.expr(ctes, expr)?;
+
+                list.iter_mut().try_for_each(|expr| self.expr(ctes, expr))
+            }
+            Expr::InSubquery { expr, subquery, .. } => {
+                self.expr(ctes, expr)?;
+
+                self.query(ctes, subquery)
+            }
+            Expr::Between {
+                expr, low, high, ..
+            } => [expr, low, high]
+                .into_iter()
+                .try_for_each(|expr| self.expr(ctes, expr)),
+            Expr::BinaryOp { left, right, .. }
+            | Expr::AnyOp { left, right, .. }
+            | Expr::AllOp { left, right, .. } => {
+                self.expr(ctes, left)?;
+
+                self.expr(ctes, right)
+            }
+            Expr::Tuple(exprs) => exprs.iter_mut().try_for_each(|expr| self.expr(ctes, expr)),
+            Expr::Identifier(_)
+            | Expr::CompoundIdentifier(_)
+            | Expr::Literal(_)
+            | Expr::Placeholder(_)
+            | Expr::TypedString { .. } => Ok(()),
+            Expr::Function(function) => function
+                .as_exprs_mut()
+                .try_for_each(|expr| self.expr(ctes, expr)),
+            Expr::Aggregate(aggregate) => self.aggregate(ctes, aggregate),
+            Expr::Window(window) => self.window(ctes, window),
+            Expr::Exists(query) | Expr::Subquery(query) => self.query(ctes, query),
+            Expr::Case {
+                operand,
+                when_then,
+                else_result,
+            } => operand
+                .iter_mut()
+                .map(|expr| &mut **expr)
+                .chain(when_then.iter_mut().flat_map(|(when, then)| [when, then]))
+                .chain(else_result.iter_mut().map(|expr| &mut **expr))
+                .try_for_each(|expr| self.expr(ctes, expr)),
+        }
+    }
+
+    fn aggregate(&self, ctes: &Ctes, aggregate: &mut Aggregate) -> Result<()> {
+        match aggregate {
+            Aggregate::Count(CountArgExpr::Wildcard) => Ok(()),
+            Aggregate::Count(CountArgExpr::Expr(expr))
+            | Aggregate::Sum(expr)
+            | Aggregate::Max(expr)
+            | Aggregate::Min(expr)
+            | Aggregate::Avg(expr)
+            | Aggregate::Variance(expr)
+            | Aggregate::Stdev(expr)
+            | Aggregate::BoolAnd(expr)
+            | Aggregate::BoolOr(expr)
+            | Aggregate::JsonAgg(expr)
+            | Aggregate::Custom { expr, .. } => self.expr(ctes, expr),
+            Aggregate::ArrayAgg { expr, order_by } => {
+                self.expr(ctes, expr)?;
+
+                match order_by {
+                    Some(OrderByExpr { expr, .. }) => self.expr(ctes, expr),
+                    None => Ok(()),
+                }
+            }
+            Aggregate::JsonObjectAgg { key, value } => {
+                self.expr(ctes, key)?;
+
+                self.expr(ctes, value)
+            }
+            Aggregate::Filter { aggregate, filter } => {
+                self.aggregate(ctes, aggregate)?;
+
+                self.expr(ctes, filter)
+            }
+            Aggregate::Grouping(exprs) => {
+                exprs.iter_mut().try_for_each(|expr| self.expr(ctes, expr))
+            }
+        }
+    }
+
+    fn window(&self, ctes: &Ctes, window: &mut Window) -> Result<()> {
+        let Window {
+            function,
+            over:
+                WindowSpec {
+                    partition_by,
+                    order_by,
+                    ..
+                },
+        } = window;
+
+        match function {
+            WindowFunction::FirstValue(expr) | WindowFunction::LastValue(expr) => {
+                self.expr(ctes, expr)?
+            }
+            WindowFunction::NthValue { expr, n } => {
+                self.expr(ctes, expr)?;
+                self.expr(ctes, n)?;
+            }
+            WindowFunction::Lag {
+                expr,
+                offset,
+                default,
+            }
+            | WindowFunction::Lead {
+                expr,
+                offset,
+                default,
+            } => {
+                self.expr(ctes, expr)?;
+                offset
+                    .iter_mut()
+                    .chain(default.iter_mut())
+                    .try_for_each(|expr| self.expr(ctes, expr))?;
+            }
+            WindowFunction::Aggregate(aggregate) => self.aggregate(ctes, aggregate)?,
+        }
+
+        partition_by
+            .iter_mut()
+            .chain(order_by.iter_mut().map(|OrderByExpr { expr, .. }| expr))
+            .try_for_each(|expr| self.expr(ctes, expr))
+    }
+}
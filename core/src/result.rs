@@ -4,10 +4,11 @@ use {
             IntervalError, KeyError, LiteralError, RowError, StringExtError, TableError, ValueError,
         },
         executor::{
-            AggregateError, AlterError, EvaluateError, ExecuteError, FetchError, SelectError,
-            UpdateError, ValidateError,
+            AggregateError, AlterError, CopyError, DeserializeError, EvaluateError, ExecuteError,
+            FetchError, JoinError, MergeError, SelectError, SequenceError, SessionError, UdfError,
+            UpdateError, ValidateError, WindowError,
         },
-        plan::PlanError,
+        plan::{ParameterError, PlanError},
         store::{GStore, GStoreMut},
         translate::TranslateError,
     },
@@ -58,10 +59,24 @@ pub enum Error {
     #[error(transparent)]
     Select(#[from] SelectError),
     #[error(transparent)]
+    Sequence(#[from] SequenceError),
+    #[error(transparent)]
+    Session(#[from] SessionError),
+    #[error(transparent)]
+    Parameter(#[from] ParameterError),
+    #[error(transparent)]
+    Udf(#[from] UdfError),
+    #[error(transparent)]
     Aggregate(#[from] AggregateError),
     #[error(transparent)]
+    Window(#[from] WindowError),
+    #[error(transparent)]
     Update(#[from] UpdateError),
     #[error(transparent)]
+    Join(#[from] JoinError),
+    #[error(transparent)]
+    Merge(#[from] MergeError),
+    #[error(transparent)]
     Row(#[from] RowError),
     #[error(transparent)]
     Table(#[from] TableError),
@@ -79,6 +94,10 @@ pub enum Error {
     StringExt(#[from] StringExtError),
     #[error(transparent)]
     Plan(#[from] PlanError),
+    #[error(transparent)]
+    Copy(#[from] CopyError),
+    #[error(transparent)]
+    Deserialize(#[from] DeserializeError),
 }
 
 pub type Result<T> = std::result::Result<T, Error>;
@@ -101,8 +120,15 @@ impl PartialEq for Error {
             (Fetch(e), Fetch(e2)) => e == e2,
             (Evaluate(e), Evaluate(e2)) => e == e2,
             (Select(e), Select(e2)) => e == e2,
+            (Sequence(e), Sequence(e2)) => e == e2,
+            (Session(e), Session(e2)) => e == e2,
+            (Parameter(e), Parameter(e2)) => e == e2,
+            (Udf(e), Udf(e2)) => e == e2,
             (Aggregate(e), Aggregate(e2)) => e == e2,
+            (Window(e), Window(e2)) => e == e2,
             (Update(e), Update(e2)) => e == e2,
+            (Join(e), Join(e2)) => e == e2,
+            (Merge(e), Merge(e2)) => e == e2,
             (Row(e), Row(e2)) => e == e2,
             (Table(e), Table(e2)) => e == e2,
             (Validate(e), Validate(e2)) => e == e2,
@@ -112,6 +138,8 @@ impl PartialEq for Error {
             (Interval(e), Interval(e2)) => e == e2,
             (StringExt(e), StringExt(e2)) => e == e2,
             (Plan(e), Plan(e2)) => e == e2,
+            (Copy(e), Copy(e2)) => e == e2,
+            (Deserialize(e), Deserialize(e2)) => e == e2,
             _ => false,
         }
     }
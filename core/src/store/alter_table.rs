@@ -1,6 +1,6 @@
 use {
     crate::{
-        ast::ColumnDef,
+        ast::{ColumnDef, DataType},
         result::{Error, MutResult},
     },
     async_trait::async_trait,
@@ -25,6 +25,9 @@ pub enum AlterTableError {
 
     #[error("Dropping column not found: {0}")]
     DroppingColumnNotFound(String),
+
+    #[error("Altering column not found: {0}")]
+    AlteringColumnNotFound(String),
 }
 
 #[async_trait(?Send)]
@@ -65,4 +68,17 @@ where
 
         Err((self, Error::StorageMsg(msg)))
     }
+
+    /// Changes the data type of a column, casting every stored value.
+    /// Nothing is changed if any value fails to convert.
+    async fn alter_column_type(
+        self,
+        _table_name: &str,
+        _column_name: &str,
+        _data_type: &DataType,
+    ) -> MutResult<Self, ()> {
+        let msg = "[Storage] AlterTable::alter_column_type is not supported".to_owned();
+
+        Err((self, Error::StorageMsg(msg)))
+    }
 }
@@ -7,7 +7,7 @@ use {
     },
     async_trait::async_trait,
     serde::Serialize,
-    std::fmt::Debug,
+    std::{fmt::Debug, ops::Bound},
     thiserror::Error as ThisError,
 };
 
@@ -51,6 +51,47 @@ pub trait Index {
             "[Storage] Index::scan_indexed_data is not supported".to_owned(),
         ))
     }
+
+    /// Rows whose index values lie between `start` and `end`.
+    ///
+    /// By default, a range with only one of its bounds is scanned by `scan_indexed_data`.
+    async fn scan_indexed_range(
+        &self,
+        table_name: &str,
+        index_name: &str,
+        asc: Option<bool>,
+        start: Bound<Value>,
+        end: Bound<Value>,
+    ) -> Result<RowIter> {
+        let cmp_value = match (start, end) {
+            (Bound::Unbounded, Bound::Unbounded) => None,
+            (Bound::Included(value), Bound::Unbounded) => Some((&IndexOperator::GtEq, value)),
+            (Bound::Excluded(value), Bound::Unbounded) => Some((&IndexOperator::Gt, value)),
+            (Bound::Unbounded, Bound::Included(value)) => Some((&IndexOperator::LtEq, value)),
+            (Bound::Unbounded, Bound::Excluded(value)) => Some((&IndexOperator::Lt, value)),
+            _ => {
+                return Err(Error::StorageMsg(
+                    "[Storage] Index::scan_indexed_range is not supported".to_owned(),
+                ));
+            }
+        };
+
+        self.scan_indexed_data(table_name, index_name, asc, cmp_value)
+            .await
+    }
+
+    /// Rows of a composite index whose leading columns equal `values`.
+    async fn scan_indexed_prefix(
+        &self,
+        _table_name: &str,
+        _index_name: &str,
+        _asc: Option<bool>,
+        _values: Vec<Value>,
+    ) -> Result<RowIter> {
+        Err(Error::StorageMsg(
+            "[Storage] Index::scan_indexed_prefix is not supported".to_owned(),
+        ))
+    }
 }
 
 #[async_trait(?Send)]
@@ -1,5 +1,9 @@
 use cfg_if::cfg_if;
 
+mod schema_cache;
+
+pub use schema_cache::SchemaCache;
+
 cfg_if! {
     if #[cfg(feature = "alter-table")] {
         mod alter_table;
@@ -63,7 +67,7 @@ cfg_if! {
 
 use {
     crate::{
-        data::{Key, Row, Schema},
+        data::{Key, Row, Schema, SchemaFunction},
         result::{MutResult, Result},
     },
     async_trait::async_trait,
@@ -77,10 +81,16 @@ pub trait Store {
     async fn fetch_schema(&self, table_name: &str) -> Result<Option<Schema>>;
 
     async fn scan_data(&self, table_name: &str) -> Result<RowIter>;
+
+    /// Last value of the sequence, or `None` if it has never been updated.
+    async fn fetch_sequence(&self, name: &str) -> Result<Option<i64>>;
+
+    /// Every function created by `CREATE FUNCTION`.
+    async fn fetch_functions(&self) -> Result<Vec<SchemaFunction>>;
 }
 
 /// By implementing `StoreMut` trait,
-/// you can run `INSERT`, `CREATE TABLE`, `DELETE`, `UPDATE` and `DROP TABLE` queries.
+/// you can run `INSERT`, `CREATE TABLE`, `DELETE`, `UPDATE`, `TRUNCATE` and `DROP TABLE` queries.
 #[async_trait(?Send)]
 pub trait StoreMut
 where
@@ -95,4 +105,31 @@ where
     async fn update_data(self, table_name: &str, rows: Vec<(Key, Row)>) -> MutResult<Self, ()>;
 
     async fn delete_data(self, table_name: &str, keys: Vec<Key>) -> MutResult<Self, ()>;
+
+    /// Deletes every row of the table at once, keeping its schema.
+    ///
+    /// By default the rows are scanned and deleted by their keys, storages which can drop the
+    /// rows of a table in one go should override it.
+    async fn truncate_data(self, table_name: &str) -> MutResult<Self, ()>
+    where
+        Self: Store,
+    {
+        let keys = self.scan_data(table_name).await.and_then(|rows| {
+            rows.map(|item| item.map(|(key, _)| key))
+                .collect::<Result<Vec<_>>>()
+        });
+
+        match keys {
+            Ok(keys) => self.delete_data(table_name, keys).await,
+            Err(e) => Err((self, e)),
+        }
+    }
+
+    async fn update_sequence(self, name: &str, value: i64) -> MutResult<Self, ()>;
+
+    async fn delete_sequence(self, name: &str) -> MutResult<Self, ()>;
+
+    async fn insert_function(self, function: &SchemaFunction) -> MutResult<Self, ()>;
+
+    async fn delete_function(self, name: &str) -> MutResult<Self, ()>;
 }
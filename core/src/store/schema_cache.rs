@@ -0,0 +1,320 @@
+use {
+    super::{GStore, GStoreMut, RowIter, Store, StoreMut},
+    crate::{
+        data::{Key, Row, Schema, SchemaFunction},
+        result::{MutResult, Result},
+    },
+    async_trait::async_trait,
+    std::{
+        cell::{Cell, RefCell},
+        collections::HashMap,
+        future::Future,
+    },
+};
+
+#[cfg(feature = "alter-table")]
+use {
+    super::AlterTable,
+    crate::ast::{ColumnDef, DataType},
+};
+
+#[cfg(feature = "index")]
+use {
+    super::{Index, IndexMut},
+    crate::{
+        ast::{IndexOperator, OrderByExpr},
+        data::Value,
+    },
+    std::ops::Bound,
+};
+
+#[cfg(feature = "metadata")]
+use super::Metadata;
+
+#[cfg(feature = "transaction")]
+use super::Transaction;
+
+/// Storage wrapper which keeps every fetched schema, so repeated queries do not read
+/// the same schema from the wrapped storage again.
+///
+/// Cached schemas are dropped by `CREATE TABLE`, `DROP TABLE`, `ALTER TABLE`,
+/// index changes and `ROLLBACK`.
+pub struct SchemaCache<T> {
+    storage: T,
+    schemas: RefCell<HashMap<String, Option<Schema>>>,
+    fetch_count: Cell<usize>,
+}
+
+impl<T> SchemaCache<T> {
+    pub fn new(storage: T) -> Self {
+        Self {
+            storage,
+            schemas: RefCell::new(HashMap::new()),
+            fetch_count: Cell::new(0),
+        }
+    }
+
+    pub fn into_inner(self) -> T {
+        self.storage
+    }
+
+    /// Number of schema reads passed through to the wrapped storage.
+    pub fn fetch_count(&self) -> usize {
+        self.fetch_count.get()
+    }
+
+    fn invalidate(&mut self, table_name: &str) {
+        self.schemas.get_mut().remove(table_name);
+    }
+
+    async fn forward<U, F, Fut>(self, f: F) -> MutResult<Self, U>
+    where
+        F: FnOnce(T) -> Fut,
+        Fut: Future<Output = MutResult<T, U>>,
+    {
+        let Self {
+            storage,
+            schemas,
+            fetch_count,
+        } = self;
+        let wrap = |storage| Self {
+            storage,
+            schemas,
+            fetch_count,
+        };
+
+        match f(storage).await {
+            Ok((storage, v)) => Ok((wrap(storage), v)),
+            Err((storage, e)) => Err((wrap(storage), e)),
+        }
+    }
+}
+
+#[async_trait(?Send)]
+impl<T: Store> Store for SchemaCache<T> {
+    async fn fetch_schema(&self, table_name: &str) -> Result<Option<Schema>> {
+        if let Some(schema) = self.schemas.borrow().get(table_name) {
+            return Ok(schema.clone());
+        }
+
+        let schema = self.storage.fetch_schema(table_name).await?;
+        self.fetch_count.set(self.fetch_count.get() + 1);
+        self.schemas
+            .borrow_mut()
+            .insert(table_name.to_owned(), schema.clone());
+
+        Ok(schema)
+    }
+
+    async fn scan_data(&self, table_name: &str) -> Result<RowIter> {
+        self.storage.scan_data(table_name).await
+    }
+
+    async fn fetch_sequence(&self, name: &str) -> Result<Option<i64>> {
+        self.storage.fetch_sequence(name).await
+    }
+
+    async fn fetch_functions(&self) -> Result<Vec<SchemaFunction>> {
+        self.storage.fetch_functions().await
+    }
+}
+
+#[async_trait(?Send)]
+impl<T: StoreMut> StoreMut for SchemaCache<T> {
+    async fn insert_schema(mut self, schema: &Schema) -> MutResult<Self, ()> {
+        self.invalidate(&schema.table_name);
+        self.forward(|storage| storage.insert_schema(schema)).await
+    }
+
+    async fn delete_schema(mut self, table_name: &str) -> MutResult<Self, ()> {
+        self.invalidate(table_name);
+        self.forward(|storage| storage.delete_schema(table_name))
+            .await
+    }
+
+    async fn insert_data(self, table_name: &str, rows: Vec<Row>) -> MutResult<Self, ()> {
+        self.forward(|storage| storage.insert_data(table_name, rows))
+            .await
+    }
+
+    async fn update_data(self, table_name: &str, rows: Vec<(Key, Row)>) -> MutResult<Self, ()> {
+        self.forward(|storage| storage.update_data(table_name, rows))
+            .await
+    }
+
+    async fn delete_data(self, table_name: &str, keys: Vec<Key>) -> MutResult<Self, ()> {
+        self.forward(|storage| storage.delete_data(table_name, keys))
+            .await
+    }
+
+    async fn truncate_data(self, table_name: &str) -> MutResult<Self, ()> {
+        self.forward(|storage| storage.truncate_data(table_name))
+            .await
+    }
+
+    async fn update_sequence(self, name: &str, value: i64) -> MutResult<Self, ()> {
+        self.forward(|storage| storage.update_sequence(name, value))
+            .await
+    }
+
+    async fn delete_sequence(self, name: &str) -> MutResult<Self, ()> {
+        self.forward(|storage| storage.delete_sequence(name)).await
+    }
+
+    async fn insert_function(self, function: &SchemaFunction) -> MutResult<Self, ()> {
+        self.forward(|storage| storage.insert_function(function))
+            .await
+    }
+
+    async fn delete_function(self, name: &str) -> MutResult<Self, ()> {
+        self.forward(|storage| storage.delete_function(name)).await
+    }
+}
+
+#[cfg(feature = "alter-table")]
+#[async_trait(?Send)]
+impl<T: AlterTable> AlterTable for SchemaCache<T> {
+    async fn rename_schema(
+        mut self,
+        table_name: &str,
+        new_table_name: &str,
+    ) -> MutResult<Self, ()> {
+        self.invalidate(table_name);
+        self.invalidate(new_table_name);
+        self.forward(|storage| storage.rename_schema(table_name, new_table_name))
+            .await
+    }
+
+    async fn rename_column(
+        mut self,
+        table_name: &str,
+        old_column_name: &str,
+        new_column_name: &str,
+    ) -> MutResult<Self, ()> {
+        self.invalidate(table_name);
+        self.forward(|storage| storage.rename_column(table_name, old_column_name, new_column_name))
+            .await
+    }
+
+    async fn add_column(mut self, table_name: &str, column_def: &ColumnDef) -> MutResult<Self, ()> {
+        self.invalidate(table_name);
+        self.forward(|storage| storage.add_column(table_name, column_def))
+            .await
+    }
+
+    async fn drop_column(
+        mut self,
+        table_name: &str,
+        column_name: &str,
+        if_exists: bool,
+    ) -> MutResult<Self, ()> {
+        self.invalidate(table_name);
+        self.forward(|storage| storage.drop_column(table_name, column_name, if_exists))
+            .await
+    }
+
+    async fn alter_column_type(
+        mut self,
+        table_name: &str,
+        column_name: &str,
+        data_type: &DataType,
+    ) -> MutResult<Self, ()> {
+        self.invalidate(table_name);
+        self.forward(|storage| storage.alter_column_type(table_name, column_name, data_type))
+            .await
+    }
+}
+
+#[cfg(feature = "index")]
+#[async_trait(?Send)]
+impl<T: Index> Index for SchemaCache<T> {
+    async fn scan_indexed_data(
+        &self,
+        table_name: &str,
+        index_name: &str,
+        asc: Option<bool>,
+        cmp_value: Option<(&IndexOperator, Value)>,
+    ) -> Result<RowIter> {
+        self.storage
+            .scan_indexed_data(table_name, index_name, asc, cmp_value)
+            .await
+    }
+
+    async fn scan_indexed_range(
+        &self,
+        table_name: &str,
+        index_name: &str,
+        asc: Option<bool>,
+        start: Bound<Value>,
+        end: Bound<Value>,
+    ) -> Result<RowIter> {
+        self.storage
+            .scan_indexed_range(table_name, index_name, asc, start, end)
+            .await
+    }
+
+    async fn scan_indexed_prefix(
+        &self,
+        table_name: &str,
+        index_name: &str,
+        asc: Option<bool>,
+        values: Vec<Value>,
+    ) -> Result<RowIter> {
+        self.storage
+            .scan_indexed_prefix(table_name, index_name, asc, values)
+            .await
+    }
+}
+
+#[cfg(feature = "index")]
+#[async_trait(?Send)]
+impl<T: IndexMut> IndexMut for SchemaCache<T> {
+    async fn create_index(
+        mut self,
+        table_name: &str,
+        index_name: &str,
+        column: &OrderByExpr,
+    ) -> MutResult<Self, ()> {
+        self.invalidate(table_name);
+        self.forward(|storage| storage.create_index(table_name, index_name, column))
+            .await
+    }
+
+    async fn drop_index(mut self, table_name: &str, index_name: &str) -> MutResult<Self, ()> {
+        self.invalidate(table_name);
+        self.forward(|storage| storage.drop_index(table_name, index_name))
+            .await
+    }
+}
+
+#[cfg(feature = "transaction")]
+#[async_trait(?Send)]
+impl<T: Transaction> Transaction for SchemaCache<T> {
+    async fn begin(self, autocommit: bool) -> MutResult<Self, bool> {
+        self.forward(|storage| storage.begin(autocommit)).await
+    }
+
+    async fn rollback(mut self) -> MutResult<Self, ()> {
+        self.schemas.get_mut().clear();
+        self.forward(|storage| storage.rollback()).await
+    }
+
+    async fn commit(self) -> MutResult<Self, ()> {
+        self.forward(|storage| storage.commit()).await
+    }
+}
+
+#[cfg(feature = "metadata")]
+#[async_trait(?Send)]
+impl<T: Metadata> Metadata for SchemaCache<T> {
+    fn version(&self) -> String {
+        self.storage.version()
+    }
+
+    async fn schema_names(&self) -> Result<Vec<String>> {
+        self.storage.schema_names().await
+    }
+}
+
+impl<T: GStore> GStore for SchemaCache<T> {}
+impl<T: GStore + GStoreMut> GStoreMut for SchemaCache<T> {}
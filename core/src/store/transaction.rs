@@ -32,4 +32,35 @@ where
             Error::StorageMsg("[Storage] Transaction::commit is not supported".to_owned()),
         ))
     }
+
+    /// Marks the current state of the transaction as the savepoint `name`. A savepoint of the
+    /// same name made earlier is shadowed until this one is released.
+    async fn savepoint(self, _name: &str) -> MutResult<Self, ()> {
+        Err((
+            self,
+            Error::StorageMsg("[Storage] Transaction::savepoint is not supported".to_owned()),
+        ))
+    }
+
+    /// Undoes the changes made after the latest savepoint `name`, which is kept while the
+    /// savepoints made after it are released.
+    async fn rollback_to_savepoint(self, _name: &str) -> MutResult<Self, ()> {
+        Err((
+            self,
+            Error::StorageMsg(
+                "[Storage] Transaction::rollback_to_savepoint is not supported".to_owned(),
+            ),
+        ))
+    }
+
+    /// Releases the latest savepoint `name` and the savepoints made after it, keeping the
+    /// changes made since.
+    async fn release_savepoint(self, _name: &str) -> MutResult<Self, ()> {
+        Err((
+            self,
+            Error::StorageMsg(
+                "[Storage] Transaction::release_savepoint is not supported".to_owned(),
+            ),
+        ))
+    }
 }
@@ -1,6 +1,6 @@
 use {
     super::TranslateError,
-    crate::{ast::DataType, result::Result},
+    crate::{ast::DataType, parse_sql::ExtendedDataType, result::Result},
     sqlparser::ast::DataType as SqlDataType,
 };
 
@@ -14,22 +14,27 @@ pub fn translate_data_type(sql_data_type: &SqlDataType) -> Result<DataType> {
         SqlDataType::Int(_) => Ok(DataType::Int),
         SqlDataType::Float(_) => Ok(DataType::Float),
         SqlDataType::Text => Ok(DataType::Text),
-        SqlDataType::Bytea => Ok(DataType::Bytea),
+        SqlDataType::Bytea | SqlDataType::Blob(_) => Ok(DataType::Bytea),
         SqlDataType::Date => Ok(DataType::Date),
         SqlDataType::Timestamp => Ok(DataType::Timestamp),
         SqlDataType::Time => Ok(DataType::Time),
         SqlDataType::Interval => Ok(DataType::Interval),
         SqlDataType::Uuid => Ok(DataType::Uuid),
         SqlDataType::Decimal(None, None) => Ok(DataType::Decimal),
-        SqlDataType::Custom(name) => {
-            let name = name.0.get(0).map(|v| v.value.to_uppercase());
-
-            match name.as_deref() {
-                Some("MAP") => Ok(DataType::Map),
-                Some("LIST") => Ok(DataType::List),
-                _ => Err(TranslateError::UnsupportedDataType(sql_data_type.to_string()).into()),
+        SqlDataType::Custom(name) => match ExtendedDataType::from_name(name) {
+            Some(ExtendedDataType::Enum(values)) => Ok(DataType::Enum(values)),
+            Some(ExtendedDataType::Bit(0)) => {
+                Err(TranslateError::UnsupportedDataType("BIT(0)".to_owned()).into())
             }
-        }
+            Some(ExtendedDataType::Bit(length)) => Ok(DataType::Bit(length)),
+            None => match name.to_string().to_uppercase().as_str() {
+                "MAP" => Ok(DataType::Map),
+                "LIST" => Ok(DataType::List),
+                "BIT" => Ok(DataType::Bit(1)),
+                "TIMESTAMPTZ" => Ok(DataType::TimestampTz),
+                _ => Err(TranslateError::UnsupportedDataType(sql_data_type.to_string()).into()),
+            },
+        },
         _ => Err(TranslateError::UnsupportedDataType(sql_data_type.to_string()).into()),
     }
 }
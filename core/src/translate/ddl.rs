@@ -1,19 +1,28 @@
 use {
     super::{data_type::translate_data_type, expr::translate_expr, TranslateError},
     crate::{
-        ast::{ColumnDef, ColumnOption, ColumnOptionDef},
+        ast::{Collation, ColumnDef, ColumnOption, ColumnOptionDef},
+        parse_sql::generated_column_expr,
         result::Result,
     },
-    sqlparser::ast::{
-        ColumnDef as SqlColumnDef, ColumnOption as SqlColumnOption,
-        ColumnOptionDef as SqlColumnOptionDef,
+    sqlparser::{
+        ast::{
+            ColumnDef as SqlColumnDef, ColumnOption as SqlColumnOption,
+            ColumnOptionDef as SqlColumnOptionDef, ObjectName as SqlObjectName,
+        },
+        keywords::Keyword,
+        tokenizer::{Token, Word},
     },
 };
 
 #[cfg(feature = "alter-table")]
 use {
-    super::translate_object_name, crate::ast::AlterTableOperation,
-    sqlparser::ast::AlterTableOperation as SqlAlterTableOperation,
+    super::translate_object_name,
+    crate::ast::AlterTableOperation,
+    sqlparser::ast::{
+        AlterColumnOperation as SqlAlterColumnOperation,
+        AlterTableOperation as SqlAlterTableOperation,
+    },
 };
 
 #[cfg(feature = "alter-table")]
@@ -27,10 +36,11 @@ pub fn translate_alter_table_operation(
         SqlAlterTableOperation::DropColumn {
             column_name,
             if_exists,
-            ..
+            cascade,
         } => Ok(AlterTableOperation::DropColumn {
             column_name: column_name.value.to_owned(),
             if_exists: *if_exists,
+            cascade: *cascade,
         }),
         SqlAlterTableOperation::RenameColumn {
             old_column_name,
@@ -44,6 +54,17 @@ pub fn translate_alter_table_operation(
                 table_name: translate_object_name(table_name),
             })
         }
+        SqlAlterTableOperation::AlterColumn {
+            column_name,
+            op:
+                SqlAlterColumnOperation::SetDataType {
+                    data_type,
+                    using: None,
+                },
+        } => Ok(AlterTableOperation::AlterColumnType {
+            column_name: column_name.value.to_owned(),
+            data_type: translate_data_type(data_type)?,
+        }),
         _ => Err(TranslateError::UnsupportedAlterTableOperation(
             sql_alter_table_operation.to_string(),
         )
@@ -55,35 +76,75 @@ pub fn translate_column_def(sql_column_def: &SqlColumnDef) -> Result<ColumnDef>
     let SqlColumnDef {
         name,
         data_type,
+        collation,
         options,
-        ..
     } = sql_column_def;
 
+    let collation = collation
+        .as_ref()
+        .map(translate_collation)
+        .transpose()?
+        .map(|collation| ColumnOptionDef {
+            name: None,
+            option: ColumnOption::Collate(collation),
+        });
+
     Ok(ColumnDef {
         name: name.value.to_owned(),
         data_type: translate_data_type(data_type)?,
         options: options
             .iter()
             .map(translate_column_option_def)
+            .chain(collation.map(Ok))
             .collect::<Result<_>>()?,
     })
 }
 
+pub fn translate_collation(sql_collation: &SqlObjectName) -> Result<Collation> {
+    match sql_collation.to_string().to_uppercase().as_str() {
+        "BINARY" => Ok(Collation::Binary),
+        "NOCASE" => Ok(Collation::NoCase),
+        _ => Err(TranslateError::UnsupportedCollation(sql_collation.to_string()).into()),
+    }
+}
+
 fn translate_column_option_def(
     sql_column_option_def: &SqlColumnOptionDef,
 ) -> Result<ColumnOptionDef> {
     let SqlColumnOptionDef { name, option } = sql_column_option_def;
 
+    if let Some(expr) = generated_column_expr(sql_column_option_def) {
+        return Ok(ColumnOptionDef {
+            name: None,
+            option: ColumnOption::Generated(translate_expr(expr)?),
+        });
+    }
+
     let name = name.as_ref().map(|name| name.value.to_owned());
     let option = match option {
         SqlColumnOption::Null => Ok(ColumnOption::Null),
         SqlColumnOption::NotNull => Ok(ColumnOption::NotNull),
         SqlColumnOption::Default(expr) => translate_expr(expr).map(ColumnOption::Default),
-        SqlColumnOption::Unique { is_primary: false } => {
-            Ok(ColumnOption::Unique { is_primary: false })
+        SqlColumnOption::Unique { is_primary } => Ok(ColumnOption::Unique {
+            is_primary: *is_primary,
+        }),
+        SqlColumnOption::DialectSpecific(tokens) if is_auto_increment(tokens) => {
+            Ok(ColumnOption::AutoIncrement)
         }
         _ => Err(TranslateError::UnsupportedColumnOption(option.to_string()).into()),
     }?;
 
     Ok(ColumnOptionDef { name, option })
 }
+
+/// Whether the tokens are `AUTOINCREMENT` or `AUTO_INCREMENT`, which the parser passes on as
+/// dialect specific column options.
+fn is_auto_increment(tokens: &[Token]) -> bool {
+    matches!(
+        tokens,
+        [Token::Word(Word {
+            keyword: Keyword::AUTOINCREMENT | Keyword::AUTO_INCREMENT,
+            ..
+        })]
+    )
+}
@@ -8,9 +8,6 @@ pub enum TranslateError {
     #[error("unimplemented - select on two or more than tables are not supported")]
     TooManyTables,
 
-    #[error("unimplemented - composite index is not supported")]
-    CompositeIndexNotSupported,
-
     #[error("unimplemented - join on update not supported")]
     JoinOnUpdateNotSupported,
 
@@ -54,12 +51,18 @@ pub enum TranslateError {
     #[error("qualified wildcard is not supported - COUNT({0})")]
     QualifiedWildcardInCountNotSupported(String),
 
-    #[error("order by - NULLS (FIRST | LAST) is not supported")]
-    OrderByNullsFirstOrLastNotSupported,
-
     #[error("unsupported function: {0}")]
     UnsupportedFunction(String),
 
+    #[error("unsupported window function: {0}")]
+    UnsupportedWindowFunction(String),
+
+    #[error("window function requires OVER clause: {0}")]
+    WindowFunctionRequiresOverClause(String),
+
+    #[error("unsupported window frame units: {0}")]
+    UnsupportedWindowFrameUnits(String),
+
     #[error("unsupported SHOW VARIABLE keyword: {0}")]
     UnsupportedShowVariableKeyword(String),
 
@@ -69,6 +72,9 @@ pub enum TranslateError {
     #[error("unsupported statement: {0}")]
     UnsupportedStatement(String),
 
+    #[error("EXPLAIN ANALYZE only runs queries, as it would modify the data otherwise")]
+    ExplainAnalyzeRequiresQuery,
+
     #[error("unsupported expr: {0}")]
     UnsupportedExpr(String),
 
@@ -81,6 +87,9 @@ pub enum TranslateError {
     #[error("unsupported ast literal: {0}")]
     UnsupportedAstLiteral(String),
 
+    #[error("invalid placeholder, expected $1, $2, ...: {0}")]
+    InvalidPlaceholder(String),
+
     #[error("unreachable unary operator: {0}")]
     UnreachableUnaryOperator(String),
 
@@ -93,6 +102,9 @@ pub enum TranslateError {
     #[error("unsupported query set expr: {0}")]
     UnsupportedQuerySetExpr(String),
 
+    #[error("recursive query must be `initial UNION [ALL] recursive` without other clauses: {0}")]
+    UnsupportedRecursiveQuery(String),
+
     #[error("unsupported query table factor: {0}")]
     UnsupportedQueryTableFactor(String),
 
@@ -105,6 +117,15 @@ pub enum TranslateError {
     #[error("unsupported column option: {0}")]
     UnsupportedColumnOption(String),
 
+    #[error("unsupported collation: {0}")]
+    UnsupportedCollation(String),
+
+    #[error("COLLATE is not supported in index columns")]
+    CollateInIndexNotSupported,
+
+    #[error("NULLS FIRST or NULLS LAST is not supported in index columns")]
+    NullsOrderInIndexNotSupported,
+
     #[error("unsupported alter table operation: {0}")]
     UnsupportedAlterTableOperation(String),
 
@@ -113,4 +134,25 @@ pub enum TranslateError {
 
     #[error("Every derived table must have its own alias")]
     LackOfAlias,
+
+    #[error("unsupported ARRAY_AGG option: {0}")]
+    UnsupportedArrayAggOption(String),
+
+    #[error("FILTER clause is only supported on aggregate functions: {0}")]
+    FilterOnNonAggregateNotSupported(String),
+
+    #[error("DISTINCT ON expressions must match the leading ORDER BY expressions")]
+    DistinctOnOrderByMismatch,
+
+    #[error("unsupported table function: {0}")]
+    UnsupportedTableFunction(String),
+
+    #[error("column alias count mismatch: expected {expected}, found {found}")]
+    ColumnAliasCountMismatch { expected: usize, found: usize },
+
+    #[error("only one grouping set construct is supported in GROUP BY")]
+    MultipleGroupingSetsNotSupported,
+
+    #[error("composite columns are not supported in grouping sets")]
+    CompositeGroupingColumnNotSupported,
 }
@@ -2,23 +2,28 @@ use {
     super::{
         ast_literal::{translate_ast_literal, translate_datetime_field},
         data_type::translate_data_type,
+        ddl::translate_collation,
         function::translate_function,
         operator::{translate_binary_operator, translate_unary_operator},
         translate_idents, translate_query, TranslateError,
     },
     crate::{
-        ast::{AstLiteral, Expr, OrderByExpr},
+        ast::{AstLiteral, BinaryOperator, Expr, OrderByExpr, UnaryOperator},
         result::Result,
-        translate::function::translate_trim,
+        translate::function::{
+            translate_aggregate_filter, translate_array_agg, translate_substring, translate_trim,
+        },
     },
     sqlparser::ast::{Expr as SqlExpr, OrderByExpr as SqlOrderByExpr},
 };
 
 pub fn translate_expr(sql_expr: &SqlExpr) -> Result<Expr> {
     match sql_expr {
+        // a double quoted word is a string as in MySQL, `backticks` quote an identifier
         SqlExpr::Identifier(ident) => match ident.quote_style {
-            Some(_) => Ok(Expr::Literal(AstLiteral::QuotedString(ident.value.clone()))),
-            None => Ok(Expr::Identifier(ident.value.clone())),
+            Some('"') => Ok(Expr::Literal(AstLiteral::QuotedString(ident.value.clone()))),
+            None if ident.value.starts_with('$') => translate_placeholder(&ident.value),
+            _ => Ok(Expr::Identifier(ident.value.clone())),
         },
         SqlExpr::CompoundIdentifier(idents) => {
             Ok(Expr::CompoundIdentifier(translate_idents(idents)))
@@ -54,11 +59,44 @@ pub fn translate_expr(sql_expr: &SqlExpr) -> Result<Expr> {
             low: translate_expr(low).map(Box::new)?,
             high: translate_expr(high).map(Box::new)?,
         }),
-        SqlExpr::BinaryOp { left, op, right } => Ok(Expr::BinaryOp {
+        SqlExpr::BinaryOp { left, op, right } => {
+            let left = translate_expr(left).map(Box::new)?;
+            let op = translate_binary_operator(op)?;
+
+            match right.as_ref() {
+                SqlExpr::AnyOp(right) => Ok(Expr::AnyOp {
+                    left,
+                    op,
+                    right: translate_expr(right).map(Box::new)?,
+                }),
+                SqlExpr::AllOp(right) => Ok(Expr::AllOp {
+                    left,
+                    op,
+                    right: translate_expr(right).map(Box::new)?,
+                }),
+                _ => Ok(Expr::BinaryOp {
+                    left,
+                    op,
+                    right: translate_expr(right).map(Box::new)?,
+                }),
+            }
+        }
+        SqlExpr::IsDistinctFrom(left, right) => Ok(Expr::BinaryOp {
+            left: translate_expr(left).map(Box::new)?,
+            op: BinaryOperator::IsDistinctFrom,
+            right: translate_expr(right).map(Box::new)?,
+        }),
+        SqlExpr::IsNotDistinctFrom(left, right) => Ok(Expr::BinaryOp {
             left: translate_expr(left).map(Box::new)?,
-            op: translate_binary_operator(op)?,
+            op: BinaryOperator::IsNotDistinctFrom,
             right: translate_expr(right).map(Box::new)?,
         }),
+        SqlExpr::IsTrue(expr) => translate_truth_test(expr, UnaryOperator::IsTrue),
+        SqlExpr::IsNotTrue(expr) => translate_truth_test(expr, UnaryOperator::IsNotTrue),
+        SqlExpr::IsFalse(expr) => translate_truth_test(expr, UnaryOperator::IsFalse),
+        SqlExpr::IsNotFalse(expr) => translate_truth_test(expr, UnaryOperator::IsNotFalse),
+        SqlExpr::IsUnknown(expr) => translate_truth_test(expr, UnaryOperator::IsUnknown),
+        SqlExpr::IsNotUnknown(expr) => translate_truth_test(expr, UnaryOperator::IsNotUnknown),
         SqlExpr::UnaryOp { op, expr } => Ok(Expr::UnaryOp {
             op: translate_unary_operator(op)?,
             expr: translate_expr(expr).map(Box::new)?,
@@ -72,13 +110,27 @@ pub fn translate_expr(sql_expr: &SqlExpr) -> Result<Expr> {
             expr: translate_expr(expr).map(Box::new)?,
         }),
         SqlExpr::Nested(expr) => translate_expr(expr).map(Box::new).map(Expr::Nested),
+        SqlExpr::Tuple(exprs) => exprs
+            .iter()
+            .map(translate_expr)
+            .collect::<Result<_>>()
+            .map(Expr::Tuple),
         SqlExpr::Value(value) => translate_ast_literal(value).map(Expr::Literal),
         SqlExpr::TypedString { data_type, value } => Ok(Expr::TypedString {
             data_type: translate_data_type(data_type)?,
             value: value.to_owned(),
         }),
         SqlExpr::Function(function) => translate_function(function),
+        SqlExpr::ArrayAgg(array_agg) => translate_array_agg(array_agg),
+        SqlExpr::AggregateExpressionWithFilter { expr, filter } => {
+            translate_aggregate_filter(expr, filter)
+        }
         SqlExpr::Trim { expr, trim_where } => translate_trim(expr, trim_where),
+        SqlExpr::Substring {
+            expr,
+            substring_from,
+            substring_for,
+        } => translate_substring(expr, substring_from, substring_for),
         SqlExpr::Exists(query) => translate_query(query).map(Box::new).map(Expr::Exists),
         SqlExpr::Subquery(query) => translate_query(query).map(Box::new).map(Expr::Subquery),
         SqlExpr::Case {
@@ -110,6 +162,21 @@ pub fn translate_expr(sql_expr: &SqlExpr) -> Result<Expr> {
     }
 }
 
+/// `$n` with `n` from 1 on, a parameter of the statement.
+fn translate_placeholder(word: &str) -> Result<Expr> {
+    match word[1..].parse::<usize>() {
+        Ok(index) if index > 0 => Ok(Expr::Placeholder(index)),
+        _ => Err(TranslateError::InvalidPlaceholder(word.to_owned()).into()),
+    }
+}
+
+fn translate_truth_test(expr: &SqlExpr, op: UnaryOperator) -> Result<Expr> {
+    translate_expr(expr).map(|expr| Expr::UnaryOp {
+        op,
+        expr: Box::new(expr),
+    })
+}
+
 pub fn translate_order_by_expr(sql_order_by_expr: &SqlOrderByExpr) -> Result<OrderByExpr> {
     let SqlOrderByExpr {
         expr,
@@ -117,12 +184,15 @@ pub fn translate_order_by_expr(sql_order_by_expr: &SqlOrderByExpr) -> Result<Ord
         nulls_first,
     } = sql_order_by_expr;
 
-    if matches!(nulls_first, Some(_)) {
-        return Err(TranslateError::OrderByNullsFirstOrLastNotSupported.into());
-    }
+    let (expr, collation) = match expr {
+        SqlExpr::Collate { expr, collation } => (expr.as_ref(), Some(collation)),
+        _ => (expr, None),
+    };
 
     Ok(OrderByExpr {
         expr: translate_expr(expr)?,
         asc: *asc,
+        nulls_first: *nulls_first,
+        collation: collation.map(translate_collation).transpose()?,
     })
 }
@@ -1,15 +1,24 @@
 use {
     super::{
-        ast_literal::translate_trim_where_field, expr::translate_expr, translate_object_name,
+        ast_literal::translate_trim_where_field,
+        expr::{translate_expr, translate_order_by_expr},
+        translate_object_name,
+        window::translate_window_spec,
         TranslateError,
     },
     crate::{
-        ast::{Aggregate, CountArgExpr, Expr, Function, ObjectName, TrimWhereField},
+        ast::{
+            Aggregate, AstLiteral, CountArgExpr, Expr, Function, ObjectName, OrderByExpr, ToSql,
+            TrimWhereField, Window, WindowFunction,
+        },
+        executor::{get_function, is_aggregate},
         result::Result,
     },
+    bigdecimal::BigDecimal,
     sqlparser::ast::{
-        Expr as SqlExpr, Function as SqlFunction, FunctionArg as SqlFunctionArg,
-        FunctionArgExpr as SqlFunctionArgExpr, TrimWhereField as SqlTrimWhereField,
+        ArrayAgg as SqlArrayAgg, Expr as SqlExpr, Function as SqlFunction,
+        FunctionArg as SqlFunctionArg, FunctionArgExpr as SqlFunctionArgExpr,
+        TrimWhereField as SqlTrimWhereField, WindowSpec as SqlWindowSpec,
     },
 };
 
@@ -41,6 +50,71 @@ pub fn translate_trim(
     })))
 }
 
+pub fn translate_array_agg(sql_array_agg: &SqlArrayAgg) -> Result<Expr> {
+    let SqlArrayAgg {
+        distinct,
+        expr,
+        order_by,
+        limit,
+        ..
+    } = sql_array_agg;
+
+    if *distinct {
+        return Err(TranslateError::UnsupportedArrayAggOption("DISTINCT".to_owned()).into());
+    } else if limit.is_some() {
+        return Err(TranslateError::UnsupportedArrayAggOption("LIMIT".to_owned()).into());
+    }
+
+    let expr = translate_expr(expr)?;
+    let order_by = order_by
+        .as_deref()
+        .map(translate_order_by_expr)
+        .transpose()?;
+    if matches!(
+        order_by,
+        Some(OrderByExpr {
+            collation: Some(_),
+            ..
+        })
+    ) {
+        return Err(TranslateError::UnsupportedArrayAggOption("COLLATE".to_owned()).into());
+    }
+
+    Ok(Expr::Aggregate(Box::new(Aggregate::ArrayAgg {
+        expr,
+        order_by,
+    })))
+}
+
+pub fn translate_aggregate_filter(expr: &SqlExpr, filter: &SqlExpr) -> Result<Expr> {
+    match translate_expr(expr)? {
+        Expr::Aggregate(aggregate) => Ok(Expr::Aggregate(Box::new(Aggregate::Filter {
+            aggregate,
+            filter: translate_expr(filter)?,
+        }))),
+        expr => Err(TranslateError::FilterOnNonAggregateNotSupported(expr.to_sql()).into()),
+    }
+}
+
+pub fn translate_substring(
+    expr: &SqlExpr,
+    substring_from: &Option<Box<SqlExpr>>,
+    substring_for: &Option<Box<SqlExpr>>,
+) -> Result<Expr> {
+    let expr = translate_expr(expr)?;
+    let start = match substring_from {
+        Some(start) => translate_expr(start)?,
+        None => Expr::Literal(AstLiteral::Number(BigDecimal::from(1))),
+    };
+    let count = substring_for.as_deref().map(translate_expr).transpose()?;
+
+    Ok(Expr::Function(Box::new(Function::Substr {
+        expr,
+        start,
+        count,
+    })))
+}
+
 fn check_len(name: String, found: usize, expected: usize) -> Result<()> {
     if found == expected {
         Ok(())
@@ -137,8 +211,87 @@ fn translate_function_trim<T: FnOnce(Expr, Option<Expr>) -> Function>(
     Ok(Expr::Function(Box::new(result)))
 }
 
+fn translate_window_aggregate_one_arg<T: FnOnce(Expr) -> Aggregate>(
+    func: T,
+    args: Vec<&SqlExpr>,
+    name: String,
+) -> Result<WindowFunction> {
+    check_len(name, args.len(), 1)?;
+
+    translate_expr(args[0])
+        .map(func)
+        .map(WindowFunction::Aggregate)
+}
+
+fn translate_window(name: String, args: Vec<&SqlExpr>, over: &SqlWindowSpec) -> Result<Expr> {
+    let function = match name.as_str() {
+        "SUM" => translate_window_aggregate_one_arg(Aggregate::Sum, args, name)?,
+        "MIN" => translate_window_aggregate_one_arg(Aggregate::Min, args, name)?,
+        "MAX" => translate_window_aggregate_one_arg(Aggregate::Max, args, name)?,
+        "AVG" => translate_window_aggregate_one_arg(Aggregate::Avg, args, name)?,
+        "VARIANCE" => translate_window_aggregate_one_arg(Aggregate::Variance, args, name)?,
+        "STDEV" => translate_window_aggregate_one_arg(Aggregate::Stdev, args, name)?,
+        "BOOL_AND" | "EVERY" => translate_window_aggregate_one_arg(Aggregate::BoolAnd, args, name)?,
+        "BOOL_OR" => translate_window_aggregate_one_arg(Aggregate::BoolOr, args, name)?,
+        "JSON_AGG" => translate_window_aggregate_one_arg(Aggregate::JsonAgg, args, name)?,
+        "FIRST_VALUE" => {
+            check_len(name, args.len(), 1)?;
+
+            translate_expr(args[0]).map(WindowFunction::FirstValue)?
+        }
+        "LAST_VALUE" => {
+            check_len(name, args.len(), 1)?;
+
+            translate_expr(args[0]).map(WindowFunction::LastValue)?
+        }
+        "NTH_VALUE" => {
+            check_len(name, args.len(), 2)?;
+
+            let expr = translate_expr(args[0])?;
+            let n = translate_expr(args[1])?;
+
+            WindowFunction::NthValue { expr, n }
+        }
+        "LAG" => {
+            check_len_range(name, args.len(), 1, 3)?;
+
+            let expr = translate_expr(args[0])?;
+            let offset = args.get(1).map(|arg| translate_expr(arg)).transpose()?;
+            let default = args.get(2).map(|arg| translate_expr(arg)).transpose()?;
+
+            WindowFunction::Lag {
+                expr,
+                offset,
+                default,
+            }
+        }
+        "LEAD" => {
+            check_len_range(name, args.len(), 1, 3)?;
+
+            let expr = translate_expr(args[0])?;
+            let offset = args.get(1).map(|arg| translate_expr(arg)).transpose()?;
+            let default = args.get(2).map(|arg| translate_expr(arg)).transpose()?;
+
+            WindowFunction::Lead {
+                expr,
+                offset,
+                default,
+            }
+        }
+        _ => {
+            return Err(TranslateError::UnsupportedWindowFunction(name).into());
+        }
+    };
+
+    let over = translate_window_spec(over)?;
+
+    Ok(Expr::Window(Box::new(Window { function, over })))
+}
+
 pub fn translate_function(sql_function: &SqlFunction) -> Result<Expr> {
-    let SqlFunction { name, args, .. } = sql_function;
+    let SqlFunction {
+        name, args, over, ..
+    } = sql_function;
     let name = {
         let ObjectName(names) = translate_object_name(name);
 
@@ -169,7 +322,15 @@ pub fn translate_function(sql_function: &SqlFunction) -> Result<Expr> {
             SqlFunctionArgExpr::Wildcard => CountArgExpr::Wildcard,
         };
 
-        return Ok(Expr::Aggregate(Box::new(Aggregate::Count(count_arg))));
+        let aggregate = Aggregate::Count(count_arg);
+
+        return match over {
+            Some(over) => Ok(Expr::Window(Box::new(Window {
+                function: WindowFunction::Aggregate(aggregate),
+                over: translate_window_spec(over)?,
+            }))),
+            None => Ok(Expr::Aggregate(Box::new(aggregate))),
+        };
     }
 
     let args = function_args
@@ -182,6 +343,30 @@ pub fn translate_function(sql_function: &SqlFunction) -> Result<Expr> {
         })
         .collect::<Result<Vec<_>>>()?;
 
+    if let Some(over) = over {
+        return translate_window(name, args, over);
+    }
+
+    // no function is registered outside of a session, e.g. for `translate` called by the user
+    if let Some(function) = get_function(&name).unwrap_or_default() {
+        check_len(name.clone(), args.len(), function.arg_types.len())?;
+
+        let args = args
+            .into_iter()
+            .map(translate_expr)
+            .collect::<Result<Vec<_>>>()?;
+
+        return Ok(Expr::Function(Box::new(Function::Custom { name, args })));
+    }
+
+    if is_aggregate(&name).unwrap_or_default() {
+        check_len(name.clone(), args.len(), 1)?;
+
+        let expr = translate_expr(args[0])?;
+
+        return Ok(Expr::Aggregate(Box::new(Aggregate::Custom { name, expr })));
+    }
+
     match name.as_str() {
         "SUM" => translate_aggregate_one_arg(Aggregate::Sum, args, name),
         "MIN" => translate_aggregate_one_arg(Aggregate::Min, args, name),
@@ -189,6 +374,30 @@ pub fn translate_function(sql_function: &SqlFunction) -> Result<Expr> {
         "AVG" => translate_aggregate_one_arg(Aggregate::Avg, args, name),
         "VARIANCE" => translate_aggregate_one_arg(Aggregate::Variance, args, name),
         "STDEV" => translate_aggregate_one_arg(Aggregate::Stdev, args, name),
+        "BOOL_AND" | "EVERY" => translate_aggregate_one_arg(Aggregate::BoolAnd, args, name),
+        "BOOL_OR" => translate_aggregate_one_arg(Aggregate::BoolOr, args, name),
+        "JSON_AGG" => translate_aggregate_one_arg(Aggregate::JsonAgg, args, name),
+        "JSON_OBJECT_AGG" => {
+            check_len(name, args.len(), 2)?;
+
+            let key = translate_expr(args[0])?;
+            let value = translate_expr(args[1])?;
+
+            Ok(Expr::Aggregate(Box::new(Aggregate::JsonObjectAgg {
+                key,
+                value,
+            })))
+        }
+        "GROUPING" => {
+            check_len_min(name, args.len(), 1)?;
+
+            let exprs = args
+                .into_iter()
+                .map(translate_expr)
+                .collect::<Result<Vec<_>>>()?;
+
+            Ok(Expr::Aggregate(Box::new(Aggregate::Grouping(exprs))))
+        }
         "CONCAT" => {
             check_len_min(name, args.len(), 1)?;
             let exprs = args
@@ -197,6 +406,29 @@ pub fn translate_function(sql_function: &SqlFunction) -> Result<Expr> {
                 .collect::<Result<Vec<_>>>()?;
             Ok(Expr::Function(Box::new(Function::Concat(exprs))))
         }
+        "CONCAT_WS" => {
+            check_len_min(name, args.len(), 2)?;
+            let mut exprs = args
+                .into_iter()
+                .map(translate_expr)
+                .collect::<Result<Vec<_>>>()?;
+            let separator = exprs.remove(0);
+
+            Ok(Expr::Function(Box::new(Function::ConcatWs {
+                separator,
+                exprs,
+            })))
+        }
+        "FORMAT" => {
+            check_len_min(name, args.len(), 1)?;
+            let mut args = args
+                .into_iter()
+                .map(translate_expr)
+                .collect::<Result<Vec<_>>>()?;
+            let format = args.remove(0);
+
+            Ok(Expr::Function(Box::new(Function::Format { format, args })))
+        }
         "LOWER" => translate_function_one_arg(Function::Lower, args, name),
         "UPPER" => translate_function_one_arg(Function::Upper, args, name),
         "LEFT" => {
@@ -341,6 +573,15 @@ pub fn translate_function(sql_function: &SqlFunction) -> Result<Expr> {
             })))
         }
         "REVERSE" => translate_function_one_arg(Function::Reverse, args, name),
+        "SOUNDEX" => translate_function_one_arg(Function::Soundex, args, name),
+        "LENGTH" => translate_function_one_arg(Function::Length, args, name),
+        "ASCII" => translate_function_one_arg(Function::Ascii, args, name),
+        "CHR" => translate_function_one_arg(Function::Chr, args, name),
+        "ORD" => translate_function_one_arg(Function::Ord, args, name),
+        "HEX" => translate_function_one_arg(Function::Hex, args, name),
+        "UNHEX" => translate_function_one_arg(Function::Unhex, args, name),
+        "TO_BASE64" => translate_function_one_arg(Function::ToBase64, args, name),
+        "FROM_BASE64" => translate_function_one_arg(Function::FromBase64, args, name),
         "REPEAT" => {
             check_len(name, args.len(), 2)?;
 
@@ -349,7 +590,43 @@ pub fn translate_function(sql_function: &SqlFunction) -> Result<Expr> {
 
             Ok(Expr::Function(Box::new(Function::Repeat { expr, num })))
         }
-        "SUBSTR" => {
+        "GLOB" => {
+            check_len(name, args.len(), 2)?;
+
+            let expr = translate_expr(args[0])?;
+            let pattern = translate_expr(args[1])?;
+
+            Ok(Expr::Function(Box::new(Function::Glob { expr, pattern })))
+        }
+        "LEVENSHTEIN" => {
+            check_len_range(name, args.len(), 2, 3)?;
+
+            let left = translate_expr(args[0])?;
+            let right = translate_expr(args[1])?;
+            let max_distance = if args.len() == 2 {
+                None
+            } else {
+                Some(translate_expr(args[2])?)
+            };
+
+            Ok(Expr::Function(Box::new(Function::Levenshtein {
+                left,
+                right,
+                max_distance,
+            })))
+        }
+        "SIMILARITY" => {
+            check_len(name, args.len(), 2)?;
+
+            let left = translate_expr(args[0])?;
+            let right = translate_expr(args[1])?;
+
+            Ok(Expr::Function(Box::new(Function::Similarity {
+                left,
+                right,
+            })))
+        }
+        "SUBSTR" | "SUBSTRING" => {
             check_len_range(name, args.len(), 2, 3)?;
 
             let expr = translate_expr(args[0])?;
@@ -378,6 +655,26 @@ pub fn translate_function(sql_function: &SqlFunction) -> Result<Expr> {
         "ABS" => translate_function_one_arg(Function::Abs, args, name),
         "SIGN" => translate_function_one_arg(Function::Sign, args, name),
         "GENERATE_UUID" => translate_function_zero_arg(Function::GenerateUuid(), args, name),
+        "RANDOM" => match args.as_slice() {
+            [] => Ok(Expr::Function(Box::new(Function::Random(None)))),
+            [min, max] => {
+                let min = translate_expr(min)?;
+                let max = translate_expr(max)?;
+
+                Ok(Expr::Function(Box::new(Function::Random(Some((min, max))))))
+            }
+            _ => Err(TranslateError::FunctionArgsLengthNotMatching {
+                name,
+                expected: 2,
+                found: args.len(),
+            }
+            .into()),
+        },
+        "NEXTVAL" => translate_function_one_arg(Function::Nextval, args, name),
+        "CURRVAL" => translate_function_one_arg(Function::Currval, args, name),
+        "FIRST_VALUE" | "LAST_VALUE" | "NTH_VALUE" | "LAG" | "LEAD" => {
+            Err(TranslateError::WindowFunctionRequiresOverClause(name).into())
+        }
         _ => Err(TranslateError::UnsupportedFunction(name).into()),
     }
 }
@@ -6,6 +6,7 @@ mod expr;
 mod function;
 mod operator;
 mod query;
+mod window;
 
 pub use self::{
     error::TranslateError,
@@ -13,28 +14,182 @@ pub use self::{
     query::{translate_query, translate_select_item},
 };
 
+use self::query::translate_table_factor;
+
 #[cfg(feature = "alter-table")]
 use ddl::translate_alter_table_operation;
 use sqlparser::ast::{TableFactor, TableWithJoins};
 
+#[cfg(feature = "index")]
+use crate::ast::{Expr, OrderByExpr};
+
 #[cfg(feature = "metadata")]
 use crate::ast::Variable;
 
 use {
-    self::ddl::translate_column_def,
+    self::{data_type::translate_data_type, ddl::translate_column_def},
     crate::{
-        ast::{Assignment, ObjectName, Statement},
+        ast::{
+            Assignment, FunctionArg, Merge, MergeClause, ObjectName, Statement,
+            TableFactor as AstTableFactor,
+        },
+        parse_sql::{ParsedMergeClause, ParsedStatement},
         result::Result,
     },
     sqlparser::ast::{
-        Assignment as SqlAssignment, Ident as SqlIdent, ObjectName as SqlObjectName,
-        ObjectType as SqlObjectType, Statement as SqlStatement,
+        Assignment as SqlAssignment, Expr as SqlExpr, Ident as SqlIdent,
+        ObjectName as SqlObjectName, ObjectType as SqlObjectType, Statement as SqlStatement,
     },
 };
 
+/// Translates a statement of `parse_extended`, see `translate` for the ones `sqlparser` parses.
+pub fn translate_extended(parsed: &ParsedStatement) -> Result<Statement> {
+    match parsed {
+        ParsedStatement::Sql(sql_statement) => translate(sql_statement),
+        ParsedStatement::Returning {
+            statement,
+            projection,
+        } => {
+            let mut statement = translate(statement)?;
+            let projection = projection
+                .iter()
+                .map(translate_select_item)
+                .collect::<Result<_>>()?;
+
+            match &mut statement {
+                Statement::Insert { returning, .. }
+                | Statement::Update { returning, .. }
+                | Statement::Delete { returning, .. } => *returning = projection,
+                _ => {}
+            }
+
+            Ok(statement)
+        }
+        ParsedStatement::RefreshMaterializedView(name) => Ok(Statement::RefreshMaterializedView {
+            name: translate_object_name(name),
+        }),
+        ParsedStatement::CreateSequence(name) => Ok(Statement::CreateSequence {
+            name: translate_object_name(name),
+        }),
+        ParsedStatement::DropSequence { if_exists, name } => Ok(Statement::DropSequence {
+            if_exists: *if_exists,
+            name: translate_object_name(name),
+        }),
+        ParsedStatement::CreateFunction {
+            name,
+            args,
+            return_type,
+            body,
+        } => Ok(Statement::CreateFunction {
+            name: translate_object_name(name),
+            args: args
+                .iter()
+                .map(|(name, data_type)| {
+                    Ok(FunctionArg {
+                        name: name.value.clone(),
+                        data_type: translate_data_type(data_type)?,
+                    })
+                })
+                .collect::<Result<_>>()?,
+            return_type: translate_data_type(return_type)?,
+            body: translate_expr(body)?,
+        }),
+        ParsedStatement::DropFunction { if_exists, name } => Ok(Statement::DropFunction {
+            if_exists: *if_exists,
+            name: translate_object_name(name),
+        }),
+        #[cfg(feature = "transaction")]
+        ParsedStatement::Savepoint(name) => Ok(Statement::Savepoint {
+            name: name.value.clone(),
+        }),
+        #[cfg(feature = "transaction")]
+        ParsedStatement::RollbackToSavepoint(name) => Ok(Statement::RollbackToSavepoint {
+            name: name.value.clone(),
+        }),
+        #[cfg(feature = "transaction")]
+        ParsedStatement::ReleaseSavepoint(name) => Ok(Statement::ReleaseSavepoint {
+            name: name.value.clone(),
+        }),
+        #[cfg(not(feature = "transaction"))]
+        ParsedStatement::Savepoint(name) => {
+            Err(TranslateError::UnsupportedStatement(format!("SAVEPOINT {name}")).into())
+        }
+        #[cfg(not(feature = "transaction"))]
+        ParsedStatement::RollbackToSavepoint(name) => Err(TranslateError::UnsupportedStatement(
+            format!("ROLLBACK TO SAVEPOINT {name}"),
+        )
+        .into()),
+        #[cfg(not(feature = "transaction"))]
+        ParsedStatement::ReleaseSavepoint(name) => {
+            Err(TranslateError::UnsupportedStatement(format!("RELEASE SAVEPOINT {name}")).into())
+        }
+        ParsedStatement::Merge {
+            table,
+            source,
+            on,
+            clauses,
+        } => {
+            let (table_name, alias) = match translate_table_factor(table)? {
+                AstTableFactor::Table { name, alias, .. } => (name, alias.map(|alias| alias.name)),
+                _ => return Err(TranslateError::UnsupportedTableFactor(table.to_string()).into()),
+            };
+
+            Ok(Statement::Merge(Box::new(Merge {
+                table_name,
+                alias,
+                source: translate_table_factor(source)?,
+                on: translate_expr(on)?,
+                clauses: clauses
+                    .iter()
+                    .map(translate_merge_clause)
+                    .collect::<Result<_>>()?,
+            })))
+        }
+    }
+}
+
+fn translate_merge_clause(clause: &ParsedMergeClause) -> Result<MergeClause> {
+    let translate_predicate = |predicate: &Option<SqlExpr>| predicate.as_ref().map(translate_expr);
+
+    match clause {
+        ParsedMergeClause::MatchedUpdate {
+            predicate,
+            assignments,
+        } => Ok(MergeClause::MatchedUpdate {
+            predicate: translate_predicate(predicate).transpose()?,
+            assignments: assignments
+                .iter()
+                .map(translate_assignment)
+                .collect::<Result<_>>()?,
+        }),
+        ParsedMergeClause::MatchedDelete { predicate } => Ok(MergeClause::MatchedDelete {
+            predicate: translate_predicate(predicate).transpose()?,
+        }),
+        ParsedMergeClause::NotMatched {
+            predicate,
+            columns,
+            values,
+        } => Ok(MergeClause::NotMatched {
+            predicate: translate_predicate(predicate).transpose()?,
+            columns: translate_idents(columns),
+            values: values.iter().map(translate_expr).collect::<Result<_>>()?,
+        }),
+    }
+}
+
 pub fn translate(sql_statement: &SqlStatement) -> Result<Statement> {
     match sql_statement {
         SqlStatement::Query(query) => translate_query(query).map(Box::new).map(Statement::Query),
+        SqlStatement::Explain {
+            analyze: true,
+            statement,
+            ..
+        } => match statement.as_ref() {
+            SqlStatement::Query(query) => translate_query(query)
+                .map(Box::new)
+                .map(Statement::ExplainAnalyze),
+            _ => Err(TranslateError::ExplainAnalyzeRequiresQuery.into()),
+        },
         SqlStatement::Insert {
             table_name,
             columns,
@@ -44,6 +199,7 @@ pub fn translate(sql_statement: &SqlStatement) -> Result<Statement> {
             table_name: translate_object_name(table_name),
             columns: translate_idents(columns),
             source: translate_query(source).map(Box::new)?,
+            returning: Vec::new(),
         }),
         SqlStatement::Update {
             table,
@@ -57,6 +213,7 @@ pub fn translate(sql_statement: &SqlStatement) -> Result<Statement> {
                 .map(translate_assignment)
                 .collect::<Result<_>>()?,
             selection: selection.as_ref().map(translate_expr).transpose()?,
+            returning: Vec::new(),
         }),
         SqlStatement::Delete {
             table_name,
@@ -64,6 +221,7 @@ pub fn translate(sql_statement: &SqlStatement) -> Result<Statement> {
         } => Ok(Statement::Delete {
             table_name: translate_object_name(table_name),
             selection: selection.as_ref().map(translate_expr).transpose()?,
+            returning: Vec::new(),
         }),
         SqlStatement::CreateTable {
             if_not_exists,
@@ -83,6 +241,30 @@ pub fn translate(sql_statement: &SqlStatement) -> Result<Statement> {
                 None => None,
             },
         }),
+        SqlStatement::CreateView {
+            or_replace: false,
+            materialized: false,
+            name,
+            columns,
+            query,
+            ..
+        } => Ok(Statement::CreateView {
+            name: translate_object_name(name),
+            columns: translate_idents(columns),
+            query: translate_query(query).map(Box::new)?,
+        }),
+        SqlStatement::CreateView {
+            or_replace: false,
+            materialized: true,
+            name,
+            columns,
+            query,
+            ..
+        } => Ok(Statement::CreateMaterializedView {
+            name: translate_object_name(name),
+            columns: translate_idents(columns),
+            query: translate_query(query).map(Box::new)?,
+        }),
         #[cfg(feature = "alter-table")]
         SqlStatement::AlterTable {
             name, operation, ..
@@ -90,6 +272,12 @@ pub fn translate(sql_statement: &SqlStatement) -> Result<Statement> {
             name: translate_object_name(name),
             operation: translate_alter_table_operation(operation)?,
         }),
+        SqlStatement::Truncate {
+            table_name,
+            partitions: None,
+        } => Ok(Statement::Truncate {
+            table_name: translate_object_name(table_name),
+        }),
         SqlStatement::Drop {
             object_type: SqlObjectType::Table,
             if_exists,
@@ -106,14 +294,31 @@ pub fn translate(sql_statement: &SqlStatement) -> Result<Statement> {
             columns,
             ..
         } => {
-            if columns.len() > 1 {
-                return Err(TranslateError::CompositeIndexNotSupported.into());
+            let mut columns = columns
+                .iter()
+                .map(translate_order_by_expr)
+                .collect::<Result<Vec<_>>>()?;
+            if columns.iter().any(|column| column.collation.is_some()) {
+                return Err(TranslateError::CollateInIndexNotSupported.into());
+            } else if columns.iter().any(|column| column.nulls_first.is_some()) {
+                return Err(TranslateError::NullsOrderInIndexNotSupported.into());
             }
 
+            // a composite index is keyed by the tuple of its columns
+            let column = match columns.len() {
+                1 => columns.remove(0),
+                _ => OrderByExpr {
+                    expr: Expr::Tuple(columns.into_iter().map(|column| column.expr).collect()),
+                    asc: None,
+                    nulls_first: None,
+                    collation: None,
+                },
+            };
+
             Ok(Statement::CreateIndex {
                 name: translate_object_name(name),
                 table_name: translate_object_name(table_name),
-                column: translate_order_by_expr(&columns[0])?,
+                column,
             })
         }
         #[cfg(feature = "index")]
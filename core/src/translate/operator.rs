@@ -13,6 +13,7 @@ pub fn translate_unary_operator(sql_unary_operator: &SqlUnaryOperator) -> Result
         SqlUnaryOperator::Minus => Ok(UnaryOperator::Minus),
         SqlUnaryOperator::Not => Ok(UnaryOperator::Not),
         SqlUnaryOperator::PGPostfixFactorial => Ok(UnaryOperator::Factorial),
+        SqlUnaryOperator::PGBitwiseNot => Ok(UnaryOperator::BitwiseNot),
         _ => Err(TranslateError::UnreachableUnaryOperator(sql_unary_operator.to_string()).into()),
     }
 }
@@ -40,6 +41,14 @@ pub fn translate_binary_operator(
         SqlBinaryOperator::ILike => Ok(BinaryOperator::ILike),
         SqlBinaryOperator::NotLike => Ok(BinaryOperator::NotLike),
         SqlBinaryOperator::NotILike => Ok(BinaryOperator::NotILike),
+        SqlBinaryOperator::BitwiseAnd => Ok(BinaryOperator::BitwiseAnd),
+        SqlBinaryOperator::BitwiseOr => Ok(BinaryOperator::BitwiseOr),
+        SqlBinaryOperator::BitwiseXor | SqlBinaryOperator::PGBitwiseXor => {
+            Ok(BinaryOperator::BitwiseXor)
+        }
+        SqlBinaryOperator::PGBitwiseShiftLeft => Ok(BinaryOperator::BitwiseShiftLeft),
+        SqlBinaryOperator::PGBitwiseShiftRight => Ok(BinaryOperator::BitwiseShiftRight),
+        SqlBinaryOperator::AtArrow => Ok(BinaryOperator::Contains),
         _ => Err(TranslateError::UnsupportedBinaryOperator(sql_binary_operator.to_string()).into()),
     }
 }
@@ -5,21 +5,28 @@ use {
     },
     crate::{
         ast::{
-            Join, JoinConstraint, JoinExecutor, JoinOperator, Query, Select, SelectItem, SetExpr,
-            TableAlias, TableFactor, TableWithJoins, Values,
+            Cte, Expr, GroupingSets, Join, JoinConstraint, JoinExecutor, JoinOperator, Query,
+            RecursiveTerm, Select, SelectItem, SetExpr, TableAlias, TableFactor, TableSample,
+            TableWithJoins, Values,
         },
-        result::Result,
+        parse_sql::ExtendedTableSample,
+        result::{Error, Result},
     },
     sqlparser::ast::{
-        Expr as SqlExpr, Join as SqlJoin, JoinConstraint as SqlJoinConstraint,
-        JoinOperator as SqlJoinOperator, OrderByExpr, Query as SqlQuery, Select as SqlSelect,
-        SelectItem as SqlSelectItem, SetExpr as SqlSetExpr, TableAlias as SqlTableAlias,
-        TableFactor as SqlTableFactor, TableWithJoins as SqlTableWithJoins,
+        Cte as SqlCte, Expr as SqlExpr, Function as SqlFunction, FunctionArg as SqlFunctionArg,
+        FunctionArgExpr as SqlFunctionArgExpr, Join as SqlJoin,
+        JoinConstraint as SqlJoinConstraint, JoinOperator as SqlJoinOperator,
+        ObjectName as SqlObjectName, OrderByExpr, Query as SqlQuery, Select as SqlSelect,
+        SelectItem as SqlSelectItem, SetExpr as SqlSetExpr, SetOperator as SqlSetOperator,
+        TableAlias as SqlTableAlias, TableFactor as SqlTableFactor,
+        TableWithJoins as SqlTableWithJoins, With as SqlWith,
     },
+    std::iter::once,
 };
 
 pub fn translate_query(sql_query: &SqlQuery) -> Result<Query> {
     let SqlQuery {
+        with,
         body,
         order_by,
         limit,
@@ -27,6 +34,10 @@ pub fn translate_query(sql_query: &SqlQuery) -> Result<Query> {
         ..
     } = sql_query;
 
+    let with = match with {
+        Some(with) => translate_with(with)?,
+        None => Vec::new(),
+    };
     let body = translate_set_expr(body, order_by)?;
 
     let limit = limit.as_ref().map(translate_expr).transpose()?;
@@ -36,12 +47,72 @@ pub fn translate_query(sql_query: &SqlQuery) -> Result<Query> {
         .transpose()?;
 
     Ok(Query {
+        with,
         body,
         limit,
         offset,
     })
 }
 
+fn translate_with(sql_with: &SqlWith) -> Result<Vec<Cte>> {
+    let SqlWith {
+        recursive,
+        cte_tables,
+    } = sql_with;
+
+    cte_tables
+        .iter()
+        .map(|SqlCte { alias, query, .. }| {
+            let alias = TableAlias {
+                name: alias.name.value.to_owned(),
+                columns: translate_idents(&alias.columns),
+            };
+
+            let (left, all, right) = match &query.body {
+                SqlSetExpr::SetOperation {
+                    op: SqlSetOperator::Union,
+                    all,
+                    left,
+                    right,
+                } if *recursive => (left, *all, right),
+                _ => {
+                    return Ok(Cte {
+                        alias,
+                        query: translate_query(query)?,
+                        recursive: None,
+                    });
+                }
+            };
+
+            if query.with.is_some()
+                || !query.order_by.is_empty()
+                || query.limit.is_some()
+                || query.offset.is_some()
+            {
+                return Err(TranslateError::UnsupportedRecursiveQuery(query.to_string()).into());
+            }
+
+            let translate_term = |set_expr: &SqlSetExpr| {
+                translate_set_expr(set_expr, &[]).map(|body| Query {
+                    with: Vec::new(),
+                    body,
+                    limit: None,
+                    offset: None,
+                })
+            };
+
+            Ok(Cte {
+                alias,
+                query: translate_term(left)?,
+                recursive: Some(RecursiveTerm {
+                    all,
+                    query: translate_term(right)?,
+                }),
+            })
+        })
+        .collect()
+}
+
 fn translate_set_expr(sql_set_expr: &SqlSetExpr, order_by: &[OrderByExpr]) -> Result<SetExpr> {
     match sql_set_expr {
         SqlSetExpr::Select(select) => translate_select(select, order_by)
@@ -62,8 +133,9 @@ fn translate_select(sql_select: &SqlSelect, order_by: &[OrderByExpr]) -> Result<
     let order_by = order_by
         .iter()
         .map(translate_order_by_expr)
-        .collect::<Result<_>>()?;
+        .collect::<Result<Vec<_>>>()?;
     let SqlSelect {
+        distinct,
         projection,
         from,
         selection,
@@ -72,31 +144,162 @@ fn translate_select(sql_select: &SqlSelect, order_by: &[OrderByExpr]) -> Result<
         ..
     } = sql_select;
 
-    if from.len() > 1 {
-        return Err(TranslateError::TooManyTables.into());
+    let (distinct_on, projection) = translate_distinct_on(*distinct, projection)?;
+    let leading_order_by = order_by
+        .iter()
+        .map(|order_by| &order_by.expr)
+        .take(distinct_on.len());
+    if !leading_order_by.eq(distinct_on.iter()) {
+        return Err(TranslateError::DistinctOnOrderByMismatch.into());
+    }
+
+    let (group_by, grouping_sets) = translate_group_by(group_by)?;
+
+    let mut from = from.iter();
+    let TableWithJoins {
+        relation,
+        mut joins,
+    } = from
+        .next()
+        .map(translate_table_with_joins)
+        .ok_or(TranslateError::LackOfTable)??;
+
+    // `FROM a, LATERAL (...) AS b` is the same as `FROM a JOIN LATERAL (...) AS b`
+    for sql_table_with_joins in from {
+        if !matches!(
+            sql_table_with_joins.relation,
+            SqlTableFactor::Derived { lateral: true, .. }
+        ) {
+            return Err(TranslateError::TooManyTables.into());
+        }
+
+        let TableWithJoins {
+            relation,
+            joins: lateral_joins,
+        } = translate_table_with_joins(sql_table_with_joins)?;
+
+        joins.push(Join {
+            relation,
+            join_operator: JoinOperator::Inner(JoinConstraint::None),
+            join_executor: JoinExecutor::NestedLoop,
+        });
+        joins.extend(lateral_joins);
     }
 
     Ok(Select {
-        projection: projection
-            .iter()
-            .map(translate_select_item)
-            .collect::<Result<_>>()?,
-        from: from
-            .iter()
-            .map(translate_table_with_joins)
-            .next()
-            .ok_or(TranslateError::LackOfTable)??,
+        projection,
+        from: TableWithJoins { relation, joins },
         selection: selection.as_ref().map(translate_expr).transpose()?,
-        group_by: group_by.iter().map(translate_expr).collect::<Result<_>>()?,
+        group_by,
+        grouping_sets,
         having: having.as_ref().map(translate_expr).transpose()?,
         order_by,
+        distinct_on,
     })
 }
 
+/// Splits `GROUP BY` into the plain expressions and a grouping set construct such as `CUBE`.
+fn translate_group_by(sql_group_by: &[SqlExpr]) -> Result<(Vec<Expr>, Option<GroupingSets>)> {
+    let mut group_by = Vec::new();
+    let mut grouping_sets = None;
+
+    for sql_expr in sql_group_by {
+        let sets = match sql_expr {
+            SqlExpr::Cube(sql_columns) => {
+                translate_grouping_columns(sql_columns).map(GroupingSets::Cube)?
+            }
+            SqlExpr::GroupingSets(sql_sets) => sql_sets
+                .iter()
+                .map(|sql_set| sql_set.iter().map(translate_expr).collect::<Result<_>>())
+                .collect::<Result<_>>()
+                .map(GroupingSets::Sets)?,
+            _ => {
+                group_by.push(translate_expr(sql_expr)?);
+
+                continue;
+            }
+        };
+
+        if grouping_sets.replace(sets).is_some() {
+            return Err(TranslateError::MultipleGroupingSetsNotSupported.into());
+        }
+    }
+
+    Ok((group_by, grouping_sets))
+}
+
+fn translate_grouping_columns(sql_columns: &[Vec<SqlExpr>]) -> Result<Vec<Expr>> {
+    sql_columns
+        .iter()
+        .map(|sql_column| match sql_column.as_slice() {
+            [sql_expr] => translate_expr(sql_expr),
+            _ => Err(TranslateError::CompositeGroupingColumnNotSupported.into()),
+        })
+        .collect()
+}
+
+/// sqlparser does not support `DISTINCT ON (...)` yet, so `SELECT DISTINCT ON (a) a, b` is
+/// parsed as `DISTINCT` followed by a call to a function named `ON`, which takes the first
+/// projected column as its alias.
+fn translate_distinct_on(
+    distinct: bool,
+    projection: &[SqlSelectItem],
+) -> Result<(Vec<Expr>, Vec<SelectItem>)> {
+    let is_distinct_on = |name: &SqlObjectName| match name.0.as_slice() {
+        [ident] => distinct && ident.quote_style.is_none() && ident.value.to_uppercase() == "ON",
+        _ => false,
+    };
+
+    let (args, first, rest) = match projection {
+        [SqlSelectItem::ExprWithAlias {
+            expr: SqlExpr::Function(SqlFunction { name, args, .. }),
+            alias,
+        }, rest @ ..]
+            if is_distinct_on(name) =>
+        {
+            (args, alias, rest)
+        }
+        _ => {
+            let projection = projection
+                .iter()
+                .map(translate_select_item)
+                .collect::<Result<_>>()?;
+
+            return Ok((Vec::new(), projection));
+        }
+    };
+
+    let distinct_on = args
+        .iter()
+        .map(|arg| match arg {
+            SqlFunctionArg::Unnamed(SqlFunctionArgExpr::Expr(expr)) => translate_expr(expr),
+            SqlFunctionArg::Unnamed(_) => {
+                Err(TranslateError::WildcardFunctionArgNotAccepted.into())
+            }
+            SqlFunctionArg::Named { .. } => {
+                Err(TranslateError::NamedFunctionArgNotSupported.into())
+            }
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let first = SelectItem::Expr {
+        expr: Expr::Identifier(first.value.to_owned()),
+        label: first.value.to_owned(),
+    };
+    let projection = once(Ok(first))
+        .chain(rest.iter().map(translate_select_item))
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok((distinct_on, projection))
+}
+
 pub fn translate_select_item(sql_select_item: &SqlSelectItem) -> Result<SelectItem> {
     match sql_select_item {
         SqlSelectItem::UnnamedExpr(expr) => {
             let label = match expr {
+                SqlExpr::Identifier(ident) if ident.quote_style != Some('"') => {
+                    ident.value.to_owned()
+                }
                 SqlExpr::CompoundIdentifier(idents) => idents
                     .last()
                     .map(|ident| ident.value.to_owned())
@@ -131,9 +334,83 @@ fn translate_table_with_joins(sql_table_with_joins: &SqlTableWithJoins) -> Resul
     })
 }
 
-fn translate_table_factor(sql_table_factor: &SqlTableFactor) -> Result<TableFactor> {
+fn translate_table_function(
+    name: &SqlObjectName,
+    alias: &Option<SqlTableAlias>,
+    args: &[SqlFunctionArg],
+) -> Result<TableFactor> {
+    let function_name = translate_object_name(name).0.join(".").to_uppercase();
+    if function_name != "GENERATE_SERIES" {
+        return Err(TranslateError::UnsupportedTableFunction(function_name).into());
+    }
+
+    let args = args
+        .iter()
+        .map(|arg| match arg {
+            SqlFunctionArg::Unnamed(SqlFunctionArgExpr::Expr(expr)) => translate_expr(expr),
+            SqlFunctionArg::Unnamed(_) => {
+                Err(TranslateError::UnsupportedTableFunction(arg.to_string()).into())
+            }
+            SqlFunctionArg::Named { .. } => {
+                Err(TranslateError::NamedFunctionArgNotSupported.into())
+            }
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let (start, end, step) = match args.as_slice() {
+        [start, end] => (start.clone(), end.clone(), None),
+        [start, end, step] => (start.clone(), end.clone(), Some(step.clone())),
+        _ => {
+            return Err(TranslateError::FunctionArgsLengthNotWithinRange {
+                name: function_name,
+                expected_minimum: 2,
+                expected_maximum: 3,
+                found: args.len(),
+            }
+            .into());
+        }
+    };
+
+    let alias = match alias {
+        Some(SqlTableAlias { columns, .. }) if columns.len() > 1 => {
+            return Err(TranslateError::ColumnAliasCountMismatch {
+                expected: 1,
+                found: columns.len(),
+            }
+            .into());
+        }
+        Some(SqlTableAlias { name, columns }) => TableAlias {
+            name: name.value.to_owned(),
+            columns: translate_idents(columns),
+        },
+        None => TableAlias {
+            name: "generate_series".to_owned(),
+            columns: Vec::new(),
+        },
+    };
+
+    Ok(TableFactor::Series {
+        alias,
+        start,
+        end,
+        step,
+    })
+}
+
+pub fn translate_table_factor(sql_table_factor: &SqlTableFactor) -> Result<TableFactor> {
     match sql_table_factor {
-        SqlTableFactor::Table { name, alias, .. } => Ok(TableFactor::Table {
+        SqlTableFactor::Table {
+            name,
+            alias,
+            args: Some(args),
+            ..
+        } => translate_table_function(name, alias, args),
+        SqlTableFactor::Table {
+            name,
+            alias,
+            with_hints,
+            ..
+        } => Ok(TableFactor::Table {
             name: translate_object_name(name),
             alias: alias
                 .as_ref()
@@ -142,16 +419,35 @@ fn translate_table_factor(sql_table_factor: &SqlTableFactor) -> Result<TableFact
                     columns: translate_idents(columns),
                 }),
             index: None, // query execution plan
+            sample: translate_table_sample(sql_table_factor, with_hints)?,
         }),
         SqlTableFactor::Derived {
-            subquery, alias, ..
+            lateral,
+            subquery,
+            alias,
         } => {
             if let Some(alias) = alias {
+                let subquery = translate_query(subquery)?;
+                let columns = translate_idents(&alias.columns);
+
+                if let SetExpr::Values(Values(values_list)) = &subquery.body {
+                    let expected = values_list.first().map(Vec::len).unwrap_or(0);
+
+                    if !columns.is_empty() && columns.len() != expected {
+                        return Err(TranslateError::ColumnAliasCountMismatch {
+                            expected,
+                            found: columns.len(),
+                        }
+                        .into());
+                    }
+                }
+
                 Ok(TableFactor::Derived {
-                    subquery: translate_query(subquery)?,
+                    lateral: *lateral,
+                    subquery,
                     alias: TableAlias {
                         name: alias.name.value.to_owned(),
-                        columns: translate_idents(&alias.columns),
+                        columns,
                     },
                 })
             } else {
@@ -163,6 +459,30 @@ fn translate_table_factor(sql_table_factor: &SqlTableFactor) -> Result<TableFact
     }
 }
 
+/// Translates the table hint `TABLESAMPLE BERNOULLI` is parsed as, the only hint supported.
+fn translate_table_sample(
+    sql_table_factor: &SqlTableFactor,
+    with_hints: &[SqlExpr],
+) -> Result<Option<TableSample>> {
+    let unsupported = || -> Error {
+        TranslateError::UnsupportedQueryTableFactor(sql_table_factor.to_string()).into()
+    };
+
+    let hint = match with_hints {
+        [] => return Ok(None),
+        [hint] => hint,
+        _ => return Err(unsupported()),
+    };
+
+    let ExtendedTableSample { percentage, seed } =
+        ExtendedTableSample::from_hint(hint).ok_or_else(unsupported)?;
+
+    Ok(Some(TableSample {
+        percentage: translate_expr(percentage)?,
+        seed: seed.map(translate_expr).transpose()?,
+    }))
+}
+
 fn translate_join(sql_join: &SqlJoin) -> Result<Join> {
     let SqlJoin {
         relation,
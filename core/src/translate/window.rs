@@ -0,0 +1,64 @@
+use {
+    super::{translate_expr, translate_order_by_expr, TranslateError},
+    crate::{
+        ast::{WindowFrame, WindowFrameBound, WindowFrameUnits, WindowSpec},
+        result::Result,
+    },
+    sqlparser::ast::{
+        WindowFrame as SqlWindowFrame, WindowFrameBound as SqlWindowFrameBound,
+        WindowFrameUnits as SqlWindowFrameUnits, WindowSpec as SqlWindowSpec,
+    },
+};
+
+pub fn translate_window_spec(sql_window_spec: &SqlWindowSpec) -> Result<WindowSpec> {
+    let SqlWindowSpec {
+        partition_by,
+        order_by,
+        window_frame,
+    } = sql_window_spec;
+
+    Ok(WindowSpec {
+        partition_by: partition_by
+            .iter()
+            .map(translate_expr)
+            .collect::<Result<_>>()?,
+        order_by: order_by
+            .iter()
+            .map(translate_order_by_expr)
+            .collect::<Result<_>>()?,
+        window_frame: window_frame
+            .as_ref()
+            .map(translate_window_frame)
+            .transpose()?,
+    })
+}
+
+fn translate_window_frame(sql_window_frame: &SqlWindowFrame) -> Result<WindowFrame> {
+    let SqlWindowFrame {
+        units,
+        start_bound,
+        end_bound,
+    } = sql_window_frame;
+
+    let units = match units {
+        SqlWindowFrameUnits::Rows => WindowFrameUnits::Rows,
+        SqlWindowFrameUnits::Range => WindowFrameUnits::Range,
+        SqlWindowFrameUnits::Groups => {
+            return Err(TranslateError::UnsupportedWindowFrameUnits(units.to_string()).into());
+        }
+    };
+
+    Ok(WindowFrame {
+        units,
+        start_bound: translate_window_frame_bound(start_bound),
+        end_bound: end_bound.as_ref().map(translate_window_frame_bound),
+    })
+}
+
+fn translate_window_frame_bound(sql_window_frame_bound: &SqlWindowFrameBound) -> WindowFrameBound {
+    match sql_window_frame_bound {
+        SqlWindowFrameBound::CurrentRow => WindowFrameBound::CurrentRow,
+        SqlWindowFrameBound::Preceding(n) => WindowFrameBound::Preceding(*n),
+        SqlWindowFrameBound::Following(n) => WindowFrameBound::Following(*n),
+    }
+}
@@ -4,7 +4,7 @@ mod payload;
 mod utils;
 
 use {
-    gluesql_core::prelude::{execute, parse, plan, translate},
+    gluesql_core::prelude::{execute, parse_extended, plan, translate_extended},
     js_sys::Promise,
     memory_storage::MemoryStorage,
     payload::convert,
@@ -55,13 +55,14 @@ impl Glue {
         let cell = Rc::clone(&self.storage);
 
         future_to_promise(async move {
-            let queries = parse(&sql).map_err(|error| JsValue::from_str(&format!("{error}")))?;
+            let queries =
+                parse_extended(&sql).map_err(|error| JsValue::from_str(&format!("{error}")))?;
 
             let mut payloads = vec![];
             let mut storage: MemoryStorage = cell.replace(None).unwrap();
 
             for query in queries.iter() {
-                let statement = translate(query);
+                let statement = translate_extended(query);
                 let statement = match statement {
                     Ok(statement) => statement,
                     Err(error) => {
@@ -19,6 +19,9 @@ pub fn convert(payloads: Vec<Payload>) -> JsValue {
 fn convert_payload(payload: Payload) -> Json {
     match payload {
         Payload::Create => json!({ "type": "CREATE TABLE" }),
+        Payload::Truncate => json!({ "type": "TRUNCATE TABLE" }),
+        Payload::DropSequence => json!({ "type": "DROP SEQUENCE" }),
+        Payload::DropFunction => json!({ "type": "DROP FUNCTION" }),
         Payload::DropTable => json!({ "type": "DROP TABLE" }),
         Payload::Select { labels, rows } => {
             let rows = rows
@@ -89,12 +92,23 @@ fn convert_payload(payload: Payload) -> Json {
             "type": "DELETE",
             "affected": num
         }),
+        Payload::Merge(num) => json!({
+            "type": "MERGE",
+            "affected": num
+        }),
+        Payload::RefreshMaterializedView(num) => json!({
+            "type": "REFRESH MATERIALIZED VIEW",
+            "affected": num
+        }),
         Payload::AlterTable => json!({ "type": "ALTER TABLE" }),
         Payload::CreateIndex => json!({ "type": "CREATE INDEX" }),
         Payload::DropIndex => json!({ "type": "DROP INDEX" }),
         Payload::StartTransaction => json!({ "type": "BEGIN" }),
         Payload::Commit => json!({ "type": "COMMIT" }),
         Payload::Rollback => json!({ "type": "ROLLBACK" }),
+        Payload::Savepoint => json!({ "type": "SAVEPOINT" }),
+        Payload::RollbackToSavepoint => json!({ "type": "ROLLBACK TO SAVEPOINT" }),
+        Payload::ReleaseSavepoint => json!({ "type": "RELEASE SAVEPOINT" }),
         Payload::ShowVariable(PayloadVariable::Version(version)) => {
             json!({
                 "type": "SHOW VERSION",
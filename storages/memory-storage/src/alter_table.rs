@@ -2,7 +2,7 @@ use {
     super::MemoryStorage,
     async_trait::async_trait,
     gluesql_core::{
-        ast::ColumnDef,
+        ast::{ColumnDef, DataType},
         data::{schema::ColumnDefExt, Value},
         result::{MutResult, Result, TrySelf},
         store::AlterTable,
@@ -72,10 +72,10 @@ impl MemoryStorage {
 
                 evaluated.try_into_value(data_type, nullable)?
             }
-            (None, true) => Value::Null,
-            (None, false) => {
+            (None, false) if !item.rows.is_empty() => {
                 return Err(AlterTableError::DefaultValueRequired(column_def.clone()).into())
             }
+            (None, _) => Value::Null,
         };
 
         let item = self
@@ -126,6 +126,41 @@ impl MemoryStorage {
 
         Ok(())
     }
+
+    pub fn alter_column_type(
+        &mut self,
+        table_name: &str,
+        column_name: &str,
+        data_type: &DataType,
+    ) -> Result<()> {
+        let item = self
+            .items
+            .get_mut(table_name)
+            .ok_or_else(|| AlterTableError::TableNotFound(table_name.to_owned()))?;
+
+        let column_index = item
+            .schema
+            .column_defs
+            .iter()
+            .position(|column_def| column_def.name == column_name)
+            .ok_or_else(|| AlterTableError::AlteringColumnNotFound(column_name.to_owned()))?;
+
+        let values = item
+            .rows
+            .values()
+            .map(|row| row.0.get(column_index).map(|value| value.cast(data_type)))
+            .map(Option::transpose)
+            .collect::<Result<Vec<_>>>()?;
+
+        item.rows.values_mut().zip(values).for_each(|(row, value)| {
+            if let Some(value) = value {
+                row.0[column_index] = value;
+            }
+        });
+        item.schema.column_defs[column_index].data_type = data_type.clone();
+
+        Ok(())
+    }
 }
 
 #[async_trait(?Send)]
@@ -165,4 +200,16 @@ impl AlterTable for MemoryStorage {
         MemoryStorage::drop_column(&mut storage, table_name, column_name, if_exists)
             .try_self(storage)
     }
+
+    async fn alter_column_type(
+        self,
+        table_name: &str,
+        column_name: &str,
+        data_type: &DataType,
+    ) -> MutResult<Self, ()> {
+        let mut storage = self;
+
+        MemoryStorage::alter_column_type(&mut storage, table_name, column_name, data_type)
+            .try_self(storage)
+    }
 }
@@ -6,7 +6,7 @@ mod transaction;
 use {
     async_trait::async_trait,
     gluesql_core::{
-        data::{Key, Row, Schema},
+        data::{Key, Row, Schema, SchemaFunction},
         result::{MutResult, Result},
         store::{GStore, GStoreMut, RowIter, Store, StoreMut},
     },
@@ -25,6 +25,8 @@ pub struct Item {
 pub struct MemoryStorage {
     pub id_counter: i64,
     pub items: HashMap<String, Item>,
+    pub sequences: HashMap<String, i64>,
+    pub functions: HashMap<String, SchemaFunction>,
 }
 
 #[async_trait(?Send)]
@@ -44,6 +46,14 @@ impl Store for MemoryStorage {
 
         Ok(rows)
     }
+
+    async fn fetch_sequence(&self, name: &str) -> Result<Option<i64>> {
+        Ok(self.sequences.get(name).copied())
+    }
+
+    async fn fetch_functions(&self) -> Result<Vec<SchemaFunction>> {
+        Ok(self.functions.values().cloned().collect())
+    }
 }
 
 impl MemoryStorage {
@@ -86,6 +96,29 @@ impl MemoryStorage {
             }
         }
     }
+
+    pub fn truncate_data(&mut self, table_name: &str) {
+        if let Some(item) = self.items.get_mut(table_name) {
+            item.rows.clear();
+        }
+    }
+
+    pub fn update_sequence(&mut self, name: &str, value: i64) {
+        self.sequences.insert(name.to_owned(), value);
+    }
+
+    pub fn delete_sequence(&mut self, name: &str) {
+        self.sequences.remove(name);
+    }
+
+    pub fn insert_function(&mut self, function: &SchemaFunction) {
+        self.functions
+            .insert(function.name.clone(), function.clone());
+    }
+
+    pub fn delete_function(&mut self, name: &str) {
+        self.functions.remove(name);
+    }
 }
 
 #[async_trait(?Send)]
@@ -129,6 +162,46 @@ impl StoreMut for MemoryStorage {
 
         Ok((storage, ()))
     }
+
+    async fn truncate_data(self, table_name: &str) -> MutResult<Self, ()> {
+        let mut storage = self;
+
+        MemoryStorage::truncate_data(&mut storage, table_name);
+
+        Ok((storage, ()))
+    }
+
+    async fn update_sequence(self, name: &str, value: i64) -> MutResult<Self, ()> {
+        let mut storage = self;
+
+        MemoryStorage::update_sequence(&mut storage, name, value);
+
+        Ok((storage, ()))
+    }
+
+    async fn delete_sequence(self, name: &str) -> MutResult<Self, ()> {
+        let mut storage = self;
+
+        MemoryStorage::delete_sequence(&mut storage, name);
+
+        Ok((storage, ()))
+    }
+
+    async fn insert_function(self, function: &SchemaFunction) -> MutResult<Self, ()> {
+        let mut storage = self;
+
+        MemoryStorage::insert_function(&mut storage, function);
+
+        Ok((storage, ()))
+    }
+
+    async fn delete_function(self, name: &str) -> MutResult<Self, ()> {
+        let mut storage = self;
+
+        MemoryStorage::delete_function(&mut storage, name);
+
+        Ok((storage, ()))
+    }
 }
 
 impl GStore for MemoryStorage {}
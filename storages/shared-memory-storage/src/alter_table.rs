@@ -1,7 +1,11 @@
 use {
     super::SharedMemoryStorage,
     async_trait::async_trait,
-    gluesql_core::{ast::ColumnDef, result::MutResult, store::AlterTable},
+    gluesql_core::{
+        ast::{ColumnDef, DataType},
+        result::MutResult,
+        store::AlterTable,
+    },
     memory_storage::MemoryStorage,
     std::sync::Arc,
 };
@@ -69,4 +73,22 @@ impl AlterTable for SharedMemoryStorage {
 
         Ok((self, ()))
     }
+
+    async fn alter_column_type(
+        self,
+        table_name: &str,
+        column_name: &str,
+        data_type: &DataType,
+    ) -> MutResult<Self, ()> {
+        let database = Arc::clone(&self.database);
+        let mut database = database.write().await;
+
+        if let Err(error) =
+            MemoryStorage::alter_column_type(&mut database, table_name, column_name, data_type)
+        {
+            return Err((self, error));
+        }
+
+        Ok((self, ()))
+    }
 }
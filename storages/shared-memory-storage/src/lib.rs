@@ -6,7 +6,7 @@ mod transaction;
 use {
     async_trait::async_trait,
     gluesql_core::{
-        data::{Key, Row, Schema},
+        data::{Key, Row, Schema, SchemaFunction},
         result::{MutResult, Result},
         store::{GStore, GStoreMut, RowIter, Store, StoreMut},
     },
@@ -57,6 +57,20 @@ impl Store for SharedMemoryStorage {
 
         database.scan_data(table_name).await
     }
+
+    async fn fetch_sequence(&self, name: &str) -> Result<Option<i64>> {
+        let database = Arc::clone(&self.database);
+        let database = database.read().await;
+
+        database.fetch_sequence(name).await
+    }
+
+    async fn fetch_functions(&self) -> Result<Vec<SchemaFunction>> {
+        let database = Arc::clone(&self.database);
+        let database = database.read().await;
+
+        database.fetch_functions().await
+    }
 }
 
 #[async_trait(?Send)]
@@ -105,6 +119,51 @@ impl StoreMut for SharedMemoryStorage {
 
         Ok((self, ()))
     }
+
+    async fn truncate_data(self, table_name: &str) -> MutResult<Self, ()> {
+        let database = Arc::clone(&self.database);
+        let mut database = database.write().await;
+
+        MemoryStorage::truncate_data(&mut database, table_name);
+
+        Ok((self, ()))
+    }
+
+    async fn update_sequence(self, name: &str, value: i64) -> MutResult<Self, ()> {
+        let database = Arc::clone(&self.database);
+        let mut database = database.write().await;
+
+        MemoryStorage::update_sequence(&mut database, name, value);
+
+        Ok((self, ()))
+    }
+
+    async fn delete_sequence(self, name: &str) -> MutResult<Self, ()> {
+        let database = Arc::clone(&self.database);
+        let mut database = database.write().await;
+
+        MemoryStorage::delete_sequence(&mut database, name);
+
+        Ok((self, ()))
+    }
+
+    async fn insert_function(self, function: &SchemaFunction) -> MutResult<Self, ()> {
+        let database = Arc::clone(&self.database);
+        let mut database = database.write().await;
+
+        MemoryStorage::insert_function(&mut database, function);
+
+        Ok((self, ()))
+    }
+
+    async fn delete_function(self, name: &str) -> MutResult<Self, ()> {
+        let database = Arc::clone(&self.database);
+        let mut database = database.write().await;
+
+        MemoryStorage::delete_function(&mut database, name);
+
+        Ok((self, ()))
+    }
 }
 
 impl GStore for SharedMemoryStorage {}
@@ -8,7 +8,7 @@ use {
     },
     async_trait::async_trait,
     gluesql_core::{
-        ast::ColumnDef,
+        ast::{ColumnDef, DataType},
         data::{
             schema::{ColumnDefExt, Schema},
             Row, Value,
@@ -53,6 +53,8 @@ impl AlterTable for SledStorage {
             let Schema {
                 column_defs,
                 indexes,
+                materialized_view,
+                view,
                 ..
             } = old_schema
                 .ok_or_else(|| AlterTableError::TableNotFound(table_name.to_string()).into())
@@ -62,6 +64,8 @@ impl AlterTable for SledStorage {
                 table_name: new_table_name.to_string(),
                 column_defs,
                 indexes,
+                materialized_view,
+                view,
             };
 
             bincode::serialize(&old_snapshot)
@@ -158,6 +162,8 @@ impl AlterTable for SledStorage {
             let Schema {
                 column_defs,
                 indexes,
+                materialized_view,
+                view,
                 ..
             } = snapshot
                 .get(txid, None)
@@ -185,6 +191,8 @@ impl AlterTable for SledStorage {
                 table_name: table_name.to_string(),
                 column_defs,
                 indexes,
+                materialized_view,
+                view,
             };
             let (snapshot, _) = snapshot.update(txid, schema);
             let value = bincode::serialize(&snapshot)
@@ -235,6 +243,8 @@ impl AlterTable for SledStorage {
                 table_name,
                 column_defs,
                 indexes,
+                materialized_view,
+                view,
             } = schema_snapshot
                 .get(txid, None)
                 .ok_or_else(|| AlterTableError::TableNotFound(table_name.to_string()).into())
@@ -260,13 +270,12 @@ impl AlterTable for SledStorage {
 
                     evaluated
                         .try_into_value(data_type, nullable)
+                        .map(Some)
                         .map_err(ConflictableTransactionError::Abort)?
                 }
-                (None, true) => Value::Null,
-                (None, false) => {
-                    return Err(AlterTableError::DefaultValueRequired(column_def.clone()).into())
-                        .map_err(ConflictableTransactionError::Abort);
-                }
+                (None, true) => Some(Value::Null),
+                // only an empty table can take a NOT NULL column without a default
+                (None, false) => None,
             };
 
             // migrate data
@@ -280,7 +289,11 @@ impl AlterTable for SledStorage {
                         continue;
                     }
                 };
-                let row = Row(row.0.into_iter().chain(once(value.clone())).collect());
+                let value = value
+                    .clone()
+                    .ok_or_else(|| AlterTableError::DefaultValueRequired(column_def.clone()).into())
+                    .map_err(ConflictableTransactionError::Abort)?;
+                let row = Row(row.0.into_iter().chain(once(value)).collect());
 
                 let (snapshot, _) = snapshot.update(txid, row);
                 let snapshot = bincode::serialize(&snapshot)
@@ -308,6 +321,8 @@ impl AlterTable for SledStorage {
                 table_name,
                 column_defs,
                 indexes,
+                materialized_view,
+                view,
             };
             let (schema_snapshot, _) = schema_snapshot.update(txid, schema);
             let schema_value = bincode::serialize(&schema_snapshot)
@@ -362,6 +377,8 @@ impl AlterTable for SledStorage {
                 table_name,
                 column_defs,
                 indexes,
+                materialized_view,
+                view,
             } = schema_snapshot
                 .get(txid, None)
                 .ok_or_else(|| AlterTableError::TableNotFound(table_name.to_string()).into())
@@ -428,6 +445,8 @@ impl AlterTable for SledStorage {
                 table_name,
                 column_defs,
                 indexes,
+                materialized_view,
+                view,
             };
             let (schema_snapshot, _) = schema_snapshot.update(txid, schema);
             let schema_value = bincode::serialize(&schema_snapshot)
@@ -447,4 +466,122 @@ impl AlterTable for SledStorage {
         })
         .await
     }
+
+    async fn alter_column_type(
+        self,
+        table_name: &str,
+        column_name: &str,
+        data_type: &DataType,
+    ) -> MutResult<Self, ()> {
+        let prefix = format!("data/{}/", table_name);
+        let items = self
+            .tree
+            .scan_prefix(prefix.as_bytes())
+            .map(|item| item.map_err(err_into))
+            .collect::<Result<Vec<_>>>();
+        let (self, items) = items.try_self(self)?;
+
+        let state = &self.state;
+        let tx_timeout = self.tx_timeout;
+        let tx_result = self.tree.transaction(move |tree| {
+            let (txid, autocommit) = match lock::acquire(tree, state, tx_timeout)? {
+                LockAcquired::Success { txid, autocommit } => (txid, autocommit),
+                LockAcquired::RollbackAndRetry { lock_txid } => {
+                    return Ok(TxPayload::RollbackAndRetry(lock_txid));
+                }
+            };
+
+            let (schema_key, schema_snapshot) = fetch_schema(tree, table_name)?;
+            let schema_snapshot = schema_snapshot
+                .ok_or_else(|| AlterTableError::TableNotFound(table_name.to_string()).into())
+                .map_err(ConflictableTransactionError::Abort)?;
+
+            let Schema {
+                table_name,
+                mut column_defs,
+                indexes,
+                materialized_view,
+                view,
+            } = schema_snapshot
+                .get(txid, None)
+                .ok_or_else(|| AlterTableError::TableNotFound(table_name.to_string()).into())
+                .map_err(ConflictableTransactionError::Abort)?;
+
+            let column_index = column_defs
+                .iter()
+                .position(|ColumnDef { name, .. }| name == column_name)
+                .ok_or_else(|| {
+                    AlterTableError::AlteringColumnNotFound(column_name.to_string()).into()
+                })
+                .map_err(ConflictableTransactionError::Abort)?;
+
+            // migrate data, a failed cast aborts the whole transaction
+            for (key, snapshot) in items.iter() {
+                let snapshot: Snapshot<Row> = bincode::deserialize(snapshot)
+                    .map_err(err_into)
+                    .map_err(ConflictableTransactionError::Abort)?;
+                let row = match snapshot.clone().extract(txid, None) {
+                    Some(row) => row,
+                    None => {
+                        continue;
+                    }
+                };
+                let row = Row(row
+                    .0
+                    .into_iter()
+                    .enumerate()
+                    .map(|(i, v)| {
+                        if i == column_index {
+                            v.cast(data_type)
+                        } else {
+                            Ok(v)
+                        }
+                    })
+                    .collect::<Result<_>>()
+                    .map_err(ConflictableTransactionError::Abort)?);
+
+                let (snapshot, _) = snapshot.update(txid, row);
+                let snapshot = bincode::serialize(&snapshot)
+                    .map_err(err_into)
+                    .map_err(ConflictableTransactionError::Abort)?;
+
+                tree.insert(key, snapshot)?;
+
+                if !autocommit {
+                    let temp_key = key::temp_data(txid, key);
+
+                    tree.insert(temp_key, key)?;
+                }
+            }
+
+            // update schema
+            column_defs[column_index].data_type = data_type.clone();
+
+            let temp_key = key::temp_schema(txid, &table_name);
+
+            let schema = Schema {
+                table_name,
+                column_defs,
+                indexes,
+                materialized_view,
+                view,
+            };
+            let (schema_snapshot, _) = schema_snapshot.update(txid, schema);
+            let schema_value = bincode::serialize(&schema_snapshot)
+                .map_err(err_into)
+                .map_err(ConflictableTransactionError::Abort)?;
+            tree.insert(schema_key.as_bytes(), schema_value)?;
+
+            if !autocommit {
+                tree.insert(temp_key, schema_key.as_bytes())?;
+            }
+
+            Ok(TxPayload::Success)
+        });
+
+        self.check_and_retry(tx_result, |storage| {
+            storage.alter_column_type(table_name, column_name, data_type)
+        })
+        .await
+    }
 }
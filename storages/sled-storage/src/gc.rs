@@ -5,7 +5,7 @@ use {
         SledStorage, Snapshot,
     },
     gluesql_core::{
-        data::{Row, Schema},
+        data::{Row, Schema, SchemaFunction},
         result::Result,
     },
     std::time::{SystemTime, UNIX_EPOCH},
@@ -111,6 +111,8 @@ impl SledStorage {
         for txid in txids {
             gc_txid!(txid, key::temp_data_prefix(txid), Row);
             gc_txid!(txid, key::temp_schema_prefix(txid), Schema);
+            gc_txid!(txid, key::temp_sequence_prefix(txid), i64);
+            gc_txid!(txid, key::temp_function_prefix(txid), SchemaFunction);
 
             for (temp_key, data_key) in fetch_keys(key::temp_index_prefix(txid))? {
                 let snapshots: Option<Vec<Snapshot<Vec<u8>>>> = self
@@ -1,7 +1,7 @@
 use {
     super::{
         err_into,
-        index_sync::{build_index_key, build_index_key_prefix},
+        index_sync::{build_composite_index_key, build_index_key, build_index_key_prefix},
         lock, SledStorage, Snapshot, State,
     },
     async_trait::async_trait,
@@ -14,7 +14,10 @@ use {
     },
     iter_enum::{DoubleEndedIterator, Iterator},
     sled::IVec,
-    std::iter::{empty, once},
+    std::{
+        iter::{empty, once},
+        ops::Bound,
+    },
     utils::Vector,
 };
 
@@ -45,20 +48,6 @@ impl Index for SledStorage {
                     DataIds::Full(self.tree.scan_prefix(prefix).map(map))
                 }
                 Some((op, value)) => {
-                    let incr = |key: Vec<u8>| {
-                        key.into_iter()
-                            .rev()
-                            .fold((false, Vector::new()), |(added, upper), v| {
-                                match (added, v) {
-                                    (true, _) => (added, upper.push(v)),
-                                    (false, u8::MAX) => (added, upper.push(v)),
-                                    (false, _) => (true, upper.push(v + 1)),
-                                }
-                            })
-                            .1
-                            .reverse()
-                            .into()
-                    };
                     let lower = || build_index_key_prefix(table_name, index_name);
                     let upper = || incr(build_index_key_prefix(table_name, index_name));
                     let key = build_index_key(table_name, index_name, value)?;
@@ -83,6 +72,72 @@ impl Index for SledStorage {
             }
         };
 
+        self.scan_data_ids(data_keys, asc)
+    }
+
+    async fn scan_indexed_range(
+        &self,
+        table_name: &str,
+        index_name: &str,
+        asc: Option<bool>,
+        start: Bound<Value>,
+        end: Bound<Value>,
+    ) -> Result<RowIter> {
+        let key = |value| build_index_key(table_name, index_name, value);
+        let start = match start {
+            Bound::Included(value) => Bound::Included(key(value)?),
+            Bound::Excluded(value) => Bound::Included(incr(key(value)?)),
+            Bound::Unbounded => Bound::Included(build_index_key_prefix(table_name, index_name)),
+        };
+        let end = match end {
+            Bound::Included(value) => Bound::Included(key(value)?),
+            Bound::Excluded(value) => Bound::Excluded(key(value)?),
+            Bound::Unbounded => {
+                Bound::Excluded(incr(build_index_key_prefix(table_name, index_name)))
+            }
+        };
+
+        if let (
+            Bound::Included(start) | Bound::Excluded(start),
+            Bound::Included(end) | Bound::Excluded(end),
+        ) = (&start, &end)
+        {
+            if start > end {
+                return Ok(Box::new(empty()));
+            }
+        }
+
+        let data_keys = self
+            .tree
+            .range((start, end))
+            .map(|item| item.map(|(_, v)| v));
+
+        self.scan_data_ids(data_keys, asc)
+    }
+
+    async fn scan_indexed_prefix(
+        &self,
+        table_name: &str,
+        index_name: &str,
+        asc: Option<bool>,
+        values: Vec<Value>,
+    ) -> Result<RowIter> {
+        let prefix = build_composite_index_key(table_name, index_name, values)?;
+        let data_keys = self
+            .tree
+            .scan_prefix(prefix)
+            .map(|item| item.map(|(_, v)| v));
+
+        self.scan_data_ids(data_keys, asc)
+    }
+}
+
+impl SledStorage {
+    /// Rows of the data keys each index entry of `data_ids` holds, in the order of the entries.
+    fn scan_data_ids<I>(&self, data_ids: I, asc: Option<bool>) -> Result<RowIter>
+    where
+        I: DoubleEndedIterator<Item = sled::Result<IVec>> + 'static,
+    {
         let (txid, created_at) = match self.state {
             State::Transaction {
                 txid, created_at, ..
@@ -144,7 +199,7 @@ impl Index for SledStorage {
             Rows::Ok(rows)
         };
 
-        let data_keys = data_keys.map(|v| v.map_err(err_into));
+        let data_keys = data_ids.map(|v| v.map_err(err_into));
 
         Ok(match asc {
             Some(true) | None => Box::new(data_keys.flat_map(flat_map)),
@@ -152,3 +207,19 @@ impl Index for SledStorage {
         })
     }
 }
+
+/// The smallest key greater than all the keys `key` prefixes.
+fn incr(key: Vec<u8>) -> Vec<u8> {
+    key.into_iter()
+        .rev()
+        .fold((false, Vector::new()), |(added, upper), v| {
+            match (added, v) {
+                (true, _) => (added, upper.push(v)),
+                (false, u8::MAX) => (added, upper.push(v)),
+                (false, _) => (true, upper.push(v + 1)),
+            }
+        })
+        .1
+        .reverse()
+        .into()
+}
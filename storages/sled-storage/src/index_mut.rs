@@ -70,6 +70,8 @@ impl IndexMut for SledStorage {
             let Schema {
                 column_defs,
                 indexes,
+                materialized_view,
+                view,
                 ..
             } = schema
                 .ok_or_else(|| IndexError::ConflictTableNotFound(table_name.to_owned()).into())
@@ -95,6 +97,8 @@ impl IndexMut for SledStorage {
                 table_name: table_name.to_owned(),
                 column_defs,
                 indexes,
+                materialized_view,
+                view,
             };
 
             let index_sync = IndexSync::from_schema(tree, txid, &schema);
@@ -147,6 +151,8 @@ impl IndexMut for SledStorage {
             let Schema {
                 column_defs,
                 indexes,
+                materialized_view,
+                view,
                 ..
             } = schema
                 .ok_or_else(|| IndexError::ConflictTableNotFound(table_name.to_owned()).into())
@@ -168,6 +174,8 @@ impl IndexMut for SledStorage {
                 table_name: table_name.to_owned(),
                 column_defs,
                 indexes,
+                materialized_view,
+                view,
             };
 
             let index_sync = IndexSync::from_schema(tree, txid, &schema);
@@ -251,13 +251,19 @@ fn evaluate_index_key(
     columns: &[String],
     row: &Row,
 ) -> ConflictableTransactionResult<Vec<u8>, Error> {
-    let evaluated = evaluate_stateless(Some((columns, row)), index_expr)
-        .map_err(ConflictableTransactionError::Abort)?;
-    let value: Value = evaluated
-        .try_into()
-        .map_err(ConflictableTransactionError::Abort)?;
+    let evaluate = |expr: &Expr| -> Result<Value> {
+        evaluate_stateless(Some((columns, row)), expr)?.try_into()
+    };
 
-    build_index_key(table_name, index_name, value).map_err(ConflictableTransactionError::Abort)
+    match index_expr {
+        Expr::Tuple(exprs) => exprs
+            .iter()
+            .map(evaluate)
+            .collect::<Result<Vec<_>>>()
+            .and_then(|values| build_composite_index_key(table_name, index_name, values)),
+        expr => evaluate(expr).and_then(|value| build_index_key(table_name, index_name, value)),
+    }
+    .map_err(ConflictableTransactionError::Abort)
 }
 
 pub fn build_index_key_prefix(table_name: &str, index_name: &str) -> Vec<u8> {
@@ -270,3 +276,29 @@ pub fn build_index_key(table_name: &str, index_name: &str, value: Value) -> Resu
         .chain(value.to_cmp_be_bytes()?)
         .collect::<Vec<_>>())
 }
+
+/// Key of a composite index, which joins the values of its columns, or the key prefix of the
+/// rows whose leading columns hold `values`.
+///
+/// Each value is terminated by `[0, 0]` with its zero bytes escaped into `[0, 1]`, which keeps
+/// the order of the values and makes no value a prefix of another.
+pub fn build_composite_index_key(
+    table_name: &str,
+    index_name: &str,
+    values: Vec<Value>,
+) -> Result<Vec<u8>> {
+    let mut key = build_index_key_prefix(table_name, index_name);
+
+    for value in values {
+        for byte in value.to_cmp_be_bytes()? {
+            match byte {
+                0 => key.extend([0, 1]),
+                byte => key.push(byte),
+            }
+        }
+
+        key.extend([0, 0]);
+    }
+
+    Ok(key)
+}
@@ -3,6 +3,9 @@ use sled::IVec;
 const TEMP_DATA: &str = "temp_data/";
 const TEMP_SCHEMA: &str = "temp_schema/";
 const TEMP_INDEX: &str = "temp_index/";
+const TEMP_SEQUENCE: &str = "temp_sequence/";
+const TEMP_FUNCTION: &str = "temp_function/";
+const SAVEPOINTS: &str = "savepoints/";
 
 macro_rules! prefix {
     ($txid: ident, $prefix: ident) => {
@@ -26,6 +29,14 @@ pub fn temp_index_prefix(txid: u64) -> IVec {
     IVec::from(prefix!(txid, TEMP_INDEX).collect::<Vec<_>>())
 }
 
+pub fn temp_sequence_prefix(txid: u64) -> IVec {
+    IVec::from(prefix!(txid, TEMP_SEQUENCE).collect::<Vec<_>>())
+}
+
+pub fn temp_function_prefix(txid: u64) -> IVec {
+    IVec::from(prefix!(txid, TEMP_FUNCTION).collect::<Vec<_>>())
+}
+
 pub fn temp_data(txid: u64, data_key: &IVec) -> IVec {
     let key = prefix!(txid, TEMP_DATA)
         .chain(data_key.iter().copied())
@@ -57,3 +68,23 @@ pub fn temp_index(txid: u64, index_key: &[u8]) -> IVec {
 
     IVec::from(key)
 }
+
+pub fn temp_sequence(txid: u64, name: &str) -> IVec {
+    let key = prefix!(txid, TEMP_SEQUENCE)
+        .chain(name.as_bytes().iter().copied())
+        .collect::<Vec<_>>();
+
+    IVec::from(key)
+}
+
+pub fn temp_function(txid: u64, name: &str) -> IVec {
+    let key = prefix!(txid, TEMP_FUNCTION)
+        .chain(name.as_bytes().iter().copied())
+        .collect::<Vec<_>>();
+
+    IVec::from(key)
+}
+
+pub fn savepoints(txid: u64) -> IVec {
+    IVec::from(prefix!(txid, SAVEPOINTS).collect::<Vec<_>>())
+}
@@ -2,7 +2,7 @@ use {
     super::{err_into, lock, SledStorage, Snapshot, State},
     async_trait::async_trait,
     gluesql_core::{
-        data::{Key, Row, Schema},
+        data::{Key, Row, Schema, SchemaFunction},
         result::{Error, Result},
         store::{RowIter, Store},
     },
@@ -67,4 +67,61 @@ impl Store for SledStorage {
 
         Ok(Box::new(result_set))
     }
+
+    async fn fetch_sequence(&self, name: &str) -> Result<Option<i64>> {
+        let (txid, created_at, temp) = match self.state {
+            State::Transaction {
+                txid, created_at, ..
+            } => (txid, created_at, false),
+            State::Idle => lock::register(&self.tree, self.id_offset)
+                .map(|(txid, created_at)| (txid, created_at, true))?,
+        };
+        let lock_txid = lock::fetch(&self.tree, txid, created_at, self.tx_timeout)?;
+
+        let key = format!("sequence/{}", name);
+        let value = self
+            .tree
+            .get(key.as_bytes())
+            .map_err(err_into)?
+            .map(|v| bincode::deserialize(&v))
+            .transpose()
+            .map_err(err_into)?
+            .and_then(|snapshot: Snapshot<i64>| snapshot.extract(txid, lock_txid));
+
+        if temp {
+            lock::unregister(&self.tree, txid)?;
+        }
+
+        Ok(value)
+    }
+
+    async fn fetch_functions(&self) -> Result<Vec<SchemaFunction>> {
+        let (txid, created_at, temp) = match self.state {
+            State::Transaction {
+                txid, created_at, ..
+            } => (txid, created_at, false),
+            State::Idle => lock::register(&self.tree, self.id_offset)
+                .map(|(txid, created_at)| (txid, created_at, true))?,
+        };
+        let lock_txid = lock::fetch(&self.tree, txid, created_at, self.tx_timeout)?;
+
+        let functions = self
+            .tree
+            .scan_prefix("function/")
+            .map(|item| {
+                let (_, value) = item.map_err(err_into)?;
+                let snapshot: Snapshot<SchemaFunction> =
+                    bincode::deserialize(&value).map_err(err_into)?;
+
+                Ok(snapshot.extract(txid, lock_txid))
+            })
+            .filter_map(|item| item.transpose())
+            .collect::<Result<Vec<_>>>();
+
+        if temp {
+            lock::unregister(&self.tree, txid)?;
+        }
+
+        functions
+    }
 }
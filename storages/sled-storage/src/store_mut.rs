@@ -9,12 +9,12 @@ use {
     },
     async_trait::async_trait,
     gluesql_core::{
-        data::{Key, Row, Schema},
+        data::{Key, Row, Schema, SchemaFunction},
         result::MutResult,
         result::Result,
         store::{IndexError, StoreMut},
     },
-    sled::{transaction::ConflictableTransactionError, IVec},
+    sled::{transaction::ConflictableTransactionError, Batch, IVec},
 };
 
 #[async_trait(?Send)]
@@ -302,4 +302,253 @@ impl StoreMut for SledStorage {
         self.check_and_retry(tx_result, |storage| storage.delete_data(table_name, keys))
             .await
     }
+
+    /// Deletes the rows and index entries of the table by their key prefixes in one batch.
+    ///
+    /// Inside a transaction the rows are deleted one by one instead, keeping their snapshots so
+    /// that ROLLBACK restores them. Like TRUNCATE of other databases, a batch delete is not
+    /// seen as a snapshot, a transaction which started earlier no longer reads the rows.
+    async fn truncate_data(self, table_name: &str) -> MutResult<Self, ()> {
+        let scan_keys = |prefix: String| {
+            self.tree
+                .scan_prefix(prefix.as_bytes())
+                .keys()
+                .map(|key| key.map_err(err_into))
+                .collect::<Result<Vec<_>>>()
+        };
+        let keys = scan_keys(format!("data/{}/", table_name)).and_then(|data_keys| {
+            scan_keys(format!("index/{}/", table_name)).map(|index_keys| (data_keys, index_keys))
+        });
+        let (data_keys, index_keys) = match keys {
+            Ok(keys) => keys,
+            Err(e) => {
+                return Err((self, e));
+            }
+        };
+
+        let state = &self.state;
+        let tx_timeout = self.tx_timeout;
+        let (tx_data_keys, tx_index_keys) = (&data_keys, &index_keys);
+
+        let tx_result = self.tree.transaction(move |tree| {
+            let (txid, autocommit) = match lock::acquire(tree, state, tx_timeout)? {
+                LockAcquired::Success { txid, autocommit } => (txid, autocommit),
+                LockAcquired::RollbackAndRetry { lock_txid } => {
+                    return Ok(TxPayload::RollbackAndRetry(lock_txid));
+                }
+            };
+
+            if autocommit {
+                let mut batch = Batch::default();
+                for key in tx_data_keys.iter().chain(tx_index_keys.iter()) {
+                    batch.remove(key);
+                }
+
+                tree.apply_batch(&batch)?;
+
+                return Ok(TxPayload::Success);
+            }
+
+            let index_sync = IndexSync::new(tree, txid, table_name)?;
+
+            for key in tx_data_keys.iter() {
+                let snapshot: Snapshot<Row> = match tree.get(key)? {
+                    Some(snapshot) => bincode::deserialize(&snapshot)
+                        .map_err(err_into)
+                        .map_err(ConflictableTransactionError::Abort)?,
+                    None => {
+                        continue;
+                    }
+                };
+
+                let (snapshot, row) = snapshot.delete(txid);
+                let row = match row {
+                    Some(row) => row,
+                    None => {
+                        continue;
+                    }
+                };
+
+                bincode::serialize(&snapshot)
+                    .map_err(err_into)
+                    .map_err(ConflictableTransactionError::Abort)
+                    .map(|snapshot| tree.insert(key, snapshot))??;
+
+                index_sync.delete(key, &row)?;
+
+                let temp_key = key::temp_data(txid, key);
+
+                tree.insert(temp_key, key)?;
+            }
+
+            Ok(TxPayload::Success)
+        });
+
+        self.check_and_retry(tx_result, |storage| storage.truncate_data(table_name))
+            .await
+    }
+
+    async fn update_sequence(self, name: &str, value: i64) -> MutResult<Self, ()> {
+        let state = &self.state;
+        let tx_timeout = self.tx_timeout;
+
+        let tx_result = self.tree.transaction(move |tree| {
+            let txid = match lock::acquire(tree, state, tx_timeout)? {
+                LockAcquired::Success { txid, .. } => txid,
+                LockAcquired::RollbackAndRetry { lock_txid } => {
+                    return Ok(TxPayload::RollbackAndRetry(lock_txid));
+                }
+            };
+
+            let key = format!("sequence/{}", name);
+            let temp_key = key::temp_sequence(txid, name);
+
+            let snapshot: Option<Snapshot<i64>> = tree
+                .get(key.as_bytes())?
+                .map(|v| bincode::deserialize(&v))
+                .transpose()
+                .map_err(err_into)
+                .map_err(ConflictableTransactionError::Abort)?;
+
+            let snapshot = match snapshot {
+                Some(snapshot) => snapshot.update(txid, value).0,
+                None => Snapshot::new(txid, value),
+            };
+            let snapshot = bincode::serialize(&snapshot)
+                .map_err(err_into)
+                .map_err(ConflictableTransactionError::Abort)?;
+
+            tree.insert(key.as_bytes(), snapshot)?;
+            tree.insert(temp_key, key.as_bytes())?;
+
+            Ok(TxPayload::Success)
+        });
+
+        self.check_and_retry(tx_result, |storage| storage.update_sequence(name, value))
+            .await
+    }
+
+    async fn delete_sequence(self, name: &str) -> MutResult<Self, ()> {
+        let state = &self.state;
+        let tx_timeout = self.tx_timeout;
+
+        let tx_result = self.tree.transaction(move |tree| {
+            let txid = match lock::acquire(tree, state, tx_timeout)? {
+                LockAcquired::Success { txid, .. } => txid,
+                LockAcquired::RollbackAndRetry { lock_txid } => {
+                    return Ok(TxPayload::RollbackAndRetry(lock_txid));
+                }
+            };
+
+            let key = format!("sequence/{}", name);
+            let temp_key = key::temp_sequence(txid, name);
+
+            let snapshot: Option<Snapshot<i64>> = tree
+                .get(key.as_bytes())?
+                .map(|v| bincode::deserialize(&v))
+                .transpose()
+                .map_err(err_into)
+                .map_err(ConflictableTransactionError::Abort)?;
+
+            let snapshot = match snapshot {
+                Some(snapshot) => snapshot.delete(txid).0,
+                None => {
+                    return Ok(TxPayload::Success);
+                }
+            };
+            let snapshot = bincode::serialize(&snapshot)
+                .map_err(err_into)
+                .map_err(ConflictableTransactionError::Abort)?;
+
+            tree.insert(key.as_bytes(), snapshot)?;
+            tree.insert(temp_key, key.as_bytes())?;
+
+            Ok(TxPayload::Success)
+        });
+
+        self.check_and_retry(tx_result, |storage| storage.delete_sequence(name))
+            .await
+    }
+
+    async fn insert_function(self, function: &SchemaFunction) -> MutResult<Self, ()> {
+        let state = &self.state;
+        let tx_timeout = self.tx_timeout;
+
+        let tx_result = self.tree.transaction(move |tree| {
+            let txid = match lock::acquire(tree, state, tx_timeout)? {
+                LockAcquired::Success { txid, .. } => txid,
+                LockAcquired::RollbackAndRetry { lock_txid } => {
+                    return Ok(TxPayload::RollbackAndRetry(lock_txid));
+                }
+            };
+
+            let key = format!("function/{}", function.name);
+            let temp_key = key::temp_function(txid, &function.name);
+
+            let snapshot: Option<Snapshot<SchemaFunction>> = tree
+                .get(key.as_bytes())?
+                .map(|v| bincode::deserialize(&v))
+                .transpose()
+                .map_err(err_into)
+                .map_err(ConflictableTransactionError::Abort)?;
+
+            let snapshot = match snapshot {
+                Some(snapshot) => snapshot.update(txid, function.clone()).0,
+                None => Snapshot::new(txid, function.clone()),
+            };
+            let snapshot = bincode::serialize(&snapshot)
+                .map_err(err_into)
+                .map_err(ConflictableTransactionError::Abort)?;
+
+            tree.insert(key.as_bytes(), snapshot)?;
+            tree.insert(temp_key, key.as_bytes())?;
+
+            Ok(TxPayload::Success)
+        });
+
+        self.check_and_retry(tx_result, |storage| storage.insert_function(function))
+            .await
+    }
+
+    async fn delete_function(self, name: &str) -> MutResult<Self, ()> {
+        let state = &self.state;
+        let tx_timeout = self.tx_timeout;
+
+        let tx_result = self.tree.transaction(move |tree| {
+            let txid = match lock::acquire(tree, state, tx_timeout)? {
+                LockAcquired::Success { txid, .. } => txid,
+                LockAcquired::RollbackAndRetry { lock_txid } => {
+                    return Ok(TxPayload::RollbackAndRetry(lock_txid));
+                }
+            };
+
+            let key = format!("function/{}", name);
+            let temp_key = key::temp_function(txid, name);
+
+            let snapshot: Option<Snapshot<SchemaFunction>> = tree
+                .get(key.as_bytes())?
+                .map(|v| bincode::deserialize(&v))
+                .transpose()
+                .map_err(err_into)
+                .map_err(ConflictableTransactionError::Abort)?;
+
+            let snapshot = match snapshot {
+                Some(snapshot) => snapshot.delete(txid).0,
+                None => {
+                    return Ok(TxPayload::Success);
+                }
+            };
+            let snapshot = bincode::serialize(&snapshot)
+                .map_err(err_into)
+                .map_err(ConflictableTransactionError::Abort)?;
+
+            tree.insert(key.as_bytes(), snapshot)?;
+            tree.insert(temp_key, key.as_bytes())?;
+
+            Ok(TxPayload::Success)
+        });
+
+        self.check_and_retry(tx_result, |storage| storage.delete_function(name))
+            .await
+    }
 }
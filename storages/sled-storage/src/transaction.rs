@@ -8,12 +8,12 @@ use {
     },
     async_trait::async_trait,
     gluesql_core::{
-        data::{Row, Schema},
+        data::{Row, Schema, SchemaFunction},
         result::MutResult,
         result::{Error, Result},
         store::Transaction,
     },
-    serde::{de::DeserializeOwned, Serialize},
+    serde::{de::DeserializeOwned, Deserialize, Serialize},
     sled::{
         transaction::{
             ConflictableTransactionError, ConflictableTransactionResult, TransactionError,
@@ -21,7 +21,7 @@ use {
         },
         IVec,
     },
-    std::result::Result as StdResult,
+    std::{collections::HashSet, result::Result as StdResult},
 };
 
 macro_rules! transaction {
@@ -52,6 +52,14 @@ pub enum TxPayload {
     RollbackAndRetry(u64),
 }
 
+/// Keys a transaction wrote before `SAVEPOINT name`, with their values at that point.
+#[derive(Serialize, Deserialize)]
+struct Savepoint {
+    name: String,
+    /// temp key, value key and the value of the value key
+    items: Vec<(Vec<u8>, Vec<u8>, Option<Vec<u8>>)>,
+}
+
 #[async_trait(?Send)]
 impl Transaction for SledStorage {
     async fn begin(self, autocommit: bool) -> MutResult<Self, bool> {
@@ -136,7 +144,11 @@ impl Transaction for SledStorage {
             return Err((self, e));
         }
 
-        let (storage, _) = transaction!(self, move |tree| { lock::release(tree, txid) })?;
+        let (storage, _) = transaction!(self, move |tree| {
+            tree.remove(key::savepoints(txid))?;
+
+            lock::release(tree, txid)
+        })?;
         let gc = || {
             if storage.tree.get("gc_lock").map_err(err_into)?.is_some() {
                 return Ok(());
@@ -156,14 +168,146 @@ impl Transaction for SledStorage {
             Err(e) => Err((storage, e)),
         }
     }
+
+    async fn savepoint(self, name: &str) -> MutResult<Self, ()> {
+        let txid = match self.state {
+            State::Transaction { txid, .. } => txid,
+            State::Idle => {
+                return Err((
+                    self,
+                    Error::StorageMsg("no transaction to make a savepoint in".to_owned()),
+                ));
+            }
+        };
+
+        let savepoint = || {
+            let items = self
+                .fetch_temp_items(txid)?
+                .into_iter()
+                .map(|(temp_key, value_key)| {
+                    let value = self.tree.get(&value_key).map_err(err_into)?;
+
+                    Ok((
+                        temp_key.to_vec(),
+                        value_key.to_vec(),
+                        value.map(|v| v.to_vec()),
+                    ))
+                })
+                .collect::<Result<Vec<_>>>()?;
+
+            let mut savepoints = self.fetch_savepoints(txid)?;
+            savepoints.push(Savepoint {
+                name: name.to_owned(),
+                items,
+            });
+
+            self.insert_savepoints(txid, &savepoints)
+        };
+
+        match savepoint() {
+            Ok(()) => Ok((self, ())),
+            Err(e) => Err((self, e)),
+        }
+    }
+
+    async fn rollback_to_savepoint(self, name: &str) -> MutResult<Self, ()> {
+        let txid = match self.state {
+            State::Transaction { txid, .. } => txid,
+            State::Idle => {
+                return Err((
+                    self,
+                    Error::StorageMsg("no transaction to rollback".to_owned()),
+                ));
+            }
+        };
+
+        let rollback = || {
+            let mut savepoints = self.fetch_savepoints(txid)?;
+            let i = find_savepoint(&savepoints, name)?;
+            savepoints.truncate(i + 1);
+
+            let Savepoint { items, .. } = &savepoints[i];
+            let saved = items
+                .iter()
+                .map(|(temp_key, ..)| temp_key.as_slice())
+                .collect::<HashSet<_>>();
+
+            self.rollback_temp_items(txid, |temp_key| !saved.contains(temp_key.as_ref()))?;
+            self.tree
+                .transaction(|tree| {
+                    for (_, value_key, value) in items.iter() {
+                        match value {
+                            Some(value) => tree.insert(value_key.as_slice(), value.as_slice())?,
+                            None => tree.remove(value_key.as_slice())?,
+                        };
+                    }
+
+                    Ok(())
+                })
+                .map_err(tx_err_into)?;
+
+            self.insert_savepoints(txid, &savepoints)
+        };
+
+        match rollback() {
+            Ok(()) => Ok((self, ())),
+            Err(e) => Err((self, e)),
+        }
+    }
+
+    async fn release_savepoint(self, name: &str) -> MutResult<Self, ()> {
+        let txid = match self.state {
+            State::Transaction { txid, .. } => txid,
+            State::Idle => {
+                return Err((
+                    self,
+                    Error::StorageMsg("no transaction to release a savepoint of".to_owned()),
+                ));
+            }
+        };
+
+        let release = || {
+            let mut savepoints = self.fetch_savepoints(txid)?;
+            let i = find_savepoint(&savepoints, name)?;
+            savepoints.truncate(i);
+
+            self.insert_savepoints(txid, &savepoints)
+        };
+
+        match release() {
+            Ok(()) => Ok((self, ())),
+            Err(e) => Err((self, e)),
+        }
+    }
+}
+
+/// Position of the latest savepoint `name`.
+fn find_savepoint(savepoints: &[Savepoint], name: &str) -> Result<usize> {
+    savepoints
+        .iter()
+        .rposition(|savepoint| savepoint.name == name)
+        .ok_or_else(|| Error::StorageMsg(format!("savepoint not found: {name}")))
 }
 
 impl SledStorage {
     pub fn rollback_txid(&self, txid: u64) -> Result<()> {
+        self.rollback_temp_items(txid, |_| true)?;
+        self.tree
+            .remove(key::savepoints(txid))
+            .map(|_| ())
+            .map_err(err_into)
+    }
+
+    /// Undoes the changes `txid` made to the keys of the temp keys `filter` accepts.
+    fn rollback_temp_items(&self, txid: u64, filter: impl Fn(&IVec) -> bool) -> Result<()> {
         let fetch_items = |prefix| {
             self.tree
                 .scan_prefix(prefix)
                 .map(|item| item.map_err(err_into))
+                .filter(|item| match item {
+                    Ok((temp_key, _)) => filter(temp_key),
+                    Err(_) => true,
+                })
                 .collect::<Result<Vec<_>>>()
         };
 
@@ -209,11 +353,15 @@ impl SledStorage {
         let data_items = fetch_items(key::temp_data_prefix(txid))?;
         let schema_items = fetch_items(key::temp_schema_prefix(txid))?;
         let index_items = fetch_items(key::temp_index_prefix(txid))?;
+        let sequence_items = fetch_items(key::temp_sequence_prefix(txid))?;
+        let function_items = fetch_items(key::temp_function_prefix(txid))?;
 
         self.tree
             .transaction(move |tree| {
                 rollback_items::<Row>(tree, txid, &data_items)?;
                 rollback_items::<Schema>(tree, txid, &schema_items)?;
+                rollback_items::<i64>(tree, txid, &sequence_items)?;
+                rollback_items::<SchemaFunction>(tree, txid, &function_items)?;
 
                 for (temp_key, value_key) in index_items.iter() {
                     tree.remove(temp_key)?;
@@ -253,6 +401,40 @@ impl SledStorage {
             .map_err(tx_err_into)
     }
 
+    /// Temp keys `txid` wrote, with the keys of the values each one stands for.
+    fn fetch_temp_items(&self, txid: u64) -> Result<Vec<(IVec, IVec)>> {
+        [
+            key::temp_data_prefix(txid),
+            key::temp_schema_prefix(txid),
+            key::temp_index_prefix(txid),
+            key::temp_sequence_prefix(txid),
+            key::temp_function_prefix(txid),
+        ]
+        .into_iter()
+        .flat_map(|prefix| self.tree.scan_prefix(prefix))
+        .map(|item| item.map_err(err_into))
+        .collect()
+    }
+
+    fn fetch_savepoints(&self, txid: u64) -> Result<Vec<Savepoint>> {
+        self.tree
+            .get(key::savepoints(txid))
+            .map_err(err_into)?
+            .map(|savepoints| bincode::deserialize(&savepoints))
+            .transpose()
+            .map_err(err_into)
+            .map(Option::unwrap_or_default)
+    }
+
+    fn insert_savepoints(&self, txid: u64, savepoints: &[Savepoint]) -> Result<()> {
+        let savepoints = bincode::serialize(savepoints).map_err(err_into)?;
+
+        self.tree
+            .insert(key::savepoints(txid), savepoints)
+            .map(|_| ())
+            .map_err(err_into)
+    }
+
     pub async fn check_and_retry<Fut>(
         self,
         tx_result: StdResult<TxPayload, TransactionError<Error>>,
@@ -77,6 +77,41 @@ fn sled_transaction_basic() {
     );
 }
 
+#[test]
+fn sled_transaction_savepoint() {
+    let path = &format!("{}/savepoint", PATH_PREFIX);
+    fs::remove_dir_all(path).unwrap_or(());
+
+    let storage = SledStorage::new(path).unwrap();
+    let mut glue = Glue::new(storage);
+
+    test!(
+        glue "SAVEPOINT sp1",
+        Err(Error::StorageMsg("no transaction to make a savepoint in".to_owned()))
+    );
+
+    exec!(glue "BEGIN;");
+    test!(
+        glue "ROLLBACK TO SAVEPOINT sp1",
+        Err(Error::StorageMsg("savepoint not found: sp1".to_owned()))
+    );
+    exec!(glue "SAVEPOINT sp1;");
+    exec!(glue "SAVEPOINT sp2;");
+    test!(glue "RELEASE SAVEPOINT sp1", Ok(Payload::ReleaseSavepoint));
+    test!(
+        glue "ROLLBACK TO SAVEPOINT sp2",
+        Err(Error::StorageMsg("savepoint not found: sp2".to_owned()))
+    );
+    exec!(glue "COMMIT;");
+
+    exec!(glue "BEGIN;");
+    test!(
+        glue "RELEASE SAVEPOINT sp1",
+        Err(Error::StorageMsg("savepoint not found: sp1".to_owned()))
+    );
+    exec!(glue "ROLLBACK;");
+}
+
 #[test]
 fn sled_transaction_read_uncommitted() {
     let path = &format!("{}/read_uncommitted", PATH_PREFIX);
@@ -104,6 +139,40 @@ fn sled_transaction_read_uncommitted() {
     exec!(glue1 "COMMIT;");
 }
 
+#[test]
+fn sled_transaction_function() {
+    let path = &format!("{}/function", PATH_PREFIX);
+    fs::remove_dir_all(path).unwrap_or(());
+
+    let storage1 = SledStorage::new(path).unwrap();
+    let storage2 = storage1.clone();
+    let mut glue1 = Glue::new(storage1);
+    let mut glue2 = Glue::new(storage2);
+
+    exec!(glue1 "CREATE TABLE Sample (id INTEGER);");
+    exec!(glue1 "INSERT INTO Sample VALUES (3);");
+    exec!(glue1 "BEGIN;");
+    exec!(glue1 "CREATE FUNCTION twice(x INT) RETURNS INT AS 'x * 2';");
+
+    let twice = || {
+        Ok(Payload::Select {
+            labels: vec!["twice".to_owned()],
+            rows: vec![vec![I64(6)]],
+        })
+    };
+    test!(
+        glue2 "SELECT twice(id) AS twice FROM Sample",
+        Err(translate::TranslateError::UnsupportedFunction("TWICE".to_owned()).into())
+    );
+    exec!(glue1 "COMMIT;");
+    test!(glue2 "SELECT twice(id) AS twice FROM Sample", twice());
+
+    // the function outlives the storage it was created in
+    drop((glue1, glue2));
+    let mut glue = Glue::new(SledStorage::new(path).unwrap());
+    test!(glue "SELECT twice(id) AS twice FROM Sample", twice());
+}
+
 #[test]
 fn sled_transaction_read_committed() {
     let path = &format!("{}/read_committed", PATH_PREFIX);
@@ -1,7 +1,11 @@
 use {
     crate::*,
     gluesql_core::{
-        data::KeyError, executor::AggregateError, prelude::Value::*, translate::TranslateError,
+        ast::DataType,
+        data::KeyError,
+        executor::{AggregateError, EmptyGroup, UdfError, UserAggregate},
+        prelude::Value::{self, *},
+        translate::TranslateError,
     },
 };
 
@@ -213,3 +217,595 @@ test_case!(group_by, async move {
         "SELECT * FROM Item GROUP BY ratio;"
     );
 });
+
+test_case!(group_by_expr, async move {
+    run!("CREATE TABLE Visit (id INTEGER, name TEXT NULL, visited_at TIMESTAMP)");
+    run!(
+        "
+        INSERT INTO Visit (id, name, visited_at) VALUES
+            (1, 'Alice', '2022-01-05 10:00:00'),
+            (2,   'Bob', '2022-01-20 09:30:00'),
+            (3, 'Carol', '2022-02-01 18:00:00'),
+            (4,    NULL, '2022-03-15 12:00:00'),
+            (5,  'Dave', '2022-02-11 08:45:00'),
+            (6,    NULL, '2022-01-30 23:10:00');
+    "
+    );
+
+    // NULL keys form a group of their own
+    test!(
+        Ok(select_with_null!(
+            len    | n;
+            I64(5)   I64(2);
+            I64(3)   I64(1);
+            Null     I64(2);
+            I64(4)   I64(1)
+        )),
+        "SELECT LENGTH(name) AS len, COUNT(*) AS n FROM Visit GROUP BY LENGTH(name)"
+    );
+    test!(
+        Ok(select!(
+            month | n   | first
+            I64   | I64 | I64;
+            1       3     1;
+            2       2     3;
+            3       1     4
+        )),
+        "SELECT EXTRACT(MONTH FROM visited_at) AS month, COUNT(*) AS n, MIN(id) AS first
+        FROM Visit
+        GROUP BY EXTRACT(MONTH FROM visited_at)"
+    );
+    test!(
+        Ok(select!(
+            month | n
+            I64   | I64;
+            2       2
+        )),
+        "SELECT EXTRACT(MONTH FROM visited_at) AS month, COUNT(name) AS n
+        FROM Visit
+        GROUP BY EXTRACT(MONTH FROM visited_at), id % 2
+        HAVING COUNT(name) > 1"
+    );
+});
+
+test_case!(filter, async move {
+    run!("CREATE TABLE Task (id INTEGER, team TEXT, status TEXT, hours INTEGER)");
+    run!(
+        "INSERT INTO Task VALUES
+            (1, 'a', 'ok', 3),
+            (2, 'a', 'fail', 5),
+            (3, 'a', 'ok', 2),
+            (4, 'b', 'fail', 2),
+            (5, 'b', 'fail', 4),
+            (6, 'c', 'ok', 1)"
+    );
+
+    test!(
+        Ok(select!(
+            ok  | fail | late | total
+            I64 | I64  | I64  | I64;
+            3     3      9      6
+        )),
+        "SELECT
+            COUNT(*) FILTER (WHERE status = 'ok') AS ok,
+            COUNT(*) FILTER (WHERE status = 'fail') AS fail,
+            SUM(hours) FILTER (WHERE id > 2) AS late,
+            COUNT(*) AS total
+        FROM Task"
+    );
+
+    // a group whose rows all fail the filter counts zero, other aggregates give NULL
+    test!(
+        Ok(select_with_null!(
+            team                     | ok     | failed | longest;
+            Str("a".to_owned())        I64(2)   I64(5)   I64(3);
+            Str("b".to_owned())        I64(0)   I64(6)   Null;
+            Str("c".to_owned())        I64(1)   Null     I64(1)
+        )),
+        "SELECT
+            team,
+            COUNT(*) FILTER (WHERE status = 'ok') AS ok,
+            SUM(hours) FILTER (WHERE status = 'fail') AS failed,
+            MAX(hours) FILTER (WHERE status = 'ok') AS longest
+        FROM Task
+        GROUP BY team"
+    );
+});
+
+test_case!(cube, async move {
+    run!("CREATE TABLE Sale (region TEXT, product TEXT, amount INTEGER)");
+    run!(
+        "INSERT INTO Sale VALUES
+            ('East', 'Apple', 10),
+            ('East', 'Pear', 20),
+            ('West', 'Apple', 30)"
+    );
+
+    let s = |v: &str| Str(v.to_owned());
+
+    // (region, product), (region), (product) and the grand total
+    test!(
+        Ok(select_with_null!(
+            region    | product    | total   | num;
+            s("East")   s("Apple")   I64(10)   I64(1);
+            s("East")   s("Pear")    I64(20)   I64(1);
+            s("East")   Null         I64(30)   I64(2);
+            s("West")   s("Apple")   I64(30)   I64(1);
+            s("West")   Null         I64(30)   I64(1);
+            Null        s("Apple")   I64(40)   I64(2);
+            Null        s("Pear")    I64(20)   I64(1);
+            Null        Null         I64(60)   I64(3)
+        )),
+        "SELECT region, product, SUM(amount) AS total, COUNT(*) AS num
+        FROM Sale
+        GROUP BY CUBE(region, product)
+        ORDER BY region, product"
+    );
+
+    count!(
+        5,
+        "SELECT region, product FROM Sale GROUP BY region, CUBE(product)"
+    );
+    count!(
+        3,
+        "SELECT region, product FROM Sale GROUP BY CUBE(region, product) HAVING region IS NULL"
+    );
+
+    test!(
+        Err(AggregateError::OnlyColumnAllowedInGroupingSet("amount + 1".to_owned()).into()),
+        "SELECT COUNT(*) FROM Sale GROUP BY CUBE(amount + 1)"
+    );
+    test!(
+        Err(AggregateError::TooManyCubeColumns {
+            limit: 12,
+            found: 13
+        }
+        .into()),
+        "SELECT COUNT(*) FROM Sale GROUP BY CUBE(
+            region, region, region, region, region, region, region,
+            product, product, product, product, product, amount
+        )"
+    );
+    test!(
+        Err(TranslateError::CompositeGroupingColumnNotSupported.into()),
+        "SELECT COUNT(*) FROM Sale GROUP BY CUBE((region, product))"
+    );
+});
+
+test_case!(grouping_sets, async move {
+    run!("CREATE TABLE Sale (region TEXT, product TEXT, amount INTEGER)");
+    run!(
+        "INSERT INTO Sale VALUES
+            ('East', 'Apple', 10),
+            ('East', 'Pear', 20),
+            ('West', 'Apple', 30)"
+    );
+
+    let s = |v: &str| Str(v.to_owned());
+
+    test!(
+        Ok(select_with_null!(
+            region    | product    | total   | grouped;
+            s("East")   s("Apple")   I64(10)   I64(0);
+            s("East")   s("Pear")    I64(20)   I64(0);
+            s("East")   Null         I64(30)   I64(1);
+            s("West")   s("Apple")   I64(30)   I64(0);
+            s("West")   Null         I64(30)   I64(1);
+            Null        Null         I64(60)   I64(3)
+        )),
+        "SELECT region, product, SUM(amount) AS total, GROUPING(region, product) AS grouped
+        FROM Sale
+        GROUP BY GROUPING SETS ((region, product), (region), ())
+        ORDER BY region, product"
+    );
+
+    // GROUPING() tells a NULL of the data from a column left out of the grouping set
+    run!("INSERT INTO Sale VALUES (NULL, 'Pear', 5)");
+    test!(
+        Ok(select_with_null!(
+            region    | total   | grouped;
+            s("East")   I64(30)   I64(0);
+            s("West")   I64(30)   I64(0);
+            Null        I64(5)    I64(0);
+            Null        I64(65)   I64(1)
+        )),
+        "SELECT region, SUM(amount) AS total, GROUPING(region) AS grouped
+        FROM Sale
+        GROUP BY GROUPING SETS ((region), ())
+        ORDER BY region, grouped"
+    );
+
+    count!(
+        5,
+        "SELECT region, product FROM Sale GROUP BY GROUPING SETS (region, product)"
+    );
+    // a set listed twice groups the rows twice
+    count!(
+        6,
+        "SELECT region FROM Sale GROUP BY GROUPING SETS ((region), (region))"
+    );
+
+    test!(
+        Err(AggregateError::GroupingArgumentNotGrouped("amount".to_owned()).into()),
+        "SELECT GROUPING(amount) FROM Sale GROUP BY region"
+    );
+});
+
+test_case!(bool_aggregate, async move {
+    run!(
+        "
+        CREATE TABLE Checklist (
+            team INTEGER,
+            passed BOOLEAN NULL,
+        );
+    "
+    );
+    run!(
+        "
+        INSERT INTO Checklist (team, passed) VALUES
+            (1,  TRUE),
+            (1,  TRUE),
+            (2,  TRUE),
+            (2, FALSE),
+            (2,  NULL),
+            (3,  NULL),
+            (3,  NULL),
+            (4, FALSE);
+    "
+    );
+
+    test!(
+        Ok(select!(
+            "BOOL_AND(passed)" | "BOOL_OR(passed)" | "EVERY(passed)"
+            Bool               | Bool              | Bool;
+            false                true                false
+        )),
+        "SELECT BOOL_AND(passed), BOOL_OR(passed), EVERY(passed) FROM Checklist"
+    );
+    test!(
+        Ok(select_with_null!(
+            team   | "BOOL_AND(passed)" | "BOOL_OR(passed)";
+            I64(1)   Bool(true)           Bool(true);
+            I64(2)   Bool(false)          Bool(true);
+            I64(3)   Null                 Null;
+            I64(4)   Bool(false)          Bool(false)
+        )),
+        "SELECT team, BOOL_AND(passed), BOOL_OR(passed) FROM Checklist GROUP BY team"
+    );
+    test!(
+        Err(AggregateError::BooleanValueRequired(I64(1)).into()),
+        "SELECT BOOL_AND(team) FROM Checklist"
+    );
+});
+
+test_case!(array_agg, async move {
+    run!(
+        "
+        CREATE TABLE Visit (
+            id INTEGER,
+            city TEXT,
+            guest TEXT NULL,
+        );
+    "
+    );
+    run!(
+        "
+        INSERT INTO Visit (id, city, guest) VALUES
+            (1, 'Seoul',  'Mike'),
+            (4, 'Busan',  'Jane'),
+            (2, 'Seoul',  NULL),
+            (5, 'Busan',  'Anne'),
+            (3, 'Seoul',  'Bob');
+    "
+    );
+
+    let s = |v: &str| Str(v.to_owned());
+
+    test!(
+        Ok(select!(
+            cities
+            List;
+            vec![s("Seoul"), s("Busan"), s("Seoul"), s("Busan"), s("Seoul")]
+        )),
+        "SELECT ARRAY_AGG(city) AS cities FROM Visit"
+    );
+    test!(
+        Ok(select!(
+            cities
+            List;
+            vec![s("Seoul"), s("Seoul"), s("Seoul"), s("Busan"), s("Busan")]
+        )),
+        "SELECT ARRAY_AGG(city ORDER BY id) AS cities FROM Visit"
+    );
+    test!(
+        Ok(select_with_null!(
+            g0   | g1        | g2        | g3;
+            Null   s("Mike")   s("Bob")    Null
+        )),
+        "SELECT
+            UNWRAP(guests, '0') AS g0,
+            UNWRAP(guests, '1') AS g1,
+            UNWRAP(guests, '2') AS g2,
+            UNWRAP(guests, '3') AS g3
+        FROM (
+            SELECT ARRAY_AGG(guest ORDER BY guest DESC) AS guests
+            FROM Visit
+            WHERE city = 'Seoul'
+        ) AS Agg"
+    );
+    test!(
+        Ok(select_with_null!(
+            city                   | ids;
+            Str("Seoul".to_owned())  List(vec![I64(3), I64(2), I64(1)]);
+            Str("Busan".to_owned())  List(vec![I64(5), I64(4)])
+        )),
+        "SELECT city, ARRAY_AGG(id ORDER BY id DESC) AS ids FROM Visit GROUP BY city"
+    );
+    test!(
+        Err(TranslateError::UnsupportedArrayAggOption("DISTINCT".to_owned()).into()),
+        "SELECT ARRAY_AGG(DISTINCT city) FROM Visit"
+    );
+});
+
+test_case!(json_agg, async move {
+    run!(
+        "
+        CREATE TABLE Player (
+            team INTEGER,
+            name TEXT,
+            score INTEGER NULL,
+            detail MAP,
+        );
+    "
+    );
+    run!(
+        r#"
+        INSERT INTO Player (team, name, score, detail) VALUES
+            (1, 'Mike', 10,   '{"name": "Mike", "score": 10}'),
+            (2, 'Jane', NULL, '{"name": "Jane"}'),
+            (1, 'Bob',  30,   '{"name": "Bob", "score": 30}'),
+            (2, 'Jane', 20,   '{"name": "Jane", "score": 20}');
+    "#
+    );
+
+    let l = |s: &str| Value::parse_json_list(s).unwrap();
+    let m = |s: &str| Value::parse_json_map(s).unwrap();
+
+    test!(
+        Ok(select_with_null!(
+            team   | players;
+            I64(1)   l(r#"[{"name": "Mike", "score": 10}, {"name": "Bob", "score": 30}]"#);
+            I64(2)   l(r#"[{"name": "Jane"}, {"name": "Jane", "score": 20}]"#)
+        )),
+        "SELECT team, JSON_AGG(detail) AS players FROM Player GROUP BY team"
+    );
+    test!(
+        Ok(select_with_null!(
+            names;
+            l(r#"["Mike", "Jane", "Bob", "Jane"]"#)
+        )),
+        "SELECT JSON_AGG(name) AS names FROM Player"
+    );
+    test!(
+        Ok(select_with_null!(
+            team   | scores;
+            I64(1)   m(r#"{"Mike": 10, "Bob": 30}"#);
+            I64(2)   m(r#"{"Jane": 20}"#)
+        )),
+        "SELECT team, JSON_OBJECT_AGG(name, score) AS scores FROM Player GROUP BY team"
+    );
+    test!(
+        Err(AggregateError::NullJsonObjectKey.into()),
+        "SELECT JSON_OBJECT_AGG(score, name) FROM Player"
+    );
+});
+
+test_case!(min_max, async move {
+    run!(
+        "
+        CREATE TABLE Visitor (
+            site INTEGER,
+            name TEXT NULL,
+            visited DATE NULL,
+            logged TIMESTAMP NULL,
+        );
+    "
+    );
+    run!(
+        "
+        INSERT INTO Visitor (site, name, visited, logged) VALUES
+            (1, NULL,     NULL,         NULL),
+            (1, 'mango',  '2022-03-01', '2022-03-01 12:00:00'),
+            (1, 'apple',  '2021-12-25', '2021-12-25 09:30:00'),
+            (1, 'banana', '2022-01-10', NULL),
+            (2, 'kiwi',   '2020-07-07', '2020-07-07 23:59:59'),
+            (3, NULL,     NULL,         NULL);
+    "
+    );
+
+    let s = |v: &str| Str(v.to_owned());
+    let d = |v: &str| Date(v.parse().unwrap());
+    let t = |v: &str| Timestamp(v.parse().unwrap());
+
+    test!(
+        Ok(select_with_null!(
+            site   | "MAX(name)" | "MIN(name)" | "MAX(visited)"  | "MIN(logged)";
+            I64(1)   s("mango")    s("apple")    d("2022-03-01")   t("2021-12-25T09:30:00");
+            I64(2)   s("kiwi")     s("kiwi")     d("2020-07-07")   t("2020-07-07T23:59:59");
+            I64(3)   Null          Null          Null              Null
+        )),
+        "SELECT site, MAX(name), MIN(name), MAX(visited), MIN(logged) FROM Visitor GROUP BY site"
+    );
+    test!(
+        Ok(select_with_null!(
+            "MIN(visited)"  | "MAX(logged)";
+            d("2020-07-07")   t("2022-03-01T12:00:00")
+        )),
+        "SELECT MIN(visited), MAX(logged) FROM Visitor"
+    );
+});
+
+struct Product;
+
+impl Product {
+    fn multiply(a: Value, b: Value) -> Result<Value, String> {
+        match (a, b) {
+            (I64(a), I64(b)) => a
+                .checked_mul(b)
+                .map(I64)
+                .ok_or_else(|| "product overflowed".to_owned()),
+            (a, b) => Err(format!("unexpected values: {:?}, {:?}", a, b)),
+        }
+    }
+}
+
+impl UserAggregate for Product {
+    fn init(&self) -> Value {
+        I64(1)
+    }
+
+    fn accumulate(&self, state: Value, value: Value) -> Result<Value, String> {
+        Self::multiply(state, value)
+    }
+
+    fn merge(&self, state: Value, other: Value) -> Result<Value, String> {
+        Self::multiply(state, other)
+    }
+
+    fn finalize(&self, state: Value) -> Result<Value, String> {
+        Ok(state)
+    }
+}
+
+/// Counts the values of a group, folding each one into the state without merging states.
+struct Tally;
+
+impl UserAggregate for Tally {
+    fn init(&self) -> Value {
+        I64(0)
+    }
+
+    fn accumulate(&self, state: Value, _: Value) -> Result<Value, String> {
+        match state {
+            I64(count) => Ok(I64(count + 1)),
+            state => Err(format!("unexpected state: {:?}", state)),
+        }
+    }
+
+    fn merge(&self, _: Value, _: Value) -> Result<Value, String> {
+        Err("states of a group are never merged".to_owned())
+    }
+
+    fn finalize(&self, state: Value) -> Result<Value, String> {
+        Ok(state)
+    }
+}
+
+test_case!(custom, async move {
+    run!("CREATE TABLE Item (id INTEGER, category TEXT, price INTEGER)");
+    run!(
+        "INSERT INTO Item VALUES
+            (1, 'a', 2),
+            (2, 'a', 3),
+            (3, 'b', NULL),
+            (4, 'b', 5),
+            (5, 'c', NULL)"
+    );
+
+    session!().register_aggregate(
+        "product",
+        DataType::Int,
+        DataType::Int,
+        EmptyGroup::Null,
+        Product,
+    );
+    session!().register_aggregate(
+        "product_or_one",
+        DataType::Int,
+        DataType::Int,
+        EmptyGroup::Finalize,
+        Product,
+    );
+
+    test!(
+        Ok(select!(product I64; 30)),
+        "SELECT PRODUCT(price) AS product FROM Item"
+    );
+
+    session!().register_aggregate(
+        "tally",
+        DataType::Text,
+        DataType::Int,
+        EmptyGroup::Finalize,
+        Tally,
+    );
+    test!(
+        Ok(select!(
+            category | tally
+            Str      | I64;
+            "a".to_owned()   2;
+            "b".to_owned()   2;
+            "c".to_owned()   1
+        )),
+        "SELECT category, TALLY(category) AS tally FROM Item GROUP BY category"
+    );
+    session!().unregister_aggregate("tally");
+
+    // NULL values are skipped, and a group without any value follows its EmptyGroup
+    let s = |v: &str| Str(v.to_owned());
+    test!(
+        Ok(select_with_null!(
+            category | product | product_or_one;
+            s("a")     I64(6)    I64(6);
+            s("b")     I64(5)    I64(5);
+            s("c")     Null      I64(1)
+        )),
+        "SELECT
+            category,
+            PRODUCT(price) AS product,
+            product_or_one(price) AS product_or_one
+        FROM Item
+        GROUP BY category"
+    );
+    test!(
+        Ok(select_with_null!(
+            category | product | product_or_one;
+            s("a")     I64(3)    I64(3);
+            s("b")     I64(5)    I64(1);
+            s("c")     Null      I64(1)
+        )),
+        "SELECT
+            category,
+            PRODUCT(price) FILTER (WHERE id % 2 = 0) AS product,
+            PRODUCT_OR_ONE(price) FILTER (WHERE id = 2) AS product_or_one
+        FROM Item
+        GROUP BY category"
+    );
+
+    test!(
+        Err(TranslateError::FunctionArgsLengthNotMatching {
+            name: "PRODUCT".to_owned(),
+            expected: 1,
+            found: 2,
+        }
+        .into()),
+        "SELECT PRODUCT(price, id) FROM Item"
+    );
+
+    run!("INSERT INTO Item VALUES (6, 'd', 9223372036854775807), (7, 'd', 2)");
+    test!(
+        Err(UdfError::FunctionFailed {
+            name: "PRODUCT".to_owned(),
+            message: "product overflowed".to_owned(),
+        }
+        .into()),
+        "SELECT category, PRODUCT(price) FROM Item GROUP BY category"
+    );
+
+    session!().unregister_aggregate("product");
+    session!().unregister_aggregate("product_or_one");
+    test!(
+        Err(TranslateError::UnsupportedFunction("PRODUCT".to_owned()).into()),
+        "SELECT PRODUCT(price) FROM Item"
+    );
+});
@@ -0,0 +1,129 @@
+use {
+    crate::*,
+    gluesql_core::prelude::{Payload, Value::*},
+};
+
+test_case!(alias, async move {
+    run!("CREATE TABLE Item (id INTEGER, name TEXT, price INTEGER)");
+    run!(
+        "INSERT INTO Item VALUES
+            (1, 'apple', 300),
+            (2, 'kiwi', 500),
+            (3, 'banana', 200),
+            (4, 'fig', 100),
+            (5, 'cherry', 400)"
+    );
+
+    test!(
+        Ok(select!(
+            name                 | len
+            Str                  | I64;
+            "banana".to_owned()    6;
+            "cherry".to_owned()    6;
+            "apple".to_owned()     5;
+            "kiwi".to_owned()      4;
+            "fig".to_owned()       3
+        )),
+        "SELECT name, LENGTH(name) AS len FROM Item ORDER BY len DESC, name"
+    );
+    test!(
+        Ok(select!(
+            name
+            Str;
+            "fig".to_owned();
+            "banana".to_owned();
+            "apple".to_owned()
+        )),
+        "SELECT name, price * 2 AS total FROM Item ORDER BY total LIMIT 3"
+    );
+    test!(
+        Ok(select!(
+            len | n
+            I64 | I64;
+            3     1;
+            4     1;
+            5     1;
+            6     2
+        )),
+        "SELECT LENGTH(name) AS len, COUNT(*) AS n FROM Item GROUP BY len ORDER BY len"
+    );
+    test!(
+        Ok(select!(
+            len | n
+            I64 | I64;
+            6     2
+        )),
+        "SELECT LENGTH(name) AS len, COUNT(*) AS n FROM Item GROUP BY len HAVING n > 1"
+    );
+    test!(
+        Ok(select!(
+            label
+            Str;
+            "apple".to_owned();
+            "banana".to_owned()
+        )),
+        "SELECT name AS label FROM (SELECT name FROM Item) AS Sub ORDER BY label LIMIT 2"
+    );
+
+    // a column of the same name wins over the alias
+    test!(
+        Ok(select!(
+            name                 | id
+            Str                  | I64;
+            "cherry".to_owned()    400;
+            "fig".to_owned()       100;
+            "banana".to_owned()    200;
+            "kiwi".to_owned()      500;
+            "apple".to_owned()     300
+        )),
+        "SELECT name, price AS id FROM Item ORDER BY id DESC"
+    );
+    test!(
+        Ok(select!(
+            id  | n
+            I64 | I64;
+            300   1
+        )),
+        "SELECT price AS id, COUNT(*) AS n FROM Item GROUP BY id HAVING id = 1"
+    );
+
+    // aliases in the queries of views, INSERT and CREATE TABLE ... AS
+    run!(
+        "CREATE VIEW LengthCount AS
+            SELECT LENGTH(name) AS len, COUNT(*) AS n FROM Item GROUP BY len"
+    );
+    test!(
+        Ok(select!(
+            len | n
+            I64 | I64;
+            6     2
+        )),
+        "SELECT * FROM LengthCount WHERE n > 1"
+    );
+    run!("CREATE TABLE Fruit (name TEXT)");
+    test!(
+        Ok(Payload::Insert(2)),
+        "INSERT INTO Fruit SELECT name AS label FROM Item ORDER BY label LIMIT 2"
+    );
+    test!(
+        Ok(select!(
+            name
+            Str;
+            "apple".to_owned();
+            "banana".to_owned()
+        )),
+        "SELECT name FROM Fruit ORDER BY name"
+    );
+    run!(
+        "CREATE TABLE Expensive AS
+            SELECT name, price AS cost FROM Item ORDER BY cost DESC LIMIT 1"
+    );
+    test!(
+        Ok(select!(
+            name                | cost
+            Str                 | I64;
+            "kiwi".to_owned()     500
+        )),
+        "SELECT * FROM Expensive"
+    );
+});
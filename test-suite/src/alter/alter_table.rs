@@ -2,7 +2,9 @@
 
 use {
     crate::*,
-    gluesql_core::{data::Value::*, prelude::Payload, store::AlterTableError},
+    gluesql_core::{
+        data::Value::*, executor::AlterError, prelude::Payload, store::AlterTableError,
+    },
 };
 
 test_case!(alter_table_rename, async move {
@@ -28,6 +30,19 @@ test_case!(alter_table_rename, async move {
             "ALTER TABLE Bar RENAME COLUMN hello TO idid",
             Err(AlterTableError::RenamingColumnNotFound.into()),
         ),
+        (
+            "CREATE TABLE Baz (id INTEGER, name TEXT);",
+            Ok(Payload::Create),
+        ),
+        (
+            "ALTER TABLE Bar RENAME TO Baz;",
+            Err(AlterError::TableAlreadyExists("Baz".to_owned()).into()),
+        ),
+        (
+            "ALTER TABLE Baz RENAME COLUMN id TO name",
+            Err(AlterError::ColumnAlreadyExists("name".to_owned()).into()),
+        ),
+        ("SELECT new_id FROM Bar", Ok(select!(new_id; I64; 1; 2; 3))),
     ];
 
     for (sql, expected) in test_cases {
@@ -145,3 +160,185 @@ test_case!(alter_table_add_drop, async move {
         test!(expected, sql);
     }
 });
+
+test_case!(alter_table_add_column, async move {
+    use gluesql_core::ast::{ColumnDef, ColumnOption, ColumnOptionDef, DataType};
+
+    let test_cases = vec![
+        ("CREATE TABLE Item (id INTEGER);", Ok(Payload::Create)),
+        ("INSERT INTO Item VALUES (1), (2);", Ok(Payload::Insert(2))),
+        (
+            "ALTER TABLE Item ADD COLUMN status TEXT DEFAULT 'new'",
+            Ok(Payload::AlterTable),
+        ),
+        ("INSERT INTO Item (id) VALUES (3);", Ok(Payload::Insert(1))),
+        (
+            "SELECT * FROM Item;",
+            Ok(select!(
+                id  | status
+                I64 | Str;
+                1     "new".to_owned();
+                2     "new".to_owned();
+                3     "new".to_owned()
+            )),
+        ),
+        (
+            "ALTER TABLE Item ADD COLUMN memo TEXT NULL",
+            Ok(Payload::AlterTable),
+        ),
+        (
+            "SELECT id, memo FROM Item;",
+            Ok(select_with_null!(
+                id     | memo;
+                I64(1)   Null;
+                I64(2)   Null;
+                I64(3)   Null
+            )),
+        ),
+        (
+            "ALTER TABLE Item ADD COLUMN price INTEGER NOT NULL",
+            Err(AlterTableError::DefaultValueRequired(ColumnDef {
+                name: "price".to_owned(),
+                data_type: DataType::Int,
+                options: vec![ColumnOptionDef {
+                    name: None,
+                    option: ColumnOption::NotNull,
+                }],
+            })
+            .into()),
+        ),
+        ("CREATE TABLE Empty (id INTEGER);", Ok(Payload::Create)),
+        (
+            "ALTER TABLE Empty ADD COLUMN price INTEGER NOT NULL",
+            Ok(Payload::AlterTable),
+        ),
+        ("INSERT INTO Empty VALUES (1, 100);", Ok(Payload::Insert(1))),
+        (
+            "SELECT * FROM Empty;",
+            Ok(select!(id | price; I64 | I64; 1 100)),
+        ),
+    ];
+
+    for (sql, expected) in test_cases {
+        test!(expected, sql);
+    }
+});
+
+test_case!(alter_table_drop_column, async move {
+    let test_cases = vec![
+        (
+            "CREATE TABLE Item (id INTEGER, name TEXT, price INTEGER, flag BOOLEAN);",
+            Ok(Payload::Create),
+        ),
+        (
+            "INSERT INTO Item VALUES (1, 'a', 100, true), (2, 'b', 200, false);",
+            Ok(Payload::Insert(2)),
+        ),
+        (
+            "ALTER TABLE Item DROP COLUMN name;",
+            Ok(Payload::AlterTable),
+        ),
+        (
+            "SELECT * FROM Item;",
+            Ok(select!(
+                id  | price | flag
+                I64 | I64   | Bool;
+                1     100     true;
+                2     200     false
+            )),
+        ),
+        (
+            "INSERT INTO Item VALUES (3, 300, true);",
+            Ok(Payload::Insert(1)),
+        ),
+        (
+            "ALTER TABLE Item DROP COLUMN price;",
+            Ok(Payload::AlterTable),
+        ),
+        (
+            "SELECT * FROM Item;",
+            Ok(select!(
+                id  | flag
+                I64 | Bool;
+                1     true;
+                2     false;
+                3     true
+            )),
+        ),
+    ];
+
+    for (sql, expected) in test_cases {
+        test!(expected, sql);
+    }
+});
+
+test_case!(alter_table_alter_column_type, async move {
+    use gluesql_core::data::ValueError;
+
+    let test_cases = vec![
+        (
+            "CREATE TABLE Foo (id INT(8), num INTEGER, code TEXT NULL);",
+            Ok(Payload::Create),
+        ),
+        (
+            "INSERT INTO Foo VALUES (1, 10, '100'), (2, 20, NULL), (3, 30, 'abc');",
+            Ok(Payload::Insert(3)),
+        ),
+        (
+            "ALTER TABLE Foo ALTER COLUMN id TYPE INTEGER;",
+            Ok(Payload::AlterTable),
+        ),
+        (
+            "ALTER TABLE Foo ALTER COLUMN num TYPE TEXT;",
+            Ok(Payload::AlterTable),
+        ),
+        (
+            "SELECT id, num FROM Foo;",
+            Ok(select!(
+                id  | num
+                I64 | Str;
+                1     "10".to_owned();
+                2     "20".to_owned();
+                3     "30".to_owned()
+            )),
+        ),
+        (
+            "ALTER TABLE Foo ALTER COLUMN code TYPE INTEGER;",
+            Err(ValueError::ImpossibleCast.into()),
+        ),
+        (
+            "SELECT id, code FROM Foo;",
+            Ok(select_with_null!(
+                id     | code;
+                I64(1)   Str("100".to_owned());
+                I64(2)   Null;
+                I64(3)   Str("abc".to_owned())
+            )),
+        ),
+        ("DELETE FROM Foo WHERE id = 3;", Ok(Payload::Delete(1))),
+        (
+            "ALTER TABLE Foo ALTER COLUMN code TYPE INTEGER;",
+            Ok(Payload::AlterTable),
+        ),
+        (
+            "SELECT id, code FROM Foo;",
+            Ok(select_with_null!(
+                id     | code;
+                I64(1)   I64(100);
+                I64(2)   Null
+            )),
+        ),
+        (
+            "INSERT INTO Foo VALUES (4, 'forty', 400);",
+            Ok(Payload::Insert(1)),
+        ),
+        (
+            "ALTER TABLE Foo ALTER COLUMN amount TYPE INTEGER;",
+            Err(AlterTableError::AlteringColumnNotFound("amount".to_owned()).into()),
+        ),
+    ];
+
+    for (sql, expected) in test_cases {
+        test!(expected, sql);
+    }
+});
@@ -65,8 +65,12 @@ test_case!(create_table, async move {
             Err(TranslateError::UnsupportedColumnOption("CHECK (true)".to_owned()).into()),
         ),
         (
-            "CREATE TABLE Glue (id INTEGER PRIMARY KEY)",
-            Err(TranslateError::UnsupportedColumnOption(("PRIMARY KEY").to_owned()).into()),
+            "CREATE TABLE Glue (id TEXT PRIMARY KEY AUTOINCREMENT)",
+            Err(AlterError::UnsupportedDataTypeForAutoIncrement(
+                "id".to_owned(),
+                gluesql_core::ast::DataType::Text,
+            )
+            .into()),
         ),
         (
             r#"
@@ -139,3 +143,80 @@ test_case!(create_table, async move {
         test!(expected, sql);
     }
 });
+
+test_case!(create_table_as_select, async move {
+    use gluesql_core::{
+        ast::DataType,
+        data::value::Value::{Date, F64},
+        prelude::Payload,
+    };
+
+    run!("CREATE TABLE Author (id INTEGER, name TEXT)");
+    run!(
+        "CREATE TABLE Book (
+            id INTEGER,
+            author_id INTEGER,
+            title TEXT,
+            price FLOAT NULL,
+            released DATE NULL
+        )"
+    );
+    run!("INSERT INTO Author VALUES (1, 'Kim'), (2, 'Lee')");
+    run!(
+        "INSERT INTO Book VALUES
+            (1, 1, 'Glue', 12.5, NULL),
+            (2, 1, 'Sled', NULL, NULL),
+            (3, 2, 'Rust', 30.0, NULL)"
+    );
+
+    let source = "
+        SELECT
+            Book.id,
+            Author.name AS author,
+            Book.price,
+            Book.released,
+            Book.price * 2 AS doubled,
+            NULL AS nothing
+        FROM Book
+        JOIN Author ON Author.id = Book.author_id";
+    run!(&format!("CREATE TABLE Catalog AS {}", source));
+
+    // a column of only NULLs takes the type of the source column of its name, TEXT otherwise
+    test!(
+        Ok(Payload::ShowColumns(vec![
+            ("id".to_owned(), DataType::Int),
+            ("author".to_owned(), DataType::Text),
+            ("price".to_owned(), DataType::Float),
+            ("released".to_owned(), DataType::Date),
+            ("doubled".to_owned(), DataType::Float),
+            ("nothing".to_owned(), DataType::Text),
+        ])),
+        "SHOW COLUMNS FROM Catalog"
+    );
+    test!(
+        Ok(select_with_null!(
+            id     | author                | price     | released | doubled   | nothing;
+            I64(1)   Str("Kim".to_owned())   F64(12.5)   Null       F64(25.0)   Null;
+            I64(2)   Str("Kim".to_owned())   Null        Null       Null        Null;
+            I64(3)   Str("Lee".to_owned())   F64(30.0)   Null       F64(60.0)   Null
+        )),
+        "SELECT * FROM Catalog ORDER BY id"
+    );
+
+    // the inferred columns are nullable and keep their types on insert
+    run!("INSERT INTO Catalog VALUES (4, 'Park', NULL, '2022-05-01', NULL, 'none')");
+    test!(
+        Ok(select_with_null!(
+            released;
+            Date("2022-05-01".parse().unwrap())
+        )),
+        "SELECT released FROM Catalog WHERE id = 4"
+    );
+
+    // an existing table is kept as it is, without the rows of the query
+    test!(
+        Ok(Payload::Create),
+        &format!("CREATE TABLE IF NOT EXISTS Catalog AS {}", source)
+    );
+    count!(4, "SELECT * FROM Catalog");
+});
@@ -150,7 +150,25 @@ CREATE TABLE Test (
         "ALTER TABLE Noname DROP COLUMN id"
     );
 
-    run!("ALTER TABLE Test DROP COLUMN id");
+    test!(
+        Err(AlterError::ColumnUsedByIndex {
+            column: "id".to_owned(),
+            index: "idx_id".to_owned(),
+        }
+        .into()),
+        "ALTER TABLE Test DROP COLUMN id"
+    );
+    test!(
+        Err(AlterError::ColumnUsedByIndex {
+            column: "id".to_owned(),
+            index: "idx_id".to_owned(),
+        }
+        .into()),
+        "ALTER TABLE Test DROP COLUMN id RESTRICT"
+    );
+    assert_eq!(6, schema!("Test").indexes.len());
+
+    run!("ALTER TABLE Test DROP COLUMN id CASCADE");
 
     test_idx!(
         Ok(select!(
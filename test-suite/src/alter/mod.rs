@@ -2,10 +2,18 @@ mod alter_table;
 mod create_table;
 mod drop_indexed;
 mod drop_table;
+mod rename_indexed;
+mod truncate_table;
 
 #[cfg(feature = "alter-table")]
-pub use alter_table::{alter_table_add_drop, alter_table_rename};
-pub use create_table::create_table;
+pub use alter_table::{
+    alter_table_add_column, alter_table_add_drop, alter_table_alter_column_type,
+    alter_table_drop_column, alter_table_rename,
+};
+pub use create_table::{create_table, create_table_as_select};
 #[cfg(all(feature = "alter-table", feature = "index"))]
 pub use drop_indexed::{drop_indexed_column, drop_indexed_table};
 pub use drop_table::drop_table;
+#[cfg(all(feature = "alter-table", feature = "index"))]
+pub use rename_indexed::rename_indexed_column;
+pub use truncate_table::truncate_table;
@@ -0,0 +1,75 @@
+#![cfg(all(feature = "alter-table", feature = "index"))]
+
+use {
+    crate::*,
+    gluesql_core::{ast::IndexOperator::*, prelude::Value::*},
+};
+
+test_case!(rename_indexed_column, async move {
+    run!(
+        r#"
+CREATE TABLE Test (
+    id INTEGER,
+    num INTEGER,
+    name TEXT
+)"#
+    );
+
+    run!(
+        r#"
+        INSERT INTO Test
+            (id, num, name)
+        VALUES
+            (1, 2, "Hello"),
+            (3, 4, "World");
+    "#
+    );
+
+    run!("CREATE INDEX idx_id ON Test (id)");
+    run!("CREATE INDEX idx_sum ON Test (id + num)");
+    run!("CREATE INDEX idx_name ON Test (name)");
+
+    run!("ALTER TABLE Test RENAME COLUMN id TO new_id");
+
+    let indexes = schema!("Test")
+        .indexes
+        .into_iter()
+        .map(|index| (index.name, index.expr))
+        .collect::<Vec<_>>();
+    assert_eq!(3, indexes.len());
+    assert!(indexes.contains(&("idx_id".to_owned(), expr!("new_id"))));
+    assert!(indexes.contains(&("idx_sum".to_owned(), expr!("new_id + num"))));
+    assert!(indexes.contains(&("idx_name".to_owned(), expr!("name"))));
+
+    test_idx!(
+        Ok(select!(
+            new_id | num | name
+            I64 | I64 | Str;
+            3     4     "World".to_owned()
+        )),
+        idx!(idx_id, Eq, "3"),
+        "SELECT new_id, num, name FROM Test WHERE new_id = 3"
+    );
+
+    test_idx!(
+        Ok(select!(
+            new_id | num | name
+            I64 | I64 | Str;
+            1     2     "Hello".to_owned()
+        )),
+        idx!(idx_sum, Lt, "5"),
+        "SELECT new_id, num, name FROM Test WHERE new_id + num < 5"
+    );
+
+    run!("ALTER TABLE Test RENAME TO Renamed");
+
+    test_idx!(
+        Ok(select!(
+            new_id | num | name
+            I64 | I64 | Str;
+            1     2     "Hello".to_owned()
+        )),
+        idx!(idx_name, Eq, r#""Hello""#),
+        r#"SELECT new_id, num, name FROM Renamed WHERE name = "Hello""#
+    );
+});
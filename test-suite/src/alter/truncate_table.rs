@@ -0,0 +1,52 @@
+use {
+    crate::*,
+    gluesql_core::{
+        executor::{AlterError, Payload},
+        prelude::Value::*,
+    },
+};
+
+test_case!(truncate_table, async move {
+    run!("CREATE TABLE Item (id INTEGER, name TEXT)");
+    run!("CREATE TABLE Other (id INTEGER)");
+    run!("INSERT INTO Item VALUES (1, 'apple'), (2, 'banana'), (3, 'cherry')");
+    run!("INSERT INTO Other VALUES (10)");
+
+    test!(Ok(Payload::Truncate), "TRUNCATE TABLE Item");
+    test!(Ok(select!(id)), "SELECT id FROM Item");
+
+    // the table keeps its schema and other tables keep their rows
+    run!("INSERT INTO Item VALUES (4, 'durian')");
+    test!(
+        Ok(select!(
+            id  | name
+            I64 | Str;
+            4     "durian".to_owned()
+        )),
+        "SELECT * FROM Item"
+    );
+    test!(Ok(select!(id I64; 10)), "SELECT id FROM Other");
+
+    test!(Ok(Payload::Truncate), "TRUNCATE TABLE Other;");
+    test!(Ok(Payload::Truncate), "TRUNCATE TABLE Other;");
+    test!(Ok(select!(id)), "SELECT id FROM Other");
+
+    // AUTOINCREMENT columns start over from 1
+    run!("CREATE TABLE Counter (id INTEGER AUTOINCREMENT, name TEXT)");
+    run!("INSERT INTO Counter (name) VALUES ('a'), ('b'), ('c')");
+    test!(Ok(Payload::Truncate), "TRUNCATE TABLE Counter");
+    run!("INSERT INTO Counter (name) VALUES ('d')");
+    test!(
+        Ok(select!(
+            id  | name
+            I64 | Str;
+            1     "d".to_owned()
+        )),
+        "SELECT * FROM Counter"
+    );
+
+    test!(
+        Err(AlterError::TableNotFound("Nothing".to_owned()).into()),
+        "TRUNCATE TABLE Nothing"
+    );
+});
@@ -3,7 +3,7 @@ use {
     bigdecimal::BigDecimal,
     gluesql_core::{
         data::{Literal, LiteralError, NumericBinaryOperator, ValueError},
-        executor::{EvaluateError, UpdateError},
+        executor::{EvaluateError, FloatDivisionByZero, UpdateError},
         prelude::Value::{self, *},
     },
     std::borrow::Cow,
@@ -231,3 +231,123 @@ test_case!(blend, async move {
     );
     assert_eq!(expected, found);
 });
+
+test_case!(overflow, async move {
+    run!("CREATE TABLE Counter (id INTEGER, amount INTEGER, small INT(8))");
+    run!("INSERT INTO Counter VALUES (1, 9223372036854775807, 127)");
+
+    let test_cases = [
+        (
+            "SELECT amount + 1 FROM Counter",
+            I64(i64::MAX),
+            NumericBinaryOperator::Add,
+            I64(1),
+        ),
+        (
+            "SELECT -amount - 2 FROM Counter",
+            I64(-i64::MAX),
+            NumericBinaryOperator::Subtract,
+            I64(2),
+        ),
+        (
+            "SELECT amount * 2 FROM Counter",
+            I64(i64::MAX),
+            NumericBinaryOperator::Multiply,
+            I64(2),
+        ),
+        (
+            "SELECT amount * amount FROM Counter",
+            I64(i64::MAX),
+            NumericBinaryOperator::Multiply,
+            I64(i64::MAX),
+        ),
+        (
+            "SELECT small + small FROM Counter",
+            I8(i8::MAX),
+            NumericBinaryOperator::Add,
+            I8(i8::MAX),
+        ),
+    ];
+
+    for (sql, lhs, operator, rhs) in test_cases {
+        test!(
+            Err(ValueError::BinaryOperationOverflow { lhs, rhs, operator }.into()),
+            sql
+        );
+    }
+
+    test!(
+        Ok(select!("amount - 1"; I64; i64::MAX - 1)),
+        "SELECT amount - 1 FROM Counter"
+    );
+    test!(
+        Err(ValueError::BinaryOperationOverflow {
+            lhs: I64(i64::MAX),
+            rhs: I64(1),
+            operator: NumericBinaryOperator::Add,
+        }
+        .into()),
+        "UPDATE Counter SET amount = amount + 1"
+    );
+    test!(
+        Ok(select!(amount; I64; i64::MAX)),
+        "SELECT amount FROM Counter"
+    );
+});
+
+test_case!(float_division, async move {
+    run!("CREATE TABLE Ratio (id INTEGER, num FLOAT, den FLOAT)");
+    run!("INSERT INTO Ratio VALUES (1, 1.0, 0.0), (2, 0.0, 0.0), (3, -1.0, 0.0), (4, 3.0, 2.0)");
+
+    test!(
+        Err(ValueError::DivisorShouldNotBeZero.into()),
+        "SELECT num / den FROM Ratio WHERE id = 1"
+    );
+    test!(
+        Err(ValueError::DivisorShouldNotBeZero.into()),
+        "SELECT num / den FROM Ratio WHERE id = 2"
+    );
+
+    session!().set_float_division_by_zero(FloatDivisionByZero::Infinity);
+
+    test!(
+        Ok(select!(
+            id  | ratio
+            I64 | F64;
+            1     f64::INFINITY;
+            3     f64::NEG_INFINITY;
+            4     1.5
+        )),
+        "SELECT id, num / den AS ratio FROM Ratio WHERE id != 2"
+    );
+    test!(
+        Ok(select!(ratio; F64; f64::INFINITY)),
+        "SELECT CAST(1.0 AS FLOAT) / 0.0 AS ratio FROM Ratio WHERE id = 1"
+    );
+    test!(
+        Ok(select!(unequal; Bool; true)),
+        "SELECT num / den != num / den AS unequal FROM Ratio WHERE id = 2"
+    );
+    test!(
+        Ok(select!(id; I64; 1; 4)),
+        "SELECT id FROM Ratio WHERE num / den > 1"
+    );
+    test!(
+        Ok(select!(id; I64; 1; 3; 4)),
+        "SELECT id FROM Ratio WHERE num / den = num / den"
+    );
+    test!(
+        Ok(select!(id; I64; 3; 4; 1; 2)),
+        "SELECT id FROM Ratio ORDER BY num / den"
+    );
+    test!(
+        Err(ValueError::DivisorShouldNotBeZero.into()),
+        "SELECT id / 0 FROM Ratio WHERE id = 1"
+    );
+    test!(
+        Err(LiteralError::DivisorShouldNotBeZero.into()),
+        "SELECT 1.0 / 0.0 FROM Ratio WHERE id = 1"
+    );
+
+    session!().set_float_division_by_zero(FloatDivisionByZero::Error);
+});
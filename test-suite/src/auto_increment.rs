@@ -0,0 +1,67 @@
+use {
+    crate::*,
+    gluesql_core::{
+        executor::ValidateError,
+        prelude::{Payload, Value::*},
+    },
+};
+
+test_case!(auto_increment, async move {
+    run!("CREATE TABLE Item (id INTEGER PRIMARY KEY AUTOINCREMENT, name TEXT)");
+
+    test!(
+        Ok(Payload::Insert(2)),
+        "INSERT INTO Item (name) VALUES ('a'), ('b')"
+    );
+    test!(
+        Ok(select!(
+            id  | name
+            I64 | Str;
+            1     "a".to_owned();
+            2     "b".to_owned()
+        )),
+        "SELECT * FROM Item"
+    );
+
+    // an explicit id moves the sequence past it, a smaller one leaves it as it is
+    run!("INSERT INTO Item VALUES (10, 'c')");
+    run!("INSERT INTO Item (name) VALUES ('d')");
+    run!("INSERT INTO Item (id, name) VALUES (5, 'e'), (NULL, 'f')");
+    test!(
+        Ok(select!(
+            id  | name
+            I64 | Str;
+            1     "a".to_owned();
+            2     "b".to_owned();
+            5     "e".to_owned();
+            10    "c".to_owned();
+            11    "d".to_owned();
+            12    "f".to_owned()
+        )),
+        "SELECT * FROM Item ORDER BY id"
+    );
+
+    // ids of deleted rows are not handed out again
+    run!("DELETE FROM Item WHERE id > 10");
+    run!("INSERT INTO Item (name) VALUES ('g')");
+    test!(
+        Ok(select!(
+            id  | name
+            I64 | Str;
+            10    "c".to_owned();
+            13    "g".to_owned()
+        )),
+        "SELECT * FROM Item WHERE id >= 10 ORDER BY id"
+    );
+
+    test!(
+        Err(ValidateError::DuplicateEntryOnUniqueField(I64(13), "id".to_owned()).into()),
+        "INSERT INTO Item VALUES (13, 'h')"
+    );
+
+    // a table created again starts a new sequence
+    run!("DROP TABLE Item");
+    run!("CREATE TABLE Item (id INTEGER AUTOINCREMENT, name TEXT)");
+    run!("INSERT INTO Item (name) VALUES ('x')");
+    test!(Ok(select!(id I64; 1)), "SELECT id FROM Item");
+});
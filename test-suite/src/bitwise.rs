@@ -0,0 +1,110 @@
+use crate::*;
+
+test_case!(bitwise, async move {
+    use gluesql_core::{
+        data::{NumericBinaryOperator, ValueError},
+        prelude::{Payload, Value::*},
+    };
+
+    let test_cases = vec![
+        (
+            "CREATE TABLE Bits (id INTEGER, a INTEGER NULL, b INTEGER, small INT(8) NULL)",
+            Ok(Payload::Create),
+        ),
+        (
+            "INSERT INTO Bits VALUES (1, 12, 10, 1), (2, -1, 3, -128), (3, NULL, 1, NULL)",
+            Ok(Payload::Insert(3)),
+        ),
+        (
+            "SELECT id, a & b AS x, a | b AS y, a ^ b AS z FROM Bits WHERE id < 3",
+            Ok(select!(
+                id  | x   | y   | z
+                I64 | I64 | I64 | I64;
+                1     8     14    6;
+                2     3     (-1)  (-4)
+            )),
+        ),
+        (
+            "SELECT id, a << 2 AS x, a >> 2 AS y FROM Bits WHERE id < 3",
+            Ok(select!(
+                id  | x   | y
+                I64 | I64 | I64;
+                1     48    3;
+                2     (-4)  (-1)
+            )),
+        ),
+        (
+            "SELECT 1 << 63 AS x, 1 << 0 AS y FROM Bits WHERE id = 1",
+            Ok(select!(
+                x        | y
+                I64      | I64;
+                i64::MIN   1
+            )),
+        ),
+        (
+            "SELECT small << 7 AS x, small & 1 AS y FROM Bits WHERE id = 1",
+            Ok(select!(
+                x    | y
+                I8   | I64;
+                -128   1
+            )),
+        ),
+        (
+            "SELECT small & 1000 AS x, small | CAST(1 AS INT(8)) AS y FROM Bits WHERE id = 2",
+            Ok(select!(
+                x   | y
+                I64 | I8;
+                896   (-127)
+            )),
+        ),
+        (
+            "SELECT a & b AS x, a << b AS y, small >> 1 AS z FROM Bits WHERE id = 3",
+            Ok(select_with_null!(
+                x    | y    | z;
+                Null   Null   Null
+            )),
+        ),
+        (
+            "SELECT 1 << 64 FROM Bits WHERE id = 1",
+            Err(ValueError::BitwiseShiftOutOfRange {
+                amount: 64,
+                bits: 64,
+            }
+            .into()),
+        ),
+        (
+            "SELECT small << 8 FROM Bits WHERE id = 1",
+            Err(ValueError::BitwiseShiftOutOfRange { amount: 8, bits: 8 }.into()),
+        ),
+        (
+            "SELECT a >> -1 FROM Bits WHERE id = 1",
+            Err(ValueError::BitwiseShiftOutOfRange {
+                amount: -1,
+                bits: 64,
+            }
+            .into()),
+        ),
+        (
+            "SELECT a & 1.5 FROM Bits WHERE id = 1",
+            Err(ValueError::NonBitwiseOperation {
+                lhs: I64(12),
+                operator: NumericBinaryOperator::BitwiseAnd,
+                rhs: F64(1.5),
+            }
+            .into()),
+        ),
+        (
+            "SELECT a << 'a' FROM Bits WHERE id = 1",
+            Err(ValueError::NonBitwiseOperation {
+                lhs: I64(12),
+                operator: NumericBinaryOperator::BitwiseShiftLeft,
+                rhs: Str("a".to_owned()),
+            }
+            .into()),
+        ),
+    ];
+
+    for (sql, expected) in test_cases {
+        test!(expected, sql);
+    }
+});
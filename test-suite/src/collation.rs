@@ -0,0 +1,193 @@
+use {
+    crate::*,
+    gluesql_core::{
+        ast::DataType, executor::AlterError, prelude::Payload, prelude::Value::*,
+        translate::TranslateError,
+    },
+};
+
+test_case!(collation, async move {
+    run!("CREATE TABLE Account (id INTEGER, email TEXT COLLATE NOCASE, name TEXT)");
+    run!(
+        "INSERT INTO Account VALUES
+            (1, 'a@x', 'Alice'),
+            (2, 'B@x', 'bob'),
+            (3, 'A@X', 'alice'),
+            (4, 'c@x', 'Carol')"
+    );
+
+    test!(
+        Ok(select!(
+            id
+            I64;
+            1;
+            3
+        )),
+        "SELECT id FROM Account WHERE email = 'A@X'"
+    );
+    test!(
+        Ok(select!(
+            id  | email
+            I64 | Str;
+            3     "A@X".to_owned()
+        )),
+        "SELECT id, email FROM Account WHERE name = 'alice'"
+    );
+
+    let test_cases = [
+        (2, "SELECT * FROM Account WHERE 'A@x' = email"),
+        (2, "SELECT * FROM Account WHERE email <> 'a@X'"),
+        (2, "SELECT * FROM Account WHERE email > 'A@Z'"),
+        (2, "SELECT * FROM Account WHERE email IN ('B@X', 'C@X')"),
+        (
+            2,
+            "SELECT * FROM Account WHERE email BETWEEN 'B@X' AND 'c@X'",
+        ),
+        (1, "SELECT * FROM Account WHERE (email, id) = ('A@x', 3)"),
+        (1, "SELECT * FROM Account a WHERE a.email = 'c@X'"),
+        (
+            2,
+            "SELECT * FROM Account WHERE id IN (SELECT id FROM Account WHERE email = 'a@X')",
+        ),
+        (3, "SELECT email FROM Account GROUP BY email"),
+        (4, "SELECT name FROM Account GROUP BY name"),
+    ];
+
+    for (num, sql) in test_cases {
+        count!(num, sql);
+    }
+
+    test!(
+        Ok(select!(
+            id
+            I64;
+            1;
+            3;
+            2;
+            4
+        )),
+        "SELECT id FROM Account ORDER BY email, id"
+    );
+    test!(
+        Ok(select!(
+            id
+            I64;
+            1;
+            4;
+            3;
+            2
+        )),
+        "SELECT id FROM Account ORDER BY name"
+    );
+    test!(
+        Ok(select!(
+            n
+            I64;
+            2
+        )),
+        "SELECT COUNT(*) AS n FROM Account GROUP BY email HAVING COUNT(*) > 1"
+    );
+    test!(
+        Ok(select!(
+            id
+            I64;
+            1;
+            2;
+            4
+        )),
+        "SELECT DISTINCT ON (email) id FROM Account ORDER BY email, id"
+    );
+
+    let test_cases = [
+        (
+            2,
+            "WITH Matches AS (SELECT * FROM Account WHERE email = 'A@x') SELECT * FROM Matches",
+        ),
+        (
+            2,
+            "WITH Ids AS (SELECT id FROM Account) SELECT * FROM Ids WHERE id IN (
+                SELECT id FROM Account WHERE email = 'a@X'
+            )",
+        ),
+        (
+            2,
+            "SELECT * FROM Account WHERE IFNULL(email = 'A@x', FALSE)",
+        ),
+    ];
+
+    for (num, sql) in test_cases {
+        count!(num, sql);
+    }
+
+    test!(
+        Ok(select!(
+            n
+            I64;
+            2
+        )),
+        "SELECT COUNT(*) FILTER (WHERE email = 'a@X') AS n FROM Account"
+    );
+    test!(
+        Ok(select!(
+            id
+            I64;
+            1;
+            3;
+            2;
+            4
+        )),
+        "WITH Ids AS (SELECT id FROM Account) SELECT id FROM Account ORDER BY email, id"
+    );
+
+    // a common table expression shadows the table of the same name and its collation
+    run!("CREATE TABLE Person (name TEXT)");
+    run!("INSERT INTO Person VALUES ('Alice')");
+    count!(
+        0,
+        "WITH Account AS (SELECT name AS email FROM Person)
+        SELECT * FROM Account WHERE email = 'alice'"
+    );
+
+    run!("CREATE TABLE Archive (id INTEGER)");
+    test!(
+        Ok(Payload::Insert(2)),
+        "INSERT INTO Archive SELECT id FROM Account WHERE email = 'A@x'"
+    );
+    run!("CREATE TABLE Copied AS SELECT id FROM Account WHERE email = 'a@X'");
+    count!(2, "SELECT * FROM Copied");
+    run!("CREATE VIEW Matched AS SELECT id FROM Account WHERE email = 'A@X'");
+    count!(2, "SELECT * FROM Matched");
+
+    run!("CREATE TABLE Invite (email TEXT)");
+    run!("INSERT INTO Invite VALUES ('A@x')");
+    count!(
+        2,
+        "SELECT Account.id FROM Account JOIN Invite ON Account.email = Invite.email"
+    );
+
+    test!(
+        Ok(Payload::Update(1)),
+        "UPDATE Account SET name = 'Bob' WHERE email = 'b@X'"
+    );
+    test!(
+        Ok(Payload::Delete(1)),
+        "DELETE FROM Account WHERE email = 'C@X'"
+    );
+    test!(
+        Ok(select!(
+            id  | name
+            I64 | Str;
+            2     "Bob".to_owned()
+        )),
+        "SELECT id, name FROM Account WHERE id > 1 AND id <> 3"
+    );
+
+    test!(
+        Err(AlterError::UnsupportedDataTypeForCollation("id".to_owned(), DataType::Int).into()),
+        "CREATE TABLE Wrong (id INTEGER COLLATE NOCASE)"
+    );
+    test!(
+        Err(TranslateError::UnsupportedCollation("korean".to_owned()).into()),
+        "CREATE TABLE Wrong (name TEXT COLLATE korean)"
+    );
+});
@@ -0,0 +1,150 @@
+use {
+    crate::*,
+    gluesql_core::{executor::SelectError, prelude::Value::*},
+};
+
+test_case!(cte, async move {
+    run!("CREATE TABLE Employee (id INTEGER, name TEXT, manager_id INTEGER NULL)");
+    run!(
+        "INSERT INTO Employee VALUES
+            (1, 'Alice', NULL),
+            (2, 'Bob', 1),
+            (3, 'Carol', 2),
+            (4, 'Dave', 2),
+            (5, 'Eve', 1)"
+    );
+
+    test!(
+        Ok(select!(
+            id  | name
+            I64 | Str;
+            2     "Bob".to_owned();
+            5     "Eve".to_owned()
+        )),
+        "WITH Reports AS (SELECT id, name FROM Employee WHERE manager_id = 1)
+        SELECT * FROM Reports"
+    );
+    test!(
+        Ok(select!(
+            name
+            Str;
+            "Carol".to_owned();
+            "Dave".to_owned()
+        )),
+        "WITH
+            Managers(manager) AS (SELECT id FROM Employee WHERE name = 'Bob'),
+            Reports AS (
+                SELECT name FROM Employee
+                WHERE manager_id IN (SELECT manager FROM Managers)
+            )
+        SELECT name FROM Reports"
+    );
+
+    // ancestors of Dave, walking up the adjacency list
+    test!(
+        Ok(select!(
+            name                | depth
+            Str                 | I64;
+            "Dave".to_owned()     0;
+            "Bob".to_owned()      1;
+            "Alice".to_owned()    2
+        )),
+        "WITH RECURSIVE Ancestors(id, name, manager_id, depth) AS (
+            SELECT id, name, manager_id, 0 FROM Employee WHERE id = 4
+            UNION ALL
+            SELECT e.id, e.name, e.manager_id, a.depth + 1
+            FROM Employee e
+            JOIN Ancestors a ON e.id = a.manager_id
+        )
+        SELECT name, depth FROM Ancestors ORDER BY depth"
+    );
+    test!(
+        Ok(select!(
+            id
+            I64;
+            2;
+            3;
+            4
+        )),
+        "WITH RECURSIVE Descendants(id) AS (
+            SELECT id FROM Employee WHERE id = 2
+            UNION
+            SELECT Employee.id FROM Employee
+            JOIN Descendants ON Employee.manager_id = Descendants.id
+        )
+        SELECT id FROM Descendants ORDER BY id"
+    );
+
+    // the labels of a derived table come from its CTEs without running them
+    test!(
+        Ok(select!(
+            id  | name              | depth
+            I64 | Str               | I64;
+            1     "Alice".to_owned()  0
+        )),
+        "SELECT * FROM (
+            WITH RECURSIVE Chain(id, name, depth) AS (
+                SELECT id, name, 0 FROM Employee WHERE manager_id IS NULL
+                UNION ALL
+                SELECT Employee.id, Employee.name, Chain.depth + 1 FROM Employee
+                JOIN Chain ON Employee.manager_id = Chain.id
+            ),
+            Roots AS (SELECT * FROM Chain WHERE depth = 0)
+            SELECT * FROM Roots
+        ) AS Found"
+    );
+
+    run!("CREATE TABLE Edge (src INTEGER, dst INTEGER)");
+    run!("INSERT INTO Edge VALUES (1, 2), (2, 3), (3, 1)");
+
+    // UNION drops the rows already found, so walking a cycle stops
+    test!(
+        Ok(select!(
+            node
+            I64;
+            1;
+            2;
+            3
+        )),
+        "WITH RECURSIVE Reach(node) AS (
+            SELECT src FROM Edge WHERE src = 1
+            UNION
+            SELECT Edge.dst FROM Edge JOIN Reach ON Edge.src = Reach.node
+        )
+        SELECT node FROM Reach ORDER BY node"
+    );
+    test!(
+        Err(SelectError::RecursionLimitExceeded("Reach".to_owned(), 1000).into()),
+        "WITH RECURSIVE Reach(node) AS (
+            SELECT src FROM Edge WHERE src = 1
+            UNION ALL
+            SELECT Edge.dst FROM Edge JOIN Reach ON Edge.src = Reach.node
+        )
+        SELECT node FROM Reach"
+    );
+
+    test!(
+        Err(SelectError::CteColumnCountMismatch {
+            name: "Pairs".to_owned(),
+            expected: 2,
+            found: 1,
+        }
+        .into()),
+        "WITH Pairs(a, b) AS (SELECT id FROM Employee) SELECT * FROM Pairs"
+    );
+    test!(
+        Err(SelectError::CteColumnCountMismatch {
+            name: "Chain".to_owned(),
+            expected: 1,
+            found: 2,
+        }
+        .into()),
+        "WITH RECURSIVE Chain AS (
+            SELECT id FROM Employee WHERE id = 1
+            UNION ALL
+            SELECT Employee.id, Employee.name FROM Employee
+            JOIN Chain ON Employee.manager_id = Chain.id
+        )
+        SELECT * FROM Chain"
+    );
+});
@@ -0,0 +1,121 @@
+use {
+    crate::*,
+    gluesql_core::{
+        data::{KeyError, NumericBinaryOperator, ValueError},
+        executor::Payload,
+        prelude::Value::*,
+    },
+};
+
+test_case!(bit, async move {
+    let bits = |v: &str| v.chars().map(|c| c == '1').collect::<Vec<_>>();
+    let bit = |v: &str| Bit(bits(v));
+
+    let test_cases = vec![
+        (
+            "CREATE TABLE Flag (id INTEGER, a BIT(4) NULL, b BIT(4) NULL)",
+            Ok(Payload::Create),
+        ),
+        (
+            "INSERT INTO Flag VALUES
+                (1, '1100', '1010'),
+                (2, '0000', '1111'),
+                (3, '0111', '0011'),
+                (4, NULL, '0001');
+            ",
+            Ok(Payload::Insert(4)),
+        ),
+        (
+            "SELECT id, a FROM Flag WHERE id < 4",
+            Ok(select!(
+                id  | a
+                I64 | Bit;
+                1     bits("1100");
+                2     bits("0000");
+                3     bits("0111")
+            )),
+        ),
+        (
+            "SELECT id FROM Flag WHERE a = '0000'",
+            Ok(select!(id I64; 2)),
+        ),
+        (
+            "SELECT a & b AS x, a | b AS y, a ^ b AS z FROM Flag WHERE id < 3",
+            Ok(select_with_null!(
+                x           | y           | z;
+                bit("1000")   bit("1110")   bit("0110");
+                bit("0000")   bit("1111")   bit("1111")
+            )),
+        ),
+        (
+            "SELECT a & b AS x, b | a AS y FROM Flag WHERE id = 4",
+            Ok(select_with_null!(x | y; Null Null)),
+        ),
+        (
+            "SELECT CAST('0110' AS BIT(4)) ^ b AS x FROM Flag WHERE id = 1",
+            Ok(select_with_null!(x; bit("1100"))),
+        ),
+        (
+            "SELECT '0110'::BIT(4) ^ b AS x FROM Flag WHERE id = 1",
+            Ok(select_with_null!(x; bit("1100"))),
+        ),
+        (
+            "SELECT CAST(a | b AS TEXT) AS x FROM Flag WHERE id = 1",
+            Ok(select!(x Str; "1110".to_owned())),
+        ),
+        (
+            "SELECT a & CAST('11' AS BIT(2)) FROM Flag WHERE id = 3",
+            Err(ValueError::BitLengthMismatch { lhs: 4, rhs: 2 }.into()),
+        ),
+        (
+            "SELECT a | 1 FROM Flag WHERE id = 1",
+            Err(ValueError::NonBitwiseOperation {
+                lhs: bit("1100"),
+                operator: NumericBinaryOperator::BitwiseOr,
+                rhs: I64(1),
+            }
+            .into()),
+        ),
+        (
+            "INSERT INTO Flag VALUES (5, '0120', NULL)",
+            Err(ValueError::FailedToParseBitString("0120".to_owned()).into()),
+        ),
+        (
+            "INSERT INTO Flag VALUES (5, '101', NULL)",
+            Err(ValueError::IncompatibleBitLength {
+                length: 4,
+                found: 3,
+            }
+            .into()),
+        ),
+        (
+            "UPDATE Flag SET b = CAST('11' AS BIT(2)) WHERE id = 1",
+            Err(ValueError::IncompatibleBitLength {
+                length: 4,
+                found: 2,
+            }
+            .into()),
+        ),
+        ("CREATE TABLE Switch (on_off BIT)", Ok(Payload::Create)),
+        (
+            "INSERT INTO Switch VALUES ('1'), ('0')",
+            Ok(Payload::Insert(2)),
+        ),
+        (
+            "INSERT INTO Switch VALUES ('10')",
+            Err(ValueError::IncompatibleBitLength {
+                length: 1,
+                found: 2,
+            }
+            .into()),
+        ),
+        (
+            "SELECT a FROM Flag GROUP BY a",
+            Err(KeyError::BitTypeKeyNotSupported.into()),
+        ),
+    ];
+
+    for (sql, expected) in test_cases {
+        test!(expected, sql);
+    }
+});
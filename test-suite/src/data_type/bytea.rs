@@ -5,7 +5,7 @@ use {
         ast::DataType,
         data::{Literal, LiteralError, ValueError},
         executor::Payload,
-        prelude::Value::Bytea,
+        prelude::Value::{Bytea, I64},
     },
     std::borrow::Cow,
 };
@@ -45,6 +45,64 @@ test_case!(bytea, async move {
             r#"INSERT INTO Bytea VALUES (X'123')"#,
             Err(LiteralError::FailedToDecodeHexString("123".to_string()).into()),
         ),
+        (
+            r#"INSERT INTO Bytea VALUES ('\x0aFF'), ('\x')"#,
+            Ok(Payload::Insert(2)),
+        ),
+        (
+            "SELECT key FROM Bytea WHERE key = X'0aff'",
+            Ok(select!(key Bytea; bytea("0aff"))),
+        ),
+        (
+            "SELECT key FROM Bytea WHERE key > X'7f' ORDER BY key",
+            Ok(select!(
+                key
+                Bytea;
+                bytea("936DA0");
+                bytea("ab0123")
+            )),
+        ),
+        (
+            "SELECT key FROM Bytea ORDER BY key DESC",
+            Ok(select!(
+                key
+                Bytea;
+                bytea("ab0123");
+                bytea("936DA0");
+                bytea("123456");
+                bytea("0aff");
+                bytea("")
+            )),
+        ),
+        (
+            "SELECT LENGTH(key) AS len FROM Bytea ORDER BY key",
+            Ok(select!(
+                len
+                I64;
+                0;
+                2;
+                3;
+                3;
+                3
+            )),
+        ),
+        (
+            r#"INSERT INTO Bytea VALUES ('\x0g')"#,
+            Err(ValueError::FailedToParseHexString(r"\x0g".to_owned()).into()),
+        ),
+        ("CREATE TABLE BlobItem (key BLOB(16))", Ok(Payload::Create)),
+        (
+            "INSERT INTO BlobItem VALUES (X'0102')",
+            Ok(Payload::Insert(1)),
+        ),
+        (
+            "SELECT key, LENGTH(key) AS len FROM BlobItem",
+            Ok(select!(
+                key         | len
+                Bytea       | I64;
+                bytea("0102") 2
+            )),
+        ),
     ];
 
     for (sql, expected) in test_cases.into_iter() {
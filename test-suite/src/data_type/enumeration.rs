@@ -0,0 +1,102 @@
+use {
+    crate::*,
+    gluesql_core::{
+        data::ValueError,
+        executor::Payload,
+        prelude::Value::{self, *},
+        result::Error,
+    },
+};
+
+test_case!(enumeration, async move {
+    let invalid = |value: &str| -> Error {
+        ValueError::InvalidEnumValue {
+            value: value.to_owned(),
+            allowed: vec![
+                "active".to_owned(),
+                "suspended".to_owned(),
+                "closed".to_owned(),
+            ],
+        }
+        .into()
+    };
+    let status = |value: &str| Str(value.to_owned());
+
+    let test_cases = vec![
+        (
+            "CREATE TABLE Account (
+                id INTEGER,
+                status ENUM('active', 'suspended', 'closed') NULL
+            )",
+            Ok(Payload::Create),
+        ),
+        (
+            "INSERT INTO Account VALUES (1, 'closed'), (2, 'active'), (3, NULL), (4, 'suspended')",
+            Ok(Payload::Insert(4)),
+        ),
+        (
+            "INSERT INTO Account VALUES (5, 'deleted')",
+            Err(invalid("deleted")),
+        ),
+        (
+            "INSERT INTO Account VALUES (5, 'Active')",
+            Err(invalid("Active")),
+        ),
+        (
+            "INSERT INTO Account SELECT id + 10, 'pending' FROM Account WHERE id = 1",
+            Err(invalid("pending")),
+        ),
+        (
+            "SELECT id, status FROM Account WHERE status = 'closed'",
+            Ok(select!(
+                id  | status
+                I64 | Str;
+                1     "closed".to_owned()
+            )),
+        ),
+        (
+            "SELECT id, status FROM Account ORDER BY status",
+            Ok(select_with_null!(
+                id     | status;
+                I64(2)   status("active");
+                I64(4)   status("suspended");
+                I64(1)   status("closed");
+                I64(3)   Value::Null
+            )),
+        ),
+        (
+            "SELECT id FROM Account ORDER BY status DESC",
+            Ok(select!(id I64; 3; 1; 4; 2)),
+        ),
+        (
+            "WITH Ids AS (SELECT id FROM Account) SELECT id FROM Account ORDER BY status DESC",
+            Ok(select!(id I64; 3; 1; 4; 2)),
+        ),
+        (
+            "UPDATE Account SET status = 'archived' WHERE id = 1",
+            Err(invalid("archived")),
+        ),
+        (
+            "UPDATE Account SET status = 'active' WHERE id = 1",
+            Ok(Payload::Update(1)),
+        ),
+        (
+            "SELECT id FROM Account WHERE status = 'active' ORDER BY id",
+            Ok(select!(id I64; 1; 2)),
+        ),
+        (
+            "SELECT CAST('closed' AS ENUM('active', 'suspended', 'closed')) AS status
+            FROM Account WHERE id = 1",
+            Ok(select!(status Str; "closed".to_owned())),
+        ),
+        (
+            "SELECT CAST('deleted' AS ENUM('active', 'suspended', 'closed')) AS status
+            FROM Account WHERE id = 1",
+            Err(invalid("deleted")),
+        ),
+    ];
+
+    for (sql, expected) in test_cases {
+        test!(expected, sql);
+    }
+});
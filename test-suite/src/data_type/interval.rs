@@ -124,3 +124,50 @@ INSERT INTO IntervalLog VALUES
         r#"SELECT INTERVAL "111" DAY TO Second FROM IntervalLog;"#
     );
 });
+
+test_case!(text, async move {
+    use gluesql_core::{data::Interval as I, prelude::Value::*};
+
+    run!("CREATE TABLE Span (id INTEGER, span INTERVAL)");
+    run!(
+        r#"
+        INSERT INTO Span VALUES
+            (1, INTERVAL "1-2" YEAR TO MONTH),
+            (2, INTERVAL "-3" MONTH),
+            (3, INTERVAL "26" HOUR),
+            (4, INTERVAL "-90" MINUTE),
+            (5, INTERVAL "1.5" SECOND),
+            (6, INTERVAL "0" SECOND);
+        "#
+    );
+
+    test!(
+        Ok(select!(
+            id  | text
+            I64 | Str;
+            1     "1 year 2 months".to_owned();
+            2     "-3 months".to_owned();
+            3     "1 day 02:00:00".to_owned();
+            4     "-01:30:00".to_owned();
+            5     "00:00:01.5".to_owned();
+            6     "00:00:00".to_owned()
+        )),
+        "SELECT id, CAST(span AS TEXT) AS text FROM Span"
+    );
+    test!(
+        Ok(select!(id; I64; 1; 2; 3; 4; 5; 6)),
+        "SELECT id FROM Span WHERE CAST(CAST(span AS TEXT) AS INTERVAL) = span"
+    );
+    test!(
+        Ok(select!(
+            a             | b             | c
+            Interval      | Interval      | Interval;
+            I::Month(-27)   I::days(-1)     I::hours(36)
+        )),
+        "SELECT
+            CAST('-2 years 3 months' AS INTERVAL) AS a,
+            CAST('-1 day 00:00:00' AS INTERVAL) AS b,
+            CAST('36:00:00' AS INTERVAL) AS c
+        FROM Span WHERE id = 1"
+    );
+});
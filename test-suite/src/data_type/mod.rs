@@ -1,6 +1,8 @@
+pub mod bit;
 pub mod bytea;
 pub mod date;
 pub mod decimal;
+pub mod enumeration;
 pub mod int128;
 pub mod int16;
 pub mod int32;
@@ -12,4 +14,5 @@ pub mod map;
 pub mod sql_types;
 pub mod time;
 pub mod timestamp;
+pub mod timestamptz;
 pub mod uuid;
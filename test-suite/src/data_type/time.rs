@@ -142,3 +142,73 @@ INSERT INTO TimeLog VALUES
         r#"INSERT INTO TimeLog VALUES (1, "12345-678", "20:05:01")"#
     );
 });
+
+test_case!(time_of_day, async move {
+    use {chrono::NaiveTime, gluesql_core::prelude::Value::*};
+
+    run!("CREATE TABLE Schedule (id INTEGER, starts TIME)");
+    run!(
+        "
+        INSERT INTO Schedule VALUES
+            (1, '13:45:00'),
+            (2, '00:15:00'),
+            (3, '23:30:15.5'),
+            (4, '09:05:00');
+    "
+    );
+
+    let t = NaiveTime::from_hms_milli;
+
+    test!(
+        Ok(select!(
+            id  | starts
+            I64 | Time;
+            2     t(0, 15, 0, 0);
+            4     t(9, 5, 0, 0);
+            1     t(13, 45, 0, 0);
+            3     t(23, 30, 15, 500)
+        )),
+        "SELECT id, starts FROM Schedule ORDER BY starts"
+    );
+
+    test!(
+        Ok(select!(
+            id  | hour | minute | second
+            I64 | I64  | I64    | I64;
+            1     13     45       0;
+            2     0      15       0;
+            3     23     30       15;
+            4     9      5        0
+        )),
+        "
+        SELECT
+            id,
+            EXTRACT(HOUR FROM starts) AS hour,
+            EXTRACT(MINUTE FROM starts) AS minute,
+            EXTRACT(SECOND FROM starts) AS second
+        FROM Schedule
+        "
+    );
+
+    test!(
+        Ok(select!(
+            id  | later              | earlier
+            I64 | Time               | Time;
+            2     t(1, 15, 0, 0)       t(23, 45, 0, 0);
+            3     t(0, 30, 15, 500)    t(23, 0, 15, 500)
+        )),
+        "
+        SELECT
+            id,
+            starts + INTERVAL '1' HOUR AS later,
+            starts - INTERVAL '30' MINUTE AS earlier
+        FROM Schedule
+        WHERE id IN (2, 3)
+        "
+    );
+
+    test!(
+        Ok(select!(wrapped; Time; t(0, 30, 15, 500))),
+        "SELECT starts + INTERVAL '25' HOUR AS wrapped FROM Schedule WHERE id = 3"
+    );
+});
@@ -118,3 +118,60 @@ INSERT INTO TimestampLog VALUES
         r#"INSERT INTO TimestampLog VALUES (1, "12345-678", "2021-05-01")"#
     );
 });
+
+test_case!(formats, async move {
+    use gluesql_core::{data::ValueError, prelude::Value::*};
+
+    macro_rules! t {
+        ($timestamp: expr) => {
+            $timestamp.parse().unwrap()
+        };
+    }
+
+    session!().set_date_formats(vec!["%Y-%m-%d".to_owned(), "%m/%d/%Y".to_owned()]);
+    session!().set_timestamp_formats(vec![
+        "%Y-%m-%d %H:%M:%S".to_owned(),
+        "%m/%d/%Y %H:%M".to_owned(),
+    ]);
+
+    run!("CREATE TABLE Event (id INTEGER, day DATE, at TIMESTAMP)");
+    run!(
+        "
+        INSERT INTO Event VALUES
+            (1, '2024-01-02', '2024-01-02 03:04:05'),
+            (2, '01/02/2024', '01/02/2024 13:30');
+    "
+    );
+
+    test!(
+        Ok(select!(
+            id  | day                 | at
+            I64 | Date                | Timestamp;
+            1     t!("2024-01-02")      t!("2024-01-02T03:04:05");
+            2     t!("2024-01-02")      t!("2024-01-02T13:30:00")
+        )),
+        "SELECT * FROM Event"
+    );
+    test!(
+        Ok(select!(id; I64; 1; 2)),
+        "SELECT id FROM Event WHERE day = '01/02/2024'"
+    );
+
+    test!(
+        Err(ValueError::FailedToParseTimestamp("2024-01-02T03:04:05Z".to_owned()).into()),
+        "INSERT INTO Event VALUES (3, '2024-01-02', '2024-01-02T03:04:05Z')"
+    );
+    test!(
+        Err(ValueError::FailedToParseDate("02.01.2024".to_owned()).into()),
+        "INSERT INTO Event VALUES (3, '02.01.2024', '2024-01-02 03:04:05')"
+    );
+
+    session!().set_date_formats(Vec::new());
+    session!().set_timestamp_formats(Vec::new());
+
+    test!(
+        Err(ValueError::FailedToParseDate("01/02/2024".to_owned()).into()),
+        "INSERT INTO Event VALUES (3, '01/02/2024', '2024-01-02 03:04:05')"
+    );
+    run!("INSERT INTO Event VALUES (3, '2024-01-02', '2024-01-02T03:04:05Z')");
+});
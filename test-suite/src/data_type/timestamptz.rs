@@ -0,0 +1,83 @@
+use {crate::*, gluesql_core::prelude::Value::*};
+
+test_case!(timestamptz, async move {
+    run!("CREATE TABLE Event (id INTEGER, ts TIMESTAMPTZ)");
+    run!(
+        "
+        INSERT INTO Event VALUES
+            (1, '2024-01-02 12:00:00+09:00'),
+            (2, '2024-01-02T03:00:00Z'),
+            (3, '2024-01-02 05:00:00 -05:00');
+    "
+    );
+
+    macro_rules! t {
+        ($timestamp: expr) => {
+            $timestamp.parse().unwrap()
+        };
+    }
+
+    test!(
+        Ok(select!(
+            id  | ts
+            I64 | TimestampTz;
+            1     t!("2024-01-02T12:00:00+09:00");
+            2     t!("2024-01-02T03:00:00+00:00");
+            3     t!("2024-01-02T05:00:00-05:00")
+        )),
+        "SELECT id, ts FROM Event"
+    );
+
+    test!(
+        Ok(select!(id; I64; 1; 2)),
+        "SELECT id FROM Event WHERE ts = '2024-01-02 03:00:00+00:00'"
+    );
+    test!(
+        Ok(select!(id; I64; 1; 2)),
+        "SELECT id FROM Event WHERE ts = '2024-01-02 03:00:00'"
+    );
+    test!(
+        Ok(select!(id | other; I64 | I64; 1 2)),
+        "SELECT a.id, b.id AS other FROM Event a JOIN Event b ON a.ts = b.ts WHERE a.id < b.id"
+    );
+    test!(
+        Ok(select!(id; I64; 3)),
+        "SELECT id FROM Event WHERE ts > '2024-01-02 12:59:59+09:00'"
+    );
+    test!(
+        Ok(select!(id; I64; 3; 1; 2)),
+        "SELECT id FROM Event ORDER BY ts DESC"
+    );
+
+    test!(
+        Ok(select!(
+            id  | local_hour | day_after
+            I64 | I64        | Str;
+            1     12           "2024-01-03 12:00:00 +09:00".to_owned();
+            2     3            "2024-01-03 03:00:00 +00:00".to_owned();
+            3     5            "2024-01-03 05:00:00 -05:00".to_owned()
+        )),
+        "
+        SELECT
+            id,
+            EXTRACT(HOUR FROM ts) AS local_hour,
+            CAST(ts + INTERVAL '1' DAY AS TEXT) AS day_after
+        FROM Event
+        "
+    );
+    test!(
+        Ok(select!(
+            id  | since
+            I64 | Interval;
+            1     gluesql_core::data::Interval::seconds(0);
+            2     gluesql_core::data::Interval::seconds(0);
+            3     gluesql_core::data::Interval::hours(7)
+        )),
+        "SELECT id, ts - CAST('2024-01-02 03:00:00Z' AS TIMESTAMPTZ) AS since FROM Event"
+    );
+
+    test!(
+        Ok(select!(ts; Timestamp; t!("2024-01-02T03:00:00"))),
+        "SELECT CAST(ts AS TIMESTAMP) AS ts FROM Event WHERE id = 1"
+    );
+});
@@ -0,0 +1,60 @@
+use {crate::*, gluesql_core::prelude::Value::*};
+
+test_case!(deterministic_order, async move {
+    run!("CREATE TABLE Player (id INTEGER, team TEXT, score INTEGER)");
+    run!("INSERT INTO Player VALUES (3, 'Red', 30), (1, 'Blue', NULL), (2, 'Red', 10)");
+    run!("DELETE FROM Player WHERE id = 1");
+    run!("INSERT INTO Player VALUES (1, 'Blue', NULL), (4, 'Blue', 40)");
+
+    session!().set_deterministic_order(true);
+
+    let sql = "SELECT * FROM Player";
+    let first = run!(sql);
+    run!("UPDATE Player SET score = score WHERE TRUE");
+    let second = run!(sql);
+    assert_eq!(first, second);
+
+    test!(
+        Ok(select_with_null!(
+            id     | team                 | score;
+            I64(1)   Str("Blue".to_owned())   Null;
+            I64(2)   Str("Red".to_owned())    I64(10);
+            I64(3)   Str("Red".to_owned())    I64(30);
+            I64(4)   Str("Blue".to_owned())   I64(40)
+        )),
+        sql
+    );
+
+    // rows are sorted before LIMIT, so the same rows are taken each time
+    test!(
+        Ok(select!(id I64; 3; 4)),
+        "SELECT id FROM Player WHERE score IS NOT NULL LIMIT 2 OFFSET 1"
+    );
+    test!(
+        Ok(select!(
+            team              | top
+            Str               | I64;
+            "Blue".to_owned()   40;
+            "Red".to_owned()    30
+        )),
+        "SELECT team, MAX(score) AS top FROM Player GROUP BY team"
+    );
+
+    // NULL comes last, as in ORDER BY
+    test!(
+        Ok(select_with_null!(
+            score;
+            I64(10);
+            I64(30);
+            I64(40);
+            Null
+        )),
+        "SELECT score FROM Player"
+    );
+
+    // ORDER BY still decides the order
+    test!(
+        Ok(select!(id I64; 4; 3; 2; 1)),
+        "SELECT id FROM Player ORDER BY id DESC"
+    );
+});
@@ -0,0 +1,55 @@
+use {crate::*, gluesql_core::prelude::Value::*};
+
+test_case!(distinct_from, async move {
+    run!("CREATE TABLE Change (id INTEGER, old INTEGER NULL, new INTEGER NULL)");
+    run!(
+        "INSERT INTO Change VALUES
+            (1, NULL, NULL),
+            (2, NULL, 1),
+            (3, 1, NULL),
+            (4, 1, 1),
+            (5, 1, 2)"
+    );
+
+    let test_cases = [
+        (3, "SELECT * FROM Change WHERE old IS DISTINCT FROM new"),
+        (2, "SELECT * FROM Change WHERE old IS NOT DISTINCT FROM new"),
+        (
+            3,
+            "SELECT * FROM Change WHERE NOT (old IS NOT DISTINCT FROM new)",
+        ),
+        (3, "SELECT * FROM Change WHERE old IS DISTINCT FROM NULL"),
+        // unlike IS NOT DISTINCT FROM, = does not hold between two NULLs
+        (1, "SELECT * FROM Change WHERE old = new"),
+    ];
+
+    for (num, sql) in test_cases {
+        count!(num, sql);
+    }
+
+    test!(
+        Ok(select!(
+            id  | changed
+            I64 | Bool;
+            1     false;
+            2     true;
+            3     true;
+            4     false;
+            5     true
+        )),
+        "SELECT id, old IS DISTINCT FROM new AS changed FROM Change"
+    );
+    test!(
+        Ok(select!(
+            a     | b    | c     | d
+            Bool  | Bool | Bool  | Bool;
+            false   true   true    true
+        )),
+        "SELECT
+            NULL IS DISTINCT FROM NULL AS a,
+            NULL IS DISTINCT FROM 1 AS b,
+            1 IS NOT DISTINCT FROM 1 AS c,
+            1 IS DISTINCT FROM 2 AS d
+        FROM Change WHERE id = 1"
+    );
+});
@@ -0,0 +1,95 @@
+use {
+    crate::*,
+    gluesql_core::{
+        prelude::{Payload, Value},
+        translate::TranslateError,
+    },
+};
+
+/// Operators and row counts of an `EXPLAIN ANALYZE` result, leaving out the times which
+/// change from run to run.
+fn actual_rows(payload: Payload) -> Vec<(String, i64)> {
+    let rows = match payload {
+        Payload::Select { rows, .. } => rows,
+        _ => panic!("unexpected payload: {:?}", payload),
+    };
+
+    rows.into_iter()
+        .map(|row| match row.as_slice() {
+            [Value::Str(operator), Value::I64(rows), Value::Interval(_)] => {
+                (operator.to_owned(), *rows)
+            }
+            _ => panic!("unexpected row: {:?}", row),
+        })
+        .collect()
+}
+
+fn num_rows(payload: Payload) -> i64 {
+    match payload {
+        Payload::Select { rows, .. } => rows.len() as i64,
+        _ => panic!("unexpected payload: {:?}", payload),
+    }
+}
+
+test_case!(explain_analyze, async move {
+    run!("CREATE TABLE Item (id INTEGER, category TEXT)");
+    run!("CREATE TABLE Tag (item_id INTEGER, name TEXT)");
+    run!(
+        "INSERT INTO Item VALUES
+            (1, 'fruit'), (2, 'fruit'), (3, 'meat'),
+            (4, 'meat'), (5, 'fish'), (6, 'fruit')"
+    );
+    run!("INSERT INTO Tag VALUES (1, 'red'), (3, 'fresh'), (3, 'sale'), (7, 'none')");
+
+    let query = "SELECT * FROM Item WHERE id > 2";
+    let expected = vec![
+        ("Output".to_owned(), 4),
+        ("Filter".to_owned(), 4),
+        ("Scan Item".to_owned(), 6),
+    ];
+    assert_eq!(
+        actual_rows(run!(&format!("EXPLAIN ANALYZE {query}"))),
+        expected
+    );
+    assert_eq!(num_rows(run!(query)), 4);
+
+    let query = "SELECT category, COUNT(*) FROM Item GROUP BY category ORDER BY category";
+    let expected = vec![
+        ("Output".to_owned(), 3),
+        ("Sort".to_owned(), 3),
+        ("Aggregate".to_owned(), 3),
+        ("Scan Item".to_owned(), 6),
+    ];
+    assert_eq!(
+        actual_rows(run!(&format!("EXPLAIN ANALYZE {query}"))),
+        expected
+    );
+    assert_eq!(num_rows(run!(query)), 3);
+
+    let query = "SELECT Item.id, Tag.name FROM Item JOIN Tag ON Item.id = Tag.item_id";
+    let expected = vec![
+        ("Output".to_owned(), 3),
+        ("Join".to_owned(), 3),
+        ("Scan Item".to_owned(), 6),
+    ];
+    assert_eq!(
+        actual_rows(run!(&format!("EXPLAIN ANALYZE {query}"))),
+        expected
+    );
+    assert_eq!(num_rows(run!(query)), 3);
+
+    let query = "SELECT id FROM Item WHERE category = 'fruit' LIMIT 1 OFFSET 1";
+    let output = actual_rows(run!(&format!("EXPLAIN ANALYZE {query}")))[0].clone();
+    assert_eq!(output, ("Output".to_owned(), num_rows(run!(query))));
+
+    // the query runs, but EXPLAIN ANALYZE does not take statements modifying data
+    test!(
+        Err(TranslateError::ExplainAnalyzeRequiresQuery.into()),
+        "EXPLAIN ANALYZE DELETE FROM Item WHERE id = 1"
+    );
+    test!(
+        Err(TranslateError::ExplainAnalyzeRequiresQuery.into()),
+        "EXPLAIN ANALYZE INSERT INTO Item VALUES (7, 'fish')"
+    );
+    assert_eq!(num_rows(run!("SELECT * FROM Item")), 6);
+});
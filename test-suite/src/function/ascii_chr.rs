@@ -0,0 +1,107 @@
+use {
+    crate::*,
+    gluesql_core::{
+        executor::EvaluateError,
+        prelude::{Payload, Value::*},
+        translate::TranslateError,
+    },
+};
+
+test_case!(ascii_chr, async move {
+    let test_cases = vec![
+        (
+            "CREATE TABLE Letter (id INTEGER, name TEXT NULL)",
+            Ok(Payload::Create),
+        ),
+        (
+            "INSERT INTO Letter VALUES (1, 'abc'), (2, ''), (3, '가나'), (4, NULL)",
+            Ok(Payload::Insert(4)),
+        ),
+        (
+            "SELECT id, ASCII(name) AS code FROM Letter WHERE id IN (1, 2, 4)",
+            Ok(select_with_null!(
+                id     | code;
+                I64(1)   I64(97);
+                I64(2)   I64(0);
+                I64(4)   Null
+            )),
+        ),
+        (
+            "SELECT id, ORD(name) AS code FROM Letter",
+            Ok(select_with_null!(
+                id     | code;
+                I64(1)   I64(97);
+                I64(2)   I64(0);
+                I64(3)   I64(44032);
+                I64(4)   Null
+            )),
+        ),
+        (
+            "SELECT CHR(65) AS a, CHR(44032) AS ga, CHR(128512) AS emoji FROM Letter WHERE id = 1",
+            Ok(select!(
+                a           | ga          | emoji
+                Str         | Str         | Str;
+                "A".to_owned() "가".to_owned() "😀".to_owned()
+            )),
+        ),
+        (
+            "SELECT id, CHR(ORD(name)) = SUBSTR(name, 1, 1) AS same FROM Letter WHERE id IN (1, 3)",
+            Ok(select!(
+                id  | same
+                I64 | Bool;
+                1     true;
+                3     true
+            )),
+        ),
+        (
+            "SELECT ASCII(CHR(id + 64)) AS code FROM Letter WHERE id < 3",
+            Ok(select!(
+                code
+                I64;
+                65;
+                66
+            )),
+        ),
+        (
+            "SELECT CHR(NULL) AS test FROM Letter WHERE id = 1",
+            Ok(select_with_null!(test; Null)),
+        ),
+        (
+            "SELECT ASCII(name) FROM Letter WHERE id = 3",
+            Err(EvaluateError::NonAsciiCharacterNotAllowed("가".to_owned()).into()),
+        ),
+        (
+            "SELECT CHR(55296) FROM Letter WHERE id = 1",
+            Err(EvaluateError::InvalidUnicodeCodePoint(55296).into()),
+        ),
+        (
+            "SELECT CHR(1114112) FROM Letter WHERE id = 1",
+            Err(EvaluateError::InvalidUnicodeCodePoint(1114112).into()),
+        ),
+        (
+            "SELECT CHR(-1) FROM Letter WHERE id = 1",
+            Err(EvaluateError::InvalidUnicodeCodePoint(-1).into()),
+        ),
+        (
+            "SELECT CHR('a') FROM Letter WHERE id = 1",
+            Err(EvaluateError::FunctionRequiresIntegerValue("CHR".to_owned()).into()),
+        ),
+        (
+            "SELECT ORD(1) FROM Letter WHERE id = 1",
+            Err(EvaluateError::FunctionRequiresStringValue("ORD".to_owned()).into()),
+        ),
+        (
+            "SELECT ASCII('a', 'b') FROM Letter WHERE id = 1",
+            Err(TranslateError::FunctionArgsLengthNotMatching {
+                name: "ASCII".to_owned(),
+                expected: 1,
+                found: 2,
+            }
+            .into()),
+        ),
+    ];
+
+    for (sql, expected) in test_cases {
+        test!(expected, sql);
+    }
+});
@@ -2,7 +2,7 @@ use crate::*;
 
 test_case!(concat, async move {
     use gluesql_core::prelude::Value::*;
-    use gluesql_core::translate::TranslateError;
+    use gluesql_core::{executor::EvaluateError, translate::TranslateError};
 
     run!(
         "
@@ -62,4 +62,68 @@ test_case!(concat, async move {
         .into()),
         r#"select concat() as myconcat from Concat;"#
     );
+
+    // CONCAT skips NULL columns, while || yields NULL
+    test!(
+        Ok(select_with_null!(
+            by_function | by_operator;
+            Str("Foo".to_owned())  Null
+        )),
+        "SELECT CONCAT(text, null_value) AS by_function, text || null_value AS by_operator FROM Concat;"
+    );
+
+    test!(
+        Ok(select!(
+           joined
+           Str;
+           "a, b, c".to_owned()
+        )),
+        "SELECT CONCAT_WS(', ', 'a', 'b', 'c') AS joined FROM Concat;"
+    );
+    // NULL arguments are skipped without leaving an empty slot
+    test!(
+        Ok(select!(
+           joined
+           Str;
+           "a-Foo".to_owned()
+        )),
+        "SELECT CONCAT_WS('-', 'a', NULL, text, null_value) AS joined FROM Concat;"
+    );
+    // non-text arguments are converted to text
+    test!(
+        Ok(select!(
+           joined
+           Str;
+           "1/2.3/TRUE".to_owned()
+        )),
+        "SELECT CONCAT_WS('/', id, rate, flag) AS joined FROM Concat;"
+    );
+    test!(
+        Ok(select!(
+           joined
+           Str;
+           "".to_owned()
+        )),
+        "SELECT CONCAT_WS(',', NULL, null_value) AS joined FROM Concat;"
+    );
+    test!(
+        Ok(select_with_null!(
+            joined;
+            Null
+        )),
+        "SELECT CONCAT_WS(NULL, 'a', 'b') AS joined FROM Concat;"
+    );
+    test!(
+        Err(EvaluateError::FunctionRequiresStringValue("CONCAT_WS".to_owned()).into()),
+        "SELECT CONCAT_WS(1, 'a', 'b') AS joined FROM Concat;"
+    );
+    test!(
+        Err(TranslateError::FunctionArgsLengthNotMatchingMin {
+            name: "CONCAT_WS".to_owned(),
+            expected_minimum: 2,
+            found: 1
+        }
+        .into()),
+        "SELECT CONCAT_WS(',') AS joined FROM Concat;"
+    );
 });
@@ -0,0 +1,109 @@
+use {
+    crate::*,
+    gluesql_core::{
+        ast::DataType,
+        executor::UdfError,
+        prelude::{Payload, Value::*},
+        translate::TranslateError,
+    },
+};
+
+test_case!(create_function, async move {
+    run!("CREATE TABLE Item (id INTEGER, name TEXT)");
+    run!("INSERT INTO Item VALUES (1, 'apple'), (2, 'banana')");
+
+    test!(
+        Ok(Payload::Create),
+        "CREATE FUNCTION twice(x INT) RETURNS INT AS 'x * 2'"
+    );
+    run!("CREATE FUNCTION add_up(a INT, b INT) RETURNS INT AS 'a + b'");
+    test!(
+        Ok(select!(
+            id  | doubled | added
+            I64 | I64     | I64;
+            1     2         3;
+            2     4         5
+        )),
+        "SELECT id, TWICE(id) AS doubled, add_up(twice(id), 1) AS added FROM Item"
+    );
+    // arguments are cast into the parameter types
+    test!(
+        Ok(select!(doubled I64; 6)),
+        "SELECT twice('3') AS doubled FROM Item WHERE id = 1"
+    );
+
+    // a function named after a data type taking arguments is called as any other
+    run!("CREATE FUNCTION bit(x INT) RETURNS INT AS 'x + 1'");
+    test!(
+        Ok(select!(next I64; 4)),
+        "SELECT bit(3) AS next FROM Item WHERE id = 1"
+    );
+
+    // a function can call the functions created before it
+    run!("CREATE FUNCTION quadruple(x INT) RETURNS INT AS 'twice(twice(x))'");
+    test!(
+        Ok(select!(id I64; 2)),
+        "SELECT id FROM Item WHERE quadruple(id) = 8"
+    );
+
+    test!(
+        Err(TranslateError::FunctionArgsLengthNotMatching {
+            name: "TWICE".to_owned(),
+            expected: 1,
+            found: 2,
+        }
+        .into()),
+        "SELECT twice(id, 1) FROM Item"
+    );
+    test!(
+        Err(UdfError::FunctionAlreadyExists("TWICE".to_owned()).into()),
+        "CREATE FUNCTION twice(x INT) RETURNS INT AS 'x + x'"
+    );
+
+    run!("CREATE FUNCTION broken(x INT) RETURNS INT AS 'x > 1'");
+    test!(
+        Err(UdfError::ReturnTypeMismatch {
+            name: "BROKEN".to_owned(),
+            data_type: DataType::Int,
+            value: Bool(false),
+        }
+        .into()),
+        "SELECT broken(id) FROM Item"
+    );
+
+    // a function cannot call itself, and calling each other is bounded
+    test!(
+        Err(TranslateError::UnsupportedFunction("AGAIN".to_owned()).into()),
+        "CREATE FUNCTION again(x INT) RETURNS INT AS 'again(x)'"
+    );
+    run!("CREATE FUNCTION ping(x INT) RETURNS INT AS 'x'");
+    run!("CREATE FUNCTION pong(x INT) RETURNS INT AS 'ping(x)'");
+    run!("DROP FUNCTION ping");
+    run!("CREATE FUNCTION ping(x INT) RETURNS INT AS 'pong(x)'");
+    test!(
+        Err(UdfError::RecursionLimitExceeded("PING".to_owned()).into()),
+        "SELECT ping(id) FROM Item"
+    );
+
+    test!(Ok(Payload::DropFunction), "DROP FUNCTION twice");
+    test!(Ok(Payload::DropFunction), "DROP FUNCTION IF EXISTS twice");
+    test!(
+        Err(UdfError::FunctionNotRegistered("TWICE".to_owned()).into()),
+        "DROP FUNCTION twice"
+    );
+    test!(
+        Err(TranslateError::UnsupportedFunction("TWICE".to_owned()).into()),
+        "SELECT twice(id) FROM Item"
+    );
+    test!(
+        Err(UdfError::FunctionNotRegistered("TWICE".to_owned()).into()),
+        "SELECT quadruple(id) FROM Item"
+    );
+
+    // created functions belong to the storage, so other sessions can call them
+    session!() = Default::default();
+    test!(
+        Ok(select!(added I64; 3)),
+        "SELECT add_up(id, 2) AS added FROM Item WHERE id = 1"
+    );
+});
@@ -0,0 +1,119 @@
+use {
+    crate::*,
+    gluesql_core::{
+        ast::DataType, executor::UdfError, prelude::Value::*, translate::TranslateError,
+    },
+};
+
+test_case!(custom, async move {
+    run!("CREATE TABLE Item (id INTEGER, name TEXT)");
+    run!("INSERT INTO Item VALUES (1, 'apple'), (2, 'banana'), (3, NULL)");
+
+    session!().register_function(
+        "double_it",
+        vec![DataType::Int],
+        DataType::Int,
+        |args| match args.as_slice() {
+            [I64(v)] => Ok(I64(v * 2)),
+            [Null] => Ok(Null),
+            _ => Err(format!("unexpected arguments: {:?}", args)),
+        },
+    );
+
+    test!(
+        Ok(select!(
+            id  | doubled
+            I64 | I64;
+            1     2;
+            2     4;
+            3     6
+        )),
+        "SELECT id, DOUBLE_IT(id) AS doubled FROM Item"
+    );
+    test!(
+        Ok(select!(id I64; 2; 3)),
+        "SELECT id FROM Item WHERE double_it(id) > 2"
+    );
+    // arguments are cast into the declared types
+    test!(
+        Ok(select!(doubled I64; 10)),
+        "SELECT DOUBLE_IT('5') AS doubled FROM Item WHERE id = 1"
+    );
+    test!(
+        Ok(select_with_null!(doubled; Null)),
+        "SELECT DOUBLE_IT(NULL) AS doubled FROM Item WHERE id = 1"
+    );
+    run!("INSERT INTO Item VALUES (DOUBLE_IT(5), 'cherry')");
+    test!(
+        Ok(select!(name Str; "cherry".to_owned())),
+        "SELECT name FROM Item WHERE id = 10"
+    );
+    test!(
+        Err(TranslateError::FunctionArgsLengthNotMatching {
+            name: "DOUBLE_IT".to_owned(),
+            expected: 1,
+            found: 2,
+        }
+        .into()),
+        "SELECT DOUBLE_IT(id, 1) FROM Item"
+    );
+
+    // a registered function takes the place of the built-in of the same name
+    session!().register_function(
+        "upper",
+        vec![DataType::Text],
+        DataType::Text,
+        |args| match args.as_slice() {
+            [Str(v)] => Ok(Str(format!("<{v}>"))),
+            _ => Ok(Null),
+        },
+    );
+    test!(
+        Ok(select!(upper Str; "<apple>".to_owned())),
+        "SELECT UPPER(name) AS upper FROM Item WHERE id = 1"
+    );
+    session!().unregister_function("upper");
+    test!(
+        Ok(select!(upper Str; "APPLE".to_owned())),
+        "SELECT UPPER(name) AS upper FROM Item WHERE id = 1"
+    );
+
+    session!().register_function("fail_it", vec![], DataType::Int, |_| {
+        Err("something went wrong".to_owned())
+    });
+    test!(
+        Err(UdfError::FunctionFailed {
+            name: "FAIL_IT".to_owned(),
+            message: "something went wrong".to_owned(),
+        }
+        .into()),
+        "SELECT FAIL_IT() FROM Item"
+    );
+
+    session!().register_function("wrong_type", vec![DataType::Int], DataType::Int, |_| {
+        Ok(Str("text".to_owned()))
+    });
+    test!(
+        Err(UdfError::ReturnTypeMismatch {
+            name: "WRONG_TYPE".to_owned(),
+            data_type: DataType::Int,
+            value: Str("text".to_owned()),
+        }
+        .into()),
+        "SELECT WRONG_TYPE(id) FROM Item"
+    );
+
+    session!().register_function("panic_it", vec![], DataType::Int, |_| panic!("panic_it"));
+    test!(
+        Err(UdfError::FunctionPanicked("PANIC_IT".to_owned()).into()),
+        "SELECT PANIC_IT() FROM Item"
+    );
+
+    for name in ["double_it", "fail_it", "wrong_type", "panic_it"] {
+        session!().unregister_function(name);
+    }
+    test!(
+        Err(TranslateError::UnsupportedFunction("DOUBLE_IT".to_owned()).into()),
+        "SELECT DOUBLE_IT(id) FROM Item"
+    );
+});
@@ -0,0 +1,121 @@
+use {
+    crate::*,
+    gluesql_core::{executor::EvaluateError, prelude::Value::*, translate::TranslateError},
+};
+
+test_case!(format, async move {
+    run!(
+        "
+        CREATE TABLE Member (
+            id INTEGER,
+            name TEXT,
+            score FLOAT,
+            nickname TEXT NULL
+        );
+    "
+    );
+    run!("INSERT INTO Member VALUES (1, 'Alice', 92.5, NULL);");
+
+    test!(
+        Ok(select!(
+            formatted
+            Str;
+            "Alice is 1".to_owned()
+        )),
+        "SELECT FORMAT('%s is %d', name, id) AS formatted FROM Member;"
+    );
+    test!(
+        Ok(select!(
+            formatted
+            Str;
+            "score: 92.50".to_owned()
+        )),
+        "SELECT FORMAT('score: %.2f', score) AS formatted FROM Member;"
+    );
+    test!(
+        Ok(select!(
+            formatted
+            Str;
+            "1.000000".to_owned()
+        )),
+        "SELECT FORMAT('%f', id) AS formatted FROM Member;"
+    );
+    test!(
+        Ok(select!(
+            formatted
+            Str;
+            "100% done".to_owned()
+        )),
+        "SELECT FORMAT('%d%% done', 100) AS formatted FROM Member;"
+    );
+    // %s accepts any value
+    test!(
+        Ok(select!(
+            formatted
+            Str;
+            "1/92.5/TRUE".to_owned()
+        )),
+        "SELECT FORMAT('%s/%s/%s', id, score, TRUE) AS formatted FROM Member;"
+    );
+    // NULL arguments render as NULL
+    test!(
+        Ok(select!(
+            formatted
+            Str;
+            "Alice (NULL)".to_owned()
+        )),
+        "SELECT FORMAT('%s (%s)', name, nickname) AS formatted FROM Member;"
+    );
+    test!(
+        Ok(select_with_null!(
+            formatted;
+            Null
+        )),
+        "SELECT FORMAT(NULL, name) AS formatted FROM Member;"
+    );
+    test!(
+        Err(EvaluateError::FormatSpecifierTypeMismatch {
+            specifier: "%d".to_owned(),
+            value: Str("Alice".to_owned()),
+        }
+        .into()),
+        "SELECT FORMAT('%d', name) AS formatted FROM Member;"
+    );
+    test!(
+        Err(EvaluateError::FormatSpecifierTypeMismatch {
+            specifier: "%.1f".to_owned(),
+            value: Str("Alice".to_owned()),
+        }
+        .into()),
+        "SELECT FORMAT('%.1f', name) AS formatted FROM Member;"
+    );
+    test!(
+        Err(EvaluateError::FormatArgumentCountMismatch {
+            expected: 2,
+            found: 1,
+        }
+        .into()),
+        "SELECT FORMAT('%s is %d', name) AS formatted FROM Member;"
+    );
+    test!(
+        Err(EvaluateError::FormatArgumentCountMismatch {
+            expected: 0,
+            found: 1,
+        }
+        .into()),
+        "SELECT FORMAT('plain', name) AS formatted FROM Member;"
+    );
+    test!(
+        Err(EvaluateError::UnsupportedFormatSpecifier("%x".to_owned()).into()),
+        "SELECT FORMAT('%x', id) AS formatted FROM Member;"
+    );
+    test!(
+        Err(TranslateError::FunctionArgsLengthNotMatchingMin {
+            name: "FORMAT".to_owned(),
+            expected_minimum: 1,
+            found: 0,
+        }
+        .into()),
+        "SELECT FORMAT() AS formatted FROM Member;"
+    );
+});
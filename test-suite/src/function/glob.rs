@@ -0,0 +1,103 @@
+use {
+    crate::*,
+    gluesql_core::{
+        executor::EvaluateError,
+        prelude::{Payload, Value::*},
+        translate::TranslateError,
+    },
+};
+
+test_case!(glob, async move {
+    let test_cases = vec![
+        (
+            "CREATE TABLE Item (name TEXT)",
+            Ok(Payload::Create),
+        ),
+        (
+            "INSERT INTO Item VALUES ('report.txt'), ('Report.txt'), ('image.png'), ('file1.log'), ('fileA.log')",
+            Ok(Payload::Insert(5)),
+        ),
+        (
+            "SELECT name FROM Item WHERE GLOB(name, '*.txt')",
+            Ok(select!(
+                name
+                Str;
+                "report.txt".to_owned();
+                "Report.txt".to_owned()
+            )),
+        ),
+        (
+            "SELECT name FROM Item WHERE GLOB(name, 'r*')",
+            Ok(select!(name; Str; "report.txt".to_owned())),
+        ),
+        (
+            "SELECT name FROM Item WHERE GLOB(name, 'file?.log')",
+            Ok(select!(
+                name
+                Str;
+                "file1.log".to_owned();
+                "fileA.log".to_owned()
+            )),
+        ),
+        (
+            "SELECT name FROM Item WHERE GLOB(name, 'file[0-9].log')",
+            Ok(select!(name; Str; "file1.log".to_owned())),
+        ),
+        (
+            "SELECT name FROM Item WHERE GLOB(name, '[!rR]*')",
+            Ok(select!(
+                name
+                Str;
+                "image.png".to_owned();
+                "file1.log".to_owned();
+                "fileA.log".to_owned()
+            )),
+        ),
+        (
+            "SELECT name FROM Item WHERE GLOB(name, '[Rr]eport.???')",
+            Ok(select!(
+                name
+                Str;
+                "report.txt".to_owned();
+                "Report.txt".to_owned()
+            )),
+        ),
+        (
+            "CREATE TABLE NullTest (name TEXT NULL)",
+            Ok(Payload::Create),
+        ),
+        (
+            "INSERT INTO NullTest VALUES (NULL)",
+            Ok(Payload::Insert(1)),
+        ),
+        (
+            "SELECT GLOB(name, '*') AS test FROM NullTest",
+            Ok(select_with_null!(test; Null)),
+        ),
+        (
+            "SELECT GLOB('abc', NULL) AS test FROM Item WHERE name = 'image.png'",
+            Ok(select_with_null!(test; Null)),
+        ),
+        (
+            "SELECT GLOB('abc', 'a*') AS test FROM Item WHERE name = 'image.png'",
+            Ok(select!(test; Bool; true)),
+        ),
+        (
+            "SELECT GLOB('abc') AS test FROM Item WHERE name = 'image.png'",
+            Err(TranslateError::FunctionArgsLengthNotMatching {
+                name: "GLOB".to_owned(),
+                expected: 2,
+                found: 1,
+            }
+            .into()),
+        ),
+        (
+            "SELECT GLOB(1, 'a*') AS test FROM Item WHERE name = 'image.png'",
+            Err(EvaluateError::FunctionRequiresStringValue("GLOB".to_owned()).into()),
+        ),
+    ];
+
+    for (sql, expected) in test_cases {
+        test!(expected, sql);
+    }
+});
@@ -0,0 +1,98 @@
+use {
+    crate::*,
+    gluesql_core::{
+        executor::EvaluateError,
+        prelude::{Payload, Value::*},
+    },
+};
+
+test_case!(hex_base64, async move {
+    let bytea = |v| hex::decode(v).unwrap();
+
+    let test_cases = vec![
+        (
+            "CREATE TABLE Asset (id INTEGER, raw BYTEA NULL, name TEXT NULL)",
+            Ok(Payload::Create),
+        ),
+        (
+            "INSERT INTO Asset VALUES (1, X'00ff10', 'GlueSQL'), (2, NULL, NULL)",
+            Ok(Payload::Insert(2)),
+        ),
+        (
+            "SELECT HEX(name) AS text_hex, HEX(raw) AS raw_hex FROM Asset WHERE id = 1",
+            Ok(select!(
+                text_hex                        | raw_hex
+                Str                             | Str;
+                "476c756553514c".to_owned()       "00ff10".to_owned()
+            )),
+        ),
+        (
+            "SELECT UNHEX('476C756553514C') AS test FROM Asset WHERE id = 1",
+            Ok(select!(test Bytea; b"GlueSQL".to_vec())),
+        ),
+        (
+            "SELECT UNHEX(HEX(raw)) = raw AS test FROM Asset WHERE id = 1",
+            Ok(select!(test Bool; true)),
+        ),
+        (
+            "SELECT TO_BASE64(name) AS text_b64, TO_BASE64(raw) AS raw_b64 FROM Asset WHERE id = 1",
+            Ok(select!(
+                text_b64                  | raw_b64
+                Str                       | Str;
+                "R2x1ZVNRTA==".to_owned()   "AP8Q".to_owned()
+            )),
+        ),
+        (
+            "SELECT FROM_BASE64('AP8Q') AS test FROM Asset WHERE id = 1",
+            Ok(select!(test Bytea; bytea("00ff10"))),
+        ),
+        (
+            "SELECT FROM_BASE64(TO_BASE64(raw)) = raw AS test FROM Asset WHERE id = 1",
+            Ok(select!(test Bool; true)),
+        ),
+        (
+            "SELECT HEX(FROM_BASE64(TO_BASE64(UNHEX('cafe')))) AS test FROM Asset WHERE id = 1",
+            Ok(select!(test Str; "cafe".to_owned())),
+        ),
+        (
+            "SELECT
+                HEX(raw) AS a,
+                UNHEX(name) AS b,
+                TO_BASE64(raw) AS c,
+                FROM_BASE64(name) AS d
+            FROM Asset WHERE id = 2",
+            Ok(select_with_null!(
+                a    | b    | c    | d;
+                Null   Null   Null   Null
+            )),
+        ),
+        (
+            "SELECT UNHEX('abc') FROM Asset WHERE id = 1",
+            Err(EvaluateError::InvalidHexString("abc".to_owned()).into()),
+        ),
+        (
+            "SELECT UNHEX('zz') FROM Asset WHERE id = 1",
+            Err(EvaluateError::InvalidHexString("zz".to_owned()).into()),
+        ),
+        (
+            "SELECT FROM_BASE64('R2x1ZVNRTA=') FROM Asset WHERE id = 1",
+            Err(EvaluateError::InvalidBase64String("R2x1ZVNRTA=".to_owned()).into()),
+        ),
+        (
+            "SELECT FROM_BASE64('not base64!') FROM Asset WHERE id = 1",
+            Err(EvaluateError::InvalidBase64String("not base64!".to_owned()).into()),
+        ),
+        (
+            "SELECT HEX(1) FROM Asset WHERE id = 1",
+            Err(EvaluateError::FunctionRequiresStringOrByteaValue("HEX".to_owned()).into()),
+        ),
+        (
+            "SELECT UNHEX(raw) FROM Asset WHERE id = 1",
+            Err(EvaluateError::FunctionRequiresStringValue("UNHEX".to_owned()).into()),
+        ),
+    ];
+
+    for (sql, expected) in test_cases {
+        test!(expected, sql);
+    }
+});
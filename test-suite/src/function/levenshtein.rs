@@ -0,0 +1,105 @@
+use {
+    crate::*,
+    gluesql_core::{
+        executor::EvaluateError,
+        prelude::{Payload, Value::*},
+        translate::TranslateError,
+    },
+};
+
+test_case!(levenshtein, async move {
+    let test_cases = vec![
+        ("CREATE TABLE Word (word TEXT NULL)", Ok(Payload::Create)),
+        (
+            "INSERT INTO Word VALUES ('kitten'), ('sitting'), ('flaw'), (NULL)",
+            Ok(Payload::Insert(4)),
+        ),
+        (
+            "SELECT word, LEVENSHTEIN(word, 'sitting') AS distance FROM Word",
+            Ok(select_with_null!(
+                word                     | distance;
+                Str("kitten".to_owned())   I64(3);
+                Str("sitting".to_owned())  I64(0);
+                Str("flaw".to_owned())     I64(7);
+                Null                       Null
+            )),
+        ),
+        (
+            "SELECT word, LEVENSHTEIN(word, 'sitting', 3) AS distance FROM Word",
+            Ok(select_with_null!(
+                word                     | distance;
+                Str("kitten".to_owned())   I64(3);
+                Str("sitting".to_owned())  I64(0);
+                Str("flaw".to_owned())     I64(4);
+                Null                       Null
+            )),
+        ),
+        (
+            "SELECT word FROM Word WHERE LEVENSHTEIN(word, 'kiten', 1) <= 1",
+            Ok(select!(word; Str; "kitten".to_owned())),
+        ),
+        (
+            "SELECT
+                LEVENSHTEIN('café', 'cafe') AS accent,
+                LEVENSHTEIN('한글', '한국어') AS hangul,
+                LEVENSHTEIN('', 'abc') AS empty
+            FROM Word WHERE word = 'flaw'",
+            Ok(select!(
+                accent | hangul | empty
+                I64    | I64    | I64;
+                1        2        3
+            )),
+        ),
+        (
+            "SELECT
+                SIMILARITY('kitten', 'sitting') AS typo,
+                SIMILARITY('abc', 'abc') AS same,
+                SIMILARITY('abc', 'xyz') AS different,
+                SIMILARITY('', '') AS empty
+            FROM Word WHERE word = 'flaw'",
+            Ok(select!(
+                typo              | same | different | empty
+                F64               | F64  | F64       | F64;
+                1.0 - 3.0 / 7.0     1.0    0.0         1.0
+            )),
+        ),
+        (
+            "SELECT
+                SIMILARITY(word, NULL) AS similarity,
+                LEVENSHTEIN('a', 'b', NULL) AS distance
+            FROM Word WHERE word = 'flaw'",
+            Ok(select_with_null!(similarity | distance; Null Null)),
+        ),
+        (
+            "SELECT LEVENSHTEIN('abc') AS test FROM Word",
+            Err(TranslateError::FunctionArgsLengthNotWithinRange {
+                name: "LEVENSHTEIN".to_owned(),
+                expected_minimum: 2,
+                expected_maximum: 3,
+                found: 1,
+            }
+            .into()),
+        ),
+        (
+            "SELECT SIMILARITY('a', 'b', 1) AS test FROM Word",
+            Err(TranslateError::FunctionArgsLengthNotMatching {
+                name: "SIMILARITY".to_owned(),
+                expected: 2,
+                found: 3,
+            }
+            .into()),
+        ),
+        (
+            "SELECT LEVENSHTEIN(1, 'a') AS test FROM Word",
+            Err(EvaluateError::FunctionRequiresStringValue("LEVENSHTEIN".to_owned()).into()),
+        ),
+        (
+            "SELECT LEVENSHTEIN('a', 'b', -1) AS test FROM Word",
+            Err(EvaluateError::FunctionRequiresUSizeValue("LEVENSHTEIN".to_owned()).into()),
+        ),
+    ];
+
+    for (sql, expected) in test_cases {
+        test!(expected, sql);
+    }
+});
@@ -1,26 +1,35 @@
 pub mod abs;
+pub mod ascii_chr;
 pub mod cast;
 pub mod ceil;
 pub mod concat;
+pub mod create_function;
+pub mod custom;
 pub mod degrees;
 pub mod div_mod;
 pub mod exp_log;
 pub mod extract;
 pub mod floor;
+pub mod format;
 pub mod gcd_lcm;
 pub mod generate_uuid;
+pub mod glob;
+pub mod hex_base64;
 pub mod ifnull;
 pub mod left_right;
+pub mod levenshtein;
 pub mod lpad_rpad;
 pub mod ltrim_rtrim;
 pub mod math_function;
 pub mod now;
 pub mod pi;
 pub mod radians;
+pub mod random;
 pub mod repeat;
 pub mod reverse;
 pub mod round;
 pub mod sign;
+pub mod soundex;
 pub mod sqrt_power;
 pub mod substr;
 pub mod trim;
@@ -0,0 +1,64 @@
+use {
+    crate::*,
+    gluesql_core::{
+        executor::EvaluateError,
+        prelude::{Payload, Value::*},
+        translate::TranslateError,
+    },
+};
+
+test_case!(random, async move {
+    run!("CREATE TABLE Dice (id INTEGER);");
+    run!("INSERT INTO Dice VALUES (1), (2), (3), (4), (5), (6), (7), (8);");
+
+    let sql = "SELECT RANDOM() AS ratio, RANDOM(1, 6) AS face FROM Dice";
+
+    session!().set_random_seed(Some(42));
+    let first = run!(sql);
+    session!().set_random_seed(Some(42));
+    let second = run!(sql);
+
+    assert_eq!(first, second);
+
+    let rows = match first {
+        Payload::Select { rows, .. } => rows,
+        _ => panic!("unexpected payload: {:?}", first),
+    };
+    for row in rows.iter() {
+        match row.as_slice() {
+            [F64(ratio), I64(face)] => {
+                assert!((0.0..1.0).contains(ratio), "ratio: {ratio}");
+                assert!((1..=6).contains(face), "face: {face}");
+            }
+            _ => panic!("unexpected row: {:?}", row),
+        }
+    }
+    // evaluated per row, not once per statement
+    assert!(rows.iter().any(|row| row[0] != rows[0][0]));
+
+    test!(
+        Ok(select!(face; I64; 3)),
+        "SELECT RANDOM(3, 3) AS face FROM Dice WHERE id = 1"
+    );
+    test!(
+        Ok(select_with_null!(face; Null)),
+        "SELECT RANDOM(NULL, 6) AS face FROM Dice WHERE id = 1"
+    );
+    test!(
+        Err(EvaluateError::InvalidRandomRange { min: 6, max: 1 }.into()),
+        "SELECT RANDOM(6, 1) FROM Dice"
+    );
+    test!(
+        Err(EvaluateError::FunctionRequiresIntegerValue("RANDOM".to_owned()).into()),
+        "SELECT RANDOM(1, 'six') FROM Dice"
+    );
+    test!(
+        Err(TranslateError::FunctionArgsLengthNotMatching {
+            name: "RANDOM".to_owned(),
+            expected: 2,
+            found: 1,
+        }
+        .into()),
+        "SELECT RANDOM(1) FROM Dice"
+    );
+});
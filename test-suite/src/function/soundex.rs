@@ -0,0 +1,59 @@
+use {
+    crate::*,
+    gluesql_core::{
+        executor::EvaluateError,
+        prelude::{Payload, Value::*},
+    },
+};
+
+test_case!(soundex, async move {
+    let test_cases = vec![
+        (
+            "CREATE TABLE Author (name TEXT NULL)",
+            Ok(Payload::Create),
+        ),
+        (
+            "INSERT INTO Author VALUES ('Robert'), ('Rupert'), ('Ashcraft'), ('Müller'), (''), (NULL)",
+            Ok(Payload::Insert(6)),
+        ),
+        (
+            "SELECT name, SOUNDEX(name) AS code FROM Author",
+            Ok(select_with_null!(
+                name                      | code;
+                Str("Robert".to_owned())    Str("R163".to_owned());
+                Str("Rupert".to_owned())    Str("R163".to_owned());
+                Str("Ashcraft".to_owned())  Str("A261".to_owned());
+                Str("Müller".to_owned())    Str("M460".to_owned());
+                Str("".to_owned())          Str("".to_owned());
+                Null                        Null
+            )),
+        ),
+        (
+            "CREATE TABLE Signer (name TEXT)",
+            Ok(Payload::Create),
+        ),
+        (
+            "INSERT INTO Signer VALUES ('Rubert'), ('Muller'), ('Tymczak')",
+            Ok(Payload::Insert(3)),
+        ),
+        (
+            "SELECT Author.name AS author, Signer.name AS signer
+            FROM Author JOIN Signer ON SOUNDEX(Author.name) = SOUNDEX(Signer.name)",
+            Ok(select!(
+                author                | signer
+                Str                   | Str;
+                "Robert".to_owned()     "Rubert".to_owned();
+                "Rupert".to_owned()     "Rubert".to_owned();
+                "Müller".to_owned()     "Muller".to_owned()
+            )),
+        ),
+        (
+            "SELECT SOUNDEX(1) AS code FROM Signer",
+            Err(EvaluateError::FunctionRequiresStringValue("SOUNDEX".to_owned()).into()),
+        ),
+    ];
+
+    for (sql, expected) in test_cases {
+        test!(expected, sql);
+    }
+});
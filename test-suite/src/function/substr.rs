@@ -129,6 +129,74 @@ test_case!(substr, async move {
             r#"SELECT SUBSTR("ABC", -1, NULL) AS test FROM SingleItem"#,
             Ok(select_with_null!(test; Null)),
         ),
+        (
+            r#"SELECT SUBSTRING('ABCDE' FROM 2 FOR 3) AS test FROM SingleItem"#,
+            Ok(select!(
+                "test"
+                Str;
+                "BCD".to_owned()
+            )),
+        ),
+        (
+            r#"SELECT SUBSTRING('ABCDE' FROM 3) AS test FROM SingleItem"#,
+            Ok(select!(
+                "test"
+                Str;
+                "CDE".to_owned()
+            )),
+        ),
+        (
+            r#"SELECT SUBSTRING('ABCDE', 2, 3) AS test FROM SingleItem"#,
+            Ok(select!(
+                "test"
+                Str;
+                "BCD".to_owned()
+            )),
+        ),
+        (
+            r#"SELECT SUBSTRING('ABCDE' FROM 0 FOR 2) AS test FROM SingleItem"#,
+            Ok(select!(
+                "test"
+                Str;
+                "A".to_owned()
+            )),
+        ),
+        (
+            r#"SELECT SUBSTRING('ABCDE' FROM -2 FOR 5) AS test FROM SingleItem"#,
+            Ok(select!(
+                "test"
+                Str;
+                "AB".to_owned()
+            )),
+        ),
+        (
+            r#"SELECT SUBSTRING('ABCDE' FROM 4 FOR 100) AS test FROM SingleItem"#,
+            Ok(select!(
+                "test"
+                Str;
+                "DE".to_owned()
+            )),
+        ),
+        (
+            r#"SELECT SUBSTRING('가나다라' FROM 2 FOR 2) AS test FROM SingleItem"#,
+            Ok(select!(
+                "test"
+                Str;
+                "나다".to_owned()
+            )),
+        ),
+        (
+            r#"SELECT SUBSTR('héllo wörld', 8) AS test FROM SingleItem"#,
+            Ok(select!(
+                "test"
+                Str;
+                "örld".to_owned()
+            )),
+        ),
+        (
+            r#"SELECT SUBSTRING(name FROM 2 FOR NULL) AS test FROM Item"#,
+            Ok(select_with_null!(test; Null; Null; Null)),
+        ),
         (
             r#"SELECT SUBSTR(name, 3) AS test FROM NullName"#,
             Ok(select_with_null!(test; Null)),
@@ -119,6 +119,54 @@ test_case!(trim, async move {
                 "xxxyzblankxyz  ".to_owned()
             )),
         ),
+        ("CREATE TABLE Code (code TEXT)", Ok(Payload::Create)),
+        (
+            r#"INSERT INTO Code VALUES ("000120"), ("0"), ("abc");"#,
+            Ok(Payload::Insert(3)),
+        ),
+        (
+            "SELECT TRIM(LEADING '0' FROM code) AS test FROM Code WHERE code LIKE '0%';",
+            Ok(select!(
+                test
+                Value::Str;
+                "120".to_owned();
+                "".to_owned()
+            )),
+        ),
+        (
+            "SELECT TRIM(TRAILING '0' FROM code) AS test FROM Code WHERE code LIKE '0%';",
+            Ok(select!(
+                test
+                Value::Str;
+                "00012".to_owned();
+                "".to_owned()
+            )),
+        ),
+        (
+            "SELECT TRIM(BOTH '0' FROM code) AS test FROM Code WHERE code LIKE '0%';",
+            Ok(select!(
+                test
+                Value::Str;
+                "12".to_owned();
+                "".to_owned()
+            )),
+        ),
+        (
+            "SELECT TRIM(BOTH 'ca' FROM code) AS test FROM Code WHERE code = 'abc';",
+            Ok(select!(
+                test
+                Value::Str;
+                "b".to_owned()
+            )),
+        ),
+        (
+            "SELECT TRIM(' \t\n 12 \n\t ') AS test FROM Code WHERE code = 'abc';",
+            Ok(select!(
+                test
+                Value::Str;
+                "12".to_owned()
+            )),
+        ),
     ];
 
     for (sql, expected) in test_cases {
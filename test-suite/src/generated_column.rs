@@ -0,0 +1,97 @@
+use {
+    crate::*,
+    gluesql_core::{
+        data::RowError,
+        executor::AlterError,
+        prelude::{Payload, Value::*},
+        translate::TranslateError,
+    },
+};
+
+test_case!(generated_column, async move {
+    run!(
+        "
+        CREATE TABLE Person (
+            id INTEGER,
+            first TEXT,
+            last TEXT NULL,
+            full_name TEXT NULL GENERATED ALWAYS AS (first || ' ' || last) STORED
+        )
+    "
+    );
+
+    test!(
+        Ok(Payload::Insert(2)),
+        "INSERT INTO Person (id, first, last) VALUES (1, 'Ada', 'Lovelace'), (2, 'Alan', 'Turing')"
+    );
+    test!(
+        Ok(Payload::Insert(1)),
+        "INSERT INTO Person VALUES (3, 'Grace', 'Hopper')"
+    );
+    test!(
+        Ok(Payload::Insert(1)),
+        "INSERT INTO Person (id, first) VALUES (4, 'Plato')"
+    );
+    test!(
+        Ok(Payload::Insert(1)),
+        "INSERT INTO Person (id, first, last) SELECT 5, last, first FROM Person WHERE id = 2"
+    );
+    test!(
+        Ok(select_with_null!(
+            id     | full_name;
+            I64(1)   Str("Ada Lovelace".to_owned());
+            I64(2)   Str("Alan Turing".to_owned());
+            I64(3)   Str("Grace Hopper".to_owned());
+            I64(4)   Null;
+            I64(5)   Str("Turing Alan".to_owned())
+        )),
+        "SELECT id, full_name FROM Person"
+    );
+
+    test!(
+        Ok(Payload::Update(1)),
+        "UPDATE Person SET last = 'King' WHERE id = 1"
+    );
+    test!(
+        Ok(select!(full_name Str; "Ada King".to_owned())),
+        "SELECT full_name FROM Person WHERE id = 1"
+    );
+
+    // generated columns can not be written directly
+    test!(
+        Err(RowError::WriteToGeneratedColumn("full_name".to_owned()).into()),
+        "INSERT INTO Person VALUES (6, 'Edsger', 'Dijkstra', 'E. Dijkstra')"
+    );
+    test!(
+        Err(RowError::WriteToGeneratedColumn("full_name".to_owned()).into()),
+        "INSERT INTO Person (id, first, full_name) VALUES (6, 'Edsger', 'Dijkstra')"
+    );
+    test!(
+        Err(RowError::WriteToGeneratedColumn("full_name".to_owned()).into()),
+        "INSERT INTO Person SELECT 6, first, last, full_name FROM Person WHERE id = 3"
+    );
+    test!(
+        Err(RowError::WriteToGeneratedColumn("full_name".to_owned()).into()),
+        "UPDATE Person SET full_name = 'Alan M. Turing' WHERE id = 2"
+    );
+
+    // the expression reads nothing but the other columns of the row
+    test!(
+        Err(AlterError::UnsupportedGeneratedColumnExpr("b".to_owned(), expr!("a + c")).into()),
+        "CREATE TABLE Derived (a INTEGER, b INTEGER GENERATED ALWAYS AS (a + c))"
+    );
+    test!(
+        Err(AlterError::UnsupportedGeneratedColumnExpr("c".to_owned(), expr!("b * 2")).into()),
+        "CREATE TABLE Derived (
+            a INTEGER,
+            b INTEGER GENERATED ALWAYS AS (a + 1),
+            c INTEGER GENERATED ALWAYS AS (b * 2)
+        )"
+    );
+
+    // a CHECK constraint of the same name is not a generated column
+    test!(
+        Err(TranslateError::UnsupportedColumnOption("CHECK (a > 0)".to_owned()).into()),
+        r#"CREATE TABLE Checked (a INTEGER CONSTRAINT "GENERATED ALWAYS" CHECK (a > 0))"#
+    );
+});
@@ -286,8 +286,8 @@ CREATE TABLE Test (
     );
 
     test!(
-        Err(TranslateError::CompositeIndexNotSupported.into()),
-        "CREATE INDEX idx_com ON Test (id, num)"
+        Err(AlterError::UnsupportedIndexExpr(expr!("a.b")).into()),
+        "CREATE INDEX idx_com ON Test (id, a.b)"
     );
 
     test!(
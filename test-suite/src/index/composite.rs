@@ -0,0 +1,109 @@
+use {
+    crate::*,
+    gluesql_core::prelude::{Payload, Value::*},
+};
+
+test_case!(composite, async move {
+    run!(
+        r#"
+CREATE TABLE Composite (
+    id INTEGER,
+    a INTEGER,
+    b INTEGER,
+    name TEXT
+)"#
+    );
+
+    run!(
+        r#"
+        INSERT INTO Composite
+            (id, a, b, name)
+        VALUES
+            (1, 1, 1, "ab"),
+            (2, 1, 2, "abc"),
+            (3, 1, 2, "ab"),
+            (4, 2, 2, "a");
+    "#
+    );
+
+    test!(
+        Ok(Payload::CreateIndex),
+        "CREATE INDEX idx_ab ON Composite (a, b)"
+    );
+
+    run!(r#"INSERT INTO Composite VALUES (5, 2, 1, "abc")"#);
+
+    test!(
+        Ok(Payload::CreateIndex),
+        "CREATE INDEX idx_name_a ON Composite (name, a)"
+    );
+
+    // every column of the index
+    test_idx!(
+        Ok(select!(id I64; 2; 3)),
+        idx!(idx_ab, PREFIX, "1", "2"),
+        "SELECT id FROM Composite WHERE a = 1 AND b = 2 ORDER BY id"
+    );
+
+    test_idx!(
+        Ok(select!(id I64; 2; 3)),
+        idx!(idx_ab, PREFIX, "1", "2"),
+        "SELECT id FROM Composite WHERE 2 = b AND a = 1 ORDER BY id"
+    );
+
+    // the leading columns of the index
+    test_idx!(
+        Ok(select!(id I64; 1; 2; 3)),
+        idx!(idx_ab, PREFIX, "1"),
+        "SELECT id FROM Composite WHERE a = 1 ORDER BY id"
+    );
+
+    test_idx!(
+        Ok(select!(id I64; 4)),
+        idx!(idx_ab, PREFIX, "2"),
+        "SELECT id FROM Composite WHERE a = 2 AND b > 1 ORDER BY id"
+    );
+
+    // the index which matches more columns
+    test_idx!(
+        Ok(select!(id I64; 1; 3)),
+        idx!(idx_name_a, PREFIX, "'ab'", "1"),
+        "SELECT id FROM Composite WHERE a = 1 AND name = 'ab' ORDER BY id"
+    );
+
+    // a text value does not match the longer ones it prefixes
+    test_idx!(
+        Ok(select!(id I64; 1; 3)),
+        idx!(idx_name_a, PREFIX, "'ab'"),
+        "SELECT id FROM Composite WHERE name = 'ab' ORDER BY id"
+    );
+
+    // no leading column
+    test_idx!(
+        Ok(select!(id I64; 2; 3; 4)),
+        idx!(),
+        "SELECT id FROM Composite WHERE b = 2 ORDER BY id"
+    );
+
+    test_idx!(
+        Ok(select!(id I64; 1; 2; 3; 5)),
+        idx!(),
+        "SELECT id FROM Composite WHERE a = 1 OR b = 1 ORDER BY id"
+    );
+
+    // the index follows the changed rows
+    run!("UPDATE Composite SET b = 3 WHERE id = 1");
+    run!("DELETE FROM Composite WHERE id = 3");
+
+    test_idx!(
+        Ok(select!(id I64; 1)),
+        idx!(idx_ab, PREFIX, "1", "3"),
+        "SELECT id FROM Composite WHERE a = 1 AND b = 3 ORDER BY id"
+    );
+
+    test_idx!(
+        Ok(select!(id I64; 2)),
+        idx!(idx_ab, PREFIX, "1", "2"),
+        "SELECT id FROM Composite WHERE a = 1 AND b = 2 ORDER BY id"
+    );
+});
@@ -0,0 +1,96 @@
+use {crate::*, gluesql_core::prelude::*};
+
+test_case!(drop_index, async move {
+    use gluesql_core::{
+        ast::{Expr, IndexOperator::*},
+        data::{SchemaIndex, SchemaIndexOrd},
+    };
+
+    run!(
+        r#"
+CREATE TABLE DropIdx (
+    id INTEGER,
+    name TEXT
+)"#
+    );
+
+    run!(
+        r#"
+        INSERT INTO DropIdx
+            (id, name)
+        VALUES
+            (1, "a"),
+            (2, "b"),
+            (2, "c"),
+            (4, "d");
+    "#
+    );
+
+    test!(
+        Ok(Payload::CreateIndex),
+        "CREATE INDEX idx_id ON DropIdx (id)"
+    );
+    test!(
+        Ok(Payload::CreateIndex),
+        "CREATE INDEX idx_name ON DropIdx (name)"
+    );
+
+    test_idx!(
+        Ok(select!(
+            id  | name
+            I64 | Str;
+            2     "b".to_owned();
+            2     "c".to_owned()
+        )),
+        idx!(idx_id, Eq, "2"),
+        "SELECT * FROM DropIdx WHERE id = 2"
+    );
+
+    test!(Ok(Payload::DropIndex), "DROP INDEX DropIdx.idx_id");
+
+    test!(
+        Ok(Payload::ShowIndexes(vec![SchemaIndex {
+            name: "idx_name".to_owned(),
+            order: SchemaIndexOrd::Both,
+            expr: Expr::Identifier("name".to_owned())
+        }])),
+        "SHOW INDEXES FROM DropIdx"
+    );
+
+    test_idx!(
+        Ok(select!(
+            id  | name
+            I64 | Str;
+            2     "b".to_owned();
+            2     "c".to_owned()
+        )),
+        idx!(),
+        "SELECT * FROM DropIdx WHERE id = 2"
+    );
+
+    test_idx!(
+        Ok(select!(
+            id  | name
+            I64 | Str;
+            4     "d".to_owned()
+        )),
+        idx!(),
+        "SELECT * FROM DropIdx WHERE id > 2"
+    );
+
+    test!(Ok(Payload::DropIndex), "DROP INDEX DropIdx.idx_name");
+    test!(
+        Ok(Payload::ShowIndexes(vec![])),
+        "SHOW INDEXES FROM DropIdx"
+    );
+
+    test_idx!(
+        Ok(select!(
+            id  | name
+            I64 | Str;
+            2     "c".to_owned()
+        )),
+        idx!(),
+        r#"SELECT * FROM DropIdx WHERE name = "c""#
+    );
+});
@@ -0,0 +1,87 @@
+use {
+    crate::*,
+    gluesql_core::{executor::EvaluateError, prelude::*},
+};
+
+test_case!(keyset, async move {
+    use gluesql_core::ast::IndexOperator::*;
+
+    run!(
+        r#"
+CREATE TABLE Item (
+    a INTEGER,
+    b INTEGER,
+    raw INTEGER,
+    name TEXT
+)"#
+    );
+
+    run!(
+        r#"
+        INSERT INTO Item
+            (a, b, raw, name)
+        VALUES
+            (1, 1, 1, "a"),
+            (1, 2, 1, "b"),
+            (2, 1, 2, "c"),
+            (2, 2, 2, "d"),
+            (2, 3, 2, "e"),
+            (3, 1, 3, "f"),
+            (4, 1, 4, "g"),
+            (4, 2, 4, "h");
+    "#
+    );
+
+    test!(Ok(Payload::CreateIndex), "CREATE INDEX idx_a ON Item (a)");
+
+    // every keyset page must match the OFFSET page it replaces
+    let test_cases = vec![
+        (
+            idx!(idx_a, GtEq, "1"),
+            "SELECT name FROM Item WHERE (a, b) > (1, 2) ORDER BY a, b LIMIT 3",
+            "SELECT name FROM Item ORDER BY a, b LIMIT 3 OFFSET 2",
+        ),
+        (
+            idx!(idx_a, GtEq, "2"),
+            "SELECT name FROM Item WHERE (a, b) > (2, 3) ORDER BY a, b LIMIT 3",
+            "SELECT name FROM Item ORDER BY a, b LIMIT 3 OFFSET 5",
+        ),
+        (
+            idx!(idx_a, GtEq, "2"),
+            "SELECT name FROM Item WHERE (2, 3) <= (a, b) ORDER BY a, b LIMIT 3",
+            "SELECT name FROM Item ORDER BY a, b LIMIT 3 OFFSET 4",
+        ),
+        (
+            idx!(idx_a, LtEq, "4"),
+            "SELECT name FROM Item WHERE (a, b) < (4, 1) ORDER BY a DESC, b DESC LIMIT 3",
+            "SELECT name FROM Item ORDER BY a DESC, b DESC LIMIT 3 OFFSET 2",
+        ),
+        (
+            idx!(),
+            "SELECT name FROM Item WHERE (raw, b) > (1, 2) ORDER BY raw, b LIMIT 3",
+            "SELECT name FROM Item ORDER BY raw, b LIMIT 3 OFFSET 2",
+        ),
+    ];
+
+    for (indexes, keyset_sql, offset_sql) in test_cases {
+        let expected = run!(offset_sql);
+
+        test_idx!(Ok(expected), indexes, keyset_sql);
+    }
+
+    test_idx!(
+        Ok(select!(
+            name
+            Str;
+            "e".to_owned();
+            "f".to_owned()
+        )),
+        idx!(idx_a, GtEq, "2"),
+        "SELECT name FROM Item WHERE (a, b) >= (2, 3) ORDER BY a, b LIMIT 2"
+    );
+
+    test!(
+        Err(EvaluateError::TupleArityMismatch { left: 2, right: 3 }.into()),
+        "SELECT name FROM Item WHERE (a, b) > (1, 2, 3)"
+    );
+});
@@ -0,0 +1,124 @@
+use {
+    crate::*,
+    gluesql_core::prelude::*,
+    std::ops::Bound::{Excluded, Included},
+};
+
+test_case!(like, async move {
+    run!(
+        r#"
+CREATE TABLE IdxLike (
+    id INTEGER,
+    name TEXT,
+    raw TEXT
+)"#
+    );
+
+    run!(
+        r#"
+        INSERT INTO IdxLike
+            (id, name, raw)
+        VALUES
+            (1, "john", "john"),
+            (2, "joi", "joi"),
+            (3, "johnny", "johnny"),
+            (4, "joh", "joh"),
+            (5, "Johan", "Johan"),
+            (6, "ajohn", "ajohn"),
+            (7, "jo", "jo"),
+            (8, "jon", "jon");
+    "#
+    );
+
+    test!(
+        Ok(Payload::CreateIndex),
+        "CREATE INDEX idx_name ON IdxLike (name)"
+    );
+
+    // `raw` holds the same values as `name` but has no index, so it is always scanned.
+    let test_cases = vec![
+        (
+            idx!(
+                idx_name,
+                RANGE,
+                Included(expr("'joh'")),
+                Excluded(expr("'joi'"))
+            ),
+            "SELECT id FROM IdxLike WHERE name LIKE 'joh%' ORDER BY id",
+            "SELECT id FROM IdxLike WHERE raw LIKE 'joh%' ORDER BY id",
+        ),
+        (
+            idx!(
+                idx_name,
+                RANGE,
+                Included(expr("'jo'")),
+                Excluded(expr("'jp'"))
+            ),
+            "SELECT id FROM IdxLike WHERE name LIKE 'jo_n%' ORDER BY id",
+            "SELECT id FROM IdxLike WHERE raw LIKE 'jo_n%' ORDER BY id",
+        ),
+        (
+            idx!(
+                idx_name,
+                RANGE,
+                Included(expr("'john'")),
+                Excluded(expr("'joho'"))
+            ),
+            "SELECT id FROM IdxLike WHERE name LIKE 'john' ORDER BY id",
+            "SELECT id FROM IdxLike WHERE raw LIKE 'john' ORDER BY id",
+        ),
+        (
+            idx!(
+                idx_name,
+                RANGE,
+                Included(expr("'joh'")),
+                Excluded(expr("'joi'"))
+            ),
+            "SELECT id FROM IdxLike WHERE name LIKE 'joh%' AND id > 1 ORDER BY id",
+            "SELECT id FROM IdxLike WHERE raw LIKE 'joh%' AND id > 1 ORDER BY id",
+        ),
+        (
+            idx!(),
+            "SELECT id FROM IdxLike WHERE name LIKE '%ohn' ORDER BY id",
+            "SELECT id FROM IdxLike WHERE raw LIKE '%ohn' ORDER BY id",
+        ),
+        (
+            idx!(),
+            "SELECT id FROM IdxLike WHERE name LIKE '_ohn%' ORDER BY id",
+            "SELECT id FROM IdxLike WHERE raw LIKE '_ohn%' ORDER BY id",
+        ),
+        (
+            idx!(),
+            "SELECT id FROM IdxLike WHERE name ILIKE 'joh%' ORDER BY id",
+            "SELECT id FROM IdxLike WHERE raw ILIKE 'joh%' ORDER BY id",
+        ),
+        (
+            idx!(),
+            "SELECT id FROM IdxLike WHERE name NOT LIKE 'joh%' ORDER BY id",
+            "SELECT id FROM IdxLike WHERE raw NOT LIKE 'joh%' ORDER BY id",
+        ),
+    ];
+
+    for (indexes, indexed_sql, scan_sql) in test_cases {
+        let expected = run!(scan_sql);
+
+        test_idx!(Ok(expected), indexes, indexed_sql);
+    }
+
+    test_idx!(
+        Ok(select!(id I64; 1; 3; 4)),
+        idx!(
+            idx_name,
+            RANGE,
+            Included(expr("'joh'")),
+            Excluded(expr("'joi'"))
+        ),
+        "SELECT id FROM IdxLike WHERE name LIKE 'joh%' ORDER BY id"
+    );
+
+    test_idx!(
+        Ok(select!(id I64; 1; 6)),
+        idx!(),
+        "SELECT id FROM IdxLike WHERE name LIKE '%ohn' ORDER BY id"
+    );
+});
@@ -0,0 +1,125 @@
+use {crate::*, gluesql_core::prelude::*, std::ops::Bound::Excluded};
+
+test_case!(lookup, async move {
+    use gluesql_core::ast::IndexOperator::*;
+
+    run!(
+        r#"
+CREATE TABLE Lookup (
+    id INTEGER,
+    raw INTEGER,
+    name TEXT
+)"#
+    );
+
+    run!(
+        r#"
+        INSERT INTO Lookup
+            (id, raw, name)
+        VALUES
+            (1, 1, "a"),
+            (2, 2, "b"),
+            (3, 3, "c"),
+            (3, 3, "d"),
+            (5, 5, "e"),
+            (8, 8, "f");
+    "#
+    );
+
+    test!(
+        Ok(Payload::CreateIndex),
+        "CREATE INDEX idx_id ON Lookup (id)"
+    );
+
+    // `raw` holds the same values as `id` but has no index, so it is always scanned.
+    let test_cases = vec![
+        (
+            idx!(idx_id, Eq, "3"),
+            "SELECT name FROM Lookup WHERE id = 3",
+            "SELECT name FROM Lookup WHERE raw = 3",
+        ),
+        (
+            idx!(idx_id, Eq, "4"),
+            "SELECT name FROM Lookup WHERE id = 4",
+            "SELECT name FROM Lookup WHERE raw = 4",
+        ),
+        (
+            idx!(idx_id, Gt, "3"),
+            "SELECT name FROM Lookup WHERE id > 3",
+            "SELECT name FROM Lookup WHERE raw > 3",
+        ),
+        (
+            idx!(idx_id, GtEq, "3"),
+            "SELECT name FROM Lookup WHERE id >= 3",
+            "SELECT name FROM Lookup WHERE raw >= 3",
+        ),
+        (
+            idx!(idx_id, Lt, "3"),
+            "SELECT name FROM Lookup WHERE 3 > id",
+            "SELECT name FROM Lookup WHERE 3 > raw",
+        ),
+        (
+            idx!(idx_id, LtEq, "5"),
+            "SELECT name FROM Lookup WHERE id <= 5",
+            "SELECT name FROM Lookup WHERE raw <= 5",
+        ),
+        (
+            idx!(idx_id, RANGE, Excluded(expr("1")), Excluded(expr("5"))),
+            "SELECT name FROM Lookup WHERE id > 1 AND id < 5",
+            "SELECT name FROM Lookup WHERE raw > 1 AND raw < 5",
+        ),
+        (
+            idx!(idx_id, IN, "1", "3", "4"),
+            "SELECT name FROM Lookup WHERE id = 1 OR 3 = id OR id = 4",
+            "SELECT name FROM Lookup WHERE raw = 1 OR 3 = raw OR raw = 4",
+        ),
+        (
+            idx!(idx_id, IN, "8", "1", "8"),
+            "SELECT name FROM Lookup WHERE id IN (8, 1, 8) ORDER BY name",
+            "SELECT name FROM Lookup WHERE raw IN (8, 1, 8) ORDER BY name",
+        ),
+        (
+            idx!(idx_id, IN, "3", "5"),
+            "SELECT name FROM Lookup WHERE (id = 3 OR id = 5) AND name <> 'd'",
+            "SELECT name FROM Lookup WHERE (raw = 3 OR raw = 5) AND name <> 'd'",
+        ),
+        (
+            idx!(),
+            "SELECT name FROM Lookup WHERE id = 1 OR raw = 3",
+            "SELECT name FROM Lookup WHERE raw = 1 OR raw = 3",
+        ),
+        (
+            idx!(),
+            "SELECT name FROM Lookup WHERE id NOT IN (1, 3)",
+            "SELECT name FROM Lookup WHERE raw NOT IN (1, 3)",
+        ),
+    ];
+
+    for (indexes, indexed_sql, scan_sql) in test_cases {
+        let expected = run!(scan_sql);
+
+        test_idx!(Ok(expected), indexes, indexed_sql);
+    }
+
+    test_idx!(
+        Ok(select!(
+            name
+            Str;
+            "c".to_owned();
+            "d".to_owned()
+        )),
+        idx!(idx_id, Eq, "3"),
+        "SELECT name FROM Lookup WHERE id = 3"
+    );
+
+    test_idx!(
+        Ok(select!(
+            name
+            Str;
+            "c".to_owned();
+            "d".to_owned()
+        )),
+        idx!(),
+        "SELECT name FROM Lookup WHERE raw = 3"
+    );
+});
@@ -2,19 +2,32 @@
 
 mod and;
 mod basic;
+mod composite;
+mod drop;
 mod expr;
+mod keyset;
+mod like;
+mod lookup;
 mod nested;
 mod null;
 mod order_by;
+mod range;
 mod showindexes;
 mod value;
 
 pub use and::and;
 pub use basic::basic;
+pub use composite::composite;
+pub use drop::drop_index;
 pub use expr::expr;
+pub use keyset::keyset;
+pub use like::like;
+pub use lookup::lookup;
 pub use nested::nested;
 pub use null::null;
 pub use order_by::order_by;
 pub use order_by::order_by_multi;
+pub use order_by::order_by_scan;
+pub use range::range;
 pub use showindexes::showindexes;
 pub use value::value;
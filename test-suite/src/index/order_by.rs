@@ -145,6 +145,21 @@ CREATE TABLE Multi (
         "SELECT * FROM Multi ORDER BY id ASC, num ASC"
     );
 
+    // aggregation does not keep the order of the index
+    test_idx!(
+        Ok(select!(num | cnt I64 | I64;
+            50 5; 40 5; 30 5; 20 5; 10 5
+        )),
+        idx!(),
+        "SELECT num, COUNT(*) AS cnt FROM Multi GROUP BY num ORDER BY num DESC"
+    );
+
+    test_idx!(
+        Ok(select!(total I64; 750)),
+        idx!(),
+        "SELECT SUM(num) AS total FROM Multi ORDER BY num"
+    );
+
     test_idx!(
         Ok(select!(id | num I64 | I64;
             1 10; 2 10; 3 10; 4 10; 5 10;
@@ -181,3 +196,51 @@ CREATE TABLE Multi (
         "SELECT * FROM Multi ORDER BY id ASC, id + num DESC"
     );
 });
+
+test_case!(order_by_scan, async move {
+    run!(
+        r#"
+CREATE TABLE Scan (
+    id INTEGER,
+    raw INTEGER
+)"#
+    );
+
+    // `raw` holds the same values as `id` but has no index, so it is always sorted.
+    run!(
+        "
+        INSERT INTO Scan
+        SELECT n % 10 * 10 + n / 10, n % 10 * 10 + n / 10
+        FROM GENERATE_SERIES(0, 99) AS s(n)
+    "
+    );
+
+    test!(Ok(Payload::CreateIndex), "CREATE INDEX idx_id ON Scan (id)");
+
+    let test_cases = vec![
+        (
+            idx!(idx_id, ASC),
+            "SELECT id FROM Scan ORDER BY id ASC",
+            "SELECT id FROM Scan ORDER BY raw ASC",
+        ),
+        (
+            idx!(idx_id, DESC),
+            "SELECT id FROM Scan ORDER BY id DESC",
+            "SELECT id FROM Scan ORDER BY raw DESC",
+        ),
+    ];
+
+    for (indexes, indexed_sql, sorted_sql) in test_cases {
+        let expected = run!(sorted_sql);
+
+        test_idx!(Ok(expected), indexes, indexed_sql);
+    }
+
+    use Value::I64;
+
+    test_idx!(
+        Ok(select!(id I64; 2; 1; 0)),
+        idx!(idx_id, DESC),
+        "SELECT id FROM Scan WHERE raw < 3 ORDER BY id DESC"
+    );
+});
@@ -0,0 +1,104 @@
+use {
+    crate::*,
+    gluesql_core::prelude::*,
+    std::ops::Bound::{Excluded, Included},
+};
+
+test_case!(range, async move {
+    use gluesql_core::ast::IndexOperator::*;
+
+    run!(
+        r#"
+CREATE TABLE IdxRange (
+    id INTEGER,
+    raw INTEGER,
+    name TEXT
+)"#
+    );
+
+    run!(
+        r#"
+        INSERT INTO IdxRange
+            (id, raw, name)
+        VALUES
+            (5, 5, "e"),
+            (1, 1, "a"),
+            (3, 3, "c"),
+            (8, 8, "f"),
+            (2, 2, "b"),
+            (3, 3, "d");
+    "#
+    );
+
+    test!(
+        Ok(Payload::CreateIndex),
+        "CREATE INDEX idx_id ON IdxRange (id)"
+    );
+
+    // `raw` holds the same values as `id` but has no index, so it is always scanned.
+    let test_cases = vec![
+        (
+            idx!(idx_id, RANGE, Included(expr("2")), Included(expr("5"))),
+            "SELECT name FROM IdxRange WHERE id BETWEEN 2 AND 5 ORDER BY name",
+            "SELECT name FROM IdxRange WHERE raw BETWEEN 2 AND 5 ORDER BY name",
+        ),
+        (
+            idx!(idx_id, RANGE, Included(expr("2")), Excluded(expr("5"))),
+            "SELECT name FROM IdxRange WHERE id >= 2 AND id < 5 ORDER BY name",
+            "SELECT name FROM IdxRange WHERE raw >= 2 AND raw < 5 ORDER BY name",
+        ),
+        (
+            idx!(idx_id, RANGE, Excluded(expr("3")), Included(expr("8"))),
+            "SELECT name FROM IdxRange WHERE id > 3 AND id <= 8 ORDER BY name",
+            "SELECT name FROM IdxRange WHERE raw > 3 AND raw <= 8 ORDER BY name",
+        ),
+        (
+            idx!(idx_id, RANGE, Excluded(expr("1")), Excluded(expr("3"))),
+            "SELECT name FROM IdxRange WHERE 3 > id AND id > 1 ORDER BY name",
+            "SELECT name FROM IdxRange WHERE 3 > raw AND raw > 1 ORDER BY name",
+        ),
+        (
+            idx!(idx_id, RANGE, Included(expr("5")), Included(expr("2"))),
+            "SELECT name FROM IdxRange WHERE id BETWEEN 5 AND 2 ORDER BY name",
+            "SELECT name FROM IdxRange WHERE raw BETWEEN 5 AND 2 ORDER BY name",
+        ),
+        (
+            idx!(idx_id, GtEq, "3"),
+            "SELECT name FROM IdxRange WHERE id >= 3 ORDER BY name",
+            "SELECT name FROM IdxRange WHERE raw >= 3 ORDER BY name",
+        ),
+        (
+            idx!(idx_id, Lt, "3"),
+            "SELECT name FROM IdxRange WHERE id < 3 ORDER BY name",
+            "SELECT name FROM IdxRange WHERE raw < 3 ORDER BY name",
+        ),
+        (
+            idx!(idx_id, Gt, "1"),
+            "SELECT name FROM IdxRange WHERE id > 1 AND id > 3 ORDER BY name",
+            "SELECT name FROM IdxRange WHERE raw > 1 AND raw > 3 ORDER BY name",
+        ),
+        (
+            idx!(),
+            "SELECT name FROM IdxRange WHERE id NOT BETWEEN 2 AND 5 ORDER BY name",
+            "SELECT name FROM IdxRange WHERE raw NOT BETWEEN 2 AND 5 ORDER BY name",
+        ),
+    ];
+
+    for (indexes, indexed_sql, scan_sql) in test_cases {
+        let expected = run!(scan_sql);
+
+        test_idx!(Ok(expected), indexes, indexed_sql);
+    }
+
+    test_idx!(
+        Ok(select!(
+            name
+            Str;
+            "b".to_owned();
+            "c".to_owned();
+            "d".to_owned()
+        )),
+        idx!(idx_id, RANGE, Included(expr("2")), Excluded(expr("5"))),
+        "SELECT name FROM IdxRange WHERE id >= 2 AND id < 5 ORDER BY name"
+    );
+});
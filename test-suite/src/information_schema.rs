@@ -0,0 +1,75 @@
+#![cfg(feature = "metadata")]
+
+use {
+    crate::*,
+    gluesql_core::prelude::{Payload, Value::*},
+};
+
+test_case!(information_schema, async move {
+    test!(
+        Ok(Payload::Select {
+            labels: vec!["table_name".to_owned()],
+            rows: vec![],
+        }),
+        "SELECT * FROM information_schema.tables"
+    );
+
+    run!(
+        "
+        CREATE TABLE Item (
+            id INTEGER,
+            name TEXT NULL,
+            price FLOAT
+        );
+    "
+    );
+    run!("CREATE TABLE Category (id INTEGER, flag BOOLEAN);");
+
+    test!(
+        Ok(select!(
+            table_name
+            Str;
+            "Category".to_owned();
+            "Item".to_owned()
+        )),
+        "SELECT table_name FROM information_schema.tables"
+    );
+
+    test!(
+        Ok(select!(
+            column_name        | ordinal_position | data_type           | is_nullable
+            Str                | I64              | Str                 | Str;
+            "id".to_owned()      1                  "INT".to_owned()      "NO".to_owned();
+            "name".to_owned()    2                  "TEXT".to_owned()     "YES".to_owned();
+            "price".to_owned()   3                  "FLOAT".to_owned()    "NO".to_owned()
+        )),
+        "
+        SELECT column_name, ordinal_position, data_type, is_nullable
+        FROM information_schema.columns
+        WHERE table_name = 'Item'
+        "
+    );
+
+    test!(
+        Ok(select!(
+            table_name            | column_name
+            Str                   | Str;
+            "Category".to_owned()   "id".to_owned();
+            "Item".to_owned()       "id".to_owned()
+        )),
+        "
+        SELECT table_name, column_name
+        FROM INFORMATION_SCHEMA.COLUMNS
+        WHERE column_name = 'id'
+        "
+    );
+
+    test!(
+        Ok(select!(
+            cnt
+            I64;
+            5
+        )),
+        "SELECT COUNT(*) AS cnt FROM information_schema.columns"
+    );
+});
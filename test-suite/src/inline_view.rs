@@ -1,7 +1,7 @@
 use {
     crate::*,
     gluesql_core::{
-        executor::EvaluateError,
+        executor::{EvaluateError, FetchError},
         prelude::{Payload, Value::*},
         translate::TranslateError,
     },
@@ -259,6 +259,72 @@ test_case!(inline_view, async move {
                 2     "SQL".to_owned()    2     "EXTRA".to_owned()
             )),
         ),
+        (
+            // left join - derived rows missing for some base rows
+            "SELECT OuterTable.id, InlineView.cnt
+            FROM OuterTable
+            LEFT JOIN (
+                SELECT id, COUNT(*) AS cnt
+                FROM InnerTable
+                WHERE id > 1
+                GROUP BY id
+            ) AS InlineView ON OuterTable.id = InlineView.id",
+            Ok(select_with_null!(
+                id     | cnt;
+                I64(1)   Null;
+                I64(2)   I64(1)
+            )),
+        ),
+        (
+            // join - outer WHERE clause on derived table columns
+            "SELECT OuterTable.name, InlineView.name
+            FROM OuterTable
+            JOIN (
+                SELECT id, name FROM InnerTable WHERE name = 'SQL'
+            ) AS InlineView ON OuterTable.id = InlineView.id
+            WHERE InlineView.id = 2",
+            Ok(select!(
+                name                | name
+                Str                 | Str;
+                "EXTRA".to_owned()    "SQL".to_owned()
+            )),
+        ),
+        (
+            // outer WHERE clause on aggregated derived table
+            "SELECT name, cnt
+            FROM (
+                SELECT name, COUNT(*) AS cnt
+                FROM InnerTable
+                GROUP BY name
+            ) AS Grouped
+            WHERE cnt > 1",
+            Ok(select!(
+                name              | cnt
+                Str               | I64;
+                "SQL".to_owned()    2
+            )),
+        ),
+        (
+            // column aliases rename the columns of the derived table
+            "SELECT label, total
+            FROM (
+                SELECT name, COUNT(*) FROM InnerTable GROUP BY name
+            ) AS Grouped (label, total)
+            WHERE total = 1",
+            Ok(select!(
+                label              | total
+                Str                | I64;
+                "GLUE".to_owned()    1
+            )),
+        ),
+        (
+            "SELECT * FROM (SELECT * FROM InnerTable) AS Renamed (num) WHERE num = 1",
+            Err(FetchError::ColumnAliasCountMismatch {
+                expected: 2,
+                found: 1,
+            }
+            .into()),
+        ),
     ];
     for (sql, expected) in test_cases {
         test!(expected, sql);
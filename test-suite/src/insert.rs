@@ -0,0 +1,48 @@
+use {
+    crate::*,
+    gluesql_core::{
+        executor::ExecuteError,
+        prelude::{Payload, Value::*},
+    },
+};
+
+test_case!(insert_select, async move {
+    run!("CREATE TABLE Source (id INTEGER, name TEXT, score INTEGER)");
+    run!("INSERT INTO Source VALUES (1, 'a', 10), (2, 'b', 20)");
+    run!("CREATE TABLE Target (id INTEGER, name TEXT)");
+
+    test!(
+        Err(ExecuteError::InsertColumnCountMismatch {
+            target_columns: vec!["id".to_owned(), "name".to_owned()],
+            source_columns: vec!["id".to_owned(), "name".to_owned(), "score".to_owned()],
+        }
+        .into()),
+        "INSERT INTO Target SELECT * FROM Source"
+    );
+    test!(
+        Err(ExecuteError::InsertColumnCountMismatch {
+            target_columns: vec!["name".to_owned()],
+            source_columns: vec!["name".to_owned(), "id".to_owned()],
+        }
+        .into()),
+        "INSERT INTO Target (name) SELECT name, id FROM Source"
+    );
+    count!(0, "SELECT * FROM Target");
+
+    // the values go to the given columns, and the others take their defaults
+    run!("CREATE TABLE Named (id INTEGER, name TEXT NULL, flag BOOLEAN DEFAULT TRUE)");
+    test!(
+        Ok(Payload::Insert(2)),
+        "INSERT INTO Named (name, id) SELECT name, id FROM Source"
+    );
+    run!("INSERT INTO Named SELECT score FROM Source WHERE id = 1");
+    test!(
+        Ok(select_with_null!(
+            id      | name                | flag;
+            I64(1)    Str("a".to_owned())   Bool(true);
+            I64(2)    Str("b".to_owned())   Bool(true);
+            I64(10)   Null                  Bool(true)
+        )),
+        "SELECT * FROM Named"
+    );
+});
@@ -249,3 +249,37 @@ test_case!(blend, async move {
     );
     test!(Ok(expected), sql);
 });
+
+test_case!(key_types, async move {
+    use {gluesql_core::executor::JoinError, Value::*};
+
+    run!("CREATE TABLE Account (id INTEGER, code TEXT)");
+    run!("INSERT INTO Account VALUES (1, 'A-1'), (2, 'A-2'), (3, 'A-3')");
+    run!("CREATE TABLE Ledger (account_id INT(8), account_code INTEGER, amount INTEGER)");
+    run!("INSERT INTO Ledger VALUES (1, 1, 100), (3, 3, 300)");
+
+    // integers of different sizes compare by their numbers
+    test!(
+        Ok(select!(
+            id  | amount
+            I64 | I64;
+            1     100;
+            3     300
+        )),
+        "SELECT Account.id, amount FROM Account JOIN Ledger ON Ledger.account_id = Account.id"
+    );
+
+    let error = || JoinError::IncompatibleKeyTypes {
+        left: "TEXT".to_owned(),
+        right: "INT".to_owned(),
+    };
+
+    test!(
+        Err(error().into()),
+        "SELECT * FROM Account JOIN Ledger ON Ledger.account_code = Account.code"
+    );
+    test!(
+        Err(error().into()),
+        "SELECT * FROM Account LEFT JOIN Ledger ON Account.code = Ledger.account_code"
+    );
+});
@@ -0,0 +1,72 @@
+use {
+    crate::*,
+    gluesql_core::{prelude::Value::*, translate::TranslateError},
+};
+
+test_case!(lateral, async move {
+    run!("CREATE TABLE Player (id INTEGER, name TEXT)");
+    run!("CREATE TABLE Score (player_id INTEGER, points INTEGER)");
+    run!("INSERT INTO Player VALUES (1, 'Alice'), (2, 'Bob'), (3, 'Carol')");
+    run!("INSERT INTO Score VALUES (1, 10), (1, 30), (2, 20), (2, 5)");
+
+    test!(
+        Ok(select!(
+            name                | points
+            Str                 | I64;
+            "Alice".to_owned()    30;
+            "Bob".to_owned()      20
+        )),
+        "SELECT Player.name, Best.points
+        FROM Player, LATERAL (
+            SELECT points FROM Score
+            WHERE Score.player_id = Player.id
+            ORDER BY points DESC
+            LIMIT 1
+        ) AS Best"
+    );
+    test!(
+        Ok(select!(
+            name                | points
+            Str                 | I64;
+            "Alice".to_owned()    10;
+            "Alice".to_owned()    30;
+            "Bob".to_owned()      20;
+            "Bob".to_owned()      5
+        )),
+        "SELECT Player.name, Sc.points
+        FROM Player
+        JOIN LATERAL (
+            SELECT points FROM Score WHERE Score.player_id = Player.id
+        ) AS Sc ON TRUE"
+    );
+    test!(
+        Ok(select_with_null!(
+            name                     | points;
+            Str("Alice".to_owned())    I64(30);
+            Str("Bob".to_owned())      I64(20);
+            Str("Carol".to_owned())    Null
+        )),
+        "SELECT Player.name, Sc.points
+        FROM Player
+        LEFT JOIN LATERAL (
+            SELECT points FROM Score
+            WHERE Score.player_id = Player.id AND points > 15
+        ) AS Sc ON TRUE"
+    );
+    test!(
+        Ok(select!(
+            name                | points
+            Str                 | I64;
+            "Alice".to_owned()    30
+        )),
+        "SELECT Player.name, Sc.points
+        FROM Player
+        JOIN LATERAL (
+            SELECT points FROM Score WHERE Score.player_id = Player.id
+        ) AS Sc ON Sc.points > 25"
+    );
+    test!(
+        Err(TranslateError::TooManyTables.into()),
+        "SELECT * FROM Player, Score"
+    );
+});
@@ -1,32 +1,56 @@
 pub mod aggregate;
+pub mod alias;
 pub mod alter;
 pub mod arithmetic;
+pub mod auto_increment;
 pub mod basic;
+pub mod bitwise;
 pub mod blend;
 pub mod case;
+pub mod collation;
 pub mod concat;
+pub mod cte;
 pub mod data_type;
 pub mod default;
+pub mod deterministic_order;
+pub mod distinct_from;
 pub mod error;
+pub mod explain;
 pub mod filter;
 pub mod function;
+pub mod generated_column;
 pub mod index;
+pub mod information_schema;
 pub mod inline_view;
+pub mod insert;
 pub mod join;
+pub mod lateral;
 pub mod limit;
+pub mod list_op;
+pub mod merge;
 pub mod metadata;
 pub mod migrate;
 pub mod nested_select;
 pub mod nullable;
 pub mod order_by;
 pub mod ordering;
+pub mod quoted_identifier;
+pub mod returning;
+pub mod row_value;
+pub mod sequence;
+pub mod series;
 pub mod showcolumns;
+pub mod strict_typing;
 pub mod synthesize;
+pub mod tablesample;
 pub mod transaction;
+pub mod truth_test;
 pub mod type_match;
 pub mod unary_operator;
 pub mod validate;
 pub mod values;
+pub mod view;
+pub mod window;
 
 mod tester;
 
@@ -59,19 +83,46 @@ macro_rules! generate_store_tests {
         glue!(basic, basic::basic);
         glue!(aggregate, aggregate::aggregate);
         glue!(aggregate_group_by, aggregate::group_by);
+        glue!(aggregate_group_by_expr, aggregate::group_by_expr);
+        glue!(aggregate_filter, aggregate::filter);
+        glue!(aggregate_cube, aggregate::cube);
+        glue!(aggregate_grouping_sets, aggregate::grouping_sets);
+        glue!(aggregate_bool, aggregate::bool_aggregate);
+        glue!(aggregate_array_agg, aggregate::array_agg);
+        glue!(aggregate_json_agg, aggregate::json_agg);
+        glue!(aggregate_min_max, aggregate::min_max);
+        glue!(aggregate_custom, aggregate::custom);
         glue!(arithmetic, arithmetic::arithmetic);
         glue!(arithmetic_blend, arithmetic::blend);
+        glue!(arithmetic_overflow, arithmetic::overflow);
+        glue!(arithmetic_float_division, arithmetic::float_division);
         glue!(concat, concat::concat);
         glue!(blend, blend::blend);
         glue!(create_table, alter::create_table);
+        glue!(create_table_as_select, alter::create_table_as_select);
         glue!(drop_table, alter::drop_table);
+        glue!(truncate_table, alter::truncate_table);
         glue!(default, default::default);
+        glue!(
+            deterministic_order,
+            deterministic_order::deterministic_order
+        );
+        glue!(auto_increment, auto_increment::auto_increment);
+        glue!(generated_column, generated_column::generated_column);
+        glue!(sequence, sequence::sequence);
         glue!(limit, limit::limit);
         glue!(error, error::error);
+        glue!(explain_analyze, explain::explain_analyze);
         glue!(filter, filter::filter);
         glue!(inline_view, inline_view::inline_view);
+        glue!(tablesample, tablesample::tablesample);
         glue!(values, values::values);
+        glue!(insert_select, insert::insert_select);
+        glue!(merge, merge::merge);
+        glue!(returning, returning::returning);
+        glue!(series, series::series);
         glue!(unary_operator, unary_operator::unary_operator);
+        glue!(bitwise, bitwise::bitwise);
         glue!(function_upper_lower, function::upper_lower::upper_lower);
         glue!(function_gcd_lcm, function::gcd_lcm::gcd_lcm);
         glue!(function_left_right, function::left_right::left_right);
@@ -104,15 +155,34 @@ macro_rules! generate_store_tests {
         glue!(function_sign, function::sign::sign);
         glue!(join, join::join);
         glue!(join_blend, join::blend);
+        glue!(join_key_types, join::key_types);
+        glue!(lateral, lateral::lateral);
+        glue!(cte, cte::cte);
+        glue!(collation, collation::collation);
+        glue!(alias, alias::alias);
         glue!(migrate, migrate::migrate);
         glue!(nested_select, nested_select::nested_select);
+        glue!(nested_select_quantified, nested_select::quantified);
+        glue!(nested_select_not_in, nested_select::not_in);
+        glue!(nested_select_semi_join, nested_select::semi_join);
         glue!(nullable, nullable::nullable);
         glue!(nullable_text, nullable::nullable_text);
         glue!(nullable_implicit_insert, nullable::nullable_implicit_insert);
         glue!(ordering, ordering::ordering);
+        glue!(quoted_identifier, quoted_identifier::quoted_identifier);
+        glue!(row_value, row_value::row_value);
+        glue!(distinct_from, distinct_from::distinct_from);
+        glue!(truth_test, truth_test::truth_test);
+        glue!(list_op, list_op::list_op);
+        glue!(view, view::view);
+        glue!(materialized_view, view::materialized_view);
         glue!(order_by, order_by::order_by);
+        glue!(order_by_distinct_on, order_by::distinct_on);
+        glue!(order_by_collate, order_by::collate);
+        glue!(order_by_nulls, order_by::nulls);
         glue!(sql_types, data_type::sql_types::sql_types);
         glue!(showcolumns, showcolumns::showcolumns);
+        glue!(strict_typing, strict_typing::strict_typing);
         glue!(int8, data_type::int8::int8);
         glue!(int16, data_type::int16::int16);
         glue!(int32, data_type::int32::int32);
@@ -120,11 +190,16 @@ macro_rules! generate_store_tests {
         glue!(int128, data_type::int128::int128);
         glue!(date, data_type::date::date);
         glue!(timestamp, data_type::timestamp::timestamp);
+        glue!(timestamp_formats, data_type::timestamp::formats);
+        glue!(timestamptz, data_type::timestamptz::timestamptz);
         glue!(time, data_type::time::time);
+        glue!(time_of_day, data_type::time::time_of_day);
         glue!(interval, data_type::interval::interval);
+        glue!(interval_text, data_type::interval::text);
         glue!(list, data_type::list::list);
         glue!(map, data_type::map::map);
         glue!(bytea, data_type::bytea::bytea);
+        glue!(bit, data_type::bit::bit);
         glue!(synthesize, synthesize::synthesize);
         glue!(validate_unique, validate::unique::unique);
         glue!(validate_types, validate::types::types);
@@ -134,15 +209,31 @@ macro_rules! generate_store_tests {
         glue!(function_pi, function::pi::pi);
         glue!(function_reverse, function::reverse::reverse);
         glue!(function_repeat, function::repeat::repeat);
+        glue!(function_glob, function::glob::glob);
+        glue!(function_levenshtein, function::levenshtein::levenshtein);
+        glue!(function_soundex, function::soundex::soundex);
+        glue!(function_ascii_chr, function::ascii_chr::ascii_chr);
+        glue!(function_hex_base64, function::hex_base64::hex_base64);
+        glue!(function_format, function::format::format);
+        glue!(function_random, function::random::random);
+        glue!(function_custom, function::custom::custom);
+        glue!(
+            function_create_function,
+            function::create_function::create_function
+        );
         glue!(case, case::case);
         glue!(function_substr, function::substr::substr);
         glue!(uuid, data_type::uuid::uuid);
         glue!(decimal, data_type::decimal::decimal);
+        glue!(enumeration, data_type::enumeration::enumeration);
         glue!(
             function_generate_uuid,
             function::generate_uuid::generate_uuid
         );
         glue!(type_match, type_match::type_match);
+        glue!(window_first_last_nth_value, window::first_last_nth_value);
+        glue!(window_lag_lead, window::lag_lead);
+        glue!(window_aggregate, window::aggregate);
     };
 }
 
@@ -158,6 +249,12 @@ macro_rules! generate_alter_table_tests {
 
         glue!(alter_table_rename, alter::alter_table_rename);
         glue!(alter_table_add_drop, alter::alter_table_add_drop);
+        glue!(alter_table_add_column, alter::alter_table_add_column);
+        glue!(alter_table_drop_column, alter::alter_table_drop_column);
+        glue!(
+            alter_table_alter_column_type,
+            alter::alter_table_alter_column_type
+        );
     };
 }
 
@@ -173,12 +270,19 @@ macro_rules! generate_index_tests {
 
         glue!(index_basic, index::basic);
         glue!(index_and, index::and);
+        glue!(index_composite, index::composite);
+        glue!(index_drop, index::drop_index);
         glue!(index_nested, index::nested);
         glue!(index_null, index::null);
         glue!(index_expr, index::expr);
+        glue!(index_lookup, index::lookup);
+        glue!(index_range, index::range);
+        glue!(index_keyset, index::keyset);
+        glue!(index_like, index::like);
         glue!(index_value, index::value);
         glue!(index_order_by, index::order_by);
         glue!(index_order_by_multi, index::order_by_multi);
+        glue!(index_order_by_scan, index::order_by_scan);
         glue!(showindexes, index::showindexes);
     };
 }
@@ -194,10 +298,16 @@ macro_rules! generate_transaction_tests {
         }
 
         glue!(transaction_basic, transaction::basic);
+        glue!(transaction_multi_statement, transaction::multi_statement);
         glue!(
             transaction_create_drop_table,
             transaction::create_drop_table
         );
+        glue!(transaction_auto_increment, transaction::auto_increment);
+        glue!(transaction_sequence, transaction::sequence);
+        glue!(transaction_savepoint, transaction::savepoint);
+        glue!(transaction_truncate, transaction::truncate);
+        glue!(transaction_function, transaction::function);
     };
 }
 
@@ -212,6 +322,7 @@ macro_rules! generate_metadata_tests {
         }
 
         glue!(metadata, metadata::metadata);
+        glue!(information_schema, information_schema::information_schema);
     };
 }
 
@@ -227,6 +338,10 @@ macro_rules! generate_alter_table_index_tests {
 
         glue!(alter_table_drop_indexed_table, alter::drop_indexed_table);
         glue!(alter_table_drop_indexed_column, alter::drop_indexed_column);
+        glue!(
+            alter_table_rename_indexed_column,
+            alter::rename_indexed_column
+        );
     };
 }
 
@@ -271,6 +386,7 @@ macro_rules! generate_transaction_index_tests {
 
         glue!(transaction_index_create, transaction::index_create);
         glue!(transaction_index_drop, transaction::index_drop);
+        glue!(transaction_index_truncate, transaction::index_truncate);
     };
 }
 
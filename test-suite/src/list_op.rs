@@ -0,0 +1,59 @@
+use {
+    crate::*,
+    gluesql_core::{executor::EvaluateError, prelude::Value::*},
+};
+
+test_case!(list_op, async move {
+    run!("CREATE TABLE Item (id INTEGER, items LIST NULL, wanted LIST)");
+    run!(
+        "INSERT INTO Item VALUES
+            (1, '[1, 2, 3]', '[1, 2]'),
+            (2, '[4, 5]', '[]'),
+            (3, '[2, null]', '[2, null]'),
+            (4, NULL, '[1]')"
+    );
+
+    let test_cases = [
+        (2, "SELECT * FROM Item WHERE items @> wanted"),
+        (2, "SELECT * FROM Item WHERE 2 = ANY(items)"),
+        (2, "SELECT * FROM Item WHERE NOT 2 = ANY(items)"),
+        (1, "SELECT * FROM Item WHERE id = ANY(items)"),
+        (2, "SELECT * FROM Item WHERE 1 = ANY(wanted)"),
+        (2, "SELECT * FROM Item WHERE 5 >= ALL(items)"),
+        (1, "SELECT * FROM Item WHERE id < ALL(items)"),
+        // an empty list satisfies ALL but never ANY
+        (1, "SELECT * FROM Item WHERE 1 > ALL(wanted)"),
+        (3, "SELECT * FROM Item WHERE 1 <= ANY(wanted)"),
+    ];
+
+    for (num, sql) in test_cases {
+        count!(num, sql);
+    }
+
+    // a NULL list or a NULL element never satisfies the comparison
+    test!(
+        Ok(select!(
+            id  | has_all  | has_two | above
+            I64 | Bool     | Bool    | Bool;
+            1     true       true      false;
+            2     true       false     true;
+            3     false      true      false;
+            4     false      false     true
+        )),
+        "SELECT
+            id,
+            items @> wanted AS has_all,
+            2 = ANY(items) AS has_two,
+            2 > ALL(wanted) AS above
+        FROM Item"
+    );
+
+    test!(
+        Err(EvaluateError::ListTypeRequired(format!("{:?}", I64(1))).into()),
+        "SELECT * FROM Item WHERE items @> id"
+    );
+    test!(
+        Err(EvaluateError::ListTypeRequired(format!("{:?}", I64(1))).into()),
+        "SELECT * FROM Item WHERE 1 = ANY(id)"
+    );
+});
@@ -10,11 +10,45 @@ macro_rules! idx {
     () => {
         vec![]
     };
+    ($name: path, IN, $( $sql_expr: literal ),+) => {
+        vec![gluesql_core::ast::IndexItem {
+            name: stringify!($name).to_owned(),
+            asc: None,
+            cmp_expr: Some(gluesql_core::ast::IndexCmp::In(vec![$(
+                gluesql_core::translate::translate_expr(
+                    &gluesql_core::parse_sql::parse_expr($sql_expr).unwrap(),
+                )
+                .unwrap()
+            ),+])),
+        }]
+    };
+    ($name: path, PREFIX, $( $sql_expr: literal ),+) => {
+        vec![gluesql_core::ast::IndexItem {
+            name: stringify!($name).to_owned(),
+            asc: None,
+            cmp_expr: Some(gluesql_core::ast::IndexCmp::Prefix(vec![$(
+                gluesql_core::translate::translate_expr(
+                    &gluesql_core::parse_sql::parse_expr($sql_expr).unwrap(),
+                )
+                .unwrap()
+            ),+])),
+        }]
+    };
+    ($name: path, RANGE, $start: expr, $end: expr) => {
+        vec![gluesql_core::ast::IndexItem {
+            name: stringify!($name).to_owned(),
+            asc: None,
+            cmp_expr: Some(gluesql_core::ast::IndexCmp::Range {
+                start: $start,
+                end: $end,
+            }),
+        }]
+    };
     ($name: path, $op: path, $sql_expr: literal) => {
         vec![gluesql_core::ast::IndexItem {
             name: stringify!($name).to_owned(),
             asc: None,
-            cmp_expr: Some((
+            cmp_expr: Some(gluesql_core::ast::IndexCmp::Op(
                 $op,
                 gluesql_core::translate::translate_expr(
                     &gluesql_core::parse_sql::parse_expr($sql_expr).unwrap(),
@@ -0,0 +1,96 @@
+use {
+    crate::*,
+    gluesql_core::{
+        executor::MergeError,
+        prelude::{Payload, Value::*},
+    },
+};
+
+test_case!(merge, async move {
+    run!("CREATE TABLE Stock (id INTEGER, name TEXT, quantity INTEGER)");
+    run!("INSERT INTO Stock VALUES (1, 'apple', 10), (2, 'banana', 20), (3, 'cherry', 30)");
+    run!("CREATE TABLE Delivery (id INTEGER, name TEXT, quantity INTEGER)");
+    run!("INSERT INTO Delivery VALUES (2, 'banana', 5), (3, 'cherry', -30), (4, 'durian', 7)");
+
+    // only the source rows without a match are inserted
+    test!(
+        Ok(Payload::Merge(1)),
+        "MERGE INTO Stock USING Delivery ON Stock.id = Delivery.id
+        WHEN NOT MATCHED THEN INSERT VALUES (Delivery.id, Delivery.name, Delivery.quantity)"
+    );
+    test!(
+        Ok(select!(
+            id  | name                 | quantity;
+            I64 | Str                  | I64;
+            1     "apple".to_owned()     10;
+            2     "banana".to_owned()    20;
+            3     "cherry".to_owned()    30;
+            4     "durian".to_owned()    7
+        )),
+        "SELECT * FROM Stock ORDER BY id"
+    );
+
+    // only the matched target rows are updated
+    run!("DELETE FROM Stock WHERE id = 4");
+    test!(
+        Ok(Payload::Merge(2)),
+        "MERGE INTO Stock AS s USING Delivery AS d ON s.id = d.id
+        WHEN MATCHED THEN UPDATE SET quantity = s.quantity + d.quantity"
+    );
+    test!(
+        Ok(select!(
+            id  | name                 | quantity;
+            I64 | Str                  | I64;
+            1     "apple".to_owned()     10;
+            2     "banana".to_owned()    25;
+            3     "cherry".to_owned()    0
+        )),
+        "SELECT * FROM Stock ORDER BY id"
+    );
+
+    // the first clause whose condition holds applies to each row
+    run!("UPDATE Stock SET quantity = 30 WHERE id = 3");
+    test!(
+        Ok(Payload::Merge(3)),
+        "MERGE INTO Stock AS s USING Delivery AS d ON s.id = d.id
+        WHEN MATCHED AND s.quantity + d.quantity = 0 THEN DELETE
+        WHEN MATCHED THEN UPDATE SET quantity = s.quantity + d.quantity
+        WHEN NOT MATCHED AND d.quantity > 0 THEN INSERT (name, id, quantity)
+            VALUES (d.name, d.id, d.quantity)"
+    );
+    test!(
+        Ok(select!(
+            id  | name                 | quantity;
+            I64 | Str                  | I64;
+            1     "apple".to_owned()     10;
+            2     "banana".to_owned()    30;
+            4     "durian".to_owned()    7
+        )),
+        "SELECT * FROM Stock ORDER BY id"
+    );
+
+    // a source row without a fitting clause is left alone
+    test!(
+        Ok(Payload::Merge(0)),
+        "MERGE INTO Stock AS s USING Delivery AS d ON s.id = d.id
+        WHEN NOT MATCHED AND d.quantity > 100 THEN INSERT VALUES (d.id, d.name, d.quantity)"
+    );
+
+    // a target row matching several source rows fails the whole statement
+    run!("INSERT INTO Delivery VALUES (2, 'banana', 1)");
+    test!(
+        Err(MergeError::TargetRowMatchedMoreThanOnce("Stock".to_owned()).into()),
+        "MERGE INTO Stock AS s USING Delivery AS d ON s.id = d.id
+        WHEN MATCHED THEN UPDATE SET quantity = s.quantity + d.quantity"
+    );
+    test!(
+        Ok(select!(
+            id  | name                 | quantity;
+            I64 | Str                  | I64;
+            1     "apple".to_owned()     10;
+            2     "banana".to_owned()    30;
+            4     "durian".to_owned()    7
+        )),
+        "SELECT * FROM Stock ORDER BY id"
+    );
+});
@@ -29,6 +29,9 @@ test_case!(metadata, async move {
     run!("CREATE TABLE Bar (id INTEGER);");
     test!(tables(vec!["Bar", "Foo", "Zoo"]), "SHOW TABLES");
 
+    run!("DROP TABLE Zoo;");
+    test!(tables(vec!["Bar", "Foo"]), "SHOW TABLES");
+
     test!(
         Err(TranslateError::UnsupportedShowVariableKeyword("WHATEVER".to_owned()).into()),
         "SHOW WHATEVER"
@@ -1,4 +1,4 @@
-use crate::*;
+use {crate::*, gluesql_core::prelude::Value::*};
 
 test_case!(nested_select, async move {
     let create_sqls: [&str; 2] = [
@@ -78,3 +78,156 @@ test_case!(nested_select, async move {
         count!(*num, sql);
     }
 });
+
+test_case!(quantified, async move {
+    run!("CREATE TABLE Score (id INTEGER, score INTEGER NULL)");
+    run!("INSERT INTO Score VALUES (1, 10), (2, 20), (3, 30), (4, NULL)");
+    run!("CREATE TABLE Threshold (bound INTEGER NULL)");
+    run!("INSERT INTO Threshold VALUES (15), (25)");
+
+    let test_cases = [
+        (1, "SELECT * FROM Score WHERE score > ALL (SELECT bound FROM Threshold)"),
+        (2, "SELECT * FROM Score WHERE score > ANY (SELECT bound FROM Threshold)"),
+        (0, "SELECT * FROM Score WHERE score = ANY (SELECT bound FROM Threshold)"),
+        (3, "SELECT * FROM Score WHERE score <> ALL (SELECT bound FROM Threshold)"),
+        (
+            1,
+            "SELECT * FROM Score WHERE score >= ALL (SELECT score FROM Score WHERE score IS NOT NULL)",
+        ),
+        // ALL over an empty subquery holds even for NULL, ANY never does
+        (
+            4,
+            "SELECT * FROM Score WHERE score > ALL (SELECT bound FROM Threshold WHERE bound > 100)",
+        ),
+        (
+            0,
+            "SELECT * FROM Score WHERE score = ANY (SELECT bound FROM Threshold WHERE bound > 100)",
+        ),
+    ];
+
+    for (num, sql) in test_cases {
+        count!(num, sql);
+    }
+
+    // a NULL in the subquery keeps ALL from holding, while ANY may still find a match
+    run!("INSERT INTO Threshold VALUES (NULL)");
+
+    count!(
+        0,
+        "SELECT * FROM Score WHERE score > ALL (SELECT bound FROM Threshold)"
+    );
+    test!(
+        Ok(select!(
+            id  | above
+            I64 | Bool;
+            1     false;
+            2     true;
+            3     true;
+            4     false
+        )),
+        "SELECT id, score > ANY (SELECT bound FROM Threshold) AS above FROM Score"
+    );
+});
+
+test_case!(not_in, async move {
+    run!("CREATE TABLE Visitor (id INTEGER NULL, name TEXT)");
+    run!("INSERT INTO Visitor VALUES (1, 'Ann'), (2, 'Bob'), (3, 'Cal'), (NULL, 'Dee')");
+    run!("CREATE TABLE Ban (visitor_id INT(8) NULL)");
+    run!("INSERT INTO Ban VALUES (2), (4)");
+
+    test!(
+        Ok(select!(name; Str; "Ann".to_owned(); "Cal".to_owned())),
+        "SELECT name FROM Visitor WHERE id NOT IN (SELECT visitor_id FROM Ban)"
+    );
+    test!(
+        Ok(select!(name; Str; "Cal".to_owned())),
+        "SELECT name FROM Visitor WHERE id > 1 AND id NOT IN (SELECT visitor_id FROM Ban)"
+    );
+
+    // NOT IN over an empty subquery holds even for NULL
+    count!(
+        4,
+        "SELECT * FROM Visitor WHERE id NOT IN (SELECT visitor_id FROM Ban WHERE visitor_id > 10)"
+    );
+
+    // a correlated subquery still runs for each row
+    test!(
+        Ok(select!(name; Str; "Ann".to_owned(); "Cal".to_owned(); "Dee".to_owned())),
+        "SELECT name FROM Visitor
+        WHERE id NOT IN (SELECT visitor_id FROM Ban WHERE visitor_id = Visitor.id)"
+    );
+
+    // a NULL in the subquery keeps NOT IN from holding, while IN may still find a match
+    run!("INSERT INTO Ban VALUES (NULL)");
+
+    count!(
+        0,
+        "SELECT * FROM Visitor WHERE id NOT IN (SELECT visitor_id FROM Ban)"
+    );
+    count!(
+        0,
+        "SELECT * FROM Visitor WHERE name <> 'Ann' AND id NOT IN (SELECT visitor_id FROM Ban)"
+    );
+    test!(
+        Ok(select!(name; Str; "Bob".to_owned())),
+        "SELECT name FROM Visitor WHERE id IN (SELECT visitor_id FROM Ban)"
+    );
+    test!(
+        Ok(select_with_null!(
+            id      | allowed;
+            I64(1)    Null;
+            I64(2)    Bool(false);
+            I64(3)    Null;
+            Null      Null
+        )),
+        "SELECT id, id NOT IN (SELECT visitor_id FROM Ban) AS allowed FROM Visitor"
+    );
+});
+
+test_case!(semi_join, async move {
+    run!("CREATE TABLE Customer (id INTEGER, name TEXT)");
+    run!("INSERT INTO Customer VALUES (1, 'Ann'), (2, 'Bob'), (2, 'Bea'), (3, 'Cal')");
+    run!("CREATE TABLE Purchase (customer_id INTEGER NULL, amount INTEGER)");
+    run!("INSERT INTO Purchase VALUES (1, 30), (2, 70), (2, 10), (2, 90), (NULL, 100)");
+
+    // a join repeats each customer for each of their purchases
+    count!(
+        7,
+        "SELECT * FROM Customer JOIN Purchase ON Purchase.customer_id = Customer.id"
+    );
+
+    // while IN and EXISTS pass each customer at most once
+    let test_cases = [
+        "SELECT name FROM Customer WHERE id IN (SELECT customer_id FROM Purchase)",
+        "SELECT name FROM Customer
+        WHERE EXISTS (SELECT * FROM Purchase WHERE customer_id = Customer.id)",
+        "SELECT name FROM Customer
+        WHERE name <> 'Cal' AND EXISTS (SELECT 1 FROM Purchase WHERE Purchase.customer_id = id)",
+    ];
+
+    for sql in test_cases {
+        test!(
+            Ok(select!(
+                name;
+                Str;
+                "Ann".to_owned();
+                "Bob".to_owned();
+                "Bea".to_owned()
+            )),
+            sql
+        );
+    }
+
+    let test_cases = [
+        "SELECT name FROM Customer WHERE id IN (SELECT customer_id FROM Purchase WHERE amount > 50)",
+        "SELECT name FROM Customer
+        WHERE EXISTS (SELECT * FROM Purchase WHERE amount > 50 AND customer_id = Customer.id)",
+    ];
+
+    for sql in test_cases {
+        test!(
+            Ok(select!(name; Str; "Bob".to_owned(); "Bea".to_owned())),
+            sql
+        );
+    }
+});
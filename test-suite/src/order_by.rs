@@ -146,7 +146,268 @@ CREATE TABLE Test (
     );
 
     test!(
-        Err(TranslateError::OrderByNullsFirstOrLastNotSupported.into()),
-        "SELECT * FROM Test ORDER BY id NULLS FIRST"
+        Ok(select_with_null!(
+            id     | name;
+            I64(1)   Null;
+            I64(1)   s!("Hello");
+            I64(4)   s!("Thursday");
+            I64(3)   s!("World")
+        )),
+        "SELECT id, name FROM Test ORDER BY name NULLS FIRST"
+    );
+
+    test!(
+        Ok(select_with_null!(
+            id     | name;
+            I64(3)   s!("World");
+            I64(4)   s!("Thursday");
+            I64(1)   s!("Hello");
+            I64(1)   Null
+        )),
+        "SELECT id, name FROM Test ORDER BY name DESC NULLS LAST"
+    );
+});
+
+test_case!(nulls, async move {
+    run!("CREATE TABLE Grade (id INTEGER, team TEXT NULL, score INTEGER NULL)");
+    run!(
+        "
+        INSERT INTO Grade (id, team, score) VALUES
+            (1, 'a', 10),
+            (2, NULL, 20),
+            (3, 'b', NULL),
+            (4, NULL, NULL),
+            (5, 'a', NULL),
+            (6, 'b', 30);
+    "
+    );
+
+    use gluesql_core::prelude::Value::*;
+
+    macro_rules! s {
+        ($v: literal) => {
+            Str($v.to_owned())
+        };
+    }
+
+    // NULLs form a single group, ordered as the greatest value unless told otherwise
+    test!(
+        Ok(select_with_null!(
+            team  | cnt;
+            s!("a")  I64(2);
+            s!("b")  I64(2);
+            Null     I64(2)
+        )),
+        "SELECT team, COUNT(*) AS cnt FROM Grade GROUP BY team ORDER BY team"
+    );
+    test!(
+        Ok(select_with_null!(
+            team  | cnt;
+            Null     I64(2);
+            s!("b")  I64(2);
+            s!("a")  I64(2)
+        )),
+        "SELECT team, COUNT(*) AS cnt FROM Grade GROUP BY team ORDER BY team DESC"
+    );
+    test!(
+        Ok(select_with_null!(
+            team  | cnt;
+            Null     I64(2);
+            s!("a")  I64(2);
+            s!("b")  I64(2)
+        )),
+        "SELECT team, COUNT(*) AS cnt FROM Grade GROUP BY team ORDER BY team NULLS FIRST"
+    );
+
+    // DISTINCT ON keeps one row for all NULLs, as GROUP BY does
+    test!(
+        Ok(select_with_null!(
+            team  | id;
+            Null     I64(2);
+            s!("a")  I64(1);
+            s!("b")  I64(3)
+        )),
+        "SELECT DISTINCT ON (team) team, id FROM Grade ORDER BY team NULLS FIRST, id"
+    );
+    test!(
+        Ok(select_with_null!(
+            team  | id;
+            s!("b")  I64(6);
+            s!("a")  I64(5);
+            Null     I64(4)
+        )),
+        "SELECT DISTINCT ON (team) team, id FROM Grade ORDER BY team DESC NULLS LAST, id DESC"
+    );
+
+    test!(
+        Ok(select!(id I64; 3; 4; 5; 1; 2; 6)),
+        "SELECT id FROM Grade ORDER BY score NULLS FIRST, id"
+    );
+    test!(
+        Ok(select!(id I64; 6; 2; 1; 3; 4; 5)),
+        "SELECT id FROM Grade ORDER BY score DESC NULLS LAST, id"
+    );
+
+    // window partitions and aggregates order their NULLs in the same way
+    test!(
+        Ok(select!(
+            id  | first_id
+            I64 | I64;
+            1     5;
+            2     4;
+            3     3;
+            4     4;
+            5     5;
+            6     3
+        )),
+        "
+        SELECT id, FIRST_VALUE(id) OVER (PARTITION BY team ORDER BY score NULLS FIRST) AS first_id
+        FROM Grade
+        ORDER BY id
+        "
+    );
+    test!(
+        Ok(select_with_null!(
+            team  | ids;
+            s!("a")  List(vec![I64(1), I64(5)]);
+            s!("b")  List(vec![I64(6), I64(3)]);
+            Null     List(vec![I64(2), I64(4)])
+        )),
+        "
+        SELECT team, ARRAY_AGG(id ORDER BY score DESC NULLS LAST) AS ids
+        FROM Grade
+        GROUP BY team
+        ORDER BY team
+        "
+    );
+});
+
+test_case!(distinct_on, async move {
+    run!(
+        "
+        CREATE TABLE PageView (
+            user_id INTEGER,
+            viewed_at INTEGER,
+            page TEXT
+        )"
+    );
+    run!(
+        "
+        INSERT INTO PageView (user_id, viewed_at, page) VALUES
+            (1, 10, 'home'),
+            (2, 15, 'about'),
+            (1, 30, 'cart'),
+            (3, 20, 'home'),
+            (2, 40, 'blog'),
+            (1, 20, 'item');
+    "
+    );
+
+    use gluesql_core::prelude::Value::*;
+
+    test!(
+        Ok(select!(
+            user_id | viewed_at | page
+            I64     | I64       | Str;
+            1         30          "cart".to_owned();
+            2         40          "blog".to_owned();
+            3         20          "home".to_owned()
+        )),
+        "SELECT DISTINCT ON (user_id) user_id, viewed_at, page
+        FROM PageView
+        ORDER BY user_id, viewed_at DESC"
+    );
+    test!(
+        Ok(select!(
+            user_id | page
+            I64     | Str;
+            3         "home".to_owned();
+            2         "about".to_owned();
+            1         "home".to_owned()
+        )),
+        "SELECT DISTINCT ON (user_id) user_id, page
+        FROM PageView
+        ORDER BY user_id DESC, viewed_at"
+    );
+    test!(
+        Err(TranslateError::DistinctOnOrderByMismatch.into()),
+        "SELECT DISTINCT ON (user_id) user_id, page FROM PageView ORDER BY viewed_at"
+    );
+    test!(
+        Err(TranslateError::DistinctOnOrderByMismatch.into()),
+        "SELECT DISTINCT ON (user_id) user_id, page FROM PageView"
+    );
+});
+
+test_case!(collate, async move {
+    run!("CREATE TABLE Fruit (id INTEGER, name TEXT)");
+    run!(
+        "INSERT INTO Fruit VALUES
+            (1, 'banana'),
+            (2, 'Apple'),
+            (3, 'cherry'),
+            (4, 'Date'),
+            (5, 'apple')"
+    );
+
+    use gluesql_core::prelude::Value::*;
+
+    // uppercase letters sort before every lowercase one without a collation
+    test!(
+        Ok(select!(
+            id
+            I64;
+            2;
+            4;
+            5;
+            1;
+            3
+        )),
+        "SELECT id FROM Fruit ORDER BY name"
+    );
+    test!(
+        Ok(select!(
+            id
+            I64;
+            2;
+            5;
+            1;
+            3;
+            4
+        )),
+        "SELECT id FROM Fruit ORDER BY name COLLATE NOCASE, id"
+    );
+    test!(
+        Ok(select!(
+            id
+            I64;
+            4;
+            3;
+            1;
+            5;
+            2
+        )),
+        "SELECT id FROM Fruit ORDER BY name COLLATE NOCASE DESC, id DESC"
+    );
+    test!(
+        Ok(select!(
+            id
+            I64;
+            2;
+            4;
+            5;
+            1;
+            3
+        )),
+        "SELECT id FROM Fruit ORDER BY name COLLATE BINARY"
+    );
+
+    test!(
+        Err(TranslateError::UnsupportedCollation("korean".to_owned()).into()),
+        "SELECT id FROM Fruit ORDER BY name COLLATE korean"
+    );
+    test!(
+        Err(TranslateError::UnsupportedArrayAggOption("COLLATE".to_owned()).into()),
+        "SELECT ARRAY_AGG(id ORDER BY name COLLATE NOCASE) FROM Fruit"
     );
 });
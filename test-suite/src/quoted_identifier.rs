@@ -0,0 +1,79 @@
+use {
+    crate::*,
+    gluesql_core::{
+        ast::DataType,
+        executor::EvaluateError,
+        prelude::{Payload, Value::*},
+    },
+};
+
+test_case!(quoted_identifier, async move {
+    // quoted identifiers keep their case and can be keywords or hold spaces, unquoted ones are
+    // not folded, so `amount` and `Amount` are two columns
+    run!(
+        r#"
+        CREATE TABLE "Order" (
+            id INTEGER,
+            "Full Name" TEXT,
+            `Unit Price` INTEGER,
+            amount INTEGER,
+            Amount INTEGER
+        )
+    "#
+    );
+    run!(
+        r#"
+        INSERT INTO `Order` (id, "Full Name", `Unit Price`, amount, Amount)
+        VALUES (1, 'Ada', 10, 1, 100), (2, 'Alan', 20, 3, 300)
+    "#
+    );
+
+    test!(
+        Ok(Payload::ShowColumns(vec![
+            ("id".to_owned(), DataType::Int),
+            ("Full Name".to_owned(), DataType::Text),
+            ("Unit Price".to_owned(), DataType::Int),
+            ("amount".to_owned(), DataType::Int),
+            ("Amount".to_owned(), DataType::Int),
+        ])),
+        r#"SHOW COLUMNS FROM "Order""#
+    );
+
+    // in expressions backticks quote identifiers, double quotes are strings
+    test!(
+        Ok(select!(
+            id  | "Full Name"       | total
+            I64 | Str               | I64;
+            2     "Alan".to_owned()   60;
+            1     "Ada".to_owned()    10
+        )),
+        "SELECT id, `Full Name`, `Unit Price` * amount AS total FROM `Order` ORDER BY `Full Name` DESC"
+    );
+    test!(
+        Ok(select!(
+            amount | Amount
+            I64    | I64;
+            1        100
+        )),
+        r#"SELECT amount, Amount FROM "Order" WHERE `Order`.`Full Name` = 'Ada'"#
+    );
+    test!(
+        Ok(select!("Full Name"; Str; "Alan".to_owned())),
+        r#"SELECT o.`Full Name` FROM "Order" AS o WHERE o.`Unit Price` > 10"#
+    );
+    test!(
+        Ok(select!(name; Str; "Full Name".to_owned(); "Full Name".to_owned())),
+        r#"SELECT "Full Name" AS name FROM "Order""#
+    );
+
+    run!(r#"UPDATE "Order" SET `Full Name` = 'Ada Lovelace' WHERE id = 1"#);
+    test!(
+        Ok(select!("Full Name"; Str; "Ada Lovelace".to_owned())),
+        "SELECT `Full Name` FROM `Order` WHERE id = 1"
+    );
+
+    test!(
+        Err(EvaluateError::ValueNotFound("AMOUNT".to_owned()).into()),
+        r#"SELECT AMOUNT FROM "Order""#
+    );
+});
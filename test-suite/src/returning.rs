@@ -0,0 +1,65 @@
+use {
+    crate::*,
+    gluesql_core::prelude::{Payload, Value::*},
+};
+
+test_case!(returning, async move {
+    run!("CREATE TABLE Item (id INTEGER PRIMARY KEY AUTOINCREMENT, name TEXT, status TEXT DEFAULT 'new')");
+
+    // the generated and defaulted values come back with the inserted rows
+    test!(
+        Ok(select!(
+            id  | status
+            I64 | Str;
+            1     "new".to_owned();
+            2     "new".to_owned()
+        )),
+        "INSERT INTO Item (name) VALUES ('a'), ('b') RETURNING id, status"
+    );
+    test!(
+        Ok(select!(
+            id  | name              | status
+            I64 | Str               | Str;
+            3     "c".to_owned()      "used".to_owned()
+        )),
+        "INSERT INTO Item (name, status) VALUES ('c', 'used') RETURNING *"
+    );
+    test!(
+        Ok(Payload::Insert(1)),
+        "INSERT INTO Item (name) VALUES ('d')"
+    );
+
+    // UPDATE returns the values after the update
+    test!(
+        Ok(select!(
+            id  | new_status
+            I64 | Str;
+            2     "sold".to_owned()
+        )),
+        "UPDATE Item SET status = 'sold' WHERE id = 2 RETURNING id, status AS new_status"
+    );
+
+    // DELETE returns the values of the rows it removed
+    test!(
+        Ok(select!(
+            name              | label
+            Str               | Str;
+            "a".to_owned()      "a:new".to_owned();
+            "b".to_owned()      "b:sold".to_owned()
+        )),
+        "DELETE FROM Item WHERE id < 3 RETURNING name, name || ':' || status AS label"
+    );
+    test!(
+        Ok(select!(id)),
+        "DELETE FROM Item WHERE id > 100 RETURNING id"
+    );
+    test!(
+        Ok(select!(
+            id  | name              | status
+            I64 | Str               | Str;
+            3     "c".to_owned()      "used".to_owned();
+            4     "d".to_owned()      "new".to_owned()
+        )),
+        "SELECT * FROM Item"
+    );
+});
@@ -0,0 +1,77 @@
+use {
+    crate::*,
+    gluesql_core::{executor::EvaluateError, prelude::Value::*},
+};
+
+test_case!(row_value, async move {
+    run!("CREATE TABLE Pair (id INTEGER, a INTEGER, b INTEGER NULL)");
+    run!(
+        "INSERT INTO Pair VALUES
+            (1, 1, 1),
+            (2, 1, 2),
+            (3, 2, 1),
+            (4, 2, NULL),
+            (5, 3, 0)"
+    );
+
+    let test_cases = [
+        (2, "SELECT * FROM Pair WHERE (a, b) < (2, 1)"),
+        (3, "SELECT * FROM Pair WHERE (a, b) <= (2, 1)"),
+        (3, "SELECT * FROM Pair WHERE (a, b) > (1, 2)"),
+        (4, "SELECT * FROM Pair WHERE (a, b) >= (1, 2)"),
+        (
+            3,
+            "SELECT * FROM Pair WHERE (2, 1) > (a, b) OR (a, b) = (2, 1)",
+        ),
+        (1, "SELECT * FROM Pair WHERE (a, b) = (1, 2)"),
+        (4, "SELECT * FROM Pair WHERE (a, b) <> (1, 2)"),
+        (5, "SELECT * FROM Pair WHERE (1, 2, 3) < (1, 3, 0)"),
+        (0, "SELECT * FROM Pair WHERE (1, 2, 3) = (1, 2, 4)"),
+        // a pair with NULL leaves the result unknown unless an earlier pair decides it
+        (3, "SELECT * FROM Pair WHERE (a, b) <> (2, 1)"),
+        (0, "SELECT * FROM Pair WHERE (a, b) = (2, NULL)"),
+        (1, "SELECT * FROM Pair WHERE (a, b) > (2, NULL)"),
+        (2, "SELECT * FROM Pair WHERE (a, b) < (2, NULL)"),
+    ];
+
+    for (num, sql) in test_cases {
+        count!(num, sql);
+    }
+
+    test!(
+        Ok(select!(
+            id
+            I64;
+            2;
+            3;
+            4;
+            5
+        )),
+        "SELECT id FROM Pair WHERE (a, b) > (1, 1) ORDER BY id"
+    );
+    test!(
+        Ok(select!(
+            id  | a   | b
+            I64 | I64 | I64;
+            3     2     1
+        )),
+        "SELECT * FROM Pair WHERE ((a, b) = (2, 1))"
+    );
+
+    test!(
+        Err(EvaluateError::TupleArityMismatch { left: 2, right: 3 }.into()),
+        "SELECT * FROM Pair WHERE (a, b) = (1, 2, 3)"
+    );
+    test!(
+        Err(EvaluateError::TupleArityMismatch { left: 3, right: 2 }.into()),
+        "SELECT * FROM Pair WHERE (id, a, b) < (1, 2)"
+    );
+    test!(
+        Err(EvaluateError::UnsupportedTupleOperation("+".to_owned()).into()),
+        "SELECT * FROM Pair WHERE (a, b) + (1, 2) = (2, 3)"
+    );
+    test!(
+        Err(EvaluateError::UnsupportedTupleOperation("(a, b)".to_owned()).into()),
+        "SELECT (a, b) FROM Pair"
+    );
+});
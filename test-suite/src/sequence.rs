@@ -0,0 +1,150 @@
+use {
+    crate::*,
+    gluesql_core::{
+        executor::{EvaluateError, SequenceError},
+        prelude::{Payload, Value::*},
+    },
+};
+
+test_case!(sequence, async move {
+    test!(Ok(Payload::Create), "CREATE SEQUENCE Seq");
+    run!("CREATE TABLE Item (id INTEGER, name TEXT)");
+
+    test!(
+        Ok(Payload::Insert(2)),
+        "INSERT INTO Item VALUES (NEXTVAL('Seq'), 'a'), (NEXTVAL('Seq'), 'b')"
+    );
+    test!(
+        Ok(select!(
+            id  | name
+            I64 | Str;
+            1     "a".to_owned();
+            2     "b".to_owned()
+        )),
+        "SELECT * FROM Item"
+    );
+    test!(
+        Ok(select!(current I64; 2)),
+        "SELECT CURRVAL('Seq') AS current FROM Item WHERE id = 1"
+    );
+
+    // every row takes its own value, and the next statement goes on from the last one
+    test!(
+        Ok(select!(
+            id  | next
+            I64 | I64;
+            1     3;
+            2     4
+        )),
+        "SELECT id, NEXTVAL('Seq') AS next FROM Item"
+    );
+    test!(
+        Ok(select!(current I64; 4)),
+        "SELECT CURRVAL('Seq') AS current FROM Item WHERE id = 1"
+    );
+    test!(
+        Ok(Payload::Update(1)),
+        "UPDATE Item SET id = NEXTVAL('Seq') WHERE name = 'b'"
+    );
+    run!("INSERT INTO Item (name, id) VALUES ('c', NEXTVAL('Seq'))");
+    test!(
+        Ok(select!(
+            id  | name
+            I64 | Str;
+            1     "a".to_owned();
+            5     "b".to_owned();
+            6     "c".to_owned()
+        )),
+        "SELECT * FROM Item ORDER BY id"
+    );
+
+    // sequences count on their own
+    run!("CREATE SEQUENCE Other");
+    test!(
+        Ok(select!(
+            other | current
+            I64   | I64;
+            1       10
+        )),
+        "SELECT NEXTVAL('Other') AS other, CURRVAL('Seq') AS current FROM Item WHERE id = 1"
+    );
+
+    // the name of the sequence may be any expression, and NEXTVAL may be used in column
+    // defaults and subqueries
+    test!(
+        Ok(Payload::Insert(1)),
+        "INSERT INTO Item VALUES (NEXTVAL('Se' || 'q'), 'd')"
+    );
+    run!("CREATE TABLE Numbered (id INTEGER DEFAULT NEXTVAL('Seq'), name TEXT)");
+    run!("INSERT INTO Numbered (name) VALUES ('e')");
+    run!("INSERT INTO Numbered (name) SELECT name FROM Item WHERE id = 1");
+    run!(
+        "INSERT INTO Numbered SELECT (SELECT NEXTVAL('Seq') FROM Item WHERE id = 1), name FROM Item WHERE id = 5"
+    );
+    test!(
+        Ok(select!(name Str; "d".to_owned())),
+        "SELECT name FROM Item WHERE id = 7"
+    );
+    test!(
+        Ok(select!(
+            id  | name
+            I64 | Str;
+            8     "e".to_owned();
+            9     "a".to_owned();
+            10    "b".to_owned()
+        )),
+        "SELECT * FROM Numbered ORDER BY id"
+    );
+
+    // the last value belongs to the session, another one has none
+    let previous = std::mem::take(&mut session!());
+    test!(
+        Err(SequenceError::CurrentValueNotDefined("Seq".to_owned()).into()),
+        "SELECT CURRVAL('Seq') FROM Item WHERE id = 1"
+    );
+    session!() = previous;
+    test!(
+        Ok(select!(current I64; 10)),
+        "SELECT CURRVAL('Seq') AS current FROM Item WHERE id = 1"
+    );
+
+    test!(
+        Err(SequenceError::AlreadyExists("Seq".to_owned()).into()),
+        "CREATE SEQUENCE Seq"
+    );
+    test!(
+        Err(SequenceError::NotFound("Nothing".to_owned()).into()),
+        "SELECT NEXTVAL('Nothing') FROM Item"
+    );
+    test!(
+        Err(SequenceError::NotFound("Nothing".to_owned()).into()),
+        "INSERT INTO Item VALUES (NEXTVAL('Nothing'), 'd')"
+    );
+    test!(
+        Err(SequenceError::CurrentValueNotDefined("Unused".to_owned()).into()),
+        "SELECT CURRVAL('Unused') FROM Item"
+    );
+    test!(
+        Err(EvaluateError::FunctionRequiresStringValue("NEXTVAL".to_owned()).into()),
+        "SELECT NEXTVAL(1) FROM Item"
+    );
+
+    test!(Ok(Payload::DropSequence), "DROP SEQUENCE Other");
+    test!(Ok(Payload::DropSequence), "DROP SEQUENCE IF EXISTS Other");
+    test!(
+        Err(SequenceError::NotFound("Other".to_owned()).into()),
+        "DROP SEQUENCE Other"
+    );
+    test!(
+        Err(SequenceError::NotFound("Other".to_owned()).into()),
+        "SELECT NEXTVAL('Other') FROM Item"
+    );
+
+    // a recreated sequence starts over
+    run!("DROP SEQUENCE Seq");
+    run!("CREATE SEQUENCE Seq");
+    test!(
+        Ok(select!(next I64; 1)),
+        "SELECT NEXTVAL('Seq') AS next FROM Item WHERE id = 1"
+    );
+});
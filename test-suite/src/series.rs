@@ -0,0 +1,111 @@
+use {
+    crate::*,
+    gluesql_core::{executor::FetchError, prelude::Value::*, translate::TranslateError},
+};
+
+test_case!(series, async move {
+    macro_rules! t {
+        ($timestamp: expr) => {
+            $timestamp.parse().unwrap()
+        };
+    }
+
+    test!(
+        Ok(select!(
+            generate_series
+            I64;
+            1;
+            2;
+            3;
+            4;
+            5
+        )),
+        "SELECT * FROM generate_series(1, 5)"
+    );
+    test!(
+        Ok(select!(
+            s
+            I64;
+            1;
+            4;
+            7;
+            10
+        )),
+        "SELECT * FROM generate_series(1, 10, 3) AS s"
+    );
+    test!(
+        Ok(select!(
+            n
+            I64;
+            5;
+            3;
+            1
+        )),
+        "SELECT n FROM generate_series(5, 1, -2) AS s(n)"
+    );
+    test!(
+        Ok(select!(
+            n
+            I64;
+            3;
+            6;
+            9
+        )),
+        "SELECT n FROM generate_series(1, 10) AS s(n) WHERE n % 3 = 0"
+    );
+    test!(
+        Ok(select!(
+            ts
+            Timestamp;
+            t!("2022-01-30T00:00:00");
+            t!("2022-01-31T00:00:00");
+            t!("2022-02-01T00:00:00")
+        )),
+        "SELECT ts FROM generate_series(
+            TIMESTAMP '2022-01-30 00:00:00',
+            TIMESTAMP '2022-02-01 00:00:00',
+            INTERVAL '1' DAY
+        ) AS d(ts)"
+    );
+
+    // a step heading away from the end yields no rows
+    test!(
+        Ok(select!(n)),
+        "SELECT n FROM generate_series(1, 5, -1) AS s(n)"
+    );
+    test!(
+        Ok(select!(ts)),
+        "SELECT ts FROM generate_series(
+            TIMESTAMP '2022-02-01 00:00:00',
+            TIMESTAMP '2022-01-30 00:00:00',
+            INTERVAL '1' DAY
+        ) AS d(ts)"
+    );
+
+    test!(
+        Err(FetchError::SeriesStepIsZero(I64(0)).into()),
+        "SELECT * FROM generate_series(1, 5, 0)"
+    );
+    test!(
+        Err(TranslateError::FunctionArgsLengthNotWithinRange {
+            name: "GENERATE_SERIES".to_owned(),
+            expected_minimum: 2,
+            expected_maximum: 3,
+            found: 1,
+        }
+        .into()),
+        "SELECT * FROM generate_series(1)"
+    );
+    test!(
+        Err(TranslateError::ColumnAliasCountMismatch {
+            expected: 1,
+            found: 2,
+        }
+        .into()),
+        "SELECT * FROM generate_series(1, 5) AS s(a, b)"
+    );
+    test!(
+        Err(TranslateError::UnsupportedTableFunction("SEQUENCE_OF".to_owned()).into()),
+        "SELECT * FROM sequence_of(1, 2)"
+    );
+});
@@ -0,0 +1,78 @@
+use {
+    crate::*,
+    gluesql_core::{executor::EvaluateError, prelude::Value::*},
+};
+
+test_case!(strict_typing, async move {
+    run!("CREATE TABLE Typed (id INTEGER, name TEXT, rate FLOAT, born DATE NULL)");
+    run!("INSERT INTO Typed VALUES (1, 'Alice', 1.5, '2000-01-01'), (2, 'Bob', 0.5, NULL)");
+
+    let mismatch = |left: &str, operator: &str, right: &str| EvaluateError::StrictTypeMismatch {
+        left: left.to_owned(),
+        operator: operator.to_owned(),
+        right: right.to_owned(),
+    };
+
+    // lenient by default, values of different types are compared and added as they are
+    test!(
+        Ok(select!(id I64; 1)),
+        "SELECT id FROM Typed WHERE id < rate"
+    );
+    test!(Ok(select!(id)), "SELECT id FROM Typed WHERE id = '1'");
+    test!(
+        Ok(select!(total F64; 2.5; 2.5)),
+        "SELECT id + rate AS total FROM Typed"
+    );
+
+    session!().set_strict_typing(true);
+
+    test!(
+        Err(mismatch("INT", "<", "FLOAT").into()),
+        "SELECT id FROM Typed WHERE id < rate"
+    );
+    test!(
+        Err(mismatch("INT", "=", "TEXT").into()),
+        "SELECT id FROM Typed WHERE id = '1'"
+    );
+    test!(
+        Err(mismatch("INT", "+", "FLOAT").into()),
+        "SELECT id + rate AS total FROM Typed"
+    );
+    test!(
+        Err(mismatch("DATE", "=", "TEXT").into()),
+        "SELECT id FROM Typed WHERE born = '2000-01-01'"
+    );
+
+    // operands of the same type, number literals on numeric values and NULL still pass
+    test!(
+        Ok(select!(id I64; 1)),
+        "SELECT id FROM Typed WHERE CAST(id AS FLOAT) < rate"
+    );
+    test!(Ok(select!(id I64; 1)), "SELECT id FROM Typed WHERE id = 1");
+    test!(
+        Ok(select!(id I64; 2)),
+        "SELECT id FROM Typed WHERE rate < 1 AND name = 'Bob'"
+    );
+    test!(
+        Ok(select!(id I64; 1)),
+        "SELECT id FROM Typed WHERE born = DATE '2000-01-01'"
+    );
+    test!(
+        Ok(select!(id I64; 1)),
+        "SELECT id FROM Typed WHERE born + INTERVAL '1' DAY > TIMESTAMP '2000-01-01 12:00:00'"
+    );
+    test!(
+        Ok(select!(doubled I64; 2; 4)),
+        "SELECT id * 2 AS doubled FROM Typed"
+    );
+
+    session!().set_strict_typing(false);
+
+    test!(Ok(select!(id)), "SELECT id FROM Typed WHERE id = '1'");
+
+    // the setting belongs to the session, another one is lenient
+    session!().set_strict_typing(true);
+    session!() = Default::default();
+
+    test!(Ok(select!(id)), "SELECT id FROM Typed WHERE id = '1'");
+});
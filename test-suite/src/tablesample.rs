@@ -0,0 +1,68 @@
+use {
+    crate::*,
+    gluesql_core::{
+        executor::FetchError,
+        prelude::{Payload, Value::*},
+    },
+};
+
+test_case!(tablesample, async move {
+    run!("CREATE TABLE Sample (id INTEGER);");
+    run!(
+        "INSERT INTO Sample VALUES
+            (1), (2), (3), (4), (5), (6), (7), (8), (9), (10),
+            (11), (12), (13), (14), (15), (16), (17), (18), (19), (20);"
+    );
+
+    let sql = "SELECT id FROM Sample TABLESAMPLE BERNOULLI (50) REPEATABLE (7)";
+    let first = run!(sql);
+    let second = run!(sql);
+
+    // the same seed keeps the same rows
+    assert_eq!(first, second);
+
+    let rows = match first {
+        Payload::Select { rows, .. } => rows,
+        _ => panic!("unexpected payload: {:?}", first),
+    };
+    assert!(rows.len() < 20, "rows: {}", rows.len());
+
+    test!(
+        Ok(select!(count; I64; 0)),
+        "SELECT COUNT(*) AS count FROM Sample TABLESAMPLE BERNOULLI (0)"
+    );
+    test!(
+        Ok(select!(count; I64; 20)),
+        "SELECT COUNT(*) AS count FROM Sample TABLESAMPLE BERNOULLI (100) REPEATABLE (1)"
+    );
+    test!(
+        Ok(select!(count; I64; 20)),
+        "SELECT COUNT(*) AS count FROM Sample AS s TABLESAMPLE BERNOULLI (100) WHERE s.id > 0"
+    );
+    test!(
+        Err(FetchError::InvalidSamplePercentage(I64(150)).into()),
+        "SELECT id FROM Sample TABLESAMPLE BERNOULLI (150)"
+    );
+    test!(
+        Err(FetchError::InvalidSampleSeed(Str("seed".to_owned())).into()),
+        "SELECT id FROM Sample TABLESAMPLE BERNOULLI (10) REPEATABLE ('seed')"
+    );
+
+    // a sample follows the table it samples, whether it is aliased or joined
+    test!(
+        Ok(select!(count; I64; 20)),
+        "SELECT COUNT(*) AS count FROM Sample s TABLESAMPLE BERNOULLI (100)"
+    );
+    test!(
+        Ok(select!(count; I64; 0)),
+        "SELECT COUNT(*) AS count
+        FROM Sample a
+        JOIN Sample b TABLESAMPLE BERNOULLI (0) ON a.id = b.id"
+    );
+    test!(
+        Ok(select!(count; I64; 0)),
+        "SELECT COUNT(*) AS count
+        FROM (SELECT id FROM Sample TABLESAMPLE BERNOULLI (0)) AS s
+        WHERE s.id > 0"
+    );
+});
@@ -2,6 +2,7 @@ use {
     async_trait::async_trait,
     gluesql_core::{
         ast::*,
+        executor::{execute_cancellable, Cancellation, Metrics, Session},
         parse_sql::parse_expr,
         prelude::*,
         result::Result,
@@ -88,6 +89,7 @@ pub fn test(expected: Result<Payload>, found: Result<Payload>) {
 
 pub async fn run<T: GStore + GStoreMut>(
     cell: Rc<RefCell<Option<T>>>,
+    session: &Session,
     sql: &str,
     indexes: Option<Vec<IndexItem>>,
 ) -> Result<Payload> {
@@ -107,13 +109,17 @@ pub async fn run<T: GStore + GStoreMut>(
     }
 
     println!("[SQL] {}", sql);
-    let parsed = try_run!(parse(sql));
-    let statement = try_run!(translate(&parsed[0]));
+    let parsed = try_run!(parse_extended(sql));
+    try_run!(session.load_functions(&storage).await);
+    let statement = try_run!(session.enter(|| translate_extended(&parsed[0])));
     let statement = try_run!(plan(&storage, statement).await);
 
     test_indexes(&statement, indexes);
 
-    match execute(storage, &statement).await {
+    let cancellation = Cancellation::default();
+    let metrics = Metrics::default();
+
+    match execute_cancellable(storage, &statement, session, &cancellation, &metrics).await {
         Ok((storage, payload)) => {
             cell.replace(Some(storage));
 
@@ -250,6 +256,15 @@ macro_rules! test_case {
             use std::rc::Rc;
 
             let cell = tester.get_cell();
+            #[allow(unused_mut)]
+            let mut session = gluesql_core::executor::Session::default();
+
+            #[allow(unused_macros)]
+            macro_rules! session {
+                () => {
+                    session
+                };
+            }
 
             #[allow(unused_macros)]
             macro_rules! schema {
@@ -274,14 +289,19 @@ macro_rules! test_case {
             #[allow(unused_macros)]
             macro_rules! run {
                 ($sql: expr) => {
-                    crate::run(Rc::clone(&cell), $sql, None).await.unwrap()
+                    crate::run(Rc::clone(&cell), &session, $sql, None)
+                        .await
+                        .unwrap()
                 };
             }
 
             #[allow(unused_macros)]
             macro_rules! count {
                 ($count: expr, $sql: expr) => {
-                    match crate::run(Rc::clone(&cell), $sql, None).await.unwrap() {
+                    match crate::run(Rc::clone(&cell), &session, $sql, None)
+                        .await
+                        .unwrap()
+                    {
                         gluesql_core::prelude::Payload::Select { rows, .. } => {
                             assert_eq!($count, rows.len())
                         }
@@ -295,7 +315,7 @@ macro_rules! test_case {
             #[allow(unused_macros)]
             macro_rules! type_match {
                 ($expected: expr, $sql: expr) => {
-                    let found = crate::run(Rc::clone(&cell), $sql, None).await;
+                    let found = crate::run(Rc::clone(&cell), &session, $sql, None).await;
 
                     crate::type_match($expected, found);
                 };
@@ -304,7 +324,7 @@ macro_rules! test_case {
             #[allow(unused_macros)]
             macro_rules! test {
                 ($expected: expr, $sql: expr) => {
-                    let found = crate::run(Rc::clone(&cell), $sql, None).await;
+                    let found = crate::run(Rc::clone(&cell), &session, $sql, None).await;
 
                     crate::test($expected, found);
                 };
@@ -313,7 +333,7 @@ macro_rules! test_case {
             #[allow(unused_macros)]
             macro_rules! test_idx {
                 ($expected: expr, $indexes: expr, $sql: expr) => {
-                    let found = crate::run(Rc::clone(&cell), $sql, Some($indexes)).await;
+                    let found = crate::run(Rc::clone(&cell), &session, $sql, Some($indexes)).await;
 
                     crate::test($expected, found);
                 };
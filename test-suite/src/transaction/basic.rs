@@ -171,3 +171,45 @@ test_case!(basic, async move {
     run!("BEGIN;");
     run!("COMMIT;");
 });
+
+test_case!(multi_statement, async move {
+    use Value::*;
+
+    run!("CREATE TABLE TxMulti (id INTEGER, name TEXT);");
+
+    test!(Ok(Payload::StartTransaction), "BEGIN;");
+    run!(r#"INSERT INTO TxMulti VALUES (1, "a");"#);
+    run!(r#"INSERT INTO TxMulti VALUES (2, "b"), (3, "c");"#);
+    run!(r#"UPDATE TxMulti SET name = "B" WHERE id = 2;"#);
+    test!(Ok(Payload::Rollback), "ROLLBACK;");
+    test!(
+        Ok(Payload::Select {
+            labels: vec!["id".to_owned(), "name".to_owned()],
+            rows: vec![],
+        }),
+        "SELECT id, name FROM TxMulti"
+    );
+
+    test!(Ok(Payload::StartTransaction), "BEGIN;");
+    run!(r#"INSERT INTO TxMulti VALUES (1, "a");"#);
+    test!(
+        Ok(select!(
+            id  | name
+            I64 | Str;
+            1     "a".to_owned()
+        )),
+        "SELECT id, name FROM TxMulti WHERE id = 1"
+    );
+    run!(r#"INSERT INTO TxMulti VALUES (2, "b"), (3, "c");"#);
+    run!("DELETE FROM TxMulti WHERE id = 1;");
+    test!(Ok(Payload::Commit), "COMMIT;");
+    test!(
+        Ok(select!(
+            id  | name
+            I64 | Str;
+            2     "b".to_owned();
+            3     "c".to_owned()
+        )),
+        "SELECT id, name FROM TxMulti"
+    );
+});
@@ -104,3 +104,38 @@ test_case!(index_drop, async move {
         "SELECT id FROM IdxDrop WHERE id = 1"
     );
 });
+
+test_case!(index_truncate, async move {
+    run!("CREATE TABLE IdxTruncate (id INTEGER);");
+    run!("INSERT INTO IdxTruncate VALUES (1), (2);");
+    run!("CREATE INDEX idx_id ON IdxTruncate (id);");
+
+    // ROLLBACK
+    run!("BEGIN;");
+    run!("TRUNCATE TABLE IdxTruncate;");
+    test_idx!(
+        Ok(select!(id)),
+        idx!(idx_id, Eq, "1"),
+        "SELECT id FROM IdxTruncate WHERE id = 1"
+    );
+    run!("ROLLBACK;");
+    test_idx!(
+        Ok(select!(id I64; 1)),
+        idx!(idx_id, Eq, "1"),
+        "SELECT id FROM IdxTruncate WHERE id = 1"
+    );
+
+    // the index no longer finds the truncated rows
+    run!("TRUNCATE TABLE IdxTruncate;");
+    run!("INSERT INTO IdxTruncate VALUES (2), (3);");
+    test_idx!(
+        Ok(select!(id I64; 2)),
+        idx!(idx_id, Eq, "2"),
+        "SELECT id FROM IdxTruncate WHERE id = 2"
+    );
+    test_idx!(
+        Ok(select!(id)),
+        idx!(idx_id, Eq, "1"),
+        "SELECT id FROM IdxTruncate WHERE id = 1"
+    );
+});
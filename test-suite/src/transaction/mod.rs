@@ -4,13 +4,15 @@ mod alter_table;
 mod basic;
 mod index;
 mod metadata;
+mod savepoint;
 mod table;
 
 #[cfg(feature = "alter-table")]
 pub use alter_table::*;
-pub use basic::basic;
+pub use basic::{basic, multi_statement};
 #[cfg(feature = "index")]
 pub use index::*;
 #[cfg(feature = "metadata")]
 pub use metadata::metadata;
+pub use savepoint::savepoint;
 pub use table::*;
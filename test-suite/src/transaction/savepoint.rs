@@ -0,0 +1,77 @@
+use {
+    crate::*,
+    gluesql_core::{executor::FetchError, prelude::*},
+};
+
+test_case!(savepoint, async move {
+    use Value::*;
+
+    run!("CREATE TABLE TxSavepoint (id INTEGER, name TEXT);");
+    run!("INSERT INTO TxSavepoint VALUES (1, 'Friday');");
+
+    test!(Ok(Payload::StartTransaction), "BEGIN;");
+    run!("INSERT INTO TxSavepoint VALUES (2, 'Phone');");
+    test!(Ok(Payload::Savepoint), "SAVEPOINT sp1;");
+    run!("INSERT INTO TxSavepoint VALUES (3, 'Vienna');");
+    run!("UPDATE TxSavepoint SET name = 'Sunday' WHERE id = 1;");
+    test!(Ok(Payload::Savepoint), "SAVEPOINT sp2;");
+    run!("DELETE FROM TxSavepoint WHERE id = 2;");
+    run!("CREATE TABLE Later (id INTEGER);");
+    test!(
+        Ok(select!(
+            id  | name
+            I64 | Str;
+            1     "Sunday".to_owned();
+            3     "Vienna".to_owned()
+        )),
+        "SELECT id, name FROM TxSavepoint"
+    );
+
+    test!(
+        Ok(Payload::RollbackToSavepoint),
+        "ROLLBACK TO SAVEPOINT sp2;"
+    );
+    test!(
+        Ok(select!(
+            id  | name
+            I64 | Str;
+            1     "Sunday".to_owned();
+            2     "Phone".to_owned();
+            3     "Vienna".to_owned()
+        )),
+        "SELECT id, name FROM TxSavepoint"
+    );
+    test!(
+        Err(FetchError::TableNotFound("Later".to_owned()).into()),
+        "SELECT * FROM Later"
+    );
+
+    // undoes only the changes made after it, the ones made before it in the transaction stay
+    test!(Ok(Payload::RollbackToSavepoint), "ROLLBACK TO sp1;");
+    test!(
+        Ok(select!(
+            id  | name
+            I64 | Str;
+            1     "Friday".to_owned();
+            2     "Phone".to_owned()
+        )),
+        "SELECT id, name FROM TxSavepoint"
+    );
+
+    // the savepoint is kept, so it can be rolled back to again
+    run!("INSERT INTO TxSavepoint VALUES (4, 'Seoul');");
+    test!(Ok(Payload::RollbackToSavepoint), "ROLLBACK TO sp1;");
+    test!(Ok(Payload::ReleaseSavepoint), "RELEASE SAVEPOINT sp1;");
+    run!("INSERT INTO TxSavepoint VALUES (5, 'Busan');");
+    test!(Ok(Payload::Commit), "COMMIT;");
+    test!(
+        Ok(select!(
+            id  | name
+            I64 | Str;
+            1     "Friday".to_owned();
+            2     "Phone".to_owned();
+            5     "Busan".to_owned()
+        )),
+        "SELECT id, name FROM TxSavepoint"
+    );
+});
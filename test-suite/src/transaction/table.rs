@@ -1,6 +1,10 @@
 use {
     crate::*,
-    gluesql_core::{executor::FetchError, prelude::Value::*},
+    gluesql_core::{
+        executor::{FetchError, UdfError},
+        prelude::Value::*,
+        translate::TranslateError,
+    },
 };
 
 test_case!(create_drop_table, async move {
@@ -41,3 +45,114 @@ test_case!(create_drop_table, async move {
         "SELECT * FROM Test;"
     );
 });
+
+test_case!(auto_increment, async move {
+    run!("CREATE TABLE Test (id INTEGER AUTOINCREMENT, name TEXT);");
+    run!("INSERT INTO Test (name) VALUES ('a');");
+
+    // a rolled back insert gives its ids back to the sequence
+    run!("BEGIN;");
+    run!("INSERT INTO Test (name) VALUES ('b'), ('c');");
+    test!(Ok(select!(id I64; 1; 2; 3)), "SELECT id FROM Test;");
+    run!("ROLLBACK;");
+    run!("INSERT INTO Test (name) VALUES ('d');");
+    test!(Ok(select!(id I64; 1; 2)), "SELECT id FROM Test;");
+
+    run!("BEGIN;");
+    run!("INSERT INTO Test (name) VALUES ('e');");
+    run!("COMMIT;");
+    run!("INSERT INTO Test (name) VALUES ('f');");
+    test!(Ok(select!(id I64; 1; 2; 3; 4)), "SELECT id FROM Test;");
+});
+
+test_case!(sequence, async move {
+    run!("CREATE SEQUENCE TxSeq;");
+    run!("CREATE TABLE Test (id INTEGER);");
+    run!("INSERT INTO Test VALUES (NEXTVAL('TxSeq'));");
+
+    // a rolled back NEXTVAL gives its value back to the sequence
+    run!("BEGIN;");
+    run!("INSERT INTO Test VALUES (NEXTVAL('TxSeq')), (NEXTVAL('TxSeq'));");
+    test!(Ok(select!(id I64; 1; 2; 3)), "SELECT id FROM Test;");
+    run!("ROLLBACK;");
+    run!("INSERT INTO Test VALUES (NEXTVAL('TxSeq'));");
+    test!(Ok(select!(id I64; 1; 2)), "SELECT id FROM Test;");
+
+    run!("BEGIN;");
+    run!("INSERT INTO Test VALUES (NEXTVAL('TxSeq'));");
+    run!("COMMIT;");
+    run!("INSERT INTO Test VALUES (NEXTVAL('TxSeq'));");
+    test!(Ok(select!(id I64; 1; 2; 3; 4)), "SELECT id FROM Test;");
+
+    // a created sequence is rolled back with its transaction
+    run!("BEGIN;");
+    run!("CREATE SEQUENCE Rolled;");
+    run!("ROLLBACK;");
+    run!("CREATE SEQUENCE Rolled;");
+});
+
+test_case!(truncate, async move {
+    run!("CREATE TABLE Test (id INTEGER AUTOINCREMENT, name TEXT);");
+    run!("INSERT INTO Test (name) VALUES ('a'), ('b');");
+
+    // a rolled back TRUNCATE keeps the rows and the AUTOINCREMENT sequence
+    run!("BEGIN;");
+    run!("TRUNCATE TABLE Test;");
+    test!(Ok(select!(id)), "SELECT id FROM Test;");
+    run!("ROLLBACK;");
+    run!("INSERT INTO Test (name) VALUES ('c');");
+    test!(Ok(select!(id I64; 1; 2; 3)), "SELECT id FROM Test;");
+
+    run!("BEGIN;");
+    run!("TRUNCATE TABLE Test;");
+    run!("COMMIT;");
+    run!("INSERT INTO Test (name) VALUES ('d');");
+    test!(Ok(select!(id I64; 1)), "SELECT id FROM Test;");
+
+    run!("TRUNCATE TABLE Test;");
+    run!("INSERT INTO Test (name) VALUES ('e');");
+    test!(Ok(select!(id I64; 1)), "SELECT id FROM Test;");
+});
+
+test_case!(function, async move {
+    run!("CREATE TABLE Test (id INTEGER);");
+    run!("INSERT INTO Test VALUES (1);");
+
+    // a created function is rolled back with its transaction
+    run!("BEGIN;");
+    run!("CREATE FUNCTION twice(x INT) RETURNS INT AS 'x * 2';");
+    test!(
+        Ok(select!(twice I64; 2)),
+        "SELECT twice(id) AS twice FROM Test;"
+    );
+    run!("ROLLBACK;");
+    test!(
+        Err(TranslateError::UnsupportedFunction("TWICE".to_owned()).into()),
+        "SELECT twice(id) FROM Test;"
+    );
+
+    run!("BEGIN;");
+    run!("CREATE FUNCTION twice(x INT) RETURNS INT AS 'x * 2';");
+    run!("COMMIT;");
+    test!(
+        Ok(select!(twice I64; 2)),
+        "SELECT twice(id) AS twice FROM Test;"
+    );
+
+    // and so is a dropped one
+    run!("BEGIN;");
+    run!("DROP FUNCTION twice;");
+    run!("ROLLBACK;");
+    test!(
+        Ok(select!(twice I64; 2)),
+        "SELECT twice(id) AS twice FROM Test;"
+    );
+
+    run!("BEGIN;");
+    run!("DROP FUNCTION twice;");
+    run!("COMMIT;");
+    test!(
+        Err(UdfError::FunctionNotRegistered("TWICE".to_owned()).into()),
+        "DROP FUNCTION twice;"
+    );
+});
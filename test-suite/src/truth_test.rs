@@ -0,0 +1,42 @@
+use {
+    crate::*,
+    gluesql_core::{executor::EvaluateError, prelude::Value::*},
+};
+
+test_case!(truth_test, async move {
+    run!("CREATE TABLE Flag (id INTEGER, done BOOLEAN NULL)");
+    run!("INSERT INTO Flag VALUES (1, TRUE), (2, FALSE), (3, NULL)");
+
+    let test_cases = [
+        (1, "SELECT * FROM Flag WHERE done IS TRUE"),
+        (2, "SELECT * FROM Flag WHERE done IS NOT TRUE"),
+        (1, "SELECT * FROM Flag WHERE done IS FALSE"),
+        (2, "SELECT * FROM Flag WHERE done IS NOT FALSE"),
+        (1, "SELECT * FROM Flag WHERE done IS UNKNOWN"),
+        (2, "SELECT * FROM Flag WHERE done IS NOT UNKNOWN"),
+        (2, "SELECT * FROM Flag WHERE (id > 1) IS TRUE"),
+        (
+            1,
+            "SELECT * FROM Flag WHERE (CASE WHEN id = 1 THEN NULL ELSE id > 2 END) IS UNKNOWN",
+        ),
+    ];
+
+    for (num, sql) in test_cases {
+        count!(num, sql);
+    }
+
+    test!(
+        Ok(select!(
+            id  | yes   | no    | unknown
+            I64 | Bool  | Bool  | Bool;
+            1     true    false   false;
+            2     false   true    false;
+            3     false   false   true
+        )),
+        "SELECT id, done IS TRUE AS yes, done IS FALSE AS no, done IS UNKNOWN AS unknown FROM Flag"
+    );
+    test!(
+        Err(EvaluateError::BooleanTypeRequired(format!("{:?}", I64(1))).into()),
+        "SELECT id IS TRUE FROM Flag"
+    );
+});
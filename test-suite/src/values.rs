@@ -6,6 +6,7 @@ use {
         data::{Literal, ValueError},
         prelude::DataType,
         prelude::Value::*,
+        translate::TranslateError,
     },
     std::borrow::Cow,
 };
@@ -87,8 +88,81 @@ test_case!(values, async move {
             }
             .into()),
         ),
+        (
+            "VALUES (CAST(NULL AS FLOAT)), (1)",
+            Ok(select_with_null!(
+                column1;
+                Null;
+                F64(1.0)
+            )),
+        ),
+        (
+            "VALUES (1, CAST(NULL AS TEXT)), (2, 3)",
+            Err(ValueError::IncompatibleLiteralForDataType {
+                data_type: DataType::Text,
+                literal: format!("{:?}", Literal::Number(Cow::Owned(BigDecimal::from(3)))),
+            }
+            .into()),
+        ),
+        (
+            "SELECT * FROM (VALUES (1, 'a'), (2, 'b')) AS Derived(id, name)",
+            Ok(select!(
+                id  | name;
+                I64 | Str;
+                1     "a".to_owned();
+                2     "b".to_owned()
+            )),
+        ),
+        (
+            "SELECT name FROM (VALUES (1, 'a'), (2, 'b')) AS Derived(id, name) WHERE id = 2",
+            Ok(select!(
+                name;
+                Str;
+                "b".to_owned()
+            )),
+        ),
+        (
+            "SELECT Derived.id FROM (VALUES (1, 'a'), (2, 'b')) AS Derived(id, name) WHERE Derived.name = 'a'",
+            Ok(select!(
+                id;
+                I64;
+                1
+            )),
+        ),
+        (
+            "SELECT column2 FROM (VALUES (1, 'a'), (2, 'b')) AS Derived WHERE column1 = 1",
+            Ok(select!(
+                column2;
+                Str;
+                "a".to_owned()
+            )),
+        ),
+        (
+            "SELECT * FROM (VALUES (1, 'a'), (2, 'b')) AS Derived(id)",
+            Err(TranslateError::ColumnAliasCountMismatch {
+                expected: 2,
+                found: 1,
+            }
+            .into()),
+        ),
     ];
     for (sql, expected) in test_cases {
         test!(expected, sql);
     }
+
+    run!("CREATE TABLE Measure (id INTEGER, score FLOAT NULL)");
+    run!("INSERT INTO Measure VALUES (1, CAST(NULL AS FLOAT)), (2, 1.5)");
+    run!("INSERT INTO Measure SELECT * FROM (VALUES (3, CAST(NULL AS FLOAT)), (4, 2)) AS Source");
+
+    test!(
+        Ok(select_with_null!(
+            id     | score;
+            I64(1)   Null;
+            I64(2)   F64(1.5);
+            I64(3)   Null;
+            I64(4)   F64(2.0)
+        )),
+        "SELECT id, score FROM Measure"
+    );
+    count!(2, "SELECT * FROM Measure WHERE score IS NULL");
 });
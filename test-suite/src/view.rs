@@ -0,0 +1,323 @@
+use {
+    crate::*,
+    gluesql_core::{
+        executor::{AlterError, ExecuteError, FetchError},
+        plan::PlanError,
+        prelude::{Payload, Value::*},
+        result::Result,
+    },
+};
+
+test_case!(materialized_view, async move {
+    run!("CREATE TABLE Sale (id INTEGER, region TEXT, amount INTEGER)");
+    run!("INSERT INTO Sale VALUES (1, 'East', 10), (2, 'West', 20), (3, 'East', 30)");
+
+    test!(
+        Ok(Payload::Create),
+        "CREATE MATERIALIZED VIEW RegionTotal AS
+            SELECT region, SUM(amount) AS total FROM Sale GROUP BY region"
+    );
+
+    let region_total = "SELECT * FROM RegionTotal ORDER BY region";
+    test!(
+        Ok(select!(
+            region             | total
+            Str                | I64;
+            "East".to_owned()    40;
+            "West".to_owned()    20
+        )),
+        region_total
+    );
+
+    // the view keeps the rows of the last refresh until it is refreshed again
+    run!("INSERT INTO Sale VALUES (4, 'West', 5), (5, 'North', 15)");
+    run!("DELETE FROM Sale WHERE id = 1");
+    test!(
+        Ok(select!(
+            region             | total
+            Str                | I64;
+            "East".to_owned()    40;
+            "West".to_owned()    20
+        )),
+        region_total
+    );
+    test!(
+        Ok(select!(total I64; 20)),
+        "SELECT total FROM RegionTotal WHERE region = 'West'"
+    );
+
+    test!(
+        Ok(Payload::RefreshMaterializedView(3)),
+        "REFRESH MATERIALIZED VIEW RegionTotal"
+    );
+    test!(
+        Ok(select!(
+            region              | total
+            Str                 | I64;
+            "East".to_owned()     30;
+            "North".to_owned()    15;
+            "West".to_owned()     25
+        )),
+        region_total
+    );
+
+    run!("DELETE FROM Sale WHERE region = 'East'");
+    test!(
+        Ok(Payload::RefreshMaterializedView(2)),
+        "REFRESH MATERIALIZED VIEW RegionTotal;"
+    );
+    test!(
+        Ok(select!(
+            region              | total
+            Str                 | I64;
+            "North".to_owned()    15;
+            "West".to_owned()     25
+        )),
+        region_total
+    );
+
+    run!(
+        "CREATE MATERIALIZED VIEW LargeSale (sale_id, value) AS
+            SELECT id, amount FROM Sale WHERE amount > 10"
+    );
+    test!(
+        Ok(select!(
+            sale_id | value
+            I64     | I64;
+            2         20;
+            5         15
+        )),
+        "SELECT * FROM LargeSale ORDER BY sale_id"
+    );
+    test!(
+        Ok(select!(
+            region              | sale_id
+            Str                 | I64;
+            "North".to_owned()    5;
+            "West".to_owned()     2
+        )),
+        "SELECT Sale.region, LargeSale.sale_id FROM Sale
+        JOIN LargeSale ON Sale.id = LargeSale.sale_id
+        ORDER BY Sale.region"
+    );
+
+    test!(
+        Err(AlterError::TableAlreadyExists("RegionTotal".to_owned()).into()),
+        "CREATE MATERIALIZED VIEW RegionTotal AS SELECT id FROM Sale"
+    );
+    test!(
+        Err(AlterError::ViewColumnCountMismatch {
+            expected: 1,
+            found: 2,
+        }
+        .into()),
+        "CREATE MATERIALIZED VIEW Mismatch (a) AS SELECT id, amount FROM Sale"
+    );
+
+    // rows of a materialized view only change by refreshing it
+    let not_writable = || -> Result<Payload> {
+        Err(ExecuteError::MaterializedViewNotWritable("LargeSale".to_owned()).into())
+    };
+    test!(not_writable(), "INSERT INTO LargeSale VALUES (9, 90)");
+    test!(
+        not_writable(),
+        "UPDATE LargeSale SET value = 0 WHERE sale_id = 2"
+    );
+    test!(not_writable(), "DELETE FROM LargeSale WHERE sale_id = 2");
+    test!(not_writable(), "TRUNCATE TABLE LargeSale");
+    test!(
+        not_writable(),
+        "MERGE INTO LargeSale USING Sale ON LargeSale.sale_id = Sale.id
+        WHEN MATCHED THEN DELETE"
+    );
+    test!(
+        Ok(select!(
+            sale_id | value
+            I64     | I64;
+            2         20;
+            5         15
+        )),
+        "SELECT * FROM LargeSale ORDER BY sale_id"
+    );
+
+    run!("CREATE MATERIALIZED VIEW SaleCopy AS SELECT * FROM Sale");
+    run!("DROP TABLE Sale");
+    run!("CREATE TABLE Sale (id INTEGER, region TEXT, amount INTEGER, note TEXT NULL)");
+    test!(
+        Err(AlterError::ViewColumnCountMismatch {
+            expected: 3,
+            found: 4,
+        }
+        .into()),
+        "REFRESH MATERIALIZED VIEW SaleCopy"
+    );
+    test!(
+        Err(AlterError::MaterializedViewNotFound("Sale".to_owned()).into()),
+        "REFRESH MATERIALIZED VIEW Sale"
+    );
+    test!(
+        Err(AlterError::MaterializedViewNotFound("Nothing".to_owned()).into()),
+        "REFRESH MATERIALIZED VIEW Nothing"
+    );
+});
+
+test_case!(view, async move {
+    run!("CREATE TABLE Player (id INTEGER, name TEXT, team_id INTEGER)");
+    run!("CREATE TABLE Team (id INTEGER, title TEXT)");
+    run!(
+        "INSERT INTO Player VALUES
+            (1, 'Amy', 1),
+            (2, 'Ben', 2),
+            (3, 'Cid', 1),
+            (4, 'Dan', 3)"
+    );
+    run!("INSERT INTO Team VALUES (1, 'Red'), (2, 'Blue'), (3, 'Green')");
+
+    test!(
+        Ok(Payload::Create),
+        "CREATE VIEW RedPlayer AS SELECT id, name FROM Player WHERE team_id = 1"
+    );
+    test!(
+        Ok(select!(
+            id  | name
+            I64 | Str;
+            1     "Amy".to_owned();
+            3     "Cid".to_owned()
+        )),
+        "SELECT * FROM RedPlayer"
+    );
+
+    // a view runs its query on every read, so it follows the changes of its tables
+    run!("INSERT INTO Player VALUES (5, 'Eve', 1)");
+    run!("UPDATE Player SET team_id = 2 WHERE id = 1");
+    test!(
+        Ok(select!(
+            name
+            Str;
+            "Cid".to_owned();
+            "Eve".to_owned()
+        )),
+        "SELECT name FROM RedPlayer ORDER BY id"
+    );
+
+    run!(
+        "CREATE VIEW Roster (player, team) AS
+            SELECT Player.name, Team.title FROM Player
+            JOIN Team ON Player.team_id = Team.id"
+    );
+    test!(
+        Ok(select!(
+            player             | team
+            Str                | Str;
+            "Amy".to_owned()     "Blue".to_owned();
+            "Ben".to_owned()     "Blue".to_owned()
+        )),
+        "SELECT player, team FROM Roster WHERE team = 'Blue' ORDER BY player"
+    );
+
+    // joining a view to a table
+    test!(
+        Ok(select!(
+            name               | title
+            Str                | Str;
+            "Cid".to_owned()     "Red".to_owned();
+            "Eve".to_owned()     "Red".to_owned()
+        )),
+        "SELECT r.name, Team.title FROM RedPlayer AS r
+        JOIN Team ON Team.id = 1
+        ORDER BY r.name"
+    );
+    test!(
+        Ok(select!(
+            title               | player
+            Str                 | Str;
+            "Blue".to_owned()     "Amy".to_owned();
+            "Blue".to_owned()     "Ben".to_owned();
+            "Green".to_owned()    "Dan".to_owned()
+        )),
+        "SELECT Team.title, Roster.player FROM Team
+        JOIN Roster ON Roster.team = Team.title
+        WHERE Team.id > 1
+        ORDER BY Roster.player"
+    );
+
+    // a view referencing another view
+    run!("CREATE VIEW RedCount (total) AS SELECT COUNT(*) FROM RedPlayer");
+    test!(Ok(select!(total I64; 2)), "SELECT total FROM RedCount");
+    run!("CREATE VIEW TeamSize AS SELECT team, COUNT(*) AS size FROM Roster GROUP BY team");
+    test!(
+        Ok(select!(
+            team                | size
+            Str                 | I64;
+            "Blue".to_owned()     2;
+            "Green".to_owned()    1;
+            "Red".to_owned()      2
+        )),
+        "SELECT * FROM TeamSize ORDER BY team"
+    );
+
+    // a view reads like a subquery in its place
+    test!(
+        Ok(select!(
+            name
+            Str;
+            "Cid".to_owned();
+            "Eve".to_owned()
+        )),
+        "SELECT name FROM Player WHERE id IN (SELECT id FROM RedPlayer) ORDER BY name"
+    );
+    test!(
+        Ok(select!(
+            pid
+            I64;
+            3;
+            5
+        )),
+        "SELECT r.pid FROM RedPlayer AS r (pid, pname) ORDER BY r.pid"
+    );
+    test!(
+        Ok(select!(n I64; 3)),
+        "WITH RedPlayer AS (SELECT id FROM Team) SELECT COUNT(*) AS n FROM RedPlayer"
+    );
+    test!(
+        Err(PlanError::TableSampleOnView("RedPlayer".to_owned()).into()),
+        "SELECT * FROM RedPlayer TABLESAMPLE BERNOULLI (50)"
+    );
+
+    // a view has no rows of its own, its tables are changed instead
+    let not_writable =
+        || -> Result<Payload> { Err(ExecuteError::ViewNotWritable("RedPlayer".to_owned()).into()) };
+    test!(not_writable(), "INSERT INTO RedPlayer VALUES (6, 'Fay')");
+    test!(
+        not_writable(),
+        "UPDATE RedPlayer SET name = 'Zed' WHERE id = 3"
+    );
+    test!(not_writable(), "DELETE FROM RedPlayer WHERE id = 3");
+    test!(not_writable(), "TRUNCATE TABLE RedPlayer");
+    test!(
+        Ok(select!(
+            name
+            Str;
+            "Cid".to_owned();
+            "Eve".to_owned()
+        )),
+        "SELECT name FROM RedPlayer ORDER BY id"
+    );
+
+    test!(
+        Err(AlterError::TableAlreadyExists("Roster".to_owned()).into()),
+        "CREATE VIEW Roster AS SELECT id FROM Player"
+    );
+    test!(
+        Err(AlterError::ViewColumnCountMismatch {
+            expected: 3,
+            found: 2,
+        }
+        .into()),
+        "CREATE VIEW Mismatch (a, b, c) AS SELECT id, name FROM Player"
+    );
+    test!(
+        Err(FetchError::TableNotFound("Nothing".to_owned()).into()),
+        "CREATE VIEW Missing AS SELECT * FROM Nothing"
+    );
+});
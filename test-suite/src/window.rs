@@ -0,0 +1,454 @@
+use {
+    crate::*,
+    gluesql_core::{executor::WindowError, prelude::Value::*, translate::TranslateError},
+};
+
+test_case!(first_last_nth_value, async move {
+    run!(
+        "
+        CREATE TABLE Item (
+            id INTEGER,
+            category TEXT,
+            price INTEGER
+        );
+    "
+    );
+    run!(
+        "
+        INSERT INTO Item (id, category, price) VALUES
+            (1, 'A', 100),
+            (2, 'A', 300),
+            (3, 'B', 200),
+            (4, 'A', 300),
+            (5, 'B', 500);
+    "
+    );
+
+    let test_cases = vec![
+        (
+            "SELECT
+                id,
+                FIRST_VALUE(price) OVER (PARTITION BY category ORDER BY id) AS first_price,
+                LAST_VALUE(price) OVER (PARTITION BY category ORDER BY id) AS last_price
+            FROM Item",
+            select!(
+                id  | first_price | last_price
+                I64 | I64         | I64;
+                1     100           100;
+                2     100           300;
+                3     200           200;
+                4     100           300;
+                5     200           500
+            ),
+        ),
+        (
+            "SELECT
+                id,
+                LAST_VALUE(price) OVER (
+                    PARTITION BY category ORDER BY id
+                    ROWS BETWEEN UNBOUNDED PRECEDING AND UNBOUNDED FOLLOWING
+                ) AS last_price
+            FROM Item",
+            select!(
+                id  | last_price
+                I64 | I64;
+                1     300;
+                2     300;
+                3     500;
+                4     300;
+                5     500
+            ),
+        ),
+        (
+            "SELECT id, LAST_VALUE(price) OVER (PARTITION BY category) AS last_price FROM Item",
+            select!(
+                id  | last_price
+                I64 | I64;
+                1     300;
+                2     300;
+                3     500;
+                4     300;
+                5     500
+            ),
+        ),
+        (
+            "SELECT
+                id,
+                LAST_VALUE(id) OVER (ORDER BY price) AS range_id,
+                LAST_VALUE(id) OVER (
+                    ORDER BY price ROWS BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW
+                ) AS rows_id
+            FROM Item",
+            select!(
+                id  | range_id | rows_id
+                I64 | I64      | I64;
+                1     1          1;
+                2     4          2;
+                3     3          3;
+                4     4          4;
+                5     5          5
+            ),
+        ),
+        (
+            "SELECT
+                id,
+                FIRST_VALUE(price) OVER (
+                    ORDER BY id ROWS BETWEEN 1 PRECEDING AND 1 FOLLOWING
+                ) AS prev_price,
+                LAST_VALUE(price) OVER (
+                    ORDER BY id ROWS BETWEEN 1 PRECEDING AND 1 FOLLOWING
+                ) AS next_price
+            FROM Item",
+            select!(
+                id  | prev_price | next_price
+                I64 | I64        | I64;
+                1     100          300;
+                2     100          200;
+                3     300          300;
+                4     200          500;
+                5     300          500
+            ),
+        ),
+        (
+            "SELECT
+                id,
+                NTH_VALUE(price, 2) OVER (PARTITION BY category ORDER BY id) AS second_price
+            FROM Item",
+            select_with_null!(
+                id     | second_price;
+                I64(1)   Null;
+                I64(2)   I64(300);
+                I64(3)   Null;
+                I64(4)   I64(300);
+                I64(5)   I64(500)
+            ),
+        ),
+        (
+            "SELECT
+                id,
+                FIRST_VALUE(id) OVER (PARTITION BY category ORDER BY price DESC) AS top_id
+            FROM Item
+            ORDER BY id DESC",
+            select!(
+                id  | top_id
+                I64 | I64;
+                5     5;
+                4     2;
+                3     5;
+                2     2;
+                1     2
+            ),
+        ),
+    ];
+
+    for (sql, expected) in test_cases {
+        test!(Ok(expected), sql);
+    }
+
+    let error_cases = vec![
+        (
+            TranslateError::WindowFunctionRequiresOverClause("FIRST_VALUE".to_owned()).into(),
+            "SELECT FIRST_VALUE(price) FROM Item",
+        ),
+        (
+            TranslateError::UnsupportedWindowFunction("UPPER".to_owned()).into(),
+            "SELECT UPPER(category) OVER () FROM Item",
+        ),
+        (
+            TranslateError::UnsupportedWindowFrameUnits("GROUPS".to_owned()).into(),
+            "SELECT LAST_VALUE(price) OVER (ORDER BY id GROUPS CURRENT ROW) FROM Item",
+        ),
+        (
+            WindowError::RequiresPositiveIntegerValue("NTH_VALUE".to_owned()).into(),
+            "SELECT NTH_VALUE(price, 0) OVER (ORDER BY id) FROM Item",
+        ),
+        (
+            WindowError::RangeOffsetRequiresSingleOrderBy.into(),
+            "SELECT LAST_VALUE(price) OVER (ORDER BY id, price RANGE 1 PRECEDING) FROM Item",
+        ),
+    ];
+
+    for (error, sql) in error_cases {
+        test!(Err(error), sql);
+    }
+});
+
+test_case!(lag_lead, async move {
+    run!(
+        "
+        CREATE TABLE Reading (
+            id INTEGER,
+            sensor TEXT,
+            amount INTEGER
+        );
+    "
+    );
+    run!(
+        "
+        INSERT INTO Reading (id, sensor, amount) VALUES
+            (1, 'a', 10),
+            (2, 'b', 5),
+            (3, 'a', 15),
+            (4, 'b', 7),
+            (5, 'a', 12);
+    "
+    );
+
+    let test_cases = vec![
+        (
+            "SELECT
+                id,
+                LAG(amount) OVER (PARTITION BY sensor ORDER BY id) AS prev,
+                LEAD(amount) OVER (PARTITION BY sensor ORDER BY id) AS next
+            FROM Reading",
+            select_with_null!(
+                id     | prev    | next;
+                I64(1)   Null      I64(15);
+                I64(2)   Null      I64(7);
+                I64(3)   I64(10)   I64(12);
+                I64(4)   I64(5)    Null;
+                I64(5)   I64(15)   Null
+            ),
+        ),
+        (
+            "SELECT
+                id,
+                LAG(amount, 1, 0) OVER (PARTITION BY sensor ORDER BY id) AS prev,
+                LEAD(amount, 2, -1) OVER (PARTITION BY sensor ORDER BY id) AS next
+            FROM Reading",
+            select_with_null!(
+                id     | prev    | next;
+                I64(1)   I64(0)    I64(12);
+                I64(2)   I64(0)    I64(-1);
+                I64(3)   I64(10)   I64(-1);
+                I64(4)   I64(5)    I64(-1);
+                I64(5)   I64(15)   I64(-1)
+            ),
+        ),
+        (
+            "SELECT
+                id,
+                amount - LAG(amount, 1, amount) OVER (PARTITION BY sensor ORDER BY id) AS diff
+            FROM Reading",
+            select_with_null!(
+                id     | diff;
+                I64(1)   I64(0);
+                I64(2)   I64(0);
+                I64(3)   I64(5);
+                I64(4)   I64(2);
+                I64(5)   I64(-3)
+            ),
+        ),
+        (
+            "SELECT id, LAG(id, 2) OVER (ORDER BY id) AS prev FROM Reading",
+            select_with_null!(
+                id     | prev;
+                I64(1)   Null;
+                I64(2)   Null;
+                I64(3)   I64(1);
+                I64(4)   I64(2);
+                I64(5)   I64(3)
+            ),
+        ),
+        (
+            "SELECT id, LAG(id, CAST(2 AS INT(8))) OVER (ORDER BY id) AS prev FROM Reading",
+            select_with_null!(
+                id     | prev;
+                I64(1)   Null;
+                I64(2)   Null;
+                I64(3)   I64(1);
+                I64(4)   I64(2);
+                I64(5)   I64(3)
+            ),
+        ),
+        (
+            "SELECT id, LEAD(id, NULL) OVER (ORDER BY id) AS next FROM Reading WHERE id < 3",
+            select_with_null!(
+                id     | next;
+                I64(1)   Null;
+                I64(2)   Null
+            ),
+        ),
+    ];
+
+    for (sql, expected) in test_cases {
+        test!(Ok(expected), sql);
+    }
+
+    let error_cases = vec![
+        (
+            TranslateError::WindowFunctionRequiresOverClause("LAG".to_owned()).into(),
+            "SELECT LAG(amount) FROM Reading",
+        ),
+        (
+            TranslateError::FunctionArgsLengthNotWithinRange {
+                name: "LEAD".to_owned(),
+                expected_minimum: 1,
+                expected_maximum: 3,
+                found: 4,
+            }
+            .into(),
+            "SELECT LEAD(amount, 1, 0, 0) OVER (ORDER BY id) FROM Reading",
+        ),
+        (
+            WindowError::RequiresPositiveIntegerValue("LAG".to_owned()).into(),
+            "SELECT LAG(amount, 0) OVER (ORDER BY id) FROM Reading",
+        ),
+        (
+            WindowError::NegativeOffset("LEAD".to_owned()).into(),
+            "SELECT LEAD(amount, -1) OVER (ORDER BY id) FROM Reading",
+        ),
+        (
+            WindowError::NegativeOffset("NTH_VALUE".to_owned()).into(),
+            "SELECT NTH_VALUE(amount, CAST(-2 AS INT(8))) OVER (ORDER BY id) FROM Reading",
+        ),
+        (
+            WindowError::RequiresPositiveIntegerValue("LAG".to_owned()).into(),
+            "SELECT LAG(amount, 1.5) OVER (ORDER BY id) FROM Reading",
+        ),
+    ];
+
+    for (error, sql) in error_cases {
+        test!(Err(error), sql);
+    }
+});
+
+test_case!(aggregate, async move {
+    run!(
+        "
+        CREATE TABLE Sale (
+            id INTEGER,
+            sold_on INTEGER,
+            amount INTEGER
+        );
+    "
+    );
+    run!(
+        "
+        INSERT INTO Sale (id, sold_on, amount) VALUES
+            (1, 1, 10),
+            (2, 2, 20),
+            (3, 2, 30),
+            (4, 3, 40),
+            (5, 5, 50);
+    "
+    );
+
+    let test_cases = vec![
+        (
+            "SELECT
+                id,
+                SUM(amount) OVER (
+                    ORDER BY id ROWS BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW
+                ) AS total
+            FROM Sale",
+            select!(
+                id  | total
+                I64 | I64;
+                1     10;
+                2     30;
+                3     60;
+                4     100;
+                5     150
+            ),
+        ),
+        (
+            "SELECT id, SUM(amount) OVER (ORDER BY sold_on) AS total FROM Sale",
+            select!(
+                id  | total
+                I64 | I64;
+                1     10;
+                2     60;
+                3     60;
+                4     100;
+                5     150
+            ),
+        ),
+        (
+            "SELECT
+                id,
+                AVG(amount) OVER (
+                    ORDER BY id ROWS BETWEEN 2 PRECEDING AND CURRENT ROW
+                ) AS moving_avg,
+                AVG(amount) OVER (
+                    ORDER BY id ROWS BETWEEN 1 PRECEDING AND 1 FOLLOWING
+                ) AS centered_avg
+            FROM Sale",
+            select!(
+                id  | moving_avg | centered_avg
+                I64 | F64        | F64;
+                1     10.0         15.0;
+                2     15.0         20.0;
+                3     20.0         30.0;
+                4     30.0         40.0;
+                5     40.0         45.0
+            ),
+        ),
+        (
+            "SELECT
+                id,
+                MAX(amount) OVER (
+                    ORDER BY id ROWS BETWEEN 1 PRECEDING AND 1 FOLLOWING
+                ) AS max_amount,
+                COUNT(*) OVER () AS count_all
+            FROM Sale",
+            select!(
+                id  | max_amount | count_all
+                I64 | I64        | I64;
+                1     20           5;
+                2     30           5;
+                3     40           5;
+                4     50           5;
+                5     50           5
+            ),
+        ),
+        (
+            "SELECT
+                id,
+                SUM(amount) OVER (
+                    ORDER BY sold_on RANGE BETWEEN 1 PRECEDING AND CURRENT ROW
+                ) AS total,
+                COUNT(amount) OVER (
+                    ORDER BY sold_on DESC RANGE BETWEEN CURRENT ROW AND 1 FOLLOWING
+                ) AS count_amount
+            FROM Sale",
+            select!(
+                id  | total | count_amount
+                I64 | I64   | I64;
+                1     10      1;
+                2     60      3;
+                3     60      3;
+                4     90      3;
+                5     50      1
+            ),
+        ),
+        (
+            "SELECT
+                id,
+                COUNT(*) OVER (
+                    ORDER BY id ROWS BETWEEN 2 FOLLOWING AND 3 FOLLOWING
+                ) AS count_next,
+                SUM(amount) OVER (
+                    ORDER BY id ROWS BETWEEN 2 FOLLOWING AND 3 FOLLOWING
+                ) AS sum_next
+            FROM Sale",
+            select_with_null!(
+                id     | count_next | sum_next;
+                I64(1)   I64(2)       I64(70);
+                I64(2)   I64(2)       I64(90);
+                I64(3)   I64(1)       I64(50);
+                I64(4)   I64(0)       Null;
+                I64(5)   I64(0)       Null
+            ),
+        ),
+    ];
+
+    for (sql, expected) in test_cases {
+        test!(Ok(expected), sql);
+    }
+
+    test!(
+        Err(WindowError::RangeOffsetRequiresSingleOrderBy.into()),
+        "SELECT SUM(amount) OVER (RANGE 1 PRECEDING) FROM Sale"
+    );
+});
@@ -1,10 +1,23 @@
 #![cfg(any(feature = "memory-storage", feature = "sled-storage"))]
 use gluesql_core::{
-    executor::Payload,
-    prelude::{Glue, Value},
+    data::Row,
+    executor::{
+        AlterError, CopyError, CsvOptions, DeserializeError, EvaluateError, ExecuteError,
+        ParameterError, Payload, SelectError,
+    },
+    prelude::{DataType, Glue, OnError, ParseOptions, StatementResult, Value},
+    result::Error,
     store::{GStore, GStoreMut},
 };
 
+/// Rows of the SELECT `sql`, which must succeed.
+fn select<T: GStore + GStoreMut>(glue: &mut Glue<T>, sql: &str) -> Vec<Vec<Value>> {
+    match glue.execute(sql).unwrap().remove(0) {
+        Payload::Select { rows, .. } => rows,
+        payload => panic!("unexpected payload: {:?}", payload),
+    }
+}
+
 fn basic<T: GStore + GStoreMut>(mut glue: Glue<T>) {
     assert_eq!(
         glue.execute("DROP TABLE IF EXISTS api_test"),
@@ -50,6 +63,756 @@ fn basic<T: GStore + GStoreMut>(mut glue: Glue<T>) {
     );
 }
 
+fn require_where<T: GStore + GStoreMut>(mut glue: Glue<T>) {
+    glue.execute("CREATE TABLE guard_test (id INTEGER)")
+        .unwrap();
+    glue.execute("INSERT INTO guard_test VALUES (1), (2), (3)")
+        .unwrap();
+
+    glue.require_where = true;
+    assert_eq!(
+        glue.execute("DELETE FROM guard_test"),
+        Err(ExecuteError::WhereClauseRequired.into())
+    );
+    assert_eq!(
+        glue.execute("UPDATE guard_test SET id = 0"),
+        Err(ExecuteError::WhereClauseRequired.into())
+    );
+    assert_eq!(
+        glue.execute("UPDATE guard_test SET id = 10 WHERE id = 1"),
+        Ok(vec![Payload::Update(1)])
+    );
+    assert_eq!(
+        glue.execute("DELETE FROM guard_test WHERE id = 2"),
+        Ok(vec![Payload::Delete(1)])
+    );
+    assert_eq!(
+        glue.execute("UPDATE guard_test SET id = 0 WHERE TRUE"),
+        Ok(vec![Payload::Update(2)])
+    );
+
+    glue.require_where = false;
+    assert_eq!(
+        glue.execute("DELETE FROM guard_test"),
+        Ok(vec![Payload::Delete(2)])
+    );
+}
+
+fn affected<T: GStore + GStoreMut>(mut glue: Glue<T>) {
+    glue.execute("CREATE TABLE affected_test (id INTEGER, name TEXT)")
+        .unwrap();
+
+    let affected = |glue: &mut Glue<T>, sql: &str| {
+        glue.execute(sql)
+            .map(|payloads| payloads.iter().map(Payload::affected).collect::<Vec<_>>())
+    };
+
+    assert_eq!(
+        affected(
+            &mut glue,
+            "INSERT INTO affected_test VALUES (1, 'a'), (2, 'b'), (3, 'c'), (4, 'd')"
+        ),
+        Ok(vec![Some(4)])
+    );
+    assert_eq!(
+        affected(&mut glue, "DELETE FROM affected_test WHERE id > 1"),
+        Ok(vec![Some(3)])
+    );
+    assert_eq!(
+        affected(
+            &mut glue,
+            "UPDATE affected_test SET name = 'z' WHERE id > 100"
+        ),
+        Ok(vec![Some(0)])
+    );
+    assert_eq!(
+        affected(&mut glue, "SELECT * FROM affected_test"),
+        Ok(vec![None])
+    );
+}
+
+fn execute_all<T: GStore + GStoreMut>(mut glue: Glue<T>) {
+    let result = |kind, result| StatementResult {
+        kind: Some(kind),
+        result,
+    };
+
+    assert_eq!(
+        glue.execute_all(
+            "
+                CREATE TABLE script_test (id INTEGER, name TEXT);
+                INSERT INTO script_test VALUES (1, 'a'), (2, 'b');
+                UPDATE script_test SET name = 'c' WHERE id = 2;
+                SELECT name FROM script_test;
+                DROP TABLE script_test;
+            ",
+            OnError::Stop
+        ),
+        Ok(vec![
+            result("CREATE TABLE", Ok(Payload::Create)),
+            result("INSERT", Ok(Payload::Insert(2))),
+            result("UPDATE", Ok(Payload::Update(1))),
+            result(
+                "SELECT",
+                Ok(Payload::Select {
+                    labels: vec!["name".to_owned()],
+                    rows: vec![
+                        vec![Value::Str("a".to_owned())],
+                        vec![Value::Str("c".to_owned())]
+                    ],
+                })
+            ),
+            result("DROP TABLE", Ok(Payload::DropTable)),
+        ])
+    );
+
+    let script = "
+        CREATE TABLE script_error (id INTEGER);
+        INSERT INTO script_missing VALUES (1);
+        INSERT INTO script_error VALUES (1);
+    ";
+    assert_eq!(
+        glue.execute_all(script, OnError::Stop),
+        Ok(vec![
+            result("CREATE TABLE", Ok(Payload::Create)),
+            result(
+                "INSERT",
+                Err(ExecuteError::TableNotFound("script_missing".to_owned()).into())
+            ),
+        ])
+    );
+    assert_eq!(
+        glue.execute("SELECT * FROM script_error"),
+        Ok(vec![Payload::Select {
+            labels: vec!["id".to_owned()],
+            rows: Vec::new(),
+        }])
+    );
+
+    glue.execute("DROP TABLE script_error").unwrap();
+    assert_eq!(
+        glue.execute_all(script, OnError::Continue),
+        Ok(vec![
+            result("CREATE TABLE", Ok(Payload::Create)),
+            result(
+                "INSERT",
+                Err(ExecuteError::TableNotFound("script_missing".to_owned()).into())
+            ),
+            result("INSERT", Ok(Payload::Insert(1))),
+        ])
+    );
+
+    assert!(matches!(
+        glue.execute_all("SELECT * FROM;", OnError::Continue),
+        Err(Error::Parser(_))
+    ));
+}
+
+fn query_as<T: GStore + GStoreMut>(mut glue: Glue<T>) {
+    use serde::Deserialize;
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    struct Item {
+        id: i64,
+        name: String,
+        price: Option<f64>,
+    }
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    struct Named {
+        name: String,
+        price: Option<f64>,
+    }
+
+    glue.execute("CREATE TABLE query_as_test (id INTEGER, name TEXT, price FLOAT NULL)")
+        .unwrap();
+    glue.execute("INSERT INTO query_as_test VALUES (1, 'a', 1.5), (2, 'b', NULL)")
+        .unwrap();
+
+    assert_eq!(
+        glue.query_as::<Item, _>("SELECT * FROM query_as_test"),
+        Ok(vec![
+            Item {
+                id: 1,
+                name: "a".to_owned(),
+                price: Some(1.5),
+            },
+            Item {
+                id: 2,
+                name: "b".to_owned(),
+                price: None,
+            },
+        ])
+    );
+
+    // an Option field without its column is None
+    assert_eq!(
+        glue.query_as::<Named, _>("SELECT name FROM query_as_test WHERE id = 1"),
+        Ok(vec![Named {
+            name: "a".to_owned(),
+            price: None,
+        }])
+    );
+
+    assert_eq!(
+        glue.query_as::<Named, _>("SELECT id, name FROM query_as_test"),
+        Err(DeserializeError::UnknownColumn("id".to_owned()).into())
+    );
+    assert_eq!(
+        glue.query_as::<Item, _>("SELECT name, price FROM query_as_test"),
+        Err(DeserializeError::Message("missing field `id`".to_owned()).into())
+    );
+    assert_eq!(
+        glue.query_as::<Item, _>("SELECT name AS id, name, price FROM query_as_test"),
+        Err(DeserializeError::InvalidColumn {
+            column: "id".to_owned(),
+            message: "invalid type: string \"a\", expected i64".to_owned(),
+        }
+        .into())
+    );
+    assert_eq!(
+        glue.query_as::<Item, _>("SELECT id, price AS name, price FROM query_as_test WHERE id = 2"),
+        Err(DeserializeError::InvalidColumn {
+            column: "name".to_owned(),
+            message: DeserializeError::UnexpectedNull.to_string(),
+        }
+        .into())
+    );
+    assert_eq!(
+        glue.query_as::<Item, _>("DELETE FROM query_as_test WHERE id = 2"),
+        Err(DeserializeError::QueryRequired.into())
+    );
+}
+
+fn cancellation<T: GStore + GStoreMut>(mut glue: Glue<T>) {
+    use std::{
+        thread,
+        time::{Duration, Instant},
+    };
+
+    let cancellation = glue.cancellation.clone();
+    let canceller = thread::spawn(move || {
+        thread::sleep(Duration::from_millis(100));
+        cancellation.cancel();
+    });
+
+    let started = Instant::now();
+    assert_eq!(
+        glue.execute("SELECT COUNT(*) FROM GENERATE_SERIES(1, 1000000000)"),
+        Err(ExecuteError::Cancelled.into())
+    );
+    assert!(started.elapsed() < Duration::from_secs(10));
+    canceller.join().unwrap();
+
+    glue.cancellation.reset();
+    glue.execute("CREATE TABLE cancel_test (id INTEGER)")
+        .unwrap();
+    glue.execute("INSERT INTO cancel_test VALUES (1), (2)")
+        .unwrap();
+
+    glue.cancellation.cancel();
+    assert_eq!(
+        glue.execute("DELETE FROM cancel_test"),
+        Err(ExecuteError::Cancelled.into())
+    );
+    assert_eq!(
+        glue.execute("UPDATE cancel_test SET id = 0"),
+        Err(ExecuteError::Cancelled.into())
+    );
+
+    glue.cancellation.reset();
+    assert_eq!(
+        glue.execute("SELECT id FROM cancel_test"),
+        Ok(vec![Payload::Select {
+            labels: vec![String::from("id")],
+            rows: vec![vec![Value::I64(1)], vec![Value::I64(2)]],
+        }])
+    );
+}
+
+fn metrics<T: GStore + GStoreMut>(mut glue: Glue<T>) {
+    glue.execute("CREATE TABLE metrics_test (id INTEGER)")
+        .unwrap();
+    glue.execute("INSERT INTO metrics_test VALUES (1), (2), (3), (4), (5)")
+        .unwrap();
+
+    glue.execute("SELECT * FROM metrics_test WHERE id > 3")
+        .unwrap();
+    assert_eq!(glue.metrics.rows_scanned(), 5);
+    assert_eq!(glue.metrics.rows_filtered(), 2);
+    assert_eq!(glue.metrics.rows_output(), 2);
+    assert_eq!(glue.metrics.join_probes(), 0);
+
+    glue.execute("SELECT * FROM metrics_test WHERE id > 1 LIMIT 1")
+        .unwrap();
+    assert_eq!(glue.metrics.rows_output(), 1);
+
+    glue.execute("SELECT * FROM metrics_test a JOIN metrics_test b ON a.id >= b.id WHERE a.id = 2")
+        .unwrap();
+    assert_eq!(glue.metrics.rows_scanned(), 5);
+    assert_eq!(glue.metrics.join_probes(), 25);
+    assert_eq!(glue.metrics.rows_filtered(), 2);
+    assert_eq!(glue.metrics.rows_output(), 2);
+
+    // hash join probes only the rows sharing the same key
+    glue.execute("SELECT * FROM metrics_test a JOIN metrics_test b ON a.id = b.id")
+        .unwrap();
+    assert_eq!(glue.metrics.join_probes(), 5);
+    assert_eq!(glue.metrics.rows_output(), 5);
+
+    glue.execute("SELECT COUNT(*) FROM metrics_test WHERE id > 1")
+        .unwrap();
+    assert_eq!(glue.metrics.rows_filtered(), 4);
+    assert_eq!(glue.metrics.rows_aggregated(), 1);
+    assert_eq!(glue.metrics.rows_output(), 1);
+    assert_eq!(glue.metrics.rows_sorted(), 0);
+
+    glue.execute("SELECT * FROM metrics_test WHERE id > 1 ORDER BY id DESC")
+        .unwrap();
+    assert_eq!(glue.metrics.rows_sorted(), 4);
+    assert_eq!(glue.metrics.rows_output(), 4);
+
+    glue.execute("CREATE INDEX idx_id ON metrics_test (id)")
+        .unwrap();
+
+    // index lookups scan only the rows of the keys looked up
+    glue.execute("SELECT * FROM metrics_test WHERE id IN (2, 4, 2)")
+        .unwrap();
+    assert_eq!(glue.metrics.rows_scanned(), 2);
+    glue.execute("SELECT * FROM metrics_test WHERE id = 1 OR id = 5")
+        .unwrap();
+    assert_eq!(glue.metrics.rows_scanned(), 2);
+
+    // read in the order of the index, so no row is sorted
+    glue.execute("SELECT * FROM metrics_test ORDER BY id DESC")
+        .unwrap();
+    assert_eq!(glue.metrics.rows_sorted(), 0);
+    assert_eq!(glue.metrics.rows_output(), 5);
+}
+
+fn import_csv<T: GStore + GStoreMut>(mut glue: Glue<T>) {
+    use gluesql_core::data::{RowError, ValueError};
+
+    glue.execute(
+        "CREATE TABLE csv_test (
+            id INTEGER,
+            name TEXT NULL,
+            score FLOAT,
+            joined DATE NULL,
+            flag BOOLEAN DEFAULT TRUE
+        )",
+    )
+    .unwrap();
+
+    let csv = r#"name,id,score,joined
+"Doe, John",1,1.5,2022-01-02
+"say ""hi""",2,2,
+"multi
+line",3,0.25,2022-03-04
+,4,1,
+"",5,0,
+"#;
+    let options = CsvOptions {
+        header: true,
+        ..CsvOptions::default()
+    };
+    assert_eq!(
+        glue.import_csv("csv_test", csv, &options),
+        Ok(Payload::Insert(5))
+    );
+    assert_eq!(
+        select(
+            &mut glue,
+            "SELECT name, score, flag FROM csv_test WHERE id < 4"
+        ),
+        vec![
+            vec![
+                Value::Str("Doe, John".to_owned()),
+                Value::F64(1.5),
+                Value::Bool(true)
+            ],
+            vec![
+                Value::Str("say \"hi\"".to_owned()),
+                Value::F64(2.0),
+                Value::Bool(true)
+            ],
+            vec![
+                Value::Str("multi\nline".to_owned()),
+                Value::F64(0.25),
+                Value::Bool(true)
+            ],
+        ]
+    );
+    assert_eq!(
+        select(&mut glue, "SELECT id FROM csv_test WHERE joined IS NULL"),
+        vec![
+            vec![Value::I64(2)],
+            vec![Value::I64(4)],
+            vec![Value::I64(5)]
+        ]
+    );
+    assert_eq!(
+        select(&mut glue, "SELECT id FROM csv_test WHERE name IS NULL"),
+        vec![vec![Value::I64(4)]]
+    );
+    assert_eq!(
+        select(&mut glue, "SELECT name FROM csv_test WHERE id = 5"),
+        vec![vec![Value::Str(String::new())]]
+    );
+
+    let options = CsvOptions {
+        delimiter: ';',
+        null: "\\N".to_owned(),
+        ..CsvOptions::default()
+    };
+    assert_eq!(
+        glue.import_csv("csv_test", "6;\\N;3.5;\\N;FALSE\r\n", &options),
+        Ok(Payload::Insert(1))
+    );
+    assert_eq!(
+        select(
+            &mut glue,
+            "SELECT id, flag FROM csv_test WHERE name IS NULL"
+        ),
+        vec![
+            vec![Value::I64(4), Value::Bool(true)],
+            vec![Value::I64(6), Value::Bool(false)]
+        ]
+    );
+
+    let options = CsvOptions {
+        header: true,
+        ..CsvOptions::default()
+    };
+    let test_cases = [
+        (
+            "id,nick\n7,Glue\n",
+            CopyError::ColumnNotFound("nick".to_owned()).into(),
+        ),
+        (
+            "id,score\n7,1\nx,2\n",
+            CopyError::InvalidRecord {
+                line: 3,
+                message: ValueError::LiteralCastFromTextToIntegerFailed("x".to_owned()).to_string(),
+            }
+            .into(),
+        ),
+        (
+            "name\nGlue\n",
+            CopyError::InvalidRecord {
+                line: 2,
+                message: RowError::LackOfRequiredColumn("id".to_owned()).to_string(),
+            }
+            .into(),
+        ),
+        (
+            "id,score\n7,1\n8\n",
+            CopyError::FieldCountMismatch {
+                line: 3,
+                expected: 2,
+                found: 1,
+            }
+            .into(),
+        ),
+        (
+            "id,name,score\n7,\"Glue,1\n",
+            CopyError::UnterminatedQuote(2).into(),
+        ),
+    ];
+    for (csv, error) in test_cases {
+        assert_eq!(glue.import_csv("csv_test", csv, &options), Err(error));
+    }
+    assert_eq!(
+        select(&mut glue, "SELECT COUNT(*) FROM csv_test"),
+        vec![vec![Value::I64(6)]]
+    );
+
+    assert_eq!(
+        glue.import_csv("missing", "1\n", &CsvOptions::default()),
+        Err(ExecuteError::TableNotFound("missing".to_owned()).into())
+    );
+}
+
+fn export_csv<T: GStore + GStoreMut>(mut glue: Glue<T>) {
+    glue.execute("CREATE TABLE export_test (id INTEGER, name TEXT NULL)")
+        .unwrap();
+    glue.execute(
+        "INSERT INTO export_test VALUES
+            (1, 'plain'),
+            (2, 'a,b'),
+            (3, 'say \"hi\"'),
+            (4, 'multi
+line'),
+            (5, NULL),
+            (6, ''),
+            (7, 'NULL')",
+    )
+    .unwrap();
+
+    let export = |glue: &Glue<T>, sql: &str, options: &CsvOptions| {
+        let mut csv = Vec::new();
+        let num_rows = glue.export_csv(sql, &mut csv, options).unwrap();
+
+        (num_rows, String::from_utf8(csv).unwrap())
+    };
+
+    let options = CsvOptions {
+        header: true,
+        ..CsvOptions::default()
+    };
+    let expected = r#"id,name
+1,plain
+2,"a,b"
+3,"say ""hi"""
+4,"multi
+line"
+5,
+6,""
+7,NULL
+"#;
+    assert_eq!(
+        export(&glue, "SELECT * FROM export_test", &options),
+        (7, expected.to_owned())
+    );
+
+    let options = CsvOptions {
+        delimiter: ';',
+        null: "NULL".to_owned(),
+        ..CsvOptions::default()
+    };
+    let expected = r#"2;a,b
+5;NULL
+6;
+7;"NULL"
+"#;
+    assert_eq!(
+        export(
+            &glue,
+            "SELECT id, name FROM export_test WHERE id IN (2, 5, 6, 7)",
+            &options
+        ),
+        (4, expected.to_owned())
+    );
+
+    let options = CsvOptions {
+        header: true,
+        ..CsvOptions::default()
+    };
+    let (_, csv) = export(&glue, "SELECT * FROM export_test", &options);
+    glue.execute("CREATE TABLE import_test (id INTEGER, name TEXT NULL)")
+        .unwrap();
+    assert_eq!(
+        glue.import_csv("import_test", &csv, &options),
+        Ok(Payload::Insert(7))
+    );
+    assert_eq!(
+        export(&glue, "SELECT * FROM import_test", &options),
+        (7, csv)
+    );
+
+    assert_eq!(
+        glue.export_csv("DELETE FROM export_test", Vec::new(), &options),
+        Err(CopyError::QueryRequired.into())
+    );
+}
+
+fn jsonl<T: GStore + GStoreMut>(mut glue: Glue<T>) {
+    glue.execute(
+        "CREATE TABLE jsonl_test (
+            id INTEGER,
+            name TEXT NULL,
+            score DECIMAL NULL,
+            active BOOLEAN NULL DEFAULT TRUE,
+            born DATE NULL
+        )",
+    )
+    .unwrap();
+    glue.execute(
+        "INSERT INTO jsonl_test VALUES
+            (1, 'Glue', 1.5, TRUE, '2022-01-01'),
+            (2, 'say \"hi\"', NULL, FALSE, NULL)",
+    )
+    .unwrap();
+
+    let export = |glue: &Glue<T>, sql: &str| {
+        let mut jsonl = Vec::new();
+        let num_rows = glue.export_jsonl(sql, &mut jsonl).unwrap();
+
+        (num_rows, String::from_utf8(jsonl).unwrap())
+    };
+
+    let expected = r#"{"id":1,"name":"Glue","score":1.5,"active":true,"born":"2022-01-01"}
+{"id":2,"name":"say \"hi\"","score":null,"active":false,"born":null}
+"#;
+    assert_eq!(
+        export(&glue, "SELECT * FROM jsonl_test"),
+        (2, expected.to_owned())
+    );
+    assert_eq!(
+        export(&glue, "SELECT name AS n, id FROM jsonl_test WHERE id = 1"),
+        (1, "{\"n\":\"Glue\",\"id\":1}\n".to_owned())
+    );
+
+    let (_, jsonl) = export(&glue, "SELECT * FROM jsonl_test");
+    glue.execute(
+        "CREATE TABLE jsonl_copy (
+            id INTEGER,
+            name TEXT NULL,
+            score DECIMAL NULL,
+            active BOOLEAN NULL,
+            born DATE NULL
+        )",
+    )
+    .unwrap();
+    assert_eq!(
+        glue.import_jsonl("jsonl_copy", &jsonl),
+        Ok(Payload::Insert(2))
+    );
+    assert_eq!(export(&glue, "SELECT * FROM jsonl_copy"), (2, jsonl));
+
+    let jsonl = r#"
+{"score": "2.25", "id": "3"}
+
+{"id": 4, "name": "Mike", "active": null}
+"#;
+    assert_eq!(
+        glue.import_jsonl("jsonl_test", jsonl),
+        Ok(Payload::Insert(2))
+    );
+    let expected = r#"{"id":3,"name":null,"score":2.25,"active":true,"born":null}
+{"id":4,"name":"Mike","score":null,"active":null,"born":null}
+"#;
+    assert_eq!(
+        export(&glue, "SELECT * FROM jsonl_test WHERE id > 2"),
+        (2, expected.to_owned())
+    );
+
+    let jsonl = r#"{"id": 5}
+{"id": 6, "nickname": "Jorno"}"#;
+    assert_eq!(
+        glue.import_jsonl("jsonl_test", jsonl),
+        Err(CopyError::InvalidRecord {
+            line: 2,
+            message: CopyError::ColumnNotFound("nickname".to_owned()).to_string(),
+        }
+        .into())
+    );
+    assert_eq!(export(&glue, "SELECT id FROM jsonl_test WHERE id = 5").0, 0);
+
+    assert!(matches!(
+        glue.import_jsonl("jsonl_test", "{\"id\": 7}\n[7]"),
+        Err(Error::Copy(CopyError::InvalidRecord { line: 2, .. }))
+    ));
+    assert!(matches!(
+        glue.import_jsonl("jsonl_test", "{\"id\": 7"),
+        Err(Error::Copy(CopyError::InvalidRecord { line: 1, .. }))
+    ));
+    assert_eq!(
+        glue.import_jsonl("missing", "{\"id\": 7}"),
+        Err(ExecuteError::TableNotFound("missing".to_owned()).into())
+    );
+}
+
+fn bulk_insert<T: GStore + GStoreMut>(mut glue: Glue<T>) {
+    glue.execute(
+        "CREATE TABLE bulk_test (
+            id INTEGER,
+            name TEXT NULL,
+            score FLOAT NULL,
+            active BOOLEAN DEFAULT TRUE
+        )",
+    )
+    .unwrap();
+
+    assert_eq!(
+        glue.bulk_insert(
+            "bulk_test",
+            &["name", "id", "score"],
+            vec![
+                vec![
+                    Value::Str("Glue".to_owned()),
+                    Value::Null,
+                    Value::Str("SQL".to_owned()),
+                ],
+                vec![Value::I64(1), Value::I64(2), Value::I8(3)],
+                vec![Value::F64(1.5), Value::I64(2), Value::Null],
+            ],
+        ),
+        Ok(Payload::Insert(3))
+    );
+    assert_eq!(
+        glue.execute("SELECT id, name, active FROM bulk_test WHERE name IS NOT NULL"),
+        Ok(vec![Payload::Select {
+            labels: vec!["id".to_owned(), "name".to_owned(), "active".to_owned()],
+            rows: vec![
+                vec![
+                    Value::I64(1),
+                    Value::Str("Glue".to_owned()),
+                    Value::Bool(true)
+                ],
+                vec![
+                    Value::I64(3),
+                    Value::Str("SQL".to_owned()),
+                    Value::Bool(true)
+                ],
+            ],
+        }])
+    );
+    assert_eq!(
+        glue.execute("SELECT id, score FROM bulk_test WHERE score > 1"),
+        Ok(vec![Payload::Select {
+            labels: vec!["id".to_owned(), "score".to_owned()],
+            rows: vec![
+                vec![Value::I64(1), Value::F64(1.5)],
+                vec![Value::I64(2), Value::F64(2.0)],
+            ],
+        }])
+    );
+    assert_eq!(
+        glue.execute("SELECT id FROM bulk_test WHERE name IS NULL OR score IS NULL"),
+        Ok(vec![Payload::Select {
+            labels: vec!["id".to_owned()],
+            rows: vec![vec![Value::I64(2)], vec![Value::I64(3)]],
+        }])
+    );
+
+    assert_eq!(
+        glue.bulk_insert(
+            "bulk_test",
+            &["id", "name"],
+            vec![
+                vec![Value::I64(4), Value::I64(5)],
+                vec![Value::Str("Mike".to_owned())],
+            ],
+        ),
+        Err(CopyError::ArrayLengthMismatch {
+            column: "name".to_owned(),
+            expected: 2,
+            found: 1,
+        }
+        .into())
+    );
+    assert_eq!(
+        glue.bulk_insert("bulk_test", &["id", "name"], vec![vec![Value::I64(4)]]),
+        Err(CopyError::ArrayCountMismatch {
+            expected: 2,
+            found: 1,
+        }
+        .into())
+    );
+    assert_eq!(
+        glue.bulk_insert("bulk_test", &["nickname"], vec![vec![Value::I64(4)]]),
+        Err(CopyError::ColumnNotFound("nickname".to_owned()).into())
+    );
+    assert_eq!(
+        glue.execute("SELECT COUNT(*) FROM bulk_test"),
+        Ok(vec![Payload::Select {
+            labels: vec!["COUNT(*)".to_owned()],
+            rows: vec![vec![Value::I64(3)]],
+        }])
+    );
+}
+
 async fn basic_async<T: GStore + GStoreMut>(mut glue: Glue<T>) {
     assert_eq!(
         glue.execute_async("DROP TABLE IF EXISTS api_test").await,
@@ -65,40 +828,320 @@ async fn basic_async<T: GStore + GStoreMut>(mut glue: Glue<T>) {
     );
 }
 
+async fn select_stream<T: GStore + GStoreMut>(glue: Glue<T>) {
+    use futures::stream::{StreamExt, TryStreamExt};
+
+    // a billion rows, which only a lazily consumed stream gets through
+    let statements = glue
+        .plan("SELECT * FROM generate_series(1, 1000000000)")
+        .await
+        .unwrap();
+    let (labels, rows) = glue.select_stream(&statements[0]).await.unwrap();
+    let rows = rows.take(3).try_collect::<Vec<_>>().await;
+
+    assert_eq!(labels, vec!["generate_series".to_owned()]);
+    assert_eq!(
+        rows,
+        Ok(vec![
+            Row(vec![Value::I64(1)]),
+            Row(vec![Value::I64(2)]),
+            Row(vec![Value::I64(3)]),
+        ])
+    );
+    assert_eq!(glue.metrics.rows_output(), 3);
+
+    let statements = glue.plan("DROP TABLE IF EXISTS stream_test").await.unwrap();
+    assert!(matches!(
+        glue.select_stream(&statements[0]).await,
+        Err(Error::Select(SelectError::QueryRequired))
+    ));
+}
+
+fn select_map<T: GStore + GStoreMut>(mut glue: Glue<T>) {
+    glue.execute("CREATE TABLE map_test (id INTEGER, name TEXT)")
+        .unwrap();
+    glue.execute("INSERT INTO map_test VALUES (1, 'Glue'), (2, 'SQL')")
+        .unwrap();
+
+    let payloads = glue.execute("SELECT name, id FROM map_test").unwrap();
+    let rows = payloads[0].select().unwrap().collect::<Vec<_>>();
+
+    assert_eq!(rows.len(), 2);
+    assert_eq!(rows[0].get(&"id"), Some(&&Value::I64(1)));
+    assert_eq!(rows[1].get(&"name"), Some(&&Value::Str("SQL".to_owned())));
+    assert_eq!(rows[0].get(&"missing"), None);
+    assert_eq!(rows[0].keys().collect::<Vec<_>>(), [&"name", &"id"]);
+
+    assert!(Payload::Create.select().is_none());
+}
+
+fn parameters<T: GStore + GStoreMut>(mut glue: Glue<T>) {
+    glue.execute("CREATE TABLE parameter_test (id INTEGER, name TEXT)")
+        .unwrap();
+
+    let sql = "SELECT name FROM parameter_test WHERE id = $1";
+    assert_eq!(
+        glue.check_parameters(sql, vec![Value::I64(2)]),
+        Ok(vec![Value::I64(2)])
+    );
+    assert_eq!(
+        glue.check_parameters(sql, vec![Value::I8(1)]),
+        Ok(vec![Value::I64(1)])
+    );
+    assert_eq!(
+        glue.check_parameters(sql, vec![Value::Null]),
+        Ok(vec![Value::Null])
+    );
+
+    // text given where an integer is expected fails before anything runs
+    assert_eq!(
+        glue.check_parameters(sql, vec![Value::Str("2".to_owned())]),
+        Err(ParameterError::TypeMismatch {
+            index: 1,
+            expected: DataType::Int,
+            found: Value::Str("2".to_owned()),
+        }
+        .into())
+    );
+    assert_eq!(
+        glue.check_parameters(sql, Vec::new()),
+        Err(ParameterError::CountMismatch {
+            expected: 1,
+            found: 0,
+        }
+        .into())
+    );
+
+    assert_eq!(
+        glue.check_parameters(
+            "INSERT INTO parameter_test (name, id) VALUES ($1, $2)",
+            vec![
+                Value::Str("Rust".to_owned()),
+                Value::Str("three".to_owned())
+            ]
+        ),
+        Err(ParameterError::TypeMismatch {
+            index: 2,
+            expected: DataType::Int,
+            found: Value::Str("three".to_owned()),
+        }
+        .into())
+    );
+    assert_eq!(
+        glue.check_parameters(
+            "UPDATE parameter_test SET name = $2 WHERE id = $1",
+            vec![Value::I32(3), Value::Str("Rust".to_owned())]
+        ),
+        Ok(vec![Value::I64(3), Value::Str("Rust".to_owned())])
+    );
+
+    assert_eq!(glue.execute(sql), Err(ParameterError::NotBound(1).into()));
+    assert_eq!(
+        glue.check_parameters("SELECT 1; SELECT 2", Vec::new()),
+        Err(ParameterError::SingleStatementRequired(2).into())
+    );
+}
+
+/// Runs each fixture as a `<storage>::<fixture>` test on a fresh `Glue` made by `$glue`,
+/// the `async` ones to completion on the current thread.
+macro_rules! glue_tests {
+    ($storage: ident, $glue: path; $( $fixture: ident ),+ ; async $( $async_fixture: ident ),*) => {
+        mod $storage {
+            use super::*;
+
+            $(
+                #[test]
+                fn $fixture() {
+                    super::$fixture($glue(stringify!($fixture)));
+                }
+            )+
+
+            $(
+                #[test]
+                fn $async_fixture() {
+                    futures::executor::block_on(super::$async_fixture($glue(stringify!(
+                        $async_fixture
+                    ))));
+                }
+            )*
+        }
+    };
+}
+
+#[cfg(feature = "memory-storage")]
+fn memory_glue(_name: &str) -> Glue<memory_storage::MemoryStorage> {
+    Glue::new(memory_storage::MemoryStorage::default())
+}
+
 #[cfg(feature = "sled-storage")]
-#[test]
-fn sled_basic() {
+fn sled_glue(name: &str) -> Glue<sled_storage::SledStorage> {
     use sled_storage::{sled, SledStorage};
 
     let config = sled::Config::default()
-        .path("data/using_config")
+        .path(format!("data/glue_{name}"))
         .temporary(true);
 
-    let storage = SledStorage::try_from(config).unwrap();
-    let glue = Glue::new(storage);
+    Glue::new(SledStorage::try_from(config).unwrap())
+}
+
+#[cfg(feature = "memory-storage")]
+glue_tests!(
+    memory, memory_glue;
+    basic,
+    require_where,
+    affected,
+    execute_all,
+    query_as,
+    cancellation,
+    metrics,
+    select_map,
+    parameters,
+    import_csv,
+    export_csv,
+    jsonl,
+    bulk_insert,
+    fold_identifiers,
+    fold_identifier_names;
+    async basic_async, select_stream
+);
+
+#[cfg(feature = "sled-storage")]
+glue_tests!(
+    sled, sled_glue;
+    basic,
+    metrics,
+    parameters,
+    fold_identifier_names;
+    async
+);
+
+#[cfg(feature = "memory-storage")]
+#[test]
+fn memory_schema_cache() {
+    use {gluesql_core::store::SchemaCache, memory_storage::MemoryStorage};
+
+    let mut glue = Glue::new(MemoryStorage::default());
+    glue.execute("CREATE TABLE cache_test (id INTEGER)")
+        .unwrap();
+    glue.execute("INSERT INTO cache_test VALUES (1)").unwrap();
+
+    let storage = SchemaCache::new(glue.storage.take().unwrap());
+    let mut glue = Glue::new(storage);
+    let fetch_count = |glue: &Glue<SchemaCache<MemoryStorage>>| {
+        glue.storage.as_ref().map(SchemaCache::fetch_count)
+    };
+
+    for _ in 0..2 {
+        assert_eq!(
+            glue.execute("SELECT * FROM cache_test"),
+            Ok(vec![Payload::Select {
+                labels: vec![String::from("id")],
+                rows: vec![vec![Value::I64(1)]],
+            }])
+        );
+    }
+    assert_eq!(fetch_count(&glue), Some(1));
+
+    glue.execute("ALTER TABLE cache_test ADD COLUMN flag BOOLEAN DEFAULT TRUE")
+        .unwrap();
+    assert_eq!(fetch_count(&glue), Some(1));
+    assert_eq!(
+        glue.execute("SELECT * FROM cache_test"),
+        Ok(vec![Payload::Select {
+            labels: vec![String::from("id"), String::from("flag")],
+            rows: vec![vec![Value::I64(1), Value::Bool(true)]],
+        }])
+    );
+    assert_eq!(fetch_count(&glue), Some(2));
 
-    basic(glue);
+    glue.execute("DROP TABLE cache_test").unwrap();
+    assert!(glue.execute("SELECT * FROM cache_test").is_err());
+    assert_eq!(fetch_count(&glue), Some(3));
 }
 
 #[cfg(feature = "memory-storage")]
 #[test]
-fn memory_basic() {
+fn memory_random_seed() {
     use memory_storage::MemoryStorage;
 
-    let storage = MemoryStorage::default();
-    let glue = Glue::new(storage);
+    fn seeded() -> Glue<MemoryStorage> {
+        let mut glue = Glue::new(MemoryStorage::default());
+        glue.execute("CREATE TABLE Dice (id INTEGER)").unwrap();
+        glue.execute("INSERT INTO Dice VALUES (1), (2), (3), (4)")
+            .unwrap();
+        glue.session.set_random_seed(Some(7));
+
+        glue
+    }
+
+    let sql = "SELECT RANDOM() AS ratio FROM Dice";
+    let mut first = seeded();
+    let mut second = seeded();
+
+    // each Glue draws from its own generator, however their statements interleave
+    let values = first.execute(sql).unwrap();
+    first.execute(sql).unwrap();
+    assert_eq!(second.execute(sql).unwrap(), values);
 
-    basic(glue);
+    let mut third = seeded();
+    let found = std::thread::spawn(move || third.execute(sql).unwrap())
+        .join()
+        .unwrap();
+    assert_eq!(found, values);
 }
 
 #[cfg(feature = "memory-storage")]
 #[test]
-fn memory_basic_async() {
-    use futures::executor::block_on;
-    use memory_storage::MemoryStorage;
+fn memory_register_function() {
+    use {
+        gluesql_core::{ast::DataType, translate::TranslateError},
+        memory_storage::MemoryStorage,
+    };
+
+    let new_glue = || {
+        let mut glue = Glue::new(MemoryStorage::default());
+        glue.execute("CREATE TABLE Item (id INTEGER)").unwrap();
+        glue.execute("INSERT INTO Item VALUES (1), (2)").unwrap();
+
+        glue
+    };
+
+    let mut glue = new_glue();
+    glue.register_function(
+        "triple",
+        vec![DataType::Int],
+        DataType::Int,
+        |args| match args.as_slice() {
+            [Value::I64(v)] => Ok(Value::I64(v * 3)),
+            _ => Ok(Value::Null),
+        },
+    );
 
-    let storage = MemoryStorage::default();
-    let glue = Glue::new(storage);
+    let sql = "SELECT TRIPLE(id) FROM Item";
+    assert_eq!(
+        select(&mut glue, sql),
+        vec![vec![Value::I64(3)], vec![Value::I64(6)]]
+    );
 
-    block_on(basic_async(glue));
+    // another Glue does not see the function, while the Glue keeps it on another thread
+    assert_eq!(
+        new_glue().execute(sql),
+        Err(TranslateError::UnsupportedFunction("TRIPLE".to_owned()).into())
+    );
+    let mut glue = std::thread::spawn(move || {
+        assert_eq!(
+            select(&mut glue, sql),
+            vec![vec![Value::I64(3)], vec![Value::I64(6)]]
+        );
+
+        glue
+    })
+    .join()
+    .unwrap();
+
+    glue.unregister_function("triple");
+    assert_eq!(
+        glue.execute(sql),
+        Err(TranslateError::UnsupportedFunction("TRIPLE".to_owned()).into())
+    );
 }
@@ -0,0 +1,138 @@
+#![cfg(all(feature = "tracing", feature = "memory-storage"))]
+use {
+    gluesql_core::prelude::Glue,
+    memory_storage::MemoryStorage,
+    std::{
+        fmt::Debug,
+        sync::{Arc, Mutex},
+    },
+    tracing::{
+        field::{Field, Visit},
+        span::{Attributes, Id, Record},
+        Event, Metadata, Subscriber,
+    },
+};
+
+/// Collects the name of every span in creation order, with the `rows` recorded into it,
+/// and the span each span was entered in.
+#[derive(Clone, Default)]
+struct Recorder(Arc<Mutex<Spans>>);
+
+#[derive(Default)]
+struct Spans {
+    spans: Vec<(&'static str, Option<u64>)>,
+    entered: Vec<u64>,
+    nesting: Vec<(&'static str, &'static str)>,
+}
+
+struct Rows(Option<u64>);
+
+impl Visit for Rows {
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        if field.name() == "rows" {
+            self.0 = Some(value);
+        }
+    }
+
+    fn record_debug(&mut self, _: &Field, _: &dyn Debug) {}
+}
+
+impl Subscriber for Recorder {
+    fn enabled(&self, _: &Metadata<'_>) -> bool {
+        true
+    }
+
+    fn new_span(&self, span: &Attributes<'_>) -> Id {
+        let Spans { spans, .. } = &mut *self.0.lock().unwrap();
+        spans.push((span.metadata().name(), None));
+
+        Id::from_u64(spans.len() as u64)
+    }
+
+    fn record(&self, span: &Id, values: &Record<'_>) {
+        let mut rows = Rows(None);
+        values.record(&mut rows);
+
+        if let Some(rows) = rows.0 {
+            self.0.lock().unwrap().spans[span.into_u64() as usize - 1].1 = Some(rows);
+        }
+    }
+
+    fn record_follows_from(&self, _: &Id, _: &Id) {}
+
+    fn event(&self, _: &Event<'_>) {}
+
+    fn enter(&self, span: &Id) {
+        let Spans {
+            spans,
+            entered,
+            nesting,
+        } = &mut *self.0.lock().unwrap();
+        let name = |id: u64| spans[id as usize - 1].0;
+
+        if let Some(parent) = entered.last() {
+            let pair = (name(span.into_u64()), name(*parent));
+
+            if !nesting.contains(&pair) {
+                nesting.push(pair);
+            }
+        }
+
+        entered.push(span.into_u64());
+    }
+
+    fn exit(&self, _: &Id) {
+        self.0.lock().unwrap().entered.pop();
+    }
+}
+
+#[test]
+fn operator_spans() {
+    let mut glue = Glue::new(MemoryStorage::default());
+    glue.execute(
+        "
+        CREATE TABLE Player (id INTEGER, name TEXT);
+        CREATE TABLE Item (id INTEGER, player_id INTEGER);
+        INSERT INTO Player VALUES (1, 'Taehoon'), (2, 'Mike'), (3, 'Jorno');
+        INSERT INTO Item VALUES (1, 1), (2, 1), (3, 2), (4, 5);
+        ",
+    )
+    .unwrap();
+
+    let recorder = Recorder::default();
+    tracing::subscriber::with_default(recorder.clone(), || {
+        glue.execute(
+            "
+            SELECT Player.name, Item.id
+            FROM Player
+            JOIN Item ON Player.id = Item.player_id
+            WHERE Item.id > 1
+            ORDER BY Item.id
+            ",
+        )
+        .unwrap();
+    });
+
+    let Spans { spans, nesting, .. } = &*recorder.0.lock().unwrap();
+    assert_eq!(
+        spans.as_slice(),
+        [
+            ("scan", Some(3)),
+            ("join", Some(3)),
+            ("filter", Some(2)),
+            ("aggregate", Some(2)),
+            ("sort", Some(2)),
+        ]
+    );
+
+    // each operator pulls the rows of the one below it inside its own span
+    assert_eq!(
+        nesting.as_slice(),
+        [
+            ("aggregate", "sort"),
+            ("filter", "aggregate"),
+            ("join", "filter"),
+            ("scan", "join"),
+        ]
+    );
+}
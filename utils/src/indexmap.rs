@@ -49,3 +49,9 @@ impl<K: Hash + Eq, V> IntoIterator for IndexMap<K, V> {
         self.0.into_iter()
     }
 }
+
+impl<K: Hash + Eq, V> FromIterator<(K, V)> for IndexMap<K, V> {
+    fn from_iter<T: IntoIterator<Item = (K, V)>>(iter: T) -> Self {
+        Self(indexmap::IndexMap::from_iter(iter))
+    }
+}